@@ -304,10 +304,9 @@ fn main() {
         .vertex_shader(vs.entry_point("main").unwrap(), ())
         .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
         .fragment_shader(fs.entry_point("main").unwrap(), ())
-        .multisample_state(MultisampleState {
-            rasterization_samples: subpass.num_samples().unwrap(),
-            ..Default::default()
-        })
+        .multisample_state(
+            MultisampleState::new().rasterization_samples(subpass.num_samples().unwrap()),
+        )
         .render_pass(subpass)
         .build(device.clone())
         .unwrap();
@@ -0,0 +1,416 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{codegen::Shader, structs};
+use ahash::{HashMap, HashSet};
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{Span, TokenStream};
+use std::collections::{btree_map::Entry, BTreeMap};
+use syn::Ident;
+use vulkano::{
+    descriptor_set::layout::DescriptorType,
+    shader::{
+        reflect,
+        spirv::{Decoration, Id, Instruction, StorageClass},
+        DescriptorBindingRequirements,
+    },
+};
+
+/// Writes a module for every descriptor set used by the shader, containing the binding numbers
+/// and a typed builder for the descriptor writes of the set, as well as a typed helper for
+/// pushing the shader's push constants.
+pub(super) fn write_bindings(shader: &Shader) -> TokenStream {
+    let mut sets: BTreeMap<u32, BTreeMap<u32, DescriptorBindingRequirements>> = BTreeMap::new();
+
+    for (_, _, info) in reflect::entry_points(&shader.spirv) {
+        for ((set_num, binding_num), reqs) in info.descriptor_binding_requirements {
+            match sets.entry(set_num).or_default().entry(binding_num) {
+                Entry::Vacant(entry) => {
+                    entry.insert(reqs);
+                }
+                Entry::Occupied(mut entry) => {
+                    // Incompatible requirements between entry points are reported when creating
+                    // the pipeline layout. The first entry point wins here.
+                    let _ = entry.get_mut().merge(&reqs);
+                }
+            }
+        }
+    }
+
+    let variables = descriptor_variables(shader);
+    let mut output = TokenStream::new();
+
+    for (set_num, bindings) in sets {
+        let module_ident = if shader.name.is_empty() {
+            format_ident!("set_{}", set_num)
+        } else {
+            format_ident!("{}_set_{}", shader.name.to_snake_case(), set_num)
+        };
+        let doc = format!("Bindings of descriptor set {set_num}.");
+        let writes_doc = format!("Typed builder for the descriptor writes of set {set_num}.");
+
+        // `set` is taken by the `SET` constant, the others by the methods of `Writes`.
+        let mut used_names: HashSet<String> = ["set", "new", "default", "into_iter"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect();
+        let mut constants = TokenStream::new();
+        let mut methods = TokenStream::new();
+
+        // Names from the shader are assigned first, so that the fallback names of the remaining
+        // bindings can't collide with them.
+        let bindings: Vec<_> = bindings
+            .into_iter()
+            .map(|(binding_num, reqs)| {
+                let variable_id = variables.get(&(set_num, binding_num)).copied();
+                let name = binding_name(shader, variable_id)
+                    .filter(|name| used_names.insert(name.clone()));
+
+                (binding_num, reqs, variable_id, name)
+            })
+            .collect();
+
+        for (binding_num, reqs, variable_id, name) in bindings {
+            let name = name.unwrap_or_else(|| {
+                let mut name = format!("binding_{binding_num}");
+                let mut suffix = 1;
+
+                while used_names.contains(&name) {
+                    name = format!("binding_{binding_num}_{suffix}");
+                    suffix += 1;
+                }
+
+                used_names.insert(name.clone());
+                name
+            });
+
+            let const_ident = Ident::new(&name.to_shouty_snake_case(), Span::call_site());
+            let const_doc = format!("Binding number of `{name}`.");
+
+            constants.extend(quote! {
+                #[doc = #const_doc]
+                pub const #const_ident: u32 = #binding_num;
+            });
+
+            if let Some(method) = write_method(shader, variable_id, &name, &const_ident, &reqs) {
+                methods.extend(method);
+            }
+        }
+
+        output.extend(quote! {
+            #[doc = #doc]
+            #[allow(dead_code)]
+            pub mod #module_ident {
+                /// The descriptor set number.
+                pub const SET: u32 = #set_num;
+
+                #constants
+
+                #[doc = #writes_doc]
+                #[derive(Default)]
+                pub struct Writes {
+                    writes: ::std::vec::Vec<::vulkano::descriptor_set::WriteDescriptorSet>,
+                }
+
+                impl Writes {
+                    /// Returns an empty builder.
+                    #[inline]
+                    pub fn new() -> Self {
+                        Self::default()
+                    }
+
+                    #methods
+                }
+
+                impl ::std::iter::IntoIterator for Writes {
+                    type Item = ::vulkano::descriptor_set::WriteDescriptorSet;
+                    type IntoIter =
+                        ::std::vec::IntoIter<::vulkano::descriptor_set::WriteDescriptorSet>;
+
+                    #[inline]
+                    fn into_iter(self) -> Self::IntoIter {
+                        self.writes.into_iter()
+                    }
+                }
+            }
+        });
+    }
+
+    output.extend(write_push_constants(shader));
+
+    output
+}
+
+/// Returns the global variables decorated with a descriptor set and binding number.
+fn descriptor_variables(shader: &Shader) -> HashMap<(u32, u32), Id> {
+    let mut variables = HashMap::default();
+
+    for instruction in shader.spirv.iter_global() {
+        let result_id = match *instruction {
+            Instruction::Variable { result_id, .. } => result_id,
+            _ => continue,
+        };
+
+        let id_info = shader.spirv.id(result_id);
+        let set_num = id_info
+            .iter_decoration()
+            .find_map(|instruction| match *instruction {
+                Instruction::Decorate {
+                    decoration: Decoration::DescriptorSet { descriptor_set },
+                    ..
+                } => Some(descriptor_set),
+                _ => None,
+            });
+        let binding_num = id_info
+            .iter_decoration()
+            .find_map(|instruction| match *instruction {
+                Instruction::Decorate {
+                    decoration: Decoration::Binding { binding_point },
+                    ..
+                } => Some(binding_point),
+                _ => None,
+            });
+
+        if let (Some(set_num), Some(binding_num)) = (set_num, binding_num) {
+            variables.entry((set_num, binding_num)).or_insert(result_id);
+        }
+    }
+
+    variables
+}
+
+/// Returns the snake case name of a descriptor variable. This is the name of the variable, or the
+/// name of its block type if the block is anonymous.
+fn binding_name(shader: &Shader, variable_id: Option<Id>) -> Option<String> {
+    let variable_id = variable_id?;
+
+    [Some(variable_id), variable_struct(shader, variable_id)]
+        .into_iter()
+        .flatten()
+        .filter_map(|id| name_of(shader, id))
+        .map(|name| name.to_snake_case())
+        .find(|name| syn::parse_str::<Ident>(name).is_ok())
+}
+
+fn name_of(shader: &Shader, id: Id) -> Option<&str> {
+    shader
+        .spirv
+        .id(id)
+        .iter_name()
+        .find_map(|instruction| match instruction {
+            Instruction::Name { name, .. } if !name.is_empty() => Some(name.as_str()),
+            _ => None,
+        })
+}
+
+/// Returns the struct that a variable points to, looking through any arrays.
+fn variable_struct(shader: &Shader, variable_id: Id) -> Option<Id> {
+    let mut id = match *shader.spirv.id(variable_id).instruction() {
+        Instruction::Variable { result_type_id, .. } => result_type_id,
+        _ => return None,
+    };
+
+    loop {
+        match *shader.spirv.id(id).instruction() {
+            Instruction::TypePointer { ty, .. }
+            | Instruction::TypeArray {
+                element_type: ty, ..
+            }
+            | Instruction::TypeRuntimeArray {
+                element_type: ty, ..
+            } => id = ty,
+            Instruction::TypeStruct { result_id, .. } => return Some(result_id),
+            _ => return None,
+        }
+    }
+}
+
+/// Returns the generated Rust struct for the struct that a variable points to, if any.
+fn variable_struct_ident(shader: &Shader, variable_id: Option<Id>) -> Option<Ident> {
+    let struct_id = variable_struct(shader, variable_id?)?;
+
+    if !structs::has_defined_layout(shader, struct_id) {
+        return None;
+    }
+
+    name_of(shader, struct_id).map(|name| Ident::new(name, Span::call_site()))
+}
+
+fn write_method(
+    shader: &Shader,
+    variable_id: Option<Id>,
+    name: &str,
+    binding: &Ident,
+    reqs: &DescriptorBindingRequirements,
+) -> Option<TokenStream> {
+    let method_ident = Ident::new(name, Span::call_site());
+    let descriptor_type = *reqs.descriptor_types.first()?;
+    let is_array = reqs.descriptor_count != Some(1);
+
+    let (element_ty, constructor, constructor_array) = match descriptor_type {
+        DescriptorType::UniformBuffer
+        | DescriptorType::StorageBuffer
+        | DescriptorType::UniformBufferDynamic
+        | DescriptorType::StorageBufferDynamic => {
+            let contents = match variable_struct_ident(shader, variable_id) {
+                Some(ident) => quote! { super::#ident },
+                None if is_array => return None,
+                None => quote! { impl ?::std::marker::Sized },
+            };
+
+            (
+                quote! { ::vulkano::buffer::Subbuffer<#contents> },
+                quote! { buffer },
+                quote! { buffer_array },
+            )
+        }
+        DescriptorType::UniformTexelBuffer | DescriptorType::StorageTexelBuffer => (
+            quote! { ::std::sync::Arc<::vulkano::buffer::view::BufferView> },
+            quote! { buffer_view },
+            quote! { buffer_view_array },
+        ),
+        DescriptorType::SampledImage
+        | DescriptorType::StorageImage
+        | DescriptorType::InputAttachment => (
+            quote! { ::std::sync::Arc<dyn ::vulkano::image::view::ImageViewAbstract> },
+            quote! { image_view },
+            quote! { image_view_array },
+        ),
+        DescriptorType::CombinedImageSampler => (
+            quote! {
+                (
+                    ::std::sync::Arc<dyn ::vulkano::image::view::ImageViewAbstract>,
+                    ::std::sync::Arc<::vulkano::sampler::Sampler>,
+                )
+            },
+            quote! { image_view_sampler },
+            quote! { image_view_sampler_array },
+        ),
+        DescriptorType::Sampler => (
+            quote! { ::std::sync::Arc<::vulkano::sampler::Sampler> },
+            quote! { sampler },
+            quote! { sampler_array },
+        ),
+        _ => return None,
+    };
+
+    let method = if is_array {
+        let doc = format!("Writes consecutive array elements of `{name}`.");
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub fn #method_ident(
+                mut self,
+                first_array_element: u32,
+                elements: impl ::std::iter::IntoIterator<Item = #element_ty>,
+            ) -> Self {
+                self.writes.push(
+                    ::vulkano::descriptor_set::WriteDescriptorSet::#constructor_array(
+                        #binding,
+                        first_array_element,
+                        elements,
+                    ),
+                );
+                self
+            }
+        }
+    } else if descriptor_type == DescriptorType::CombinedImageSampler {
+        let doc = format!("Writes `{name}`.");
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub fn #method_ident(
+                mut self,
+                image_view: ::std::sync::Arc<dyn ::vulkano::image::view::ImageViewAbstract>,
+                sampler: ::std::sync::Arc<::vulkano::sampler::Sampler>,
+            ) -> Self {
+                self.writes.push(
+                    ::vulkano::descriptor_set::WriteDescriptorSet::#constructor(
+                        #binding,
+                        image_view,
+                        sampler,
+                    ),
+                );
+                self
+            }
+        }
+    } else {
+        let doc = format!("Writes `{name}`.");
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub fn #method_ident(mut self, element: #element_ty) -> Self {
+                self.writes.push(
+                    ::vulkano::descriptor_set::WriteDescriptorSet::#constructor(#binding, element),
+                );
+                self
+            }
+        }
+    };
+
+    Some(method)
+}
+
+/// Writes a type alias for the push constant block of the shader, and a function that records
+/// the push constants into a command buffer.
+fn write_push_constants(shader: &Shader) -> TokenStream {
+    let struct_id = shader
+        .spirv
+        .iter_global()
+        .find_map(|instruction| match *instruction {
+            Instruction::TypePointer {
+                ty,
+                storage_class: StorageClass::PushConstant,
+                ..
+            } => Some(ty),
+            _ => None,
+        });
+    let struct_ident = match struct_id
+        .filter(|&struct_id| structs::has_defined_layout(shader, struct_id))
+        .and_then(|struct_id| name_of(shader, struct_id))
+    {
+        Some(name) => Ident::new(name, Span::call_site()),
+        None => return TokenStream::new(),
+    };
+
+    let (alias_ident, fn_ident) = if shader.name.is_empty() {
+        (
+            format_ident!("PushConstants"),
+            format_ident!("push_constants"),
+        )
+    } else {
+        (
+            format_ident!("{}PushConstants", shader.name.to_upper_camel_case()),
+            format_ident!("push_constants_{}", shader.name.to_snake_case()),
+        )
+    };
+
+    quote! {
+        /// The push constant block of the shader.
+        #[allow(dead_code)]
+        pub type #alias_ident = #struct_ident;
+
+        /// Records a command that sets the push constants of the shader, starting at offset 0.
+        #[allow(dead_code)]
+        #[inline]
+        pub fn #fn_ident<L, A>(
+            builder: &mut ::vulkano::command_buffer::AutoCommandBufferBuilder<L, A>,
+            pipeline_layout: ::std::sync::Arc<::vulkano::pipeline::PipelineLayout>,
+            push_constants: #alias_ident,
+        ) -> &mut ::vulkano::command_buffer::AutoCommandBufferBuilder<L, A>
+        where
+            A: ::vulkano::command_buffer::allocator::CommandBufferAllocator,
+        {
+            builder.push_constants(pipeline_layout, 0, push_constants)
+        }
+    }
+}
@@ -8,7 +8,7 @@
 // according to those terms.
 
 use crate::{
-    entry_point,
+    bindings, entry_point,
     structs::{self, TypeRegistry},
     MacroInput,
 };
@@ -270,6 +270,8 @@ pub(super) fn reflect(
         format_ident!("load_{}", shader.name.to_snake_case())
     };
 
+    let bindings = bindings::write_bindings(&shader);
+
     let shader_code = quote! {
         /// Loads the shader as a `ShaderModule`.
         #[allow(unsafe_code)]
@@ -297,6 +299,8 @@ pub(super) fn reflect(
         }
 
         #specialization_constants
+
+        #bindings
     };
 
     let structs = structs::write_structs(input, &shader, type_registry)?;
@@ -673,4 +677,113 @@ mod tests {
         }
         panic!("could not find entrypoint");
     }
+
+    #[test]
+    fn typed_bindings() {
+        let (comp, _) = compile(
+            &MacroInput::empty(),
+            None,
+            Path::new(""),
+            r#"
+                #version 450
+
+                layout(set = 0, binding = 0) uniform Uniforms {
+                    mat4 transform;
+                } uniforms;
+
+                layout(set = 0, binding = 1) uniform sampler2D textures[4];
+
+                layout(set = 1, binding = 0) buffer Output {
+                    vec4 data[];
+                };
+
+                layout(push_constant) uniform Constants {
+                    float scale;
+                } constants;
+
+                void main() {
+                    data[0] = uniforms.transform * texture(textures[1], vec2(constants.scale));
+                }
+            "#,
+            ShaderKind::Fragment,
+        )
+        .unwrap();
+        let (shader_code, _) = reflect(
+            &MacroInput::empty(),
+            LitStr::new("", proc_macro2::Span::call_site()),
+            String::new(),
+            comp.as_binary(),
+            Vec::new(),
+            &mut TypeRegistry::default(),
+        )
+        .unwrap();
+        let shader_code: String = shader_code
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        for expected in [
+            "pubmodset_0",
+            "pubconstUNIFORMS:u32=0u32",
+            "pubconstTEXTURES:u32=1u32",
+            "pubfnuniforms(mutself,element:::vulkano::buffer::Subbuffer<super::Uniforms>)",
+            "pubfntextures(mutself,first_array_element:u32",
+            "pubmodset_1",
+            "pubconstOUTPUT:u32=0u32",
+            "pubfnoutput(mutself,element:::vulkano::buffer::Subbuffer<super::Output>)",
+            "pubtypePushConstants=Constants",
+        ] {
+            assert!(shader_code.contains(expected), "missing `{expected}`");
+        }
+    }
+
+    #[test]
+    fn typed_bindings_fallback_names() {
+        let (comp, _) = compile(
+            &MacroInput::empty(),
+            None,
+            Path::new(""),
+            r#"
+                #version 450
+
+                layout(set = 0, binding = 0) buffer Data {
+                    vec4 values[];
+                };
+
+                layout(set = 0, binding = 1) uniform sampler2D binding_2;
+                layout(set = 0, binding = 2) uniform sampler2D data;
+
+                void main() {
+                    values[0] = texture(binding_2, vec2(0.0)) + texture(data, vec2(0.0));
+                }
+            "#,
+            ShaderKind::Fragment,
+        )
+        .unwrap();
+        let (shader_code, _) = reflect(
+            &MacroInput::empty(),
+            LitStr::new("", proc_macro2::Span::call_site()),
+            String::new(),
+            comp.as_binary(),
+            Vec::new(),
+            &mut TypeRegistry::default(),
+        )
+        .unwrap();
+        let shader_code: String = shader_code
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        // `data` is taken by the block at binding 0, and `binding_2` by the sampler at binding 1.
+        for expected in [
+            "pubconstDATA:u32=0u32",
+            "pubconstBINDING_2:u32=1u32",
+            "pubconstBINDING_2_1:u32=2u32",
+            "pubfnbinding_2_1(",
+        ] {
+            assert!(shader_code.contains(expected), "missing `{expected}`");
+        }
+    }
 }
@@ -15,7 +15,7 @@ use vulkano::{
         spirv::ExecutionModel, DescriptorBindingRequirements, DescriptorIdentifier,
         DescriptorRequirements, EntryPointInfo, ShaderExecution, ShaderInterface,
         ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStages,
-        SpecializationConstantRequirements,
+        SpecializationConstantRequirements, TransformFeedbackOutput,
     },
 };
 
@@ -34,6 +34,8 @@ pub(super) fn write_entry_point(
         write_specialization_constant_requirements(&info.specialization_constant_requirements);
     let input_interface = write_interface(&info.input_interface);
     let output_interface = write_interface(&info.output_interface);
+    let transform_feedback_outputs =
+        write_transform_feedback_outputs(&info.transform_feedback_outputs);
 
     quote! {
         (
@@ -46,6 +48,7 @@ pub(super) fn write_entry_point(
                 specialization_constant_requirements: #specialization_constant_requirements.into_iter().collect(),
                 input_interface: #input_interface,
                 output_interface: #output_interface,
+                transform_feedback_outputs: #transform_feedback_outputs,
             },
         )
     }
@@ -116,6 +119,7 @@ fn write_descriptor_binding_requirements(
                     image_multisampled,
                     image_scalar_type,
                     image_view_type,
+                    uniform_block_size,
                     stages,
                     descriptors,
                 } = binding_reqs;
@@ -149,6 +153,10 @@ fn write_descriptor_binding_requirements(
                     }
                     None => quote! { None },
                 };
+                let uniform_block_size = match uniform_block_size {
+                    Some(uniform_block_size) => quote! { Some(#uniform_block_size) },
+                    None => quote! { None },
+                };
                 let stages = stages_to_items(*stages);
                 let descriptor_items = descriptors.iter().map(|(index, desc_reqs)| {
                     let DescriptorRequirements {
@@ -209,6 +217,7 @@ fn write_descriptor_binding_requirements(
                             image_multisampled: #image_multisampled,
                             image_scalar_type: #image_scalar_type,
                             image_view_type: #image_view_type,
+                            uniform_block_size: #uniform_block_size,
                             stages: #stages,
                             descriptors: [ #( #descriptor_items ),* ].into_iter().collect(),
                         },
@@ -312,6 +321,55 @@ fn write_interface(interface: &ShaderInterface) -> TokenStream {
     }
 }
 
+fn write_transform_feedback_outputs(
+    transform_feedback_outputs: &Option<Vec<TransformFeedbackOutput>>,
+) -> TokenStream {
+    match transform_feedback_outputs {
+        Some(outputs) => {
+            let items = outputs.iter().map(
+                |TransformFeedbackOutput {
+                     buffer,
+                     stride,
+                     offset,
+                     size,
+                     stream,
+                     name,
+                 }| {
+                    let name = match name {
+                        Some(name) => {
+                            let name = name.as_ref();
+                            quote! {
+                                ::std::option::Option::Some(::std::borrow::Cow::Borrowed(#name))
+                            }
+                        }
+                        None => quote! { ::std::option::Option::None },
+                    };
+
+                    quote! {
+                        ::vulkano::shader::TransformFeedbackOutput {
+                            buffer: #buffer,
+                            stride: #stride,
+                            offset: #offset,
+                            size: #size,
+                            stream: #stream,
+                            name: #name,
+                        }
+                    }
+                },
+            );
+
+            quote! {
+                Some(vec![
+                    #( #items ),*
+                ])
+            }
+        }
+        None => quote! {
+            None
+        },
+    }
+}
+
 fn stages_to_items(stages: ShaderStages) -> TokenStream {
     if stages.is_empty() {
         quote! { ::vulkano::shader::ShaderStages::empty() }
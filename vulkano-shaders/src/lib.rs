@@ -45,6 +45,17 @@
 //! - The `SpecializationConstants` struct. This contains a field for every specialization constant
 //!   found in the shader data. Implementations of [`Default`] and [`SpecializationConstants`] are
 //!   also generated for the struct.
+//! - A `set_N` module for each descriptor set `N` used by the shader. It contains the `SET`
+//!   constant, a constant holding the binding number of each binding, named after the shader
+//!   variable (for example `UNIFORMS`), and a `Writes` builder with a method for each binding that
+//!   produces a [`WriteDescriptorSet`] of the right kind. Buffer bindings whose block has a
+//!   generated struct take a `Subbuffer` of that struct, so that passing a buffer of the wrong type
+//!   is a compile error.
+//! - If the shader has a push constant block, a `PushConstants` alias for its generated struct and
+//!   a `push_constants` function that records it into an `AutoCommandBufferBuilder` at offset 0.
+//! - If the `shaders` option is used, the descriptor set modules and push constant items are
+//!   prefixed with the name of the shader, for example `first_set_0`, `FirstPushConstants` and
+//!   `push_constants_first`.
 //!
 //! All of these generated items will be accessed through the module where the macro was invoked.
 //! If you wanted to store the `ShaderModule` in a struct of your own, you could do something like
@@ -86,6 +97,60 @@
 //! }
 //! ```
 //!
+//! The descriptor set modules can be used to write the descriptors of a set without having to
+//! repeat the binding numbers and descriptor types of the shader:
+//!
+//! ```
+//! # fn main() {}
+//! use std::sync::Arc;
+//! use vulkano::{
+//!     buffer::Subbuffer,
+//!     descriptor_set::WriteDescriptorSet,
+//!     image::view::ImageViewAbstract,
+//!     sampler::Sampler,
+//! };
+//!
+//! mod fs {
+//!     vulkano_shaders::shader!{
+//!         ty: "fragment",
+//!         src: r"
+//!             #version 450
+//!
+//!             layout(set = 0, binding = 0) uniform Material {
+//!                 vec4 color;
+//!             } material;
+//!             layout(set = 0, binding = 1) uniform sampler2D textures[2];
+//!             layout(set = 0, binding = 2) uniform New {
+//!                 vec4 tint;
+//!             };
+//!
+//!             layout(location = 0) out vec4 f_color;
+//!
+//!             void main() {
+//!                 f_color = material.color * tint * texture(textures[1], vec2(0.0));
+//!             }
+//!         ",
+//!     }
+//! }
+//!
+//! fn material_writes(
+//!     material: Subbuffer<fs::Material>,
+//!     tint: Subbuffer<fs::New>,
+//!     textures: [Arc<dyn ImageViewAbstract>; 2],
+//!     sampler: Arc<Sampler>,
+//! ) -> Vec<WriteDescriptorSet> {
+//!     assert_eq!(fs::set_0::TEXTURES, 1);
+//!
+//!     fs::set_0::Writes::new()
+//!         .material(material)
+//!         .textures(0, textures.map(|texture| (texture, sampler.clone())))
+//!         // `new` is taken by the constructor, so the binding number is used instead.
+//!         .binding_2(tint)
+//!         .into_iter()
+//!         .collect()
+//! }
+//! ```
+//!
 //! # Options
 //!
 //! The options available are in the form of the following fields:
@@ -207,6 +272,7 @@
 //! [`set_target_env`]: shaderc::CompileOptions::set_target_env
 //! [`set_target_spirv`]: shaderc::CompileOptions::set_target_spirv
 //! [`BufferContents`]: vulkano::buffer::BufferContents
+//! [`WriteDescriptorSet`]: vulkano::descriptor_set::WriteDescriptorSet
 
 #![doc(html_logo_url = "https://raw.githubusercontent.com/vulkano-rs/vulkano/master/logo.png")]
 #![recursion_limit = "1024"]
@@ -233,6 +299,7 @@ use syn::{
     Error, Ident, LitBool, LitStr, Path as SynPath,
 };
 
+mod bindings;
 mod codegen;
 mod entry_point;
 mod structs;
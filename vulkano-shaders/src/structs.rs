@@ -139,7 +139,7 @@ pub(super) fn write_structs(
     Ok(structs)
 }
 
-fn has_defined_layout(shader: &Shader, struct_id: Id) -> bool {
+pub(super) fn has_defined_layout(shader: &Shader, struct_id: Id) -> bool {
     for member_info in shader.spirv.id(struct_id).iter_members() {
         let mut offset_found = false;
 
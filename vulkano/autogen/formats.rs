@@ -56,16 +56,34 @@ struct FormatMember {
     type_depth: Option<Ident>,
     type_stencil: Option<Ident>,
     ycbcr_chroma_sampling: Option<Ident>,
+    texel_layout: Option<TexelLayoutMember>,
 
     type_std_array: Option<TokenStream>,
     type_cgmath: Option<TokenStream>,
     type_nalgebra: Option<TokenStream>,
 }
 
+#[derive(Clone, Debug)]
+struct TexelLayoutMember {
+    size: u32,
+    components: Vec<TexelComponentMember>,
+}
+
+#[derive(Clone, Debug)]
+struct TexelComponentMember {
+    channel: Ident,
+    offset: u32,
+    bits: u32,
+    numeric_type: Ident,
+}
+
 fn formats_output(members: &[FormatMember]) -> TokenStream {
     let enum_items = members.iter().map(|FormatMember { name, ffi_name, .. }| {
         quote! { #name = ash::vk::Format::#ffi_name.as_raw(), }
     });
+    let all_items = members
+        .iter()
+        .map(|FormatMember { name, .. }| quote! { Self::#name, });
     let aspects_items = members.iter().map(
         |FormatMember {
              name,
@@ -208,6 +226,43 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
                 .map(|ty| quote! { Self::#name => Some(ChromaSampling::#ty), })
         },
     );
+    let texel_layout_items = members.iter().filter_map(
+        |FormatMember {
+             name, texel_layout, ..
+         }| {
+            texel_layout
+                .as_ref()
+                .map(|TexelLayoutMember { size, components }| {
+                    let size = Literal::usize_unsuffixed(*size as usize);
+                    let component_items = components.iter().map(
+                        |TexelComponentMember {
+                             channel,
+                             offset,
+                             bits,
+                             numeric_type,
+                         }| {
+                            let offset = Literal::u16_unsuffixed(*offset as u16);
+                            let bits = Literal::u8_unsuffixed(*bits as u8);
+                            quote! {
+                                TexelComponent {
+                                    channel: TexelChannel::#channel,
+                                    offset: #offset,
+                                    bits: #bits,
+                                    numeric_type: NumericType::#numeric_type,
+                                },
+                            }
+                        },
+                    );
+
+                    quote! {
+                        Self::#name => Some(TexelLayout {
+                            size: #size,
+                            components: &[#(#component_items)*],
+                        }),
+                    }
+                })
+        },
+    );
     let try_from_items = members.iter().map(|FormatMember { name, ffi_name, .. }| {
         quote! { ash::vk::Format::#ffi_name => Ok(Self::#name), }
     });
@@ -363,6 +418,11 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
         }
 
         impl Format {
+            /// Returns all formats, in the order that they are listed in the Vulkan registry.
+            pub fn all() -> &'static [Self] {
+                &[#(#all_items)*]
+            }
+
             /// Returns the aspects that images of this format have.
             pub fn aspects(self) -> ImageAspects {
                 match self {
@@ -497,6 +557,16 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
                 }
             }
 
+            /// Returns the memory layout of a single texel of this format, as used by the CPU-side
+            /// texel codecs. Returns `None` for compressed, YCbCr and combined depth/stencil
+            /// formats.
+            pub(crate) fn texel_layout(self) -> Option<TexelLayout> {
+                match self {
+                    #(#texel_layout_items)*
+                    _ => None,
+                }
+            }
+
             #[allow(dead_code)]
             pub(crate) fn validate_device(
                 self,
@@ -586,6 +656,90 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
     }
 }
 
+/// Derives the bit layout of a texel from the name of the format. The component order in the name
+/// is the order in memory for unpacked formats, and most-significant first within each element
+/// for packed formats.
+fn texel_layout(format: &Format, vulkan_name: &str) -> TexelLayoutMember {
+    static COMPONENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"([RGBADSXE])(\d+)").unwrap());
+    static SEGMENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([RGBADSXE]\d+)+$").unwrap());
+
+    let size = format.blockSize as u32 * 8;
+    let mut tokens = Vec::new();
+    let mut total_bits = 0;
+
+    for segment in vulkan_name.split('_') {
+        if total_bits == size || !SEGMENT_REGEX.is_match(segment) {
+            break;
+        }
+
+        for captures in COMPONENT_REGEX.captures_iter(segment) {
+            let letter = captures.get(1).unwrap().as_str();
+            let bits: u32 = captures.get(2).unwrap().as_str().parse().unwrap();
+            total_bits += bits;
+            tokens.push((letter, bits));
+        }
+    }
+
+    assert_eq!(
+        total_bits, size,
+        "could not parse texel layout of {}",
+        vulkan_name
+    );
+
+    let numeric_type = |letter: &str| -> Ident {
+        if letter == "E" {
+            return format_ident!("UFLOAT");
+        }
+
+        let component_type = |matches_name: &dyn Fn(&str) -> bool| {
+            format.children.iter().find_map(|child| match child {
+                FormatChild::Component {
+                    name,
+                    numericFormat,
+                    ..
+                } if matches_name(name) => Some(format_ident!("{}", numericFormat)),
+                _ => None,
+            })
+        };
+
+        // Some formats list the wrong component names in vk.xml, but all components of a color
+        // format have the same numeric type.
+        component_type(&|name| name == letter)
+            .or_else(|| component_type(&|_| true))
+            .unwrap()
+    };
+
+    let element_bits = format.packed.map_or(0, |bits| bits as u32);
+    let mut components = Vec::new();
+    let mut offset = 0u32;
+
+    for (letter, bits) in tokens {
+        let component_offset = match offset.checked_div(element_bits) {
+            // Packed components are stored from the most significant bit down.
+            Some(element_index) => {
+                let element_start = element_index * element_bits;
+                element_start + element_bits - (offset - element_start) - bits
+            }
+            None => offset,
+        };
+        offset += bits;
+
+        if letter != "X" {
+            components.push(TexelComponentMember {
+                channel: format_ident!("{}", letter),
+                offset: component_offset,
+                bits,
+                numeric_type: numeric_type(letter),
+            });
+        }
+    }
+
+    TexelLayoutMember {
+        size: format.blockSize as u32,
+        components,
+    }
+}
+
 fn formats_members(
     formats: &[&Format],
     features: &IndexMap<&str, &Feature>,
@@ -637,6 +791,7 @@ fn formats_members(
                 type_depth: None,
                 type_stencil: None,
                 ycbcr_chroma_sampling: None,
+                texel_layout: None,
 
                 type_std_array: None,
                 type_cgmath: None,
@@ -802,6 +957,17 @@ fn formats_members(
                 member.ycbcr_chroma_sampling = Some(format_ident!("Mode{}", chroma));
             }
 
+            // Compressed, YCbCr and multi-planar formats are handled by separate codecs, if any.
+            // Combined depth/stencil formats have no CPU-visible representation; their aspects
+            // are always copied to and from buffers separately.
+            if format.compressed.is_none()
+                && format.chroma.is_none()
+                && member.planes.is_empty()
+                && !(member.aspect_depth && member.aspect_stencil)
+            {
+                member.texel_layout = Some(texel_layout(format, vulkan_name));
+            }
+
             debug_assert!(
                 !member.components.iter().all(|x| *x == 0),
                 "format {} has 0 components",
@@ -197,6 +197,7 @@ fn properties_members(types: &HashMap<&str, (&Type, Vec<&str>)>) -> Vec<Properti
                 let vulkano_ty = match name {
                     "apiVersion" => quote! { Version },
                     "bufferImageGranularity"
+                    | "descriptorBufferOffsetAlignment"
                     | "minStorageBufferOffsetAlignment"
                     | "minTexelBufferOffsetAlignment"
                     | "minUniformBufferOffsetAlignment"
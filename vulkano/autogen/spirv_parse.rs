@@ -172,6 +172,98 @@ fn instruction_output(members: &[InstructionMember], spec_constant: bool) -> Tok
         },
     );
 
+    let opcode_items = members.iter().map(
+        |InstructionMember {
+             name,
+             opcode,
+             operands,
+             ..
+         }| {
+            if operands.is_empty() {
+                quote! { Self::#name => #opcode, }
+            } else {
+                quote! { Self::#name { .. } => #opcode, }
+            }
+        },
+    );
+    let write_items = members
+        .iter()
+        .map(|InstructionMember { name, operands, .. }| {
+            if operands.is_empty() {
+                quote! { Self::#name => (), }
+            } else {
+                let operand_names = operands.iter().map(|OperandMember { name, .. }| name);
+                let write_items = operands.iter().map(|OperandMember { name, .. }| {
+                    quote! { #name.write_operand(writer); }
+                });
+
+                quote! {
+                    Self::#name { #(#operand_names),* } => {
+                        #(#write_items)*
+                    }
+                }
+            }
+        });
+    let visit_items = members
+        .iter()
+        .map(|InstructionMember { name, operands, .. }| {
+            // The result id is defined by the instruction, not referenced by it.
+            let operands: Vec<_> = operands
+                .iter()
+                .filter(|OperandMember { name, .. }| name != "result_id")
+                .collect();
+
+            if operands.is_empty() {
+                quote! { Self::#name { .. } => (), }
+            } else {
+                let operand_names = operands.iter().map(|OperandMember { name, .. }| name);
+                let visit_items = operands.iter().map(|OperandMember { name, .. }| {
+                    quote! { #name.visit_ids(f); }
+                });
+
+                quote! {
+                    Self::#name { #(#operand_names),* , .. } => {
+                        #(#visit_items)*
+                    }
+                }
+            }
+        });
+
+    let opname_items = members
+        .iter()
+        .map(|InstructionMember { name, operands, .. }| {
+            let opname = format!("Op{}", name);
+
+            if operands.is_empty() {
+                quote! { Self::#name => #opname, }
+            } else {
+                quote! { Self::#name { .. } => #opname, }
+            }
+        });
+    let fmt_items = members
+        .iter()
+        .map(|InstructionMember { name, operands, .. }| {
+            let operands: Vec<_> = operands
+                .iter()
+                .filter(|OperandMember { name, .. }| name != "result_id")
+                .collect();
+
+            if operands.is_empty() {
+                quote! { Self::#name { .. } => (), }
+            } else {
+                let operand_names = operands.iter().map(|OperandMember { name, .. }| name);
+                let fmt_items = operands.iter().map(|OperandMember { name, .. }| {
+                    quote! { #name.fmt_operand(f); }
+                });
+
+                quote! {
+                    Self::#name { #(#operand_names),* , .. } => {
+                        #(#fmt_items)*
+                    }
+                }
+            }
+        });
+
     let doc = if spec_constant {
         "An instruction that is used as the operand of the `SpecConstantOp` instruction."
     } else {
@@ -236,6 +328,41 @@ fn instruction_output(members: &[InstructionMember], spec_constant: bool) -> Tok
                 })
             }
 
+            /// Returns the opcode of the instruction.
+            pub fn opcode(&self) -> u16 {
+                match self {
+                    #(#opcode_items)*
+                }
+            }
+
+            /// Writes the operands of the instruction, not including the opcode.
+            fn write_operands(&self, writer: &mut InstructionWriter<'_>) {
+                match self {
+                    #(#write_items)*
+                }
+            }
+
+            /// Returns the name of the instruction, as it is written in the SPIR-V specification.
+            pub fn opname(&self) -> &'static str {
+                match self {
+                    #(#opname_items)*
+                }
+            }
+
+            /// Formats the operands of the instruction, not including the result id.
+            fn fmt_operands(&self, f: &mut OperandFormatter<'_>) {
+                match self {
+                    #(#fmt_items)*
+                }
+            }
+
+            /// Calls `f` for every `Id` operand of the instruction, not including the result id.
+            fn visit_operand_ids(&self, f: &mut dyn FnMut(Id)) {
+                match self {
+                    #(#visit_items)*
+                }
+            }
+
             #result_fns
         }
     }
@@ -323,6 +450,7 @@ fn instruction_members(grammar: &SpirvGrammar) -> Vec<InstructionMember> {
 #[derive(Clone, Debug)]
 struct KindEnumMember {
     name: Ident,
+    spirv_name: String,
     value: u32,
     parameters: Vec<OperandMember>,
 }
@@ -381,8 +509,84 @@ fn bit_enum_output(enums: &[(Ident, Vec<KindEnumMember>)]) -> TokenStream {
             },
         );
 
+        let value_items = members.iter().map(
+            |KindEnumMember {
+                 name,
+                 value,
+                 parameters,
+                 ..
+             }| {
+                if parameters.is_empty() {
+                    quote! {
+                        if self.#name {
+                            value |= #value;
+                        }
+                    }
+                } else {
+                    quote! {
+                        if self.#name.is_some() {
+                            value |= #value;
+                        }
+                    }
+                }
+            },
+        );
+        let write_items = members
+            .iter()
+            .filter(|member| !member.parameters.is_empty())
+            .map(|KindEnumMember { name, .. }| {
+                quote! {
+                    if let Some(parameters) = &self.#name {
+                        parameters.write_operand(writer);
+                    }
+                }
+            });
+        let visit_items = members
+            .iter()
+            .filter(|member| !member.parameters.is_empty())
+            .map(|KindEnumMember { name, .. }| {
+                quote! {
+                    if let Some(parameters) = &self.#name {
+                        parameters.visit_ids(f);
+                    }
+                }
+            });
+
+        let flag_name_items = members.iter().map(
+            |KindEnumMember {
+                 name,
+                 spirv_name,
+                 parameters,
+                 ..
+             }| {
+                if parameters.is_empty() {
+                    quote! {
+                        if self.#name {
+                            names.push(#spirv_name);
+                        }
+                    }
+                } else {
+                    quote! {
+                        if self.#name.is_some() {
+                            names.push(#spirv_name);
+                        }
+                    }
+                }
+            },
+        );
+        let fmt_items = members
+            .iter()
+            .filter(|member| !member.parameters.is_empty())
+            .map(|KindEnumMember { name, .. }| {
+                quote! {
+                    if let Some(parameters) = &self.#name {
+                        parameters.fmt_operand(f);
+                    }
+                }
+            });
+
         quote! {
-            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
             #[allow(non_camel_case_types)]
             pub struct #name {
                 #(#members_items)*
@@ -398,6 +602,38 @@ fn bit_enum_output(enums: &[(Ident, Vec<KindEnumMember>)]) -> TokenStream {
                     })
                 }
             }
+
+            impl WriteOperand for #name {
+                #[allow(unused_variables)]
+                fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+                    #[allow(unused_mut)]
+                    let mut value = 0u32;
+                    #(#value_items)*
+                    writer.push_u32(value);
+                    #(#write_items)*
+                }
+
+                #[allow(unused_variables)]
+                fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+                    #(#visit_items)*
+                }
+            }
+
+            impl FormatOperand for #name {
+                fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+                    #[allow(unused_mut)]
+                    let mut names: Vec<&str> = Vec::new();
+                    #(#flag_name_items)*
+
+                    if names.is_empty() {
+                        f.push("None");
+                    } else {
+                        f.push(names.join("|"));
+                    }
+
+                    #(#fmt_items)*
+                }
+            }
         }
     });
 
@@ -458,6 +694,7 @@ fn bit_enum_members(grammar: &SpirvGrammar) -> Vec<(Ident, Vec<KindEnumMember>)>
 
                     Some(KindEnumMember {
                         name,
+                        spirv_name: enumerant.enumerant.clone(),
                         value,
                         parameters,
                     })
@@ -526,6 +763,82 @@ fn value_enum_output(enums: &[(Ident, Vec<KindEnumMember>)]) -> TokenStream {
             _ => quote! { #[derive(Clone, Copy, Debug, PartialEq, Eq)] },
         };
 
+        let write_items = members.iter().map(
+            |KindEnumMember {
+                 name,
+                 value,
+                 parameters,
+                 ..
+             }| {
+                if parameters.is_empty() {
+                    quote! {
+                        Self::#name => writer.push_u32(#value),
+                    }
+                } else {
+                    let params_names = parameters.iter().map(|OperandMember { name, .. }| name);
+                    let params_items = parameters.iter().map(|OperandMember { name, .. }| {
+                        quote! { #name.write_operand(writer); }
+                    });
+
+                    quote! {
+                        Self::#name { #(#params_names),* } => {
+                            writer.push_u32(#value);
+                            #(#params_items)*
+                        }
+                    }
+                }
+            },
+        );
+        let visit_items = members.iter().map(
+            |KindEnumMember {
+                 name, parameters, ..
+             }| {
+                if parameters.is_empty() {
+                    quote! {
+                        Self::#name => (),
+                    }
+                } else {
+                    let params_names = parameters.iter().map(|OperandMember { name, .. }| name);
+                    let params_items = parameters.iter().map(|OperandMember { name, .. }| {
+                        quote! { #name.visit_ids(f); }
+                    });
+
+                    quote! {
+                        Self::#name { #(#params_names),* } => {
+                            #(#params_items)*
+                        }
+                    }
+                }
+            },
+        );
+
+        let fmt_items = members.iter().map(
+            |KindEnumMember {
+                 name,
+                 spirv_name,
+                 parameters,
+                 ..
+             }| {
+                if parameters.is_empty() {
+                    quote! {
+                        Self::#name => f.push(#spirv_name),
+                    }
+                } else {
+                    let params_names = parameters.iter().map(|OperandMember { name, .. }| name);
+                    let params_items = parameters.iter().map(|OperandMember { name, .. }| {
+                        quote! { #name.fmt_operand(f); }
+                    });
+
+                    quote! {
+                        Self::#name { #(#params_names),* } => {
+                            f.push(#spirv_name);
+                            #(#params_items)*
+                        }
+                    }
+                }
+            },
+        );
+
         quote! {
             #derives
             #[allow(non_camel_case_types)]
@@ -542,6 +855,29 @@ fn value_enum_output(enums: &[(Ident, Vec<KindEnumMember>)]) -> TokenStream {
                     })
                 }
             }
+
+            impl WriteOperand for #name {
+                fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+                    match self {
+                        #(#write_items)*
+                    }
+                }
+
+                #[allow(unused_variables)]
+                fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+                    match self {
+                        #(#visit_items)*
+                    }
+                }
+            }
+
+            impl FormatOperand for #name {
+                fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+                    match self {
+                        #(#fmt_items)*
+                    }
+                }
+            }
         }
     });
 
@@ -590,6 +926,7 @@ fn value_enum_members(grammar: &SpirvGrammar) -> Vec<(Ident, Vec<KindEnumMember>
 
                     Some(KindEnumMember {
                         name,
+                        spirv_name: enumerant.enumerant.clone(),
                         value: enumerant.value.as_u64().unwrap() as u32,
                         parameters,
                     })
@@ -69,23 +69,22 @@ vulkan_bitflags! {
         device_extensions: [khr_video_decode_queue],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a transform feedback buffer, to capture the vertex outputs of
+    /// the last vertex processing stage.
     TRANSFORM_FEEDBACK_BUFFER = TRANSFORM_FEEDBACK_BUFFER_EXT {
         device_extensions: [ext_transform_feedback],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can hold the byte counter of a transform feedback buffer, which records how
+    /// much data has been captured.
     TRANSFORM_FEEDBACK_COUNTER_BUFFER = TRANSFORM_FEEDBACK_COUNTER_BUFFER_EXT {
         device_extensions: [ext_transform_feedback],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can hold the predicate of a conditional rendering block.
     CONDITIONAL_RENDERING = CONDITIONAL_RENDERING_EXT {
         device_extensions: [ext_conditional_rendering],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -117,17 +116,17 @@ vulkan_bitflags! {
         device_extensions: [khr_video_encode_queue],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can hold sampler and combined image sampler descriptors, and can be bound as a
+    /// descriptor buffer.
     SAMPLER_DESCRIPTOR_BUFFER = SAMPLER_DESCRIPTOR_BUFFER_EXT {
         device_extensions: [ext_descriptor_buffer],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can hold descriptors of resources other than samplers, and can be bound as a
+    /// descriptor buffer.
     RESOURCE_DESCRIPTOR_BUFFER = RESOURCE_DESCRIPTOR_BUFFER_EXT {
         device_extensions: [ext_descriptor_buffer],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -12,21 +12,28 @@ use super::{
         CommandBufferAlloc, CommandBufferAllocator, CommandBufferBuilderAlloc,
         StandardCommandBufferAlloc, StandardCommandBufferAllocator,
     },
+    commands::mipmaps::DownsamplePipeline,
     synced::{CommandBufferBuilderState, SyncCommandBuffer, SyncCommandBufferBuilder},
-    sys::CommandBufferBeginInfo,
-    CommandBufferExecError, CommandBufferInheritanceInfo, CommandBufferInheritanceRenderPassInfo,
+    sys::{CommandBufferBeginInfo, UnsafeCommandBuffer, UnsafeCommandBufferBuilder},
+    CommandBufferExecError, CommandBufferImageRangeUsage, CommandBufferImageUsage,
+    CommandBufferInheritanceInfo, CommandBufferInheritanceRenderPassInfo,
     CommandBufferInheritanceRenderPassType, CommandBufferLevel, CommandBufferResourcesUsage,
     CommandBufferState, CommandBufferUsage, PrimaryCommandBufferAbstract, RenderingAttachmentInfo,
     SecondaryCommandBufferAbstract, SecondaryCommandBufferResourcesUsage, SubpassContents,
 };
 use crate::{
+    buffer::Subbuffer,
     command_buffer::CommandBufferInheritanceRenderingInfo,
-    device::{Device, DeviceOwned, QueueFamilyProperties},
+    device::{Device, DeviceOwned, QueueFamilyProperties, QueueFlags},
     format::{Format, FormatFeatures},
-    image::ImageAspects,
+    image::{sys::Image, ImageAspects, ImageLayout},
     query::{QueryControlFlags, QueryType},
+    range_map::RangeMap,
     render_pass::{Framebuffer, Subpass},
-    OomError, RequirementNotMet, RequiresOneOf, VulkanObject,
+    sync::{
+        event::EventRecordingLock, AccessFlags, DependencyInfo, ImageMemoryBarrier, PipelineStages,
+    },
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanObject,
 };
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
@@ -34,6 +41,7 @@ use std::{
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
     marker::PhantomData,
+    ops::Range,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -67,6 +75,19 @@ where
     // If any queries are active, this hashmap contains their state.
     pub(super) query_state: HashMap<ash::vk::QueryType, QueryState>,
 
+    // If conditional rendering is active, contains its state.
+    pub(super) conditional_rendering_state: Option<ConditionalRenderingState>,
+
+    // Whether transform feedback is active.
+    pub(super) transform_feedback_active: bool,
+
+    // The state of the events that were set or reset by a command in this command buffer.
+    pub(super) event_state: HashMap<ash::vk::Event, EventState>,
+
+    // The pipelines used to generate mipmaps, which are kept alive so that other command buffers
+    // can reuse them.
+    pub(super) downsample_pipelines: Vec<Arc<DownsamplePipeline>>,
+
     _data: PhantomData<L>,
 }
 
@@ -124,6 +145,32 @@ pub(super) struct QueryState {
     pub(super) in_subpass: bool,
 }
 
+// The state of an event that was set or reset by a command.
+pub(super) struct EventState {
+    // Keeps other command buffers and host futures from changing the event while this command
+    // buffer exists.
+    pub(super) lock: EventRecordingLock,
+    pub(super) status: EventStatus,
+}
+
+pub(super) enum EventStatus {
+    // The event was reset, and not set since.
+    Reset,
+    // The event was set by a single command since it was last reset.
+    Set(DependencyInfo),
+    // The event was set without being reset first in this command buffer, or was set more than
+    // once since being reset, so it's not known which command signaled it.
+    Unknown,
+}
+
+// The state of an active conditional rendering block.
+pub(super) struct ConditionalRenderingState {
+    pub(super) predicate: Subbuffer<u32>,
+    // The index of the subpass that conditional rendering was begun in, if it was begun inside a
+    // render pass.
+    pub(super) subpass_index: Option<u32>,
+}
+
 impl<A> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, A>
 where
     A: CommandBufferAllocator,
@@ -265,7 +312,19 @@ where
             .next()
             .expect("requested one command buffer from the command pool, but got zero");
 
-        let inner = SyncCommandBufferBuilder::new(builder_alloc.inner(), begin_info)?;
+        let mut inner = SyncCommandBufferBuilder::new(builder_alloc.inner(), begin_info)?;
+
+        // Split barriers are recorded with events, which are not available on every queue family
+        // and device. Executions of a simultaneous-use command buffer would share the events, so
+        // that one execution's set could satisfy another execution's wait.
+        let device = allocator.device();
+        let queue_flags = device.physical_device().queue_family_properties()
+            [queue_family_index as usize]
+            .queue_flags;
+        inner.split_barriers = usage != CommandBufferUsage::SimultaneousUse
+            && queue_flags.intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+            && (!device.enabled_extensions().khr_portability_subset
+                || device.enabled_features().events);
 
         Ok(AutoCommandBufferBuilder {
             inner,
@@ -273,6 +332,10 @@ where
             queue_family_index,
             render_pass_state,
             query_state: HashMap::default(),
+            conditional_rendering_state: None,
+            transform_feedback_active: false,
+            event_state: HashMap::default(),
+            downsample_pipelines: Vec::new(),
             inheritance_info,
             usage,
             _data: PhantomData,
@@ -619,10 +682,16 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        if self.conditional_rendering_state.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
         Ok(PrimaryAutoCommandBuffer {
             inner: self.inner.build()?,
             _alloc: self.builder_alloc.into_alloc(),
             usage: self.usage,
+            _event_locks: self.event_state.into_values().map(|s| s.lock).collect(),
+            _downsample_pipelines: self.downsample_pipelines,
 
             state: Mutex::new(Default::default()),
         })
@@ -639,6 +708,14 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        if self.conditional_rendering_state.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
+        if self.transform_feedback_active {
+            return Err(BuildError::TransformFeedbackActive);
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -655,6 +732,8 @@ where
             usage: self.usage,
             inheritance_info: self.inheritance_info.unwrap(),
             submit_state,
+            _event_locks: self.event_state.into_values().map(|s| s.lock).collect(),
+            _downsample_pipelines: self.downsample_pipelines,
         })
     }
 }
@@ -669,6 +748,12 @@ pub enum BuildError {
 
     /// A query is still active on the command buffer.
     QueryActive,
+
+    /// Conditional rendering is still active on the command buffer.
+    ConditionalRenderingActive,
+
+    /// Transform feedback is still active on the command buffer.
+    TransformFeedbackActive,
 }
 
 impl Error for BuildError {
@@ -688,6 +773,16 @@ impl Display for BuildError {
                 write!(f, "a render pass is still active on the command buffer")
             }
             Self::QueryActive => write!(f, "a query is still active on the command buffer"),
+            Self::ConditionalRenderingActive => write!(
+                f,
+                "conditional rendering is still active on the command buffer",
+            ),
+            Self::TransformFeedbackActive => {
+                write!(
+                    f,
+                    "transform feedback is still active on the command buffer"
+                )
+            }
         }
     }
 }
@@ -725,6 +820,8 @@ pub struct PrimaryAutoCommandBuffer<A = StandardCommandBufferAlloc> {
     inner: SyncCommandBuffer,
     _alloc: A, // Safety: must be dropped after `inner`
     usage: CommandBufferUsage,
+    _event_locks: Vec<EventRecordingLock>,
+    _downsample_pipelines: Vec<Arc<DownsamplePipeline>>,
 
     state: Mutex<CommandBufferState>,
 }
@@ -768,6 +865,9 @@ pub struct SecondaryAutoCommandBuffer<A = StandardCommandBufferAlloc> {
 
     // Tracks usage of the command buffer on the GPU.
     submit_state: SubmitState,
+
+    _event_locks: Vec<EventRecordingLock>,
+    _downsample_pipelines: Vec<Arc<DownsamplePipeline>>,
 }
 
 unsafe impl<A> VulkanObject for SecondaryAutoCommandBuffer<A> {
@@ -859,6 +959,148 @@ enum SubmitState {
     },
 }
 
+/// A command buffer that transitions subresources of images created with [`Image::new`], from the
+/// layout that the previous submissions left them in to the layout that the next command buffer
+/// expects. It is inserted in front of that command buffer when it is submitted.
+pub(crate) struct LayoutTransitionCommandBuffer {
+    inner: UnsafeCommandBuffer,
+    _alloc: StandardCommandBufferAlloc, // Safety: must be dropped after `inner`
+    resources_usage: CommandBufferResourcesUsage,
+
+    state: Mutex<CommandBufferState>,
+}
+
+impl LayoutTransitionCommandBuffer {
+    /// Records a command buffer that transitions each range of each image from the first layout to
+    /// the second.
+    pub(crate) fn new(
+        allocator: &StandardCommandBufferAllocator,
+        queue_family_index: u32,
+        transitions: impl IntoIterator<Item = (Arc<Image>, Range<DeviceSize>, ImageLayout, ImageLayout)>,
+    ) -> Result<Arc<Self>, OomError> {
+        let alloc = allocator
+            .allocate(queue_family_index, CommandBufferLevel::Primary, 1)?
+            .next()
+            .expect("requested one command buffer from the command pool, but got zero");
+
+        let mut dependency_info = DependencyInfo::default();
+        let mut images: HashMap<Arc<Image>, RangeMap<DeviceSize, CommandBufferImageRangeUsage>> =
+            HashMap::default();
+
+        for (image, range, old_layout, new_layout) in transitions {
+            let device = image.device();
+
+            // VUID-VkImageMemoryBarrier2-image-03320
+            // The depth and stencil aspects are always used together, so their layouts are the
+            // same and only the ranges of the depth aspect need a barrier.
+            let combine_depth_stencil = !device.enabled_features().separate_depth_stencil_layouts
+                && image
+                    .format()
+                    .unwrap()
+                    .aspects()
+                    .contains(ImageAspects::DEPTH | ImageAspects::STENCIL);
+
+            for mut subresource_range in image.range_to_subresources_split(range.clone()) {
+                if combine_depth_stencil {
+                    if subresource_range.aspects == ImageAspects::STENCIL {
+                        continue;
+                    }
+
+                    subresource_range.aspects = ImageAspects::DEPTH | ImageAspects::STENCIL;
+                }
+
+                // There is no telling what the previous submissions did with the image, so
+                // synchronize with everything, like the first use in a command buffer does.
+                dependency_info
+                    .image_memory_barriers
+                    .push(ImageMemoryBarrier {
+                        src_stages: PipelineStages::ALL_COMMANDS,
+                        src_access: AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE,
+                        dst_stages: PipelineStages::ALL_COMMANDS,
+                        dst_access: AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE,
+                        old_layout,
+                        new_layout,
+                        subresource_range,
+                        ..ImageMemoryBarrier::image(image.clone())
+                    });
+            }
+
+            images.entry(image).or_default().insert(
+                range,
+                CommandBufferImageRangeUsage {
+                    first_use: None,
+                    mutable: true,
+                    expected_layout: old_layout,
+                    final_layout: new_layout,
+                },
+            );
+        }
+
+        let inner = unsafe {
+            let mut builder = UnsafeCommandBufferBuilder::new(
+                alloc.inner(),
+                CommandBufferBeginInfo {
+                    usage: CommandBufferUsage::OneTimeSubmit,
+                    ..Default::default()
+                },
+            )?;
+            builder.pipeline_barrier(&dependency_info);
+            builder.build()?
+        };
+
+        let images: Vec<_> = images
+            .into_iter()
+            .map(|(image, ranges)| CommandBufferImageUsage { image, ranges })
+            .collect();
+        let image_indices = images
+            .iter()
+            .enumerate()
+            .map(|(index, usage)| (usage.image.clone(), index))
+            .collect();
+
+        Ok(Arc::new(LayoutTransitionCommandBuffer {
+            inner,
+            _alloc: alloc.into_alloc(),
+            resources_usage: CommandBufferResourcesUsage {
+                buffers: Vec::new(),
+                images,
+                buffer_indices: HashMap::default(),
+                image_indices,
+            },
+
+            state: Mutex::new(Default::default()),
+        }))
+    }
+}
+
+unsafe impl DeviceOwned for LayoutTransitionCommandBuffer {
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+unsafe impl VulkanObject for LayoutTransitionCommandBuffer {
+    type Handle = ash::vk::CommandBuffer;
+
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
+unsafe impl PrimaryCommandBufferAbstract for LayoutTransitionCommandBuffer {
+    fn usage(&self) -> CommandBufferUsage {
+        CommandBufferUsage::OneTimeSubmit
+    }
+
+    fn state(&self) -> MutexGuard<'_, CommandBufferState> {
+        self.state.lock()
+    }
+
+    fn resources_usage(&self) -> &CommandBufferResourcesUsage {
+        &self.resources_usage
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
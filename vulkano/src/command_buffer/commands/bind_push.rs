@@ -8,7 +8,7 @@
 // according to those terms.
 
 use crate::{
-    buffer::{BufferContents, BufferUsage, Subbuffer},
+    buffer::{Buffer, BufferContents, BufferUsage, Subbuffer},
     command_buffer::{
         allocator::CommandBufferAllocator,
         auto::RenderPassStateType,
@@ -17,10 +17,10 @@ use crate::{
         AutoCommandBufferBuilder,
     },
     descriptor_set::{
-        check_descriptor_write, layout::DescriptorType, sys::UnsafeDescriptorSet,
-        DescriptorBindingResources, DescriptorSetResources, DescriptorSetUpdateError,
-        DescriptorSetWithOffsets, DescriptorSetsCollection, DescriptorWriteInfo,
-        WriteDescriptorSet,
+        check_descriptor_write, descriptor_buffer::DescriptorBufferSet, layout::DescriptorType,
+        sys::UnsafeDescriptorSet, DescriptorBindingResources, DescriptorSetResources,
+        DescriptorSetUpdateError, DescriptorSetWithOffsets, DescriptorSetsCollection,
+        DescriptorWriteInfo, WriteDescriptorSet,
     },
     device::{DeviceOwned, QueueFlags},
     memory::{is_aligned, DeviceAlignment},
@@ -35,6 +35,7 @@ use crate::{
     shader::ShaderStages,
     DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
 };
+use ahash::HashMap;
 use parking_lot::Mutex;
 use smallvec::SmallVec;
 use std::{
@@ -224,6 +225,169 @@ where
         Ok(())
     }
 
+    /// Binds descriptor sets that are stored in descriptor buffers for future dispatch or draw
+    /// calls.
+    ///
+    /// The buffers of all `descriptor_sets` are bound together, replacing any descriptor buffers
+    /// that were bound before. Descriptor buffer sets that were bound by an earlier call, for any
+    /// pipeline bind point, are therefore unbound and must be bound again if they are still
+    /// needed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support `pipeline_bind_point`.
+    /// - Panics if the [`descriptor_buffer`](crate::device::Features::descriptor_buffer) feature
+    ///   is not enabled on the device.
+    /// - Panics if the highest descriptor set slot being bound is not less than the number of sets
+    ///   in `pipeline_layout`.
+    /// - Panics if an element of `descriptor_sets` is not compatible with the corresponding set
+    ///   layout in `pipeline_layout`.
+    /// - Panics if the descriptor sets use more buffers than the
+    ///   [`max_descriptor_buffer_bindings`](crate::device::Properties::max_descriptor_buffer_bindings)
+    ///   limit allows, or more buffers with the `sampler_descriptor_buffer` or
+    ///   `resource_descriptor_buffer` usage than the
+    ///   [`max_sampler_descriptor_buffer_bindings`](crate::device::Properties::max_sampler_descriptor_buffer_bindings)
+    ///   or
+    ///   [`max_resource_descriptor_buffer_bindings`](crate::device::Properties::max_resource_descriptor_buffer_bindings)
+    ///   limits allow.
+    /// - Panics if `self` and any element of `descriptor_sets` do not belong to the same device.
+    pub fn bind_descriptor_buffer_sets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: Arc<PipelineLayout>,
+        first_set: u32,
+        descriptor_sets: impl IntoIterator<Item = Arc<DescriptorBufferSet>>,
+    ) -> &mut Self {
+        let descriptor_sets: SmallVec<[_; 4]> = descriptor_sets.into_iter().collect();
+        self.validate_bind_descriptor_buffer_sets(
+            pipeline_bind_point,
+            &pipeline_layout,
+            first_set,
+            &descriptor_sets,
+        )
+        .unwrap();
+
+        unsafe {
+            self.inner.bind_descriptor_buffer_sets(
+                pipeline_bind_point,
+                pipeline_layout,
+                first_set,
+                descriptor_sets,
+            );
+        }
+
+        self
+    }
+
+    fn validate_bind_descriptor_buffer_sets(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        descriptor_sets: &[Arc<DescriptorBufferSet>],
+    ) -> Result<(), BindPushError> {
+        // VUID-vkCmdBindDescriptorBuffersEXT-None-08047
+        if !self.device().enabled_features().descriptor_buffer {
+            return Err(BindPushError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::bind_descriptor_buffer_sets`",
+                requires_one_of: RequiresOneOf {
+                    features: &["descriptor_buffer"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-parameter
+        pipeline_bind_point.validate_device(self.device())?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-commandBuffer-cmdpool
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-08067
+        match pipeline_bind_point {
+            PipelineBindPoint::Compute => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::COMPUTE)
+                {
+                    return Err(BindPushError::NotSupportedByQueueFamily);
+                }
+            }
+            PipelineBindPoint::Graphics => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(BindPushError::NotSupportedByQueueFamily);
+                }
+            }
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-commonparent
+        assert_eq!(self.device(), pipeline_layout.device());
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-08066
+        if first_set + descriptor_sets.len() as u32 > pipeline_layout.set_layouts().len() as u32 {
+            return Err(BindPushError::DescriptorSetOutOfRange {
+                set_num: first_set + descriptor_sets.len() as u32,
+                pipeline_layout_set_count: pipeline_layout.set_layouts().len() as u32,
+            });
+        }
+
+        for (i, set) in descriptor_sets.iter().enumerate() {
+            let set_num = first_set + i as u32;
+
+            assert_eq!(self.device(), set.device());
+
+            // VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-08066
+            if !pipeline_layout.set_layouts()[set_num as usize].is_compatible_with(set.layout()) {
+                return Err(BindPushError::DescriptorSetNotCompatible { set_num });
+            }
+        }
+
+        let properties = self.device().physical_device().properties();
+        let buffers = descriptor_buffers(descriptor_sets).0;
+        let count_with_usage = |usage: BufferUsage| {
+            buffers
+                .iter()
+                .filter(|buffer| buffer.usage().intersects(usage))
+                .count() as u32
+        };
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-bufferCount-08051
+        // VUID-vkCmdBindDescriptorBuffersEXT-maxSamplerDescriptorBufferBindings-08048
+        // VUID-vkCmdBindDescriptorBuffersEXT-maxResourceDescriptorBufferBindings-08049
+        for (limit, buffer_count, max) in [
+            (
+                "max_descriptor_buffer_bindings",
+                buffers.len() as u32,
+                properties.max_descriptor_buffer_bindings,
+            ),
+            (
+                "max_sampler_descriptor_buffer_bindings",
+                count_with_usage(BufferUsage::SAMPLER_DESCRIPTOR_BUFFER),
+                properties.max_sampler_descriptor_buffer_bindings,
+            ),
+            (
+                "max_resource_descriptor_buffer_bindings",
+                count_with_usage(BufferUsage::RESOURCE_DESCRIPTOR_BUFFER),
+                properties.max_resource_descriptor_buffer_bindings,
+            ),
+        ] {
+            let max = max.unwrap_or(0);
+
+            if buffer_count > max {
+                return Err(BindPushError::MaxDescriptorBufferBindingsExceeded {
+                    limit,
+                    buffer_count,
+                    max,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Binds an index buffer for future indexed draw calls.
     ///
     /// # Panics
@@ -361,6 +525,11 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // VUID-vkCmdBindPipeline-None-02323
+        if self.transform_feedback_active {
+            return Err(BindPushError::TransformFeedbackIsActive);
+        }
+
         if let Some(last_pipeline) = self
             .render_pass_state
             .as_ref()
@@ -724,6 +893,75 @@ impl SyncCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdBindDescriptorBuffersEXT` and `vkCmdSetDescriptorBufferOffsetsEXT` on the
+    /// builder.
+    pub unsafe fn bind_descriptor_buffer_sets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: Arc<PipelineLayout>,
+        first_set: u32,
+        descriptor_sets: SmallVec<[Arc<DescriptorBufferSet>; 4]>,
+    ) {
+        struct Cmd {
+            pipeline_bind_point: PipelineBindPoint,
+            pipeline_layout: Arc<PipelineLayout>,
+            first_set: u32,
+            descriptor_sets: SmallVec<[Arc<DescriptorBufferSet>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_descriptor_buffer_sets"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                let (buffers, buffer_indices) = descriptor_buffers(&self.descriptor_sets);
+                let offsets = self.descriptor_sets.iter().map(|set| set.buffer().offset());
+
+                out.bind_descriptor_buffers(buffers);
+                out.set_descriptor_buffer_offsets(
+                    self.pipeline_bind_point,
+                    &self.pipeline_layout,
+                    self.first_set,
+                    buffer_indices,
+                    offsets,
+                );
+            }
+        }
+
+        if descriptor_sets.is_empty() {
+            return;
+        }
+
+        // Binding descriptor buffers replaces all previously bound ones, so any set that was
+        // bound from a descriptor buffer before is no longer valid.
+        for state in self.current_state.descriptor_sets.values_mut() {
+            state
+                .descriptor_sets
+                .retain(|_, set| !matches!(set, SetOrPush::Buffer(_)));
+        }
+
+        let state = self.current_state.invalidate_descriptor_sets(
+            pipeline_bind_point,
+            pipeline_layout.clone(),
+            first_set,
+            descriptor_sets.len() as u32,
+        );
+
+        for (set_num, set) in descriptor_sets.iter().enumerate() {
+            state
+                .descriptor_sets
+                .insert(first_set + set_num as u32, SetOrPush::Buffer(set.clone()));
+        }
+
+        self.commands.push(Box::new(Cmd {
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            descriptor_sets,
+        }));
+    }
+
     /// Calls `vkCmdBindIndexBuffer` on the builder.
     #[inline]
     pub unsafe fn bind_index_buffer(&mut self, buffer: Subbuffer<[u8]>, index_type: IndexType) {
@@ -1093,6 +1331,73 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdBindDescriptorBuffersEXT` on the builder. Each buffer is bound from its
+    /// start, with the usage it was created with.
+    ///
+    /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
+    /// usage of the command anyway.
+    pub unsafe fn bind_descriptor_buffers<'a>(
+        &mut self,
+        buffers: impl IntoIterator<Item = &'a Arc<Buffer>>,
+    ) {
+        let binding_infos: SmallVec<[_; 4]> = buffers
+            .into_iter()
+            .map(|buffer| ash::vk::DescriptorBufferBindingInfoEXT {
+                address: buffer.device_address().unwrap().get(),
+                usage: buffer.usage().into(),
+                ..Default::default()
+            })
+            .collect();
+
+        if binding_infos.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_descriptor_buffer.cmd_bind_descriptor_buffers_ext)(
+            self.handle,
+            binding_infos.len() as u32,
+            binding_infos.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdSetDescriptorBufferOffsetsEXT` on the builder.
+    ///
+    /// Does nothing if the list of buffer indices is empty, as it would be a no-op and isn't a
+    /// valid usage of the command anyway.
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: impl IntoIterator<Item = u32>,
+        offsets: impl IntoIterator<Item = DeviceSize>,
+    ) {
+        let buffer_indices: SmallVec<[u32; 4]> = buffer_indices.into_iter().collect();
+        let offsets: SmallVec<[DeviceSize; 4]> = offsets.into_iter().collect();
+        debug_assert_eq!(buffer_indices.len(), offsets.len());
+
+        if buffer_indices.is_empty() {
+            return;
+        }
+
+        debug_assert!(
+            first_set + buffer_indices.len() as u32 <= pipeline_layout.set_layouts().len() as u32
+        );
+
+        let fns = self.device.fns();
+        (fns.ext_descriptor_buffer
+            .cmd_set_descriptor_buffer_offsets_ext)(
+            self.handle,
+            pipeline_bind_point.into(),
+            pipeline_layout.handle(),
+            first_set,
+            buffer_indices.len() as u32,
+            buffer_indices.as_ptr(),
+            offsets.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdBindIndexBuffer` on the builder.
     #[inline]
     pub unsafe fn bind_index_buffer(&mut self, buffer: &Subbuffer<[u8]>, index_type: IndexType) {
@@ -1232,7 +1537,14 @@ impl UnsafeCommandBufferBuilder {
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        let inline_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
+        for ((info, inline_info), write) in
+            infos.iter().zip(inline_infos.iter()).zip(writes.iter_mut())
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -1246,6 +1558,10 @@ impl UnsafeCommandBufferBuilder {
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -1295,6 +1611,28 @@ impl UnsafeCommandBufferBuilderBindVertexBuffer {
     }
 }
 
+/// Returns the distinct buffers that `descriptor_sets` are stored in, and for each set, the index
+/// of its buffer in that list.
+fn descriptor_buffers(
+    descriptor_sets: &[Arc<DescriptorBufferSet>],
+) -> (SmallVec<[&Arc<Buffer>; 4]>, SmallVec<[u32; 4]>) {
+    let mut buffers: SmallVec<[&Arc<Buffer>; 4]> = SmallVec::new();
+    let mut indices_by_buffer = HashMap::default();
+
+    let buffer_indices = descriptor_sets
+        .iter()
+        .map(|set| {
+            let buffer = set.buffer().buffer();
+            *indices_by_buffer.entry(buffer.handle()).or_insert_with(|| {
+                buffers.push(buffer);
+                buffers.len() as u32 - 1
+            })
+        })
+        .collect();
+
+    (buffers, buffer_indices)
+}
+
 #[derive(Clone, Debug)]
 pub(in super::super) enum BindPushError {
     DescriptorSetUpdateError(DescriptorSetUpdateError),
@@ -1359,6 +1697,15 @@ pub(in super::super) enum BindPushError {
     /// An index buffer is missing the `index_buffer` usage.
     IndexBufferMissingUsage,
 
+    /// The descriptor sets being bound are stored in more buffers than the
+    /// `max_descriptor_buffer_bindings` limit, or more sampler or resource descriptor buffers
+    /// than the corresponding limits.
+    MaxDescriptorBufferBindingsExceeded {
+        limit: &'static str,
+        buffer_count: u32,
+        max: u32,
+    },
+
     /// The `max_vertex_input_bindings` limit has been exceeded.
     MaxVertexInputBindingsExceeded {
         _binding_count: u32,
@@ -1392,6 +1739,9 @@ pub(in super::super) enum BindPushError {
     /// The push constants size is not a multiple of 4.
     PushConstantsSizeNotAligned,
 
+    /// Transform feedback is active, which conflicts with the current operation.
+    TransformFeedbackIsActive,
+
     /// A vertex buffer is missing the `vertex_buffer` usage.
     VertexBufferMissingUsage,
 }
@@ -1480,6 +1830,16 @@ impl Display for BindPushError {
             Self::IndexBufferMissingUsage => {
                 write!(f, "an index buffer is missing the `index_buffer` usage")
             }
+            Self::MaxDescriptorBufferBindingsExceeded {
+                limit,
+                buffer_count,
+                max,
+            } => write!(
+                f,
+                "the descriptor sets being bound are stored in more buffers ({}) than the `{}` \
+                limit ({})",
+                buffer_count, limit, max,
+            ),
             Self::MaxVertexInputBindingsExceeded { .. } => {
                 write!(f, "the `max_vertex_input_bindings` limit has been exceeded")
             }
@@ -1513,6 +1873,10 @@ impl Display for BindPushError {
             Self::PushConstantsSizeNotAligned => {
                 write!(f, "the push constants size is not a multiple of 4")
             }
+            Self::TransformFeedbackIsActive => write!(
+                f,
+                "transform feedback is active, which conflicts with the current operation",
+            ),
             Self::VertexBufferMissingUsage => {
                 write!(f, "a vertex buffer is missing the `vertex_buffer` usage")
             }
@@ -0,0 +1,429 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{ConditionalRenderingState, RenderPassStateType},
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    RequirementNotMet, RequiresOneOf, VulkanObject,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+};
+
+/// # Commands for conditional rendering.
+///
+/// These commands require the [`conditional_rendering`] feature to be enabled on the device.
+///
+/// [`conditional_rendering`]: crate::device::Features::conditional_rendering
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a conditional rendering block.
+    ///
+    /// Until [`end_conditional_rendering`] is called, draw and dispatch commands are discarded if
+    /// the 32-bit value in `predicate` is zero, or non-zero if `inverted` is `true`. The value is
+    /// read by the device when the commands are executed, not when they are recorded.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `predicate` was not created from the same device as `self`.
+    ///
+    /// [`end_conditional_rendering`]: Self::end_conditional_rendering
+    pub fn begin_conditional_rendering(
+        &mut self,
+        predicate: Subbuffer<u32>,
+        inverted: bool,
+    ) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_begin_conditional_rendering(&predicate, inverted)?;
+
+        unsafe {
+            self.inner
+                .begin_conditional_rendering(predicate.clone(), inverted)?;
+        }
+
+        self.conditional_rendering_state = Some(ConditionalRenderingState {
+            predicate,
+            subpass_index: self.current_subpass_index(),
+        });
+
+        Ok(self)
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        predicate: &Subbuffer<u32>,
+        _inverted: bool,
+    ) -> Result<(), ConditionalRenderingError> {
+        let device = self.device();
+
+        if !device.enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-None-01980
+        if self.conditional_rendering_state.is_some() {
+            return Err(ConditionalRenderingError::AlreadyActive);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-parameter
+        assert_eq!(device, predicate.device());
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-01982
+        if !predicate
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(ConditionalRenderingError::PredicateMissingUsage);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01983
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01984
+        // Guaranteed because we take `Subbuffer<u32>`
+
+        Ok(())
+    }
+
+    /// Ends the active conditional rendering block.
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe {
+            self.inner.end_conditional_rendering();
+        }
+
+        self.conditional_rendering_state = None;
+
+        Ok(self)
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), ConditionalRenderingError> {
+        if !self.device().enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::end_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01985
+        let state = self
+            .conditional_rendering_state
+            .as_ref()
+            .ok_or(ConditionalRenderingError::NotActive)?;
+
+        match (state.subpass_index, self.current_subpass_index()) {
+            (None, None) => (),
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+            (None, Some(_)) => return Err(ConditionalRenderingError::ForbiddenInsideRenderPass),
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+            (Some(begun), current) => {
+                if current != Some(begun) {
+                    return Err(ConditionalRenderingError::SubpassMismatch);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn current_subpass_index(&self) -> Option<u32> {
+        self.render_pass_state
+            .as_ref()
+            .map(|state| match &state.render_pass {
+                RenderPassStateType::BeginRenderPass(state) => state.subpass.index(),
+                RenderPassStateType::BeginRendering(_) => 0,
+            })
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        predicate: Subbuffer<u32>,
+        inverted: bool,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            predicate: Subbuffer<u32>,
+            inverted: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_conditional_rendering(&self.predicate, self.inverted);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "begin_conditional_rendering";
+        let resources = [(
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::ConditionalRenderingPredicate,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: predicate.as_bytes().clone(),
+                range: 0..predicate.size(),
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::CONDITIONAL_RENDERING,
+                    access: AccessFlags::CONDITIONAL_RENDERING_READ,
+                    exclusive: false,
+                },
+            },
+        )];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            predicate,
+            inverted,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        struct Cmd;
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_conditional_rendering();
+            }
+        }
+
+        self.commands.push(Box::new(Cmd));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        predicate: &Subbuffer<u32>,
+        inverted: bool,
+    ) {
+        let mut flags = ash::vk::ConditionalRenderingFlagsEXT::empty();
+
+        if inverted {
+            flags |= ash::vk::ConditionalRenderingFlagsEXT::INVERTED;
+        }
+
+        let begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: predicate.buffer().handle(),
+            offset: predicate.offset(),
+            flags,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(self.handle, &begin_info);
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle);
+    }
+}
+
+/// Error that can happen when recording a conditional rendering command.
+#[derive(Clone, Debug)]
+pub enum ConditionalRenderingError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// Conditional rendering is already active.
+    AlreadyActive,
+
+    /// Conditional rendering was begun outside a render pass, but is ended inside one.
+    ForbiddenInsideRenderPass,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// Conditional rendering is not active.
+    NotActive,
+
+    /// The predicate buffer is missing the `conditional_rendering` usage.
+    PredicateMissingUsage,
+
+    /// Conditional rendering was begun inside a subpass, but is ended in a different subpass or
+    /// outside the render pass.
+    SubpassMismatch,
+}
+
+impl Error for ConditionalRenderingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ConditionalRenderingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::AlreadyActive => write!(f, "conditional rendering is already active"),
+            Self::ForbiddenInsideRenderPass => write!(
+                f,
+                "conditional rendering was begun outside a render pass, but is ended inside one",
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::NotActive => write!(f, "conditional rendering is not active"),
+            Self::PredicateMissingUsage => write!(
+                f,
+                "the predicate buffer is missing the `conditional_rendering` usage",
+            ),
+            Self::SubpassMismatch => write!(
+                f,
+                "conditional rendering was begun inside a subpass, but is ended in a different \
+                subpass or outside the render pass",
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for ConditionalRenderingError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+impl From<RequirementNotMet> for ConditionalRenderingError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo},
+        command_buffer::{allocator::StandardCommandBufferAllocator, CommandBufferUsage},
+        memory::allocator::{AllocationCreateInfo, MemoryUsage, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let predicate = Buffer::from_data(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Upload,
+                ..Default::default()
+            },
+            1u32,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.begin_conditional_rendering(predicate, false),
+            Err(ConditionalRenderingError::RequirementNotMet { .. }),
+        ));
+        assert!(matches!(
+            cbb.end_conditional_rendering(),
+            Err(ConditionalRenderingError::RequirementNotMet { .. }),
+        ));
+    }
+}
@@ -14,13 +14,22 @@ use crate::{
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder,
     },
-    device::{DeviceOwned, QueueFlags},
+    device::{Device, DeviceOwned, QueueFlags},
+    format::{Format, FormatFeatures},
+    image::SampleCount,
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, BlendFactor, BlendOp, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
+            fragment_shading_rate::{FragmentShadingRate, FragmentShadingRateCombinerOp},
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            rasterization::{
+                CullMode, DepthBias, FrontFace, LineRasterizationMode, LineStipple, PolygonMode,
+            },
+            vertex_input::{
+                VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+                VertexInputState,
+            },
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -57,6 +66,123 @@ where
         Ok(())
     }
 
+    /// Sets whether alpha-to-coverage is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_alpha_to_coverage_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_coverage_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_alpha_to_coverage_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_coverage_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_alpha_to_coverage_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_alpha_to_coverage_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToCoverageEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-extendedDynamicState3AlphaToCoverageEnable-07343
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_coverage_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_alpha_to_coverage_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_coverage_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets whether alpha-to-one is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_alpha_to_one_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_one_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `enable` is `true` and the
+    ///   [`alpha_to_one`](crate::device::Features::alpha_to_one) feature is not enabled on the
+    ///   device.
+    pub fn set_alpha_to_one_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_one_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_alpha_to_one_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToOneEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-extendedDynamicState3AlphaToOneEnable-07345
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_one_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_alpha_to_one_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_one_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-alphaToOne-07607
+        if enable && !self.device().enabled_features().alpha_to_one {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["alpha_to_one"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     ///
     /// # Panics
@@ -92,6 +218,236 @@ where
         Ok(())
     }
 
+    /// Sets whether blending is enabled for future draw calls, for each color attachment
+    /// starting at `first_attachment`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_color_blend_enable`](crate::device::Features::extended_dynamic_state3_color_blend_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the highest attachment being set is not less than the
+    ///   [`max_color_attachments`](crate::device::Properties::max_color_attachments) device
+    ///   property.
+    pub fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: impl IntoIterator<Item = bool>,
+    ) -> &mut Self {
+        let enables: SmallVec<[bool; 4]> = enables.into_iter().collect();
+        self.validate_set_color_blend_enable(first_attachment, &enables)
+            .unwrap();
+
+        unsafe {
+            self.inner.set_color_blend_enable(first_attachment, enables);
+        }
+
+        self
+    }
+
+    fn validate_set_color_blend_enable(
+        &self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ColorBlendEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetColorBlendEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetColorBlendEnableEXT-extendedDynamicState3ColorBlendEnable-07355
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_color_blend_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_color_blend_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID?
+        if first_attachment + enables.len() as u32 > properties.max_color_attachments {
+            return Err(SetDynamicStateError::MaxColorAttachmentsExceeded {
+                provided: first_attachment + enables.len() as u32,
+                max: properties.max_color_attachments,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets the blend equation for future draw calls, for each color attachment starting at
+    /// `first_attachment`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_color_blend_equation`](crate::device::Features::extended_dynamic_state3_color_blend_equation)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the highest attachment being set is not less than the
+    ///   [`max_color_attachments`](crate::device::Properties::max_color_attachments) device
+    ///   property.
+    /// - Panics if an element of `equations` uses a `BlendFactor::Src1*` blend factor and the
+    ///   [`dual_src_blend`](crate::device::Features::dual_src_blend) feature is not enabled on the
+    ///   device.
+    /// - Panics if an element of `equations` uses an advanced blend operation.
+    pub fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: impl IntoIterator<Item = AttachmentBlend>,
+    ) -> &mut Self {
+        let equations: SmallVec<[AttachmentBlend; 4]> = equations.into_iter().collect();
+        self.validate_set_color_blend_equation(first_attachment, &equations)
+            .unwrap();
+
+        unsafe {
+            self.inner
+                .set_color_blend_equation(first_attachment, equations);
+        }
+
+        self
+    }
+
+    fn validate_set_color_blend_equation(
+        &self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ColorBlendEquation)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetColorBlendEquationEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetColorBlendEquationEXT-extendedDynamicState3ColorBlendEquation-07356
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_equation
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_color_blend_equation`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_color_blend_equation"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID?
+        if first_attachment + equations.len() as u32 > properties.max_color_attachments {
+            return Err(SetDynamicStateError::MaxColorAttachmentsExceeded {
+                provided: first_attachment + equations.len() as u32,
+                max: properties.max_color_attachments,
+            });
+        }
+
+        for (num, equation) in equations.iter().enumerate() {
+            let attachment = num as u32 + first_attachment;
+            let &AttachmentBlend {
+                color_op,
+                color_source,
+                color_destination,
+                alpha_op,
+                alpha_source,
+                alpha_destination,
+            } = equation;
+
+            // VUID-VkColorBlendEquationEXT-colorBlendOp-parameter
+            color_op.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-srcColorBlendFactor-parameter
+            color_source.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-dstColorBlendFactor-parameter
+            color_destination.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-alphaBlendOp-parameter
+            alpha_op.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-srcAlphaBlendFactor-parameter
+            alpha_source.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-dstAlphaBlendFactor-parameter
+            alpha_destination.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07357
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07358
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07359
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07360
+            if !self.device().enabled_features().dual_src_blend
+                && [
+                    color_source,
+                    color_destination,
+                    alpha_source,
+                    alpha_destination,
+                ]
+                .into_iter()
+                .any(|blend_factor| {
+                    matches!(
+                        blend_factor,
+                        BlendFactor::Src1Color
+                            | BlendFactor::OneMinusSrc1Color
+                            | BlendFactor::Src1Alpha
+                            | BlendFactor::OneMinusSrc1Alpha
+                    )
+                })
+            {
+                return Err(SetDynamicStateError::RequirementNotMet {
+                    required_for: "`equations` has an element where `color_source`, \
+                        `color_destination`, `alpha_source` or `alpha_destination` is \
+                        `BlendFactor::Src1*`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["dual_src_blend"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkColorBlendEquationEXT-colorBlendOp-07361
+            if [color_op, alpha_op].into_iter().any(|blend_op| {
+                !matches!(
+                    blend_op,
+                    BlendOp::Add
+                        | BlendOp::Subtract
+                        | BlendOp::ReverseSubtract
+                        | BlendOp::Min
+                        | BlendOp::Max
+                )
+            }) {
+                return Err(SetDynamicStateError::ColorBlendEquationAdvancedBlendOp { attachment });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Sets whether dynamic color writes should be enabled for each attachment in the
     /// framebuffer.
     ///
@@ -166,6 +522,82 @@ where
         Ok(())
     }
 
+    /// Sets the color write mask for future draw calls, for each color attachment starting at
+    /// `first_attachment`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_color_write_mask`](crate::device::Features::extended_dynamic_state3_color_write_mask)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the highest attachment being set is not less than the
+    ///   [`max_color_attachments`](crate::device::Properties::max_color_attachments) device
+    ///   property.
+    pub fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: impl IntoIterator<Item = ColorComponents>,
+    ) -> &mut Self {
+        let color_write_masks: SmallVec<[ColorComponents; 4]> =
+            color_write_masks.into_iter().collect();
+        self.validate_set_color_write_mask(first_attachment, &color_write_masks)
+            .unwrap();
+
+        unsafe {
+            self.inner
+                .set_color_write_mask(first_attachment, color_write_masks);
+        }
+
+        self
+    }
+
+    fn validate_set_color_write_mask(
+        &self,
+        first_attachment: u32,
+        color_write_masks: &[ColorComponents],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ColorWriteMask)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetColorWriteMaskEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetColorWriteMaskEXT-extendedDynamicState3ColorWriteMask-07364
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_write_mask
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_color_write_mask`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_color_write_mask"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID?
+        if first_attachment + color_write_masks.len() as u32 > properties.max_color_attachments {
+            return Err(SetDynamicStateError::MaxColorAttachmentsExceeded {
+                provided: first_attachment + color_write_masks.len() as u32,
+                max: properties.max_color_attachments,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic cull mode for future draw calls.
     ///
     /// # Panics
@@ -432,20 +864,83 @@ where
         Ok(())
     }
 
-    /// Sets the dynamic depth compare op for future draw calls.
+    /// Sets whether depth clamping is enabled for future draw calls.
     ///
     /// # Panics
     ///
     /// - Panics if the queue family of the command buffer does not support graphics operations.
-    /// - Panics if the device API version is less than 1.3 and the
-    ///   [`extended_dynamic_state`](crate::device::Features::extended_dynamic_state) feature is
-    ///   not enabled on the device.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_depth_clamp_enable`](crate::device::Features::extended_dynamic_state3_depth_clamp_enable)
+    ///   feature is not enabled on the device.
     /// - Panics if the currently bound graphics pipeline already contains this state internally.
-    pub fn set_depth_compare_op(&mut self, compare_op: CompareOp) -> &mut Self {
-        self.validate_set_depth_compare_op(compare_op).unwrap();
+    /// - Panics if `enable` is `true` and the [`depth_clamp`](crate::device::Features::depth_clamp)
+    ///   feature is not enabled on the device.
+    pub fn set_depth_clamp_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_depth_clamp_enable(enable).unwrap();
 
         unsafe {
-            self.inner.set_depth_compare_op(compare_op);
+            self.inner.set_depth_clamp_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::DepthClampEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDepthClampEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-extendedDynamicState3DepthClampEnable-07448
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clamp_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_depth_clamp_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_depth_clamp_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-depthClamp-07449
+        if enable && !self.device().enabled_features().depth_clamp {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["depth_clamp"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets the dynamic depth compare op for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the device API version is less than 1.3 and the
+    ///   [`extended_dynamic_state`](crate::device::Features::extended_dynamic_state) feature is
+    ///   not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_depth_compare_op(&mut self, compare_op: CompareOp) -> &mut Self {
+        self.validate_set_depth_compare_op(compare_op).unwrap();
+
+        unsafe {
+            self.inner.set_depth_compare_op(compare_op);
         }
 
         self
@@ -664,6 +1159,157 @@ where
         Ok(())
     }
 
+    /// Sets the dynamic fragment shading rate and combiner operations for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if none of the
+    ///   [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate),
+    ///   [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    ///   or
+    ///   [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    ///   features are enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if a component of `fragment_size` is not 1, 2 or 4, or is not 1 and the
+    ///   `pipeline_fragment_shading_rate` feature is not enabled.
+    /// - Panics if `combiner_ops[0]` is not `Keep` and the `primitive_fragment_shading_rate`
+    ///   feature is not enabled, or if `combiner_ops[1]` is not `Keep` and the
+    ///   `attachment_fragment_shading_rate` feature is not enabled.
+    /// - Panics if an element of `combiner_ops` is not `Keep` or `Replace` and the
+    ///   [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    ///   device property is `false`.
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)
+            .unwrap();
+
+        unsafe {
+            self.inner
+                .set_fragment_shading_rate(fragment_size, combiner_ops);
+        }
+
+        self
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::FragmentShadingRate)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+        let enabled_features = device.enabled_features();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04509
+        if !(enabled_features.pipeline_fragment_shading_rate
+            || enabled_features.primitive_fragment_shading_rate
+            || enabled_features.attachment_fragment_shading_rate)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_fragment_shading_rate`",
+                requires_one_of: RequiresOneOf {
+                    features: &[
+                        "pipeline_fragment_shading_rate",
+                        "primitive_fragment_shading_rate",
+                        "attachment_fragment_shading_rate",
+                    ],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04513
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04514
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04515
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04516
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04517
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04518
+        if !fragment_size.iter().all(|&size| matches!(size, 1 | 2 | 4)) {
+            return Err(
+                SetDynamicStateError::FragmentShadingRateInvalidFragmentSize { fragment_size },
+            );
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04507
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04508
+        if fragment_size != [1, 1] && !enabled_features.pipeline_fragment_shading_rate {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_fragment_shading_rate` where \
+                    `fragment_size` is not `[1, 1]`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-combinerOps-parameter
+        for op in combiner_ops {
+            op.validate_device(device)?;
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-primitiveFragmentShadingRate-04510
+        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+            && !enabled_features.primitive_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_fragment_shading_rate` where \
+                    `combiner_ops[0]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["primitive_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-attachmentFragmentShadingRate-04511
+        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+            && !enabled_features.attachment_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_fragment_shading_rate` where \
+                    `combiner_ops[1]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["attachment_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-fragmentSizeNonTrivialCombinerOps-04512
+        if !device
+            .physical_device()
+            .properties()
+            .fragment_shading_rate_non_trivial_combiner_ops
+            .unwrap_or(false)
+            && !combiner_ops.iter().all(|&op| {
+                matches!(
+                    op,
+                    FragmentShadingRateCombinerOp::Keep | FragmentShadingRateCombinerOp::Replace
+                )
+            })
+        {
+            return Err(SetDynamicStateError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic front face for future draw calls.
     ///
     /// # Panics
@@ -716,6 +1362,112 @@ where
         Ok(())
     }
 
+    /// Sets the line rasterization mode for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_line_rasterization_mode`](crate::device::Features::extended_dynamic_state3_line_rasterization_mode)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the feature corresponding to `line_rasterization_mode` is not enabled on the
+    ///   device.
+    pub fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        self.validate_set_line_rasterization_mode(line_rasterization_mode)
+            .unwrap();
+
+        unsafe {
+            self.inner
+                .set_line_rasterization_mode(line_rasterization_mode);
+        }
+
+        self
+    }
+
+    fn validate_set_line_rasterization_mode(
+        &self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::LineRasterizationMode)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetLineRasterizationModeEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetLineRasterizationModeEXT-extendedDynamicState3LineRasterizationMode-07417
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_line_rasterization_mode
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_line_rasterization_mode`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_line_rasterization_mode"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-parameter
+        line_rasterization_mode.validate_device(self.device())?;
+
+        match line_rasterization_mode {
+            LineRasterizationMode::Default => (),
+            LineRasterizationMode::Rectangular => {
+                // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07418
+                if !self.device().enabled_features().rectangular_lines {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`line_rasterization_mode` is \
+                            `LineRasterizationMode::Rectangular`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["rectangular_lines"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            LineRasterizationMode::Bresenham => {
+                // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07419
+                if !self.device().enabled_features().bresenham_lines {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`line_rasterization_mode` is \
+                            `LineRasterizationMode::Bresenham`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["bresenham_lines"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            LineRasterizationMode::RectangularSmooth => {
+                // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07420
+                if !self.device().enabled_features().smooth_lines {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`line_rasterization_mode` is \
+                            `LineRasterizationMode::RectangularSmooth`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["smooth_lines"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic line stipple values for future draw calls.
     ///
     /// # Panics
@@ -770,6 +1522,56 @@ where
         Ok(())
     }
 
+    /// Sets whether line stippling is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_line_stipple_enable`](crate::device::Features::extended_dynamic_state3_line_stipple_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_line_stipple_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_line_stipple_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_line_stipple_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_line_stipple_enable(&self, _enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::LineStippleEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetLineStippleEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetLineStippleEnableEXT-extendedDynamicState3LineStippleEnable-07421
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_line_stipple_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_line_stipple_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_line_stipple_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic line width for future draw calls.
     ///
     /// # Panics
@@ -868,35 +1670,31 @@ where
         Ok(())
     }
 
-    /// Sets the dynamic number of patch control points for future draw calls.
+    /// Sets whether logic operations are enabled for future draw calls.
     ///
     /// # Panics
     ///
     /// - Panics if the queue family of the command buffer does not support graphics operations.
     /// - Panics if the
-    ///   [`extended_dynamic_state2_patch_control_points`](crate::device::Features::extended_dynamic_state2_patch_control_points)
+    ///   [`extended_dynamic_state3_logic_op_enable`](crate::device::Features::extended_dynamic_state3_logic_op_enable)
     ///   feature is not enabled on the device.
     /// - Panics if the currently bound graphics pipeline already contains this state internally.
-    /// - Panics if `num` is 0.
-    /// - Panics if `num` is greater than the
-    ///   [`max_tessellation_patch_size`](crate::device::Properties::max_tessellation_patch_size)
-    ///   property of the device.
-    pub fn set_patch_control_points(&mut self, num: u32) -> &mut Self {
-        self.validate_set_patch_control_points(num).unwrap();
+    pub fn set_logic_op_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_logic_op_enable(enable).unwrap();
 
         unsafe {
-            self.inner.set_patch_control_points(num);
+            self.inner.set_logic_op_enable(enable);
         }
 
         self
     }
 
-    fn validate_set_patch_control_points(&self, num: u32) -> Result<(), SetDynamicStateError> {
-        self.validate_pipeline_fixed_state(DynamicState::PatchControlPoints)?;
+    fn validate_set_logic_op_enable(&self, _enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::LogicOpEnable)?;
 
         let queue_family_properties = self.queue_family_properties();
 
-        // VUID-vkCmdSetPatchControlPointsEXT-commandBuffer-cmdpool
+        // VUID-vkCmdSetLogicOpEnableEXT-commandBuffer-cmdpool
         if !queue_family_properties
             .queue_flags
             .intersects(QueueFlags::GRAPHICS)
@@ -904,33 +1702,87 @@ where
             return Err(SetDynamicStateError::NotSupportedByQueueFamily);
         }
 
-        // VUID-vkCmdSetPatchControlPointsEXT-None-04873
+        // VUID-vkCmdSetLogicOpEnableEXT-extendedDynamicState3LogicOpEnable-07365
         if !self
             .device()
             .enabled_features()
-            .extended_dynamic_state2_patch_control_points
+            .extended_dynamic_state3_logic_op_enable
         {
             return Err(SetDynamicStateError::RequirementNotMet {
-                required_for: "`AutoCommandBufferBuilder::set_patch_control_points`",
+                required_for: "`AutoCommandBufferBuilder::set_logic_op_enable`",
                 requires_one_of: RequiresOneOf {
-                    features: &["extended_dynamic_state2_patch_control_points"],
+                    features: &["extended_dynamic_state3_logic_op_enable"],
                     ..Default::default()
                 },
             });
         }
 
-        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
-        assert!(num > 0, "num must be greater than 0");
+        Ok(())
+    }
 
-        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
-        if num
-            > self
-                .device()
-                .physical_device()
-                .properties()
-                .max_tessellation_patch_size
-        {
-            return Err(SetDynamicStateError::MaxTessellationPatchSizeExceeded {
+    /// Sets the dynamic number of patch control points for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state2_patch_control_points`](crate::device::Features::extended_dynamic_state2_patch_control_points)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `num` is 0.
+    /// - Panics if `num` is greater than the
+    ///   [`max_tessellation_patch_size`](crate::device::Properties::max_tessellation_patch_size)
+    ///   property of the device.
+    pub fn set_patch_control_points(&mut self, num: u32) -> &mut Self {
+        self.validate_set_patch_control_points(num).unwrap();
+
+        unsafe {
+            self.inner.set_patch_control_points(num);
+        }
+
+        self
+    }
+
+    fn validate_set_patch_control_points(&self, num: u32) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::PatchControlPoints)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetPatchControlPointsEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetPatchControlPointsEXT-None-04873
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state2_patch_control_points
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_patch_control_points`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state2_patch_control_points"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
+        assert!(num > 0, "num must be greater than 0");
+
+        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
+        if num
+            > self
+                .device()
+                .physical_device()
+                .properties()
+                .max_tessellation_patch_size
+        {
+            return Err(SetDynamicStateError::MaxTessellationPatchSizeExceeded {
                 provided: num,
                 max: self
                     .device()
@@ -943,6 +1795,96 @@ where
         Ok(())
     }
 
+    /// Sets the polygon mode for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_polygon_mode`](crate::device::Features::extended_dynamic_state3_polygon_mode)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `polygon_mode` is not `PolygonMode::Fill` and the
+    ///   [`fill_mode_non_solid`](crate::device::Features::fill_mode_non_solid) feature is not
+    ///   enabled on the device.
+    /// - On portability subset devices, panics if `polygon_mode` is `PolygonMode::Point` and the
+    ///   [`point_polygons`](crate::device::Features::point_polygons) feature is not enabled on the
+    ///   device.
+    pub fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.validate_set_polygon_mode(polygon_mode).unwrap();
+
+        unsafe {
+            self.inner.set_polygon_mode(polygon_mode);
+        }
+
+        self
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::PolygonMode)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetPolygonModeEXT-extendedDynamicState3PolygonMode-07422
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_polygon_mode
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_polygon_mode`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_polygon_mode"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter
+        polygon_mode.validate_device(self.device())?;
+
+        // VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424
+        if polygon_mode != PolygonMode::Fill
+            && !self.device().enabled_features().fill_mode_non_solid
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`polygon_mode` is not `PolygonMode::Fill`",
+                requires_one_of: RequiresOneOf {
+                    features: &["fill_mode_non_solid"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID?
+        if self.device().enabled_extensions().khr_portability_subset
+            && !self.device().enabled_features().point_polygons
+            && polygon_mode == PolygonMode::Point
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "this device is a portability subset device, and `polygon_mode` is \
+                    `PolygonMode::Point`",
+                requires_one_of: RequiresOneOf {
+                    features: &["point_polygons"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets whether dynamic primitive restart is enabled for future draw calls.
     ///
     /// # Panics
@@ -1100,6 +2042,157 @@ where
         Ok(())
     }
 
+    /// Sets the number of rasterization samples for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_rasterization_samples`](crate::device::Features::extended_dynamic_state3_rasterization_samples)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_rasterization_samples(&mut self, rasterization_samples: SampleCount) -> &mut Self {
+        self.validate_set_rasterization_samples(rasterization_samples)
+            .unwrap();
+
+        unsafe {
+            self.inner.set_rasterization_samples(rasterization_samples);
+        }
+
+        self
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::RasterizationSamples)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetRasterizationSamplesEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetRasterizationSamplesEXT-extendedDynamicState3RasterizationSamples-07414
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_samples
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_rasterization_samples`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_rasterization_samples"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetRasterizationSamplesEXT-rasterizationSamples-parameter
+        rasterization_samples.validate_device(self.device())?;
+
+        Ok(())
+    }
+
+    /// Sets the vertex stream that is used for rasterization for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_rasterization_stream`](crate::device::Features::extended_dynamic_state3_rasterization_stream)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the [`transform_feedback`](crate::device::Features::transform_feedback) feature
+    ///   is not enabled on the device.
+    /// - Panics if `rasterization_stream` is not less than the
+    ///   [`max_transform_feedback_streams`](crate::device::Properties::max_transform_feedback_streams)
+    ///   device property.
+    /// - Panics if `rasterization_stream` is not 0 and the
+    ///   [`transform_feedback_rasterization_stream_select`](crate::device::Properties::transform_feedback_rasterization_stream_select)
+    ///   device property is `false`.
+    pub fn set_rasterization_stream(&mut self, rasterization_stream: u32) -> &mut Self {
+        self.validate_set_rasterization_stream(rasterization_stream)
+            .unwrap();
+
+        unsafe {
+            self.inner.set_rasterization_stream(rasterization_stream);
+        }
+
+        self
+    }
+
+    fn validate_set_rasterization_stream(
+        &self,
+        rasterization_stream: u32,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::RasterizationStream)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetRasterizationStreamEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetRasterizationStreamEXT-extendedDynamicState3RasterizationStream-07410
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_stream
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_rasterization_stream`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_rasterization_stream"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetRasterizationStreamEXT-transformFeedback-07411
+        if !self.device().enabled_features().transform_feedback {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_rasterization_stream`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID-vkCmdSetRasterizationStreamEXT-rasterizationStream-07412
+        if rasterization_stream >= properties.max_transform_feedback_streams.unwrap_or(0) {
+            return Err(SetDynamicStateError::MaxTransformFeedbackStreamsExceeded {
+                provided: rasterization_stream + 1,
+                max: properties.max_transform_feedback_streams.unwrap_or(0),
+            });
+        }
+
+        // VUID-vkCmdSetRasterizationStreamEXT-rasterizationStream-07413
+        if rasterization_stream != 0
+            && !properties
+                .transform_feedback_rasterization_stream_select
+                .unwrap_or(false)
+        {
+            return Err(
+                SetDynamicStateError::TransformFeedbackRasterizationStreamSelectNotSupported,
+            );
+        }
+
+        Ok(())
+    }
+
     /// Sets whether dynamic rasterizer discard is enabled for future draw calls.
     ///
     /// # Panics
@@ -1152,6 +2245,66 @@ where
         Ok(())
     }
 
+    /// Sets the sample mask for future draw calls.
+    ///
+    /// `samples` is the number of rasterization samples that `sample_mask` covers. Bits beyond
+    /// this number are ignored.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_sample_mask`](crate::device::Features::extended_dynamic_state3_sample_mask)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_sample_mask(&mut self, samples: SampleCount, sample_mask: [u32; 2]) -> &mut Self {
+        self.validate_set_sample_mask(samples, sample_mask).unwrap();
+
+        unsafe {
+            self.inner.set_sample_mask(samples, sample_mask);
+        }
+
+        self
+    }
+
+    fn validate_set_sample_mask(
+        &self,
+        samples: SampleCount,
+        _sample_mask: [u32; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::SampleMask)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetSampleMaskEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetSampleMaskEXT-extendedDynamicState3SampleMask-07342
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_sample_mask
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_sample_mask`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_sample_mask"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetSampleMaskEXT-samples-parameter
+        samples.validate_device(self.device())?;
+
+        Ok(())
+    }
+
     /// Sets the dynamic scissors for future draw calls.
     ///
     /// # Panics
@@ -1664,21 +2817,103 @@ where
         self.validate_set_viewport_with_count(&viewports).unwrap();
 
         unsafe {
-            self.inner.set_viewport_with_count(viewports);
+            self.inner.set_viewport_with_count(viewports);
+        }
+
+        self
+    }
+
+    fn validate_set_viewport_with_count(
+        &self,
+        viewports: &[Viewport],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ViewportWithCount)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetViewportWithCount-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetViewportWithCount-None-03393
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_viewport_with_count`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_3),
+                    features: &["extended_dynamic_state"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetViewportWithCount-viewportCount-03394
+        if viewports.len() as u32 > self.device().physical_device().properties().max_viewports {
+            return Err(SetDynamicStateError::MaxViewportsExceeded {
+                provided: viewports.len() as u32,
+                max: self.device().physical_device().properties().max_viewports,
+            });
+        }
+
+        // VUID-vkCmdSetViewportWithCount-viewportCount-03395
+        if !self.device().enabled_features().multi_viewport && viewports.len() > 1 {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`viewports.len()` is greater than `1`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_viewport"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets the dynamic vertex input state for future draw calls.
+    ///
+    /// The vertex input state for a given vertex layout can be created by passing the
+    /// [`vertex_input_interface`] of the pipeline to [`VertexDefinition::definition`], for example
+    /// with a [`BuffersDefinition`] or `Vertex::per_vertex()`. At draw time, the state must
+    /// provide a compatible attribute for every input of the vertex shader.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`vertex_input_dynamic_state`](crate::device::Features::vertex_input_dynamic_state)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `vertex_input_state` exceeds the vertex input limits of the device, or if an
+    ///   attribute refers to a binding that is not in `vertex_input_state.bindings`.
+    ///
+    /// [`vertex_input_interface`]: crate::pipeline::GraphicsPipeline::vertex_input_interface
+    /// [`VertexDefinition::definition`]: crate::pipeline::graphics::vertex_input::VertexDefinition::definition
+    /// [`BuffersDefinition`]: crate::pipeline::graphics::vertex_input::BuffersDefinition
+    pub fn set_vertex_input(&mut self, vertex_input_state: VertexInputState) -> &mut Self {
+        self.validate_set_vertex_input(&vertex_input_state).unwrap();
+
+        unsafe {
+            self.inner.set_vertex_input(vertex_input_state);
         }
 
         self
     }
 
-    fn validate_set_viewport_with_count(
+    fn validate_set_vertex_input(
         &self,
-        viewports: &[Viewport],
+        vertex_input_state: &VertexInputState,
     ) -> Result<(), SetDynamicStateError> {
-        self.validate_pipeline_fixed_state(DynamicState::ViewportWithCount)?;
+        self.validate_pipeline_fixed_state(DynamicState::VertexInput)?;
 
         let queue_family_properties = self.queue_family_properties();
 
-        // VUID-vkCmdSetViewportWithCount-commandBuffer-cmdpool
+        // VUID-vkCmdSetVertexInputEXT-commandBuffer-cmdpool
         if !queue_family_properties
             .queue_flags
             .intersects(QueueFlags::GRAPHICS)
@@ -1686,44 +2921,206 @@ where
             return Err(SetDynamicStateError::NotSupportedByQueueFamily);
         }
 
-        // VUID-vkCmdSetViewportWithCount-None-03393
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
-        {
+        // VUID-vkCmdSetVertexInputEXT-None-04790
+        if !self.device().enabled_features().vertex_input_dynamic_state {
             return Err(SetDynamicStateError::RequirementNotMet {
-                required_for: "`AutoCommandBufferBuilder::set_viewport_with_count`",
+                required_for: "`AutoCommandBufferBuilder::set_vertex_input`",
                 requires_one_of: RequiresOneOf {
-                    api_version: Some(Version::V1_3),
-                    features: &["extended_dynamic_state"],
+                    features: &["vertex_input_dynamic_state"],
                     ..Default::default()
                 },
             });
         }
 
-        // VUID-vkCmdSetViewportWithCount-viewportCount-03394
-        if viewports.len() as u32 > self.device().physical_device().properties().max_viewports {
-            return Err(SetDynamicStateError::MaxViewportsExceeded {
-                provided: viewports.len() as u32,
-                max: self.device().physical_device().properties().max_viewports,
+        validate_vertex_input_state(self.device(), vertex_input_state)
+    }
+}
+
+/// Checks `vertex_input_state` against the limits of `device`, for `vkCmdSetVertexInputEXT`.
+pub(in super::super) fn validate_vertex_input_state(
+    device: &Device,
+    vertex_input_state: &VertexInputState,
+) -> Result<(), SetDynamicStateError> {
+    let properties = device.physical_device().properties();
+    let VertexInputState {
+        bindings,
+        attributes,
+    } = vertex_input_state;
+
+    // VUID-vkCmdSetVertexInputEXT-vertexBindingDescriptionCount-04791
+    // VUID-VkVertexInputBindingDescription2EXT-binding-04796
+    // Binding numbers are unique, so checking the highest one also checks the count.
+    if let Some(&binding) = bindings.keys().max() {
+        if binding >= properties.max_vertex_input_bindings {
+            return Err(SetDynamicStateError::MaxVertexInputBindingsExceeded {
+                provided: binding + 1,
+                max: properties.max_vertex_input_bindings,
             });
         }
+    }
 
-        // VUID-vkCmdSetViewportWithCount-viewportCount-03395
-        if !self.device().enabled_features().multi_viewport && viewports.len() > 1 {
-            return Err(SetDynamicStateError::RequirementNotMet {
-                required_for: "`viewports.len()` is greater than `1`",
-                requires_one_of: RequiresOneOf {
-                    features: &["multi_viewport"],
-                    ..Default::default()
-                },
+    for (&binding, binding_desc) in bindings {
+        let &VertexInputBindingDescription { stride, input_rate } = binding_desc;
+
+        // VUID-VkVertexInputBindingDescription2EXT-stride-04797
+        if stride > properties.max_vertex_input_binding_stride {
+            return Err(SetDynamicStateError::MaxVertexInputBindingStrideExceeded {
+                binding,
+                provided: stride,
+                max: properties.max_vertex_input_binding_stride,
             });
         }
 
-        Ok(())
+        match input_rate {
+            VertexInputRate::Instance { divisor } if divisor != 1 => {
+                // VUID-VkVertexInputBindingDescription2EXT-divisor-04799
+                if !device
+                    .enabled_features()
+                    .vertex_attribute_instance_rate_divisor
+                {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`vertex_input_state.bindings` has an element where \
+                            `input_rate` is `VertexInputRate::Instance`, where `divisor` is not \
+                            `1`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["vertex_attribute_instance_rate_divisor"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkVertexInputBindingDescription2EXT-divisor-04798
+                if divisor == 0
+                    && !device
+                        .enabled_features()
+                        .vertex_attribute_instance_rate_zero_divisor
+                {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`vertex_input_state.bindings` has an element where \
+                            `input_rate` is `VertexInputRate::Instance`, where `divisor` is `0`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["vertex_attribute_instance_rate_zero_divisor"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkVertexInputBindingDescription2EXT-divisor-06226
+                if divisor > properties.max_vertex_attrib_divisor.unwrap() {
+                    return Err(SetDynamicStateError::MaxVertexAttribDivisorExceeded {
+                        binding,
+                        provided: divisor,
+                        max: properties.max_vertex_attrib_divisor.unwrap(),
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // VUID-vkCmdSetVertexInputEXT-vertexAttributeDescriptionCount-04792
+    // VUID-VkVertexInputAttributeDescription2EXT-location-06228
+    // Locations are unique, so checking the highest one also checks the count.
+    if let Some(&location) = attributes.keys().max() {
+        if location >= properties.max_vertex_input_attributes {
+            return Err(SetDynamicStateError::MaxVertexInputAttributesExceeded {
+                provided: location + 1,
+                max: properties.max_vertex_input_attributes,
+            });
+        }
+    }
+
+    for (&location, attribute_desc) in attributes {
+        let &VertexInputAttributeDescription {
+            binding,
+            format,
+            offset,
+        } = attribute_desc;
+
+        // VUID-VkVertexInputAttributeDescription2EXT-format-parameter
+        format.validate_device(device)?;
+
+        // VUID-vkCmdSetVertexInputEXT-binding-04793
+        if !bindings.contains_key(&binding) {
+            return Err(SetDynamicStateError::VertexInputAttributeInvalidBinding {
+                location,
+                binding,
+            });
+        }
+
+        // VUID-VkVertexInputAttributeDescription2EXT-offset-06229
+        if offset > properties.max_vertex_input_attribute_offset {
+            return Err(
+                SetDynamicStateError::MaxVertexInputAttributeOffsetExceeded {
+                    location,
+                    provided: offset,
+                    max: properties.max_vertex_input_attribute_offset,
+                },
+            );
+        }
+
+        // Use unchecked, because all validation has been done above.
+        let format_features = unsafe {
+            device
+                .physical_device()
+                .format_properties_unchecked(format)
+                .buffer_features
+        };
+
+        // VUID-VkVertexInputAttributeDescription2EXT-format-04805
+        if !format_features.intersects(FormatFeatures::VERTEX_BUFFER) {
+            return Err(
+                SetDynamicStateError::VertexInputAttributeUnsupportedFormat { location, format },
+            );
+        }
     }
+
+    Ok(())
 }
 
 impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdSetAlphaToCoverageEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_coverage_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_alpha_to_coverage_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_alpha_to_coverage_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.alpha_to_coverage_enable = Some(enable);
+    }
+
+    /// Calls `vkCmdSetAlphaToOneEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_one_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_alpha_to_one_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_alpha_to_one_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.alpha_to_one_enable = Some(enable);
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -1745,6 +3142,76 @@ impl SyncCommandBufferBuilder {
         self.current_state.blend_constants = Some(constants);
     }
 
+    /// Calls `vkCmdSetColorBlendEnableEXT` on the builder.
+    pub unsafe fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: impl IntoIterator<Item = bool>,
+    ) {
+        struct Cmd {
+            first_attachment: u32,
+            enables: SmallVec<[bool; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_color_blend_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_blend_enable(self.first_attachment, self.enables.iter().copied());
+            }
+        }
+
+        let enables: SmallVec<[bool; 4]> = enables.into_iter().collect();
+
+        for (num, &enable) in enables.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.current_state.color_blend_enable.insert(num, enable);
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_attachment,
+            enables,
+        }));
+    }
+
+    /// Calls `vkCmdSetColorBlendEquationEXT` on the builder.
+    pub unsafe fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: impl IntoIterator<Item = AttachmentBlend>,
+    ) {
+        struct Cmd {
+            first_attachment: u32,
+            equations: SmallVec<[AttachmentBlend; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_color_blend_equation"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_blend_equation(self.first_attachment, self.equations.iter().copied());
+            }
+        }
+
+        let equations: SmallVec<[AttachmentBlend; 4]> = equations.into_iter().collect();
+
+        for (num, &equation) in equations.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.current_state
+                .color_blend_equation
+                .insert(num, equation);
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_attachment,
+            equations,
+        }));
+    }
+
     /// Calls `vkCmdSetColorWriteEnableEXT` on the builder.
     ///
     /// If the list is empty then the command is automatically ignored.
@@ -1773,6 +3240,46 @@ impl SyncCommandBufferBuilder {
         }));
     }
 
+    /// Calls `vkCmdSetColorWriteMaskEXT` on the builder.
+    pub unsafe fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: impl IntoIterator<Item = ColorComponents>,
+    ) {
+        struct Cmd {
+            first_attachment: u32,
+            color_write_masks: SmallVec<[ColorComponents; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_color_write_mask"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_write_mask(
+                    self.first_attachment,
+                    self.color_write_masks.iter().copied(),
+                );
+            }
+        }
+
+        let color_write_masks: SmallVec<[ColorComponents; 4]> =
+            color_write_masks.into_iter().collect();
+
+        for (num, &color_write_mask) in color_write_masks.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.current_state
+                .color_write_mask
+                .insert(num, color_write_mask);
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_attachment,
+            color_write_masks,
+        }));
+    }
+
     /// Calls `vkCmdSetCullModeEXT` on the builder.
     #[inline]
     pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
@@ -1890,6 +3397,27 @@ impl SyncCommandBufferBuilder {
         self.current_state.depth_bounds_test_enable = Some(enable);
     }
 
+    /// Calls `vkCmdSetDepthClampEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_clamp_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_depth_clamp_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_clamp_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.depth_clamp_enable = Some(enable);
+    }
+
     /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
     #[inline]
     pub unsafe fn set_depth_compare_op(&mut self, compare_op: CompareOp) {
@@ -1989,6 +3517,38 @@ impl SyncCommandBufferBuilder {
         }));
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        struct Cmd {
+            fragment_size: [u32; 2],
+            combiner_ops: [FragmentShadingRateCombinerOp; 2],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_fragment_shading_rate"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_fragment_shading_rate(self.fragment_size, self.combiner_ops);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            fragment_size,
+            combiner_ops,
+        }));
+        self.current_state.fragment_shading_rate = Some(FragmentShadingRate {
+            fragment_size,
+            combiner_ops,
+        });
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2010,6 +3570,32 @@ impl SyncCommandBufferBuilder {
         self.current_state.front_face = Some(face);
     }
 
+    /// Calls `vkCmdSetLineRasterizationModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) {
+        struct Cmd {
+            line_rasterization_mode: LineRasterizationMode,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_line_rasterization_mode"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_line_rasterization_mode(self.line_rasterization_mode);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            line_rasterization_mode,
+        }));
+        self.current_state.line_rasterization_mode = Some(line_rasterization_mode);
+    }
+
     /// Calls `vkCmdSetLineStippleEXT` on the builder.
     #[inline]
     pub unsafe fn set_line_stipple(&mut self, factor: u32, pattern: u16) {
@@ -2032,6 +3618,27 @@ impl SyncCommandBufferBuilder {
         self.current_state.line_stipple = Some(LineStipple { factor, pattern });
     }
 
+    /// Calls `vkCmdSetLineStippleEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_line_stipple_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_line_stipple_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_line_stipple_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.line_stipple_enable = Some(enable);
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -2062,16 +3669,37 @@ impl SyncCommandBufferBuilder {
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "set_logic_op"
+                "set_logic_op"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_logic_op(self.logic_op);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { logic_op }));
+        self.current_state.logic_op = Some(logic_op);
+    }
+
+    /// Calls `vkCmdSetLogicOpEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_logic_op_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_logic_op_enable"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.set_logic_op(self.logic_op);
+                out.set_logic_op_enable(self.enable);
             }
         }
 
-        self.commands.push(Box::new(Cmd { logic_op }));
-        self.current_state.logic_op = Some(logic_op);
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.logic_op_enable = Some(enable);
     }
 
     /// Calls `vkCmdSetPatchControlPointsEXT` on the builder.
@@ -2095,6 +3723,27 @@ impl SyncCommandBufferBuilder {
         self.current_state.patch_control_points = Some(num);
     }
 
+    /// Calls `vkCmdSetPolygonModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) {
+        struct Cmd {
+            polygon_mode: PolygonMode,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_polygon_mode"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_polygon_mode(self.polygon_mode);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { polygon_mode }));
+        self.current_state.polygon_mode = Some(polygon_mode);
+    }
+
     /// Calls `vkCmdSetPrimitiveRestartEnableEXT` on the builder.
     #[inline]
     pub unsafe fn set_primitive_restart_enable(&mut self, enable: bool) {
@@ -2137,6 +3786,52 @@ impl SyncCommandBufferBuilder {
         self.current_state.primitive_topology = Some(topology);
     }
 
+    /// Calls `vkCmdSetRasterizationSamplesEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_rasterization_samples(&mut self, rasterization_samples: SampleCount) {
+        struct Cmd {
+            rasterization_samples: SampleCount,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_rasterization_samples"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_rasterization_samples(self.rasterization_samples);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            rasterization_samples,
+        }));
+        self.current_state.rasterization_samples = Some(rasterization_samples);
+    }
+
+    /// Calls `vkCmdSetRasterizationStreamEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_rasterization_stream(&mut self, rasterization_stream: u32) {
+        struct Cmd {
+            rasterization_stream: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_rasterization_stream"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_rasterization_stream(self.rasterization_stream);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            rasterization_stream,
+        }));
+        self.current_state.rasterization_stream = Some(rasterization_stream);
+    }
+
     /// Calls `vkCmdSetRasterizerDiscardEnableEXT` on the builder.
     #[inline]
     pub unsafe fn set_rasterizer_discard_enable(&mut self, enable: bool) {
@@ -2158,6 +3853,31 @@ impl SyncCommandBufferBuilder {
         self.current_state.rasterizer_discard_enable = Some(enable);
     }
 
+    /// Calls `vkCmdSetSampleMaskEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_sample_mask(&mut self, samples: SampleCount, sample_mask: [u32; 2]) {
+        struct Cmd {
+            samples: SampleCount,
+            sample_mask: [u32; 2],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_sample_mask"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_sample_mask(self.samples, self.sample_mask);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            samples,
+            sample_mask,
+        }));
+        self.current_state.sample_mask = Some(sample_mask);
+    }
+
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
     pub unsafe fn set_stencil_compare_mask(&mut self, faces: StencilFaces, compare_mask: u32) {
@@ -2462,9 +4182,46 @@ impl SyncCommandBufferBuilder {
             viewports: Mutex::new(viewports),
         }));
     }
+
+    /// Calls `vkCmdSetVertexInputEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_vertex_input(&mut self, vertex_input_state: VertexInputState) {
+        struct Cmd {
+            vertex_input_state: VertexInputState,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_vertex_input"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_vertex_input(&self.vertex_input_state);
+            }
+        }
+
+        self.current_state.vertex_input = Some(vertex_input_state.clone());
+        self.commands.push(Box::new(Cmd { vertex_input_state }));
+    }
 }
 
 impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdSetAlphaToCoverageEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_coverage_enable(&mut self, enable: bool) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_coverage_enable_ext)(self.handle, enable.into());
+    }
+
+    /// Calls `vkCmdSetAlphaToOneEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_one_enable(&mut self, enable: bool) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_one_enable_ext)(self.handle, enable.into());
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -2472,6 +4229,58 @@ impl UnsafeCommandBufferBuilder {
         (fns.v1_0.cmd_set_blend_constants)(self.handle, &constants);
     }
 
+    /// Calls `vkCmdSetColorBlendEnableEXT` on the builder.
+    ///
+    /// If the list is empty then the command is automatically ignored.
+    pub unsafe fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: impl IntoIterator<Item = bool>,
+    ) {
+        let enables_vk = enables
+            .into_iter()
+            .map(ash::vk::Bool32::from)
+            .collect::<SmallVec<[_; 4]>>();
+        if enables_vk.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_color_blend_enable_ext)(
+            self.handle,
+            first_attachment,
+            enables_vk.len() as u32,
+            enables_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdSetColorBlendEquationEXT` on the builder.
+    ///
+    /// If the list is empty then the command is automatically ignored.
+    pub unsafe fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: impl IntoIterator<Item = AttachmentBlend>,
+    ) {
+        let equations_vk = equations
+            .into_iter()
+            .map(ash::vk::ColorBlendEquationEXT::from)
+            .collect::<SmallVec<[_; 4]>>();
+        if equations_vk.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_color_blend_equation_ext)(
+            self.handle,
+            first_attachment,
+            equations_vk.len() as u32,
+            equations_vk.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdSetColorWriteEnableEXT` on the builder.
     ///
     /// If the list is empty then the command is automatically ignored.
@@ -2494,6 +4303,31 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdSetColorWriteMaskEXT` on the builder.
+    ///
+    /// If the list is empty then the command is automatically ignored.
+    pub unsafe fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: impl IntoIterator<Item = ColorComponents>,
+    ) {
+        let color_write_masks_vk = color_write_masks
+            .into_iter()
+            .map(ash::vk::ColorComponentFlags::from)
+            .collect::<SmallVec<[_; 4]>>();
+        if color_write_masks_vk.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_color_write_mask_ext)(
+            self.handle,
+            first_attachment,
+            color_write_masks_vk.len() as u32,
+            color_write_masks_vk.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdSetCullModeEXT` on the builder.
     #[inline]
     pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
@@ -2549,6 +4383,14 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetDepthClampEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_clamp_enable(&mut self, enable: bool) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_depth_clamp_enable_ext)(self.handle, enable.into());
+    }
+
     /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
     #[inline]
     pub unsafe fn set_depth_compare_op(&mut self, compare_op: CompareOp) {
@@ -2632,6 +4474,26 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        let fns = self.device.fns();
+        let fragment_size = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops: [ash::vk::FragmentShadingRateCombinerOpKHR; 2] =
+            [combiner_ops[0].into(), combiner_ops[1].into()];
+
+        debug_assert!(self.device.enabled_extensions().khr_fragment_shading_rate);
+        (fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr)(self.handle, &fragment_size, &combiner_ops);
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2645,6 +4507,19 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetLineRasterizationModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_line_rasterization_mode_ext)(
+            self.handle, line_rasterization_mode.into()
+        );
+    }
+
     /// Calls `vkCmdSetLineStippleEXT` on the builder.
     #[inline]
     pub unsafe fn set_line_stipple(&mut self, factor: u32, pattern: u16) {
@@ -2653,6 +4528,14 @@ impl UnsafeCommandBufferBuilder {
         (fns.ext_line_rasterization.cmd_set_line_stipple_ext)(self.handle, factor, pattern);
     }
 
+    /// Calls `vkCmdSetLineStippleEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_line_stipple_enable(&mut self, enable: bool) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_line_stipple_enable_ext)(self.handle, enable.into());
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -2674,6 +4557,13 @@ impl UnsafeCommandBufferBuilder {
         (fns.ext_extended_dynamic_state2.cmd_set_logic_op_ext)(self.handle, logic_op.into());
     }
 
+    /// Calls `vkCmdSetLogicOpEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_logic_op_enable(&mut self, enable: bool) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_logic_op_enable_ext)(self.handle, enable.into());
+    }
+
     /// Calls `vkCmdSetPatchControlPointsEXT` on the builder.
     #[inline]
     pub unsafe fn set_patch_control_points(&mut self, num: u32) {
@@ -2683,6 +4573,16 @@ impl UnsafeCommandBufferBuilder {
             .cmd_set_patch_control_points_ext)(self.handle, num);
     }
 
+    /// Calls `vkCmdSetPolygonModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+            self.handle,
+            polygon_mode.into(),
+        );
+    }
+
     /// Calls `vkCmdSetPrimitiveRestartEnableEXT` on the builder.
     #[inline]
     pub unsafe fn set_primitive_restart_enable(&mut self, enable: bool) {
@@ -2711,6 +4611,22 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetRasterizationSamplesEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_rasterization_samples(&mut self, rasterization_samples: SampleCount) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_rasterization_samples_ext)(self.handle, rasterization_samples.into());
+    }
+
+    /// Calls `vkCmdSetRasterizationStreamEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_rasterization_stream(&mut self, rasterization_stream: u32) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_rasterization_stream_ext)(self.handle, rasterization_stream);
+    }
+
     /// Calls `vkCmdSetRasterizerDiscardEnableEXT` on the builder.
     #[inline]
     pub unsafe fn set_rasterizer_discard_enable(&mut self, enable: bool) {
@@ -2725,6 +4641,17 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetSampleMaskEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_sample_mask(&mut self, samples: SampleCount, sample_mask: [u32; 2]) {
+        let fns = self.device.fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_sample_mask_ext)(
+            self.handle,
+            samples.into(),
+            sample_mask.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
     pub unsafe fn set_stencil_compare_mask(&mut self, face_mask: StencilFaces, compare_mask: u32) {
@@ -2908,6 +4835,62 @@ impl UnsafeCommandBufferBuilder {
             );
         }
     }
+
+    /// Calls `vkCmdSetVertexInputEXT` on the builder.
+    pub unsafe fn set_vertex_input(&mut self, vertex_input_state: &VertexInputState) {
+        let (bindings_vk, attributes_vk) = vertex_input_state_to_vk(vertex_input_state);
+
+        let fns = self.device.fns();
+        (fns.ext_vertex_input_dynamic_state.cmd_set_vertex_input_ext)(
+            self.handle,
+            bindings_vk.len() as u32,
+            bindings_vk.as_ptr(),
+            attributes_vk.len() as u32,
+            attributes_vk.as_ptr(),
+        );
+    }
+}
+
+pub(in super::super) fn vertex_input_state_to_vk(
+    vertex_input_state: &VertexInputState,
+) -> (
+    SmallVec<[ash::vk::VertexInputBindingDescription2EXT; 8]>,
+    SmallVec<[ash::vk::VertexInputAttributeDescription2EXT; 8]>,
+) {
+    let bindings_vk = vertex_input_state
+        .bindings
+        .iter()
+        .map(|(&binding, binding_desc)| {
+            let divisor = match binding_desc.input_rate {
+                VertexInputRate::Vertex => 1,
+                VertexInputRate::Instance { divisor } => divisor,
+            };
+
+            ash::vk::VertexInputBindingDescription2EXT {
+                binding,
+                stride: binding_desc.stride,
+                input_rate: binding_desc.input_rate.into(),
+                divisor,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let attributes_vk = vertex_input_state
+        .attributes
+        .iter()
+        .map(
+            |(&location, attribute_desc)| ash::vk::VertexInputAttributeDescription2EXT {
+                location,
+                binding: attribute_desc.binding,
+                format: attribute_desc.format.into(),
+                offset: attribute_desc.offset,
+                ..Default::default()
+            },
+        )
+        .collect();
+
+    (bindings_vk, attributes_vk)
 }
 
 #[derive(Clone, Debug)]
@@ -2918,9 +4901,24 @@ pub(in super::super) enum SetDynamicStateError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// A provided blend equation uses an advanced blend operation.
+    ColorBlendEquationAdvancedBlendOp { attachment: u32 },
+
     /// The provided `factor` is not between 1 and 256 inclusive.
     FactorOutOfRange,
 
+    /// A component of the provided fragment size is not 1, 2 or 4.
+    FragmentShadingRateInvalidFragmentSize { fragment_size: [u32; 2] },
+
+    /// A provided combiner operation is not `Keep` or `Replace`, but the
+    /// [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    /// device property is `false`.
+    FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+
+    /// The [`max_color_attachments`](crate::device::Properties::max_color_attachments)
+    /// limit has been exceeded.
+    MaxColorAttachmentsExceeded { provided: u32, max: u32 },
+
     /// The [`max_discard_rectangles`](crate::device::Properties::max_discard_rectangles)
     /// limit has been exceeded.
     MaxDiscardRectanglesExceeded { provided: u32, max: u32 },
@@ -2929,6 +4927,42 @@ pub(in super::super) enum SetDynamicStateError {
     /// limit has been exceeded.
     MaxTessellationPatchSizeExceeded { provided: u32, max: u32 },
 
+    /// The [`max_transform_feedback_streams`](crate::device::Properties::max_transform_feedback_streams)
+    /// limit has been exceeded.
+    MaxTransformFeedbackStreamsExceeded { provided: u32, max: u32 },
+
+    /// The [`max_vertex_attrib_divisor`](crate::device::Properties::max_vertex_attrib_divisor)
+    /// limit has been exceeded.
+    MaxVertexAttribDivisorExceeded {
+        binding: u32,
+        provided: u32,
+        max: u32,
+    },
+
+    /// The [`max_vertex_input_attribute_offset`](crate::device::Properties::max_vertex_input_attribute_offset)
+    /// limit has been exceeded.
+    MaxVertexInputAttributeOffsetExceeded {
+        location: u32,
+        provided: u32,
+        max: u32,
+    },
+
+    /// The [`max_vertex_input_attributes`](crate::device::Properties::max_vertex_input_attributes)
+    /// limit has been exceeded.
+    MaxVertexInputAttributesExceeded { provided: u32, max: u32 },
+
+    /// The [`max_vertex_input_binding_stride`](crate::device::Properties::max_vertex_input_binding_stride)
+    /// limit has been exceeded.
+    MaxVertexInputBindingStrideExceeded {
+        binding: u32,
+        provided: u32,
+        max: u32,
+    },
+
+    /// The [`max_vertex_input_bindings`](crate::device::Properties::max_vertex_input_bindings)
+    /// limit has been exceeded.
+    MaxVertexInputBindingsExceeded { provided: u32, max: u32 },
+
     /// The [`max_viewports`](crate::device::Properties::max_viewports)
     /// limit has been exceeded.
     MaxViewportsExceeded { provided: u32, max: u32 },
@@ -2946,6 +4980,17 @@ pub(in super::super) enum SetDynamicStateError {
     /// The currently bound pipeline contains this state as internally fixed state, which cannot be
     /// overridden with dynamic state.
     PipelineHasFixedState,
+
+    /// A non-zero rasterization stream was provided, but the
+    /// [`transform_feedback_rasterization_stream_select`](crate::device::Properties::transform_feedback_rasterization_stream_select)
+    /// device property is `false`.
+    TransformFeedbackRasterizationStreamSelectNotSupported,
+
+    /// A vertex attribute refers to a binding that is not in the vertex input state.
+    VertexInputAttributeInvalidBinding { location: u32, binding: u32 },
+
+    /// The format of a vertex attribute does not support being used as a vertex buffer.
+    VertexInputAttributeUnsupportedFormat { location: u32, format: Format },
 }
 
 impl Error for SetDynamicStateError {}
@@ -2961,10 +5006,28 @@ impl Display for SetDynamicStateError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::ColorBlendEquationAdvancedBlendOp { attachment } => write!(
+                f,
+                "the blend equation provided for attachment {} uses an advanced blend operation",
+                attachment,
+            ),
             Self::FactorOutOfRange => write!(
                 f,
                 "the provided `factor` is not between 1 and 256 inclusive",
             ),
+            Self::FragmentShadingRateInvalidFragmentSize { fragment_size } => write!(
+                f,
+                "a component of the provided fragment size {:?} is not 1, 2 or 4",
+                fragment_size,
+            ),
+            Self::FragmentShadingRateNonTrivialCombinerOpsNotSupported => write!(
+                f,
+                "a provided combiner operation is not `Keep` or `Replace`, but the \
+                `fragment_shading_rate_non_trivial_combiner_ops` device property is `false`",
+            ),
+            Self::MaxColorAttachmentsExceeded { .. } => {
+                write!(f, "the `max_color_attachments` limit has been exceeded")
+            }
             Self::MaxDiscardRectanglesExceeded { .. } => {
                 write!(f, "the `max_discard_rectangles` limit has been exceeded")
             }
@@ -2972,6 +5035,28 @@ impl Display for SetDynamicStateError {
                 f,
                 "the `max_tessellation_patch_size` limit has been exceeded",
             ),
+            Self::MaxTransformFeedbackStreamsExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_streams` limit has been exceeded",
+            ),
+            Self::MaxVertexAttribDivisorExceeded { .. } => {
+                write!(f, "the `max_vertex_attrib_divisor` limit has been exceeded")
+            }
+            Self::MaxVertexInputAttributeOffsetExceeded { .. } => write!(
+                f,
+                "the `max_vertex_input_attribute_offset` limit has been exceeded",
+            ),
+            Self::MaxVertexInputAttributesExceeded { .. } => write!(
+                f,
+                "the `max_vertex_input_attributes` limit has been exceeded",
+            ),
+            Self::MaxVertexInputBindingStrideExceeded { .. } => write!(
+                f,
+                "the `max_vertex_input_binding_stride` limit has been exceeded",
+            ),
+            Self::MaxVertexInputBindingsExceeded { .. } => {
+                write!(f, "the `max_vertex_input_bindings` limit has been exceeded")
+            }
             Self::MaxViewportsExceeded { .. } => {
                 write!(f, "the `max_viewports` limit has been exceeded")
             }
@@ -2992,6 +5077,23 @@ impl Display for SetDynamicStateError {
                 "the currently bound pipeline contains this state as internally fixed state, which \
                 cannot be overridden with dynamic state",
             ),
+            Self::TransformFeedbackRasterizationStreamSelectNotSupported => write!(
+                f,
+                "a non-zero rasterization stream was provided, but the \
+                `transform_feedback_rasterization_stream_select` device property is `false`",
+            ),
+            Self::VertexInputAttributeInvalidBinding { location, binding } => write!(
+                f,
+                "the vertex attribute at location {} refers to binding {}, which is not in the \
+                vertex input state",
+                location, binding,
+            ),
+            Self::VertexInputAttributeUnsupportedFormat { location, format } => write!(
+                f,
+                "the format {:?} of the vertex attribute at location {} does not support being \
+                used as a vertex buffer",
+                format, location,
+            ),
         }
     }
 }
@@ -3004,3 +5106,177 @@ impl From<RequirementNotMet> for SetDynamicStateError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_buffer::{allocator::StandardCommandBufferAllocator, CommandBufferUsage};
+
+    #[test]
+    fn set_vertex_input_missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.validate_set_vertex_input(&VertexInputState::new()),
+            Err(SetDynamicStateError::RequirementNotMet { .. }),
+        ));
+    }
+
+    #[test]
+    fn vertex_input_limits() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let properties = device.physical_device().properties();
+        let max_vertex_input_bindings = properties.max_vertex_input_bindings;
+        let max_vertex_input_attributes = properties.max_vertex_input_attributes;
+
+        let binding = VertexInputBindingDescription {
+            stride: 16,
+            input_rate: VertexInputRate::Vertex,
+        };
+        let attribute = |binding| VertexInputAttributeDescription {
+            binding,
+            format: Format::R32G32B32A32_SFLOAT,
+            offset: 0,
+        };
+
+        let vertex_input_state = VertexInputState::new()
+            .binding(max_vertex_input_bindings - 1, binding.clone())
+            .attribute(
+                max_vertex_input_attributes - 1,
+                attribute(max_vertex_input_bindings - 1),
+            );
+        assert!(validate_vertex_input_state(&device, &vertex_input_state).is_ok());
+
+        let vertex_input_state = VertexInputState::new()
+            .binding(max_vertex_input_bindings, binding.clone())
+            .attribute(0, attribute(max_vertex_input_bindings));
+        assert!(matches!(
+            validate_vertex_input_state(&device, &vertex_input_state),
+            Err(SetDynamicStateError::MaxVertexInputBindingsExceeded { provided, max })
+                if provided == max_vertex_input_bindings + 1 && max == max_vertex_input_bindings,
+        ));
+
+        let vertex_input_state = VertexInputState::new()
+            .binding(0, binding.clone())
+            .attribute(max_vertex_input_attributes, attribute(0));
+        assert!(matches!(
+            validate_vertex_input_state(&device, &vertex_input_state),
+            Err(SetDynamicStateError::MaxVertexInputAttributesExceeded { provided, max })
+                if provided == max_vertex_input_attributes + 1
+                    && max == max_vertex_input_attributes,
+        ));
+
+        let vertex_input_state = VertexInputState::new()
+            .binding(0, binding)
+            .attribute(0, attribute(1));
+        assert!(matches!(
+            validate_vertex_input_state(&device, &vertex_input_state),
+            Err(SetDynamicStateError::VertexInputAttributeInvalidBinding {
+                location: 0,
+                binding: 1,
+            }),
+        ));
+    }
+
+    #[test]
+    fn set_fragment_shading_rate_combiner_ops() {
+        use FragmentShadingRateCombinerOp::{Keep, Replace};
+
+        let (device, queue) =
+            gfx_dev_and_queue!(pipeline_fragment_shading_rate; khr_fragment_shading_rate);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(cbb
+            .validate_set_fragment_shading_rate([2, 2], [Keep, Keep])
+            .is_ok());
+        assert!(matches!(
+            cbb.validate_set_fragment_shading_rate([3, 1], [Keep, Keep]),
+            Err(
+                SetDynamicStateError::FragmentShadingRateInvalidFragmentSize {
+                    fragment_size: [3, 1],
+                }
+            ),
+        ));
+
+        // Each combiner op requires the feature for the shading rate it combines with.
+        assert!(matches!(
+            cbb.validate_set_fragment_shading_rate([1, 1], [Replace, Keep]),
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf {
+                    features: ["primitive_fragment_shading_rate"],
+                    ..
+                },
+                ..
+            }),
+        ));
+        assert!(matches!(
+            cbb.validate_set_fragment_shading_rate([1, 1], [Keep, Replace]),
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf {
+                    features: ["attachment_fragment_shading_rate"],
+                    ..
+                },
+                ..
+            }),
+        ));
+    }
+
+    #[test]
+    fn set_fragment_shading_rate_non_trivial_combiner_ops() {
+        use FragmentShadingRateCombinerOp::{Keep, Max, Min, Mul, Replace};
+
+        let (device, queue) = gfx_dev_and_queue!(
+            pipeline_fragment_shading_rate,
+            primitive_fragment_shading_rate,
+            attachment_fragment_shading_rate;
+            khr_fragment_shading_rate
+        );
+
+        let non_trivial_combiner_ops = device
+            .physical_device()
+            .properties()
+            .fragment_shading_rate_non_trivial_combiner_ops
+            .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(cbb
+            .validate_set_fragment_shading_rate([1, 1], [Replace, Replace])
+            .is_ok());
+
+        for combiner_ops in [[Min, Keep], [Keep, Max], [Mul, Replace]] {
+            let result = cbb.validate_set_fragment_shading_rate([1, 1], combiner_ops);
+
+            if non_trivial_combiner_ops {
+                assert!(result.is_ok());
+            } else {
+                assert!(matches!(
+                    result,
+                    Err(SetDynamicStateError::FragmentShadingRateNonTrivialCombinerOpsNotSupported),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,1280 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BlitImageInfo, CopyError,
+        ImageBlit, PipelineExecutionError,
+    },
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, DescriptorSetCreationError,
+        PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::{Device, DeviceOwned, QueueFlags},
+    format::{Format, FormatFeatures},
+    image::{
+        view::{ImageView, ImageViewCreateInfo, ImageViewCreationError},
+        ImageAccess, ImageAspects, ImageDimensions, ImageSubresourceLayers, ImageSubresourceRange,
+        ImageUsage, ImageViewType, SampleCount,
+    },
+    pipeline::{
+        compute::ComputePipelineCreationError, ComputePipeline, Pipeline, PipelineBindPoint,
+    },
+    sampler::Filter,
+    shader::{
+        spirv::{
+            AddressingModel, BuiltIn, Capability, Decoration, Dim, ExecutionMode, ExecutionModel,
+            FunctionControl, Id, ImageFormat, ImageOperands, Instruction, MemoryModel,
+            SelectionControl, Spirv, StorageClass,
+        },
+        ShaderCreationError, ShaderModule, ShaderScalarType,
+    },
+    Version,
+};
+use ahash::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    num::NonZeroU64,
+    sync::{Arc, Weak},
+};
+
+/// # Commands for generating mipmaps.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Fills mip levels 1 and above of all array layers of `image`, by downsampling each level
+    /// from the one before it, starting with the current contents of mip level 0.
+    ///
+    /// If the format features of the image include `BLIT_SRC`, `BLIT_DST` and
+    /// `SAMPLED_IMAGE_FILTER_LINEAR`, each level is blitted from the previous one with a linear
+    /// filter. The image must have the `transfer_src` and `transfer_dst` usages, and the queue
+    /// family must support graphics operations.
+    ///
+    /// Otherwise, a compute shader is used, which writes each texel as the average of a 2×2 block
+    /// of texels in the previous level. This requires:
+    /// - a two-dimensional, single-sampled image with a color format;
+    /// - the `sampled` and `storage` usages on the image, and the `SAMPLED_IMAGE` and
+    ///   `STORAGE_IMAGE` format features;
+    /// - a format that can be declared on a storage image in SPIR-V;
+    /// - a queue family that supports compute operations.
+    ///
+    /// The compute pipeline of the fallback is created once per device and format, and shared by
+    /// all command buffers that use it, for as long as any of them is alive.
+    ///
+    /// # Bound state
+    ///
+    /// The compute fallback binds its own compute pipeline and descriptor set 0, and doesn't
+    /// restore what was bound before. Afterwards, no compute pipeline or compute descriptor sets
+    /// are considered bound, so they must be bound again before the next
+    /// [`dispatch`](Self::dispatch) or [`dispatch_indirect`](Self::dispatch_indirect), which
+    /// return an error otherwise. Graphics state is not affected.
+    pub fn generate_mipmaps(
+        &mut self,
+        image: Arc<dyn ImageAccess>,
+    ) -> Result<&mut Self, GenerateMipmapsError> {
+        let use_blit = self.validate_generate_mipmaps(&image)?;
+
+        if image.mip_levels() == 1 {
+            return Ok(self);
+        }
+
+        if use_blit {
+            self.generate_mipmaps_blit(image)?;
+        } else {
+            self.generate_mipmaps_compute(image)?;
+        }
+
+        Ok(self)
+    }
+
+    // Returns whether the mipmaps are generated with blits rather than the compute fallback.
+    fn validate_generate_mipmaps(
+        &self,
+        image: &Arc<dyn ImageAccess>,
+    ) -> Result<bool, GenerateMipmapsError> {
+        if self.render_pass_state.is_some() {
+            return Err(GenerateMipmapsError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-VkBlitImageInfo2-commonparent
+        // VUID-VkWriteDescriptorSet-commonparent
+        assert_eq!(self.device(), image.device());
+
+        let format = image.format();
+        let format_features = image.format_features();
+        let usage = image.usage();
+
+        if format_features.contains(
+            FormatFeatures::BLIT_SRC
+                | FormatFeatures::BLIT_DST
+                | FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR,
+        ) {
+            if !usage.intersects(ImageUsage::TRANSFER_SRC) {
+                return Err(GenerateMipmapsError::MissingUsage {
+                    usage: "transfer_src",
+                });
+            }
+
+            if !usage.intersects(ImageUsage::TRANSFER_DST) {
+                return Err(GenerateMipmapsError::MissingUsage {
+                    usage: "transfer_dst",
+                });
+            }
+
+            if !self
+                .queue_family_properties()
+                .queue_flags
+                .intersects(QueueFlags::GRAPHICS)
+            {
+                return Err(GenerateMipmapsError::NotSupportedByQueueFamily);
+            }
+
+            return Ok(true);
+        }
+
+        if !matches!(image.dimensions(), ImageDimensions::Dim2d { .. })
+            || image.samples() != SampleCount::Sample1
+            || format.aspects() != ImageAspects::COLOR
+        {
+            return Err(GenerateMipmapsError::ImageNotSupported);
+        }
+
+        if !format_features.contains(FormatFeatures::SAMPLED_IMAGE | FormatFeatures::STORAGE_IMAGE)
+            || storage_image_format(format).is_none()
+        {
+            return Err(GenerateMipmapsError::FormatNotSupported { format });
+        }
+
+        if !usage.intersects(ImageUsage::SAMPLED) {
+            return Err(GenerateMipmapsError::MissingUsage { usage: "sampled" });
+        }
+
+        if !usage.intersects(ImageUsage::STORAGE) {
+            return Err(GenerateMipmapsError::MissingUsage { usage: "storage" });
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(GenerateMipmapsError::NotSupportedByQueueFamily);
+        }
+
+        Ok(false)
+    }
+
+    fn generate_mipmaps_blit(&mut self, image: Arc<dyn ImageAccess>) -> Result<(), CopyError> {
+        let dimensions = image.dimensions();
+
+        for level in 1..image.mip_levels() {
+            let src_size = dimensions
+                .mip_level_dimensions(level - 1)
+                .unwrap()
+                .width_height_depth();
+            let dst_size = dimensions
+                .mip_level_dimensions(level)
+                .unwrap()
+                .width_height_depth();
+
+            self.blit_image(BlitImageInfo {
+                regions: [ImageBlit {
+                    src_subresource: ImageSubresourceLayers {
+                        mip_level: level - 1,
+                        ..image.subresource_layers()
+                    },
+                    src_offsets: [[0; 3], src_size],
+                    dst_subresource: ImageSubresourceLayers {
+                        mip_level: level,
+                        ..image.subresource_layers()
+                    },
+                    dst_offsets: [[0; 3], dst_size],
+                    ..Default::default()
+                }]
+                .into(),
+                filter: Filter::Linear,
+                ..BlitImageInfo::images(image.clone(), image.clone())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn generate_mipmaps_compute(
+        &mut self,
+        image: Arc<dyn ImageAccess>,
+    ) -> Result<(), GenerateMipmapsError> {
+        let format = image.format();
+        let dimensions = image.dimensions();
+        let array_layers = dimensions.array_layers();
+
+        let downsample = match self
+            .downsample_pipelines
+            .iter()
+            .find(|downsample| downsample.format == format)
+        {
+            Some(downsample) => downsample.clone(),
+            None => {
+                let downsample = DownsamplePipeline::get_or_create(image.device(), format)?;
+                self.downsample_pipelines.push(downsample.clone());
+                downsample
+            }
+        };
+        let pipeline = &downsample.pipeline;
+
+        // The views must implement `ImageViewAbstract`, which `dyn ImageAccess` itself can't.
+        let image = Arc::new(image);
+        let level_view = |level: u32| {
+            ImageView::new(
+                image.clone(),
+                ImageViewCreateInfo {
+                    view_type: ImageViewType::Dim2dArray,
+                    format: Some(format),
+                    subresource_range: ImageSubresourceRange {
+                        aspects: ImageAspects::COLOR,
+                        mip_levels: level..level + 1,
+                        array_layers: 0..array_layers,
+                    },
+                    ..Default::default()
+                },
+            )
+        };
+
+        self.bind_pipeline_compute(pipeline.clone());
+
+        let result = (1..image.mip_levels()).try_for_each(|level| {
+            let descriptor_set = PersistentDescriptorSet::new(
+                &downsample.descriptor_set_allocator,
+                pipeline.layout().set_layouts()[0].clone(),
+                [
+                    WriteDescriptorSet::image_view(0, level_view(level - 1)?),
+                    WriteDescriptorSet::image_view(1, level_view(level)?),
+                ],
+            )?;
+            let [width, height, _] = dimensions
+                .mip_level_dimensions(level)
+                .unwrap()
+                .width_height_depth();
+
+            self.bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                descriptor_set,
+            )
+            .dispatch([
+                width.div_ceil(LOCAL_SIZE),
+                height.div_ceil(LOCAL_SIZE),
+                array_layers,
+            ])?;
+
+            Ok(())
+        });
+
+        // Forget the bindings of the fallback, so that a later dispatch can't use them by
+        // accident.
+        let current_state = &mut self.inner.current_state;
+        current_state.pipeline_compute = None;
+        current_state
+            .descriptor_sets
+            .remove(&PipelineBindPoint::Compute);
+
+        result
+    }
+}
+
+const LOCAL_SIZE: u32 = 8;
+
+/// The compute pipeline of the mipmap generation fallback for one format, and the allocator of its
+/// descriptor sets.
+///
+/// Command buffers that use it keep it alive, and it is shared through a cache by all command
+/// buffers of the same device.
+pub(in crate::command_buffer) struct DownsamplePipeline {
+    format: Format,
+    pipeline: Arc<ComputePipeline>,
+    descriptor_set_allocator: StandardDescriptorSetAllocator,
+}
+
+// Holding strong references here would keep the devices alive forever, because the pipelines hold
+// a reference to their device.
+static DOWNSAMPLE_PIPELINES: Lazy<Mutex<HashMap<(NonZeroU64, Format), Weak<DownsamplePipeline>>>> =
+    Lazy::new(Default::default);
+
+impl DownsamplePipeline {
+    fn get_or_create(
+        device: &Arc<Device>,
+        format: Format,
+    ) -> Result<Arc<Self>, GenerateMipmapsError> {
+        let mut pipelines = DOWNSAMPLE_PIPELINES.lock();
+
+        if let Some(downsample) = pipelines
+            .get(&(device.id(), format))
+            .and_then(Weak::upgrade)
+        {
+            return Ok(downsample);
+        }
+
+        let words = downsample_shader(
+            format.type_color().unwrap().into(),
+            storage_image_format(format).unwrap(),
+        );
+        let shader = unsafe { ShaderModule::from_words(device.clone(), &words)? };
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            shader.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )?;
+        let downsample = Arc::new(DownsamplePipeline {
+            format,
+            pipeline,
+            descriptor_set_allocator: StandardDescriptorSetAllocator::new(device.clone()),
+        });
+
+        pipelines.retain(|_, downsample| downsample.strong_count() > 0);
+        pipelines.insert((device.id(), format), Arc::downgrade(&downsample));
+
+        Ok(downsample)
+    }
+}
+
+/// Returns the format to declare in SPIR-V for a storage image with the given format. This is
+/// the reverse of the mapping from `ImageFormat` to `Format`.
+fn storage_image_format(format: Format) -> Option<ImageFormat> {
+    Some(match format {
+        Format::R32G32B32A32_SFLOAT => ImageFormat::Rgba32f,
+        Format::R16G16B16A16_SFLOAT => ImageFormat::Rgba16f,
+        Format::R32_SFLOAT => ImageFormat::R32f,
+        Format::R8G8B8A8_UNORM => ImageFormat::Rgba8,
+        Format::R8G8B8A8_SNORM => ImageFormat::Rgba8Snorm,
+        Format::R32G32_SFLOAT => ImageFormat::Rg32f,
+        Format::R16G16_SFLOAT => ImageFormat::Rg16f,
+        Format::B10G11R11_UFLOAT_PACK32 => ImageFormat::R11fG11fB10f,
+        Format::R16_SFLOAT => ImageFormat::R16f,
+        Format::R16G16B16A16_UNORM => ImageFormat::Rgba16,
+        Format::A2B10G10R10_UNORM_PACK32 => ImageFormat::Rgb10A2,
+        Format::R16G16_UNORM => ImageFormat::Rg16,
+        Format::R8G8_UNORM => ImageFormat::Rg8,
+        Format::R16_UNORM => ImageFormat::R16,
+        Format::R8_UNORM => ImageFormat::R8,
+        Format::R16G16B16A16_SNORM => ImageFormat::Rgba16Snorm,
+        Format::R16G16_SNORM => ImageFormat::Rg16Snorm,
+        Format::R8G8_SNORM => ImageFormat::Rg8Snorm,
+        Format::R16_SNORM => ImageFormat::R16Snorm,
+        Format::R8_SNORM => ImageFormat::R8Snorm,
+        Format::R32G32B32A32_SINT => ImageFormat::Rgba32i,
+        Format::R16G16B16A16_SINT => ImageFormat::Rgba16i,
+        Format::R8G8B8A8_SINT => ImageFormat::Rgba8i,
+        Format::R32_SINT => ImageFormat::R32i,
+        Format::R32G32_SINT => ImageFormat::Rg32i,
+        Format::R16G16_SINT => ImageFormat::Rg16i,
+        Format::R8G8_SINT => ImageFormat::Rg8i,
+        Format::R16_SINT => ImageFormat::R16i,
+        Format::R8_SINT => ImageFormat::R8i,
+        Format::R32G32B32A32_UINT => ImageFormat::Rgba32ui,
+        Format::R16G16B16A16_UINT => ImageFormat::Rgba16ui,
+        Format::R8G8B8A8_UINT => ImageFormat::Rgba8ui,
+        Format::R32_UINT => ImageFormat::R32ui,
+        Format::A2B10G10R10_UINT_PACK32 => ImageFormat::Rgb10a2ui,
+        Format::R32G32_UINT => ImageFormat::Rg32ui,
+        Format::R16G16_UINT => ImageFormat::Rg16ui,
+        Format::R8G8_UINT => ImageFormat::Rg8ui,
+        Format::R16_UINT => ImageFormat::R16ui,
+        Format::R8_UINT => ImageFormat::R8ui,
+        _ => return None,
+    })
+}
+
+/// Builds the compute shader of the mipmap generation fallback.
+///
+/// Binding 0 is a 2D array view of the previous mip level, as a sampled image that is read with
+/// `OpImageFetch`. Binding 1 is a 2D array view of the level to write, as a storage image of
+/// `image_format`. Each invocation writes one texel, and the Z coordinate of the invocation is the
+/// array layer. Texel coordinates in the previous level are clamped to its extent, so that levels
+/// with an odd width or height are handled.
+///
+/// The shader is the equivalent of the following GLSL, with integer formats averaged in a way
+/// that can't overflow:
+///
+/// ```glsl
+/// layout(local_size_x = 8, local_size_y = 8) in;
+/// layout(binding = 0) uniform texture2DArray src;
+/// layout(binding = 1) uniform writeonly image2DArray dst;
+///
+/// void main() {
+///     ivec3 id = ivec3(gl_GlobalInvocationID);
+///     if (any(greaterThanEqual(id.xy, imageSize(dst).xy))) return;
+///     ivec2 src_max = textureSize(src, 0).xy - 1;
+///     ivec2 base = id.xy * 2;
+///     vec4 sum = texelFetch(src, ivec3(base, id.z), 0)
+///         + texelFetch(src, ivec3(min(base + ivec2(1, 0), src_max), id.z), 0)
+///         + texelFetch(src, ivec3(min(base + ivec2(0, 1), src_max), id.z), 0)
+///         + texelFetch(src, ivec3(min(base + ivec2(1, 1), src_max), id.z), 0);
+///     imageStore(dst, id, sum * 0.25);
+/// }
+/// ```
+fn downsample_shader(scalar_type: ShaderScalarType, image_format: ImageFormat) -> Vec<u32> {
+    // GLSL.std.450 extended instruction.
+    const SMIN: u32 = 39;
+
+    let mut bound = 1;
+    let mut new_id = || {
+        let id = Id::new(bound);
+        bound += 1;
+        id
+    };
+
+    let glsl = new_id();
+    let main = new_id();
+    let global_id = new_id();
+    let src = new_id();
+    let dst = new_id();
+
+    let ty_void = new_id();
+    let ty_main = new_id();
+    let ty_bool = new_id();
+    let ty_bvec2 = new_id();
+    let ty_uint = new_id();
+    let ty_uvec3 = new_id();
+    let ty_int = new_id();
+    let ty_ivec2 = new_id();
+    let ty_ivec3 = new_id();
+    let ty_scalar = new_id();
+    let ty_texel = new_id();
+    let ty_src = new_id();
+    let ty_dst = new_id();
+    let ty_ptr_uvec3 = new_id();
+    let ty_ptr_src = new_id();
+    let ty_ptr_dst = new_id();
+
+    let int_0 = new_id();
+    let int_1 = new_id();
+    let int_2 = new_id();
+    let ivec2_1_0 = new_id();
+    let ivec2_0_1 = new_id();
+    let ivec2_1_1 = new_id();
+    let ivec2_2_2 = new_id();
+
+    // Vulkan supports these capabilities on all devices.
+    let mut instructions = vec![
+        Instruction::Capability {
+            capability: Capability::Shader,
+        },
+        Instruction::Capability {
+            capability: Capability::ImageQuery,
+        },
+    ];
+
+    if !matches!(
+        image_format,
+        ImageFormat::Rgba32f
+            | ImageFormat::Rgba16f
+            | ImageFormat::R32f
+            | ImageFormat::Rgba8
+            | ImageFormat::Rgba8Snorm
+            | ImageFormat::Rgba32i
+            | ImageFormat::Rgba16i
+            | ImageFormat::Rgba8i
+            | ImageFormat::R32i
+            | ImageFormat::Rgba32ui
+            | ImageFormat::Rgba16ui
+            | ImageFormat::Rgba8ui
+            | ImageFormat::R32ui
+    ) {
+        instructions.push(Instruction::Capability {
+            capability: Capability::StorageImageExtendedFormats,
+        });
+    }
+
+    instructions.extend([
+        Instruction::ExtInstImport {
+            result_id: glsl,
+            name: "GLSL.std.450".into(),
+        },
+        Instruction::MemoryModel {
+            addressing_model: AddressingModel::Logical,
+            memory_model: MemoryModel::GLSL450,
+        },
+        Instruction::EntryPoint {
+            execution_model: ExecutionModel::GLCompute,
+            entry_point: main,
+            name: "main".into(),
+            interface: vec![global_id],
+        },
+        Instruction::ExecutionMode {
+            entry_point: main,
+            mode: ExecutionMode::LocalSize {
+                x_size: LOCAL_SIZE,
+                y_size: LOCAL_SIZE,
+                z_size: 1,
+            },
+        },
+        Instruction::Decorate {
+            target: global_id,
+            decoration: Decoration::BuiltIn {
+                built_in: BuiltIn::GlobalInvocationId,
+            },
+        },
+        Instruction::Decorate {
+            target: src,
+            decoration: Decoration::DescriptorSet { descriptor_set: 0 },
+        },
+        Instruction::Decorate {
+            target: src,
+            decoration: Decoration::Binding { binding_point: 0 },
+        },
+        Instruction::Decorate {
+            target: dst,
+            decoration: Decoration::DescriptorSet { descriptor_set: 0 },
+        },
+        Instruction::Decorate {
+            target: dst,
+            decoration: Decoration::Binding { binding_point: 1 },
+        },
+        Instruction::Decorate {
+            target: dst,
+            decoration: Decoration::NonReadable,
+        },
+        Instruction::TypeVoid { result_id: ty_void },
+        Instruction::TypeFunction {
+            result_id: ty_main,
+            return_type: ty_void,
+            parameter_types: vec![],
+        },
+        Instruction::TypeBool { result_id: ty_bool },
+        Instruction::TypeVector {
+            result_id: ty_bvec2,
+            component_type: ty_bool,
+            component_count: 2,
+        },
+        Instruction::TypeInt {
+            result_id: ty_uint,
+            width: 32,
+            signedness: 0,
+        },
+        Instruction::TypeVector {
+            result_id: ty_uvec3,
+            component_type: ty_uint,
+            component_count: 3,
+        },
+        Instruction::TypeInt {
+            result_id: ty_int,
+            width: 32,
+            signedness: 1,
+        },
+        Instruction::TypeVector {
+            result_id: ty_ivec2,
+            component_type: ty_int,
+            component_count: 2,
+        },
+        Instruction::TypeVector {
+            result_id: ty_ivec3,
+            component_type: ty_int,
+            component_count: 3,
+        },
+        match scalar_type {
+            ShaderScalarType::Float => Instruction::TypeFloat {
+                result_id: ty_scalar,
+                width: 32,
+            },
+            ShaderScalarType::Sint => Instruction::TypeInt {
+                result_id: ty_scalar,
+                width: 32,
+                signedness: 1,
+            },
+            ShaderScalarType::Uint => Instruction::TypeInt {
+                result_id: ty_scalar,
+                width: 32,
+                signedness: 0,
+            },
+        },
+        Instruction::TypeVector {
+            result_id: ty_texel,
+            component_type: ty_scalar,
+            component_count: 4,
+        },
+        Instruction::TypeImage {
+            result_id: ty_src,
+            sampled_type: ty_scalar,
+            dim: Dim::Dim2D,
+            depth: 0,
+            arrayed: 1,
+            ms: 0,
+            sampled: 1,
+            image_format: ImageFormat::Unknown,
+            access_qualifier: None,
+        },
+        Instruction::TypeImage {
+            result_id: ty_dst,
+            sampled_type: ty_scalar,
+            dim: Dim::Dim2D,
+            depth: 0,
+            arrayed: 1,
+            ms: 0,
+            sampled: 2,
+            image_format,
+            access_qualifier: None,
+        },
+        Instruction::TypePointer {
+            result_id: ty_ptr_uvec3,
+            storage_class: StorageClass::Input,
+            ty: ty_uvec3,
+        },
+        Instruction::TypePointer {
+            result_id: ty_ptr_src,
+            storage_class: StorageClass::UniformConstant,
+            ty: ty_src,
+        },
+        Instruction::TypePointer {
+            result_id: ty_ptr_dst,
+            storage_class: StorageClass::UniformConstant,
+            ty: ty_dst,
+        },
+        Instruction::Constant {
+            result_type_id: ty_int,
+            result_id: int_0,
+            value: vec![0],
+        },
+        Instruction::Constant {
+            result_type_id: ty_int,
+            result_id: int_1,
+            value: vec![1],
+        },
+        Instruction::Constant {
+            result_type_id: ty_int,
+            result_id: int_2,
+            value: vec![2],
+        },
+        Instruction::ConstantComposite {
+            result_type_id: ty_ivec2,
+            result_id: ivec2_1_0,
+            constituents: vec![int_1, int_0],
+        },
+        Instruction::ConstantComposite {
+            result_type_id: ty_ivec2,
+            result_id: ivec2_0_1,
+            constituents: vec![int_0, int_1],
+        },
+        Instruction::ConstantComposite {
+            result_type_id: ty_ivec2,
+            result_id: ivec2_1_1,
+            constituents: vec![int_1, int_1],
+        },
+        Instruction::ConstantComposite {
+            result_type_id: ty_ivec2,
+            result_id: ivec2_2_2,
+            constituents: vec![int_2, int_2],
+        },
+    ]);
+
+    // Constants that are used to average the texels.
+    let quarter = new_id();
+    let texel_2 = new_id();
+    let texel_3 = new_id();
+
+    match scalar_type {
+        ShaderScalarType::Float => instructions.push(Instruction::Constant {
+            result_type_id: ty_scalar,
+            result_id: quarter,
+            value: vec![0.25f32.to_bits()],
+        }),
+        ShaderScalarType::Sint | ShaderScalarType::Uint => {
+            let scalar_2 = new_id();
+            let scalar_3 = new_id();
+
+            instructions.extend([
+                Instruction::Constant {
+                    result_type_id: ty_scalar,
+                    result_id: scalar_2,
+                    value: vec![2],
+                },
+                Instruction::Constant {
+                    result_type_id: ty_scalar,
+                    result_id: scalar_3,
+                    value: vec![3],
+                },
+                Instruction::ConstantComposite {
+                    result_type_id: ty_texel,
+                    result_id: texel_2,
+                    constituents: vec![scalar_2; 4],
+                },
+                Instruction::ConstantComposite {
+                    result_type_id: ty_texel,
+                    result_id: texel_3,
+                    constituents: vec![scalar_3; 4],
+                },
+            ]);
+        }
+    }
+
+    let entry_label = new_id();
+    let body_label = new_id();
+    let merge_label = new_id();
+    let global_id_value = new_id();
+    let id = new_id();
+    let id_xy = new_id();
+    let layer = new_id();
+    let dst_image = new_id();
+    let dst_size = new_id();
+    let dst_size_xy = new_id();
+    let out_of_bounds = new_id();
+    let any_out_of_bounds = new_id();
+    let src_image = new_id();
+    let src_size = new_id();
+    let src_size_xy = new_id();
+    let src_max = new_id();
+    let base = new_id();
+
+    instructions.extend([
+        Instruction::Variable {
+            result_type_id: ty_ptr_uvec3,
+            result_id: global_id,
+            storage_class: StorageClass::Input,
+            initializer: None,
+        },
+        Instruction::Variable {
+            result_type_id: ty_ptr_src,
+            result_id: src,
+            storage_class: StorageClass::UniformConstant,
+            initializer: None,
+        },
+        Instruction::Variable {
+            result_type_id: ty_ptr_dst,
+            result_id: dst,
+            storage_class: StorageClass::UniformConstant,
+            initializer: None,
+        },
+        Instruction::Function {
+            result_type_id: ty_void,
+            result_id: main,
+            function_control: FunctionControl::default(),
+            function_type: ty_main,
+        },
+        Instruction::Label {
+            result_id: entry_label,
+        },
+        Instruction::Load {
+            result_type_id: ty_uvec3,
+            result_id: global_id_value,
+            pointer: global_id,
+            memory_access: None,
+        },
+        Instruction::Bitcast {
+            result_type_id: ty_ivec3,
+            result_id: id,
+            operand: global_id_value,
+        },
+        Instruction::VectorShuffle {
+            result_type_id: ty_ivec2,
+            result_id: id_xy,
+            vector_1: id,
+            vector_2: id,
+            components: vec![0, 1],
+        },
+        Instruction::CompositeExtract {
+            result_type_id: ty_int,
+            result_id: layer,
+            composite: id,
+            indexes: vec![2],
+        },
+        Instruction::Load {
+            result_type_id: ty_dst,
+            result_id: dst_image,
+            pointer: dst,
+            memory_access: None,
+        },
+        Instruction::ImageQuerySize {
+            result_type_id: ty_ivec3,
+            result_id: dst_size,
+            image: dst_image,
+        },
+        Instruction::VectorShuffle {
+            result_type_id: ty_ivec2,
+            result_id: dst_size_xy,
+            vector_1: dst_size,
+            vector_2: dst_size,
+            components: vec![0, 1],
+        },
+        Instruction::SGreaterThanEqual {
+            result_type_id: ty_bvec2,
+            result_id: out_of_bounds,
+            operand1: id_xy,
+            operand2: dst_size_xy,
+        },
+        Instruction::Any {
+            result_type_id: ty_bool,
+            result_id: any_out_of_bounds,
+            vector: out_of_bounds,
+        },
+        Instruction::SelectionMerge {
+            merge_block: merge_label,
+            selection_control: SelectionControl::default(),
+        },
+        Instruction::BranchConditional {
+            condition: any_out_of_bounds,
+            true_label: merge_label,
+            false_label: body_label,
+            branch_weights: vec![],
+        },
+        Instruction::Label {
+            result_id: body_label,
+        },
+        Instruction::Load {
+            result_type_id: ty_src,
+            result_id: src_image,
+            pointer: src,
+            memory_access: None,
+        },
+        Instruction::ImageQuerySizeLod {
+            result_type_id: ty_ivec3,
+            result_id: src_size,
+            image: src_image,
+            level_of_detail: int_0,
+        },
+        Instruction::VectorShuffle {
+            result_type_id: ty_ivec2,
+            result_id: src_size_xy,
+            vector_1: src_size,
+            vector_2: src_size,
+            components: vec![0, 1],
+        },
+        Instruction::ISub {
+            result_type_id: ty_ivec2,
+            result_id: src_max,
+            operand1: src_size_xy,
+            operand2: ivec2_1_1,
+        },
+        Instruction::IMul {
+            result_type_id: ty_ivec2,
+            result_id: base,
+            operand1: id_xy,
+            operand2: ivec2_2_2,
+        },
+    ]);
+
+    // Fetch the 2×2 block of texels.
+    let mut texels = Vec::with_capacity(4);
+
+    for offset in [None, Some(ivec2_1_0), Some(ivec2_0_1), Some(ivec2_1_1)] {
+        let coord_xy = match offset {
+            Some(offset) => {
+                let offset_coord = new_id();
+                let clamped_coord = new_id();
+
+                instructions.extend([
+                    Instruction::IAdd {
+                        result_type_id: ty_ivec2,
+                        result_id: offset_coord,
+                        operand1: base,
+                        operand2: offset,
+                    },
+                    Instruction::ExtInst {
+                        result_type_id: ty_ivec2,
+                        result_id: clamped_coord,
+                        set: glsl,
+                        instruction: SMIN,
+                        operands: vec![offset_coord, src_max],
+                    },
+                ]);
+
+                clamped_coord
+            }
+            None => base,
+        };
+        let coord = new_id();
+        let texel = new_id();
+
+        instructions.extend([
+            Instruction::CompositeConstruct {
+                result_type_id: ty_ivec3,
+                result_id: coord,
+                constituents: vec![coord_xy, layer],
+            },
+            Instruction::ImageFetch {
+                result_type_id: ty_texel,
+                result_id: texel,
+                image: src_image,
+                coordinate: coord,
+                image_operands: Some(ImageOperands {
+                    lod: Some(int_0),
+                    ..Default::default()
+                }),
+            },
+        ]);
+
+        texels.push(texel);
+    }
+
+    let average = match scalar_type {
+        ShaderScalarType::Float => {
+            let sum = sum(&mut instructions, &mut new_id, ty_texel, &texels, true);
+            let average = new_id();
+
+            instructions.push(Instruction::VectorTimesScalar {
+                result_type_id: ty_texel,
+                result_id: average,
+                vector: sum,
+                scalar: quarter,
+            });
+
+            average
+        }
+        ShaderScalarType::Sint | ShaderScalarType::Uint => {
+            // (a + b + c + d) / 4 == (a >> 2) + (b >> 2) + (c >> 2) + (d >> 2)
+            //     + (((a & 3) + (b & 3) + (c & 3) + (d & 3)) >> 2)
+            let signed = matches!(scalar_type, ShaderScalarType::Sint);
+            let shift = |instructions: &mut Vec<Instruction>, base, result_id| {
+                instructions.push(if signed {
+                    Instruction::ShiftRightArithmetic {
+                        result_type_id: ty_texel,
+                        result_id,
+                        base,
+                        shift: texel_2,
+                    }
+                } else {
+                    Instruction::ShiftRightLogical {
+                        result_type_id: ty_texel,
+                        result_id,
+                        base,
+                        shift: texel_2,
+                    }
+                });
+            };
+
+            let mut quotients = Vec::with_capacity(4);
+            let mut remainders = Vec::with_capacity(4);
+
+            for &texel in &texels {
+                let quotient = new_id();
+                let remainder = new_id();
+                shift(&mut instructions, texel, quotient);
+                instructions.push(Instruction::BitwiseAnd {
+                    result_type_id: ty_texel,
+                    result_id: remainder,
+                    operand1: texel,
+                    operand2: texel_3,
+                });
+                quotients.push(quotient);
+                remainders.push(remainder);
+            }
+
+            let remainder_sum = sum(&mut instructions, &mut new_id, ty_texel, &remainders, false);
+            let remainder_quotient = new_id();
+            shift(&mut instructions, remainder_sum, remainder_quotient);
+            quotients.push(remainder_quotient);
+
+            sum(&mut instructions, &mut new_id, ty_texel, &quotients, false)
+        }
+    };
+
+    instructions.extend([
+        Instruction::ImageWrite {
+            image: dst_image,
+            coordinate: id,
+            texel: average,
+            image_operands: None,
+        },
+        Instruction::Branch {
+            target_label: merge_label,
+        },
+        Instruction::Label {
+            result_id: merge_label,
+        },
+        Instruction::Return,
+        Instruction::FunctionEnd,
+    ]);
+
+    Spirv::from_instructions(Version::V1_0, bound, instructions)
+        .unwrap()
+        .to_words()
+}
+
+/// Appends instructions that add together `operands`, and returns the `Id` of the result.
+fn sum(
+    instructions: &mut Vec<Instruction>,
+    new_id: &mut impl FnMut() -> Id,
+    result_type_id: Id,
+    operands: &[Id],
+    float: bool,
+) -> Id {
+    operands[1..].iter().fold(operands[0], |sum, &operand| {
+        let result_id = new_id();
+        instructions.push(if float {
+            Instruction::FAdd {
+                result_type_id,
+                result_id,
+                operand1: sum,
+                operand2: operand,
+            }
+        } else {
+            Instruction::IAdd {
+                result_type_id,
+                result_id,
+                operand1: sum,
+                operand2: operand,
+            }
+        });
+
+        result_id
+    })
+}
+
+/// Error that can happen when recording a mipmap generation command.
+#[derive(Clone, Debug)]
+pub enum GenerateMipmapsError {
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The format of the image supports neither linear blitting, nor downsampling with the
+    /// compute shader fallback.
+    FormatNotSupported { format: Format },
+
+    /// The format of the image does not support linear blitting, and the image is not a
+    /// single-sampled two-dimensional color image, so the compute shader fallback can't be used
+    /// either.
+    ImageNotSupported,
+
+    /// The image is missing a usage that is needed to generate its mipmaps.
+    MissingUsage { usage: &'static str },
+
+    /// Recording a blit command failed.
+    CopyError(CopyError),
+
+    /// Creating the image views of the compute shader fallback failed.
+    ImageViewCreationError(ImageViewCreationError),
+
+    /// Creating the shader module of the compute shader fallback failed.
+    ShaderCreationError(ShaderCreationError),
+
+    /// Creating the compute pipeline of the compute shader fallback failed.
+    ComputePipelineCreationError(ComputePipelineCreationError),
+
+    /// Creating a descriptor set of the compute shader fallback failed.
+    DescriptorSetCreationError(DescriptorSetCreationError),
+
+    /// Recording a dispatch command failed.
+    PipelineExecutionError(PipelineExecutionError),
+}
+
+impl Error for GenerateMipmapsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CopyError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::ShaderCreationError(err) => Some(err),
+            Self::ComputePipelineCreationError(err) => Some(err),
+            Self::DescriptorSetCreationError(err) => Some(err),
+            Self::PipelineExecutionError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GenerateMipmapsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::FormatNotSupported { format } => write!(
+                f,
+                "the format {:?} supports neither linear blitting, nor downsampling with a \
+                compute shader",
+                format,
+            ),
+            Self::ImageNotSupported => write!(
+                f,
+                "the format of the image does not support linear blitting, and the image is not a \
+                single-sampled two-dimensional color image",
+            ),
+            Self::MissingUsage { usage } => write!(
+                f,
+                "the image is missing the `{}` usage, which is needed to generate its mipmaps",
+                usage,
+            ),
+            Self::CopyError(_) => write!(f, "recording a blit command failed"),
+            Self::ImageViewCreationError(_) => write!(f, "creating an image view failed"),
+            Self::ShaderCreationError(_) => write!(f, "creating the shader module failed"),
+            Self::ComputePipelineCreationError(_) => {
+                write!(f, "creating the compute pipeline failed")
+            }
+            Self::DescriptorSetCreationError(_) => write!(f, "creating a descriptor set failed"),
+            Self::PipelineExecutionError(_) => write!(f, "recording a dispatch command failed"),
+        }
+    }
+}
+
+impl From<CopyError> for GenerateMipmapsError {
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for GenerateMipmapsError {
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<ShaderCreationError> for GenerateMipmapsError {
+    fn from(err: ShaderCreationError) -> Self {
+        Self::ShaderCreationError(err)
+    }
+}
+
+impl From<ComputePipelineCreationError> for GenerateMipmapsError {
+    fn from(err: ComputePipelineCreationError) -> Self {
+        Self::ComputePipelineCreationError(err)
+    }
+}
+
+impl From<DescriptorSetCreationError> for GenerateMipmapsError {
+    fn from(err: DescriptorSetCreationError) -> Self {
+        Self::DescriptorSetCreationError(err)
+    }
+}
+
+impl From<PipelineExecutionError> for GenerateMipmapsError {
+    fn from(err: PipelineExecutionError) -> Self {
+        Self::PipelineExecutionError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command_buffer::{allocator::StandardCommandBufferAllocator, CommandBufferUsage},
+        image::{
+            sys::{Image, ImageCreateInfo, TrackedImage},
+            AttachmentImage,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn downsample_shader_is_valid() {
+        for (scalar_type, image_format) in [
+            (ShaderScalarType::Float, ImageFormat::Rgba16f),
+            (ShaderScalarType::Float, ImageFormat::R8),
+            (ShaderScalarType::Sint, ImageFormat::Rgba32i),
+            (ShaderScalarType::Uint, ImageFormat::Rg8ui),
+        ] {
+            let words = downsample_shader(scalar_type, image_format);
+            let spirv = Spirv::new(&words).unwrap();
+
+            assert_eq!(spirv.to_words(), words);
+            assert_eq!(spirv.iter_entry_point().count(), 1);
+            assert_eq!(
+                spirv.iter_capability().any(|instruction| matches!(
+                    instruction,
+                    Instruction::Capability {
+                        capability: Capability::StorageImageExtendedFormats,
+                    }
+                )),
+                matches!(image_format, ImageFormat::R8 | ImageFormat::Rg8ui),
+            );
+        }
+    }
+
+    #[test]
+    fn downsample_pipeline_is_cached() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let first = DownsamplePipeline::get_or_create(&device, Format::R8G8B8A8_UNORM).unwrap();
+        let second = DownsamplePipeline::get_or_create(&device, Format::R8G8B8A8_UNORM).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other = DownsamplePipeline::get_or_create(&device, Format::R8G8B8A8_UINT).unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let image =
+            AttachmentImage::new(&memory_allocator, [32, 32], Format::R8G8B8A8_UNORM).unwrap();
+
+        // `R8G8B8A8_UNORM` supports linear blitting on all devices.
+        assert!(matches!(
+            cbb.generate_mipmaps(image),
+            Err(GenerateMipmapsError::MissingUsage {
+                usage: "transfer_src",
+            }),
+        ));
+    }
+
+    #[test]
+    fn compute_fallback_forgets_bindings() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Integer formats don't support linear filtering, so the compute fallback is used.
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let image = TrackedImage::new(
+            Image::new(
+                &memory_allocator,
+                ImageCreateInfo {
+                    dimensions: ImageDimensions::Dim2d {
+                        width: 32,
+                        height: 32,
+                        array_layers: 1,
+                    },
+                    format: Some(Format::R8G8B8A8_UINT),
+                    mip_levels: 6,
+                    usage: ImageUsage::SAMPLED | ImageUsage::STORAGE,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        );
+
+        cbb.generate_mipmaps(image).unwrap();
+
+        assert!(cbb.state().pipeline_compute().is_none());
+        assert!(cbb
+            .state()
+            .descriptor_set(PipelineBindPoint::Compute, 0)
+            .is_none());
+        assert!(matches!(
+            cbb.dispatch([1, 1, 1]),
+            Err(PipelineExecutionError::PipelineNotBound),
+        ));
+    }
+}
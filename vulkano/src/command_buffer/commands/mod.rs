@@ -9,11 +9,14 @@
 
 pub(super) mod bind_push;
 pub(super) mod clear;
+pub(super) mod conditional_rendering;
 pub(super) mod copy;
 pub(super) mod debug;
 pub(super) mod dynamic_state;
+pub(super) mod mipmaps;
 pub(super) mod pipeline;
 pub(super) mod query;
 pub(super) mod render_pass;
 pub(super) mod secondary;
 pub(super) mod sync;
+pub(super) mod transform_feedback;
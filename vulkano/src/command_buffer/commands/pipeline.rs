@@ -12,14 +12,17 @@ use crate::{
     command_buffer::{
         allocator::CommandBufferAllocator,
         auto::{RenderPassState, RenderPassStateType},
-        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        synced::{
+            Command, Resource, SetOrPush, SyncCommandBufferBuilder, SyncCommandBufferBuilderError,
+        },
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder, DispatchIndirectCommand, DrawIndexedIndirectCommand,
-        DrawIndirectCommand, ResourceInCommand, ResourceUseRef, SubpassContents,
+        DrawIndirectCommand, MultiDrawIndexedInfo, MultiDrawInfo, ResourceInCommand,
+        ResourceUseRef, SubpassContents,
     },
     descriptor_set::{layout::DescriptorType, DescriptorBindingResources},
     device::{DeviceOwned, QueueFlags},
-    format::{Format, FormatFeatures},
+    format::{Format, FormatFeatures, NumericType},
     image::{
         view::ImageViewType, ImageAccess, ImageAspects, ImageSubresourceRange, ImageViewAbstract,
         SampleCount,
@@ -27,8 +30,9 @@ use crate::{
     pipeline::{
         graphics::{
             input_assembly::{PrimitiveTopology, PrimitiveTopologyClass},
+            is_numeric_type_compatible,
             render_pass::PipelineRenderPassType,
-            vertex_input::VertexInputRate,
+            vertex_input::{VertexInputRate, VertexInputState},
         },
         DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineLayout,
     },
@@ -37,12 +41,14 @@ use crate::{
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
     DeviceSize, RequiresOneOf, VulkanObject,
 };
+use ahash::HashMap;
 use std::{
     cmp::min,
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
     mem::size_of,
     ops::Range,
+    ptr,
     sync::Arc,
 };
 
@@ -413,6 +419,222 @@ where
         Ok(())
     }
 
+    /// Perform multiple draw operations using a graphics pipeline, in a single command.
+    ///
+    /// One draw is performed for each element of `vertex_info`, which specifies the first vertex
+    /// and the number of vertices to draw. All draws share the same `instance_count` and
+    /// `first_instance`. This is equivalent to calling [`draw`](Self::draw) once for each
+    /// element, but the bound state is validated only once for the whole batch. The number of
+    /// draws must be less than the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex ranges of
+    /// each draw and the provided instance range must be in range of the bound vertex buffers.
+    pub fn draw_multi(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_multi(vertex_info, instance_count, first_instance)?;
+
+        unsafe {
+            self.inner
+                .draw_multi(vertex_info.into(), instance_count, first_instance)?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_multi(
+        &self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMultiEXT-None-04933
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_multi`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMultiEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMultiEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
+
+        self.validate_multi_draw_count(vertex_info.len())?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        // Only the draw that reaches furthest into the vertex buffers needs to be checked.
+        let vertices = vertex_info
+            .iter()
+            .max_by_key(|info| info.first_vertex as u64 + info.vertex_count as u64)
+            .map(|info| (info.first_vertex, info.vertex_count));
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            vertices,
+            Some((first_instance, instance_count)),
+        )?;
+
+        Ok(())
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline and an index buffer, in a single
+    /// command.
+    ///
+    /// One draw is performed for each element of `index_info`, which specifies the first index,
+    /// the number of indices and the vertex offset to draw with. All draws share the same
+    /// `instance_count` and `first_instance`. If `vertex_offset` is `Some`, it is used for all
+    /// draws instead of the per-draw vertex offsets. This is equivalent to calling
+    /// [`draw_indexed`](Self::draw_indexed) once for each element, but the bound state is
+    /// validated only once for the whole batch. The number of draws must be less than the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each draw must be
+    /// in range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
+    /// must be in range of the bound vertex buffers.
+    pub fn draw_multi_indexed(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_multi_indexed(
+            index_info,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        )?;
+
+        unsafe {
+            self.inner.draw_multi_indexed(
+                index_info.into(),
+                instance_count,
+                first_instance,
+                vertex_offset,
+            )?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_multi_indexed(
+        &self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        _vertex_offset: Option<i32>,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMultiIndexedEXT-None-04937
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_multi_indexed`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMultiIndexedEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMultiIndexedEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
+
+        self.validate_multi_draw_count(index_info.len())?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        // Only the draw that reaches furthest into the index buffer needs to be checked.
+        // VUID-vkCmdDrawMultiIndexedEXT-firstIndex-04938
+        let indices = index_info
+            .iter()
+            .max_by_key(|info| info.first_index as u64 + info.index_count as u64)
+            .map(|info| (info.first_index, info.index_count));
+        self.validate_index_buffer(indices)?;
+
+        Ok(())
+    }
+
+    fn validate_multi_draw_count(&self, draw_count: usize) -> Result<(), PipelineExecutionError> {
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_multi_draw_count
+            .unwrap_or(0);
+
+        // VUID-vkCmdDrawMultiEXT-drawCount-04934
+        // VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939
+        if draw_count >= max as usize {
+            return Err(PipelineExecutionError::MaxMultiDrawCountExceeded {
+                provided: draw_count as u32,
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Perform multiple draw operations using a graphics pipeline, using an index buffer.
     ///
     /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
@@ -537,7 +759,7 @@ where
         Ok(())
     }
 
-    fn validate_indirect_buffer(
+    pub(super) fn validate_indirect_buffer(
         &self,
         buffer: &Subbuffer<[u8]>,
     ) -> Result<(), PipelineExecutionError> {
@@ -559,7 +781,7 @@ where
         Ok(())
     }
 
-    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+    pub(super) fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
         &self,
         pipeline: &Pl,
     ) -> Result<(), PipelineExecutionError> {
@@ -567,6 +789,7 @@ where
             set_num: u32,
             binding_num: u32,
             binding_reqs: &DescriptorBindingRequirements,
+            partially_bound: bool,
             elements: &[Option<T>],
             mut extra_check: impl FnMut(u32, &T) -> Result<(), DescriptorResourceInvalidError>,
         ) -> Result<(), PipelineExecutionError> {
@@ -594,6 +817,9 @@ where
                 // VUID-vkCmdDispatch-None-02699
                 let element = match element {
                     Some(x) => x,
+                    // Descriptors of partially bound bindings only need to be valid if they are
+                    // dynamically used, which can't be known here.
+                    None if partially_bound => continue,
                     None => {
                         return Err(PipelineExecutionError::DescriptorResourceInvalid {
                             set_num,
@@ -638,17 +864,52 @@ where
             return Err(PipelineExecutionError::PipelineLayoutNotCompatible);
         }
 
+        // The predicate of the active conditional rendering block may be read by the device at
+        // any point until the block ends, so it must not be written to in the meantime.
+        let overlaps_predicate = |buffer: &Subbuffer<[u8]>, range: Range<DeviceSize>| {
+            self.conditional_rendering_state
+                .as_ref()
+                .map_or(false, |state| {
+                    let predicate = &state.predicate;
+                    buffer.buffer() == predicate.buffer()
+                        && buffer.offset() + range.start < predicate.offset() + predicate.size()
+                        && predicate.offset() < buffer.offset() + range.end
+                })
+        };
+
         for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
             let layout_binding =
                 &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
 
             let check_buffer =
-                |_index: u32, (_buffer, _range): &(Subbuffer<[u8]>, Range<DeviceSize>)| Ok(());
+                |index: u32, (buffer, range): &(Subbuffer<[u8]>, Range<DeviceSize>)| {
+                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                        .chain(binding_reqs.descriptors.get(&None))
+                    {
+                        if !desc_reqs.memory_write.is_empty()
+                            && overlaps_predicate(buffer, range.clone())
+                        {
+                            return Err(
+                                DescriptorResourceInvalidError::ConditionalRenderingPredicateWritten,
+                            );
+                        }
+                    }
+
+                    Ok(())
+                };
 
             let check_buffer_view = |index: u32, buffer_view: &Arc<BufferView>| {
                 for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
                     .chain(binding_reqs.descriptors.get(&None))
                 {
+                    if !desc_reqs.memory_write.is_empty()
+                        && overlaps_predicate(buffer_view.buffer(), buffer_view.range())
+                    {
+                        return Err(
+                            DescriptorResourceInvalidError::ConditionalRenderingPredicateWritten,
+                        );
+                    }
+
                     if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
                         // VUID-vkCmdDispatch-OpTypeImage-06423
                         if binding_reqs.image_format.is_none()
@@ -915,16 +1176,31 @@ where
 
             match binding_resources {
                 DescriptorBindingResources::None(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_none)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_none,
+                    )?;
                 }
                 DescriptorBindingResources::Buffer(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_buffer)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_buffer,
+                    )?;
                 }
                 DescriptorBindingResources::BufferView(elements) => {
                     validate_resources(
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_buffer_view,
                     )?;
@@ -934,6 +1210,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view,
                     )?;
@@ -943,6 +1220,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view_sampler,
                     )?;
@@ -952,17 +1230,32 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_sampler,
                     )?;
                 }
+                DescriptorBindingResources::InlineUniformBlock(elements) => {
+                    // The elements are the bytes of the block, which the shader accesses as a whole,
+                    // so the array size in the shader is not relevant here.
+                    if !layout_binding.partially_bound {
+                        if let Some(index) = elements.iter().position(Option::is_none) {
+                            return Err(PipelineExecutionError::DescriptorResourceInvalid {
+                                set_num,
+                                binding_num,
+                                index: index as u32,
+                                error: DescriptorResourceInvalidError::Missing,
+                            });
+                        }
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
-    fn validate_pipeline_push_constants(
+    pub(super) fn validate_pipeline_push_constants(
         &self,
         pipeline_layout: &PipelineLayout,
     ) -> Result<(), PipelineExecutionError> {
@@ -1002,7 +1295,7 @@ where
         Ok(())
     }
 
-    fn validate_pipeline_graphics_dynamic_state(
+    pub(super) fn validate_pipeline_graphics_dynamic_state(
         &self,
         pipeline: &GraphicsPipeline,
     ) -> Result<(), PipelineExecutionError> {
@@ -1109,7 +1402,12 @@ where
                     }
                 }
                 DynamicState::ExclusiveScissor => todo!(),
-                DynamicState::FragmentShadingRate => todo!(),
+                DynamicState::FragmentShadingRate => {
+                    // VUID?
+                    if current_state.fragment_shading_rate().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::FrontFace => {
                     // VUID?
                     if current_state.front_face().is_none() {
@@ -1328,7 +1626,14 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
-                DynamicState::VertexInput => todo!(),
+                DynamicState::VertexInput => {
+                    // VUID-vkCmdDraw-None-04914
+                    let vertex_input = current_state
+                        .vertex_input()
+                        .ok_or(PipelineExecutionError::DynamicStateNotSet { dynamic_state })?;
+
+                    validate_vertex_input_against_pipeline(pipeline, vertex_input)?;
+                }
                 DynamicState::VertexInputBindingStride => todo!(),
                 DynamicState::Viewport => {
                     for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
@@ -1384,44 +1689,120 @@ where
                     // vkCmdSetViewportWithCountEXT must be 1
                 }
                 DynamicState::ViewportWScaling => todo!(),
-                DynamicState::TessellationDomainOrigin => todo!(),
-                DynamicState::DepthClampEnable => todo!(),
-                DynamicState::PolygonMode => todo!(),
-                DynamicState::RasterizationSamples => todo!(),
-                DynamicState::SampleMask => todo!(),
-                DynamicState::AlphaToCoverageEnable => todo!(),
-                DynamicState::AlphaToOneEnable => todo!(),
-                DynamicState::LogicOpEnable => todo!(),
-                DynamicState::ColorBlendEnable => todo!(),
-                DynamicState::ColorBlendEquation => todo!(),
-                DynamicState::ColorWriteMask => todo!(),
-                DynamicState::RasterizationStream => todo!(),
-                DynamicState::ConservativeRasterizationMode => todo!(),
-                DynamicState::ExtraPrimitiveOverestimationSize => todo!(),
-                DynamicState::DepthClipEnable => todo!(),
-                DynamicState::SampleLocationsEnable => todo!(),
-                DynamicState::ColorBlendAdvanced => todo!(),
-                DynamicState::ProvokingVertexMode => todo!(),
-                DynamicState::LineRasterizationMode => todo!(),
-                DynamicState::LineStippleEnable => todo!(),
-                DynamicState::DepthClipNegativeOneToOne => todo!(),
-                DynamicState::ViewportWScalingEnable => todo!(),
-                DynamicState::ViewportSwizzle => todo!(),
-                DynamicState::CoverageToColorEnable => todo!(),
-                DynamicState::CoverageToColorLocation => todo!(),
-                DynamicState::CoverageModulationMode => todo!(),
-                DynamicState::CoverageModulationTableEnable => todo!(),
-                DynamicState::CoverageModulationTable => todo!(),
-                DynamicState::ShadingRateImageEnable => todo!(),
-                DynamicState::RepresentativeFragmentTestEnable => todo!(),
-                DynamicState::CoverageReductionMode => todo!(),
+                DynamicState::DepthClampEnable => {
+                    // VUID?
+                    if current_state.depth_clamp_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::PolygonMode => {
+                    // VUID?
+                    if current_state.polygon_mode().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::RasterizationSamples => {
+                    // VUID?
+                    if current_state.rasterization_samples().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::SampleMask => {
+                    // VUID?
+                    if current_state.sample_mask().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::AlphaToCoverageEnable => {
+                    // VUID?
+                    if current_state.alpha_to_coverage_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::AlphaToOneEnable => {
+                    // VUID?
+                    if current_state.alpha_to_one_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::LogicOpEnable => {
+                    // VUID?
+                    if current_state.logic_op_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::ColorBlendEnable => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        // VUID?
+                        if current_state.color_blend_enable(num).is_none() {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::ColorBlendEquation => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        // VUID?
+                        if current_state.color_blend_equation(num).is_none() {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::ColorWriteMask => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        // VUID?
+                        if current_state.color_write_mask(num).is_none() {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::RasterizationStream => {
+                    // VUID?
+                    if current_state.rasterization_stream().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::LineRasterizationMode => {
+                    // VUID?
+                    if current_state.line_rasterization_mode().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::LineStippleEnable => {
+                    // VUID?
+                    if current_state.line_stipple_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::TessellationDomainOrigin
+                | DynamicState::ConservativeRasterizationMode
+                | DynamicState::ExtraPrimitiveOverestimationSize
+                | DynamicState::DepthClipEnable
+                | DynamicState::SampleLocationsEnable
+                | DynamicState::ColorBlendAdvanced
+                | DynamicState::ProvokingVertexMode
+                | DynamicState::DepthClipNegativeOneToOne
+                | DynamicState::ViewportWScalingEnable
+                | DynamicState::ViewportSwizzle
+                | DynamicState::CoverageToColorEnable
+                | DynamicState::CoverageToColorLocation
+                | DynamicState::CoverageModulationMode
+                | DynamicState::CoverageModulationTableEnable
+                | DynamicState::CoverageModulationTable
+                | DynamicState::ShadingRateImageEnable
+                | DynamicState::RepresentativeFragmentTestEnable
+                | DynamicState::CoverageReductionMode => {
+                    // Vulkano doesn't support the pipeline state that these control, so
+                    // `GraphicsPipelineBuilder` never makes them dynamic and no bound pipeline can
+                    // require them.
+                    unreachable!()
+                }
             }
         }
 
         Ok(())
     }
 
-    fn validate_pipeline_graphics_render_pass(
+    pub(super) fn validate_pipeline_graphics_render_pass(
         &self,
         pipeline: &GraphicsPipeline,
         render_pass_state: &RenderPassState,
@@ -1546,22 +1927,65 @@ where
             _ => return Err(PipelineExecutionError::PipelineRenderPassTypeMismatch),
         }
 
+        if pipeline.dynamic_state(DynamicState::RasterizationSamples) == Some(true) {
+            let device = self.device();
+
+            // Checked to be set by `validate_pipeline_graphics_dynamic_state`.
+            let rasterization_samples = self.state().rasterization_samples().unwrap();
+
+            // Not known for secondary command buffers that inherit a dynamic rendering scope.
+            // The attachments are checked against the inherited sample count when executed.
+            let attachment_samples = match &render_pass_state.render_pass {
+                RenderPassStateType::BeginRenderPass(state) => state.subpass.num_samples(),
+                RenderPassStateType::BeginRendering(state) => {
+                    state.attachments.as_ref().and_then(|attachments| {
+                        attachments
+                            .color_attachments
+                            .iter()
+                            .flatten()
+                            .chain(attachments.depth_attachment.iter())
+                            .chain(attachments.stencil_attachment.iter())
+                            .next()
+                            .map(|attachment_info| attachment_info.image_view.image().samples())
+                    })
+                }
+            };
+
+            // VUID-vkCmdDraw-multisampledRenderToSingleSampled-07284
+            if let Some(attachment_samples) = attachment_samples {
+                if rasterization_samples != attachment_samples
+                    && !(device.enabled_extensions().amd_mixed_attachment_samples
+                        || device.enabled_extensions().nv_framebuffer_mixed_samples)
+                {
+                    return Err(PipelineExecutionError::RasterizationSamplesMismatch {
+                        provided: rasterization_samples,
+                        required: attachment_samples,
+                    });
+                }
+            }
+        }
+
         // VUID-vkCmdDraw-None-02686
         // TODO:
 
         Ok(())
     }
 
-    fn validate_pipeline_graphics_vertex_buffers(
+    pub(super) fn validate_pipeline_graphics_vertex_buffers(
         &self,
         pipeline: &GraphicsPipeline,
         vertices: Option<(u32, u32)>,
         instances: Option<(u32, u32)>,
     ) -> Result<(), PipelineExecutionError> {
-        let vertex_input = pipeline.vertex_input_state();
+        let current_state = self.state();
+        let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+            // Checked to be set by `validate_pipeline_graphics_dynamic_state`.
+            current_state.vertex_input().unwrap()
+        } else {
+            pipeline.vertex_input_state()
+        };
         let mut vertices_in_buffers: Option<u64> = None;
         let mut instances_in_buffers: Option<u64> = None;
-        let current_state = self.state();
 
         for (&binding_num, binding_desc) in &vertex_input.bindings {
             // VUID-vkCmdDraw-None-04007
@@ -1663,6 +2087,41 @@ where
     }
 }
 
+/// Checks that the dynamic vertex input state `vertex_input` provides a compatible attribute for
+/// every input of the vertex shader of `pipeline`.
+pub(in crate::command_buffer) fn validate_vertex_input_against_pipeline(
+    pipeline: &GraphicsPipeline,
+    vertex_input: &VertexInputState,
+) -> Result<(), PipelineExecutionError> {
+    for element in pipeline.vertex_input_interface().elements() {
+        let location_range = element.location..element.location + element.ty.num_locations();
+
+        for location in location_range {
+            // VUID-vkCmdDraw-Input-07939
+            let attribute_desc = vertex_input
+                .attributes
+                .get(&location)
+                .ok_or(PipelineExecutionError::VertexInputAttributeMissing { location })?;
+
+            let shader_type = element.ty.base_type;
+            let attribute_type = attribute_desc.format.type_color().unwrap();
+
+            // VUID-vkCmdDraw-Input-08734
+            if !is_numeric_type_compatible(shader_type, Some(attribute_type)) {
+                return Err(
+                    PipelineExecutionError::VertexInputAttributeIncompatibleFormat {
+                        location,
+                        shader_type,
+                        attribute_type,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdDispatch` on the builder.
     #[inline]
@@ -1889,6 +2348,123 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdDrawMultiEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: Vec<MultiDrawInfo>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            vertex_info: Vec<MultiDrawInfo>,
+            instance_count: u32,
+            first_instance: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_multi"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_multi(&self.vertex_info, self.instance_count, self.first_instance);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_multi";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            vertex_info,
+            instance_count,
+            first_instance,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMultiIndexedEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: Vec<MultiDrawIndexedInfo>,
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            index_info: Vec<MultiDrawIndexedInfo>,
+            instance_count: u32,
+            first_instance: u32,
+            vertex_offset: Option<i32>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_multi_indexed"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_multi_indexed(
+                    &self.index_info,
+                    self.instance_count,
+                    self.first_instance,
+                    self.vertex_offset,
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_multi_indexed";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_index_buffer(&mut resources, command_index, command_name);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            index_info,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
     /// Calls `vkCmdDrawIndirect` on the builder.
     #[inline]
     pub unsafe fn draw_indirect(
@@ -2010,7 +2586,7 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    fn add_descriptor_sets<Pl: Pipeline>(
+    pub(super) fn add_descriptor_sets<Pl: Pipeline>(
         &self,
         resources: &mut Vec<(ResourceUseRef, Resource)>,
         command_index: usize,
@@ -2033,7 +2609,9 @@ impl SyncCommandBufferBuilder {
                 .descriptor_type;
 
             let (access_read, access_write) = match descriptor_type {
-                DescriptorType::Sampler => continue,
+                // Inline uniform blocks are stored in the descriptor set itself, so there is no
+                // resource to synchronize.
+                DescriptorType::Sampler | DescriptorType::InlineUniformBlock => continue,
                 DescriptorType::InputAttachment => {
                     // FIXME: This is tricky. Since we read from the input attachment
                     // and this input attachment is being written in an earlier pass,
@@ -2219,44 +2797,89 @@ impl SyncCommandBufferBuilder {
                             .flat_map(image_resource),
                     );
                 }
-                DescriptorBindingResources::Sampler(_) => (),
+                DescriptorBindingResources::Sampler(_)
+                | DescriptorBindingResources::InlineUniformBlock(_) => (),
+            }
+        }
+
+        // The descriptors of descriptor buffer sets are read from the buffer by the shaders that
+        // use them, so the buffer must not be written while the command executes.
+        let mut descriptor_buffer_stages: HashMap<u32, PipelineStages> = HashMap::default();
+
+        for (&(set, _), binding_reqs) in pipeline.descriptor_binding_requirements() {
+            if let Some(SetOrPush::Buffer(_)) = descriptor_sets_state.descriptor_sets.get(&set) {
+                *descriptor_buffer_stages
+                    .entry(set)
+                    .or_insert_with(PipelineStages::empty) |= binding_reqs.stages.into();
             }
         }
+
+        // Without `synchronization2`, descriptor buffer reads are covered by shader reads.
+        let access = if self.device().enabled_features().synchronization2 {
+            AccessFlags::DESCRIPTOR_BUFFER_READ
+        } else {
+            AccessFlags::SHADER_READ
+        };
+
+        for (set, stages) in descriptor_buffer_stages {
+            let buffer = match &descriptor_sets_state.descriptor_sets[&set] {
+                SetOrPush::Buffer(descriptor_set) => descriptor_set.buffer(),
+                _ => unreachable!(),
+            };
+
+            resources.push((
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::DescriptorBuffer { set },
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: buffer.clone(),
+                    range: 0..buffer.size(),
+                    memory: PipelineMemoryAccess {
+                        stages,
+                        access,
+                        exclusive: false,
+                    },
+                },
+            ));
+        }
     }
 
-    fn add_vertex_buffers(
+    pub(super) fn add_vertex_buffers(
         &self,
         resources: &mut Vec<(ResourceUseRef, Resource)>,
         command_index: usize,
         command_name: &'static str,
         pipeline: &GraphicsPipeline,
     ) {
-        resources.extend(
-            pipeline
-                .vertex_input_state()
-                .bindings
-                .iter()
-                .map(|(&binding, _)| {
-                    let vertex_buffer = &self.current_state.vertex_buffers[&binding];
-                    (
-                        ResourceUseRef {
-                            command_index,
-                            command_name,
-                            resource_in_command: ResourceInCommand::VertexBuffer { binding },
-                            secondary_use_ref: None,
-                        },
-                        Resource::Buffer {
-                            buffer: vertex_buffer.clone(),
-                            range: 0..vertex_buffer.size(), // TODO:
-                            memory: PipelineMemoryAccess {
-                                stages: PipelineStages::VERTEX_INPUT,
-                                access: AccessFlags::VERTEX_ATTRIBUTE_READ,
-                                exclusive: false,
-                            },
-                        },
-                    )
-                }),
-        );
+        let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+            self.current_state.vertex_input.as_ref().unwrap()
+        } else {
+            pipeline.vertex_input_state()
+        };
+
+        resources.extend(vertex_input.bindings.iter().map(|(&binding, _)| {
+            let vertex_buffer = &self.current_state.vertex_buffers[&binding];
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::VertexBuffer { binding },
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: vertex_buffer.clone(),
+                    range: 0..vertex_buffer.size(), // TODO:
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::VERTEX_INPUT,
+                        access: AccessFlags::VERTEX_ATTRIBUTE_READ,
+                        exclusive: false,
+                    },
+                },
+            )
+        }));
     }
 
     fn add_index_buffer(
@@ -2391,6 +3014,56 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdDrawMultiEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) {
+        if vertex_info.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
+            self.handle,
+            vertex_info.len() as u32,
+            vertex_info.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawInfo>() as u32,
+        );
+    }
+
+    /// Calls `vkCmdDrawMultiIndexedEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) {
+        if index_info.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle,
+            index_info.len() as u32,
+            index_info.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawIndexedInfo>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset as *const _),
+        );
+    }
+
     /// Calls `vkCmdDrawIndirect` on the builder.
     #[inline]
     pub unsafe fn draw_indirect(
@@ -2540,6 +3213,13 @@ pub enum PipelineExecutionError {
         max: u32,
     },
 
+    /// The number of draws in a multi-draw command is not less than the `max_multi_draw_count`
+    /// limit.
+    MaxMultiDrawCountExceeded {
+        provided: u32,
+        max: u32,
+    },
+
     /// The `max_multiview_instance_index` limit has been exceeded.
     MaxMultiviewInstanceIndexExceeded {
         highest_instance: u64,
@@ -2611,12 +3291,33 @@ pub enum PipelineExecutionError {
     /// Not all push constants used by the pipeline have been set.
     PushConstantsMissing,
 
+    /// The dynamic rasterization sample count does not match the sample count of the attachments
+    /// of the current subpass or rendering.
+    RasterizationSamplesMismatch {
+        provided: SampleCount,
+        required: SampleCount,
+    },
+
     /// The bound graphics pipeline requires a vertex buffer bound to a binding number, but none
     /// was bound.
     VertexBufferNotBound {
         binding_num: u32,
     },
 
+    /// The dynamic vertex input state does not provide an attribute for an input of the vertex
+    /// shader of the bound graphics pipeline.
+    VertexInputAttributeMissing {
+        location: u32,
+    },
+
+    /// The format of an attribute in the dynamic vertex input state is not compatible with the
+    /// type of the corresponding input of the vertex shader of the bound graphics pipeline.
+    VertexInputAttributeIncompatibleFormat {
+        location: u32,
+        shader_type: ShaderScalarType,
+        attribute_type: NumericType,
+    },
+
     /// The number of instances to be drawn exceeds the available number of indices in the
     /// bound vertex buffers used by the pipeline.
     VertexBufferInstanceRangeOutOfBounds {
@@ -2745,6 +3446,11 @@ impl Display for PipelineExecutionError {
             Self::MaxDrawIndirectCountExceeded { .. } => {
                 write!(f, "the `max_draw_indirect_count` limit has been exceeded")
             }
+            Self::MaxMultiDrawCountExceeded { provided, max } => write!(
+                f,
+                "the number of draws ({}) is not less than the `max_multi_draw_count` limit ({})",
+                provided, max,
+            ),
             Self::MaxMultiviewInstanceIndexExceeded { .. } => write!(
                 f,
                 "the `max_multiview_instance_index` limit has been exceeded",
@@ -2831,12 +3537,34 @@ impl Display for PipelineExecutionError {
                 f,
                 "not all push constants used by the pipeline have been set",
             ),
+            Self::RasterizationSamplesMismatch { provided, required } => write!(
+                f,
+                "the dynamic rasterization sample count ({:?}) does not match the sample count of \
+                the attachments of the current subpass or rendering ({:?})",
+                provided, required,
+            ),
             Self::VertexBufferNotBound { binding_num } => write!(
                 f,
                 "the bound graphics pipeline requires a vertex buffer bound to binding number {}, \
                 but none was bound",
                 binding_num,
             ),
+            Self::VertexInputAttributeMissing { location } => write!(
+                f,
+                "the dynamic vertex input state does not provide an attribute for location {}, \
+                which is an input of the vertex shader",
+                location,
+            ),
+            Self::VertexInputAttributeIncompatibleFormat {
+                location,
+                shader_type,
+                attribute_type,
+            } => write!(
+                f,
+                "the format of the attribute at location {} in the dynamic vertex input state has \
+                numeric type {:?}, which is not compatible with the vertex shader input type {:?}",
+                location, attribute_type, shader_type,
+            ),
             Self::VertexBufferInstanceRangeOutOfBounds {
                 instances_needed,
                 instances_in_buffers,
@@ -2867,6 +3595,7 @@ impl From<SyncCommandBufferBuilderError> for PipelineExecutionError {
 
 #[derive(Clone, Copy, Debug)]
 pub enum DescriptorResourceInvalidError {
+    ConditionalRenderingPredicateWritten,
     ImageViewFormatMismatch {
         required: Format,
         provided: Option<Format>,
@@ -2913,6 +3642,11 @@ impl Error for DescriptorResourceInvalidError {
 impl Display for DescriptorResourceInvalidError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
+            Self::ConditionalRenderingPredicateWritten => write!(
+                f,
+                "the bound buffer or buffer view may be written to by the shader, but overlaps \
+                the predicate of the active conditional rendering block",
+            ),
             Self::ImageViewFormatMismatch { provided, required } => write!(
                 f,
                 "the format of the bound image view ({:?}) does not match what the pipeline \
@@ -2973,3 +3707,284 @@ impl Display for DescriptorResourceInvalidError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, CommandBufferUsage, RenderPassBeginInfo,
+        },
+        image::{view::ImageView, AttachmentImage},
+        memory::allocator::StandardMemoryAllocator,
+        pipeline::graphics::{
+            multisample::MultisampleState,
+            viewport::{Viewport, ViewportState},
+            GraphicsPipelineCreationError,
+        },
+        render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass, Subpass},
+        shader::ShaderModule,
+    };
+
+    /*
+        #version 450
+
+        void main() {
+        }
+    */
+    const VS: [u8; 116] = [
+        3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0, 14,
+        0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 0, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0,
+        0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 54, 0, 5, 0, 2, 0,
+        0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1,
+        0,
+    ];
+    // The same shader, as a fragment shader.
+    const FS: [u8; 128] = [
+        3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0, 14,
+        0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 4, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0,
+        0, 0, 0, 16, 0, 3, 0, 4, 0, 0, 0, 7, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0,
+        0, 0, 2, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2,
+        0, 5, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+    ];
+
+    #[test]
+    fn draw_multi_missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let vertex_info = [MultiDrawInfo {
+            first_vertex: 0,
+            vertex_count: 3,
+        }];
+        let index_info = [MultiDrawIndexedInfo {
+            first_index: 0,
+            index_count: 3,
+            vertex_offset: 0,
+        }];
+
+        assert!(matches!(
+            cbb.draw_multi(&vertex_info, 1, 0),
+            Err(PipelineExecutionError::RequirementNotMet { .. }),
+        ));
+        assert!(matches!(
+            cbb.draw_multi_indexed(&index_info, 1, 0, None),
+            Err(PipelineExecutionError::RequirementNotMet { .. }),
+        ));
+    }
+
+    #[test]
+    fn draw_multi_count() {
+        let (device, queue) = gfx_dev_and_queue!(multi_draw; ext_multi_draw);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let max_multi_draw_count = device
+            .physical_device()
+            .properties()
+            .max_multi_draw_count
+            .unwrap();
+
+        // An empty list of draws is valid, and so is one draw less than the limit.
+        assert!(cbb.validate_multi_draw_count(0).is_ok());
+        assert!(cbb
+            .validate_multi_draw_count(max_multi_draw_count as usize - 1)
+            .is_ok());
+        assert!(matches!(
+            cbb.validate_multi_draw_count(max_multi_draw_count as usize),
+            Err(PipelineExecutionError::MaxMultiDrawCountExceeded { provided, max })
+                if provided == max_multi_draw_count && max == max_multi_draw_count,
+        ));
+
+        // The other requirements are still checked for an empty list.
+        assert!(matches!(
+            cbb.draw_multi(&[], 1, 0),
+            Err(PipelineExecutionError::ForbiddenOutsideRenderPass),
+        ));
+        assert!(matches!(
+            cbb.draw_multi_indexed(&[], 1, 0, None),
+            Err(PipelineExecutionError::ForbiddenOutsideRenderPass),
+        ));
+    }
+
+    #[test]
+    fn vertex_input_not_set() {
+        let (device, queue) =
+            gfx_dev_and_queue!(vertex_input_dynamic_state; ext_vertex_input_dynamic_state);
+
+        let vs = unsafe { ShaderModule::from_bytes(device.clone(), &VS).unwrap() };
+        let fs = unsafe { ShaderModule::from_bytes(device.clone(), &FS).unwrap() };
+
+        let render_pass = RenderPass::empty_single_pass(device.clone()).unwrap();
+
+        let pipeline = GraphicsPipeline::start()
+            .render_pass(Subpass::from(render_pass, 0).unwrap())
+            .vertex_input_state_dynamic()
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant([
+                Viewport {
+                    origin: [0.0, 0.0],
+                    dimensions: [1.0, 1.0],
+                    depth_range: 0.0..1.0,
+                },
+            ]))
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .build(device.clone())
+            .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.bind_pipeline_graphics(pipeline.clone());
+
+        assert!(matches!(
+            cbb.validate_pipeline_graphics_dynamic_state(&pipeline),
+            Err(PipelineExecutionError::DynamicStateNotSet {
+                dynamic_state: DynamicState::VertexInput,
+            }),
+        ));
+
+        cbb.set_vertex_input(VertexInputState::new());
+        assert!(cbb
+            .validate_pipeline_graphics_dynamic_state(&pipeline)
+            .is_ok());
+    }
+
+    #[test]
+    fn rasterization_samples_dynamic_missing_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_bytes(device.clone(), &VS).unwrap() };
+        let fs = unsafe { ShaderModule::from_bytes(device.clone(), &FS).unwrap() };
+        let render_pass = RenderPass::empty_single_pass(device.clone()).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .render_pass(Subpass::from(render_pass, 0).unwrap())
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant([
+                Viewport {
+                    origin: [0.0, 0.0],
+                    dimensions: [1.0, 1.0],
+                    depth_range: 0.0..1.0,
+                },
+            ]))
+            .multisample_state(MultisampleState::new().rasterization_samples_dynamic())
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .build(device);
+
+        assert!(matches!(
+            result,
+            Err(GraphicsPipelineCreationError::RequirementNotMet { .. }),
+        ));
+    }
+
+    #[test]
+    fn rasterization_samples_mismatch() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extended_dynamic_state3_rasterization_samples;
+            ext_extended_dynamic_state3
+        );
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = AttachmentImage::multisampled(
+            &memory_allocator,
+            [1, 1],
+            SampleCount::Sample4,
+            Format::R8G8B8A8_UNORM,
+        )
+        .unwrap();
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    load: DontCare,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 4,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let vs = unsafe { ShaderModule::from_bytes(device.clone(), &VS).unwrap() };
+        let fs = unsafe { ShaderModule::from_bytes(device.clone(), &FS).unwrap() };
+
+        let pipeline = GraphicsPipeline::start()
+            .render_pass(Subpass::from(render_pass, 0).unwrap())
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant([
+                Viewport {
+                    origin: [0.0, 0.0],
+                    dimensions: [1.0, 1.0],
+                    depth_range: 0.0..1.0,
+                },
+            ]))
+            .multisample_state(MultisampleState::new().rasterization_samples_dynamic())
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .build(device.clone())
+            .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.begin_render_pass(
+            RenderPassBeginInfo::framebuffer(framebuffer),
+            SubpassContents::Inline,
+        )
+        .unwrap()
+        .bind_pipeline_graphics(pipeline);
+
+        assert!(matches!(
+            cbb.draw(3, 1, 0, 0),
+            Err(PipelineExecutionError::DynamicStateNotSet {
+                dynamic_state: DynamicState::RasterizationSamples,
+            }),
+        ));
+
+        cbb.set_rasterization_samples(SampleCount::Sample1);
+        assert!(matches!(
+            cbb.draw(3, 1, 0, 0),
+            Err(PipelineExecutionError::RasterizationSamplesMismatch {
+                provided: SampleCount::Sample1,
+                required: SampleCount::Sample4,
+            }),
+        ));
+
+        cbb.set_rasterization_samples(SampleCount::Sample4);
+        assert!(cbb.draw(3, 1, 0, 0).is_ok());
+    }
+}
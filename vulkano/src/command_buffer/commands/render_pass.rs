@@ -223,14 +223,19 @@ where
                 resolve_attachments,
                 depth_stencil_attachment,
                 preserve_attachments: _,
+                fragment_shading_rate_attachment,
                 _ne: _,
             } = subpass_desc;
 
-            for atch_ref in (input_attachments.iter())
-                .chain(color_attachments)
-                .chain(resolve_attachments)
-                .chain([depth_stencil_attachment])
-                .flatten()
+            for atch_ref in (input_attachments.iter().flatten())
+                .chain(color_attachments.iter().flatten())
+                .chain(resolve_attachments.iter().flatten())
+                .chain(depth_stencil_attachment)
+                .chain(
+                    fragment_shading_rate_attachment
+                        .iter()
+                        .map(|fsr_atch| &fsr_atch.attachment_ref),
+                )
             {
                 let image_view = &framebuffer.attachments()[atch_ref.attachment as usize];
 
@@ -289,6 +294,18 @@ where
                             });
                         }
                     }
+                    ImageLayout::FragmentShadingRateAttachmentOptimal => {
+                        // VUID?
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                        {
+                            return Err(RenderPassError::AttachmentImageMissingUsage {
+                                attachment_index: atch_ref.attachment,
+                                usage: "fragment_shading_rate_attachment",
+                            });
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -470,6 +487,11 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdNextSubpass2-None-02349
+        if self.transform_feedback_active {
+            return Err(RenderPassError::TransformFeedbackIsActive);
+        }
+
         // VUID-vkCmdNextSubpass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -528,6 +550,11 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndRenderPass2-None-02352
+        if self.transform_feedback_active {
+            return Err(RenderPassError::TransformFeedbackIsActive);
+        }
+
         // VUID-vkCmdEndRenderPass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -564,6 +591,7 @@ where
                 ref color_attachments,
                 ref depth_attachment,
                 ref stencil_attachment,
+                fragment_shading_rate_attachment: _,
                 contents,
                 _ne: _,
             } = rendering_info;
@@ -643,6 +671,7 @@ where
             ref color_attachments,
             ref depth_attachment,
             ref stencil_attachment,
+            ref fragment_shading_rate_attachment,
             contents,
             _ne: _,
         } = rendering_info;
@@ -1157,6 +1186,80 @@ where
             }
         }
 
+        if let Some(attachment_info) = fragment_shading_rate_attachment {
+            let &RenderingFragmentShadingRateAttachmentInfo {
+                ref image_view,
+                image_layout,
+                texel_size,
+            } = attachment_info;
+
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06147
+            if !device.enabled_features().attachment_fragment_shading_rate {
+                return Err(RenderPassError::RequirementNotMet {
+                    required_for: "`rendering_info.fragment_shading_rate_attachment` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["attachment_fragment_shading_rate"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06147
+            if !matches!(
+                image_layout,
+                ImageLayout::General | ImageLayout::FragmentShadingRateAttachmentOptimal
+            ) {
+                return Err(RenderPassError::FragmentShadingRateAttachmentLayoutInvalid);
+            }
+
+            // VUID-VkRenderingInfo-imageView-06142
+            if !image_view
+                .usage()
+                .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+            {
+                return Err(RenderPassError::FragmentShadingRateAttachmentMissingUsage);
+            }
+
+            // VUID-VkRenderingInfo-imageView-06116
+            if color_attachments
+                .iter()
+                .flatten()
+                .chain(depth_attachment.iter())
+                .chain(stencil_attachment.iter())
+                .any(|other| &other.image_view == image_view)
+            {
+                return Err(RenderPassError::FragmentShadingRateAttachmentImageViewUsedElsewhere);
+            }
+
+            let min_texel_size = properties
+                .min_fragment_shading_rate_attachment_texel_size
+                .unwrap_or([0; 2]);
+            let max_texel_size = properties
+                .max_fragment_shading_rate_attachment_texel_size
+                .unwrap_or([0; 2]);
+            let max_aspect_ratio = properties
+                .max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+                .unwrap_or(0);
+
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06149
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06150
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06151
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06152
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06153
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06154
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06155
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06156
+            if !(0..2).all(|i| {
+                texel_size[i].is_power_of_two()
+                    && texel_size[i] >= min_texel_size[i]
+                    && texel_size[i] <= max_texel_size[i]
+            }) || texel_size[0] / texel_size[1] > max_aspect_ratio
+                || texel_size[1] / texel_size[0] > max_aspect_ratio
+            {
+                return Err(RenderPassError::FragmentShadingRateAttachmentTexelSizeInvalid);
+            }
+        }
+
         Ok(())
     }
 
@@ -1193,6 +1296,11 @@ where
             RenderPassStateType::BeginRendering(_) => (),
         }
 
+        // VUID-vkCmdEndRendering-None-06781
+        if self.transform_feedback_active {
+            return Err(RenderPassError::TransformFeedbackIsActive);
+        }
+
         // VUID-vkCmdEndRendering-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -1613,6 +1721,7 @@ impl SyncCommandBufferBuilder {
             color_attachments,
             depth_attachment,
             stencil_attachment,
+            fragment_shading_rate_attachment,
             contents: _,
             _ne,
         } = &rendering_info;
@@ -1821,6 +1930,37 @@ impl SyncCommandBufferBuilder {
             .into_iter()
             .flatten()
         }))
+        .chain(
+            fragment_shading_rate_attachment
+                .iter()
+                .map(|attachment_info| {
+                    let &RenderingFragmentShadingRateAttachmentInfo {
+                        ref image_view,
+                        image_layout,
+                        texel_size: _,
+                    } = attachment_info;
+
+                    (
+                        ResourceUseRef {
+                            command_index,
+                            command_name,
+                            resource_in_command: ResourceInCommand::FragmentShadingRateAttachment,
+                            secondary_use_ref: None,
+                        },
+                        Resource::Image {
+                            image: image_view.image(),
+                            subresource_range: image_view.subresource_range().clone(),
+                            memory: PipelineMemoryAccess {
+                                stages: PipelineStages::FRAGMENT_SHADING_RATE_ATTACHMENT,
+                                access: AccessFlags::FRAGMENT_SHADING_RATE_ATTACHMENT_READ,
+                                exclusive: false,
+                            },
+                            start_layout: image_layout,
+                            end_layout: image_layout,
+                        },
+                    )
+                }),
+        )
         .collect::<Vec<_>>();
 
         for resource in &resources {
@@ -2031,6 +2171,7 @@ impl UnsafeCommandBufferBuilder {
             ref color_attachments,
             ref depth_attachment,
             ref stencil_attachment,
+            ref fragment_shading_rate_attachment,
             contents,
             _ne: _,
         } = rendering_info;
@@ -2088,7 +2229,7 @@ impl UnsafeCommandBufferBuilder {
         let depth_attachment = map_attachment_info(depth_attachment);
         let stencil_attachment = map_attachment_info(stencil_attachment);
 
-        let rendering_info = ash::vk::RenderingInfo {
+        let mut rendering_info = ash::vk::RenderingInfo {
             flags: contents.into(),
             render_area: ash::vk::Rect2D {
                 offset: ash::vk::Offset2D {
@@ -2109,6 +2250,31 @@ impl UnsafeCommandBufferBuilder {
             ..Default::default()
         };
 
+        let fragment_shading_rate_attachment_vk =
+            fragment_shading_rate_attachment
+                .as_ref()
+                .map(|attachment_info| {
+                    let &RenderingFragmentShadingRateAttachmentInfo {
+                        ref image_view,
+                        image_layout,
+                        texel_size,
+                    } = attachment_info;
+
+                    ash::vk::RenderingFragmentShadingRateAttachmentInfoKHR {
+                        image_view: image_view.handle(),
+                        image_layout: image_layout.into(),
+                        shading_rate_attachment_texel_size: ash::vk::Extent2D {
+                            width: texel_size[0],
+                            height: texel_size[1],
+                        },
+                        ..Default::default()
+                    }
+                });
+
+        if let Some(next) = fragment_shading_rate_attachment_vk.as_ref() {
+            rendering_info.p_next = next as *const _ as *const _;
+        }
+
         let fns = self.device.fns();
 
         if self.device.api_version() >= Version::V1_3 {
@@ -2300,6 +2466,16 @@ pub struct RenderingInfo {
     /// The default value is `None`.
     pub stencil_attachment: Option<RenderingAttachmentInfo>,
 
+    /// The fragment shading rate attachment to use for rendering.
+    ///
+    /// If set to `Some`, the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device, and the image view must not also be used as a
+    /// color, depth or stencil attachment.
+    ///
+    /// The default value is `None`.
+    pub fragment_shading_rate_attachment: Option<RenderingFragmentShadingRateAttachmentInfo>,
+
     /// What kinds of commands will be recorded in the render pass: either inline draw commands, or
     /// executions of secondary command buffers.
     ///
@@ -2322,6 +2498,7 @@ impl Default for RenderingInfo {
             color_attachments: Vec::new(),
             depth_attachment: None,
             stencil_attachment: None,
+            fragment_shading_rate_attachment: None,
             contents: SubpassContents::Inline,
             _ne: crate::NonExhaustive(()),
         }
@@ -2338,6 +2515,7 @@ impl RenderingInfo {
             ref color_attachments,
             ref depth_attachment,
             ref stencil_attachment,
+            fragment_shading_rate_attachment: _,
             contents: _,
             _ne: _,
         } = self;
@@ -2518,6 +2696,52 @@ impl RenderingAttachmentResolveInfo {
     }
 }
 
+/// Parameters to specify the fragment shading rate attachment of a dynamic render pass instance.
+#[derive(Clone, Debug)]
+pub struct RenderingFragmentShadingRateAttachmentInfo {
+    /// The image view to use as the attachment.
+    ///
+    /// There is no default value.
+    pub image_view: Arc<dyn ImageViewAbstract>,
+
+    /// The image layout that `image_view` should be in during rendering.
+    ///
+    /// This must be [`ImageLayout::General`] or
+    /// [`ImageLayout::FragmentShadingRateAttachmentOptimal`].
+    ///
+    /// The default value is [`ImageLayout::FragmentShadingRateAttachmentOptimal`].
+    pub image_layout: ImageLayout,
+
+    /// The width and height, in pixels, of the area of the render area that each texel of the
+    /// attachment corresponds to.
+    ///
+    /// Both values must be powers of two, and must lie between the
+    /// [`min_fragment_shading_rate_attachment_texel_size`] and
+    /// [`max_fragment_shading_rate_attachment_texel_size`] device properties. The ratio between
+    /// the larger and the smaller value must not exceed the
+    /// [`max_fragment_shading_rate_attachment_texel_size_aspect_ratio`] device property.
+    ///
+    /// There is no default value.
+    ///
+    /// [`min_fragment_shading_rate_attachment_texel_size`]: crate::device::Properties::min_fragment_shading_rate_attachment_texel_size
+    /// [`max_fragment_shading_rate_attachment_texel_size`]: crate::device::Properties::max_fragment_shading_rate_attachment_texel_size
+    /// [`max_fragment_shading_rate_attachment_texel_size_aspect_ratio`]: crate::device::Properties::max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+    pub texel_size: [u32; 2],
+}
+
+impl RenderingFragmentShadingRateAttachmentInfo {
+    /// Returns a `RenderingFragmentShadingRateAttachmentInfo` with the specified `image_view` and
+    /// `texel_size`.
+    #[inline]
+    pub fn image_view(image_view: Arc<dyn ImageViewAbstract>, texel_size: [u32; 2]) -> Self {
+        Self {
+            image_view,
+            image_layout: ImageLayout::FragmentShadingRateAttachmentOptimal,
+            texel_size,
+        }
+    }
+}
+
 /// Clear attachment type, used in [`clear_attachments`] command.
 ///
 /// [`clear_attachments`]: crate::command_buffer::AutoCommandBufferBuilder::clear_attachments
@@ -2732,6 +2956,21 @@ pub enum RenderPassError {
     /// The combination of depth and stencil resolve modes is not supported by the device.
     DepthStencilAttachmentResolveModesNotSupported,
 
+    /// The fragment shading rate attachment has a layout that is not supported.
+    FragmentShadingRateAttachmentLayoutInvalid,
+
+    /// The fragment shading rate attachment is missing the `fragment_shading_rate_attachment`
+    /// usage.
+    FragmentShadingRateAttachmentMissingUsage,
+
+    /// The image view of the fragment shading rate attachment is also used as a color, depth or
+    /// stencil attachment.
+    FragmentShadingRateAttachmentImageViewUsedElsewhere,
+
+    /// The texel size of the fragment shading rate attachment is not a power of two, is outside
+    /// the range allowed by the device, or exceeds the maximum aspect ratio.
+    FragmentShadingRateAttachmentTexelSizeInvalid,
+
     /// Operation forbidden inside a render pass.
     ForbiddenInsideRenderPass,
 
@@ -2847,6 +3086,9 @@ pub enum RenderPassError {
         current_subpass: u32,
         remaining_subpasses: u32,
     },
+
+    /// Transform feedback is active, which conflicts with the current operation.
+    TransformFeedbackIsActive,
 }
 
 impl Error for RenderPassError {
@@ -3022,6 +3264,25 @@ impl Display for RenderPassError {
                 f,
                 "the combination of depth and stencil resolve modes is not supported by the device",
             ),
+            Self::FragmentShadingRateAttachmentLayoutInvalid => write!(
+                f,
+                "the fragment shading rate attachment has a layout that is not supported",
+            ),
+            Self::FragmentShadingRateAttachmentMissingUsage => write!(
+                f,
+                "the fragment shading rate attachment is missing the \
+                `fragment_shading_rate_attachment` usage",
+            ),
+            Self::FragmentShadingRateAttachmentImageViewUsedElsewhere => write!(
+                f,
+                "the image view of the fragment shading rate attachment is also used as a color, \
+                depth or stencil attachment",
+            ),
+            Self::FragmentShadingRateAttachmentTexelSizeInvalid => write!(
+                f,
+                "the texel size of the fragment shading rate attachment is not supported by the \
+                device",
+            ),
             Self::ForbiddenInsideRenderPass => {
                 write!(f, "operation forbidden inside a render pass")
             }
@@ -3155,6 +3416,10 @@ impl Display for RenderPassError {
                 the render pass",
                 current_subpass, remaining_subpasses,
             ),
+            Self::TransformFeedbackIsActive => write!(
+                f,
+                "transform feedback is active, which conflicts with the current operation",
+            ),
         }
     }
 }
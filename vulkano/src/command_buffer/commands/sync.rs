@@ -9,18 +9,519 @@
 
 use crate::{
     command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{EventState, EventStatus},
         synced::{Command, SyncCommandBufferBuilder},
         sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, CommandBufferUsage,
     },
+    device::{Device, DeviceOwned, QueueFamilyProperties, QueueFlags},
     image::ImageLayout,
     sync::{
-        event::Event, AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
-        ImageMemoryBarrier, MemoryBarrier, PipelineStages,
+        event::{Event, EventRecordingLock},
+        AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
+        MemoryBarrier, PipelineStages,
     },
-    Version, VulkanObject,
+    RequirementNotMet, RequiresOneOf, Version, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::{ptr, sync::Arc};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    ptr,
+    sync::Arc,
+};
+
+/// # Commands to synchronize work using events.
+///
+/// Barriers for buffers and images are inserted automatically by the builder, so the
+/// `DependencyInfo` given to these commands may only contain global memory barriers.
+///
+/// The builder tracks the state of each event within the command buffer, so that a wait can't
+/// be satisfied by a different signal than the one it expects:
+///
+/// - An event that is waited on after being set by [`set_event`] must have been reset by
+///   [`reset_event`] earlier in the same command buffer, and set exactly once since, with the
+///   same `DependencyInfo` as the wait.
+/// - Otherwise, the event must be signaled from the host, and the memory barriers of the wait may
+///   only have the [`HOST`] stage in their source stages.
+///
+/// Only one command buffer at a time can set or reset a given event, and none can while an
+/// [`EventSetFuture`] for it exists. Event commands can't be recorded in a command buffer with
+/// the [`SimultaneousUse`] usage.
+///
+/// [`set_event`]: Self::set_event
+/// [`reset_event`]: Self::reset_event
+/// [`HOST`]: PipelineStages::HOST
+/// [`EventSetFuture`]: crate::sync::future::EventSetFuture
+/// [`SimultaneousUse`]: CommandBufferUsage::SimultaneousUse
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Signals an [`Event`] from the device, once all previous commands have completed the
+    /// source stages of `dependency_info`.
+    pub fn set_event(
+        &mut self,
+        event: Arc<Event>,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, EventCommandError> {
+        let lock = self.lock_event(&event)?;
+        self.validate_set_event(&event, &dependency_info)?;
+
+        let status = match self.event_status(&event) {
+            Some(EventStatus::Reset) => EventStatus::Set(dependency_info.clone()),
+            _ => EventStatus::Unknown,
+        };
+        self.update_event_status(&event, lock, status);
+
+        unsafe {
+            self.inner.set_event(event, dependency_info);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_set_event(
+        &self,
+        event: &Event,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), EventCommandError> {
+        // VUID-vkCmdSetEvent2-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(EventCommandError::ForbiddenInsideRenderPass);
+        }
+
+        if self.usage == CommandBufferUsage::SimultaneousUse {
+            return Err(EventCommandError::SimultaneousUseNotAllowed);
+        }
+
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetEvent2-commandBuffer-cmdpool
+        if !queue_family_properties.queue_flags.intersects(
+            QueueFlags::GRAPHICS
+                | QueueFlags::COMPUTE
+                | QueueFlags::VIDEO_DECODE
+                | QueueFlags::VIDEO_ENCODE,
+        ) {
+            return Err(EventCommandError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetEvent2-commonparent
+        assert_eq!(device, event.device());
+
+        // VUID-vkCmdSetEvent2-srcStageMask-03827
+        // VUID-vkCmdSetEvent2-dstStageMask-03828
+        // VUID-vkCmdSetEvent2-synchronization2-03824
+        validate_event_dependency_info(device, queue_family_properties, dependency_info, false)?;
+
+        Ok(())
+    }
+
+    /// Waits for one or more [`Event`]s to be signaled.
+    ///
+    /// Each event must either have been set earlier in this command buffer by [`set_event`] with
+    /// an equal `DependencyInfo`, or be signaled from the host with [`Event::set`] or
+    /// [`then_set_event`]. A host-signaled event that is never set will eventually make the
+    /// device time out.
+    ///
+    /// [`set_event`]: Self::set_event
+    /// [`Event::set`]: Event::set
+    /// [`then_set_event`]: crate::sync::GpuFuture::then_set_event
+    pub fn wait_events(
+        &mut self,
+        events: impl IntoIterator<Item = (Arc<Event>, DependencyInfo)>,
+    ) -> Result<&mut Self, EventCommandError> {
+        let events: SmallVec<[(Arc<Event>, DependencyInfo); 4]> = events.into_iter().collect();
+        self.validate_wait_events(&events)?;
+
+        if !events.is_empty() {
+            unsafe {
+                self.inner.wait_events(events);
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn validate_wait_events(
+        &self,
+        events: &[(Arc<Event>, DependencyInfo)],
+    ) -> Result<(), EventCommandError> {
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdWaitEvents2-commandBuffer-cmdpool
+        if !queue_family_properties.queue_flags.intersects(
+            QueueFlags::GRAPHICS
+                | QueueFlags::COMPUTE
+                | QueueFlags::VIDEO_DECODE
+                | QueueFlags::VIDEO_ENCODE,
+        ) {
+            return Err(EventCommandError::NotSupportedByQueueFamily);
+        }
+
+        if self.usage == CommandBufferUsage::SimultaneousUse {
+            return Err(EventCommandError::SimultaneousUseNotAllowed);
+        }
+
+        for (event_index, (event, dependency_info)) in events.iter().enumerate() {
+            // VUID-vkCmdWaitEvents2-commonparent
+            assert_eq!(device, event.device());
+
+            // VUID-vkCmdWaitEvents2-srcStageMask-03842
+            // VUID-vkCmdWaitEvents2-dstStageMask-03843
+            // VUID-vkCmdWaitEvents2-synchronization2-03836
+            validate_event_dependency_info(device, queue_family_properties, dependency_info, true)?;
+
+            // VUID-vkCmdWaitEvents2-dependencyFlags-03844
+            if self.render_pass_state.is_some()
+                && dependency_info
+                    .memory_barriers
+                    .iter()
+                    .any(|barrier| barrier.src_stages.intersects(PipelineStages::HOST))
+            {
+                return Err(EventCommandError::HostStageNotAllowed);
+            }
+
+            // VUID-vkCmdWaitEvents2-pEvents-03837
+            // VUID-vkCmdWaitEvents2-pEvents-03838
+            // VUID-vkCmdWaitEvents2-pEvents-03839
+            match self.event_status(event) {
+                Some(EventStatus::Set(set_dependency_info)) => {
+                    if !dependency_info_eq(set_dependency_info, dependency_info) {
+                        return Err(EventCommandError::DependencyInfoMismatch { event_index });
+                    }
+                }
+                Some(EventStatus::Reset | EventStatus::Unknown) => {
+                    return Err(EventCommandError::EventNotSet { event_index });
+                }
+                None => {
+                    if !dependency_info
+                        .memory_barriers
+                        .iter()
+                        .all(|barrier| PipelineStages::HOST.contains(barrier.src_stages))
+                    {
+                        return Err(EventCommandError::EventNotSet { event_index });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resets an [`Event`] back to the unsignaled state, once all previous commands have completed
+    /// `stages`.
+    ///
+    /// A pipeline barrier is inserted before the reset, so that it can't overtake any previous
+    /// [`set_event`] or [`wait_events`] command on the event, including those of previous
+    /// submissions.
+    ///
+    /// [`set_event`]: Self::set_event
+    /// [`wait_events`]: Self::wait_events
+    pub fn reset_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, EventCommandError> {
+        let lock = self.lock_event(&event)?;
+        self.validate_reset_event(&event, stages)?;
+
+        self.update_event_status(&event, lock, EventStatus::Reset);
+
+        unsafe {
+            self.inner.reset_event(event, stages);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_reset_event(
+        &self,
+        event: &Event,
+        stages: PipelineStages,
+    ) -> Result<(), EventCommandError> {
+        // VUID-vkCmdResetEvent2-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(EventCommandError::ForbiddenInsideRenderPass);
+        }
+
+        if self.usage == CommandBufferUsage::SimultaneousUse {
+            return Err(EventCommandError::SimultaneousUseNotAllowed);
+        }
+
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdResetEvent2-commandBuffer-cmdpool
+        if !queue_family_properties.queue_flags.intersects(
+            QueueFlags::GRAPHICS
+                | QueueFlags::COMPUTE
+                | QueueFlags::VIDEO_DECODE
+                | QueueFlags::VIDEO_ENCODE,
+        ) {
+            return Err(EventCommandError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdResetEvent2-commonparent
+        assert_eq!(device, event.device());
+
+        // VUID-vkCmdResetEvent-stageMask-03937
+        if stages.is_empty() && !device.enabled_features().synchronization2 {
+            return Err(EventCommandError::RequirementNotMet {
+                required_for: "`stages` is empty",
+                requires_one_of: RequiresOneOf {
+                    features: &["synchronization2"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdResetEvent2-stageMask-03830
+        if stages.intersects(PipelineStages::HOST) {
+            return Err(EventCommandError::HostStageNotAllowed);
+        }
+
+        // VUID-vkCmdResetEvent2-stageMask-parameter
+        // VUID-vkCmdResetEvent2-synchronization2-03829
+        validate_event_stages(device, queue_family_properties, stages)?;
+
+        Ok(())
+    }
+
+    // Takes the recording lock of `event`, unless this command buffer already holds it.
+    fn lock_event(
+        &self,
+        event: &Arc<Event>,
+    ) -> Result<Option<EventRecordingLock>, EventCommandError> {
+        if self.event_state.contains_key(&event.handle()) {
+            return Ok(None);
+        }
+
+        event
+            .lock_recording()
+            .map(Some)
+            .ok_or(EventCommandError::EventInUse)
+    }
+
+    fn event_status(&self, event: &Event) -> Option<&EventStatus> {
+        self.event_state
+            .get(&event.handle())
+            .map(|state| &state.status)
+    }
+
+    fn update_event_status(
+        &mut self,
+        event: &Event,
+        lock: Option<EventRecordingLock>,
+        status: EventStatus,
+    ) {
+        match lock {
+            Some(lock) => {
+                self.event_state
+                    .insert(event.handle(), EventState { lock, status });
+            }
+            None => self.event_state.get_mut(&event.handle()).unwrap().status = status,
+        }
+    }
+}
+
+// Returns whether two `DependencyInfo`s of event commands, which only contain memory barriers,
+// are equal.
+fn dependency_info_eq(a: &DependencyInfo, b: &DependencyInfo) -> bool {
+    a.dependency_flags == b.dependency_flags
+        && a.memory_barriers.len() == b.memory_barriers.len()
+        && a.memory_barriers
+            .iter()
+            .zip(&b.memory_barriers)
+            .all(|(a, b)| {
+                a.src_stages == b.src_stages
+                    && a.src_access == b.src_access
+                    && a.dst_stages == b.dst_stages
+                    && a.dst_access == b.dst_access
+            })
+}
+
+// Validates the `DependencyInfo` of an event command. Only global memory barriers are allowed,
+// and the `HOST` stage is only allowed in the source stages when waiting.
+fn validate_event_dependency_info(
+    device: &Device,
+    queue_family_properties: &QueueFamilyProperties,
+    dependency_info: &DependencyInfo,
+    is_wait: bool,
+) -> Result<(), EventCommandError> {
+    let &DependencyInfo {
+        dependency_flags,
+        ref memory_barriers,
+        ref buffer_memory_barriers,
+        ref image_memory_barriers,
+        _ne: _,
+    } = dependency_info;
+
+    // VUID-vkCmdSetEvent2-dependencyFlags-03825
+    // VUID-vkCmdWaitEvents2-dependencyFlags-03844
+    if !dependency_flags.is_empty() {
+        return Err(EventCommandError::DependencyFlagsNotAllowed);
+    }
+
+    // The auto builder tracks buffers and images itself.
+    if !buffer_memory_barriers.is_empty() || !image_memory_barriers.is_empty() {
+        return Err(EventCommandError::ResourceBarriersNotAllowed);
+    }
+
+    for (barrier_index, barrier) in memory_barriers.iter().enumerate() {
+        let &MemoryBarrier {
+            src_stages,
+            src_access,
+            dst_stages,
+            dst_access,
+            _ne: _,
+        } = barrier;
+
+        for (stages, access, host_allowed) in [
+            (src_stages, src_access, is_wait),
+            (dst_stages, dst_access, false),
+        ] {
+            // VUID-vkCmdSetEvent2-srcStageMask-03827
+            if stages.intersects(PipelineStages::HOST) && !host_allowed {
+                return Err(EventCommandError::HostStageNotAllowed);
+            }
+
+            validate_event_stages(
+                device,
+                queue_family_properties,
+                stages - PipelineStages::HOST,
+            )?;
+
+            // VUID-VkMemoryBarrier2-srcAccessMask-parameter
+            // VUID-VkMemoryBarrier2-dstAccessMask-parameter
+            access.validate_device(device)?;
+
+            if !device.enabled_features().synchronization2 && access.is_2() {
+                return Err(EventCommandError::RequirementNotMet {
+                    required_for: "One of `dependency_info.memory_barriers` has an element where \
+                        `src_access` or `dst_access` contains flags from `VkAccessFlagBits2`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["synchronization2"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkMemoryBarrier2-srcAccessMask-03900
+            // VUID-VkMemoryBarrier2-dstAccessMask-03900
+            // etc.
+            if !AccessFlags::from(stages).contains(access) {
+                return Err(EventCommandError::AccessNotSupportedByStages { barrier_index });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Validates that `stages` can be used in an event command on the given queue family.
+fn validate_event_stages(
+    device: &Device,
+    queue_family_properties: &QueueFamilyProperties,
+    stages: PipelineStages,
+) -> Result<(), EventCommandError> {
+    // VUID-VkMemoryBarrier2-srcStageMask-parameter
+    // VUID-VkMemoryBarrier2-dstStageMask-parameter
+    stages.validate_device(device)?;
+
+    if !device.enabled_features().synchronization2 && stages.is_2() {
+        return Err(EventCommandError::RequirementNotMet {
+            required_for: "the stages of an event command contain flags from \
+                `VkPipelineStageFlagBits2`",
+            requires_one_of: RequiresOneOf {
+                features: &["synchronization2"],
+                ..Default::default()
+            },
+        });
+    }
+
+    let enabled_features = device.enabled_features();
+    let stage_features: [(PipelineStages, bool, &'static [&'static str]); 10] = [
+        (
+            PipelineStages::GEOMETRY_SHADER,
+            enabled_features.geometry_shader,
+            &["geometry_shader"],
+        ),
+        (
+            PipelineStages::TESSELLATION_CONTROL_SHADER
+                | PipelineStages::TESSELLATION_EVALUATION_SHADER,
+            enabled_features.tessellation_shader,
+            &["tessellation_shader"],
+        ),
+        (
+            PipelineStages::CONDITIONAL_RENDERING,
+            enabled_features.conditional_rendering,
+            &["conditional_rendering"],
+        ),
+        (
+            PipelineStages::FRAGMENT_DENSITY_PROCESS,
+            enabled_features.fragment_density_map,
+            &["fragment_density_map"],
+        ),
+        (
+            PipelineStages::TRANSFORM_FEEDBACK,
+            enabled_features.transform_feedback,
+            &["transform_feedback"],
+        ),
+        (
+            PipelineStages::MESH_SHADER,
+            enabled_features.mesh_shader,
+            &["mesh_shader"],
+        ),
+        (
+            PipelineStages::TASK_SHADER,
+            enabled_features.task_shader,
+            &["task_shader"],
+        ),
+        (
+            PipelineStages::FRAGMENT_SHADING_RATE_ATTACHMENT,
+            enabled_features.attachment_fragment_shading_rate
+                || enabled_features.shading_rate_image,
+            &["attachment_fragment_shading_rate", "shading_rate_image"],
+        ),
+        (
+            PipelineStages::SUBPASS_SHADING,
+            enabled_features.subpass_shading,
+            &["subpass_shading"],
+        ),
+        (
+            PipelineStages::INVOCATION_MASK,
+            enabled_features.invocation_mask,
+            &["invocation_mask"],
+        ),
+    ];
+
+    for (stage_flags, enabled, features) in stage_features {
+        // VUID-VkMemoryBarrier2-srcStageMask-03929
+        // VUID-VkMemoryBarrier2-srcStageMask-03930
+        // etc.
+        if stages.intersects(stage_flags) && !enabled {
+            return Err(EventCommandError::RequirementNotMet {
+                required_for: "the stages of an event command contain a stage whose feature is \
+                    not enabled",
+                requires_one_of: RequiresOneOf {
+                    features,
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    if !PipelineStages::from(queue_family_properties.queue_flags).contains(stages) {
+        return Err(EventCommandError::StageNotSupported);
+    }
+
+    Ok(())
+}
 
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdSetEvent` on the builder.
@@ -90,6 +591,19 @@ impl SyncCommandBufferBuilder {
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                // VUID-vkCmdResetEvent2-event-03831
+                // VUID-vkCmdResetEvent2-event-03832
+                // Previous `set_event` and `wait_events` commands on the event must have finished.
+                out.pipeline_barrier(&DependencyInfo {
+                    memory_barriers: [MemoryBarrier {
+                        src_stages: PipelineStages::ALL_COMMANDS,
+                        dst_stages: PipelineStages::ALL_COMMANDS,
+                        ..Default::default()
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                });
                 out.reset_event(&self.event, self.stages);
             }
         }
@@ -422,16 +936,13 @@ impl UnsafeCommandBufferBuilder {
     #[inline]
     pub unsafe fn set_event(&mut self, event: &Event, dependency_info: &DependencyInfo) {
         let &DependencyInfo {
-            mut dependency_flags,
+            dependency_flags,
             ref memory_barriers,
             ref buffer_memory_barriers,
             ref image_memory_barriers,
             _ne: _,
         } = dependency_info;
 
-        // TODO: Is this needed?
-        dependency_flags |= DependencyFlags::BY_REGION;
-
         let fns = self.device.fns();
 
         if self.device.enabled_features().synchronization2 {
@@ -599,16 +1110,13 @@ impl UnsafeCommandBufferBuilder {
 
             for (event, dependency_info) in events {
                 let &DependencyInfo {
-                    mut dependency_flags,
+                    dependency_flags,
                     ref memory_barriers,
                     ref buffer_memory_barriers,
                     ref image_memory_barriers,
                     _ne: _,
                 } = dependency_info;
 
-                // TODO: Is this needed?
-                dependency_flags |= DependencyFlags::BY_REGION;
-
                 let memory_barriers_vk: SmallVec<[_; 2]> = memory_barriers
                     .into_iter()
                     .map(|barrier| {
@@ -929,3 +1437,223 @@ impl UnsafeCommandBufferBuilder {
 
     // TODO: wait_event
 }
+
+/// Error that can happen when recording an event command.
+#[derive(Clone, Debug)]
+pub enum EventCommandError {
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// One or more accesses of a memory barrier are not supported by the corresponding pipeline
+    /// stages.
+    AccessNotSupportedByStages { barrier_index: usize },
+
+    /// The dependency flags of the `DependencyInfo` are not empty.
+    DependencyFlagsNotAllowed,
+
+    /// The `DependencyInfo` of a waited event is not equal to the one it was set with.
+    DependencyInfoMismatch { event_index: usize },
+
+    /// The event is already set or reset by another command buffer, or set by an
+    /// `EventSetFuture`.
+    EventInUse,
+
+    /// A waited event was not set by a single `set_event` command after being reset in this
+    /// command buffer, and the source stages of its memory barriers are not only `HOST`.
+    EventNotSet { event_index: usize },
+
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// The [`HOST`](PipelineStages::HOST) pipeline stage was provided where it is not allowed.
+    HostStageNotAllowed,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The `DependencyInfo` contains buffer or image memory barriers. These are inserted
+    /// automatically by the builder.
+    ResourceBarriersNotAllowed,
+
+    /// The command buffer has the `SimultaneousUse` usage.
+    SimultaneousUseNotAllowed,
+
+    /// The provided stage is not supported by the queue family.
+    StageNotSupported,
+}
+
+impl Error for EventCommandError {}
+
+impl Display for EventCommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::AccessNotSupportedByStages { barrier_index } => write!(
+                f,
+                "one or more accesses of memory barrier {} are not supported by the \
+                corresponding pipeline stages",
+                barrier_index,
+            ),
+            Self::DependencyFlagsNotAllowed => {
+                write!(
+                    f,
+                    "the dependency flags of the `DependencyInfo` are not empty"
+                )
+            }
+            Self::DependencyInfoMismatch { event_index } => write!(
+                f,
+                "the `DependencyInfo` of event {} is not equal to the one it was set with",
+                event_index,
+            ),
+            Self::EventInUse => write!(
+                f,
+                "the event is already set or reset by another command buffer, or set by an \
+                `EventSetFuture`",
+            ),
+            Self::EventNotSet { event_index } => write!(
+                f,
+                "event {} was not set by a single `set_event` command after being reset in this \
+                command buffer, and the source stages of its memory barriers are not only `HOST`",
+                event_index,
+            ),
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::HostStageNotAllowed => write!(
+                f,
+                "the `HOST` pipeline stage was provided where it is not allowed",
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::ResourceBarriersNotAllowed => write!(
+                f,
+                "the `DependencyInfo` contains buffer or image memory barriers, which are \
+                inserted automatically by the builder",
+            ),
+            Self::SimultaneousUseNotAllowed => {
+                write!(f, "the command buffer has the `SimultaneousUse` usage")
+            }
+            Self::StageNotSupported => {
+                write!(f, "the provided stage is not supported by the queue family")
+            }
+        }
+    }
+}
+
+impl From<RequirementNotMet> for EventCommandError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_buffer::allocator::StandardCommandBufferAllocator;
+
+    fn compute_dependency_info() -> DependencyInfo {
+        DependencyInfo {
+            memory_barriers: [MemoryBarrier {
+                src_stages: PipelineStages::COMPUTE_SHADER,
+                dst_stages: PipelineStages::COMPUTE_SHADER,
+                ..Default::default()
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wait_requires_set_after_reset() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let event = Arc::new(Event::new(device, Default::default()).unwrap());
+
+        // Not set in this command buffer, and not only waiting on the host.
+        assert!(matches!(
+            cbb.wait_events([(event.clone(), compute_dependency_info())]),
+            Err(EventCommandError::EventNotSet { event_index: 0 }),
+        ));
+
+        // Set without being reset first, so an earlier signal may satisfy the wait.
+        cbb.set_event(event.clone(), compute_dependency_info())
+            .unwrap();
+        assert!(matches!(
+            cbb.wait_events([(event.clone(), compute_dependency_info())]),
+            Err(EventCommandError::EventNotSet { event_index: 0 }),
+        ));
+
+        cbb.reset_event(event.clone(), PipelineStages::COMPUTE_SHADER)
+            .unwrap();
+        cbb.set_event(event.clone(), compute_dependency_info())
+            .unwrap();
+        assert!(matches!(
+            cbb.wait_events([(event.clone(), DependencyInfo::default())]),
+            Err(EventCommandError::DependencyInfoMismatch { event_index: 0 }),
+        ));
+        cbb.wait_events([(event, compute_dependency_info())])
+            .unwrap();
+    }
+
+    #[test]
+    fn event_in_use() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let event = Arc::new(Event::new(device, Default::default()).unwrap());
+
+        let mut first = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        first
+            .reset_event(event.clone(), PipelineStages::COMPUTE_SHADER)
+            .unwrap();
+        let first = first.build().unwrap();
+
+        let mut second = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        assert!(matches!(
+            second.reset_event(event.clone(), PipelineStages::COMPUTE_SHADER),
+            Err(EventCommandError::EventInUse),
+        ));
+
+        // Waiting on the host doesn't need the event.
+        second
+            .wait_events([(event.clone(), DependencyInfo::default())])
+            .unwrap();
+
+        drop(first);
+        second
+            .reset_event(event, PipelineStages::COMPUTE_SHADER)
+            .unwrap();
+    }
+}
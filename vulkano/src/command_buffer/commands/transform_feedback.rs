@@ -0,0 +1,1327 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::pipeline::PipelineExecutionError;
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::RenderPassStateType,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    pipeline::Pipeline,
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    ptr,
+};
+
+/// # Commands for transform feedback.
+///
+/// These commands require the [`transform_feedback`] feature to be enabled on the device.
+///
+/// [`transform_feedback`]: crate::device::Features::transform_feedback
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Binds buffers to the transform feedback bindings, starting at `first_binding`.
+    ///
+    /// While transform feedback is active, the outputs of the last pre-rasterization shader that
+    /// are decorated with `XfbBuffer` are written to the buffer bound to the binding with that
+    /// number.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if any of the buffers was not created from the same device as `self`.
+    pub fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: impl IntoIterator<Item = Subbuffer<[u8]>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let buffers: SmallVec<[_; 4]> = buffers.into_iter().collect();
+        self.validate_bind_transform_feedback_buffers(first_binding, &buffers)?;
+
+        unsafe {
+            self.inner
+                .bind_transform_feedback_buffers(first_binding, buffers);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        buffers: &[Subbuffer<[u8]>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-transformFeedback-02355
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::bind_transform_feedback_buffers`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-None-02365
+        if self.transform_feedback_active {
+            return Err(TransformFeedbackError::AlreadyActive);
+        }
+
+        let properties = device.physical_device().properties();
+        let max_transform_feedback_buffers = properties.max_transform_feedback_buffers.unwrap();
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02356
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02357
+        if first_binding + buffers.len() as u32 > max_transform_feedback_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    max: max_transform_feedback_buffers,
+                    obtained: first_binding + buffers.len() as u32,
+                },
+            );
+        }
+
+        let max_transform_feedback_buffer_size =
+            properties.max_transform_feedback_buffer_size.unwrap();
+
+        for (index, buffer) in buffers.iter().enumerate() {
+            let index = index as u32;
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02359
+            if buffer.offset() % 4 != 0 {
+                return Err(TransformFeedbackError::BufferOffsetNotAligned { index });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pBuffers-02360
+            if !buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER)
+            {
+                return Err(TransformFeedbackError::BufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pSize-02362
+            if buffer.size() > max_transform_feedback_buffer_size {
+                return Err(
+                    TransformFeedbackError::MaxTransformFeedbackBufferSizeExceeded {
+                        index,
+                        max: max_transform_feedback_buffer_size,
+                        obtained: buffer.size(),
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Begins capturing vertices into the bound transform feedback buffers.
+    ///
+    /// `counter_buffers` optionally provides, for each transform feedback binding starting at
+    /// `first_counter_buffer`, a counter that holds the byte offset at which to resume writing to
+    /// that binding. If a counter buffer is `None`, writing starts at the beginning of the bound
+    /// buffer.
+    ///
+    /// This command must be recorded inside a render pass, and a graphics pipeline whose last
+    /// pre-rasterization shader captures outputs must be bound. Every transform feedback buffer
+    /// that the pipeline writes to must be bound, and large enough to hold at least one vertex.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if any of the counter buffers was not created from the same device as `self`.
+    pub fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Subbuffer<u32>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_begin_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            self.inner
+                .begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+        }
+
+        self.transform_feedback_active = true;
+
+        Ok(self)
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-transformFeedback-02366
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_transform_feedback`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(TransformFeedbackError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02367
+        if self.transform_feedback_active {
+            return Err(TransformFeedbackError::AlreadyActive);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02373
+        if render_pass_state.view_mask != 0 {
+            return Err(TransformFeedbackError::ForbiddenWithMultiview);
+        }
+
+        self.validate_counter_buffers(first_counter_buffer, counter_buffers)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-04128
+        let pipeline = self
+            .state()
+            .pipeline_graphics()
+            .ok_or(TransformFeedbackError::PipelineNotBound)?;
+        let outputs = pipeline
+            .transform_feedback_outputs()
+            .ok_or(TransformFeedbackError::PipelineNoTransformFeedback)?;
+
+        for output in outputs {
+            let buffer = self
+                .state()
+                .transform_feedback_buffer(output.buffer)
+                .ok_or(TransformFeedbackError::BufferNotBound {
+                    binding: output.buffer,
+                })?;
+
+            let required_size = (output.offset + output.size) as DeviceSize;
+
+            if buffer.size() < required_size {
+                return Err(TransformFeedbackError::BufferTooSmall {
+                    binding: output.buffer,
+                    required_size,
+                    actual_size: buffer.size(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stops capturing vertices into the bound transform feedback buffers.
+    ///
+    /// `counter_buffers` optionally provides, for each transform feedback binding starting at
+    /// `first_counter_buffer`, a counter that the byte offset following the last written vertex is
+    /// stored into. It can be passed to a later call to
+    /// [`begin_transform_feedback`](Self::begin_transform_feedback) to resume capturing, or to
+    /// [`draw_indirect_byte_count`](Self::draw_indirect_byte_count).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if any of the counter buffers was not created from the same device as `self`.
+    pub fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Subbuffer<u32>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_end_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            self.inner
+                .end_transform_feedback(first_counter_buffer, counter_buffers)?;
+        }
+
+        self.transform_feedback_active = false;
+
+        Ok(self)
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdEndTransformFeedbackEXT-transformFeedback-02374
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::end_transform_feedback`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdEndTransformFeedbackEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-None-02375
+        if !self.transform_feedback_active {
+            return Err(TransformFeedbackError::NotActive);
+        }
+
+        self.validate_counter_buffers(first_counter_buffer, counter_buffers)?;
+
+        Ok(())
+    }
+
+    fn validate_counter_buffers(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+        let max_transform_feedback_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap();
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02368
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02369
+        // VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02376
+        // VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02377
+        if first_counter_buffer + counter_buffers.len() as u32 > max_transform_feedback_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    max: max_transform_feedback_buffers,
+                    obtained: first_counter_buffer + counter_buffers.len() as u32,
+                },
+            );
+        }
+
+        for (index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let counter_buffer = match counter_buffer {
+                Some(x) => x,
+                None => continue,
+            };
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-commonparent
+            // VUID-vkCmdEndTransformFeedbackEXT-commonparent
+            assert_eq!(device, counter_buffer.device());
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBufferOffsets-02370
+            // VUID-vkCmdEndTransformFeedbackEXT-pCounterBufferOffsets-02378
+            // Guaranteed because we take `Subbuffer<u32>`
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBuffers-02372
+            // VUID-vkCmdEndTransformFeedbackEXT-pCounterBuffers-02380
+            if !counter_buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(TransformFeedbackError::CounterBufferMissingUsage {
+                    index: index as u32,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform a single draw operation using a graphics pipeline, where the number of vertices is
+    /// taken from a transform feedback counter.
+    ///
+    /// The number of vertices that are drawn is the value in `counter_buffer`, minus
+    /// `counter_offset`, divided by `vertex_stride`. This makes it possible to draw the vertices
+    /// that were previously captured with transform feedback, without reading back how many there
+    /// were. The counter is read by the device when the command is executed.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `counter_buffer` was not created from the same device as `self`.
+    pub fn draw_indirect_byte_count(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        self.validate_draw_indirect_byte_count(
+            instance_count,
+            first_instance,
+            &counter_buffer,
+            counter_offset,
+            vertex_stride,
+        )?;
+
+        unsafe {
+            self.inner.draw_indirect_byte_count(
+                instance_count,
+                first_instance,
+                counter_buffer,
+                counter_offset,
+                vertex_stride,
+            )?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_indirect_byte_count(
+        &self,
+        _instance_count: u32,
+        _first_instance: u32,
+        counter_buffer: &Subbuffer<u32>,
+        _counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-transformFeedback-02287
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_indirect_byte_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = device.physical_device().properties();
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-transformFeedbackDraw-02288
+        if !properties.transform_feedback_draw.unwrap_or(false) {
+            return Err(TransformFeedbackError::DrawNotSupported);
+        }
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-renderpass
+        let render_pass_state = self.render_pass_state.as_ref().ok_or(
+            TransformFeedbackError::PipelineExecutionError(
+                PipelineExecutionError::ForbiddenOutsideRenderPass,
+            ),
+        )?;
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-None-02700
+        let pipeline = self
+            .state()
+            .pipeline_graphics()
+            .ok_or(TransformFeedbackError::PipelineExecutionError(
+                PipelineExecutionError::PipelineNotBound,
+            ))?
+            .as_ref();
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBuffer-02290
+        self.validate_indirect_buffer(counter_buffer.as_bytes())?;
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBufferOffset-04568
+        // Guaranteed because we take `Subbuffer<u32>`
+
+        let max_transform_feedback_buffer_data_stride = properties
+            .max_transform_feedback_buffer_data_stride
+            .unwrap();
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-vertexStride-02289
+        if vertex_stride == 0 || vertex_stride > max_transform_feedback_buffer_data_stride {
+            return Err(TransformFeedbackError::VertexStrideOutOfRange {
+                vertex_stride,
+                max: max_transform_feedback_buffer_data_stride,
+            });
+        }
+
+        // VUID?
+        if vertex_stride % 4 != 0 {
+            return Err(TransformFeedbackError::VertexStrideNotAligned { vertex_stride });
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    ///
+    /// Does not check the type of the buffers.
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: SmallVec<[Subbuffer<[u8]>; 4]>,
+    ) {
+        struct Cmd {
+            first_binding: u32,
+            buffers: SmallVec<[Subbuffer<[u8]>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_transform_feedback_buffers"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_transform_feedback_buffers(self.first_binding, &self.buffers);
+            }
+        }
+
+        for (binding, buffer) in (first_binding..).zip(buffers.iter()) {
+            self.current_state
+                .transform_feedback_buffers
+                .insert(binding, buffer.clone());
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_binding,
+            buffers,
+        }));
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    ///
+    /// The bound transform feedback buffers are considered to be written by this command, since
+    /// the draw commands that follow it write to them.
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_transform_feedback"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_transform_feedback(self.first_counter_buffer, &self.counter_buffers);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "begin_transform_feedback";
+        let mut resources = Vec::new();
+        self.add_counter_buffers(
+            &mut resources,
+            command_index,
+            command_name,
+            first_counter_buffer,
+            &counter_buffers,
+            PipelineMemoryAccess {
+                stages: PipelineStages::TRANSFORM_FEEDBACK,
+                access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_READ,
+                exclusive: false,
+            },
+        );
+        resources.extend(self.current_state.transform_feedback_buffers.iter().map(
+            |(&binding, buffer)| {
+                (
+                    ResourceUseRef {
+                        command_index,
+                        command_name,
+                        resource_in_command: ResourceInCommand::TransformFeedbackBuffer { binding },
+                        secondary_use_ref: None,
+                    },
+                    Resource::Buffer {
+                        buffer: buffer.clone(),
+                        range: 0..buffer.size(),
+                        memory: PipelineMemoryAccess {
+                            stages: PipelineStages::TRANSFORM_FEEDBACK,
+                            access: AccessFlags::TRANSFORM_FEEDBACK_WRITE,
+                            exclusive: true,
+                        },
+                    },
+                )
+            },
+        ));
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_counter_buffer,
+            counter_buffers,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_transform_feedback"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_transform_feedback(self.first_counter_buffer, &self.counter_buffers);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "end_transform_feedback";
+        let mut resources = Vec::new();
+        self.add_counter_buffers(
+            &mut resources,
+            command_index,
+            command_name,
+            first_counter_buffer,
+            &counter_buffers,
+            PipelineMemoryAccess {
+                stages: PipelineStages::TRANSFORM_FEEDBACK,
+                access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_WRITE,
+                exclusive: true,
+            },
+        );
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_counter_buffer,
+            counter_buffers,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndirectByteCountEXT` on the builder.
+    pub unsafe fn draw_indirect_byte_count(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            instance_count: u32,
+            first_instance: u32,
+            counter_buffer: Subbuffer<u32>,
+            counter_offset: u32,
+            vertex_stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indirect_byte_count"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indirect_byte_count(
+                    self.instance_count,
+                    self.first_instance,
+                    &self.counter_buffer,
+                    self.counter_offset,
+                    self.vertex_stride,
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_indirect_byte_count";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        resources.push((
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::IndirectBuffer,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: counter_buffer.as_bytes().clone(),
+                range: 0..counter_buffer.size(),
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::DRAW_INDIRECT,
+                    access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_READ,
+                    exclusive: false,
+                },
+            },
+        ));
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            instance_count,
+            first_instance,
+            counter_buffer,
+            counter_offset,
+            vertex_stride,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    fn add_counter_buffers(
+        &self,
+        resources: &mut Vec<(ResourceUseRef, Resource)>,
+        command_index: usize,
+        command_name: &'static str,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+        memory: PipelineMemoryAccess,
+    ) {
+        resources.extend((first_counter_buffer..).zip(counter_buffers).filter_map(
+            |(index, counter_buffer)| {
+                let counter_buffer = counter_buffer.as_ref()?;
+
+                Some((
+                    ResourceUseRef {
+                        command_index,
+                        command_name,
+                        resource_in_command: ResourceInCommand::TransformFeedbackCounterBuffer {
+                            index,
+                        },
+                        secondary_use_ref: None,
+                    },
+                    Resource::Buffer {
+                        buffer: counter_buffer.as_bytes().clone(),
+                        range: 0..counter_buffer.size(),
+                        memory,
+                    },
+                ))
+            },
+        ));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: &[Subbuffer<[u8]>],
+    ) {
+        if buffers.is_empty() {
+            return;
+        }
+
+        let mut buffers_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(buffers.len());
+        let mut offsets_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(buffers.len());
+        let mut sizes_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(buffers.len());
+
+        for buffer in buffers {
+            buffers_vk.push(buffer.buffer().handle());
+            offsets_vk.push(buffer.offset());
+            sizes_vk.push(buffer.size());
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext)(
+            self.handle,
+            first_binding,
+            buffers_vk.len() as u32,
+            buffers_vk.as_ptr(),
+            offsets_vk.as_ptr(),
+            sizes_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) {
+        let (counter_buffers_vk, counter_buffer_offsets_vk) = counter_buffers_vk(counter_buffers);
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_begin_transform_feedback_ext)(
+            self.handle,
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            if counter_buffers_vk.is_empty() {
+                ptr::null()
+            } else {
+                counter_buffers_vk.as_ptr()
+            },
+            if counter_buffer_offsets_vk.is_empty() {
+                ptr::null()
+            } else {
+                counter_buffer_offsets_vk.as_ptr()
+            },
+        );
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) {
+        let (counter_buffers_vk, counter_buffer_offsets_vk) = counter_buffers_vk(counter_buffers);
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_end_transform_feedback_ext)(
+            self.handle,
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            if counter_buffers_vk.is_empty() {
+                ptr::null()
+            } else {
+                counter_buffers_vk.as_ptr()
+            },
+            if counter_buffer_offsets_vk.is_empty() {
+                ptr::null()
+            } else {
+                counter_buffer_offsets_vk.as_ptr()
+            },
+        );
+    }
+
+    /// Calls `vkCmdDrawIndirectByteCountEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_byte_count(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: &Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) {
+        debug_assert!(counter_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_draw_indirect_byte_count_ext)(
+            self.handle,
+            instance_count,
+            first_instance,
+            counter_buffer.buffer().handle(),
+            counter_buffer.offset(),
+            counter_offset,
+            vertex_stride,
+        );
+    }
+}
+
+// A counter buffer that is `None` is passed to Vulkan as a null handle, which means that there is
+// no counter for that binding.
+fn counter_buffers_vk(
+    counter_buffers: &[Option<Subbuffer<u32>>],
+) -> (SmallVec<[ash::vk::Buffer; 4]>, SmallVec<[DeviceSize; 4]>) {
+    counter_buffers
+        .iter()
+        .map(|counter_buffer| match counter_buffer {
+            Some(counter_buffer) => (counter_buffer.buffer().handle(), counter_buffer.offset()),
+            None => (ash::vk::Buffer::null(), 0),
+        })
+        .unzip()
+}
+
+/// Error that can happen when recording a transform feedback command.
+#[derive(Clone, Debug)]
+pub enum TransformFeedbackError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// An error that can happen for any draw command.
+    PipelineExecutionError(PipelineExecutionError),
+
+    /// Transform feedback is already active.
+    AlreadyActive,
+
+    /// A transform feedback buffer is missing the `transform_feedback_buffer` usage.
+    BufferMissingUsage {
+        index: u32,
+    },
+
+    /// The bound graphics pipeline writes to a transform feedback binding that has no buffer
+    /// bound to it.
+    BufferNotBound {
+        binding: u32,
+    },
+
+    /// The offset of a transform feedback buffer is not a multiple of 4.
+    BufferOffsetNotAligned {
+        index: u32,
+    },
+
+    /// A transform feedback buffer is too small to hold even a single vertex of the outputs that
+    /// the bound graphics pipeline writes to it.
+    BufferTooSmall {
+        binding: u32,
+        required_size: DeviceSize,
+        actual_size: DeviceSize,
+    },
+
+    /// A counter buffer is missing the `transform_feedback_counter_buffer` usage.
+    CounterBufferMissingUsage {
+        index: u32,
+    },
+
+    /// The [`transform_feedback_draw`](crate::device::Properties::transform_feedback_draw)
+    /// property is not supported by the physical device.
+    DrawNotSupported,
+
+    /// Operation forbidden outside of a render pass.
+    ForbiddenOutsideRenderPass,
+
+    /// Operation forbidden inside a render pass instance that uses multiview.
+    ForbiddenWithMultiview,
+
+    /// The maximum number of transform feedback buffers has been exceeded.
+    MaxTransformFeedbackBuffersExceeded {
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
+    /// The size of a transform feedback buffer exceeds the
+    /// [`max_transform_feedback_buffer_size`](crate::device::Properties::max_transform_feedback_buffer_size)
+    /// limit.
+    MaxTransformFeedbackBufferSizeExceeded {
+        index: u32,
+        max: DeviceSize,
+        obtained: DeviceSize,
+    },
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// Transform feedback is not active.
+    NotActive,
+
+    /// The last pre-rasterization shader of the bound graphics pipeline does not capture any
+    /// outputs with transform feedback.
+    PipelineNoTransformFeedback,
+
+    /// No graphics pipeline is currently bound.
+    PipelineNotBound,
+
+    /// The vertex stride is not a multiple of 4.
+    VertexStrideNotAligned {
+        vertex_stride: u32,
+    },
+
+    /// The vertex stride is zero, or exceeds the
+    /// [`max_transform_feedback_buffer_data_stride`](crate::device::Properties::max_transform_feedback_buffer_data_stride)
+    /// limit.
+    VertexStrideOutOfRange {
+        vertex_stride: u32,
+        max: u32,
+    },
+}
+
+impl Error for TransformFeedbackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            Self::PipelineExecutionError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TransformFeedbackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::PipelineExecutionError(_) => write!(f, "a PipelineExecutionError"),
+            Self::AlreadyActive => write!(f, "transform feedback is already active"),
+            Self::BufferMissingUsage { index } => write!(
+                f,
+                "transform feedback buffer {} is missing the `transform_feedback_buffer` usage",
+                index,
+            ),
+            Self::BufferNotBound { binding } => write!(
+                f,
+                "the bound graphics pipeline writes to transform feedback binding {}, but no \
+                buffer is bound to it",
+                binding,
+            ),
+            Self::BufferOffsetNotAligned { index } => write!(
+                f,
+                "the offset of transform feedback buffer {} is not a multiple of 4",
+                index,
+            ),
+            Self::BufferTooSmall {
+                binding,
+                required_size,
+                actual_size,
+            } => write!(
+                f,
+                "the buffer bound to transform feedback binding {} is {} bytes in size, but the \
+                bound graphics pipeline requires at least {} bytes",
+                binding, actual_size, required_size,
+            ),
+            Self::CounterBufferMissingUsage { index } => write!(
+                f,
+                "counter buffer {} is missing the `transform_feedback_counter_buffer` usage",
+                index,
+            ),
+            Self::DrawNotSupported => write!(
+                f,
+                "the `transform_feedback_draw` property is not supported by the physical device",
+            ),
+            Self::ForbiddenOutsideRenderPass => {
+                write!(f, "operation forbidden outside of a render pass")
+            }
+            Self::ForbiddenWithMultiview => write!(
+                f,
+                "operation forbidden inside a render pass instance that uses multiview",
+            ),
+            Self::MaxTransformFeedbackBuffersExceeded { max, obtained } => write!(
+                f,
+                "the maximum number of transform feedback buffers has been exceeded (max: {}, \
+                obtained: {})",
+                max, obtained,
+            ),
+            Self::MaxTransformFeedbackBufferSizeExceeded {
+                index,
+                max,
+                obtained,
+            } => write!(
+                f,
+                "the size of transform feedback buffer {} ({}) exceeds the \
+                `max_transform_feedback_buffer_size` limit ({})",
+                index, obtained, max,
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::NotActive => write!(f, "transform feedback is not active"),
+            Self::PipelineNoTransformFeedback => write!(
+                f,
+                "the last pre-rasterization shader of the bound graphics pipeline does not \
+                capture any outputs with transform feedback",
+            ),
+            Self::PipelineNotBound => write!(f, "no graphics pipeline is currently bound"),
+            Self::VertexStrideNotAligned { vertex_stride } => write!(
+                f,
+                "the vertex stride ({}) is not a multiple of 4",
+                vertex_stride,
+            ),
+            Self::VertexStrideOutOfRange { vertex_stride, max } => write!(
+                f,
+                "the vertex stride ({}) is zero, or exceeds the \
+                `max_transform_feedback_buffer_data_stride` limit ({})",
+                vertex_stride, max,
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for TransformFeedbackError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+impl From<PipelineExecutionError> for TransformFeedbackError {
+    fn from(err: PipelineExecutionError) -> Self {
+        Self::PipelineExecutionError(err)
+    }
+}
+
+impl From<RequirementNotMet> for TransformFeedbackError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo},
+        command_buffer::{allocator::StandardCommandBufferAllocator, CommandBufferUsage},
+        memory::allocator::{AllocationCreateInfo, MemoryUsage, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let buffer = Buffer::from_data(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Upload,
+                ..Default::default()
+            },
+            0u32,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.bind_transform_feedback_buffers(0, [buffer.as_bytes().clone()]),
+            Err(TransformFeedbackError::RequirementNotMet { .. }),
+        ));
+        assert!(matches!(
+            cbb.begin_transform_feedback(0, [Some(buffer.clone())]),
+            Err(TransformFeedbackError::RequirementNotMet { .. }),
+        ));
+        assert!(matches!(
+            cbb.end_transform_feedback(0, [Some(buffer.clone())]),
+            Err(TransformFeedbackError::RequirementNotMet { .. }),
+        ));
+        assert!(matches!(
+            cbb.draw_indirect_byte_count(1, 0, buffer, 0, 4),
+            Err(TransformFeedbackError::RequirementNotMet { .. }),
+        ));
+    }
+
+    #[test]
+    fn bind_buffers_missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!(transform_feedback; ext_transform_feedback);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let buffer = Buffer::new_slice::<u8>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Upload,
+                ..Default::default()
+            },
+            16,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.bind_transform_feedback_buffers(0, [buffer]),
+            Err(TransformFeedbackError::BufferMissingUsage { index: 0 }),
+        ));
+    }
+
+    #[test]
+    fn bind_buffers_offset_not_aligned() {
+        let (device, queue) = gfx_dev_and_queue!(transform_feedback; ext_transform_feedback);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let buffer = Buffer::new_slice::<u8>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFORM_FEEDBACK_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Upload,
+                ..Default::default()
+            },
+            16,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.bind_transform_feedback_buffers(0, [buffer.clone().slice(4..), buffer.slice(2..)]),
+            Err(TransformFeedbackError::BufferOffsetNotAligned { index: 1 }),
+        ));
+    }
+
+    #[test]
+    fn counter_buffers() {
+        let (device, queue) = gfx_dev_and_queue!(transform_feedback; ext_transform_feedback);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let max_transform_feedback_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let create_buffer = |usage| {
+            Buffer::from_data(
+                &memory_allocator,
+                BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    usage: MemoryUsage::Upload,
+                    ..Default::default()
+                },
+                0u32,
+            )
+            .unwrap()
+        };
+        let counter_buffer = create_buffer(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER);
+        let other_buffer = create_buffer(BufferUsage::TRANSFORM_FEEDBACK_BUFFER);
+
+        assert!(cbb
+            .validate_counter_buffers(0, &[Some(counter_buffer.clone()), None])
+            .is_ok());
+        assert!(matches!(
+            cbb.validate_counter_buffers(max_transform_feedback_buffers, &[None]),
+            Err(TransformFeedbackError::MaxTransformFeedbackBuffersExceeded { max, obtained })
+                if max == max_transform_feedback_buffers
+                    && obtained == max_transform_feedback_buffers + 1,
+        ));
+        assert!(matches!(
+            cbb.validate_counter_buffers(0, &[None, Some(other_buffer)]),
+            Err(TransformFeedbackError::CounterBufferMissingUsage { index: 1 }),
+        ));
+
+        // Counter buffers can only be passed inside a render pass, while transform feedback is
+        // active.
+        assert!(matches!(
+            cbb.validate_begin_transform_feedback(0, &[Some(counter_buffer.clone())]),
+            Err(TransformFeedbackError::ForbiddenOutsideRenderPass),
+        ));
+        assert!(matches!(
+            cbb.validate_end_transform_feedback(0, &[Some(counter_buffer)]),
+            Err(TransformFeedbackError::NotActive),
+        ));
+    }
+}
@@ -108,6 +108,7 @@
 //! [pipeline barriers]: CommandBufferBuilder::pipeline_barrier
 //! [`GpuFuture`]: crate::sync::GpuFuture
 
+pub(crate) use self::auto::LayoutTransitionCommandBuffer;
 #[doc(no_inline)]
 pub(crate) use self::standard::{PrimaryCommandBuffer, SecondaryCommandBuffer};
 pub use self::{
@@ -117,19 +118,24 @@ pub use self::{
     },
     commands::{
         clear::{ClearColorImageInfo, ClearDepthStencilImageInfo, ClearError},
+        conditional_rendering::ConditionalRenderingError,
         copy::{
             BlitImageInfo, BufferCopy, BufferImageCopy, CopyBufferInfo, CopyBufferInfoTyped,
             CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo,
             CopyImageToBufferInfo, ImageBlit, ImageCopy, ImageResolve, ResolveImageInfo,
         },
         debug::DebugUtilsError,
+        mipmaps::GenerateMipmapsError,
         pipeline::PipelineExecutionError,
         query::QueryError,
         render_pass::{
             ClearAttachment, ClearRect, RenderPassBeginInfo, RenderPassError,
-            RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo,
+            RenderingAttachmentInfo, RenderingAttachmentResolveInfo,
+            RenderingFragmentShadingRateAttachmentInfo, RenderingInfo,
         },
         secondary::ExecuteCommandsError,
+        sync::EventCommandError,
+        transform_feedback::TransformFeedbackError,
     },
     traits::{
         CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
@@ -179,6 +185,21 @@ pub struct DrawIndexedIndirectCommand {
     pub first_instance: u32,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawInfo {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawIndexedInfo {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub vertex_offset: i32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DispatchIndirectCommand {
@@ -596,16 +617,21 @@ impl From<ResourceUseRef> for SecondaryResourceUseRef {
 pub enum ResourceInCommand {
     ColorAttachment { index: u32 },
     ColorResolveAttachment { index: u32 },
+    ConditionalRenderingPredicate,
     DepthStencilAttachment,
     DepthStencilResolveAttachment,
+    DescriptorBuffer { set: u32 },
     DescriptorSet { set: u32, binding: u32, index: u32 },
     Destination,
+    FragmentShadingRateAttachment,
     FramebufferAttachment { index: u32 },
     ImageMemoryBarrier { index: u32 },
     IndexBuffer,
     IndirectBuffer,
     SecondaryCommandBuffer { index: u32 },
     Source,
+    TransformFeedbackBuffer { binding: u32 },
+    TransformFeedbackCounterBuffer { index: u32 },
     VertexBuffer { binding: u32 },
 }
 
@@ -879,7 +879,14 @@ where
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        let inline_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
+        for ((info, inline_info), write) in
+            infos.iter().zip(inline_infos.iter()).zip(writes.iter_mut())
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -893,6 +900,10 @@ where
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -0,0 +1,220 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    CommandBufferBuilder, ConditionalRenderingError, ConditionalRenderingState, RenderPassStateType,
+};
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{allocator::CommandBufferAllocator, ResourceInCommand, ResourceUseRef},
+    device::{DeviceOwned, QueueFlags},
+    sync::PipelineStageAccess,
+    RequiresOneOf, VulkanObject,
+};
+
+impl<L, A> CommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a conditional rendering block.
+    ///
+    /// Until [`end_conditional_rendering`] is called, draw and dispatch commands are discarded if
+    /// the 32-bit value in `predicate` is zero, or non-zero if `inverted` is `true`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `predicate` was not created from the same device as `self`.
+    ///
+    /// [`end_conditional_rendering`]: Self::end_conditional_rendering
+    #[inline]
+    pub fn begin_conditional_rendering(
+        &mut self,
+        predicate: Subbuffer<u32>,
+        inverted: bool,
+    ) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_begin_conditional_rendering(&predicate, inverted)?;
+
+        unsafe { Ok(self.begin_conditional_rendering_unchecked(predicate, inverted)) }
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        predicate: &Subbuffer<u32>,
+        _inverted: bool,
+    ) -> Result<(), ConditionalRenderingError> {
+        let device = self.device();
+
+        if !device.enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::begin_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-None-01980
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(ConditionalRenderingError::AlreadyActive);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-parameter
+        assert_eq!(device, predicate.device());
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-01982
+        if !predicate
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(ConditionalRenderingError::PredicateMissingUsage);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01983
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01984
+        // Guaranteed because we take `Subbuffer<u32>`
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_conditional_rendering_unchecked(
+        &mut self,
+        predicate: Subbuffer<u32>,
+        inverted: bool,
+    ) -> &mut Self {
+        let mut flags = ash::vk::ConditionalRenderingFlagsEXT::empty();
+
+        if inverted {
+            flags |= ash::vk::ConditionalRenderingFlagsEXT::INVERTED;
+        }
+
+        let begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: predicate.buffer().handle(),
+            offset: predicate.offset(),
+            flags,
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(self.handle(), &begin_info);
+
+        let command_index = self.next_command_index;
+        let command_name = "begin_conditional_rendering";
+        let use_ref = ResourceUseRef {
+            command_index,
+            command_name,
+            resource_in_command: ResourceInCommand::ConditionalRenderingPredicate,
+            secondary_use_ref: None,
+        };
+
+        let range = predicate.offset()..predicate.offset() + predicate.size();
+        self.resources_usage_state.record_buffer_access(
+            &use_ref,
+            predicate.buffer(),
+            range,
+            PipelineStageAccess::ConditionalRendering_ConditionalRenderingRead,
+        );
+
+        self.builder_state.conditional_rendering = Some(ConditionalRenderingState {
+            predicate: predicate.clone(),
+            subpass_index: self.current_subpass_index(),
+        });
+
+        self.resources.push(Box::new(predicate));
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Ends the active conditional rendering block.
+    #[inline]
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe { Ok(self.end_conditional_rendering_unchecked()) }
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), ConditionalRenderingError> {
+        if !self.device().enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::end_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01985
+        let state = self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .ok_or(ConditionalRenderingError::NotActive)?;
+
+        match (state.subpass_index, self.current_subpass_index()) {
+            (None, None) => (),
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+            (None, Some(_)) => return Err(ConditionalRenderingError::ForbiddenInsideRenderPass),
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+            (Some(begun), current) => {
+                if current != Some(begun) {
+                    return Err(ConditionalRenderingError::SubpassMismatch);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_conditional_rendering_unchecked(&mut self) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle());
+
+        self.builder_state.conditional_rendering = None;
+
+        self.next_command_index += 1;
+        self
+    }
+
+    fn current_subpass_index(&self) -> Option<u32> {
+        self.builder_state
+            .render_pass
+            .as_ref()
+            .map(|state| match &state.render_pass {
+                RenderPassStateType::BeginRenderPass(state) => state.subpass.index(),
+                RenderPassStateType::BeginRendering(_) => 0,
+            })
+    }
+}
@@ -10,15 +10,23 @@
 use super::CommandBufferBuilder;
 use crate::{
     command_buffer::{
-        allocator::CommandBufferAllocator, commands::dynamic_state::SetDynamicStateError,
+        allocator::CommandBufferAllocator,
+        commands::dynamic_state::{
+            validate_vertex_input_state, vertex_input_state_to_vk, SetDynamicStateError,
+        },
     },
     device::{DeviceOwned, QueueFlags},
+    image::SampleCount,
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, BlendFactor, BlendOp, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
+            fragment_shading_rate::{FragmentShadingRate, FragmentShadingRateCombinerOp},
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            rasterization::{
+                CullMode, DepthBias, FrontFace, LineRasterizationMode, LineStipple, PolygonMode,
+            },
+            vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -53,6 +61,143 @@ where
         Ok(())
     }
 
+    /// Sets whether alpha-to-coverage is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_alpha_to_coverage_enable`] feature is not enabled
+    ///   on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    ///
+    /// [`extended_dynamic_state3_alpha_to_coverage_enable`]: crate::device::Features::extended_dynamic_state3_alpha_to_coverage_enable
+    #[inline]
+    pub fn set_alpha_to_coverage_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_coverage_enable(enable).unwrap();
+
+        unsafe { self.set_alpha_to_coverage_enable_unchecked(enable) }
+    }
+
+    fn validate_set_alpha_to_coverage_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToCoverageEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-extendedDynamicState3AlphaToCoverageEnable-07343
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_coverage_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_alpha_to_coverage_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_coverage_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_coverage_enable_ext)(self.handle(), enable.into());
+
+        self.builder_state.alpha_to_coverage_enable = Some(enable);
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Sets whether alpha-to-one is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_alpha_to_one_enable`] feature is not enabled on
+    ///   the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `enable` is `true` and the [`alpha_to_one`] feature is not enabled on the
+    ///   device.
+    ///
+    /// [`extended_dynamic_state3_alpha_to_one_enable`]: crate::device::Features::extended_dynamic_state3_alpha_to_one_enable
+    /// [`alpha_to_one`]: crate::device::Features::alpha_to_one
+    #[inline]
+    pub fn set_alpha_to_one_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_one_enable(enable).unwrap();
+
+        unsafe { self.set_alpha_to_one_enable_unchecked(enable) }
+    }
+
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToOneEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-extendedDynamicState3AlphaToOneEnable-07345
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_one_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_alpha_to_one_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_one_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-alphaToOne-07607
+        if enable && !self.device().enabled_features().alpha_to_one {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["alpha_to_one"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_one_enable_ext)(self.handle(), enable.into());
+
+        self.builder_state.alpha_to_one_enable = Some(enable);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     ///
     /// # Panics
@@ -96,6 +241,305 @@ where
         self
     }
 
+    /// Sets whether blending is enabled for future draw calls, for each color attachment
+    /// starting at `first_attachment`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_color_blend_enable`] feature is not enabled on the
+    ///   device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the highest attachment being set is not less than the [`max_color_attachments`]
+    ///   device property.
+    ///
+    /// [`extended_dynamic_state3_color_blend_enable`]: crate::device::Features::extended_dynamic_state3_color_blend_enable
+    /// [`max_color_attachments`]: crate::device::Properties::max_color_attachments
+    #[inline]
+    pub fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: impl IntoIterator<Item = bool>,
+    ) -> &mut Self {
+        let enables: SmallVec<[bool; 4]> = enables.into_iter().collect();
+        self.validate_set_color_blend_enable(first_attachment, &enables)
+            .unwrap();
+
+        unsafe { self.set_color_blend_enable_unchecked(first_attachment, enables) }
+    }
+
+    fn validate_set_color_blend_enable(
+        &self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ColorBlendEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetColorBlendEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetColorBlendEnableEXT-extendedDynamicState3ColorBlendEnable-07355
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_color_blend_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_color_blend_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID?
+        if first_attachment + enables.len() as u32 > properties.max_color_attachments {
+            return Err(SetDynamicStateError::MaxColorAttachmentsExceeded {
+                provided: first_attachment + enables.len() as u32,
+                max: properties.max_color_attachments,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: impl IntoIterator<Item = bool>,
+    ) -> &mut Self {
+        let enables: SmallVec<[bool; 4]> = enables.into_iter().collect();
+
+        if enables.is_empty() {
+            return self;
+        }
+
+        let enables_vk = enables
+            .iter()
+            .copied()
+            .map(ash::vk::Bool32::from)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_color_blend_enable_ext)(
+            self.handle(),
+            first_attachment,
+            enables_vk.len() as u32,
+            enables_vk.as_ptr(),
+        );
+
+        for (num, &enable) in enables.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_blend_enable.insert(num, enable);
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Sets the blend equation for future draw calls, for each color attachment starting at
+    /// `first_attachment`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_color_blend_equation`] feature is not enabled on
+    ///   the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the highest attachment being set is not less than the [`max_color_attachments`]
+    ///   device property.
+    /// - Panics if an element of `equations` uses a `BlendFactor::Src1*` blend factor and the
+    ///   [`dual_src_blend`] feature is not enabled on the device.
+    /// - Panics if an element of `equations` uses an advanced blend operation.
+    ///
+    /// [`extended_dynamic_state3_color_blend_equation`]: crate::device::Features::extended_dynamic_state3_color_blend_equation
+    /// [`max_color_attachments`]: crate::device::Properties::max_color_attachments
+    /// [`dual_src_blend`]: crate::device::Features::dual_src_blend
+    #[inline]
+    pub fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: impl IntoIterator<Item = AttachmentBlend>,
+    ) -> &mut Self {
+        let equations: SmallVec<[AttachmentBlend; 4]> = equations.into_iter().collect();
+        self.validate_set_color_blend_equation(first_attachment, &equations)
+            .unwrap();
+
+        unsafe { self.set_color_blend_equation_unchecked(first_attachment, equations) }
+    }
+
+    fn validate_set_color_blend_equation(
+        &self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ColorBlendEquation)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetColorBlendEquationEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetColorBlendEquationEXT-extendedDynamicState3ColorBlendEquation-07356
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_equation
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_color_blend_equation`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_color_blend_equation"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID?
+        if first_attachment + equations.len() as u32 > properties.max_color_attachments {
+            return Err(SetDynamicStateError::MaxColorAttachmentsExceeded {
+                provided: first_attachment + equations.len() as u32,
+                max: properties.max_color_attachments,
+            });
+        }
+
+        for (num, equation) in equations.iter().enumerate() {
+            let attachment = num as u32 + first_attachment;
+            let &AttachmentBlend {
+                color_op,
+                color_source,
+                color_destination,
+                alpha_op,
+                alpha_source,
+                alpha_destination,
+            } = equation;
+
+            // VUID-VkColorBlendEquationEXT-colorBlendOp-parameter
+            color_op.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-srcColorBlendFactor-parameter
+            color_source.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-dstColorBlendFactor-parameter
+            color_destination.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-alphaBlendOp-parameter
+            alpha_op.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-srcAlphaBlendFactor-parameter
+            alpha_source.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-dstAlphaBlendFactor-parameter
+            alpha_destination.validate_device(self.device())?;
+
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07357
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07358
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07359
+            // VUID-VkColorBlendEquationEXT-dualSrcBlend-07360
+            if !self.device().enabled_features().dual_src_blend
+                && [
+                    color_source,
+                    color_destination,
+                    alpha_source,
+                    alpha_destination,
+                ]
+                .into_iter()
+                .any(|blend_factor| {
+                    matches!(
+                        blend_factor,
+                        BlendFactor::Src1Color
+                            | BlendFactor::OneMinusSrc1Color
+                            | BlendFactor::Src1Alpha
+                            | BlendFactor::OneMinusSrc1Alpha
+                    )
+                })
+            {
+                return Err(SetDynamicStateError::RequirementNotMet {
+                    required_for: "`equations` has an element where `color_source`, \
+                        `color_destination`, `alpha_source` or `alpha_destination` is \
+                        `BlendFactor::Src1*`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["dual_src_blend"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkColorBlendEquationEXT-colorBlendOp-07361
+            if [color_op, alpha_op].into_iter().any(|blend_op| {
+                !matches!(
+                    blend_op,
+                    BlendOp::Add
+                        | BlendOp::Subtract
+                        | BlendOp::ReverseSubtract
+                        | BlendOp::Min
+                        | BlendOp::Max
+                )
+            }) {
+                return Err(SetDynamicStateError::ColorBlendEquationAdvancedBlendOp { attachment });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_equation_unchecked(
+        &mut self,
+        first_attachment: u32,
+        equations: impl IntoIterator<Item = AttachmentBlend>,
+    ) -> &mut Self {
+        let equations: SmallVec<[AttachmentBlend; 4]> = equations.into_iter().collect();
+
+        if equations.is_empty() {
+            return self;
+        }
+
+        let equations_vk = equations
+            .iter()
+            .copied()
+            .map(ash::vk::ColorBlendEquationEXT::from)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_color_blend_equation_ext)(
+            self.handle(),
+            first_attachment,
+            equations_vk.len() as u32,
+            equations_vk.as_ptr(),
+        );
+
+        for (num, &equation) in equations.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state
+                .color_blend_equation
+                .insert(num, equation);
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets whether dynamic color writes should be enabled for each attachment in the
     /// framebuffer.
     ///
@@ -200,6 +644,117 @@ where
         self
     }
 
+    /// Sets the color write mask for future draw calls, for each color attachment starting at
+    /// `first_attachment`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_color_write_mask`] feature is not enabled on the
+    ///   device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the highest attachment being set is not less than the [`max_color_attachments`]
+    ///   device property.
+    ///
+    /// [`extended_dynamic_state3_color_write_mask`]: crate::device::Features::extended_dynamic_state3_color_write_mask
+    /// [`max_color_attachments`]: crate::device::Properties::max_color_attachments
+    #[inline]
+    pub fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: impl IntoIterator<Item = ColorComponents>,
+    ) -> &mut Self {
+        let color_write_masks: SmallVec<[ColorComponents; 4]> =
+            color_write_masks.into_iter().collect();
+        self.validate_set_color_write_mask(first_attachment, &color_write_masks)
+            .unwrap();
+
+        unsafe { self.set_color_write_mask_unchecked(first_attachment, color_write_masks) }
+    }
+
+    fn validate_set_color_write_mask(
+        &self,
+        first_attachment: u32,
+        color_write_masks: &[ColorComponents],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::ColorWriteMask)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetColorWriteMaskEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetColorWriteMaskEXT-extendedDynamicState3ColorWriteMask-07364
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_write_mask
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_color_write_mask`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_color_write_mask"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID?
+        if first_attachment + color_write_masks.len() as u32 > properties.max_color_attachments {
+            return Err(SetDynamicStateError::MaxColorAttachmentsExceeded {
+                provided: first_attachment + color_write_masks.len() as u32,
+                max: properties.max_color_attachments,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: impl IntoIterator<Item = ColorComponents>,
+    ) -> &mut Self {
+        let color_write_masks: SmallVec<[ColorComponents; 4]> =
+            color_write_masks.into_iter().collect();
+
+        if color_write_masks.is_empty() {
+            return self;
+        }
+
+        let color_write_masks_vk = color_write_masks
+            .iter()
+            .copied()
+            .map(ash::vk::ColorComponentFlags::from)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_color_write_mask_ext)(
+            self.handle(),
+            first_attachment,
+            color_write_masks_vk.len() as u32,
+            color_write_masks_vk.as_ptr(),
+        );
+
+        for (num, &color_write_mask) in color_write_masks.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state
+                .color_write_mask
+                .insert(num, color_write_mask);
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic cull mode for future draw calls.
     ///
     /// # Panics
@@ -550,6 +1105,79 @@ where
         self
     }
 
+    /// Sets whether depth clamping is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_depth_clamp_enable`] feature is not enabled on the
+    ///   device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `enable` is `true` and the [`depth_clamp`] feature is not enabled on the device.
+    ///
+    /// [`extended_dynamic_state3_depth_clamp_enable`]: crate::device::Features::extended_dynamic_state3_depth_clamp_enable
+    /// [`depth_clamp`]: crate::device::Features::depth_clamp
+    #[inline]
+    pub fn set_depth_clamp_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_depth_clamp_enable(enable).unwrap();
+
+        unsafe { self.set_depth_clamp_enable_unchecked(enable) }
+    }
+
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::DepthClampEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDepthClampEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-extendedDynamicState3DepthClampEnable-07448
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clamp_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_depth_clamp_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_depth_clamp_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-depthClamp-07449
+        if enable && !self.device().enabled_features().depth_clamp {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["depth_clamp"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_depth_clamp_enable_ext)(self.handle(), enable.into());
+
+        self.builder_state.depth_clamp_enable = Some(enable);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic depth compare op for future draw calls.
     ///
     /// # Panics
@@ -869,17 +1497,193 @@ where
         );
 
         let fns = self.device().fns();
-        (fns.ext_discard_rectangles.cmd_set_discard_rectangle_ext)(
-            self.handle(),
-            first_rectangle,
-            rectangles_vk.len() as u32,
-            rectangles_vk.as_ptr(),
+        (fns.ext_discard_rectangles.cmd_set_discard_rectangle_ext)(
+            self.handle(),
+            first_rectangle,
+            rectangles_vk.len() as u32,
+            rectangles_vk.as_ptr(),
+        );
+
+        for (num, rectangle) in rectangles.iter().enumerate() {
+            let num = num as u32 + first_rectangle;
+            self.builder_state.discard_rectangle.insert(num, *rectangle);
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Sets the dynamic fragment shading rate and combiner operations for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if none of the [`pipeline_fragment_shading_rate`],
+    ///   [`primitive_fragment_shading_rate`] or [`attachment_fragment_shading_rate`] features are
+    ///   enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if a component of `fragment_size` is not 1, 2 or 4, or is not 1 and the
+    ///   `pipeline_fragment_shading_rate` feature is not enabled.
+    /// - Panics if `combiner_ops[0]` is not `Keep` and the `primitive_fragment_shading_rate`
+    ///   feature is not enabled, or if `combiner_ops[1]` is not `Keep` and the
+    ///   `attachment_fragment_shading_rate` feature is not enabled.
+    /// - Panics if an element of `combiner_ops` is not `Keep` or `Replace` and the
+    ///   [`fragment_shading_rate_non_trivial_combiner_ops`] device property is `false`.
+    ///
+    /// [`pipeline_fragment_shading_rate`]: crate::device::Features::pipeline_fragment_shading_rate
+    /// [`primitive_fragment_shading_rate`]: crate::device::Features::primitive_fragment_shading_rate
+    /// [`attachment_fragment_shading_rate`]: crate::device::Features::attachment_fragment_shading_rate
+    /// [`fragment_shading_rate_non_trivial_combiner_ops`]: crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops
+    #[inline]
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)
+            .unwrap();
+
+        unsafe { self.set_fragment_shading_rate_unchecked(fragment_size, combiner_ops) }
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::FragmentShadingRate)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+        let enabled_features = device.enabled_features();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04509
+        if !(enabled_features.pipeline_fragment_shading_rate
+            || enabled_features.primitive_fragment_shading_rate
+            || enabled_features.attachment_fragment_shading_rate)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_fragment_shading_rate`",
+                requires_one_of: RequiresOneOf {
+                    features: &[
+                        "pipeline_fragment_shading_rate",
+                        "primitive_fragment_shading_rate",
+                        "attachment_fragment_shading_rate",
+                    ],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04513
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04514
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04515
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04516
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04517
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04518
+        if !fragment_size.iter().all(|&size| matches!(size, 1 | 2 | 4)) {
+            return Err(
+                SetDynamicStateError::FragmentShadingRateInvalidFragmentSize { fragment_size },
+            );
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04507
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04508
+        if fragment_size != [1, 1] && !enabled_features.pipeline_fragment_shading_rate {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_fragment_shading_rate` where \
+                    `fragment_size` is not `[1, 1]`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-combinerOps-parameter
+        for op in combiner_ops {
+            op.validate_device(device)?;
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-primitiveFragmentShadingRate-04510
+        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+            && !enabled_features.primitive_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_fragment_shading_rate` where \
+                    `combiner_ops[0]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["primitive_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-attachmentFragmentShadingRate-04511
+        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+            && !enabled_features.attachment_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_fragment_shading_rate` where \
+                    `combiner_ops[1]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["attachment_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-fragmentSizeNonTrivialCombinerOps-04512
+        if !device
+            .physical_device()
+            .properties()
+            .fragment_shading_rate_non_trivial_combiner_ops
+            .unwrap_or(false)
+            && !combiner_ops.iter().all(|&op| {
+                matches!(
+                    op,
+                    FragmentShadingRateCombinerOp::Keep | FragmentShadingRateCombinerOp::Replace
+                )
+            })
+        {
+            return Err(SetDynamicStateError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_fragment_shading_rate_unchecked(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        let fragment_size_vk = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops_vk: [ash::vk::FragmentShadingRateCombinerOpKHR; 2] =
+            [combiner_ops[0].into(), combiner_ops[1].into()];
+
+        let fns = self.device().fns();
+        (fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr)(
+            self.handle(), &fragment_size_vk, &combiner_ops_vk
         );
 
-        for (num, rectangle) in rectangles.iter().enumerate() {
-            let num = num as u32 + first_rectangle;
-            self.builder_state.discard_rectangle.insert(num, *rectangle);
-        }
+        self.builder_state.fragment_shading_rate = Some(FragmentShadingRate {
+            fragment_size,
+            combiner_ops,
+        });
 
         self.next_command_index += 1;
         self
@@ -955,6 +1759,126 @@ where
         self
     }
 
+    /// Sets the line rasterization mode for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_line_rasterization_mode`] feature is not enabled
+    ///   on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the feature corresponding to `line_rasterization_mode` is not enabled on the
+    ///   device.
+    ///
+    /// [`extended_dynamic_state3_line_rasterization_mode`]: crate::device::Features::extended_dynamic_state3_line_rasterization_mode
+    #[inline]
+    pub fn set_line_rasterization_mode(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        self.validate_set_line_rasterization_mode(line_rasterization_mode)
+            .unwrap();
+
+        unsafe { self.set_line_rasterization_mode_unchecked(line_rasterization_mode) }
+    }
+
+    fn validate_set_line_rasterization_mode(
+        &self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::LineRasterizationMode)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetLineRasterizationModeEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetLineRasterizationModeEXT-extendedDynamicState3LineRasterizationMode-07417
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_line_rasterization_mode
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_line_rasterization_mode`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_line_rasterization_mode"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-parameter
+        line_rasterization_mode.validate_device(self.device())?;
+
+        match line_rasterization_mode {
+            LineRasterizationMode::Default => (),
+            LineRasterizationMode::Rectangular => {
+                // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07418
+                if !self.device().enabled_features().rectangular_lines {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`line_rasterization_mode` is \
+                            `LineRasterizationMode::Rectangular`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["rectangular_lines"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            LineRasterizationMode::Bresenham => {
+                // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07419
+                if !self.device().enabled_features().bresenham_lines {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`line_rasterization_mode` is \
+                            `LineRasterizationMode::Bresenham`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["bresenham_lines"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            LineRasterizationMode::RectangularSmooth => {
+                // VUID-vkCmdSetLineRasterizationModeEXT-lineRasterizationMode-07420
+                if !self.device().enabled_features().smooth_lines {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`line_rasterization_mode` is \
+                            `LineRasterizationMode::RectangularSmooth`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["smooth_lines"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_rasterization_mode_unchecked(
+        &mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_line_rasterization_mode_ext)(
+            self.handle(), line_rasterization_mode.into()
+        );
+
+        self.builder_state.line_rasterization_mode = Some(line_rasterization_mode);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic line stipple values for future draw calls.
     ///
     /// # Panics
@@ -1018,6 +1942,66 @@ where
         self
     }
 
+    /// Sets whether line stippling is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_line_stipple_enable`] feature is not enabled on
+    ///   the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    ///
+    /// [`extended_dynamic_state3_line_stipple_enable`]: crate::device::Features::extended_dynamic_state3_line_stipple_enable
+    #[inline]
+    pub fn set_line_stipple_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_line_stipple_enable(enable).unwrap();
+
+        unsafe { self.set_line_stipple_enable_unchecked(enable) }
+    }
+
+    fn validate_set_line_stipple_enable(&self, _enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::LineStippleEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetLineStippleEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetLineStippleEnableEXT-extendedDynamicState3LineStippleEnable-07421
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_line_stipple_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_line_stipple_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_line_stipple_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_stipple_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_line_stipple_enable_ext)(self.handle(), enable.into());
+
+        self.builder_state.line_stipple_enable = Some(enable);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic line width for future draw calls.
     ///
     /// # Panics
@@ -1141,6 +2125,65 @@ where
         self
     }
 
+    /// Sets whether logic operations are enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_logic_op_enable`] feature is not enabled on the
+    ///   device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    ///
+    /// [`extended_dynamic_state3_logic_op_enable`]: crate::device::Features::extended_dynamic_state3_logic_op_enable
+    #[inline]
+    pub fn set_logic_op_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_logic_op_enable(enable).unwrap();
+
+        unsafe { self.set_logic_op_enable_unchecked(enable) }
+    }
+
+    fn validate_set_logic_op_enable(&self, _enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::LogicOpEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetLogicOpEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetLogicOpEnableEXT-extendedDynamicState3LogicOpEnable-07365
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_logic_op_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_logic_op_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_logic_op_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_logic_op_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_logic_op_enable_ext)(self.handle(), enable.into());
+
+        self.builder_state.logic_op_enable = Some(enable);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic number of patch control points for future draw calls.
     ///
     /// # Panics
@@ -1161,12 +2204,107 @@ where
         unsafe { self.set_patch_control_points_unchecked(num) }
     }
 
-    fn validate_set_patch_control_points(&self, num: u32) -> Result<(), SetDynamicStateError> {
-        self.validate_pipeline_fixed_state(DynamicState::PatchControlPoints)?;
+    fn validate_set_patch_control_points(&self, num: u32) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::PatchControlPoints)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetPatchControlPointsEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetPatchControlPointsEXT-None-04873
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state2_patch_control_points
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_patch_control_points`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state2_patch_control_points"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
+        assert!(num > 0, "num must be greater than 0");
+
+        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
+        if num
+            > self
+                .device()
+                .physical_device()
+                .properties()
+                .max_tessellation_patch_size
+        {
+            return Err(SetDynamicStateError::MaxTessellationPatchSizeExceeded {
+                provided: num,
+                max: self
+                    .device()
+                    .physical_device()
+                    .properties()
+                    .max_tessellation_patch_size,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_patch_control_points_unchecked(&mut self, num: u32) -> &mut Self {
+        debug_assert!(
+            self.device()
+                .enabled_extensions()
+                .ext_extended_dynamic_state2
+        );
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state2
+            .cmd_set_patch_control_points_ext)(self.handle(), num);
+
+        self.builder_state.patch_control_points = Some(num);
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Sets the polygon mode for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_polygon_mode`] feature is not enabled on the
+    ///   device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `polygon_mode` is not `PolygonMode::Fill` and the [`fill_mode_non_solid`]
+    ///   feature is not enabled on the device.
+    /// - On portability subset devices, panics if `polygon_mode` is `PolygonMode::Point` and the
+    ///   [`point_polygons`] feature is not enabled on the device.
+    ///
+    /// [`extended_dynamic_state3_polygon_mode`]: crate::device::Features::extended_dynamic_state3_polygon_mode
+    /// [`fill_mode_non_solid`]: crate::device::Features::fill_mode_non_solid
+    /// [`point_polygons`]: crate::device::Features::point_polygons
+    #[inline]
+    pub fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.validate_set_polygon_mode(polygon_mode).unwrap();
+
+        unsafe { self.set_polygon_mode_unchecked(polygon_mode) }
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::PolygonMode)?;
 
         let queue_family_properties = self.queue_family_properties();
 
-        // VUID-vkCmdSetPatchControlPointsEXT-commandBuffer-cmdpool
+        // VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool
         if !queue_family_properties
             .queue_flags
             .intersects(QueueFlags::GRAPHICS)
@@ -1174,39 +2312,49 @@ where
             return Err(SetDynamicStateError::NotSupportedByQueueFamily);
         }
 
-        // VUID-vkCmdSetPatchControlPointsEXT-None-04873
+        // VUID-vkCmdSetPolygonModeEXT-extendedDynamicState3PolygonMode-07422
         if !self
             .device()
             .enabled_features()
-            .extended_dynamic_state2_patch_control_points
+            .extended_dynamic_state3_polygon_mode
         {
             return Err(SetDynamicStateError::RequirementNotMet {
-                required_for: "`CommandBufferBuilder::set_patch_control_points`",
+                required_for: "`CommandBufferBuilder::set_polygon_mode`",
                 requires_one_of: RequiresOneOf {
-                    features: &["extended_dynamic_state2_patch_control_points"],
+                    features: &["extended_dynamic_state3_polygon_mode"],
                     ..Default::default()
                 },
             });
         }
 
-        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
-        assert!(num > 0, "num must be greater than 0");
+        // VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter
+        polygon_mode.validate_device(self.device())?;
 
-        // VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874
-        if num
-            > self
-                .device()
-                .physical_device()
-                .properties()
-                .max_tessellation_patch_size
+        // VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424
+        if polygon_mode != PolygonMode::Fill
+            && !self.device().enabled_features().fill_mode_non_solid
         {
-            return Err(SetDynamicStateError::MaxTessellationPatchSizeExceeded {
-                provided: num,
-                max: self
-                    .device()
-                    .physical_device()
-                    .properties()
-                    .max_tessellation_patch_size,
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`polygon_mode` is not `PolygonMode::Fill`",
+                requires_one_of: RequiresOneOf {
+                    features: &["fill_mode_non_solid"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID?
+        if self.device().enabled_extensions().khr_portability_subset
+            && !self.device().enabled_features().point_polygons
+            && polygon_mode == PolygonMode::Point
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "this device is a portability subset device, and `polygon_mode` is \
+                    `PolygonMode::Point`",
+                requires_one_of: RequiresOneOf {
+                    features: &["point_polygons"],
+                    ..Default::default()
+                },
             });
         }
 
@@ -1214,17 +2362,14 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_patch_control_points_unchecked(&mut self, num: u32) -> &mut Self {
-        debug_assert!(
-            self.device()
-                .enabled_extensions()
-                .ext_extended_dynamic_state2
-        );
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
         let fns = self.device().fns();
-        (fns.ext_extended_dynamic_state2
-            .cmd_set_patch_control_points_ext)(self.handle(), num);
+        (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+            self.handle(),
+            polygon_mode.into(),
+        );
 
-        self.builder_state.patch_control_points = Some(num);
+        self.builder_state.polygon_mode = Some(polygon_mode);
 
         self.next_command_index += 1;
         self
@@ -1428,6 +2573,183 @@ where
         self
     }
 
+    /// Sets the number of rasterization samples for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_rasterization_samples`] feature is not enabled on
+    ///   the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    ///
+    /// [`extended_dynamic_state3_rasterization_samples`]: crate::device::Features::extended_dynamic_state3_rasterization_samples
+    #[inline]
+    pub fn set_rasterization_samples(&mut self, rasterization_samples: SampleCount) -> &mut Self {
+        self.validate_set_rasterization_samples(rasterization_samples)
+            .unwrap();
+
+        unsafe { self.set_rasterization_samples_unchecked(rasterization_samples) }
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::RasterizationSamples)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetRasterizationSamplesEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetRasterizationSamplesEXT-extendedDynamicState3RasterizationSamples-07414
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_samples
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_rasterization_samples`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_rasterization_samples"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetRasterizationSamplesEXT-rasterizationSamples-parameter
+        rasterization_samples.validate_device(self.device())?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_rasterization_samples_ext)(self.handle(), rasterization_samples.into());
+
+        self.builder_state.rasterization_samples = Some(rasterization_samples);
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Sets the vertex stream that is used for rasterization for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_rasterization_stream`] feature is not enabled on
+    ///   the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the [`transform_feedback`] feature is not enabled on the device.
+    /// - Panics if `rasterization_stream` is not less than the [`max_transform_feedback_streams`]
+    ///   device property.
+    /// - Panics if `rasterization_stream` is not 0 and the
+    ///   [`transform_feedback_rasterization_stream_select`] device property is `false`.
+    ///
+    /// [`extended_dynamic_state3_rasterization_stream`]: crate::device::Features::extended_dynamic_state3_rasterization_stream
+    /// [`transform_feedback`]: crate::device::Features::transform_feedback
+    /// [`max_transform_feedback_streams`]: crate::device::Properties::max_transform_feedback_streams
+    /// [`transform_feedback_rasterization_stream_select`]: crate::device::Properties::transform_feedback_rasterization_stream_select
+    #[inline]
+    pub fn set_rasterization_stream(&mut self, rasterization_stream: u32) -> &mut Self {
+        self.validate_set_rasterization_stream(rasterization_stream)
+            .unwrap();
+
+        unsafe { self.set_rasterization_stream_unchecked(rasterization_stream) }
+    }
+
+    fn validate_set_rasterization_stream(
+        &self,
+        rasterization_stream: u32,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::RasterizationStream)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetRasterizationStreamEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetRasterizationStreamEXT-extendedDynamicState3RasterizationStream-07410
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_stream
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_rasterization_stream`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_rasterization_stream"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetRasterizationStreamEXT-transformFeedback-07411
+        if !self.device().enabled_features().transform_feedback {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_rasterization_stream`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        // VUID-vkCmdSetRasterizationStreamEXT-rasterizationStream-07412
+        if rasterization_stream >= properties.max_transform_feedback_streams.unwrap_or(0) {
+            return Err(SetDynamicStateError::MaxTransformFeedbackStreamsExceeded {
+                provided: rasterization_stream + 1,
+                max: properties.max_transform_feedback_streams.unwrap_or(0),
+            });
+        }
+
+        // VUID-vkCmdSetRasterizationStreamEXT-rasterizationStream-07413
+        if rasterization_stream != 0
+            && !properties
+                .transform_feedback_rasterization_stream_select
+                .unwrap_or(false)
+        {
+            return Err(
+                SetDynamicStateError::TransformFeedbackRasterizationStreamSelectNotSupported,
+            );
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_stream_unchecked(
+        &mut self,
+        rasterization_stream: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_rasterization_stream_ext)(self.handle(), rasterization_stream);
+
+        self.builder_state.rasterization_stream = Some(rasterization_stream);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets whether dynamic rasterizer discard is enabled for future draw calls.
     ///
     /// # Panics
@@ -1499,6 +2821,83 @@ where
         self
     }
 
+    /// Sets the sample mask for future draw calls.
+    ///
+    /// `samples` is the number of rasterization samples that `sample_mask` covers. Bits beyond
+    /// this number are ignored.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`extended_dynamic_state3_sample_mask`] feature is not enabled on the
+    ///   device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    ///
+    /// [`extended_dynamic_state3_sample_mask`]: crate::device::Features::extended_dynamic_state3_sample_mask
+    #[inline]
+    pub fn set_sample_mask(&mut self, samples: SampleCount, sample_mask: [u32; 2]) -> &mut Self {
+        self.validate_set_sample_mask(samples, sample_mask).unwrap();
+
+        unsafe { self.set_sample_mask_unchecked(samples, sample_mask) }
+    }
+
+    fn validate_set_sample_mask(
+        &self,
+        samples: SampleCount,
+        _sample_mask: [u32; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::SampleMask)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetSampleMaskEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetSampleMaskEXT-extendedDynamicState3SampleMask-07342
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_sample_mask
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_sample_mask`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_sample_mask"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetSampleMaskEXT-samples-parameter
+        samples.validate_device(self.device())?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_mask_unchecked(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_sample_mask_ext)(
+            self.handle(),
+            samples.into(),
+            sample_mask.as_ptr(),
+        );
+
+        self.builder_state.sample_mask = Some(sample_mask);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic scissors for future draw calls.
     ///
     /// # Panics
@@ -2347,4 +3746,83 @@ where
         self.next_command_index += 1;
         self
     }
+
+    /// Sets the dynamic vertex input state for future draw calls.
+    ///
+    /// The vertex input state for a given vertex layout can be created by passing the
+    /// [`vertex_input_interface`] of the pipeline to [`VertexDefinition::definition`], for example
+    /// with a [`BuffersDefinition`] or `Vertex::per_vertex()`. At draw time, the state must
+    /// provide a compatible attribute for every input of the vertex shader.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`vertex_input_dynamic_state`](crate::device::Features::vertex_input_dynamic_state)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `vertex_input_state` exceeds the vertex input limits of the device, or if an
+    ///   attribute refers to a binding that is not in `vertex_input_state.bindings`.
+    ///
+    /// [`vertex_input_interface`]: crate::pipeline::GraphicsPipeline::vertex_input_interface
+    /// [`VertexDefinition::definition`]: crate::pipeline::graphics::vertex_input::VertexDefinition::definition
+    /// [`BuffersDefinition`]: crate::pipeline::graphics::vertex_input::BuffersDefinition
+    #[inline]
+    pub fn set_vertex_input(&mut self, vertex_input_state: VertexInputState) -> &mut Self {
+        self.validate_set_vertex_input(&vertex_input_state).unwrap();
+
+        unsafe { self.set_vertex_input_unchecked(vertex_input_state) }
+    }
+
+    fn validate_set_vertex_input(
+        &self,
+        vertex_input_state: &VertexInputState,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::VertexInput)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetVertexInputEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetVertexInputEXT-None-04790
+        if !self.device().enabled_features().vertex_input_dynamic_state {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_vertex_input`",
+                requires_one_of: RequiresOneOf {
+                    features: &["vertex_input_dynamic_state"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        validate_vertex_input_state(self.device(), vertex_input_state)
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_vertex_input_unchecked(
+        &mut self,
+        vertex_input_state: VertexInputState,
+    ) -> &mut Self {
+        let (bindings_vk, attributes_vk) = vertex_input_state_to_vk(&vertex_input_state);
+
+        let fns = self.device().fns();
+        (fns.ext_vertex_input_dynamic_state.cmd_set_vertex_input_ext)(
+            self.handle(),
+            bindings_vk.len() as u32,
+            bindings_vk.as_ptr(),
+            attributes_vk.len() as u32,
+            attributes_vk.as_ptr(),
+        );
+
+        self.builder_state.vertex_input = Some(vertex_input_state);
+
+        self.next_command_index += 1;
+        self
+    }
 }
@@ -8,17 +8,18 @@
 // according to those terms.
 
 pub use self::{
-    bind_push::*, clear::*, copy::*, debug::*, dynamic_state::*, pipeline::*, query::*,
-    render_pass::*, secondary::*, sync::*,
+    bind_push::*, clear::*, conditional_rendering::*, copy::*, debug::*, dynamic_state::*,
+    pipeline::*, query::*, render_pass::*, secondary::*, sync::*,
 };
 use super::{PrimaryCommandBuffer, SecondaryCommandBuffer, SubmitState};
 pub use crate::command_buffer::{
     BlitImageInfo, BufferCopy, BufferImageCopy, ClearAttachment, ClearColorImageInfo,
-    ClearDepthStencilImageInfo, ClearError, ClearRect, CopyBufferInfo, CopyBufferInfoTyped,
-    CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo, CopyImageToBufferInfo,
-    DebugUtilsError, ExecuteCommandsError, ImageBlit, ImageCopy, ImageResolve,
-    PipelineExecutionError, QueryError, RenderPassBeginInfo, RenderPassError,
-    RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo, ResolveImageInfo,
+    ClearDepthStencilImageInfo, ClearError, ClearRect, ConditionalRenderingError, CopyBufferInfo,
+    CopyBufferInfoTyped, CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo,
+    CopyImageToBufferInfo, DebugUtilsError, ExecuteCommandsError, ImageBlit, ImageCopy,
+    ImageResolve, PipelineExecutionError, QueryError, RenderPassBeginInfo, RenderPassError,
+    RenderingAttachmentInfo, RenderingAttachmentResolveInfo,
+    RenderingFragmentShadingRateAttachmentInfo, RenderingInfo, ResolveImageInfo,
 };
 use crate::{
     buffer::{Buffer, Subbuffer},
@@ -35,14 +36,21 @@ use crate::{
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
     device::{Device, DeviceOwned, QueueFamilyProperties, QueueFlags},
     format::FormatFeatures,
-    image::{sys::Image, ImageAspects, ImageLayout, ImageSubresourceRange, ImageViewAbstract},
+    image::{
+        sys::Image, ImageAspects, ImageLayout, ImageSubresourceRange, ImageViewAbstract,
+        SampleCount,
+    },
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilOps},
+            fragment_shading_rate::FragmentShadingRate,
             input_assembly::{IndexType, PrimitiveTopology},
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            rasterization::{
+                CullMode, DepthBias, FrontFace, LineRasterizationMode, LineStipple, PolygonMode,
+            },
             render_pass::PipelineRenderingCreateInfo,
+            vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
@@ -71,6 +79,7 @@ use std::{
 
 mod bind_push;
 mod clear;
+mod conditional_rendering;
 mod copy;
 mod debug;
 mod dynamic_state;
@@ -600,6 +609,10 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
         Ok(unsafe { self.build_unchecked()? })
     }
 
@@ -630,6 +643,10 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
         Ok(unsafe { self.build_unchecked()? })
     }
 
@@ -678,25 +695,39 @@ struct CommandBufferBuilderState {
     push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
 
     // Dynamic state
+    alpha_to_coverage_enable: Option<bool>,
+    alpha_to_one_enable: Option<bool>,
     blend_constants: Option<[f32; 4]>,
+    color_blend_enable: HashMap<u32, bool>,
+    color_blend_equation: HashMap<u32, AttachmentBlend>,
     color_write_enable: Option<SmallVec<[bool; 4]>>,
+    color_write_mask: HashMap<u32, ColorComponents>,
     cull_mode: Option<CullMode>,
     depth_bias: Option<DepthBias>,
     depth_bias_enable: Option<bool>,
     depth_bounds: Option<RangeInclusive<f32>>,
     depth_bounds_test_enable: Option<bool>,
+    depth_clamp_enable: Option<bool>,
     depth_compare_op: Option<CompareOp>,
     depth_test_enable: Option<bool>,
     depth_write_enable: Option<bool>,
     discard_rectangle: HashMap<u32, Scissor>,
+    fragment_shading_rate: Option<FragmentShadingRate>,
     front_face: Option<FrontFace>,
+    line_rasterization_mode: Option<LineRasterizationMode>,
     line_stipple: Option<LineStipple>,
+    line_stipple_enable: Option<bool>,
     line_width: Option<f32>,
     logic_op: Option<LogicOp>,
+    logic_op_enable: Option<bool>,
     patch_control_points: Option<u32>,
+    polygon_mode: Option<PolygonMode>,
     primitive_restart_enable: Option<bool>,
     primitive_topology: Option<PrimitiveTopology>,
+    rasterization_samples: Option<SampleCount>,
+    rasterization_stream: Option<u32>,
     rasterizer_discard_enable: Option<bool>,
+    sample_mask: Option<[u32; 2]>,
     scissor: HashMap<u32, Scissor>,
     scissor_with_count: Option<SmallVec<[Scissor; 2]>>,
     stencil_compare_mask: StencilStateDynamic,
@@ -704,11 +735,15 @@ struct CommandBufferBuilderState {
     stencil_reference: StencilStateDynamic,
     stencil_test_enable: Option<bool>,
     stencil_write_mask: StencilStateDynamic,
+    vertex_input: Option<VertexInputState>,
     viewport: HashMap<u32, Viewport>,
     viewport_with_count: Option<SmallVec<[Viewport; 2]>>,
 
     // Active queries
     queries: HashMap<ash::vk::QueryType, QueryState>,
+
+    // Active conditional rendering
+    conditional_rendering: Option<ConditionalRenderingState>,
 }
 
 impl CommandBufferBuilderState {
@@ -727,7 +762,7 @@ impl CommandBufferBuilderState {
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
                 DynamicState::ExclusiveScissor => (), // TODO;
-                DynamicState::FragmentShadingRate => (), // TODO:
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
@@ -745,7 +780,7 @@ impl CommandBufferBuilderState {
                 DynamicState::StencilReference => self.stencil_reference = Default::default(),
                 DynamicState::StencilTestEnable => self.stencil_test_enable = None,
                 DynamicState::StencilWriteMask => self.stencil_write_mask = Default::default(),
-                DynamicState::VertexInput => (), // TODO:
+                DynamicState::VertexInput => self.vertex_input = None,
                 DynamicState::VertexInputBindingStride => (), // TODO:
                 DynamicState::Viewport => self.viewport.clear(),
                 DynamicState::ViewportCoarseSampleOrder => (), // TODO:
@@ -753,36 +788,36 @@ impl CommandBufferBuilderState {
                 DynamicState::ViewportWScaling => (),          // TODO:
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
                 DynamicState::TessellationDomainOrigin => (), // TODO:
-                DynamicState::DepthClampEnable => (),         // TODO:
-                DynamicState::PolygonMode => (),              // TODO:
-                DynamicState::RasterizationSamples => (),     // TODO:
-                DynamicState::SampleMask => (),               // TODO:
-                DynamicState::AlphaToCoverageEnable => (),    // TODO:
-                DynamicState::AlphaToOneEnable => (),         // TODO:
-                DynamicState::LogicOpEnable => (),            // TODO:
-                DynamicState::ColorBlendEnable => (),         // TODO:
-                DynamicState::ColorBlendEquation => (),       // TODO:
-                DynamicState::ColorWriteMask => (),           // TODO:
-                DynamicState::RasterizationStream => (),      // TODO:
+                DynamicState::DepthClampEnable => self.depth_clamp_enable = None,
+                DynamicState::PolygonMode => self.polygon_mode = None,
+                DynamicState::RasterizationSamples => self.rasterization_samples = None,
+                DynamicState::SampleMask => self.sample_mask = None,
+                DynamicState::AlphaToCoverageEnable => self.alpha_to_coverage_enable = None,
+                DynamicState::AlphaToOneEnable => self.alpha_to_one_enable = None,
+                DynamicState::LogicOpEnable => self.logic_op_enable = None,
+                DynamicState::ColorBlendEnable => self.color_blend_enable.clear(),
+                DynamicState::ColorBlendEquation => self.color_blend_equation.clear(),
+                DynamicState::ColorWriteMask => self.color_write_mask.clear(),
+                DynamicState::RasterizationStream => self.rasterization_stream = None,
                 DynamicState::ConservativeRasterizationMode => (), // TODO:
                 DynamicState::ExtraPrimitiveOverestimationSize => (), // TODO:
-                DynamicState::DepthClipEnable => (),          // TODO:
-                DynamicState::SampleLocationsEnable => (),    // TODO:
-                DynamicState::ColorBlendAdvanced => (),       // TODO:
-                DynamicState::ProvokingVertexMode => (),      // TODO:
-                DynamicState::LineRasterizationMode => (),    // TODO:
-                DynamicState::LineStippleEnable => (),        // TODO:
+                DynamicState::DepthClipEnable => (),               // TODO:
+                DynamicState::SampleLocationsEnable => (),         // TODO:
+                DynamicState::ColorBlendAdvanced => (),            // TODO:
+                DynamicState::ProvokingVertexMode => (),           // TODO:
+                DynamicState::LineRasterizationMode => self.line_rasterization_mode = None,
+                DynamicState::LineStippleEnable => self.line_stipple_enable = None,
                 DynamicState::DepthClipNegativeOneToOne => (), // TODO:
-                DynamicState::ViewportWScalingEnable => (),   // TODO:
-                DynamicState::ViewportSwizzle => (),          // TODO:
-                DynamicState::CoverageToColorEnable => (),    // TODO:
-                DynamicState::CoverageToColorLocation => (),  // TODO:
-                DynamicState::CoverageModulationMode => (),   // TODO:
+                DynamicState::ViewportWScalingEnable => (),    // TODO:
+                DynamicState::ViewportSwizzle => (),           // TODO:
+                DynamicState::CoverageToColorEnable => (),     // TODO:
+                DynamicState::CoverageToColorLocation => (),   // TODO:
+                DynamicState::CoverageModulationMode => (),    // TODO:
                 DynamicState::CoverageModulationTableEnable => (), // TODO:
-                DynamicState::CoverageModulationTable => (),  // TODO:
-                DynamicState::ShadingRateImageEnable => (),   // TODO:
+                DynamicState::CoverageModulationTable => (),   // TODO:
+                DynamicState::ShadingRateImageEnable => (),    // TODO:
                 DynamicState::RepresentativeFragmentTestEnable => (), // TODO:
-                DynamicState::CoverageReductionMode => (),    // TODO:
+                DynamicState::CoverageReductionMode => (),     // TODO:
             }
         }
     }
@@ -1154,6 +1189,11 @@ struct QueryState {
     in_subpass: bool,
 }
 
+struct ConditionalRenderingState {
+    predicate: Subbuffer<u32>,
+    subpass_index: Option<u32>,
+}
+
 #[derive(Debug, Default)]
 struct ResourcesState {
     buffers: HashMap<Arc<Buffer>, RangeMap<DeviceSize, BufferRangeState>>,
@@ -14,9 +14,12 @@ use super::{
 use crate::{
     buffer::{view::BufferView, BufferUsage, Subbuffer},
     command_buffer::{
-        allocator::CommandBufferAllocator, commands::pipeline::DescriptorResourceInvalidError,
+        allocator::CommandBufferAllocator,
+        commands::pipeline::{
+            validate_vertex_input_against_pipeline, DescriptorResourceInvalidError,
+        },
         DispatchIndirectCommand, DrawIndexedIndirectCommand, DrawIndirectCommand,
-        ResourceInCommand, ResourceUseRef, SubpassContents,
+        MultiDrawIndexedInfo, MultiDrawInfo, ResourceInCommand, ResourceUseRef, SubpassContents,
     },
     descriptor_set::{layout::DescriptorType, DescriptorBindingResources},
     device::{DeviceOwned, QueueFlags},
@@ -26,7 +29,7 @@ use crate::{
         graphics::{
             input_assembly::{IndexType, PrimitiveTopology},
             render_pass::PipelineRenderPassType,
-            vertex_input::VertexInputRate,
+            vertex_input::{VertexInputRate, VertexInputState},
         },
         DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineBindPoint,
         PipelineLayout,
@@ -40,7 +43,7 @@ use crate::{
     DeviceSize, RequiresOneOf, VulkanObject,
 };
 use ahash::HashMap;
-use std::{cmp::min, mem::size_of, ops::Range, sync::Arc};
+use std::{cmp::min, mem::size_of, ops::Range, ptr, sync::Arc};
 
 impl<L, A> CommandBufferBuilder<L, A>
 where
@@ -352,6 +355,7 @@ where
             command_index,
             command_name,
             &self.builder_state.vertex_buffers,
+            self.builder_state.vertex_input.as_ref(),
             pipeline,
         );
         record_subpass_attachments_access(
@@ -502,6 +506,7 @@ where
             command_index,
             command_name,
             &self.builder_state.vertex_buffers,
+            self.builder_state.vertex_input.as_ref(),
             pipeline,
         );
         record_indirect_buffer_access(
@@ -665,6 +670,345 @@ where
             command_index,
             command_name,
             &self.builder_state.vertex_buffers,
+            self.builder_state.vertex_input.as_ref(),
+            pipeline,
+        );
+        record_index_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.index_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, in a single command.
+    ///
+    /// One draw is performed for each element of `vertex_info`, which specifies the first vertex
+    /// and the number of vertices to draw. All draws share the same `instance_count` and
+    /// `first_instance`. The bound state is validated only once for the whole batch. The number of
+    /// draws must be less than the [`max_multi_draw_count`] limit.
+    ///
+    /// The [`multi_draw`] feature must be enabled on the device.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// vertex ranges of each draw and the provided instance range must be in range of the bound
+    /// vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`max_multi_draw_count`]: crate::device::Properties::max_multi_draw_count
+    /// [`multi_draw`]: crate::device::Features::multi_draw
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_multi(vertex_info, instance_count, first_instance)?;
+
+        unsafe { Ok(self.draw_multi_unchecked(vertex_info, instance_count, first_instance)) }
+    }
+
+    fn validate_draw_multi(
+        &self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMultiEXT-None-04933
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_multi`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMultiEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMultiEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        self.validate_multi_draw_count(vertex_info.len())?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        // Only the draw that reaches furthest into the vertex buffers needs to be checked.
+        let vertices = vertex_info
+            .iter()
+            .max_by_key(|info| info.first_vertex as u64 + info.vertex_count as u64)
+            .map(|info| (info.first_vertex, info.vertex_count));
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            vertices,
+            Some((first_instance, instance_count)),
+        )?;
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if vertex_info.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
+            self.handle(),
+            vertex_info.len() as u32,
+            vertex_info.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawInfo>() as u32,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_multi";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.vertex_buffers,
+            self.builder_state.vertex_input.as_ref(),
+            pipeline,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline and an index buffer, in a single
+    /// command.
+    ///
+    /// One draw is performed for each element of `index_info`, which specifies the first index,
+    /// the number of indices and the vertex offset to draw with. All draws share the same
+    /// `instance_count` and `first_instance`. If `vertex_offset` is `Some`, it is used for all
+    /// draws instead of the per-draw vertex offsets. The bound state is validated only once for
+    /// the whole batch. The number of draws must be less than the [`max_multi_draw_count`] limit.
+    ///
+    /// The [`multi_draw`] feature must be enabled on the device.
+    ///
+    /// An index buffer must have been bound using [`bind_index_buffer`], and the index ranges of
+    /// each draw must be in range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// provided instance range must be in range of the bound vertex buffers. The vertex indices in
+    /// the index buffer must be in range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`max_multi_draw_count`]: crate::device::Properties::max_multi_draw_count
+    /// [`multi_draw`]: crate::device::Features::multi_draw
+    /// [`bind_index_buffer`]: Self::bind_index_buffer
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_multi_indexed(
+            index_info,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        )?;
+
+        unsafe {
+            Ok(self.draw_multi_indexed_unchecked(
+                index_info,
+                instance_count,
+                first_instance,
+                vertex_offset,
+            ))
+        }
+    }
+
+    fn validate_draw_multi_indexed(
+        &self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        _vertex_offset: Option<i32>,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMultiIndexedEXT-None-04937
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_multi_indexed`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMultiIndexedEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMultiIndexedEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        self.validate_multi_draw_count(index_info.len())?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        // Only the draw that reaches furthest into the index buffer needs to be checked.
+        // VUID-vkCmdDrawMultiIndexedEXT-firstIndex-04938
+        let indices = index_info
+            .iter()
+            .max_by_key(|info| info.first_index as u64 + info.index_count as u64)
+            .map(|info| (info.first_index, info.index_count));
+        self.validate_index_buffer(indices)?;
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> &mut Self {
+        if index_info.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle(),
+            index_info.len() as u32,
+            index_info.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawIndexedInfo>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset as *const _),
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_multi_indexed";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.vertex_buffers,
+            self.builder_state.vertex_input.as_ref(),
             pipeline,
         );
         record_index_buffer_access(
@@ -827,6 +1171,7 @@ where
             command_index,
             command_name,
             &self.builder_state.vertex_buffers,
+            self.builder_state.vertex_input.as_ref(),
             pipeline,
         );
         record_index_buffer_access(
@@ -888,6 +1233,26 @@ where
         Ok(())
     }
 
+    fn validate_multi_draw_count(&self, draw_count: usize) -> Result<(), PipelineExecutionError> {
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_multi_draw_count
+            .unwrap_or(0);
+
+        // VUID-vkCmdDrawMultiEXT-drawCount-04934
+        // VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939
+        if draw_count >= max as usize {
+            return Err(PipelineExecutionError::MaxMultiDrawCountExceeded {
+                provided: draw_count as u32,
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
     fn validate_indirect_buffer(
         &self,
         buffer: &Subbuffer<[u8]>,
@@ -918,6 +1283,7 @@ where
             set_num: u32,
             binding_num: u32,
             binding_reqs: &DescriptorBindingRequirements,
+            partially_bound: bool,
             elements: &[Option<T>],
             mut extra_check: impl FnMut(u32, &T) -> Result<(), DescriptorResourceInvalidError>,
         ) -> Result<(), PipelineExecutionError> {
@@ -945,6 +1311,9 @@ where
                 // VUID-vkCmdDispatch-None-02699
                 let element = match element {
                     Some(x) => x,
+                    // Descriptors of partially bound bindings only need to be valid if they are
+                    // dynamically used, which can't be known here.
+                    None if partially_bound => continue,
                     None => {
                         return Err(PipelineExecutionError::DescriptorResourceInvalid {
                             set_num,
@@ -987,17 +1356,53 @@ where
             return Err(PipelineExecutionError::PipelineLayoutNotCompatible);
         }
 
+        // The predicate of the active conditional rendering block may be read by the device at
+        // any point until the block ends, so it must not be written to in the meantime.
+        let overlaps_predicate = |buffer: &Subbuffer<[u8]>, range: Range<DeviceSize>| {
+            self.builder_state
+                .conditional_rendering
+                .as_ref()
+                .map_or(false, |state| {
+                    let predicate = &state.predicate;
+                    buffer.buffer() == predicate.buffer()
+                        && buffer.offset() + range.start < predicate.offset() + predicate.size()
+                        && predicate.offset() < buffer.offset() + range.end
+                })
+        };
+
         for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
             let layout_binding =
                 &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
 
             let check_buffer =
-                |_index: u32, (_buffer, _range): &(Subbuffer<[u8]>, Range<DeviceSize>)| Ok(());
+                |index: u32, (buffer, range): &(Subbuffer<[u8]>, Range<DeviceSize>)| {
+                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                        .chain(binding_reqs.descriptors.get(&None))
+                    {
+                        if !desc_reqs.memory_write.is_empty()
+                            && overlaps_predicate(buffer, range.clone())
+                        {
+                            return Err(
+                                DescriptorResourceInvalidError::ConditionalRenderingPredicateWritten,
+                            );
+                        }
+                    }
+
+                    Ok(())
+                };
 
             let check_buffer_view = |index: u32, buffer_view: &Arc<BufferView>| {
                 for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
                     .chain(binding_reqs.descriptors.get(&None))
                 {
+                    if !desc_reqs.memory_write.is_empty()
+                        && overlaps_predicate(buffer_view.buffer(), buffer_view.range())
+                    {
+                        return Err(
+                            DescriptorResourceInvalidError::ConditionalRenderingPredicateWritten,
+                        );
+                    }
+
                     if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
                         // VUID-vkCmdDispatch-OpTypeImage-06423
                         if binding_reqs.image_format.is_none()
@@ -1266,16 +1671,31 @@ where
 
             match binding_resources {
                 DescriptorBindingResources::None(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_none)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_none,
+                    )?;
                 }
                 DescriptorBindingResources::Buffer(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_buffer)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_buffer,
+                    )?;
                 }
                 DescriptorBindingResources::BufferView(elements) => {
                     validate_resources(
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_buffer_view,
                     )?;
@@ -1285,6 +1705,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view,
                     )?;
@@ -1294,6 +1715,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view_sampler,
                     )?;
@@ -1303,10 +1725,25 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_sampler,
                     )?;
                 }
+                DescriptorBindingResources::InlineUniformBlock(elements) => {
+                    // The elements are the bytes of the block, which the shader accesses as a whole,
+                    // so the array size in the shader is not relevant here.
+                    if !layout_binding.partially_bound {
+                        if let Some(index) = elements.iter().position(Option::is_none) {
+                            return Err(PipelineExecutionError::DescriptorResourceInvalid {
+                                set_num,
+                                binding_num,
+                                index: index as u32,
+                                error: DescriptorResourceInvalidError::Missing,
+                            });
+                        }
+                    }
+                }
             }
         }
 
@@ -1454,7 +1891,12 @@ where
                     }
                 }
                 DynamicState::ExclusiveScissor => todo!(),
-                DynamicState::FragmentShadingRate => todo!(),
+                DynamicState::FragmentShadingRate => {
+                    // VUID?
+                    if self.builder_state.fragment_shading_rate.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::FrontFace => {
                     // VUID?
                     if self.builder_state.front_face.is_none() {
@@ -1669,7 +2111,16 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
-                DynamicState::VertexInput => todo!(),
+                DynamicState::VertexInput => {
+                    // VUID-vkCmdDraw-None-04914
+                    let vertex_input = self
+                        .builder_state
+                        .vertex_input
+                        .as_ref()
+                        .ok_or(PipelineExecutionError::DynamicStateNotSet { dynamic_state })?;
+
+                    validate_vertex_input_against_pipeline(pipeline, vertex_input)?;
+                }
                 DynamicState::VertexInputBindingStride => todo!(),
                 DynamicState::Viewport => {
                     for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
@@ -1717,37 +2168,113 @@ where
                     // vkCmdSetViewportWithCountEXT must be 1
                 }
                 DynamicState::ViewportWScaling => todo!(),
-                DynamicState::TessellationDomainOrigin => todo!(),
-                DynamicState::DepthClampEnable => todo!(),
-                DynamicState::PolygonMode => todo!(),
-                DynamicState::RasterizationSamples => todo!(),
-                DynamicState::SampleMask => todo!(),
-                DynamicState::AlphaToCoverageEnable => todo!(),
-                DynamicState::AlphaToOneEnable => todo!(),
-                DynamicState::LogicOpEnable => todo!(),
-                DynamicState::ColorBlendEnable => todo!(),
-                DynamicState::ColorBlendEquation => todo!(),
-                DynamicState::ColorWriteMask => todo!(),
-                DynamicState::RasterizationStream => todo!(),
-                DynamicState::ConservativeRasterizationMode => todo!(),
-                DynamicState::ExtraPrimitiveOverestimationSize => todo!(),
-                DynamicState::DepthClipEnable => todo!(),
-                DynamicState::SampleLocationsEnable => todo!(),
-                DynamicState::ColorBlendAdvanced => todo!(),
-                DynamicState::ProvokingVertexMode => todo!(),
-                DynamicState::LineRasterizationMode => todo!(),
-                DynamicState::LineStippleEnable => todo!(),
-                DynamicState::DepthClipNegativeOneToOne => todo!(),
-                DynamicState::ViewportWScalingEnable => todo!(),
-                DynamicState::ViewportSwizzle => todo!(),
-                DynamicState::CoverageToColorEnable => todo!(),
-                DynamicState::CoverageToColorLocation => todo!(),
-                DynamicState::CoverageModulationMode => todo!(),
-                DynamicState::CoverageModulationTableEnable => todo!(),
-                DynamicState::CoverageModulationTable => todo!(),
-                DynamicState::ShadingRateImageEnable => todo!(),
-                DynamicState::RepresentativeFragmentTestEnable => todo!(),
-                DynamicState::CoverageReductionMode => todo!(),
+                DynamicState::DepthClampEnable => {
+                    // VUID?
+                    if self.builder_state.depth_clamp_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::PolygonMode => {
+                    // VUID?
+                    if self.builder_state.polygon_mode.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::RasterizationSamples => {
+                    // VUID?
+                    if self.builder_state.rasterization_samples.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::SampleMask => {
+                    // VUID?
+                    if self.builder_state.sample_mask.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::AlphaToCoverageEnable => {
+                    // VUID?
+                    if self.builder_state.alpha_to_coverage_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::AlphaToOneEnable => {
+                    // VUID?
+                    if self.builder_state.alpha_to_one_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::LogicOpEnable => {
+                    // VUID?
+                    if self.builder_state.logic_op_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::ColorBlendEnable => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        // VUID?
+                        if !self.builder_state.color_blend_enable.contains_key(&num) {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::ColorBlendEquation => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        // VUID?
+                        if !self.builder_state.color_blend_equation.contains_key(&num) {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::ColorWriteMask => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        // VUID?
+                        if !self.builder_state.color_write_mask.contains_key(&num) {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::RasterizationStream => {
+                    // VUID?
+                    if self.builder_state.rasterization_stream.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::LineRasterizationMode => {
+                    // VUID?
+                    if self.builder_state.line_rasterization_mode.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::LineStippleEnable => {
+                    // VUID?
+                    if self.builder_state.line_stipple_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::TessellationDomainOrigin
+                | DynamicState::ConservativeRasterizationMode
+                | DynamicState::ExtraPrimitiveOverestimationSize
+                | DynamicState::DepthClipEnable
+                | DynamicState::SampleLocationsEnable
+                | DynamicState::ColorBlendAdvanced
+                | DynamicState::ProvokingVertexMode
+                | DynamicState::DepthClipNegativeOneToOne
+                | DynamicState::ViewportWScalingEnable
+                | DynamicState::ViewportSwizzle
+                | DynamicState::CoverageToColorEnable
+                | DynamicState::CoverageToColorLocation
+                | DynamicState::CoverageModulationMode
+                | DynamicState::CoverageModulationTableEnable
+                | DynamicState::CoverageModulationTable
+                | DynamicState::ShadingRateImageEnable
+                | DynamicState::RepresentativeFragmentTestEnable
+                | DynamicState::CoverageReductionMode => {
+                    // Vulkano doesn't support the pipeline state that these control, so
+                    // `GraphicsPipelineBuilder` never makes them dynamic and no bound pipeline can
+                    // require them.
+                    unreachable!()
+                }
             }
         }
 
@@ -1886,6 +2413,43 @@ where
             _ => return Err(PipelineExecutionError::PipelineRenderPassTypeMismatch),
         }
 
+        if pipeline.dynamic_state(DynamicState::RasterizationSamples) == Some(true) {
+            let device = self.device();
+
+            // Checked to be set by `validate_pipeline_graphics_dynamic_state`.
+            let rasterization_samples = self.builder_state.rasterization_samples.unwrap();
+
+            // Not known for secondary command buffers that inherit a dynamic rendering scope.
+            // The attachments are checked against the inherited sample count when executed.
+            let attachment_samples = match &render_pass_state.attachments {
+                Some(attachments) => attachments
+                    .color_attachments
+                    .iter()
+                    .flatten()
+                    .chain(attachments.depth_attachment.iter())
+                    .chain(attachments.stencil_attachment.iter())
+                    .next()
+                    .map(|attachment_info| attachment_info.image_view.image().samples()),
+                None => match &render_pass_state.render_pass {
+                    RenderPassStateType::BeginRenderPass(state) => state.subpass.num_samples(),
+                    RenderPassStateType::BeginRendering(_) => None,
+                },
+            };
+
+            // VUID-vkCmdDraw-multisampledRenderToSingleSampled-07284
+            if let Some(attachment_samples) = attachment_samples {
+                if rasterization_samples != attachment_samples
+                    && !(device.enabled_extensions().amd_mixed_attachment_samples
+                        || device.enabled_extensions().nv_framebuffer_mixed_samples)
+                {
+                    return Err(PipelineExecutionError::RasterizationSamplesMismatch {
+                        provided: rasterization_samples,
+                        required: attachment_samples,
+                    });
+                }
+            }
+        }
+
         // VUID-vkCmdDraw-None-02686
         // TODO:
 
@@ -1898,7 +2462,12 @@ where
         vertices: Option<(u32, u32)>,
         instances: Option<(u32, u32)>,
     ) -> Result<(), PipelineExecutionError> {
-        let vertex_input = pipeline.vertex_input_state();
+        let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+            // Checked to be set by `validate_pipeline_graphics_dynamic_state`.
+            self.builder_state.vertex_input.as_ref().unwrap()
+        } else {
+            pipeline.vertex_input_state()
+        };
         let mut vertices_in_buffers: Option<u64> = None;
         let mut instances_in_buffers: Option<u64> = None;
 
@@ -2189,7 +2758,8 @@ fn record_descriptor_sets_access(
                     }
                 }
             }
-            DescriptorBindingResources::Sampler(_) => (),
+            DescriptorBindingResources::Sampler(_)
+            | DescriptorBindingResources::InlineUniformBlock(_) => (),
         }
     }
 }
@@ -2199,9 +2769,16 @@ fn record_vertex_buffers_access(
     command_index: usize,
     command_name: &'static str,
     vertex_buffers_state: &HashMap<u32, Subbuffer<[u8]>>,
+    vertex_input_state: Option<&VertexInputState>,
     pipeline: &GraphicsPipeline,
 ) {
-    for &binding in pipeline.vertex_input_state().bindings.keys() {
+    let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+        vertex_input_state.unwrap()
+    } else {
+        pipeline.vertex_input_state()
+    };
+
+    for &binding in vertex_input.bindings.keys() {
         let buffer = &vertex_buffers_state[&binding];
         let use_ref = ResourceUseRef {
             command_index,
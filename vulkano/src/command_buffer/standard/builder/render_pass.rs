@@ -11,7 +11,8 @@ use super::{
     BeginRenderPassState, BeginRenderingState, ClearAttachment, ClearRect, CommandBufferBuilder,
     RenderPassBeginInfo, RenderPassError, RenderPassState, RenderPassStateAttachmentInfo,
     RenderPassStateAttachmentResolveInfo, RenderPassStateAttachments, RenderPassStateType,
-    RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo, ResourcesState,
+    RenderingAttachmentInfo, RenderingAttachmentResolveInfo,
+    RenderingFragmentShadingRateAttachmentInfo, RenderingInfo, ResourcesState,
 };
 use crate::{
     command_buffer::{
@@ -189,14 +190,19 @@ where
                 resolve_attachments,
                 depth_stencil_attachment,
                 preserve_attachments: _,
+                fragment_shading_rate_attachment,
                 _ne: _,
             } = subpass_desc;
 
-            for atch_ref in (input_attachments.iter())
-                .chain(color_attachments)
-                .chain(resolve_attachments)
-                .chain([depth_stencil_attachment])
-                .flatten()
+            for atch_ref in (input_attachments.iter().flatten())
+                .chain(color_attachments.iter().flatten())
+                .chain(resolve_attachments.iter().flatten())
+                .chain(depth_stencil_attachment)
+                .chain(
+                    fragment_shading_rate_attachment
+                        .iter()
+                        .map(|fsr_atch| &fsr_atch.attachment_ref),
+                )
             {
                 let image_view = &framebuffer.attachments()[atch_ref.attachment as usize];
 
@@ -255,6 +261,18 @@ where
                             });
                         }
                     }
+                    ImageLayout::FragmentShadingRateAttachmentOptimal => {
+                        // VUID?
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                        {
+                            return Err(RenderPassError::AttachmentImageMissingUsage {
+                                attachment_index: atch_ref.attachment,
+                                usage: "fragment_shading_rate_attachment",
+                            });
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -833,6 +851,7 @@ where
             ref color_attachments,
             ref depth_attachment,
             ref stencil_attachment,
+            ref fragment_shading_rate_attachment,
             contents,
             _ne: _,
         } = rendering_info;
@@ -1347,6 +1366,80 @@ where
             }
         }
 
+        if let Some(attachment_info) = fragment_shading_rate_attachment {
+            let &RenderingFragmentShadingRateAttachmentInfo {
+                ref image_view,
+                image_layout,
+                texel_size,
+            } = attachment_info;
+
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06147
+            if !device.enabled_features().attachment_fragment_shading_rate {
+                return Err(RenderPassError::RequirementNotMet {
+                    required_for: "`rendering_info.fragment_shading_rate_attachment` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["attachment_fragment_shading_rate"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06147
+            if !matches!(
+                image_layout,
+                ImageLayout::General | ImageLayout::FragmentShadingRateAttachmentOptimal
+            ) {
+                return Err(RenderPassError::FragmentShadingRateAttachmentLayoutInvalid);
+            }
+
+            // VUID-VkRenderingInfo-imageView-06142
+            if !image_view
+                .usage()
+                .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+            {
+                return Err(RenderPassError::FragmentShadingRateAttachmentMissingUsage);
+            }
+
+            // VUID-VkRenderingInfo-imageView-06116
+            if color_attachments
+                .iter()
+                .flatten()
+                .chain(depth_attachment.iter())
+                .chain(stencil_attachment.iter())
+                .any(|other| &other.image_view == image_view)
+            {
+                return Err(RenderPassError::FragmentShadingRateAttachmentImageViewUsedElsewhere);
+            }
+
+            let min_texel_size = properties
+                .min_fragment_shading_rate_attachment_texel_size
+                .unwrap_or([0; 2]);
+            let max_texel_size = properties
+                .max_fragment_shading_rate_attachment_texel_size
+                .unwrap_or([0; 2]);
+            let max_aspect_ratio = properties
+                .max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+                .unwrap_or(0);
+
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06149
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06150
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06151
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06152
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06153
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06154
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06155
+            // VUID-VkRenderingFragmentShadingRateAttachmentInfoKHR-imageView-06156
+            if !(0..2).all(|i| {
+                texel_size[i].is_power_of_two()
+                    && texel_size[i] >= min_texel_size[i]
+                    && texel_size[i] <= max_texel_size[i]
+            }) || texel_size[0] / texel_size[1] > max_aspect_ratio
+                || texel_size[1] / texel_size[0] > max_aspect_ratio
+            {
+                return Err(RenderPassError::FragmentShadingRateAttachmentTexelSizeInvalid);
+            }
+        }
+
         // TODO: sync check
 
         Ok(())
@@ -1363,6 +1456,7 @@ where
                 ref color_attachments,
                 ref depth_attachment,
                 ref stencil_attachment,
+                ref fragment_shading_rate_attachment,
                 contents,
                 _ne,
             } = &rendering_info;
@@ -1420,7 +1514,7 @@ where
             let depth_attachment_vk = map_attachment_info(depth_attachment);
             let stencil_attachment_vk = map_attachment_info(stencil_attachment);
 
-            let rendering_info_vk = ash::vk::RenderingInfo {
+            let mut rendering_info_vk = ash::vk::RenderingInfo {
                 flags: contents.into(),
                 render_area: ash::vk::Rect2D {
                     offset: ash::vk::Offset2D {
@@ -1441,6 +1535,30 @@ where
                 ..Default::default()
             };
 
+            let fragment_shading_rate_attachment_vk = fragment_shading_rate_attachment
+                .as_ref()
+                .map(|attachment_info| {
+                    let &RenderingFragmentShadingRateAttachmentInfo {
+                        ref image_view,
+                        image_layout,
+                        texel_size,
+                    } = attachment_info;
+
+                    ash::vk::RenderingFragmentShadingRateAttachmentInfoKHR {
+                        image_view: image_view.handle(),
+                        image_layout: image_layout.into(),
+                        shading_rate_attachment_texel_size: ash::vk::Extent2D {
+                            width: texel_size[0],
+                            height: texel_size[1],
+                        },
+                        ..Default::default()
+                    }
+                });
+
+            if let Some(next) = fragment_shading_rate_attachment_vk.as_ref() {
+                rendering_info_vk.p_next = next as *const _ as *const _;
+            }
+
             let fns = self.device().fns();
 
             if self.device().api_version() >= Version::V1_3 {
@@ -1474,6 +1592,7 @@ where
             color_attachments,
             depth_attachment,
             stencil_attachment,
+            fragment_shading_rate_attachment,
             ..
         } = rendering_info;
 
@@ -1549,6 +1668,16 @@ where
             }
         }
 
+        if let Some(attachment_info) = fragment_shading_rate_attachment {
+            let RenderingFragmentShadingRateAttachmentInfo {
+                image_view,
+                image_layout: _,
+                texel_size: _,
+            } = attachment_info;
+
+            self.resources.push(Box::new(image_view));
+        }
+
         self.next_command_index += 1;
         self
     }
@@ -24,15 +24,23 @@ use crate::{
         CommandBufferResourcesUsage, ResourceUseRef, SecondaryCommandBufferBufferUsage,
         SecondaryCommandBufferImageUsage, SecondaryCommandBufferResourcesUsage,
     },
-    descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
+    descriptor_set::{
+        descriptor_buffer::DescriptorBufferSet, DescriptorSetResources, DescriptorSetWithOffsets,
+    },
     device::{Device, DeviceOwned},
-    image::{sys::Image, ImageAccess, ImageAspects, ImageLayout, ImageSubresourceRange},
+    image::{
+        sys::Image, ImageAccess, ImageAspects, ImageLayout, ImageSubresourceRange, SampleCount,
+    },
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilOps},
+            fragment_shading_rate::FragmentShadingRate,
             input_assembly::{IndexType, PrimitiveTopology},
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            rasterization::{
+                CullMode, DepthBias, FrontFace, LineRasterizationMode, LineStipple, PolygonMode,
+            },
+            vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
@@ -40,15 +48,15 @@ use crate::{
     range_map::RangeMap,
     range_set::RangeSet,
     sync::{
-        AccessFlags, BufferMemoryBarrier, DependencyInfo, ImageMemoryBarrier, PipelineMemoryAccess,
-        PipelineStages,
+        event::Event, AccessFlags, BufferMemoryBarrier, DependencyInfo, ImageMemoryBarrier,
+        PipelineMemoryAccess, PipelineStages,
     },
     DeviceSize, OomError, VulkanObject,
 };
 use ahash::HashMap;
 use smallvec::SmallVec;
 use std::{
-    collections::hash_map::Entry,
+    collections::{hash_map::Entry, VecDeque},
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
     ops::{Range, RangeInclusive},
@@ -79,10 +87,30 @@ pub struct SyncCommandBufferBuilder {
     // in `commands`.
     pending_barrier: DependencyInfo,
 
+    // Barriers that will be split into a `set_event` command, submitted along with
+    // `pending_barrier`, and a `wait_events` command, submitted before the command at the given
+    // index. Sorted by command index.
+    pending_split_barriers: Vec<(usize, DependencyInfo)>,
+
+    // Events that have been set, but not yet waited on, along with the index of the command that
+    // must wait on them. Sorted by command index.
+    pending_event_waits: VecDeque<(usize, Arc<Event>, DependencyInfo)>,
+
+    // Events used by split barriers that have already been submitted.
+    events: Vec<Arc<Event>>,
+
+    // Whether barriers against already flushed commands may be turned into split barriers,
+    // using events. Requires that the queue family supports events.
+    pub(in crate::command_buffer) split_barriers: bool,
+
     // Locations within commands that pipeline barriers were inserted. For debugging purposes.
     // TODO: present only in cfg(debug_assertions)?
     barriers: Vec<usize>,
 
+    // Locations within commands that split barriers were set and waited on. For debugging
+    // purposes.
+    split_barrier_locations: Vec<(usize, usize)>,
+
     // Only the commands before `first_unflushed` have already been sent to the inner
     // `UnsafeCommandBufferBuilder`.
     first_unflushed: usize,
@@ -153,7 +181,12 @@ impl SyncCommandBufferBuilder {
             level,
             commands: Vec::new(),
             pending_barrier: DependencyInfo::default(),
+            pending_split_barriers: Vec::new(),
+            pending_event_waits: VecDeque::new(),
+            events: Vec::new(),
+            split_barriers: false,
             barriers: Vec::new(),
+            split_barrier_locations: Vec::new(),
             first_unflushed: 0,
             latest_render_pass_enter,
             buffers2: HashMap::default(),
@@ -415,6 +448,16 @@ impl SyncCommandBufferBuilder {
         range_map.split_at(&range.start);
         range_map.split_at(&range.end);
 
+        // Barriers that must be submitted after the pending commands have been flushed.
+        let mut flushed_barriers: SmallVec<[BufferMemoryBarrier; 4]> = SmallVec::new();
+        let mut needs_flush_any = false;
+
+        // A barrier against commands that were already flushed can be split, if there are
+        // pending commands that the current command doesn't depend on.
+        let split_allowed = self.split_barriers
+            && self.latest_render_pass_enter.is_none()
+            && use_ref.command_index > self.first_unflushed;
+
         for (range, state) in range_map.range_mut(&range) {
             if state.resource_uses.is_empty() {
                 // This is the first time we use this resource range in this command buffer.
@@ -458,38 +501,36 @@ impl SyncCommandBufferBuilder {
                     // collision. But since the pipeline barrier is going to be submitted before
                     // the flushed commands, it would be a mistake if `collision_cmd_id` hasn't
                     // been flushed yet.
-                    if state
+                    let needs_flush = state
                         .resource_uses
                         .iter()
-                        .any(|resource_use| resource_use.command_index >= self.first_unflushed)
-                    {
-                        unsafe {
-                            // Flush the pending barrier.
-                            self.inner.pipeline_barrier(&self.pending_barrier);
-                            self.pending_barrier.clear();
-                            self.barriers.push(self.first_unflushed); // Track inserted barriers
-
-                            for command in
-                                &mut self.commands[self.first_unflushed..last_allowed_barrier_index]
-                            {
-                                command.send(&mut self.inner);
-                            }
-
-                            self.first_unflushed = last_allowed_barrier_index;
-                        }
-                    }
+                        .any(|resource_use| resource_use.command_index >= self.first_unflushed);
+                    needs_flush_any |= needs_flush;
+
+                    let barrier = BufferMemoryBarrier {
+                        src_stages: state.memory.stages,
+                        src_access: state.memory.access,
+                        dst_stages: memory.stages,
+                        dst_access: memory.access,
+                        range: range.clone(),
+                        ..BufferMemoryBarrier::buffer(buffer.buffer().clone())
+                    };
 
                     // Modify the pipeline barrier to handle the collision.
-                    self.pending_barrier
+                    if needs_flush {
+                        flushed_barriers.push(barrier);
+                    } else if split_allowed
+                        && !(state.memory.stages | memory.stages).intersects(PipelineStages::HOST)
+                    {
+                        split_barrier_group(
+                            &mut self.pending_split_barriers,
+                            use_ref.command_index,
+                        )
                         .buffer_memory_barriers
-                        .push(BufferMemoryBarrier {
-                            src_stages: state.memory.stages,
-                            src_access: state.memory.access,
-                            dst_stages: memory.stages,
-                            dst_access: memory.access,
-                            range: range.clone(),
-                            ..BufferMemoryBarrier::buffer(buffer.buffer().clone())
-                        });
+                        .push(barrier);
+                    } else {
+                        self.pending_barrier.buffer_memory_barriers.push(barrier);
+                    }
 
                     // Update state.
                     state.memory = memory;
@@ -503,6 +544,14 @@ impl SyncCommandBufferBuilder {
                 state.resource_uses.push(use_ref);
             }
         }
+
+        if needs_flush_any {
+            unsafe { self.flush(last_allowed_barrier_index) };
+        }
+
+        self.pending_barrier
+            .buffer_memory_barriers
+            .extend(flushed_barriers);
     }
 
     fn add_image(
@@ -550,11 +599,12 @@ impl SyncCommandBufferBuilder {
             subresource_range.aspects = ImageAspects::DEPTH | ImageAspects::STENCIL;
         }
 
+        let tracks_layouts = inner.image.tracks_layouts();
         let range_map = self.images2.entry(inner.image.clone()).or_insert_with(|| {
             [(
                 0..inner.image.range_size(),
                 match self.level {
-                    CommandBufferLevel::Primary => {
+                    CommandBufferLevel::Primary if !tracks_layouts => {
                         // In a primary command buffer, the initial layout is determined
                         // by the image.
                         let initial_layout = if !image.is_layout_initialized() {
@@ -576,9 +626,11 @@ impl SyncCommandBufferBuilder {
                             final_layout: image.final_layout_requirement(),
                         }
                     }
-                    CommandBufferLevel::Secondary => {
+                    CommandBufferLevel::Primary | CommandBufferLevel::Secondary => {
                         // In a secondary command buffer, the initial layout is the layout
-                        // of the first use.
+                        // of the first use. The same goes for images that track their layouts,
+                        // they are transitioned to that layout when the command buffer is
+                        // submitted.
                         ImageState {
                             resource_uses: Vec::new(),
                             memory: PipelineMemoryAccess::default(),
@@ -594,6 +646,16 @@ impl SyncCommandBufferBuilder {
             .collect()
         });
 
+        // Barriers that must be submitted after the pending commands have been flushed.
+        let mut flushed_barriers: SmallVec<[ImageMemoryBarrier; 4]> = SmallVec::new();
+        let mut needs_flush_any = false;
+
+        // A barrier against commands that were already flushed can be split, if there are
+        // pending commands that the current command doesn't depend on.
+        let split_allowed = self.split_barriers
+            && self.latest_render_pass_enter.is_none()
+            && use_ref.command_index > self.first_unflushed;
+
         for range in inner.image.iter_ranges(subresource_range) {
             range_map.split_at(&range.start);
             range_map.split_at(&range.end);
@@ -604,6 +666,10 @@ impl SyncCommandBufferBuilder {
 
                     debug_assert_eq!(state.initial_layout, state.current_layout);
 
+                    if tracks_layouts {
+                        state.initial_layout = start_layout;
+                    }
+
                     state.resource_uses.push(use_ref);
                     state.memory = PipelineMemoryAccess {
                         stages: memory.stages,
@@ -715,40 +781,39 @@ impl SyncCommandBufferBuilder {
                         // collision. But since the pipeline barrier is going to be submitted before
                         // the flushed commands, it would be a mistake if `collision_cmd_id` hasn't
                         // been flushed yet.
-                        if state
-                            .resource_uses
-                            .iter()
-                            .any(|resource_use| resource_use.command_index >= self.first_unflushed)
-                            || state.current_layout != start_layout
-                        {
-                            unsafe {
-                                // Flush the pending barrier.
-                                self.inner.pipeline_barrier(&self.pending_barrier);
-                                self.pending_barrier.clear();
-                                self.barriers.push(self.first_unflushed); // Track inserted barriers
-
-                                for command in &mut self.commands
-                                    [self.first_unflushed..last_allowed_barrier_index]
-                                {
-                                    command.send(&mut self.inner);
-                                }
-                                self.first_unflushed = last_allowed_barrier_index;
-                            }
-                        }
+                        let needs_flush =
+                            state.resource_uses.iter().any(|resource_use| {
+                                resource_use.command_index >= self.first_unflushed
+                            }) || state.current_layout != start_layout;
+                        needs_flush_any |= needs_flush;
+
+                        let barrier = ImageMemoryBarrier {
+                            src_stages: state.memory.stages,
+                            src_access: state.memory.access,
+                            dst_stages: memory.stages,
+                            dst_access: memory.access,
+                            old_layout: state.current_layout,
+                            new_layout: start_layout,
+                            subresource_range: inner.image.range_to_subresources(range.clone()),
+                            ..ImageMemoryBarrier::image(inner.image.clone())
+                        };
 
                         // Modify the pipeline barrier to handle the collision.
-                        self.pending_barrier
+                        if needs_flush {
+                            flushed_barriers.push(barrier);
+                        } else if split_allowed
+                            && !(state.memory.stages | memory.stages)
+                                .intersects(PipelineStages::HOST)
+                        {
+                            split_barrier_group(
+                                &mut self.pending_split_barriers,
+                                use_ref.command_index,
+                            )
                             .image_memory_barriers
-                            .push(ImageMemoryBarrier {
-                                src_stages: state.memory.stages,
-                                src_access: state.memory.access,
-                                dst_stages: memory.stages,
-                                dst_access: memory.access,
-                                old_layout: state.current_layout,
-                                new_layout: start_layout,
-                                subresource_range: inner.image.range_to_subresources(range.clone()),
-                                ..ImageMemoryBarrier::image(inner.image.clone())
-                            });
+                            .push(barrier);
+                        } else {
+                            self.pending_barrier.image_memory_barriers.push(barrier);
+                        }
 
                         // Update state.
                         state.memory = memory;
@@ -769,6 +834,87 @@ impl SyncCommandBufferBuilder {
                 }
             }
         }
+
+        if needs_flush_any {
+            unsafe { self.flush(last_allowed_barrier_index) };
+        }
+
+        self.pending_barrier
+            .image_memory_barriers
+            .extend(flushed_barriers);
+    }
+
+    /// Submits the pending barrier to the inner builder, followed by the pending commands up to
+    /// `end`.
+    ///
+    /// Pending split barriers are turned into a `set_event` command that is submitted along with
+    /// the pending barrier, and a `wait_events` command that is submitted right before the
+    /// command that needs it.
+    unsafe fn flush(&mut self, end: usize) {
+        let mut split_barriers: SmallVec<[_; 4]> = SmallVec::new();
+
+        for (command_index, dependency_info) in self.pending_split_barriers.drain(..) {
+            match Event::from_pool(self.inner.device().clone()) {
+                Ok(event) => split_barriers.push((command_index, Arc::new(event), dependency_info)),
+                Err(_) => {
+                    // If no event is available, fall back to a regular pipeline barrier.
+                    let DependencyInfo {
+                        buffer_memory_barriers,
+                        image_memory_barriers,
+                        ..
+                    } = dependency_info;
+                    self.pending_barrier
+                        .buffer_memory_barriers
+                        .extend(buffer_memory_barriers);
+                    self.pending_barrier
+                        .image_memory_barriers
+                        .extend(image_memory_barriers);
+                }
+            }
+        }
+
+        self.inner.pipeline_barrier(&self.pending_barrier);
+        self.pending_barrier.clear();
+        self.barriers.push(self.first_unflushed); // Track inserted barriers
+
+        for (command_index, event, dependency_info) in split_barriers {
+            self.inner.set_event(&event, &dependency_info);
+            self.split_barrier_locations
+                .push((self.first_unflushed, command_index));
+            self.pending_event_waits
+                .push_back((command_index, event, dependency_info));
+        }
+
+        for command_index in self.first_unflushed..end {
+            if matches!(
+                self.pending_event_waits.front(),
+                Some(&(wait_index, ..)) if wait_index == command_index
+            ) {
+                let (_, event, dependency_info) = self.pending_event_waits.pop_front().unwrap();
+                let dst_stages = dependency_info
+                    .buffer_memory_barriers
+                    .iter()
+                    .map(|barrier| barrier.dst_stages)
+                    .chain(
+                        dependency_info
+                            .image_memory_barriers
+                            .iter()
+                            .map(|barrier| barrier.dst_stages),
+                    )
+                    .fold(PipelineStages::empty(), |total, stages| total | stages);
+
+                self.inner.wait_events([(event.as_ref(), &dependency_info)]);
+
+                // Reset the event once it has been waited on, so that the command buffer can be
+                // submitted again.
+                self.inner.reset_event(&event, dst_stages);
+                self.events.push(event);
+            }
+
+            self.commands[command_index].send(&mut self.inner);
+        }
+
+        self.first_unflushed = end;
     }
 
     /// Builds the command buffer and turns it into a `SyncCommandBuffer`.
@@ -778,20 +924,30 @@ impl SyncCommandBufferBuilder {
 
         // The commands that haven't been sent to the inner command buffer yet need to be sent.
         unsafe {
-            self.inner.pipeline_barrier(&self.pending_barrier);
-            self.pending_barrier.clear();
-            let start = self.first_unflushed;
-            self.barriers.push(start); // Track inserted barriers
-
-            for command in &mut self.commands[start..] {
-                command.send(&mut self.inner);
-            }
+            self.flush(self.commands.len());
         }
+        debug_assert!(self.pending_event_waits.is_empty());
 
         // Transition images to their desired final layout.
         if self.level == CommandBufferLevel::Primary {
             unsafe {
                 for (image, range_map) in self.images2.iter_mut() {
+                    // Images that track their layouts are left in the layout of their last use,
+                    // the next command buffer will pick up from there.
+                    if image.tracks_layouts() {
+                        for (_range, state) in range_map
+                            .iter_mut()
+                            .filter(|(_range, state)| !state.resource_uses.is_empty())
+                        {
+                            state.final_layout = match state.current_layout {
+                                ImageLayout::Undefined | ImageLayout::Preinitialized => {
+                                    ImageLayout::General
+                                }
+                                layout => layout,
+                            };
+                        }
+                    }
+
                     for (range, state) in range_map
                         .iter_mut()
                         .filter(|(_range, state)| state.final_layout != state.current_layout)
@@ -893,10 +1049,25 @@ impl SyncCommandBufferBuilder {
             secondary_resources_usage: self.secondary_resources_usage,
             _commands: self.commands,
             _barriers: self.barriers,
+            _split_barriers: self.split_barrier_locations,
+            _events: self.events,
         })
     }
 }
 
+// Returns the group of split barriers that the command at `command_index` waits on, creating it
+// if it doesn't exist yet.
+fn split_barrier_group(
+    groups: &mut Vec<(usize, DependencyInfo)>,
+    command_index: usize,
+) -> &mut DependencyInfo {
+    if !matches!(groups.last(), Some(&(index, _)) if index == command_index) {
+        groups.push((command_index, DependencyInfo::default()));
+    }
+
+    &mut groups.last_mut().unwrap().1
+}
+
 unsafe impl DeviceOwned for SyncCommandBufferBuilder {
     #[inline]
     fn device(&self) -> &Arc<Device> {
@@ -986,29 +1157,44 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) pipeline_compute: Option<Arc<ComputePipeline>>,
     pub(in crate::command_buffer) pipeline_graphics: Option<Arc<GraphicsPipeline>>,
     pub(in crate::command_buffer) vertex_buffers: HashMap<u32, Subbuffer<[u8]>>,
+    pub(in crate::command_buffer) transform_feedback_buffers: HashMap<u32, Subbuffer<[u8]>>,
 
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
 
+    pub(in crate::command_buffer) alpha_to_coverage_enable: Option<bool>,
+    pub(in crate::command_buffer) alpha_to_one_enable: Option<bool>,
     pub(in crate::command_buffer) blend_constants: Option<[f32; 4]>,
+    pub(in crate::command_buffer) color_blend_enable: HashMap<u32, bool>,
+    pub(in crate::command_buffer) color_blend_equation: HashMap<u32, AttachmentBlend>,
     pub(in crate::command_buffer) color_write_enable: Option<SmallVec<[bool; 4]>>,
+    pub(in crate::command_buffer) color_write_mask: HashMap<u32, ColorComponents>,
     pub(in crate::command_buffer) cull_mode: Option<CullMode>,
     pub(in crate::command_buffer) depth_bias: Option<DepthBias>,
     pub(in crate::command_buffer) depth_bias_enable: Option<bool>,
     pub(in crate::command_buffer) depth_bounds: Option<RangeInclusive<f32>>,
     pub(in crate::command_buffer) depth_bounds_test_enable: Option<bool>,
+    pub(in crate::command_buffer) depth_clamp_enable: Option<bool>,
     pub(in crate::command_buffer) depth_compare_op: Option<CompareOp>,
     pub(in crate::command_buffer) depth_test_enable: Option<bool>,
     pub(in crate::command_buffer) depth_write_enable: Option<bool>,
     pub(in crate::command_buffer) discard_rectangle: HashMap<u32, Scissor>,
+    pub(in crate::command_buffer) fragment_shading_rate: Option<FragmentShadingRate>,
     pub(in crate::command_buffer) front_face: Option<FrontFace>,
+    pub(in crate::command_buffer) line_rasterization_mode: Option<LineRasterizationMode>,
     pub(in crate::command_buffer) line_stipple: Option<LineStipple>,
+    pub(in crate::command_buffer) line_stipple_enable: Option<bool>,
     pub(in crate::command_buffer) line_width: Option<f32>,
     pub(in crate::command_buffer) logic_op: Option<LogicOp>,
+    pub(in crate::command_buffer) logic_op_enable: Option<bool>,
     pub(in crate::command_buffer) patch_control_points: Option<u32>,
+    pub(in crate::command_buffer) polygon_mode: Option<PolygonMode>,
     pub(in crate::command_buffer) primitive_restart_enable: Option<bool>,
     pub(in crate::command_buffer) primitive_topology: Option<PrimitiveTopology>,
+    pub(in crate::command_buffer) rasterization_samples: Option<SampleCount>,
+    pub(in crate::command_buffer) rasterization_stream: Option<u32>,
     pub(in crate::command_buffer) rasterizer_discard_enable: Option<bool>,
+    pub(in crate::command_buffer) sample_mask: Option<[u32; 2]>,
     pub(in crate::command_buffer) scissor: HashMap<u32, Scissor>,
     pub(in crate::command_buffer) scissor_with_count: Option<SmallVec<[Scissor; 2]>>,
     pub(in crate::command_buffer) stencil_compare_mask: StencilStateDynamic,
@@ -1016,6 +1202,7 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) stencil_reference: StencilStateDynamic,
     pub(in crate::command_buffer) stencil_test_enable: Option<bool>,
     pub(in crate::command_buffer) stencil_write_mask: StencilStateDynamic,
+    pub(in crate::command_buffer) vertex_input: Option<VertexInputState>,
     pub(in crate::command_buffer) viewport: HashMap<u32, Viewport>,
     pub(in crate::command_buffer) viewport_with_count: Option<SmallVec<[Viewport; 2]>>,
 }
@@ -1039,7 +1226,7 @@ impl CurrentState {
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
                 DynamicState::ExclusiveScissor => (), // TODO;
-                DynamicState::FragmentShadingRate => (), // TODO:
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
@@ -1057,7 +1244,7 @@ impl CurrentState {
                 DynamicState::StencilReference => self.stencil_reference = Default::default(),
                 DynamicState::StencilTestEnable => self.stencil_test_enable = None,
                 DynamicState::StencilWriteMask => self.stencil_write_mask = Default::default(),
-                DynamicState::VertexInput => (), // TODO:
+                DynamicState::VertexInput => self.vertex_input = None,
                 DynamicState::VertexInputBindingStride => (), // TODO:
                 DynamicState::Viewport => self.viewport.clear(),
                 DynamicState::ViewportCoarseSampleOrder => (), // TODO:
@@ -1065,36 +1252,36 @@ impl CurrentState {
                 DynamicState::ViewportWScaling => (),          // TODO:
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
                 DynamicState::TessellationDomainOrigin => (), // TODO:
-                DynamicState::DepthClampEnable => (),         // TODO:
-                DynamicState::PolygonMode => (),              // TODO:
-                DynamicState::RasterizationSamples => (),     // TODO:
-                DynamicState::SampleMask => (),               // TODO:
-                DynamicState::AlphaToCoverageEnable => (),    // TODO:
-                DynamicState::AlphaToOneEnable => (),         // TODO:
-                DynamicState::LogicOpEnable => (),            // TODO:
-                DynamicState::ColorBlendEnable => (),         // TODO:
-                DynamicState::ColorBlendEquation => (),       // TODO:
-                DynamicState::ColorWriteMask => (),           // TODO:
-                DynamicState::RasterizationStream => (),      // TODO:
+                DynamicState::DepthClampEnable => self.depth_clamp_enable = None,
+                DynamicState::PolygonMode => self.polygon_mode = None,
+                DynamicState::RasterizationSamples => self.rasterization_samples = None,
+                DynamicState::SampleMask => self.sample_mask = None,
+                DynamicState::AlphaToCoverageEnable => self.alpha_to_coverage_enable = None,
+                DynamicState::AlphaToOneEnable => self.alpha_to_one_enable = None,
+                DynamicState::LogicOpEnable => self.logic_op_enable = None,
+                DynamicState::ColorBlendEnable => self.color_blend_enable.clear(),
+                DynamicState::ColorBlendEquation => self.color_blend_equation.clear(),
+                DynamicState::ColorWriteMask => self.color_write_mask.clear(),
+                DynamicState::RasterizationStream => self.rasterization_stream = None,
                 DynamicState::ConservativeRasterizationMode => (), // TODO:
                 DynamicState::ExtraPrimitiveOverestimationSize => (), // TODO:
-                DynamicState::DepthClipEnable => (),          // TODO:
-                DynamicState::SampleLocationsEnable => (),    // TODO:
-                DynamicState::ColorBlendAdvanced => (),       // TODO:
-                DynamicState::ProvokingVertexMode => (),      // TODO:
-                DynamicState::LineRasterizationMode => (),    // TODO:
-                DynamicState::LineStippleEnable => (),        // TODO:
+                DynamicState::DepthClipEnable => (),               // TODO:
+                DynamicState::SampleLocationsEnable => (),         // TODO:
+                DynamicState::ColorBlendAdvanced => (),            // TODO:
+                DynamicState::ProvokingVertexMode => (),           // TODO:
+                DynamicState::LineRasterizationMode => self.line_rasterization_mode = None,
+                DynamicState::LineStippleEnable => self.line_stipple_enable = None,
                 DynamicState::DepthClipNegativeOneToOne => (), // TODO:
-                DynamicState::ViewportWScalingEnable => (),   // TODO:
-                DynamicState::ViewportSwizzle => (),          // TODO:
-                DynamicState::CoverageToColorEnable => (),    // TODO:
-                DynamicState::CoverageToColorLocation => (),  // TODO:
-                DynamicState::CoverageModulationMode => (),   // TODO:
+                DynamicState::ViewportWScalingEnable => (),    // TODO:
+                DynamicState::ViewportSwizzle => (),           // TODO:
+                DynamicState::CoverageToColorEnable => (),     // TODO:
+                DynamicState::CoverageToColorLocation => (),   // TODO:
+                DynamicState::CoverageModulationMode => (),    // TODO:
                 DynamicState::CoverageModulationTableEnable => (), // TODO:
-                DynamicState::CoverageModulationTable => (),  // TODO:
-                DynamicState::ShadingRateImageEnable => (),   // TODO:
+                DynamicState::CoverageModulationTable => (),   // TODO:
+                DynamicState::ShadingRateImageEnable => (),    // TODO:
                 DynamicState::RepresentativeFragmentTestEnable => (), // TODO:
-                DynamicState::CoverageReductionMode => (),    // TODO:
+                DynamicState::CoverageReductionMode => (),     // TODO:
             }
         }
     }
@@ -1166,6 +1353,7 @@ pub(in crate::command_buffer) struct DescriptorSetState {
 pub enum SetOrPush {
     Set(DescriptorSetWithOffsets),
     Push(DescriptorSetResources),
+    Buffer(Arc<DescriptorBufferSet>),
 }
 
 impl SetOrPush {
@@ -1174,6 +1362,7 @@ impl SetOrPush {
         match self {
             Self::Set(set) => set.as_ref().0.resources(),
             Self::Push(resources) => resources,
+            Self::Buffer(set) => set.resources(),
         }
     }
 
@@ -1181,7 +1370,7 @@ impl SetOrPush {
     pub fn dynamic_offsets(&self) -> &[u32] {
         match self {
             Self::Set(set) => set.as_ref().1,
-            Self::Push(_) => &[],
+            Self::Push(_) | Self::Buffer(_) => &[],
         }
     }
 }
@@ -1250,6 +1439,15 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.vertex_buffers.get(&binding_num)
     }
 
+    /// Returns the transform feedback buffer currently bound to a given binding slot number, or
+    /// `None` if nothing has been bound yet.
+    #[inline]
+    pub fn transform_feedback_buffer(&self, binding_num: u32) -> Option<&'a Subbuffer<[u8]>> {
+        self.current_state
+            .transform_feedback_buffers
+            .get(&binding_num)
+    }
+
     /// Returns a set containing push constant bytes that have been set.
     #[inline]
     pub fn push_constants(&self) -> &'a RangeSet<u32> {
@@ -1264,12 +1462,38 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.push_constants_pipeline_layout.as_ref()
     }
 
+    /// Returns whether alpha-to-coverage is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn alpha_to_coverage_enable(&self) -> Option<bool> {
+        self.current_state.alpha_to_coverage_enable
+    }
+
+    /// Returns whether alpha-to-one is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn alpha_to_one_enable(&self) -> Option<bool> {
+        self.current_state.alpha_to_one_enable
+    }
+
     /// Returns the current blend constants, or `None` if nothing has been set yet.
     #[inline]
     pub fn blend_constants(&self) -> Option<[f32; 4]> {
         self.current_state.blend_constants
     }
 
+    /// Returns whether blending is enabled for a given color attachment, or `None` if nothing
+    /// has been set yet.
+    #[inline]
+    pub fn color_blend_enable(&self, num: u32) -> Option<bool> {
+        self.current_state.color_blend_enable.get(&num).copied()
+    }
+
+    /// Returns the current blend equation for a given color attachment, or `None` if nothing
+    /// has been set yet.
+    #[inline]
+    pub fn color_blend_equation(&self, num: u32) -> Option<AttachmentBlend> {
+        self.current_state.color_blend_equation.get(&num).copied()
+    }
+
     /// Returns the current color write enable settings, or `None` if nothing has been set yet.
     #[inline]
     pub fn color_write_enable(&self) -> Option<&'a [bool]> {
@@ -1279,6 +1503,13 @@ impl<'a> CommandBufferBuilderState<'a> {
             .map(|x| x.as_slice())
     }
 
+    /// Returns the current color write mask for a given color attachment, or `None` if nothing
+    /// has been set yet.
+    #[inline]
+    pub fn color_write_mask(&self, num: u32) -> Option<ColorComponents> {
+        self.current_state.color_write_mask.get(&num).copied()
+    }
+
     /// Returns the current cull mode, or `None` if nothing has been set yet.
     #[inline]
     pub fn cull_mode(&self) -> Option<CullMode> {
@@ -1309,6 +1540,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.depth_bounds_test_enable
     }
 
+    /// Returns whether depth clamping is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn depth_clamp_enable(&self) -> Option<bool> {
+        self.current_state.depth_clamp_enable
+    }
+
     /// Returns the current depth compare op, or `None` if nothing has been set yet.
     #[inline]
     pub fn depth_compare_op(&self) -> Option<CompareOp> {
@@ -1333,18 +1570,37 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.discard_rectangle.get(&num)
     }
 
+    /// Returns the current fragment shading rate and combiner operations, or `None` if nothing has
+    /// been set yet.
+    #[inline]
+    pub fn fragment_shading_rate(&self) -> Option<FragmentShadingRate> {
+        self.current_state.fragment_shading_rate
+    }
+
     /// Returns the current front face, or `None` if nothing has been set yet.
     #[inline]
     pub fn front_face(&self) -> Option<FrontFace> {
         self.current_state.front_face
     }
 
+    /// Returns the current line rasterization mode, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn line_rasterization_mode(&self) -> Option<LineRasterizationMode> {
+        self.current_state.line_rasterization_mode
+    }
+
     /// Returns the current line stipple settings, or `None` if nothing has been set yet.
     #[inline]
     pub fn line_stipple(&self) -> Option<LineStipple> {
         self.current_state.line_stipple
     }
 
+    /// Returns whether line stippling is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn line_stipple_enable(&self) -> Option<bool> {
+        self.current_state.line_stipple_enable
+    }
+
     /// Returns the current line width, or `None` if nothing has been set yet.
     #[inline]
     pub fn line_width(&self) -> Option<f32> {
@@ -1357,12 +1613,24 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.logic_op
     }
 
+    /// Returns whether logic operations are enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn logic_op_enable(&self) -> Option<bool> {
+        self.current_state.logic_op_enable
+    }
+
     /// Returns the current number of patch control points, or `None` if nothing has been set yet.
     #[inline]
     pub fn patch_control_points(&self) -> Option<u32> {
         self.current_state.patch_control_points
     }
 
+    /// Returns the current polygon mode, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn polygon_mode(&self) -> Option<PolygonMode> {
+        self.current_state.polygon_mode
+    }
+
     /// Returns whether primitive restart is enabled, or `None` if nothing has been set yet.
     #[inline]
     pub fn primitive_restart_enable(&self) -> Option<bool> {
@@ -1375,12 +1643,31 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.primitive_topology
     }
 
+    /// Returns the current number of rasterization samples, or `None` if nothing has been set
+    /// yet.
+    #[inline]
+    pub fn rasterization_samples(&self) -> Option<SampleCount> {
+        self.current_state.rasterization_samples
+    }
+
+    /// Returns the current rasterization stream, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn rasterization_stream(&self) -> Option<u32> {
+        self.current_state.rasterization_stream
+    }
+
     /// Returns whether rasterizer discard is enabled, or `None` if nothing has been set yet.
     #[inline]
     pub fn rasterizer_discard_enable(&self) -> Option<bool> {
         self.current_state.rasterizer_discard_enable
     }
 
+    /// Returns the current sample mask, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn sample_mask(&self) -> Option<[u32; 2]> {
+        self.current_state.sample_mask
+    }
+
     /// Returns the current scissor for a given viewport slot, or `None` if nothing has been set
     /// yet.
     #[inline]
@@ -1427,6 +1714,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.stencil_write_mask
     }
 
+    /// Returns the current vertex input state, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn vertex_input(&self) -> Option<&'a VertexInputState> {
+        self.current_state.vertex_input.as_ref()
+    }
+
     /// Returns the current viewport for a given viewport slot, or `None` if nothing has been set
     /// yet.
     #[inline]
@@ -63,6 +63,13 @@
 //! command. If so, it will try to merge this barrier with the prototype and add the command to the
 //! queue. If not possible, the queue will be entirely flushed and the command added to a fresh new
 //! queue with a fresh new barrier prototype.
+//!
+//! ## Split barriers
+//!
+//! If a command depends on a command that was already flushed, but there are other queued
+//! commands in between that it doesn't depend on, a regular barrier would needlessly make those
+//! commands wait as well. If enabled, the builder instead signals an event along with the flushed
+//! barrier, and waits on the event right before the command that needs it.
 
 pub use self::builder::{
     CommandBufferBuilderState, SetOrPush, StencilOpStateDynamic, StencilStateDynamic,
@@ -78,7 +85,7 @@ use crate::{
     buffer::Subbuffer,
     device::{Device, DeviceOwned},
     image::{ImageAccess, ImageLayout, ImageSubresourceRange},
-    sync::PipelineMemoryAccess,
+    sync::{event::Event, PipelineMemoryAccess},
     DeviceSize,
 };
 use std::{
@@ -103,6 +110,13 @@ pub struct SyncCommandBuffer {
     // TODO: present only in cfg(debug_assertions)?
     _barriers: Vec<usize>,
 
+    // Locations within commands that split barriers were set and waited on. For debugging
+    // purposes.
+    _split_barriers: Vec<(usize, usize)>,
+
+    // Events used by split barriers.
+    _events: Vec<Arc<Event>>,
+
     // Resources accessed by this command buffer.
     resources_usage: CommandBufferResourcesUsage,
 
@@ -179,8 +193,8 @@ mod tests {
                 CommandBufferAllocator, CommandBufferBuilderAlloc, StandardCommandBufferAllocator,
             },
             sys::CommandBufferBeginInfo,
-            AutoCommandBufferBuilder, CommandBufferLevel, CommandBufferUsage,
-            PrimaryCommandBufferAbstract,
+            AutoCommandBufferBuilder, ClearColorImageInfo, CommandBufferLevel, CommandBufferUsage,
+            CopyBufferInfo, CopyImageToBufferInfo, PrimaryCommandBufferAbstract,
         },
         descriptor_set::{
             allocator::StandardDescriptorSetAllocator,
@@ -190,6 +204,9 @@ mod tests {
             },
             PersistentDescriptorSet, WriteDescriptorSet,
         },
+        device::{DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo, QueueFlags},
+        format::Format,
+        image::{ImageCreateFlags, ImageDimensions, ImageUsage, StorageImage},
         memory::allocator::{AllocationCreateInfo, MemoryUsage, StandardMemoryAllocator},
         pipeline::{layout::PipelineLayoutCreateInfo, PipelineBindPoint, PipelineLayout},
         sampler::{Sampler, SamplerCreateInfo},
@@ -331,6 +348,284 @@ mod tests {
         }
     }
 
+    #[test]
+    fn split_barrier() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let builder_alloc = cb_allocator
+                .allocate(queue.queue_family_index(), CommandBufferLevel::Primary, 1)
+                .unwrap()
+                .next()
+                .unwrap();
+            let mut sync = SyncCommandBufferBuilder::new(
+                builder_alloc.inner(),
+                CommandBufferBeginInfo {
+                    usage: CommandBufferUsage::MultipleSubmit,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            sync.split_barriers = true;
+
+            let memory_allocator = StandardMemoryAllocator::new_default(device);
+            let [a, b, c] = [(); 3].map(|_| {
+                Buffer::from_data(
+                    &memory_allocator,
+                    BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo {
+                        usage: MemoryUsage::Upload,
+                        ..Default::default()
+                    },
+                    0u32,
+                )
+                .unwrap()
+                .into_slice()
+            });
+
+            sync.fill_buffer(a.clone(), 1).unwrap();
+            sync.fill_buffer(b.clone(), 1).unwrap();
+            // Conflicts with the previous command, so a barrier is inserted.
+            sync.fill_buffer(b, 2).unwrap();
+            sync.fill_buffer(c, 1).unwrap();
+            // Conflicts with a command that was already flushed, and there is independent work
+            // in between, so the barrier is split.
+            sync.fill_buffer(a, 2).unwrap();
+
+            let primary = sync.build().unwrap();
+            assert_eq!(&primary._barriers, &[0, 2]);
+            // The event is set after the first write to `a`, along with the barrier that is
+            // submitted before the second write to `b`, and waited on right before the second
+            // write to `a`.
+            assert_eq!(&primary._split_barriers, &[(2, 4)]);
+            assert_eq!(primary._events.len(), 1);
+        }
+    }
+
+    #[test]
+    fn split_barrier_no_independent_work() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let builder_alloc = cb_allocator
+                .allocate(queue.queue_family_index(), CommandBufferLevel::Primary, 1)
+                .unwrap()
+                .next()
+                .unwrap();
+            let mut sync = SyncCommandBufferBuilder::new(
+                builder_alloc.inner(),
+                CommandBufferBeginInfo {
+                    usage: CommandBufferUsage::MultipleSubmit,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            sync.split_barriers = true;
+
+            let memory_allocator = StandardMemoryAllocator::new_default(device);
+            let [a, b] = [(); 2].map(|_| {
+                Buffer::from_data(
+                    &memory_allocator,
+                    BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo {
+                        usage: MemoryUsage::Upload,
+                        ..Default::default()
+                    },
+                    0u32,
+                )
+                .unwrap()
+                .into_slice()
+            });
+
+            sync.fill_buffer(a.clone(), 1).unwrap();
+            sync.fill_buffer(b.clone(), 1).unwrap();
+            sync.fill_buffer(b.clone(), 2).unwrap();
+            // Reading `b` conflicts with the previous command, which is flushed. The copy is then
+            // the only pending command, so there is nothing that a split barrier for the write to
+            // `a` could overlap with, and a regular barrier is used.
+            sync.copy_buffer(CopyBufferInfo::buffers(b, a)).unwrap();
+
+            let primary = sync.build().unwrap();
+            assert_eq!(&primary._barriers, &[0, 2, 3]);
+            assert!(primary._split_barriers.is_empty());
+            assert!(primary._events.is_empty());
+        }
+    }
+
+    #[test]
+    fn split_barrier_layout_transition() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let builder_alloc = cb_allocator
+                .allocate(queue.queue_family_index(), CommandBufferLevel::Primary, 1)
+                .unwrap()
+                .next()
+                .unwrap();
+            let mut sync = SyncCommandBufferBuilder::new(
+                builder_alloc.inner(),
+                CommandBufferBeginInfo {
+                    usage: CommandBufferUsage::MultipleSubmit,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            sync.split_barriers = true;
+
+            let memory_allocator = StandardMemoryAllocator::new_default(device);
+            let image = StorageImage::with_usage(
+                &memory_allocator,
+                ImageDimensions::Dim2d {
+                    width: 4,
+                    height: 4,
+                    array_layers: 1,
+                },
+                Format::R8G8B8A8_UNORM,
+                ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                ImageCreateFlags::empty(),
+                [queue.queue_family_index()],
+            )
+            .unwrap();
+            let [a, b, c] = [(); 3].map(|_| {
+                Buffer::from_iter(
+                    &memory_allocator,
+                    BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo {
+                        usage: MemoryUsage::Upload,
+                        ..Default::default()
+                    },
+                    [0u32; 16],
+                )
+                .unwrap()
+            });
+
+            sync.clear_color_image(ClearColorImageInfo::image(image.clone()))
+                .unwrap();
+            sync.fill_buffer(a.clone(), 1).unwrap();
+            sync.fill_buffer(a, 2).unwrap();
+            sync.fill_buffer(b, 1).unwrap();
+            // Conflicts with the first clear, without a layout transition, so the barrier is
+            // split.
+            sync.clear_color_image(ClearColorImageInfo::image(image.clone()))
+                .unwrap();
+            // Needs a layout transition, which is never split, so the pending commands are
+            // flushed and a regular barrier is used.
+            sync.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(image, c))
+                .unwrap();
+
+            let primary = sync.build().unwrap();
+            assert_eq!(&primary._barriers, &[0, 2, 5]);
+            assert_eq!(&primary._split_barriers, &[(2, 4)]);
+            assert_eq!(primary._events.len(), 1);
+        }
+    }
+
+    #[test]
+    fn conditional_rendering_predicate_write() {
+        unsafe {
+            let instance = instance!();
+
+            let enabled_extensions = DeviceExtensions {
+                ext_conditional_rendering: true,
+                ..DeviceExtensions::empty()
+            };
+            let enabled_features = Features {
+                conditional_rendering: true,
+                ..Features::empty()
+            };
+
+            let (physical_device, queue_family_index) = match instance
+                .enumerate_physical_devices()
+                .unwrap()
+                .filter(|p| {
+                    p.supported_extensions().contains(&enabled_extensions)
+                        && p.supported_features().contains(&enabled_features)
+                })
+                .find_map(|p| {
+                    p.queue_family_properties()
+                        .iter()
+                        .position(|q| q.queue_flags.intersects(QueueFlags::GRAPHICS))
+                        .map(|i| (p, i as u32))
+                }) {
+                Some(x) => x,
+                None => return,
+            };
+
+            let (device, _) = match Device::new(
+                physical_device,
+                DeviceCreateInfo {
+                    queue_create_infos: vec![QueueCreateInfo {
+                        queue_family_index,
+                        ..Default::default()
+                    }],
+                    enabled_extensions,
+                    enabled_features,
+                    ..Default::default()
+                },
+            ) {
+                Ok(x) => x,
+                Err(_) => return,
+            };
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let builder_alloc = cb_allocator
+                .allocate(queue_family_index, CommandBufferLevel::Primary, 1)
+                .unwrap()
+                .next()
+                .unwrap();
+            let mut sync = SyncCommandBufferBuilder::new(
+                builder_alloc.inner(),
+                CommandBufferBeginInfo {
+                    usage: CommandBufferUsage::MultipleSubmit,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let memory_allocator = StandardMemoryAllocator::new_default(device);
+            let predicate = Buffer::from_data(
+                &memory_allocator,
+                BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_DST | BufferUsage::CONDITIONAL_RENDERING,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    usage: MemoryUsage::Upload,
+                    ..Default::default()
+                },
+                0u32,
+            )
+            .unwrap();
+
+            sync.fill_buffer(predicate.clone().into_slice(), 1).unwrap();
+            // Reads the value written by the previous command, so a barrier is inserted.
+            sync.begin_conditional_rendering(predicate.clone(), false)
+                .unwrap();
+            sync.end_conditional_rendering();
+            // Overwrites the value read by the conditional rendering block.
+            sync.fill_buffer(predicate.into_slice(), 0).unwrap();
+
+            let primary = sync.build().unwrap();
+            assert_eq!(&primary._barriers, &[1, 3]);
+        }
+    }
+
     #[test]
     fn vertex_buffer_binding() {
         unsafe {
@@ -18,7 +18,7 @@
 
 use self::sorted_map::SortedMap;
 use super::{
-    layout::DescriptorSetLayout,
+    layout::{DescriptorSetLayout, DescriptorType},
     pool::{
         DescriptorPool, DescriptorPoolAllocError, DescriptorPoolCreateInfo,
         DescriptorSetAllocateInfo,
@@ -148,6 +148,7 @@ unsafe impl DescriptorSetAllocator for StandardDescriptorSetAllocator {
     ///
     /// - Panics if the provided `layout` is for push descriptors rather than regular descriptor
     ///   sets.
+    /// - Panics if the provided `layout` is for descriptor buffers.
     /// - Panics if the provided `variable_descriptor_count` is greater than the maximum number of
     ///   variable count descriptors in the set.
     #[inline]
@@ -161,6 +162,11 @@ unsafe impl DescriptorSetAllocator for StandardDescriptorSetAllocator {
             "the provided descriptor set layout is for push descriptors, and cannot be used to \
             build a descriptor set object",
         );
+        assert!(
+            !layout.descriptor_buffer(),
+            "the provided descriptor set layout is for descriptor buffers, and cannot be used to \
+            build a descriptor set object",
+        );
 
         let max_count = layout.variable_descriptor_count();
 
@@ -245,6 +251,19 @@ impl FixedEntry {
     }
 }
 
+/// Returns the number of inline uniform block bindings in `layout`, which must be reserved in
+/// the pool separately from the number of bytes.
+fn inline_uniform_block_bindings(layout: &DescriptorSetLayout) -> u32 {
+    layout
+        .bindings()
+        .values()
+        .filter(|binding| {
+            binding.descriptor_type == DescriptorType::InlineUniformBlock
+                && binding.descriptor_count != 0
+        })
+        .count() as u32
+}
+
 #[derive(Debug)]
 struct FixedPool {
     // The actual Vulkan descriptor pool. This field isn't actually used anywhere, but we need to
@@ -266,6 +285,9 @@ impl FixedPool {
                     .iter()
                     .map(|(&ty, &count)| (ty, count * set_count as u32))
                     .collect(),
+                update_after_bind: layout.update_after_bind_pool(),
+                max_inline_uniform_block_bindings: inline_uniform_block_bindings(layout)
+                    * set_count as u32,
                 ..Default::default()
             },
         )?;
@@ -402,6 +424,9 @@ impl VariablePool {
                     .iter()
                     .map(|(&ty, &count)| (ty, count * MAX_SETS as u32))
                     .collect(),
+                update_after_bind: layout.update_after_bind_pool(),
+                max_inline_uniform_block_bindings: inline_uniform_block_bindings(layout)
+                    * MAX_SETS as u32,
                 ..Default::default()
             },
         )
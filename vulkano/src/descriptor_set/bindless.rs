@@ -0,0 +1,684 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A large, global descriptor set for bindless resource access.
+//!
+//! Instead of creating a descriptor set for every combination of resources that a draw call
+//! needs, a [`BindlessDescriptorSet`] holds big arrays of sampled images, storage buffers and
+//! samplers. Resources are added to the set once, which gives them a slot index. Shaders then
+//! receive these indices, for example through push constants, and index into runtime-sized
+//! arrays:
+//!
+//! ```glsl
+//! #extension GL_EXT_nonuniform_qualifier : require
+//!
+//! layout(set = 0, binding = 0) uniform texture2D textures[];
+//! layout(set = 0, binding = 1) buffer Data { vec4 data[]; } buffers[];
+//! layout(set = 0, binding = 2) uniform sampler samplers[];
+//! ```
+//!
+//! The layout of the set uses `update_after_bind`, `update_unused_while_pending` and
+//! `partially_bound` for every binding, so descriptors can be written while the set is bound or
+//! in use by the device, and slots that are not in use don't need to hold anything. The device
+//! must have the corresponding descriptor indexing features enabled: `runtime_descriptor_array`,
+//! `descriptor_binding_partially_bound`, `descriptor_binding_update_unused_while_pending`, and
+//! the `update_after_bind` features for sampled images and storage buffers.
+//!
+//! To bind the set, call [`BindlessDescriptorSet::snapshot`]. A snapshot captures which resources
+//! are currently in the set, so that command buffers keep them alive. A slot that has been
+//! removed is only reused once all snapshots that were taken before its removal have been
+//! dropped, so a pending command buffer never sees a slot change under it.
+
+use super::{
+    check_descriptor_write,
+    layout::{
+        DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+        DescriptorSetLayoutCreationError, DescriptorType,
+    },
+    pool::{
+        DescriptorPool, DescriptorPoolAllocError, DescriptorPoolCreateInfo,
+        DescriptorSetAllocateInfo,
+    },
+    sys::UnsafeDescriptorSet,
+    DescriptorSet, DescriptorSetResources, DescriptorSetUpdateError, WriteDescriptorSet,
+};
+use crate::{
+    buffer::Subbuffer,
+    device::{Device, DeviceOwned},
+    image::view::ImageViewAbstract,
+    sampler::Sampler,
+    shader::ShaderStages,
+    OomError, VulkanObject,
+};
+use ahash::HashSet;
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    hash::{Hash, Hasher},
+    sync::{Arc, Weak},
+};
+
+/// The binding number of the sampled image array.
+pub const SAMPLED_IMAGE_BINDING: u32 = 0;
+
+/// The binding number of the storage buffer array.
+pub const STORAGE_BUFFER_BINDING: u32 = 1;
+
+/// The binding number of the sampler array.
+pub const SAMPLER_BINDING: u32 = 2;
+
+/// A global descriptor set with slot allocation for sampled images, storage buffers and
+/// samplers.
+///
+/// See the [module-level documentation](self) for more.
+pub struct BindlessDescriptorSet {
+    inner: Arc<BindlessInner>,
+    set: UnsafeDescriptorSet,
+    resources: DescriptorSetResources,
+
+    sampled_images: SlotAllocator,
+    storage_buffers: SlotAllocator,
+    samplers: SlotAllocator,
+    // Slots that were removed, along with the id of the next snapshot at the time of removal.
+    pending_frees: Vec<(u32, u32, u64)>,
+
+    snapshots: Vec<(u64, Weak<BindlessSnapshot>)>,
+    next_snapshot_id: u64,
+    modified: bool,
+}
+
+impl BindlessDescriptorSet {
+    /// Creates a new `BindlessDescriptorSet`, along with its layout and its own descriptor pool.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: BindlessDescriptorSetCreateInfo,
+    ) -> Result<BindlessDescriptorSet, BindlessDescriptorSetCreationError> {
+        let BindlessDescriptorSetCreateInfo {
+            max_sampled_images,
+            max_storage_buffers,
+            max_samplers,
+            stages,
+            _ne: _,
+        } = create_info;
+
+        let bindings: BTreeMap<_, _> = [
+            (
+                SAMPLED_IMAGE_BINDING,
+                DescriptorType::SampledImage,
+                max_sampled_images,
+            ),
+            (
+                STORAGE_BUFFER_BINDING,
+                DescriptorType::StorageBuffer,
+                max_storage_buffers,
+            ),
+            (SAMPLER_BINDING, DescriptorType::Sampler, max_samplers),
+        ]
+        .into_iter()
+        .filter(|&(_, _, descriptor_count)| descriptor_count != 0)
+        .map(|(binding_num, descriptor_type, descriptor_count)| {
+            (
+                binding_num,
+                DescriptorSetLayoutBinding {
+                    descriptor_count,
+                    update_after_bind: true,
+                    update_unused_while_pending: true,
+                    partially_bound: true,
+                    stages,
+                    ..DescriptorSetLayoutBinding::descriptor_type(descriptor_type)
+                },
+            )
+        })
+        .collect();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings,
+                update_after_bind_pool: true,
+                ..Default::default()
+            },
+        )?;
+
+        let pool = DescriptorPool::new(
+            device,
+            DescriptorPoolCreateInfo {
+                max_sets: 1,
+                pool_sizes: layout.descriptor_counts().clone(),
+                update_after_bind: true,
+                ..Default::default()
+            },
+        )?;
+
+        let set = unsafe {
+            pool.allocate_descriptor_sets([DescriptorSetAllocateInfo {
+                layout: &layout,
+                variable_descriptor_count: 0,
+            }])?
+            .next()
+            .unwrap()
+        };
+
+        Ok(BindlessDescriptorSet {
+            resources: DescriptorSetResources::new(&layout, 0),
+            inner: Arc::new(BindlessInner {
+                layout,
+                _pool: Mutex::new(pool),
+            }),
+            set,
+            sampled_images: SlotAllocator::new(max_sampled_images),
+            storage_buffers: SlotAllocator::new(max_storage_buffers),
+            samplers: SlotAllocator::new(max_samplers),
+            pending_frees: Vec::new(),
+            snapshots: Vec::new(),
+            next_snapshot_id: 0,
+            modified: true,
+        })
+    }
+
+    /// Returns the layout of the descriptor set.
+    #[inline]
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.inner.layout
+    }
+
+    /// Adds a sampled image to the set, and returns the slot index that shaders can use to
+    /// access it.
+    pub fn add_sampled_image(
+        &mut self,
+        image_view: Arc<dyn ImageViewAbstract>,
+    ) -> Result<u32, BindlessDescriptorSetError> {
+        self.add(SAMPLED_IMAGE_BINDING, |slot| {
+            WriteDescriptorSet::image_view_array(SAMPLED_IMAGE_BINDING, slot, [image_view])
+        })
+    }
+
+    /// Adds a storage buffer to the set, and returns the slot index that shaders can use to
+    /// access it.
+    pub fn add_storage_buffer(
+        &mut self,
+        buffer: Subbuffer<impl ?Sized>,
+    ) -> Result<u32, BindlessDescriptorSetError> {
+        self.add(STORAGE_BUFFER_BINDING, |slot| {
+            WriteDescriptorSet::buffer_array(STORAGE_BUFFER_BINDING, slot, [buffer])
+        })
+    }
+
+    /// Adds a sampler to the set, and returns the slot index that shaders can use to access it.
+    pub fn add_sampler(
+        &mut self,
+        sampler: Arc<Sampler>,
+    ) -> Result<u32, BindlessDescriptorSetError> {
+        self.add(SAMPLER_BINDING, |slot| {
+            WriteDescriptorSet::sampler_array(SAMPLER_BINDING, slot, [sampler])
+        })
+    }
+
+    /// Removes the sampled image in `slot` from the set.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `slot` does not hold a sampled image.
+    #[inline]
+    pub fn remove_sampled_image(&mut self, slot: u32) {
+        self.remove(SAMPLED_IMAGE_BINDING, slot);
+    }
+
+    /// Removes the storage buffer in `slot` from the set.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `slot` does not hold a storage buffer.
+    #[inline]
+    pub fn remove_storage_buffer(&mut self, slot: u32) {
+        self.remove(STORAGE_BUFFER_BINDING, slot);
+    }
+
+    /// Removes the sampler in `slot` from the set.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `slot` does not hold a sampler.
+    #[inline]
+    pub fn remove_sampler(&mut self, slot: u32) {
+        self.remove(SAMPLER_BINDING, slot);
+    }
+
+    /// Returns a descriptor set that can be bound to a command buffer, holding the resources that
+    /// are currently in the set.
+    ///
+    /// Snapshots are cheap to bind, but taking one clones the list of resources. If nothing was
+    /// added or removed since the previous snapshot and that snapshot is still alive, it is
+    /// returned again.
+    pub fn snapshot(&mut self) -> Arc<BindlessSnapshot> {
+        if !self.modified {
+            if let Some(snapshot) = self.snapshots.last().and_then(|(_, s)| s.upgrade()) {
+                return snapshot;
+            }
+        }
+
+        let snapshot = Arc::new(BindlessSnapshot {
+            inner: self.inner.clone(),
+            set: UnsafeDescriptorSet::new(self.set.handle()),
+            resources: self.resources.clone(),
+        });
+
+        self.snapshots
+            .push((self.next_snapshot_id, Arc::downgrade(&snapshot)));
+        self.next_snapshot_id += 1;
+        self.modified = false;
+
+        snapshot
+    }
+
+    fn add(
+        &mut self,
+        binding_num: u32,
+        write: impl FnOnce(u32) -> WriteDescriptorSet,
+    ) -> Result<u32, BindlessDescriptorSetError> {
+        self.collect_pending_frees();
+
+        let slot = self
+            .slot_allocator(binding_num)
+            .allocate()
+            .ok_or(BindlessDescriptorSetError::SlotsExhausted { binding_num })?;
+        let write = write(slot);
+
+        if let Err(err) = check_descriptor_write(&write, &self.inner.layout, 0) {
+            let slot_allocator = self.slot_allocator(binding_num);
+            slot_allocator.release(slot);
+            slot_allocator.free(slot);
+            return Err(err.into());
+        }
+
+        // The layout has `update_after_bind` and `update_unused_while_pending`, and the slot is
+        // not referenced by any live snapshot, so it can't be in use.
+        unsafe {
+            self.set.write(&self.inner.layout, [&write]);
+        }
+
+        self.resources.update(&write);
+        self.modified = true;
+
+        Ok(slot)
+    }
+
+    fn remove(&mut self, binding_num: u32, slot: u32) {
+        // The slot stops being in use right away, so that removing it twice panics, but it only
+        // becomes available for allocation in `collect_pending_frees`.
+        assert!(
+            self.slot_allocator(binding_num).release(slot),
+            "slot {} of binding {} is not in use",
+            slot,
+            binding_num,
+        );
+
+        self.resources.invalidate(binding_num, slot..slot + 1);
+        self.pending_frees
+            .push((binding_num, slot, self.next_snapshot_id));
+        self.modified = true;
+    }
+
+    // Returns the removed slots to their allocators once no snapshot that could still reference
+    // them is alive.
+    fn collect_pending_frees(&mut self) {
+        self.snapshots
+            .retain(|(_, snapshot)| snapshot.strong_count() != 0);
+        let oldest_snapshot_id = self.snapshots.first().map(|&(id, _)| id);
+
+        let mut pending_frees = std::mem::take(&mut self.pending_frees);
+        pending_frees.retain(|&(binding_num, slot, next_snapshot_id)| {
+            if oldest_snapshot_id.map_or(true, |id| id >= next_snapshot_id) {
+                self.slot_allocator(binding_num).free(slot);
+                false
+            } else {
+                true
+            }
+        });
+        self.pending_frees = pending_frees;
+    }
+
+    fn slot_allocator(&mut self, binding_num: u32) -> &mut SlotAllocator {
+        match binding_num {
+            SAMPLED_IMAGE_BINDING => &mut self.sampled_images,
+            STORAGE_BUFFER_BINDING => &mut self.storage_buffers,
+            SAMPLER_BINDING => &mut self.samplers,
+            _ => unreachable!(),
+        }
+    }
+}
+
+unsafe impl DeviceOwned for BindlessDescriptorSet {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.layout.device()
+    }
+}
+
+/// Parameters to create a new `BindlessDescriptorSet`.
+#[derive(Clone, Debug)]
+pub struct BindlessDescriptorSetCreateInfo {
+    /// The number of slots for sampled images. If `0`, the binding is left out of the layout.
+    ///
+    /// The default value is `0`.
+    pub max_sampled_images: u32,
+
+    /// The number of slots for storage buffers. If `0`, the binding is left out of the layout.
+    ///
+    /// The default value is `0`.
+    pub max_storage_buffers: u32,
+
+    /// The number of slots for samplers. If `0`, the binding is left out of the layout.
+    ///
+    /// The default value is `0`.
+    pub max_samplers: u32,
+
+    /// The shader stages that can access the descriptors.
+    ///
+    /// The default value is [`ShaderStages::all_graphics()`] and [`ShaderStages::COMPUTE`].
+    pub stages: ShaderStages,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for BindlessDescriptorSetCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_sampled_images: 0,
+            max_storage_buffers: 0,
+            max_samplers: 0,
+            stages: ShaderStages::all_graphics() | ShaderStages::COMPUTE,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The state of a [`BindlessDescriptorSet`] at a point in time, which can be bound to a command
+/// buffer.
+pub struct BindlessSnapshot {
+    inner: Arc<BindlessInner>,
+    set: UnsafeDescriptorSet,
+    resources: DescriptorSetResources,
+}
+
+unsafe impl DescriptorSet for BindlessSnapshot {
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        &self.set
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.inner.layout
+    }
+
+    #[inline]
+    fn variable_descriptor_count(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn resources(&self) -> &DescriptorSetResources {
+        &self.resources
+    }
+}
+
+unsafe impl DeviceOwned for BindlessSnapshot {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.layout.device()
+    }
+}
+
+impl PartialEq for BindlessSnapshot {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for BindlessSnapshot {}
+
+impl Hash for BindlessSnapshot {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
+struct BindlessInner {
+    layout: Arc<DescriptorSetLayout>,
+    // Destroying the pool frees the descriptor set, so it must outlive all snapshots. It is never
+    // accessed after creation; the mutex only makes the inner state `Sync`.
+    _pool: Mutex<DescriptorPool>,
+}
+
+struct SlotAllocator {
+    free: Vec<u32>,
+    next: u32,
+    max: u32,
+    allocated: HashSet<u32>,
+}
+
+impl SlotAllocator {
+    fn new(max: u32) -> Self {
+        SlotAllocator {
+            free: Vec::new(),
+            next: 0,
+            max,
+            allocated: HashSet::default(),
+        }
+    }
+
+    fn allocate(&mut self) -> Option<u32> {
+        let slot = self.free.pop().or_else(|| {
+            (self.next < self.max).then(|| {
+                self.next += 1;
+                self.next - 1
+            })
+        })?;
+        self.allocated.insert(slot);
+
+        Some(slot)
+    }
+
+    // Marks a slot as no longer in use, without making it available for allocation yet.
+    // Returns whether the slot was in use.
+    fn release(&mut self, slot: u32) -> bool {
+        self.allocated.remove(&slot)
+    }
+
+    // Makes a released slot available for allocation again.
+    fn free(&mut self, slot: u32) {
+        debug_assert!(!self.allocated.contains(&slot));
+        self.free.push(slot);
+    }
+
+    #[cfg(test)]
+    fn is_allocated(&self, slot: u32) -> bool {
+        self.allocated.contains(&slot)
+    }
+}
+
+/// Error that can happen when creating a `BindlessDescriptorSet`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindlessDescriptorSetCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// Creating the descriptor set layout failed.
+    DescriptorSetLayoutCreationError(DescriptorSetLayoutCreationError),
+
+    /// Allocating the descriptor set failed.
+    DescriptorPoolAllocError(DescriptorPoolAllocError),
+}
+
+impl Error for BindlessDescriptorSetCreationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            Self::DescriptorSetLayoutCreationError(err) => Some(err),
+            Self::DescriptorPoolAllocError(err) => Some(err),
+        }
+    }
+}
+
+impl Display for BindlessDescriptorSetCreationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::DescriptorSetLayoutCreationError(_) => {
+                write!(f, "creating the descriptor set layout failed")
+            }
+            Self::DescriptorPoolAllocError(_) => {
+                write!(f, "allocating the descriptor set failed")
+            }
+        }
+    }
+}
+
+impl From<OomError> for BindlessDescriptorSetCreationError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<DescriptorSetLayoutCreationError> for BindlessDescriptorSetCreationError {
+    fn from(err: DescriptorSetLayoutCreationError) -> Self {
+        Self::DescriptorSetLayoutCreationError(err)
+    }
+}
+
+impl From<DescriptorPoolAllocError> for BindlessDescriptorSetCreationError {
+    fn from(err: DescriptorPoolAllocError) -> Self {
+        Self::DescriptorPoolAllocError(err)
+    }
+}
+
+/// Error that can happen when adding a resource to a `BindlessDescriptorSet`.
+#[derive(Clone, Copy, Debug)]
+pub enum BindlessDescriptorSetError {
+    /// The resource can't be written to the descriptor set.
+    DescriptorSetUpdateError(DescriptorSetUpdateError),
+
+    /// All slots of the binding are in use, or are waiting for snapshots to be dropped.
+    SlotsExhausted { binding_num: u32 },
+}
+
+impl Error for BindlessDescriptorSetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DescriptorSetUpdateError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for BindlessDescriptorSetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::DescriptorSetUpdateError(_) => {
+                write!(f, "an error occurred while updating the descriptor set")
+            }
+            Self::SlotsExhausted { binding_num } => write!(
+                f,
+                "all slots of binding {} are in use or waiting for snapshots to be dropped",
+                binding_num,
+            ),
+        }
+    }
+}
+
+impl From<DescriptorSetUpdateError> for BindlessDescriptorSetError {
+    fn from(err: DescriptorSetUpdateError) -> Self {
+        Self::DescriptorSetUpdateError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BindlessDescriptorSet, BindlessDescriptorSetCreateInfo, SlotAllocator};
+    use crate::sampler::{Sampler, SamplerCreateInfo};
+
+    #[test]
+    fn slot_allocator() {
+        let mut slots = SlotAllocator::new(2);
+        assert_eq!(slots.allocate(), Some(0));
+        assert_eq!(slots.allocate(), Some(1));
+        assert_eq!(slots.allocate(), None);
+
+        assert!(slots.release(0));
+        assert!(!slots.is_allocated(0));
+        assert!(!slots.release(0));
+        assert_eq!(slots.allocate(), None);
+
+        slots.free(0);
+        assert_eq!(slots.allocate(), Some(0));
+        assert!(slots.is_allocated(0));
+    }
+
+    #[test]
+    fn slots_reused_after_snapshot_drop() {
+        let (device, _) = gfx_dev_and_queue!(
+            descriptor_binding_partially_bound,
+            descriptor_binding_update_unused_while_pending,
+            descriptor_binding_sampled_image_update_after_bind
+        );
+
+        let mut set = BindlessDescriptorSet::new(
+            device.clone(),
+            BindlessDescriptorSetCreateInfo {
+                max_samplers: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+
+        assert_eq!(set.add_sampler(sampler.clone()).unwrap(), 0);
+        let snapshot = set.snapshot();
+
+        // The slot is still referenced by the snapshot.
+        set.remove_sampler(0);
+        assert!(set.add_sampler(sampler.clone()).is_err());
+
+        drop(snapshot);
+        assert_eq!(set.add_sampler(sampler).unwrap(), 0);
+    }
+
+    #[test]
+    fn remove_twice() {
+        let (device, _) = gfx_dev_and_queue!(
+            descriptor_binding_partially_bound,
+            descriptor_binding_update_unused_while_pending,
+            descriptor_binding_sampled_image_update_after_bind
+        );
+
+        let mut set = BindlessDescriptorSet::new(
+            device.clone(),
+            BindlessDescriptorSetCreateInfo {
+                max_samplers: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+
+        assert_eq!(set.add_sampler(sampler).unwrap(), 0);
+        let _snapshot = set.snapshot();
+        set.remove_sampler(0);
+
+        assert_should_panic!("is not in use", {
+            set.remove_sampler(0);
+        });
+    }
+}
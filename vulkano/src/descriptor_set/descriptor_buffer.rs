@@ -0,0 +1,656 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Descriptor sets that live directly in buffer memory.
+//!
+//! With the [`ext_descriptor_buffer`] extension, descriptors are no longer allocated from a
+//! descriptor pool. Instead, the implementation tells how many bytes a set with a given layout
+//! occupies and where each binding starts, and the descriptors are written as opaque blobs of
+//! bytes into a host-visible buffer. The buffer is then bound to the command buffer with
+//! [`AutoCommandBufferBuilder::bind_descriptor_buffer_sets`].
+//!
+//! To use descriptor buffers:
+//!
+//! - Enable the [`descriptor_buffer`] feature on the device.
+//! - Create descriptor set layouts with [`DescriptorSetLayoutCreateInfo::descriptor_buffer`]
+//!   enabled. All set layouts in a pipeline layout must have it enabled.
+//! - Create a buffer with the [`SHADER_DEVICE_ADDRESS`] usage, and with
+//!   [`RESOURCE_DESCRIPTOR_BUFFER`] and/or [`SAMPLER_DESCRIPTOR_BUFFER`] depending on what kind of
+//!   descriptors the layouts contain, in host-visible memory.
+//! - Create a [`DescriptorBufferSet`] for each set from a subbuffer that is at least
+//!   [`DescriptorSetLayout::descriptor_buffer_size`] bytes large and aligned to the
+//!   [`descriptor_buffer_offset_alignment`] property. Many sets can share the same buffer.
+//!
+//! The device reads descriptors from the buffer when it executes commands, so a set must not be
+//! written while a command buffer that uses it is executing. Command buffers hold the sets that
+//! are bound to them through an `Arc`, and [`DescriptorBufferSet::write`] needs exclusive access,
+//! so a set can only be written once no command buffer holds it anymore. To change the
+//! descriptors for later commands while earlier ones may still be executing, create a new set in
+//! another part of the buffer instead.
+//!
+//! Draw and dispatch commands also record a read of the buffer of every descriptor buffer set
+//! that the pipeline uses, the same way as for the resources in the sets. While such a command
+//! buffer is executing, writing to the same memory through any other subbuffer fails with
+//! [`BufferError::InUseByDevice`].
+//!
+//! [`ext_descriptor_buffer`]: crate::device::DeviceExtensions::ext_descriptor_buffer
+//! [`AutoCommandBufferBuilder::bind_descriptor_buffer_sets`]: crate::command_buffer::AutoCommandBufferBuilder::bind_descriptor_buffer_sets
+//! [`descriptor_buffer`]: crate::device::Features::descriptor_buffer
+//! [`DescriptorSetLayoutCreateInfo::descriptor_buffer`]: super::layout::DescriptorSetLayoutCreateInfo::descriptor_buffer
+//! [`SHADER_DEVICE_ADDRESS`]: crate::buffer::BufferUsage::SHADER_DEVICE_ADDRESS
+//! [`RESOURCE_DESCRIPTOR_BUFFER`]: crate::buffer::BufferUsage::RESOURCE_DESCRIPTOR_BUFFER
+//! [`SAMPLER_DESCRIPTOR_BUFFER`]: crate::buffer::BufferUsage::SAMPLER_DESCRIPTOR_BUFFER
+//! [`descriptor_buffer_offset_alignment`]: crate::device::Properties::descriptor_buffer_offset_alignment
+//! [`BufferError::InUseByDevice`]: crate::buffer::BufferError::InUseByDevice
+
+use super::{
+    check_descriptor_write,
+    layout::{DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorType},
+    DescriptorSetResources, DescriptorSetUpdateError, DescriptorWriteInfo, WriteDescriptorSet,
+    WriteDescriptorSetElements,
+};
+use crate::{
+    buffer::{BufferError, BufferUsage, Subbuffer},
+    device::{Device, DeviceOwned},
+    memory::{is_aligned, DeviceAlignment},
+    DeviceSize, NonZeroDeviceSize, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// A descriptor set whose descriptors are stored in a buffer.
+///
+/// See the [module-level documentation](self) for more.
+pub struct DescriptorBufferSet {
+    buffer: Subbuffer<[u8]>,
+    device_address: NonZeroDeviceSize,
+    layout: Arc<DescriptorSetLayout>,
+    variable_descriptor_count: u32,
+    resources: DescriptorSetResources,
+}
+
+impl DescriptorBufferSet {
+    /// Creates a new descriptor set in `buffer` with a variable descriptor count of 0, and
+    /// writes `descriptor_writes` into it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `layout` was not created for descriptor buffers, or was created for push
+    ///   descriptors.
+    /// - Panics if `buffer` and `layout` do not belong to the same device.
+    #[inline]
+    pub fn new(
+        buffer: Subbuffer<[u8]>,
+        layout: Arc<DescriptorSetLayout>,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<DescriptorBufferSet, DescriptorBufferError> {
+        Self::new_variable(buffer, layout, 0, descriptor_writes)
+    }
+
+    /// Creates a new descriptor set in `buffer` with the requested variable descriptor count,
+    /// and writes `descriptor_writes` into it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `layout` was not created for descriptor buffers, or was created for push
+    ///   descriptors.
+    /// - Panics if `buffer` and `layout` do not belong to the same device.
+    /// - Panics if `variable_descriptor_count` is too large for the given `layout`.
+    pub fn new_variable(
+        buffer: Subbuffer<[u8]>,
+        layout: Arc<DescriptorSetLayout>,
+        variable_descriptor_count: u32,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<DescriptorBufferSet, DescriptorBufferError> {
+        let required_size = layout.descriptor_buffer_size().expect(
+            "the provided descriptor set layout is not for descriptor buffers, or is for push \
+            descriptors",
+        );
+        assert_eq!(buffer.device(), layout.device());
+
+        let max_count = layout.variable_descriptor_count();
+
+        assert!(
+            variable_descriptor_count <= max_count,
+            "the provided variable_descriptor_count ({}) is greater than the maximum number of \
+            variable count descriptors in the set ({})",
+            variable_descriptor_count,
+            max_count,
+        );
+
+        let device = layout.device();
+        let properties = device.physical_device().properties();
+
+        let (needs_sampler_usage, needs_resource_usage) = layout
+            .bindings()
+            .values()
+            .filter(|binding| binding.descriptor_count != 0)
+            .fold(
+                (false, false),
+                |(sampler, resource), binding| match binding.descriptor_type {
+                    DescriptorType::Sampler => (true, resource),
+                    DescriptorType::CombinedImageSampler => (true, true),
+                    _ => (sampler, true),
+                },
+            );
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08126
+        if needs_sampler_usage
+            && !buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::SAMPLER_DESCRIPTOR_BUFFER)
+        {
+            return Err(DescriptorBufferError::BufferMissingUsage {
+                usage: "sampler_descriptor_buffer",
+            });
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08127
+        if needs_resource_usage
+            && !buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::RESOURCE_DESCRIPTOR_BUFFER)
+        {
+            return Err(DescriptorBufferError::BufferMissingUsage {
+                usage: "resource_descriptor_buffer",
+            });
+        }
+
+        let required_alignment = properties.descriptor_buffer_offset_alignment.ok_or(
+            DescriptorBufferError::RequirementNotMet {
+                required_for: "`DescriptorBufferSet::new`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_descriptor_buffer"],
+                    ..Default::default()
+                },
+            },
+        )?;
+
+        for (&binding_num, binding) in layout.bindings() {
+            descriptor_size(device, binding_num, binding.descriptor_type)?;
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08061
+        if !is_aligned(buffer.offset(), required_alignment) {
+            return Err(DescriptorBufferError::BufferNotAligned {
+                offset: buffer.offset(),
+                required_alignment,
+            });
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08063
+        if buffer.size() < required_size {
+            return Err(DescriptorBufferError::BufferTooSmall {
+                required_size,
+                provided_size: buffer.size(),
+            });
+        }
+
+        let device_address = buffer.device_address()?;
+        let resources = DescriptorSetResources::new(&layout, variable_descriptor_count);
+
+        let mut set = DescriptorBufferSet {
+            buffer,
+            device_address,
+            layout,
+            variable_descriptor_count,
+            resources,
+        };
+        set.write(descriptor_writes)?;
+
+        Ok(set)
+    }
+
+    /// Returns the subbuffer that holds the descriptors of the set.
+    #[inline]
+    pub fn buffer(&self) -> &Subbuffer<[u8]> {
+        &self.buffer
+    }
+
+    /// Returns the device address of the start of the set in the buffer.
+    #[inline]
+    pub fn device_address(&self) -> NonZeroDeviceSize {
+        self.device_address
+    }
+
+    /// Returns the layout of the descriptor set.
+    #[inline]
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// Returns the variable descriptor count that the descriptor set was created with.
+    #[inline]
+    pub fn variable_descriptor_count(&self) -> u32 {
+        self.variable_descriptor_count
+    }
+
+    /// Returns the resources that are currently in the descriptor set.
+    #[inline]
+    pub fn resources(&self) -> &DescriptorSetResources {
+        &self.resources
+    }
+
+    /// Writes descriptors into the buffer of the descriptor set.
+    ///
+    /// All writes are validated before any of them is performed, so if an error is returned,
+    /// the descriptor set is left unchanged. If the memory of the set is currently accessed
+    /// through another subbuffer, [`DescriptorBufferError::BufferError`] is returned.
+    pub fn write(
+        &mut self,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<(), DescriptorBufferError> {
+        let descriptor_writes: SmallVec<[_; 8]> = descriptor_writes.into_iter().collect();
+
+        if descriptor_writes.is_empty() {
+            return Ok(());
+        }
+
+        for write in &descriptor_writes {
+            check_descriptor_write(write, &self.layout, self.variable_descriptor_count)?;
+
+            for_each_buffer_element(write, |buffer| {
+                buffer.device_address()?;
+                Ok(())
+            })?;
+        }
+
+        let device = self.layout.device().clone();
+        let mut mapping = self.buffer.write()?;
+
+        for write in &descriptor_writes {
+            let layout_binding = &self.layout.bindings()[&write.binding()];
+            let binding_offset = self
+                .layout
+                .descriptor_buffer_binding_offset(write.binding())
+                .unwrap() as usize;
+
+            // The data of inline uniform blocks is stored directly in the buffer, and the array
+            // elements are its bytes.
+            if let WriteDescriptorSetElements::InlineUniformBlock(data) = write.elements() {
+                let offset = binding_offset + write.first_array_element() as usize;
+                mapping[offset..offset + data.len()].copy_from_slice(data);
+                continue;
+            }
+
+            let descriptor_type = layout_binding.descriptor_type;
+            let descriptor_size = descriptor_size(&device, write.binding(), descriptor_type)?;
+
+            // Arrays of combined image samplers may need to be stored as an array of image
+            // descriptors followed by an array of sampler descriptors.
+            let split_combined_image_sampler = descriptor_type
+                == DescriptorType::CombinedImageSampler
+                && layout_binding.descriptor_count > 1
+                && !device
+                    .physical_device()
+                    .properties()
+                    .combined_image_sampler_descriptor_single_array
+                    .unwrap_or(true);
+
+            for (i, data) in
+                unsafe { get_descriptors(&device, write, layout_binding, descriptor_size) }
+                    .into_iter()
+                    .enumerate()
+            {
+                let data = match data {
+                    Some(data) => data,
+                    // Samplers that are immutable in the layout are not stored in the buffer.
+                    None => continue,
+                };
+                let index = (write.first_array_element() as usize) + i;
+
+                if split_combined_image_sampler {
+                    let image_size = self::descriptor_size(
+                        &device,
+                        write.binding(),
+                        DescriptorType::SampledImage,
+                    )?;
+                    let sampler_size =
+                        self::descriptor_size(&device, write.binding(), DescriptorType::Sampler)?;
+                    let count = layout_binding.descriptor_count as usize;
+
+                    let image_offset = binding_offset + index * image_size;
+                    mapping[image_offset..image_offset + image_size]
+                        .copy_from_slice(&data[..image_size]);
+
+                    let sampler_offset = binding_offset + count * image_size + index * sampler_size;
+                    mapping[sampler_offset..sampler_offset + sampler_size]
+                        .copy_from_slice(&data[image_size..image_size + sampler_size]);
+                } else {
+                    let offset = binding_offset + index * descriptor_size;
+                    mapping[offset..offset + descriptor_size].copy_from_slice(&data);
+                }
+            }
+        }
+
+        drop(mapping);
+
+        for write in &descriptor_writes {
+            self.resources.update(write);
+        }
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for DescriptorBufferSet {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.layout.device()
+    }
+}
+
+/// Returns the size in bytes of a descriptor of the given type in a descriptor buffer.
+///
+/// The array elements of an inline uniform block are single bytes. Dynamic buffers can't be
+/// stored in a descriptor buffer, so an error is returned for them.
+fn descriptor_size(
+    device: &Device,
+    binding_num: u32,
+    descriptor_type: DescriptorType,
+) -> Result<usize, DescriptorBufferError> {
+    let properties = device.physical_device().properties();
+    let robust = device.enabled_features().robust_buffer_access;
+
+    let size = match descriptor_type {
+        DescriptorType::Sampler => properties.sampler_descriptor_size,
+        DescriptorType::CombinedImageSampler => properties.combined_image_sampler_descriptor_size,
+        DescriptorType::SampledImage => properties.sampled_image_descriptor_size,
+        DescriptorType::StorageImage => properties.storage_image_descriptor_size,
+        DescriptorType::UniformTexelBuffer if robust => {
+            properties.robust_uniform_texel_buffer_descriptor_size
+        }
+        DescriptorType::UniformTexelBuffer => properties.uniform_texel_buffer_descriptor_size,
+        DescriptorType::StorageTexelBuffer if robust => {
+            properties.robust_storage_texel_buffer_descriptor_size
+        }
+        DescriptorType::StorageTexelBuffer => properties.storage_texel_buffer_descriptor_size,
+        DescriptorType::UniformBuffer if robust => properties.robust_uniform_buffer_descriptor_size,
+        DescriptorType::UniformBuffer => properties.uniform_buffer_descriptor_size,
+        DescriptorType::StorageBuffer if robust => properties.robust_storage_buffer_descriptor_size,
+        DescriptorType::StorageBuffer => properties.storage_buffer_descriptor_size,
+        DescriptorType::InputAttachment => properties.input_attachment_descriptor_size,
+        DescriptorType::InlineUniformBlock => Some(1),
+        DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic => {
+            return Err(DescriptorBufferError::DescriptorTypeIncompatible { binding_num });
+        }
+    };
+
+    size.ok_or(DescriptorBufferError::RequirementNotMet {
+        required_for: "`DescriptorBufferSet`",
+        requires_one_of: RequiresOneOf {
+            device_extensions: &["ext_descriptor_buffer"],
+            ..Default::default()
+        },
+    })
+}
+
+fn for_each_buffer_element(
+    write: &WriteDescriptorSet,
+    mut f: impl FnMut(&Subbuffer<[u8]>) -> Result<(), DescriptorBufferError>,
+) -> Result<(), DescriptorBufferError> {
+    match write.elements() {
+        WriteDescriptorSetElements::Buffer(elements) => {
+            for (buffer, _) in elements {
+                f(buffer)?;
+            }
+        }
+        WriteDescriptorSetElements::BufferView(elements) => {
+            for buffer_view in elements {
+                f(buffer_view.buffer())?;
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Retrieves the descriptor data for every element of `write`. Elements that don't need to be
+/// stored in the buffer are returned as `None`.
+///
+/// The buffers in `write` must have a device address, and `data_size` must be the descriptor
+/// size of the binding's descriptor type.
+unsafe fn get_descriptors(
+    device: &Device,
+    write: &WriteDescriptorSet,
+    layout_binding: &DescriptorSetLayoutBinding,
+    data_size: usize,
+) -> SmallVec<[Option<SmallVec<[u8; 64]>>; 1]> {
+    let fns = device.fns();
+    let descriptor_type = layout_binding.descriptor_type;
+
+    let get = |data: ash::vk::DescriptorDataEXT| {
+        let info = ash::vk::DescriptorGetInfoEXT {
+            ty: descriptor_type.into(),
+            data,
+            ..Default::default()
+        };
+        let mut output: SmallVec<[u8; 64]> = SmallVec::from_elem(0, data_size);
+        (fns.ext_descriptor_buffer.get_descriptor_ext)(
+            device.handle(),
+            &info,
+            data_size,
+            output.as_mut_ptr() as *mut _,
+        );
+        Some(output)
+    };
+
+    let image_data = |info: &ash::vk::DescriptorImageInfo| match descriptor_type {
+        DescriptorType::CombinedImageSampler => ash::vk::DescriptorDataEXT {
+            p_combined_image_sampler: info,
+        },
+        DescriptorType::SampledImage => ash::vk::DescriptorDataEXT {
+            p_sampled_image: info,
+        },
+        DescriptorType::StorageImage => ash::vk::DescriptorDataEXT {
+            p_storage_image: info,
+        },
+        DescriptorType::InputAttachment => ash::vk::DescriptorDataEXT {
+            p_input_attachment_image: info,
+        },
+        _ => unreachable!(),
+    };
+
+    let address_data = |info: &ash::vk::DescriptorAddressInfoEXT| match descriptor_type {
+        DescriptorType::UniformBuffer => ash::vk::DescriptorDataEXT {
+            p_uniform_buffer: info,
+        },
+        DescriptorType::StorageBuffer => ash::vk::DescriptorDataEXT {
+            p_storage_buffer: info,
+        },
+        DescriptorType::UniformTexelBuffer => ash::vk::DescriptorDataEXT {
+            p_uniform_texel_buffer: info,
+        },
+        DescriptorType::StorageTexelBuffer => ash::vk::DescriptorDataEXT {
+            p_storage_texel_buffer: info,
+        },
+        _ => unreachable!(),
+    };
+
+    match write.elements() {
+        WriteDescriptorSetElements::None(num_elements) => std::iter::repeat(None)
+            .take(*num_elements as usize)
+            .collect(),
+        WriteDescriptorSetElements::Buffer(elements) => elements
+            .iter()
+            .map(|(buffer, range)| {
+                let info = ash::vk::DescriptorAddressInfoEXT {
+                    address: buffer.device_address().unwrap().get() + range.start,
+                    range: range.end - range.start,
+                    format: ash::vk::Format::UNDEFINED,
+                    ..Default::default()
+                };
+                get(address_data(&info))
+            })
+            .collect(),
+        WriteDescriptorSetElements::BufferView(elements) => elements
+            .iter()
+            .map(|buffer_view| {
+                let range = buffer_view.range();
+                let info = ash::vk::DescriptorAddressInfoEXT {
+                    address: buffer_view.buffer().device_address().unwrap().get() + range.start,
+                    range: range.end - range.start,
+                    format: buffer_view
+                        .format()
+                        .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                    ..Default::default()
+                };
+                get(address_data(&info))
+            })
+            .collect(),
+        WriteDescriptorSetElements::ImageView(_)
+        | WriteDescriptorSetElements::ImageViewSampler(_) => {
+            let infos = match write.to_vulkan_info(descriptor_type) {
+                DescriptorWriteInfo::Image(infos) => infos,
+                _ => unreachable!(),
+            };
+            let immutable_samplers = &layout_binding.immutable_samplers;
+
+            infos
+                .iter()
+                .enumerate()
+                .map(|(i, info)| {
+                    let mut info = *info;
+
+                    // Immutable samplers must still be provided when getting a combined image
+                    // sampler descriptor.
+                    if let Some(sampler) =
+                        immutable_samplers.get(write.first_array_element() as usize + i)
+                    {
+                        info.sampler = sampler.handle();
+                    }
+
+                    get(image_data(&info))
+                })
+                .collect()
+        }
+        WriteDescriptorSetElements::Sampler(elements) => elements
+            .iter()
+            .map(|sampler| {
+                let handle = sampler.handle();
+                get(ash::vk::DescriptorDataEXT { p_sampler: &handle })
+            })
+            .collect(),
+        WriteDescriptorSetElements::InlineUniformBlock(_) => unreachable!(),
+    }
+}
+
+/// Error that can happen when creating or writing a `DescriptorBufferSet`.
+#[derive(Clone, Debug)]
+pub enum DescriptorBufferError {
+    /// An error occurred while accessing the buffer.
+    BufferError(BufferError),
+
+    /// A descriptor write was not valid for the layout.
+    DescriptorSetUpdateError(DescriptorSetUpdateError),
+
+    /// The buffer is missing a usage that is required for the descriptors in the layout.
+    BufferMissingUsage { usage: &'static str },
+
+    /// The offset of the subbuffer is not a multiple of the
+    /// [`descriptor_buffer_offset_alignment`](crate::device::Properties::descriptor_buffer_offset_alignment)
+    /// property.
+    BufferNotAligned {
+        offset: DeviceSize,
+        required_alignment: DeviceAlignment,
+    },
+
+    /// The subbuffer is smaller than the size of a set with the layout.
+    BufferTooSmall {
+        required_size: DeviceSize,
+        provided_size: DeviceSize,
+    },
+
+    /// A binding of the layout has a descriptor type that can't be stored in a descriptor
+    /// buffer.
+    DescriptorTypeIncompatible { binding_num: u32 },
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for DescriptorBufferError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::BufferError(err) => Some(err),
+            Self::DescriptorSetUpdateError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DescriptorBufferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::BufferError(_) => write!(f, "an error occurred while accessing the buffer"),
+            Self::DescriptorSetUpdateError(_) => {
+                write!(f, "a descriptor write was not valid for the layout")
+            }
+            Self::BufferMissingUsage { usage } => write!(
+                f,
+                "the buffer is missing the `{}` usage, which is required for the descriptors in \
+                the layout",
+                usage,
+            ),
+            Self::BufferNotAligned {
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset of the subbuffer ({}) is not a multiple of the \
+                `descriptor_buffer_offset_alignment` property ({:?})",
+                offset, required_alignment,
+            ),
+            Self::BufferTooSmall {
+                required_size,
+                provided_size,
+            } => write!(
+                f,
+                "the subbuffer ({} bytes) is smaller than the size of a set with the layout ({} \
+                bytes)",
+                provided_size, required_size,
+            ),
+            Self::DescriptorTypeIncompatible { binding_num } => write!(
+                f,
+                "binding {} of the layout has a descriptor type that can't be stored in a \
+                descriptor buffer",
+                binding_num,
+            ),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<BufferError> for DescriptorBufferError {
+    fn from(err: BufferError) -> Self {
+        Self::BufferError(err)
+    }
+}
+
+impl From<DescriptorSetUpdateError> for DescriptorBufferError {
+    fn from(err: DescriptorSetUpdateError) -> Self {
+        Self::DescriptorSetUpdateError(err)
+    }
+}
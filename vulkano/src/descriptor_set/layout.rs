@@ -16,7 +16,7 @@ use crate::{
     macros::{impl_id_counter, vulkan_enum},
     sampler::Sampler,
     shader::{DescriptorBindingRequirements, ShaderStages},
-    OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use std::{
@@ -38,8 +38,12 @@ pub struct DescriptorSetLayout {
 
     bindings: BTreeMap<u32, DescriptorSetLayoutBinding>,
     push_descriptor: bool,
+    update_after_bind_pool: bool,
+    descriptor_buffer: bool,
 
     descriptor_counts: HashMap<DescriptorType, u32>,
+    descriptor_buffer_size: DeviceSize,
+    descriptor_buffer_binding_offsets: HashMap<u32, DeviceSize>,
 }
 
 impl DescriptorSetLayout {
@@ -52,20 +56,14 @@ impl DescriptorSetLayout {
         let descriptor_counts = Self::validate(&device, &mut create_info)?;
         let handle = unsafe { Self::create(&device, &create_info)? };
 
-        let DescriptorSetLayoutCreateInfo {
-            bindings,
-            push_descriptor,
-            _ne: _,
-        } = create_info;
-
-        Ok(Arc::new(DescriptorSetLayout {
-            handle,
-            device,
-            id: Self::next_id(),
-            bindings,
-            push_descriptor,
-            descriptor_counts,
-        }))
+        unsafe {
+            Ok(Self::from_handle_with_counts(
+                device,
+                handle,
+                create_info,
+                descriptor_counts,
+            ))
+        }
     }
 
     /// Creates a new `DescriptorSetLayout` from a raw object handle.
@@ -79,19 +77,56 @@ impl DescriptorSetLayout {
         device: Arc<Device>,
         handle: ash::vk::DescriptorSetLayout,
         create_info: DescriptorSetLayoutCreateInfo,
+    ) -> Arc<DescriptorSetLayout> {
+        let mut descriptor_counts = HashMap::default();
+        for binding in create_info.bindings.values() {
+            if binding.descriptor_count != 0 {
+                *descriptor_counts
+                    .entry(binding.descriptor_type)
+                    .or_default() += binding.descriptor_count;
+            }
+        }
+
+        Self::from_handle_with_counts(device, handle, create_info, descriptor_counts)
+    }
+
+    unsafe fn from_handle_with_counts(
+        device: Arc<Device>,
+        handle: ash::vk::DescriptorSetLayout,
+        create_info: DescriptorSetLayoutCreateInfo,
+        descriptor_counts: HashMap<DescriptorType, u32>,
     ) -> Arc<DescriptorSetLayout> {
         let DescriptorSetLayoutCreateInfo {
             bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
-        let mut descriptor_counts = HashMap::default();
-        for binding in bindings.values() {
-            if binding.descriptor_count != 0 {
-                *descriptor_counts
-                    .entry(binding.descriptor_type)
-                    .or_default() += binding.descriptor_count;
+        let mut descriptor_buffer_size = 0;
+        let mut descriptor_buffer_binding_offsets = HashMap::default();
+
+        // Push descriptors are not stored in a descriptor buffer, so there is nothing to query.
+        if descriptor_buffer && !push_descriptor {
+            let fns = device.fns();
+
+            (fns.ext_descriptor_buffer.get_descriptor_set_layout_size_ext)(
+                device.handle(),
+                handle,
+                &mut descriptor_buffer_size,
+            );
+
+            for &binding_num in bindings.keys() {
+                let mut offset = 0;
+                (fns.ext_descriptor_buffer
+                    .get_descriptor_set_layout_binding_offset_ext)(
+                    device.handle(),
+                    handle,
+                    binding_num,
+                    &mut offset,
+                );
+                descriptor_buffer_binding_offsets.insert(binding_num, offset);
             }
         }
 
@@ -101,7 +136,11 @@ impl DescriptorSetLayout {
             id: Self::next_id(),
             bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             descriptor_counts,
+            descriptor_buffer_size,
+            descriptor_buffer_binding_offsets,
         })
     }
 
@@ -112,11 +151,41 @@ impl DescriptorSetLayout {
         let &mut DescriptorSetLayoutCreateInfo {
             ref bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
         let mut descriptor_counts = HashMap::default();
 
+        if descriptor_buffer {
+            if !device.enabled_features().descriptor_buffer {
+                return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                    required_for: "`create_info.descriptor_buffer` is set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["descriptor_buffer"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkDescriptorSetLayoutCreateInfo-flags-08001
+            if update_after_bind_pool {
+                return Err(DescriptorSetLayoutCreationError::DescriptorBufferUpdateAfterBindPool);
+            }
+
+            if push_descriptor && !device.enabled_features().descriptor_buffer_push_descriptors {
+                return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                    required_for: "`create_info.descriptor_buffer` and \
+                        `create_info.push_descriptor` are both set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["descriptor_buffer_push_descriptors"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
         if push_descriptor {
             if !device.enabled_extensions().khr_push_descriptor {
                 return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
@@ -127,6 +196,11 @@ impl DescriptorSetLayout {
                     },
                 });
             }
+
+            // VUID-VkDescriptorSetLayoutCreateInfo-flags-00281
+            if update_after_bind_pool {
+                return Err(DescriptorSetLayoutCreationError::PushDescriptorUpdateAfterBindPool);
+            }
         }
 
         let highest_binding_num = bindings.keys().copied().next_back();
@@ -136,6 +210,9 @@ impl DescriptorSetLayout {
                 descriptor_type,
                 descriptor_count,
                 variable_descriptor_count,
+                update_after_bind,
+                update_unused_while_pending,
+                partially_bound,
                 stages,
                 ref immutable_samplers,
                 _ne: _,
@@ -151,6 +228,60 @@ impl DescriptorSetLayout {
                 *descriptor_counts.entry(descriptor_type).or_default() += descriptor_count;
             }
 
+            if descriptor_type == DescriptorType::InlineUniformBlock {
+                // VUID-VkDescriptorSetLayoutBinding-descriptorType-04604
+                if !device.enabled_features().inline_uniform_block {
+                    return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                        required_for: "`create_info.bindings` has an element where \
+                            `descriptor_type` is `DescriptorType::InlineUniformBlock`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["inline_uniform_block"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkDescriptorSetLayoutBinding-descriptorType-02209
+                if descriptor_count % 4 != 0 {
+                    return Err(
+                        DescriptorSetLayoutCreationError::InlineUniformBlockSizeNotAligned {
+                            binding_num,
+                        },
+                    );
+                }
+
+                let max_inline_uniform_block_size = device
+                    .physical_device()
+                    .properties()
+                    .max_inline_uniform_block_size
+                    .unwrap_or(0);
+
+                // VUID-VkDescriptorSetLayoutBinding-descriptorType-08004
+                if descriptor_count > max_inline_uniform_block_size {
+                    return Err(
+                        DescriptorSetLayoutCreationError::MaxInlineUniformBlockSizeExceeded {
+                            binding_num,
+                            provided: descriptor_count,
+                            max_supported: max_inline_uniform_block_size,
+                        },
+                    );
+                }
+            }
+
+            // VUID-VkDescriptorSetLayoutCreateInfo-flags-08000
+            if descriptor_buffer
+                && matches!(
+                    descriptor_type,
+                    DescriptorType::StorageBufferDynamic | DescriptorType::UniformBufferDynamic
+                )
+            {
+                return Err(
+                    DescriptorSetLayoutCreationError::DescriptorBufferDescriptorTypeIncompatible {
+                        binding_num,
+                    },
+                );
+            }
+
             if push_descriptor {
                 // VUID-VkDescriptorSetLayoutCreateInfo-flags-00280
                 if matches!(
@@ -172,6 +303,15 @@ impl DescriptorSetLayout {
                         },
                     );
                 }
+
+                // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-flags-03003
+                if update_after_bind || update_unused_while_pending {
+                    return Err(
+                        DescriptorSetLayoutCreationError::PushDescriptorUpdateAfterBind {
+                            binding_num,
+                        },
+                    );
+                }
             }
 
             if !immutable_samplers.is_empty() {
@@ -252,27 +392,134 @@ impl DescriptorSetLayout {
                     );
                 }
             }
+
+            if update_after_bind {
+                let features = device.enabled_features();
+                let (feature_enabled, required_features): (_, &[_]) = match descriptor_type {
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformBufferUpdateAfterBind-03005
+                    DescriptorType::UniformBuffer => (
+                        features.descriptor_binding_uniform_buffer_update_after_bind,
+                        &["descriptor_binding_uniform_buffer_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingSampledImageUpdateAfterBind-03006
+                    DescriptorType::Sampler
+                    | DescriptorType::CombinedImageSampler
+                    | DescriptorType::SampledImage => (
+                        features.descriptor_binding_sampled_image_update_after_bind,
+                        &["descriptor_binding_sampled_image_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageImageUpdateAfterBind-03007
+                    DescriptorType::StorageImage => (
+                        features.descriptor_binding_storage_image_update_after_bind,
+                        &["descriptor_binding_storage_image_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageBufferUpdateAfterBind-03008
+                    DescriptorType::StorageBuffer => (
+                        features.descriptor_binding_storage_buffer_update_after_bind,
+                        &["descriptor_binding_storage_buffer_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformTexelBufferUpdateAfterBind-03009
+                    DescriptorType::UniformTexelBuffer => (
+                        features.descriptor_binding_uniform_texel_buffer_update_after_bind,
+                        &["descriptor_binding_uniform_texel_buffer_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageTexelBufferUpdateAfterBind-03010
+                    DescriptorType::StorageTexelBuffer => (
+                        features.descriptor_binding_storage_texel_buffer_update_after_bind,
+                        &["descriptor_binding_storage_texel_buffer_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingInlineUniformBlockUpdateAfterBind-02211
+                    DescriptorType::InlineUniformBlock => (
+                        features.descriptor_binding_inline_uniform_block_update_after_bind,
+                        &["descriptor_binding_inline_uniform_block_update_after_bind"],
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-None-03011
+                    DescriptorType::UniformBufferDynamic
+                    | DescriptorType::StorageBufferDynamic
+                    | DescriptorType::InputAttachment => {
+                        return Err(
+                            DescriptorSetLayoutCreationError::UpdateAfterBindDescriptorTypeIncompatible {
+                                binding_num,
+                            },
+                        );
+                    }
+                };
+
+                if !feature_enabled {
+                    return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                        required_for: "`create_info.bindings` has an element where \
+                            `update_after_bind` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: required_features,
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkDescriptorSetLayoutCreateInfo-flags-03000
+                if !update_after_bind_pool {
+                    return Err(
+                        DescriptorSetLayoutCreationError::UpdateAfterBindPoolNotEnabled {
+                            binding_num,
+                        },
+                    );
+                }
+            }
+
+            // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUpdateUnusedWhilePending-03012
+            if update_unused_while_pending
+                && !device
+                    .enabled_features()
+                    .descriptor_binding_update_unused_while_pending
+            {
+                return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                    required_for: "`create_info.bindings` has an element where \
+                        `update_unused_while_pending` is set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["descriptor_binding_update_unused_while_pending"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingPartiallyBound-03013
+            if partially_bound && !device.enabled_features().descriptor_binding_partially_bound {
+                return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                    required_for: "`create_info.bindings` has an element where \
+                        `partially_bound` is set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["descriptor_binding_partially_bound"],
+                        ..Default::default()
+                    },
+                });
+            }
         }
 
         // VUID-VkDescriptorSetLayoutCreateInfo-flags-00281
-        if push_descriptor
-            && descriptor_counts.values().copied().sum::<u32>()
-                > device
-                    .physical_device()
-                    .properties()
-                    .max_push_descriptors
-                    .unwrap_or(0)
-        {
-            return Err(
-                DescriptorSetLayoutCreationError::MaxPushDescriptorsExceeded {
-                    provided: descriptor_counts.values().copied().sum(),
-                    max_supported: device
-                        .physical_device()
-                        .properties()
-                        .max_push_descriptors
-                        .unwrap_or(0),
-                },
-            );
+        if push_descriptor {
+            // The descriptor count of an inline uniform block is its size in bytes, but it counts
+            // as only a single descriptor.
+            let provided = bindings
+                .values()
+                .map(|binding| match binding.descriptor_type {
+                    DescriptorType::InlineUniformBlock => (binding.descriptor_count != 0) as u32,
+                    _ => binding.descriptor_count,
+                })
+                .sum::<u32>();
+            let max_supported = device
+                .physical_device()
+                .properties()
+                .max_push_descriptors
+                .unwrap_or(0);
+
+            if provided > max_supported {
+                return Err(
+                    DescriptorSetLayoutCreationError::MaxPushDescriptorsExceeded {
+                        provided,
+                        max_supported,
+                    },
+                );
+            }
         }
 
         Ok(descriptor_counts)
@@ -285,6 +532,8 @@ impl DescriptorSetLayout {
         let &DescriptorSetLayoutCreateInfo {
             ref bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
@@ -297,6 +546,14 @@ impl DescriptorSetLayout {
             flags |= ash::vk::DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR;
         }
 
+        if update_after_bind_pool {
+            flags |= ash::vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL;
+        }
+
+        if descriptor_buffer {
+            flags |= ash::vk::DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER_EXT;
+        }
+
         for (&binding_num, binding) in bindings.iter() {
             let mut binding_flags = ash::vk::DescriptorBindingFlags::empty();
 
@@ -319,6 +576,18 @@ impl DescriptorSetLayout {
                 binding_flags |= ash::vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
             }
 
+            if binding.update_after_bind {
+                binding_flags |= ash::vk::DescriptorBindingFlags::UPDATE_AFTER_BIND;
+            }
+
+            if binding.update_unused_while_pending {
+                binding_flags |= ash::vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING;
+            }
+
+            if binding.partially_bound {
+                binding_flags |= ash::vk::DescriptorBindingFlags::PARTIALLY_BOUND;
+            }
+
             // VUID-VkDescriptorSetLayoutCreateInfo-binding-00279
             // Guaranteed by BTreeMap
             bindings_vk.push(ash::vk::DescriptorSetLayoutBinding {
@@ -390,6 +659,42 @@ impl DescriptorSetLayout {
         self.push_descriptor
     }
 
+    /// Returns whether descriptor sets with this layout must be allocated from a pool that
+    /// supports updating descriptors after they have been bound.
+    #[inline]
+    pub fn update_after_bind_pool(&self) -> bool {
+        self.update_after_bind_pool
+    }
+
+    /// Returns whether the descriptor set layout is for descriptor buffers rather than
+    /// descriptor sets allocated from a pool.
+    #[inline]
+    pub fn descriptor_buffer(&self) -> bool {
+        self.descriptor_buffer
+    }
+
+    /// If the layout is for descriptor buffers, returns the number of bytes that a set with this
+    /// layout occupies in a descriptor buffer. This assumes the maximum `descriptor_count` for a
+    /// binding with a variable descriptor count.
+    ///
+    /// Returns `None` if the layout is not for descriptor buffers, or is for push descriptors.
+    #[inline]
+    pub fn descriptor_buffer_size(&self) -> Option<DeviceSize> {
+        (self.descriptor_buffer && !self.push_descriptor).then_some(self.descriptor_buffer_size)
+    }
+
+    /// If the layout is for descriptor buffers, returns the offset in bytes of `binding_num`
+    /// from the start of a set with this layout in a descriptor buffer.
+    ///
+    /// Returns `None` if the layout is not for descriptor buffers, is for push descriptors, or
+    /// `binding_num` does not exist in the layout.
+    #[inline]
+    pub fn descriptor_buffer_binding_offset(&self, binding_num: u32) -> Option<DeviceSize> {
+        self.descriptor_buffer_binding_offsets
+            .get(&binding_num)
+            .copied()
+    }
+
     /// Returns the number of descriptors of each type.
     ///
     /// The map is guaranteed to not contain any elements with a count of `0`.
@@ -423,7 +728,10 @@ impl DescriptorSetLayout {
     #[inline]
     pub fn is_compatible_with(&self, other: &DescriptorSetLayout) -> bool {
         self == other
-            || (self.bindings == other.bindings && self.push_descriptor == other.push_descriptor)
+            || (self.bindings == other.bindings
+                && self.push_descriptor == other.push_descriptor
+                && self.update_after_bind_pool == other.update_after_bind_pool
+                && self.descriptor_buffer == other.descriptor_buffer)
     }
 }
 
@@ -470,6 +778,12 @@ pub enum DescriptorSetLayoutCreationError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// `descriptor_buffer` is enabled, but a binding has an incompatible `descriptor_type`.
+    DescriptorBufferDescriptorTypeIncompatible { binding_num: u32 },
+
+    /// `descriptor_buffer` and `update_after_bind_pool` are both enabled.
+    DescriptorBufferUpdateAfterBindPool,
+
     /// A binding includes immutable samplers but their number differs from  `descriptor_count`.
     ImmutableSamplersCountMismatch {
         binding_num: u32,
@@ -480,6 +794,19 @@ pub enum DescriptorSetLayoutCreationError {
     /// A binding includes immutable samplers but it has an incompatible `descriptor_type`.
     ImmutableSamplersDescriptorTypeIncompatible { binding_num: u32 },
 
+    /// A binding has the `InlineUniformBlock` descriptor type, but its `descriptor_count` is not
+    /// a multiple of 4.
+    InlineUniformBlockSizeNotAligned { binding_num: u32 },
+
+    /// A binding has the `InlineUniformBlock` descriptor type, and its `descriptor_count` exceeds
+    /// the [`max_inline_uniform_block_size`](crate::device::Properties::max_inline_uniform_block_size)
+    /// limit.
+    MaxInlineUniformBlockSizeExceeded {
+        binding_num: u32,
+        provided: u32,
+        max_supported: u32,
+    },
+
     /// More descriptors were provided in all bindings than the
     /// [`max_push_descriptors`](crate::device::Properties::max_push_descriptors) limit.
     MaxPushDescriptorsExceeded { provided: u32, max_supported: u32 },
@@ -490,6 +817,19 @@ pub enum DescriptorSetLayoutCreationError {
     /// `push_descriptor` is enabled, but a binding has `variable_descriptor_count` enabled.
     PushDescriptorVariableDescriptorCount { binding_num: u32 },
 
+    /// `push_descriptor` is enabled, but a binding has `update_after_bind` or
+    /// `update_unused_while_pending` enabled.
+    PushDescriptorUpdateAfterBind { binding_num: u32 },
+
+    /// `push_descriptor` and `update_after_bind_pool` are both enabled.
+    PushDescriptorUpdateAfterBindPool,
+
+    /// A binding has `update_after_bind` enabled, but it has an incompatible `descriptor_type`.
+    UpdateAfterBindDescriptorTypeIncompatible { binding_num: u32 },
+
+    /// A binding has `update_after_bind` enabled, but `update_after_bind_pool` is not enabled.
+    UpdateAfterBindPoolNotEnabled { binding_num: u32 },
+
     /// A binding has `variable_descriptor_count` enabled, but it is not the highest-numbered
     /// binding.
     VariableDescriptorCountBindingNotHighest {
@@ -524,6 +864,16 @@ impl Display for DescriptorSetLayoutCreationError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::DescriptorBufferDescriptorTypeIncompatible { binding_num } => write!(
+                f,
+                "`descriptor_buffer` is enabled, but binding {} has an incompatible \
+                `descriptor_type`",
+                binding_num,
+            ),
+            Self::DescriptorBufferUpdateAfterBindPool => write!(
+                f,
+                "`descriptor_buffer` and `update_after_bind_pool` are both enabled",
+            ),
             Self::ImmutableSamplersCountMismatch {
                 binding_num,
                 sampler_count,
@@ -540,6 +890,22 @@ impl Display for DescriptorSetLayoutCreationError {
                 `descriptor_type`",
                 binding_num,
             ),
+            Self::InlineUniformBlockSizeNotAligned { binding_num } => write!(
+                f,
+                "binding {} has the `InlineUniformBlock` descriptor type, but its \
+                `descriptor_count` is not a multiple of 4",
+                binding_num,
+            ),
+            Self::MaxInlineUniformBlockSizeExceeded {
+                binding_num,
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the size of the inline uniform block at binding {} ({}) exceeds the \
+                `max_inline_uniform_block_size` limit ({})",
+                binding_num, provided, max_supported,
+            ),
             Self::MaxPushDescriptorsExceeded {
                 provided,
                 max_supported,
@@ -561,6 +927,28 @@ impl Display for DescriptorSetLayoutCreationError {
                 enabled",
                 binding_num,
             ),
+            Self::PushDescriptorUpdateAfterBind { binding_num } => write!(
+                f,
+                "`push_descriptor` is enabled, but binding {} has `update_after_bind` or \
+                `update_unused_while_pending` enabled",
+                binding_num,
+            ),
+            Self::PushDescriptorUpdateAfterBindPool => write!(
+                f,
+                "`push_descriptor` and `update_after_bind_pool` are both enabled",
+            ),
+            Self::UpdateAfterBindDescriptorTypeIncompatible { binding_num } => write!(
+                f,
+                "binding {} has `update_after_bind` enabled, but it has an incompatible \
+                `descriptor_type`",
+                binding_num,
+            ),
+            Self::UpdateAfterBindPoolNotEnabled { binding_num } => write!(
+                f,
+                "binding {} has `update_after_bind` enabled, but `update_after_bind_pool` is \
+                not enabled",
+                binding_num,
+            ),
             Self::VariableDescriptorCountBindingNotHighest {
                 binding_num,
                 highest_binding_num,
@@ -617,6 +1005,34 @@ pub struct DescriptorSetLayoutCreateInfo {
     /// The default value is `false`.
     pub push_descriptor: bool,
 
+    /// Whether descriptor sets with this layout must be allocated from a pool that supports
+    /// updating descriptors after the set has been bound.
+    ///
+    /// This must be `true` if any binding has `update_after_bind` enabled, and must be `false` if
+    /// `push_descriptor` is enabled.
+    ///
+    /// The default value is `false`.
+    pub update_after_bind_pool: bool,
+
+    /// Whether the descriptor set layout should be created for descriptor buffers.
+    ///
+    /// If `true`, descriptors are written directly into buffer memory with a
+    /// [`DescriptorBufferSet`](crate::descriptor_set::descriptor_buffer::DescriptorBufferSet),
+    /// and the layout can't be used to allocate descriptor sets from a pool. All set layouts of a
+    /// pipeline layout must agree on this setting.
+    ///
+    /// If set to `true`, the [`descriptor_buffer`](crate::device::Features::descriptor_buffer)
+    /// feature must be enabled on the device, and there are several restrictions:
+    /// - There must be no bindings with a type of [`DescriptorType::UniformBufferDynamic`]
+    ///   or [`DescriptorType::StorageBufferDynamic`].
+    /// - `update_after_bind_pool` must be `false`.
+    /// - If `push_descriptor` is also enabled, the
+    ///   [`descriptor_buffer_push_descriptors`](crate::device::Features::descriptor_buffer_push_descriptors)
+    ///   feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub descriptor_buffer: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -626,6 +1042,8 @@ impl Default for DescriptorSetLayoutCreateInfo {
         Self {
             bindings: BTreeMap::new(),
             push_descriptor: false,
+            update_after_bind_pool: false,
+            descriptor_buffer: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -654,6 +1072,40 @@ impl DescriptorSetLayoutCreateInfo {
 
         create_infos
     }
+
+    /// Turns the bindings that `from_requirements` inferred from runtime-sized arrays in a shader
+    /// into bindless bindings that hold up to `max_descriptor_count` descriptors.
+    ///
+    /// The affected bindings are those with a `descriptor_count` of 0. They get
+    /// `partially_bound`, `update_after_bind` and `update_unused_while_pending` enabled, and if
+    /// one of them is the highest-numbered binding in the set, it also gets
+    /// `variable_descriptor_count`. If any binding was changed, `update_after_bind_pool` is
+    /// enabled as well. The device must have the corresponding descriptor indexing features
+    /// enabled.
+    ///
+    /// This is meant to be called from the closure given to
+    /// [`GraphicsPipelineBuilder::with_auto_layout`] or [`ComputePipeline::new`].
+    ///
+    /// [`GraphicsPipelineBuilder::with_auto_layout`]: crate::pipeline::graphics::GraphicsPipelineBuilder::with_auto_layout
+    /// [`ComputePipeline::new`]: crate::pipeline::ComputePipeline::new
+    pub fn enable_bindless(&mut self, max_descriptor_count: u32) -> &mut Self {
+        let highest_binding_num = self.bindings.keys().copied().next_back();
+
+        for (&binding_num, binding) in self
+            .bindings
+            .iter_mut()
+            .filter(|(_, binding)| binding.descriptor_count == 0)
+        {
+            binding.descriptor_count = max_descriptor_count;
+            binding.variable_descriptor_count = Some(binding_num) == highest_binding_num;
+            binding.update_after_bind = true;
+            binding.update_unused_while_pending = true;
+            binding.partially_bound = true;
+            self.update_after_bind_pool = true;
+        }
+
+        self
+    }
 }
 
 /// A binding in a descriptor set layout.
@@ -686,6 +1138,43 @@ pub struct DescriptorSetLayoutBinding {
     /// [`descriptor_binding_variable_descriptor_count`]: crate::device::Features::descriptor_binding_variable_descriptor_count
     pub variable_descriptor_count: bool,
 
+    /// Whether the descriptors in this binding can be updated after a descriptor set using them
+    /// has been bound, up until the command buffer is submitted.
+    ///
+    /// If set to `true`, the `update_after_bind` feature for `descriptor_type`, such as
+    /// [`descriptor_binding_sampled_image_update_after_bind`], must be enabled, and
+    /// `update_after_bind_pool` must be enabled on the layout. The `descriptor_type` must not be
+    /// [`DescriptorType::UniformBufferDynamic`], [`DescriptorType::StorageBufferDynamic`] or
+    /// [`DescriptorType::InputAttachment`].
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`descriptor_binding_sampled_image_update_after_bind`]: crate::device::Features::descriptor_binding_sampled_image_update_after_bind
+    pub update_after_bind: bool,
+
+    /// Whether the descriptors in this binding that are not used by a pending command buffer can
+    /// be updated while the command buffer is executing.
+    ///
+    /// If set to `true`, the [`descriptor_binding_update_unused_while_pending`] feature must be
+    /// enabled.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`descriptor_binding_update_unused_while_pending`]: crate::device::Features::descriptor_binding_update_unused_while_pending
+    pub update_unused_while_pending: bool,
+
+    /// Whether descriptors in this binding that are not dynamically used by a shader may be left
+    /// unwritten.
+    ///
+    /// If set to `true`, the [`descriptor_binding_partially_bound`] feature must be enabled.
+    /// Vulkano can then no longer check that every descriptor the shader accesses has been
+    /// written, so shaders are required to declare the binding as a runtime-sized array.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`descriptor_binding_partially_bound`]: crate::device::Features::descriptor_binding_partially_bound
+    pub partially_bound: bool,
+
     /// Which shader stages are going to access the descriptors in this binding.
     ///
     /// The default value is [`ShaderStages::empty()`], which must be overridden.
@@ -713,6 +1202,9 @@ impl DescriptorSetLayoutBinding {
             descriptor_type,
             descriptor_count: 1,
             variable_descriptor_count: false,
+            update_after_bind: false,
+            update_unused_while_pending: false,
+            partially_bound: false,
             stages: ShaderStages::empty(),
             immutable_samplers: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -733,6 +1225,7 @@ impl DescriptorSetLayoutBinding {
             image_multisampled: _,
             image_scalar_type: _,
             image_view_type: _,
+            uniform_block_size,
             stages,
             descriptors: _,
         } = binding_requirements;
@@ -753,6 +1246,18 @@ impl DescriptorSetLayoutBinding {
             }
         }
 
+        // The descriptor count of an inline uniform block is its size in bytes.
+        if self.descriptor_type == DescriptorType::InlineUniformBlock {
+            if let Some(required) = uniform_block_size {
+                if self.descriptor_count < required {
+                    return Err(DescriptorRequirementsNotMet::InlineUniformBlockSize {
+                        required,
+                        obtained: self.descriptor_count,
+                    });
+                }
+            }
+        }
+
         if !self.stages.contains(stages) {
             return Err(DescriptorRequirementsNotMet::ShaderStages {
                 required: stages,
@@ -771,6 +1276,9 @@ impl From<&DescriptorBindingRequirements> for DescriptorSetLayoutBinding {
             descriptor_type: reqs.descriptor_types[0],
             descriptor_count: reqs.descriptor_count.unwrap_or(0),
             variable_descriptor_count: false,
+            update_after_bind: false,
+            update_unused_while_pending: false,
+            partially_bound: false,
             stages: reqs.stages,
             immutable_samplers: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -790,6 +1298,10 @@ pub enum DescriptorRequirementsNotMet {
     /// The binding's `descriptor_count` is less than what is required.
     DescriptorCount { required: u32, obtained: u32 },
 
+    /// The binding is an inline uniform block that is smaller than the block that the shader
+    /// declares.
+    InlineUniformBlockSize { required: u32, obtained: u32 },
+
     /// The binding's `stages` does not contain the stages that are required.
     ShaderStages {
         required: ShaderStages,
@@ -812,6 +1324,12 @@ impl Display for DescriptorRequirementsNotMet {
                 "the descriptor count ({}) is less than what is required ({})",
                 obtained, required,
             ),
+            Self::InlineUniformBlockSize { required, obtained } => write!(
+                f,
+                "the size of the inline uniform block ({} bytes) is less than the size of the \
+                block in the shader ({} bytes)",
+                obtained, required,
+            ),
             Self::ShaderStages { .. } => write!(
                 f,
                 "the descriptor's shader stages do not contain the stages that are required",
@@ -866,12 +1384,15 @@ vulkan_enum! {
     /// pixel that is currently being processed by the fragment shader.
     InputAttachment = INPUT_ATTACHMENT,
 
-    /* TODO: enable
-    // TODO: document
+    /// Gives read-only access to a small block of data that is stored directly in the descriptor
+    /// set, without a backing buffer. The data is accessed in the shader as a uniform block.
+    ///
+    /// For this descriptor type, `descriptor_count` in the layout and the array indices of
+    /// descriptor writes and copies are measured in bytes, and must be a multiple of 4.
     InlineUniformBlock = INLINE_UNIFORM_BLOCK {
         api_version: V1_3,
         device_extensions: [ext_inline_uniform_block],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -908,10 +1429,11 @@ vulkan_enum! {
 mod tests {
     use crate::{
         descriptor_set::layout::{
-            DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
-            DescriptorType,
+            DescriptorRequirementsNotMet, DescriptorSetLayout, DescriptorSetLayoutBinding,
+            DescriptorSetLayoutCreateInfo, DescriptorSetLayoutCreationError, DescriptorType,
         },
-        shader::ShaderStages,
+        descriptor_set::{check_descriptor_write, DescriptorSetUpdateError, WriteDescriptorSet},
+        shader::{DescriptorBindingRequirements, ShaderStages},
     };
     use ahash::HashMap;
 
@@ -948,4 +1470,191 @@ mod tests {
                 .collect::<HashMap<_, _>>(),
         );
     }
+
+    #[test]
+    fn descriptor_buffer_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = DescriptorSetLayout::new(
+            device,
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                descriptor_buffer: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(DescriptorSetLayoutCreationError::RequirementNotMet { .. }),
+        ));
+    }
+
+    #[test]
+    fn inline_uniform_block_size() {
+        let (device, _) = gfx_dev_and_queue!(inline_uniform_block);
+
+        let create_info = |descriptor_count| DescriptorSetLayoutCreateInfo {
+            bindings: [(
+                0,
+                DescriptorSetLayoutBinding {
+                    descriptor_count,
+                    stages: ShaderStages::all_graphics(),
+                    ..DescriptorSetLayoutBinding::descriptor_type(
+                        DescriptorType::InlineUniformBlock,
+                    )
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            DescriptorSetLayout::new(device.clone(), create_info(6)),
+            Err(
+                DescriptorSetLayoutCreationError::InlineUniformBlockSizeNotAligned {
+                    binding_num: 0
+                }
+            ),
+        ));
+
+        let max_size = device
+            .physical_device()
+            .properties()
+            .max_inline_uniform_block_size
+            .unwrap();
+
+        assert!(matches!(
+            DescriptorSetLayout::new(device.clone(), create_info(max_size + 4)),
+            Err(
+                DescriptorSetLayoutCreationError::MaxInlineUniformBlockSizeExceeded {
+                    binding_num: 0,
+                    ..
+                }
+            ),
+        ));
+
+        let layout = DescriptorSetLayout::new(device, create_info(max_size.min(64))).unwrap();
+        assert_eq!(
+            layout.descriptor_counts()[&DescriptorType::InlineUniformBlock],
+            max_size.min(64),
+        );
+
+        assert!(matches!(
+            check_descriptor_write(
+                &WriteDescriptorSet::inline_uniform_block(0, 0, []),
+                &layout,
+                0
+            ),
+            Err(DescriptorSetUpdateError::InlineUniformBlockEmpty { binding: 0 }),
+        ));
+    }
+
+    #[test]
+    fn inline_uniform_block_shader_size() {
+        let mut binding = DescriptorSetLayoutBinding {
+            descriptor_count: 16,
+            stages: ShaderStages::FRAGMENT,
+            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::InlineUniformBlock)
+        };
+        let reqs = DescriptorBindingRequirements {
+            descriptor_types: vec![
+                DescriptorType::UniformBuffer,
+                DescriptorType::UniformBufferDynamic,
+                DescriptorType::InlineUniformBlock,
+            ],
+            descriptor_count: Some(1),
+            uniform_block_size: Some(32),
+            stages: ShaderStages::FRAGMENT,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            binding.ensure_compatible_with_shader(&reqs),
+            Err(DescriptorRequirementsNotMet::InlineUniformBlockSize {
+                required: 32,
+                obtained: 16,
+            }),
+        ));
+
+        binding.descriptor_count = 32;
+        assert!(binding.ensure_compatible_with_shader(&reqs).is_ok());
+    }
+
+    #[test]
+    fn enable_bindless() {
+        let mut create_info = DescriptorSetLayoutCreateInfo::from_requirements([
+            (
+                (0, 0),
+                &DescriptorBindingRequirements {
+                    descriptor_types: vec![DescriptorType::UniformBuffer],
+                    descriptor_count: Some(1),
+                    stages: ShaderStages::FRAGMENT,
+                    ..Default::default()
+                },
+            ),
+            (
+                (0, 1),
+                &DescriptorBindingRequirements {
+                    descriptor_types: vec![DescriptorType::SampledImage],
+                    descriptor_count: None,
+                    stages: ShaderStages::FRAGMENT,
+                    ..Default::default()
+                },
+            ),
+        ])
+        .remove(0);
+        create_info.enable_bindless(64);
+
+        assert!(create_info.update_after_bind_pool);
+
+        let fixed = &create_info.bindings[&0];
+        assert_eq!(fixed.descriptor_count, 1);
+        assert!(!fixed.partially_bound && !fixed.update_after_bind);
+
+        let runtime = &create_info.bindings[&1];
+        assert_eq!(runtime.descriptor_count, 64);
+        assert!(runtime.variable_descriptor_count);
+        assert!(runtime.partially_bound && runtime.update_after_bind);
+    }
+
+    #[test]
+    fn partially_bound_fixed_size_array() {
+        let mut binding = DescriptorSetLayoutBinding {
+            descriptor_count: 16,
+            partially_bound: true,
+            stages: ShaderStages::FRAGMENT,
+            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::SampledImage)
+        };
+        let mut reqs = DescriptorBindingRequirements {
+            descriptor_types: vec![DescriptorType::SampledImage],
+            descriptor_count: Some(16),
+            stages: ShaderStages::FRAGMENT,
+            ..Default::default()
+        };
+
+        assert!(binding.ensure_compatible_with_shader(&reqs).is_ok());
+
+        binding.variable_descriptor_count = true;
+        assert!(binding.ensure_compatible_with_shader(&reqs).is_ok());
+
+        reqs.descriptor_count = None;
+        assert!(binding.ensure_compatible_with_shader(&reqs).is_ok());
+
+        reqs.descriptor_count = Some(32);
+        assert!(matches!(
+            binding.ensure_compatible_with_shader(&reqs),
+            Err(DescriptorRequirementsNotMet::DescriptorCount {
+                required: 32,
+                obtained: 16,
+            }),
+        ));
+    }
 }
@@ -78,11 +78,14 @@
 //! [`DescriptorSetAllocator`]: allocator::DescriptorSetAllocator
 //! [`StandardDescriptorSetAllocator`]: allocator::StandardDescriptorSetAllocator
 
-pub(crate) use self::update::{check_descriptor_write, DescriptorWriteInfo};
+pub(crate) use self::update::{check_descriptor_copy, check_descriptor_write, DescriptorWriteInfo};
 pub use self::{
     collection::DescriptorSetsCollection,
     persistent::PersistentDescriptorSet,
-    update::{DescriptorSetUpdateError, WriteDescriptorSet, WriteDescriptorSetElements},
+    updatable::{UpdatableDescriptorSet, UpdatableDescriptorSetSnapshot},
+    update::{
+        CopyDescriptorSet, DescriptorSetUpdateError, WriteDescriptorSet, WriteDescriptorSetElements,
+    },
 };
 use self::{layout::DescriptorSetLayout, sys::UnsafeDescriptorSet};
 use crate::{
@@ -105,11 +108,14 @@ use std::{
 };
 
 pub mod allocator;
+pub mod bindless;
 mod collection;
+pub mod descriptor_buffer;
 pub mod layout;
 pub mod persistent;
 pub mod pool;
 pub mod sys;
+pub mod updatable;
 mod update;
 
 /// Trait for objects that contain a collection of resources that will be accessible by shaders.
@@ -173,6 +179,11 @@ impl DescriptorSetInner {
             "the provided descriptor set layout is for push descriptors, and cannot be used to \
             build a descriptor set object",
         );
+        assert!(
+            !layout.descriptor_buffer(),
+            "the provided descriptor set layout is for descriptor buffers, and cannot be used to \
+            build a descriptor set object",
+        );
 
         let max_count = layout.variable_descriptor_count();
 
@@ -201,8 +212,14 @@ impl DescriptorSetInner {
         }
 
         if !write_descriptor_set.is_empty() {
-            for (info, write) in descriptor_write_info
+            let inline_infos: SmallVec<[_; 8]> = descriptor_write_info
                 .iter()
+                .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+                .collect();
+
+            for ((info, inline_info), write) in descriptor_write_info
+                .iter()
+                .zip(inline_infos.iter())
                 .zip(write_descriptor_set.iter_mut())
             {
                 match info {
@@ -218,6 +235,10 @@ impl DescriptorSetInner {
                         write.descriptor_count = info.len() as u32;
                         write.p_texel_buffer_view = info.as_ptr();
                     }
+                    DescriptorWriteInfo::InlineUniformBlock(data) => {
+                        write.descriptor_count = data.len() as u32;
+                        write.p_next = inline_info as *const _ as *const _;
+                    }
                 }
             }
         }
@@ -308,6 +329,9 @@ impl DescriptorSetResources {
                             DescriptorBindingResources::None(smallvec![Some(()); count])
                         }
                     }
+                    DescriptorType::InlineUniformBlock => {
+                        DescriptorBindingResources::InlineUniformBlock(smallvec![None; count])
+                    }
                 };
                 (binding_num, binding_resources)
             })
@@ -330,12 +354,136 @@ impl DescriptorSetResources {
             .update(write)
     }
 
+    /// Applies a descriptor copy to the resources.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the source or destination binding number of the copy does not exist in the
+    ///   resources of the source set or these resources.
+    /// - Panics if the resource types of the two bindings do not match.
+    /// - Panics if the copy goes out of bounds.
+    pub fn copy(&mut self, copy: &CopyDescriptorSet) {
+        let src = copy
+            .src_set
+            .resources()
+            .binding(copy.src_binding)
+            .expect("descriptor copy has invalid source binding number");
+
+        self.copy_from(copy, src);
+    }
+
+    // Applies a descriptor copy, reading from `src` instead of the resources of the source set.
+    // This is needed when the source set is the set being updated, whose resources in
+    // `copy.src_set` may not include the writes and copies that came before.
+    pub(crate) fn copy_from(&mut self, copy: &CopyDescriptorSet, src: &DescriptorBindingResources) {
+        fn copy_resources<T: Clone>(
+            dst: &mut [Option<T>],
+            dst_first: usize,
+            src: &[Option<T>],
+            src_first: usize,
+            count: usize,
+        ) {
+            dst.get_mut(dst_first..dst_first + count)
+                .expect("descriptor copy for destination binding out of bounds")
+                .clone_from_slice(
+                    src.get(src_first..src_first + count)
+                        .expect("descriptor copy for source binding out of bounds"),
+                );
+        }
+
+        let dst = self
+            .binding_resources
+            .get_mut(&copy.dst_binding)
+            .expect("descriptor copy has invalid destination binding number");
+
+        let dst_first = copy.dst_first_array_element as usize;
+        let src_first = copy.src_first_array_element as usize;
+        let count = copy.descriptor_count as usize;
+
+        match (dst, src) {
+            (DescriptorBindingResources::None(dst), DescriptorBindingResources::None(src)) => {
+                copy_resources(dst, dst_first, src, src_first, count)
+            }
+            (DescriptorBindingResources::Buffer(dst), DescriptorBindingResources::Buffer(src)) => {
+                copy_resources(dst, dst_first, src, src_first, count)
+            }
+            (
+                DescriptorBindingResources::BufferView(dst),
+                DescriptorBindingResources::BufferView(src),
+            ) => copy_resources(dst, dst_first, src, src_first, count),
+            (
+                DescriptorBindingResources::ImageView(dst),
+                DescriptorBindingResources::ImageView(src),
+            ) => copy_resources(dst, dst_first, src, src_first, count),
+            (
+                DescriptorBindingResources::ImageViewSampler(dst),
+                DescriptorBindingResources::ImageViewSampler(src),
+            ) => copy_resources(dst, dst_first, src, src_first, count),
+            (
+                DescriptorBindingResources::Sampler(dst),
+                DescriptorBindingResources::Sampler(src),
+            ) => copy_resources(dst, dst_first, src, src_first, count),
+            (
+                DescriptorBindingResources::InlineUniformBlock(dst),
+                DescriptorBindingResources::InlineUniformBlock(src),
+            ) => copy_resources(dst, dst_first, src, src_first, count),
+            _ => panic!(
+                "descriptor copy from binding {} to binding {} has mismatched resource types",
+                copy.src_binding, copy.dst_binding,
+            ),
+        }
+    }
+
     /// Returns a reference to the bound resources for `binding`. Returns `None` if the binding
     /// doesn't exist.
     #[inline]
     pub fn binding(&self, binding: u32) -> Option<&DescriptorBindingResources> {
         self.binding_resources.get(&binding)
     }
+
+    /// Marks the descriptors in `elements` of `binding` as not holding a resource anymore.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the binding number does not exist in the resources.
+    /// - Panics if `elements` goes out of bounds.
+    pub(crate) fn invalidate(&mut self, binding: u32, elements: Range<u32>) {
+        fn invalidate_resources<T>(resources: &mut [Option<T>], elements: Range<u32>) {
+            resources
+                .get_mut(elements.start as usize..elements.end as usize)
+                .expect("descriptor invalidation for binding out of bounds")
+                .iter_mut()
+                .for_each(|resource| *resource = None);
+        }
+
+        match self
+            .binding_resources
+            .get_mut(&binding)
+            .expect("descriptor invalidation has invalid binding number")
+        {
+            DescriptorBindingResources::None(resources) => {
+                invalidate_resources(resources, elements)
+            }
+            DescriptorBindingResources::Buffer(resources) => {
+                invalidate_resources(resources, elements)
+            }
+            DescriptorBindingResources::BufferView(resources) => {
+                invalidate_resources(resources, elements)
+            }
+            DescriptorBindingResources::ImageView(resources) => {
+                invalidate_resources(resources, elements)
+            }
+            DescriptorBindingResources::ImageViewSampler(resources) => {
+                invalidate_resources(resources, elements)
+            }
+            DescriptorBindingResources::Sampler(resources) => {
+                invalidate_resources(resources, elements)
+            }
+            DescriptorBindingResources::InlineUniformBlock(resources) => {
+                invalidate_resources(resources, elements)
+            }
+        }
+    }
 }
 
 /// The resources that are bound to a single descriptor set binding.
@@ -347,6 +495,8 @@ pub enum DescriptorBindingResources {
     ImageView(Elements<Arc<dyn ImageViewAbstract>>),
     ImageViewSampler(Elements<(Arc<dyn ImageViewAbstract>, Arc<Sampler>)>),
     Sampler(Elements<Arc<Sampler>>),
+    /// The bytes of an inline uniform block, one element per byte.
+    InlineUniformBlock(Elements<u8>),
 }
 
 type Elements<T> = SmallVec<[Option<T>; 1]>;
@@ -406,6 +556,10 @@ impl DescriptorBindingResources {
                 DescriptorBindingResources::Sampler(resources),
                 WriteDescriptorSetElements::Sampler(elements),
             ) => write_resources(first, resources, elements),
+            (
+                DescriptorBindingResources::InlineUniformBlock(resources),
+                WriteDescriptorSetElements::InlineUniformBlock(data),
+            ) => write_resources(first, resources, data),
             _ => panic!(
                 "descriptor write for binding {} has wrong resource type",
                 write.binding(),
@@ -64,6 +64,7 @@ impl PersistentDescriptorSet {
     /// # Panics
     ///
     /// - Panics if `layout` was created for push descriptors rather than descriptor sets.
+    /// - Panics if `layout` was created for descriptor buffers.
     /// - Panics if `variable_descriptor_count` is too large for the given `layout`.
     pub fn new_variable<A>(
         allocator: &A,
@@ -42,6 +42,8 @@ pub struct DescriptorPool {
     max_sets: u32,
     pool_sizes: HashMap<DescriptorType, u32>,
     can_free_descriptor_sets: bool,
+    update_after_bind: bool,
+    max_inline_uniform_block_bindings: u32,
     // Unimplement `Sync`, as Vulkan descriptor pools are not thread safe.
     _marker: PhantomData<Cell<ash::vk::DescriptorPool>>,
 }
@@ -62,6 +64,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _ne: _,
         } = create_info;
 
@@ -91,7 +95,11 @@ impl DescriptorPool {
                 flags |= ash::vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET;
             }
 
-            let create_info = ash::vk::DescriptorPoolCreateInfo {
+            if update_after_bind {
+                flags |= ash::vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND;
+            }
+
+            let mut create_info = ash::vk::DescriptorPoolCreateInfo {
                 flags,
                 max_sets,
                 pool_size_count: pool_sizes.len() as u32,
@@ -99,6 +107,20 @@ impl DescriptorPool {
                 ..Default::default()
             };
 
+            let mut inline_uniform_block_create_info = None;
+
+            if max_inline_uniform_block_bindings != 0 {
+                let next = inline_uniform_block_create_info.insert(
+                    ash::vk::DescriptorPoolInlineUniformBlockCreateInfo {
+                        max_inline_uniform_block_bindings,
+                        ..Default::default()
+                    },
+                );
+
+                next.p_next = create_info.p_next;
+                create_info.p_next = next as *const _ as *const _;
+            }
+
             unsafe {
                 let fns = device.fns();
                 let mut output = MaybeUninit::uninit();
@@ -121,6 +143,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _marker: PhantomData,
         })
     }
@@ -141,6 +165,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _ne: _,
         } = create_info;
 
@@ -151,6 +177,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _marker: PhantomData,
         }
     }
@@ -173,6 +201,20 @@ impl DescriptorPool {
         self.can_free_descriptor_sets
     }
 
+    /// Returns whether descriptor sets with an `update_after_bind_pool` layout can be allocated
+    /// from the pool.
+    #[inline]
+    pub fn update_after_bind(&self) -> bool {
+        self.update_after_bind
+    }
+
+    /// Returns the maximum number of inline uniform block bindings that can be allocated from the
+    /// pool.
+    #[inline]
+    pub fn max_inline_uniform_block_bindings(&self) -> u32 {
+        self.max_inline_uniform_block_bindings
+    }
+
     /// Allocates descriptor sets from the pool, one for each element in `create_info`.
     /// Returns an iterator to the allocated sets, or an error.
     ///
@@ -188,6 +230,8 @@ impl DescriptorPool {
     /// See also the `new` function.
     ///
     /// - The total descriptors of the layouts must fit in the pool.
+    /// - If a layout has `update_after_bind_pool` enabled, the pool must have been created with
+    ///   `update_after_bind` enabled.
     /// - The total number of descriptor sets allocated from the pool must not overflow the pool.
     /// - You must ensure that the allocated descriptor sets are no longer in use when the pool
     ///   is destroyed, as destroying the pool is equivalent to freeing all the sets.
@@ -201,6 +245,8 @@ impl DescriptorPool {
                 .map(|info| {
                     assert_eq!(self.device.handle(), info.layout.device().handle(),);
                     debug_assert!(!info.layout.push_descriptor());
+                    debug_assert!(!info.layout.descriptor_buffer());
+                    debug_assert!(!info.layout.update_after_bind_pool() || self.update_after_bind);
                     debug_assert!(
                         info.variable_descriptor_count <= info.layout.variable_descriptor_count()
                     );
@@ -368,6 +414,23 @@ pub struct DescriptorPoolCreateInfo {
     /// The default value is `false`.
     pub can_free_descriptor_sets: bool,
 
+    /// Whether descriptor sets whose layout has `update_after_bind_pool` enabled can be allocated
+    /// from the pool. Such layouts can only be allocated from pools with this flag.
+    ///
+    /// The default value is `false`.
+    pub update_after_bind: bool,
+
+    /// The maximum number of [`DescriptorType::InlineUniformBlock`] bindings, summed over all
+    /// descriptor sets, that can be allocated from the pool. The number of bytes for inline
+    /// uniform blocks is specified in `pool_sizes` as usual.
+    ///
+    /// If this is not `0`, the
+    /// [`inline_uniform_block`](crate::device::Features::inline_uniform_block) feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is `0`.
+    pub max_inline_uniform_block_bindings: u32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -378,6 +441,8 @@ impl Default for DescriptorPoolCreateInfo {
             max_sets: 0,
             pool_sizes: HashMap::default(),
             can_free_descriptor_sets: false,
+            update_after_bind: false,
+            max_inline_uniform_block_bindings: 0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -12,7 +12,7 @@
 use crate::{
     descriptor_set::{
         layout::DescriptorSetLayout,
-        update::{DescriptorWriteInfo, WriteDescriptorSet},
+        update::{CopyDescriptorSet, DescriptorWriteInfo, WriteDescriptorSet},
     },
     device::DeviceOwned,
     macros::impl_id_counter,
@@ -22,7 +22,6 @@ use smallvec::SmallVec;
 use std::{
     fmt::{Debug, Error as FmtError, Formatter},
     num::NonZeroU64,
-    ptr,
 };
 
 /// Low-level descriptor set.
@@ -45,8 +44,8 @@ impl UnsafeDescriptorSet {
         }
     }
 
-    /// Modifies a descriptor set. Doesn't check that the writes or copies are correct, and
-    /// doesn't check whether the descriptor set is in use.
+    /// Modifies a descriptor set. Doesn't check that the writes are correct, and doesn't check
+    /// whether the descriptor set is in use.
     ///
     /// # Safety
     ///
@@ -56,10 +55,42 @@ impl UnsafeDescriptorSet {
     /// - Updating a descriptor set obeys synchronization rules that aren't checked here. Once a
     ///   command buffer contains a pointer/reference to a descriptor set, it is illegal to write
     ///   to it.
+    #[inline]
     pub unsafe fn write<'a>(
         &mut self,
         layout: &DescriptorSetLayout,
         writes: impl IntoIterator<Item = &'a WriteDescriptorSet>,
+    ) {
+        self.update(layout, writes, []);
+    }
+
+    /// Copies descriptors from other descriptor sets into this one. Doesn't check that the copies
+    /// are correct, and doesn't check whether the descriptor set is in use.
+    ///
+    /// # Safety
+    ///
+    /// - The same requirements as for [`write`](Self::write) apply.
+    /// - The descriptors that are copied from must be valid.
+    #[inline]
+    pub unsafe fn copy<'a>(
+        &mut self,
+        layout: &DescriptorSetLayout,
+        copies: impl IntoIterator<Item = &'a CopyDescriptorSet>,
+    ) {
+        self.update(layout, [], copies);
+    }
+
+    /// Performs both descriptor writes and descriptor copies on this descriptor set, in a single
+    /// operation. The writes are performed before the copies.
+    ///
+    /// # Safety
+    ///
+    /// - The same requirements as for [`write`](Self::write) and [`copy`](Self::copy) apply.
+    pub unsafe fn update<'a>(
+        &mut self,
+        layout: &DescriptorSetLayout,
+        writes: impl IntoIterator<Item = &'a WriteDescriptorSet>,
+        copies: impl IntoIterator<Item = &'a CopyDescriptorSet>,
     ) {
         let (infos, mut writes): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = writes
             .into_iter()
@@ -72,15 +103,26 @@ impl UnsafeDescriptorSet {
                 )
             })
             .unzip();
+        let copies: SmallVec<[_; 8]> = copies
+            .into_iter()
+            .map(|copy| copy.to_vulkan(self.handle))
+            .collect();
 
-        // It is forbidden to call `vkUpdateDescriptorSets` with 0 writes, so we need to perform
-        // this emptiness check.
-        if writes.is_empty() {
+        // It is forbidden to call `vkUpdateDescriptorSets` with 0 writes and 0 copies, so we need
+        // to perform this emptiness check.
+        if writes.is_empty() && copies.is_empty() {
             return;
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        let inline_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
+        for ((info, inline_info), write) in
+            infos.iter().zip(inline_infos.iter()).zip(writes.iter_mut())
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -94,6 +136,10 @@ impl UnsafeDescriptorSet {
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -105,14 +151,10 @@ impl UnsafeDescriptorSet {
             layout.device().handle(),
             writes.len() as u32,
             writes.as_ptr(),
-            0,
-            ptr::null(),
+            copies.len() as u32,
+            copies.as_ptr(),
         );
     }
-
-    // TODO: add copying from other descriptor sets
-    //       add a `copy` method that just takes a copy, and an `update` method that takes both
-    //       writes and copies and that actually performs the operation
 }
 
 unsafe impl VulkanObject for UnsafeDescriptorSet {
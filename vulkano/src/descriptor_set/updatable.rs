@@ -0,0 +1,585 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A descriptor set that can be updated after it has been created.
+//!
+//! Unlike a [`PersistentDescriptorSet`], an [`UpdatableDescriptorSet`] accepts further
+//! [`WriteDescriptorSet`]s and [`CopyDescriptorSet`]s after creation. To bind the set, call
+//! [`UpdatableDescriptorSet::snapshot`], which captures the resources that are currently in the
+//! set, so that command buffers keep them alive.
+//!
+//! Updating a descriptor set invalidates every command buffer that it was bound in, and it must
+//! not happen while such a command buffer is executing. Therefore, as long as a snapshot is alive
+//! (which includes being held by a command buffer), the set is considered in use, and updates are
+//! refused with [`DescriptorSetUpdateError::InUse`].
+//!
+//! The exceptions are bindings that were created with [`update_after_bind`] or
+//! [`update_unused_while_pending`]. Their descriptors can be updated while the set is in use, as
+//! long as no live snapshot holds a resource in the updated array elements. All snapshots share
+//! the same Vulkan descriptor set, so a pending command buffer can't be affected by the update,
+//! since it doesn't access the elements that were empty when it was recorded.
+//!
+//! [`PersistentDescriptorSet`]: super::PersistentDescriptorSet
+//! [`update_after_bind`]: super::layout::DescriptorSetLayoutBinding::update_after_bind
+//! [`update_unused_while_pending`]: super::layout::DescriptorSetLayoutBinding::update_unused_while_pending
+
+use super::{
+    allocator::{DescriptorSetAlloc, DescriptorSetAllocator, StandardDescriptorSetAlloc},
+    check_descriptor_copy, check_descriptor_write,
+    layout::DescriptorSetLayout,
+    sys::UnsafeDescriptorSet,
+    CopyDescriptorSet, DescriptorBindingResources, DescriptorSet, DescriptorSetCreationError,
+    DescriptorSetInner, DescriptorSetResources, DescriptorSetUpdateError, WriteDescriptorSet,
+};
+use crate::{
+    device::{Device, DeviceOwned},
+    VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    hash::{Hash, Hasher},
+    ops::Range,
+    sync::{Arc, Weak},
+};
+
+/// A descriptor set that can be updated after it has been created.
+///
+/// See the [module-level documentation](self) for more.
+pub struct UpdatableDescriptorSet<P = StandardDescriptorSetAlloc> {
+    inner: Arc<UpdatableInner<P>>,
+    set: UnsafeDescriptorSet,
+    resources: DescriptorSetResources,
+
+    snapshots: Vec<Weak<UpdatableDescriptorSetSnapshot<P>>>,
+    modified: bool,
+}
+
+impl UpdatableDescriptorSet {
+    /// Creates and returns a new descriptor set with a variable descriptor count of 0.
+    #[inline]
+    pub fn new<A>(
+        allocator: &A,
+        layout: Arc<DescriptorSetLayout>,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<UpdatableDescriptorSet<A::Alloc>, DescriptorSetCreationError>
+    where
+        A: DescriptorSetAllocator + ?Sized,
+    {
+        Self::new_variable(allocator, layout, 0, descriptor_writes)
+    }
+
+    /// Creates and returns a new descriptor set with the requested variable descriptor count,
+    /// allocating it from the provided allocator.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `layout` was created for push descriptors rather than descriptor sets.
+    /// - Panics if `layout` was created for descriptor buffers.
+    /// - Panics if `variable_descriptor_count` is too large for the given `layout`.
+    pub fn new_variable<A>(
+        allocator: &A,
+        layout: Arc<DescriptorSetLayout>,
+        variable_descriptor_count: u32,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<UpdatableDescriptorSet<A::Alloc>, DescriptorSetCreationError>
+    where
+        A: DescriptorSetAllocator + ?Sized,
+    {
+        assert!(
+            !layout.push_descriptor(),
+            "the provided descriptor set layout is for push descriptors, and cannot be used to \
+            build a descriptor set object",
+        );
+
+        let max_count = layout.variable_descriptor_count();
+
+        assert!(
+            variable_descriptor_count <= max_count,
+            "the provided variable_descriptor_count ({}) is greater than the maximum number of \
+            variable count descriptors in the set ({})",
+            variable_descriptor_count,
+            max_count,
+        );
+
+        let alloc = allocator.allocate(&layout, variable_descriptor_count)?;
+        let handle = alloc.inner().handle();
+        let DescriptorSetInner {
+            layout,
+            variable_descriptor_count,
+            resources,
+        } = DescriptorSetInner::new(handle, layout, variable_descriptor_count, descriptor_writes)?;
+
+        Ok(UpdatableDescriptorSet {
+            inner: Arc::new(UpdatableInner {
+                _alloc: alloc,
+                layout,
+                variable_descriptor_count,
+            }),
+            set: UnsafeDescriptorSet::new(handle),
+            resources,
+            snapshots: Vec::new(),
+            modified: true,
+        })
+    }
+}
+
+impl<P> UpdatableDescriptorSet<P>
+where
+    P: DescriptorSetAlloc,
+{
+    /// Returns the layout of the descriptor set.
+    #[inline]
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.inner.layout
+    }
+
+    /// Returns the variable descriptor count that the descriptor set was allocated with.
+    #[inline]
+    pub fn variable_descriptor_count(&self) -> u32 {
+        self.inner.variable_descriptor_count
+    }
+
+    /// Returns the resources that are currently in the descriptor set.
+    #[inline]
+    pub fn resources(&self) -> &DescriptorSetResources {
+        &self.resources
+    }
+
+    /// Returns whether a snapshot of the descriptor set is still alive, meaning that it may be
+    /// in use by a command buffer.
+    #[inline]
+    pub fn is_in_use(&self) -> bool {
+        self.snapshots
+            .iter()
+            .any(|snapshot| snapshot.strong_count() != 0)
+    }
+
+    /// Writes and copies descriptors into the descriptor set. The writes are performed before
+    /// the copies.
+    ///
+    /// If the descriptor set [is in use](Self::is_in_use), [`DescriptorSetUpdateError::InUse`] is
+    /// returned, unless every updated binding has `update_after_bind` or
+    /// `update_unused_while_pending` and the updated elements are empty in all live snapshots.
+    /// If an error is returned, the descriptor set is left unchanged.
+    pub fn update(
+        &mut self,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+        descriptor_copies: impl IntoIterator<Item = CopyDescriptorSet>,
+    ) -> Result<(), DescriptorSetUpdateError> {
+        let descriptor_writes: SmallVec<[_; 8]> = descriptor_writes.into_iter().collect();
+        let descriptor_copies: SmallVec<[_; 8]> = descriptor_copies.into_iter().collect();
+
+        self.snapshots
+            .retain(|snapshot| snapshot.strong_count() != 0);
+
+        // A snapshot that is only referenced by the copies themselves, to copy between bindings
+        // of this set, is not in use by a command buffer.
+        let in_use = self.snapshots.iter().any(|snapshot| {
+            let copy_references = descriptor_copies
+                .iter()
+                .filter(|copy| {
+                    Arc::as_ptr(&copy.src_set) as *const () == snapshot.as_ptr() as *const ()
+                })
+                .count();
+
+            snapshot.strong_count() > copy_references
+        });
+
+        // VUID-vkUpdateDescriptorSets-None-03047
+        for write in &descriptor_writes {
+            check_descriptor_write(write, &self.inner.layout, self.variable_descriptor_count())?;
+
+            let first = write.first_array_element();
+
+            if in_use
+                && !self.can_update_in_use(write.binding(), first..first + write.elements().len())
+            {
+                return Err(DescriptorSetUpdateError::InUse {
+                    binding: write.binding(),
+                });
+            }
+        }
+
+        for copy in &descriptor_copies {
+            check_descriptor_copy(
+                copy,
+                self.set.handle(),
+                &self.inner.layout,
+                self.variable_descriptor_count(),
+            )?;
+
+            let first = copy.dst_first_array_element;
+
+            if in_use
+                && !self.can_update_in_use(copy.dst_binding, first..first + copy.descriptor_count)
+            {
+                return Err(DescriptorSetUpdateError::InUse {
+                    binding: copy.dst_binding,
+                });
+            }
+        }
+
+        unsafe {
+            self.set
+                .update(&self.inner.layout, &descriptor_writes, &descriptor_copies);
+        }
+
+        for write in &descriptor_writes {
+            self.resources.update(write);
+        }
+
+        for copy in &descriptor_copies {
+            // The writes and earlier copies are not in the resources of snapshots of this set.
+            if copy.src_set.inner().handle() == self.set.handle() {
+                let src = self
+                    .resources
+                    .binding(copy.src_binding)
+                    .expect("descriptor copy has invalid source binding number")
+                    .clone();
+                self.resources.copy_from(copy, &src);
+            } else {
+                self.resources.copy(copy);
+            }
+        }
+
+        if !descriptor_writes.is_empty() || !descriptor_copies.is_empty() {
+            self.modified = true;
+        }
+
+        Ok(())
+    }
+
+    // Returns whether `elements` of `binding_num` can be updated while the set is in use. The
+    // binding must allow it, and no live snapshot may hold a resource in the elements, so that
+    // pending command buffers don't access them.
+    fn can_update_in_use(&self, binding_num: u32, elements: Range<u32>) -> bool {
+        fn is_empty<T>(resources: &[Option<T>], elements: Range<usize>) -> bool {
+            resources
+                .get(elements)
+                .map_or(false, |resources| resources.iter().all(Option::is_none))
+        }
+
+        let binding = &self.inner.layout.bindings()[&binding_num];
+
+        if !(binding.update_after_bind || binding.update_unused_while_pending) {
+            return false;
+        }
+
+        let elements = elements.start as usize..elements.end as usize;
+
+        self.snapshots
+            .iter()
+            .filter_map(Weak::upgrade)
+            .all(|snapshot| match snapshot.resources.binding(binding_num) {
+                Some(DescriptorBindingResources::None(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                Some(DescriptorBindingResources::Buffer(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                Some(DescriptorBindingResources::BufferView(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                Some(DescriptorBindingResources::ImageView(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                Some(DescriptorBindingResources::ImageViewSampler(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                Some(DescriptorBindingResources::Sampler(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                Some(DescriptorBindingResources::InlineUniformBlock(resources)) => {
+                    is_empty(resources, elements.clone())
+                }
+                None => false,
+            })
+    }
+
+    /// Returns a descriptor set that can be bound to a command buffer, holding the resources that
+    /// are currently in the set.
+    ///
+    /// If the set was not updated since the previous snapshot and that snapshot is still alive,
+    /// it is returned again.
+    pub fn snapshot(&mut self) -> Arc<UpdatableDescriptorSetSnapshot<P>> {
+        if !self.modified {
+            if let Some(snapshot) = self.snapshots.last().and_then(Weak::upgrade) {
+                return snapshot;
+            }
+        }
+
+        let snapshot = Arc::new(UpdatableDescriptorSetSnapshot {
+            inner: self.inner.clone(),
+            set: UnsafeDescriptorSet::new(self.set.handle()),
+            resources: self.resources.clone(),
+        });
+
+        self.snapshots.push(Arc::downgrade(&snapshot));
+        self.modified = false;
+
+        snapshot
+    }
+}
+
+unsafe impl<P> DeviceOwned for UpdatableDescriptorSet<P>
+where
+    P: DescriptorSetAlloc,
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.layout.device()
+    }
+}
+
+/// The state of an [`UpdatableDescriptorSet`] at a point in time, which can be bound to a
+/// command buffer.
+pub struct UpdatableDescriptorSetSnapshot<P = StandardDescriptorSetAlloc> {
+    inner: Arc<UpdatableInner<P>>,
+    set: UnsafeDescriptorSet,
+    resources: DescriptorSetResources,
+}
+
+unsafe impl<P> DescriptorSet for UpdatableDescriptorSetSnapshot<P>
+where
+    P: DescriptorSetAlloc,
+{
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        &self.set
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.inner.layout
+    }
+
+    #[inline]
+    fn variable_descriptor_count(&self) -> u32 {
+        self.inner.variable_descriptor_count
+    }
+
+    #[inline]
+    fn resources(&self) -> &DescriptorSetResources {
+        &self.resources
+    }
+}
+
+unsafe impl<P> DeviceOwned for UpdatableDescriptorSetSnapshot<P>
+where
+    P: DescriptorSetAlloc,
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.layout.device()
+    }
+}
+
+impl<P> PartialEq for UpdatableDescriptorSetSnapshot<P>
+where
+    P: DescriptorSetAlloc,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl<P> Eq for UpdatableDescriptorSetSnapshot<P> where P: DescriptorSetAlloc {}
+
+impl<P> Hash for UpdatableDescriptorSetSnapshot<P>
+where
+    P: DescriptorSetAlloc,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
+struct UpdatableInner<P> {
+    // Dropping the allocation frees the descriptor set, so it must outlive all snapshots.
+    _alloc: P,
+    layout: Arc<DescriptorSetLayout>,
+    variable_descriptor_count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UpdatableDescriptorSet;
+    use crate::{
+        descriptor_set::{
+            allocator::StandardDescriptorSetAllocator,
+            layout::{
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+                DescriptorType,
+            },
+            CopyDescriptorSet, DescriptorBindingResources, DescriptorSet, DescriptorSetUpdateError,
+            WriteDescriptorSet,
+        },
+        sampler::{Sampler, SamplerCreateInfo},
+        shader::ShaderStages,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn update_refused_while_in_use() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        descriptor_count: 2,
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let allocator = StandardDescriptorSetAllocator::new(device.clone());
+        let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+
+        let mut set = UpdatableDescriptorSet::new(
+            &allocator,
+            layout,
+            [WriteDescriptorSet::sampler(0, sampler.clone())],
+        )
+        .unwrap();
+        let snapshot = set.snapshot();
+        assert!(set.is_in_use());
+
+        assert!(matches!(
+            set.update([WriteDescriptorSet::sampler_array(0, 1, [sampler])], []),
+            Err(DescriptorSetUpdateError::InUse { binding: 0 }),
+        ));
+
+        drop(snapshot);
+        assert!(!set.is_in_use());
+
+        let src: Arc<dyn DescriptorSet> = set.snapshot();
+        let mut set2 = UpdatableDescriptorSet::new(&allocator, src.layout().clone(), []).unwrap();
+        set2.update(
+            [],
+            [CopyDescriptorSet {
+                descriptor_count: 1,
+                dst_first_array_element: 1,
+                ..CopyDescriptorSet::new(src)
+            }],
+        )
+        .unwrap();
+
+        match set2.resources().binding(0).unwrap() {
+            DescriptorBindingResources::Sampler(elements) => {
+                assert!(elements[0].is_none());
+                assert!(elements[1].is_some());
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn update_after_bind_while_in_use() {
+        let (device, _) = gfx_dev_and_queue!(descriptor_binding_sampled_image_update_after_bind);
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        descriptor_count: 2,
+                        update_after_bind: true,
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                update_after_bind_pool: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let allocator = StandardDescriptorSetAllocator::new(device.clone());
+        let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+
+        let mut set = UpdatableDescriptorSet::new(
+            &allocator,
+            layout,
+            [WriteDescriptorSet::sampler(0, sampler.clone())],
+        )
+        .unwrap();
+        let _snapshot = set.snapshot();
+        assert!(set.is_in_use());
+
+        // Element 1 is empty in the snapshot, element 0 is not.
+        set.update(
+            [WriteDescriptorSet::sampler_array(0, 1, [sampler.clone()])],
+            [],
+        )
+        .unwrap();
+        assert!(matches!(
+            set.update([WriteDescriptorSet::sampler(0, sampler)], []),
+            Err(DescriptorSetUpdateError::InUse { binding: 0 }),
+        ));
+    }
+
+    #[test]
+    fn copy_within_set_sees_writes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [0, 1]
+                    .into_iter()
+                    .map(|binding_num| {
+                        (
+                            binding_num,
+                            DescriptorSetLayoutBinding {
+                                stages: ShaderStages::all_graphics(),
+                                ..DescriptorSetLayoutBinding::descriptor_type(
+                                    DescriptorType::Sampler,
+                                )
+                            },
+                        )
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let allocator = StandardDescriptorSetAllocator::new(device.clone());
+        let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+
+        let mut set = UpdatableDescriptorSet::new(&allocator, layout, []).unwrap();
+        let src: Arc<dyn DescriptorSet> = set.snapshot();
+
+        // The snapshot is only referenced by the copy, so the set is not in use.
+        set.update(
+            [WriteDescriptorSet::sampler(0, sampler)],
+            [CopyDescriptorSet {
+                src_binding: 0,
+                dst_binding: 1,
+                descriptor_count: 1,
+                ..CopyDescriptorSet::new(src)
+            }],
+        )
+        .unwrap();
+
+        match set.resources().binding(1).unwrap() {
+            DescriptorBindingResources::Sampler(elements) => assert!(elements[0].is_some()),
+            _ => panic!(),
+        }
+    }
+}
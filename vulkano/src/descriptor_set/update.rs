@@ -7,7 +7,10 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::layout::{DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorType};
+use super::{
+    layout::{DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorType},
+    DescriptorSet,
+};
 use crate::{
     buffer::{view::BufferView, BufferUsage, Subbuffer},
     device::DeviceOwned,
@@ -225,6 +228,20 @@ impl WriteDescriptorSet {
         }
     }
 
+    /// Write raw bytes into an inline uniform block, starting at byte `offset`.
+    ///
+    /// For inline uniform block bindings, the array elements of the binding are the bytes of the
+    /// block, so `offset` is stored as the first array element of the write. Both `offset` and
+    /// the length of `data` must be a multiple of 4, and `data` must not be empty.
+    pub fn inline_uniform_block(binding: u32, offset: u32, data: impl Into<Vec<u8>>) -> Self {
+        let data = data.into();
+        Self {
+            binding,
+            first_array_element: offset,
+            elements: WriteDescriptorSetElements::InlineUniformBlock(data),
+        }
+    }
+
     /// Returns the binding number that is updated by this descriptor write.
     #[inline]
     pub fn binding(&self) -> u32 {
@@ -352,6 +369,13 @@ impl WriteDescriptorSet {
                         .collect(),
                 )
             }
+            WriteDescriptorSetElements::InlineUniformBlock(data) => {
+                debug_assert!(matches!(
+                    descriptor_type,
+                    DescriptorType::InlineUniformBlock
+                ));
+                DescriptorWriteInfo::InlineUniformBlock(data.clone())
+            }
         }
     }
 
@@ -382,10 +406,13 @@ pub enum WriteDescriptorSetElements {
     ImageView(SmallVec<[Arc<dyn ImageViewAbstract>; 1]>),
     ImageViewSampler(SmallVec<[(Arc<dyn ImageViewAbstract>, Arc<Sampler>); 1]>),
     Sampler(SmallVec<[Arc<Sampler>; 1]>),
+    InlineUniformBlock(Vec<u8>),
 }
 
 impl WriteDescriptorSetElements {
     /// Returns the number of elements.
+    ///
+    /// For inline uniform blocks, this is the number of bytes.
     #[inline]
     pub fn len(&self) -> u32 {
         match self {
@@ -395,6 +422,77 @@ impl WriteDescriptorSetElements {
             Self::ImageView(elements) => elements.len() as u32,
             Self::ImageViewSampler(elements) => elements.len() as u32,
             Self::Sampler(elements) => elements.len() as u32,
+            Self::InlineUniformBlock(data) => data.len() as u32,
+        }
+    }
+}
+
+/// Represents a single copy operation to the binding of a descriptor set.
+///
+/// The descriptors in `src_first_array_element..src_first_array_element + descriptor_count` of
+/// `src_binding` in `src_set` are copied to the same number of descriptors of `dst_binding` in
+/// the destination set, starting at `dst_first_array_element`. The two bindings must have the
+/// same descriptor type.
+#[derive(Clone)]
+pub struct CopyDescriptorSet {
+    /// The source descriptor set to copy from.
+    ///
+    /// There is no default value.
+    pub src_set: Arc<dyn DescriptorSet>,
+
+    /// The binding number in the source descriptor set to copy from.
+    ///
+    /// The default value is 0.
+    pub src_binding: u32,
+
+    /// The first array element in the source descriptor set to copy from.
+    ///
+    /// The default value is 0.
+    pub src_first_array_element: u32,
+
+    /// The binding number in the destination descriptor set to copy into.
+    ///
+    /// The default value is 0.
+    pub dst_binding: u32,
+
+    /// The first array element in the destination descriptor set to copy into.
+    ///
+    /// The default value is 0.
+    pub dst_first_array_element: u32,
+
+    /// The number of descriptors (array elements) to copy.
+    ///
+    /// The default value is 1.
+    pub descriptor_count: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl CopyDescriptorSet {
+    /// Returns a `CopyDescriptorSet` with the specified `src_set`.
+    #[inline]
+    pub fn new(src_set: Arc<dyn DescriptorSet>) -> Self {
+        Self {
+            src_set,
+            src_binding: 0,
+            src_first_array_element: 0,
+            dst_binding: 0,
+            dst_first_array_element: 0,
+            descriptor_count: 1,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn to_vulkan(&self, dst_set: ash::vk::DescriptorSet) -> ash::vk::CopyDescriptorSet {
+        ash::vk::CopyDescriptorSet {
+            src_set: self.src_set.inner().handle(),
+            src_binding: self.src_binding,
+            src_array_element: self.src_first_array_element,
+            dst_set,
+            dst_binding: self.dst_binding,
+            dst_array_element: self.dst_first_array_element,
+            descriptor_count: self.descriptor_count,
+            ..Default::default()
         }
     }
 }
@@ -404,6 +502,26 @@ pub(crate) enum DescriptorWriteInfo {
     Image(SmallVec<[ash::vk::DescriptorImageInfo; 1]>),
     Buffer(SmallVec<[ash::vk::DescriptorBufferInfo; 1]>),
     BufferView(SmallVec<[ash::vk::BufferView; 1]>),
+    InlineUniformBlock(Vec<u8>),
+}
+
+impl DescriptorWriteInfo {
+    /// Returns the structure that must be chained to the `p_next` of the write, for inline
+    /// uniform block writes. For other writes, an empty structure is returned, which is ignored.
+    ///
+    /// The returned structure points into `self`, so it must not outlive it.
+    pub(crate) fn to_vulkan_inline_uniform_block(
+        &self,
+    ) -> ash::vk::WriteDescriptorSetInlineUniformBlock {
+        match self {
+            Self::InlineUniformBlock(data) => ash::vk::WriteDescriptorSetInlineUniformBlock {
+                data_size: data.len() as u32,
+                p_data: data.as_ptr() as *const _,
+                ..Default::default()
+            },
+            _ => Default::default(),
+        }
+    }
 }
 
 pub(crate) fn check_descriptor_write<'a>(
@@ -419,6 +537,7 @@ pub(crate) fn check_descriptor_write<'a>(
             WriteDescriptorSetElements::ImageView(_) => "image_view",
             WriteDescriptorSetElements::ImageViewSampler(_) => "image_view_sampler",
             WriteDescriptorSetElements::Sampler(_) => "sampler",
+            WriteDescriptorSetElements::InlineUniformBlock(_) => "inline_uniform_block",
         }
     }
 
@@ -442,7 +561,12 @@ pub(crate) fn check_descriptor_write<'a>(
     let binding = write.binding();
     let elements = write.elements();
     let num_elements = elements.len();
-    debug_assert!(num_elements != 0);
+
+    // VUID-VkWriteDescriptorSet-descriptorCount-arraylength
+    // The other kinds of elements can't be empty.
+    if num_elements == 0 {
+        return Err(DescriptorSetUpdateError::InlineUniformBlockEmpty { binding });
+    }
 
     let descriptor_range_start = write.first_array_element();
     let descriptor_range_end = descriptor_range_start + num_elements;
@@ -995,11 +1119,170 @@ pub(crate) fn check_descriptor_write<'a>(
                 }
             }
         }
+
+        DescriptorType::InlineUniformBlock => {
+            if !matches!(elements, WriteDescriptorSetElements::InlineUniformBlock(_)) {
+                return Err(DescriptorSetUpdateError::IncompatibleElementType {
+                    binding,
+                    provided_element_type: provided_element_type(elements),
+                    allowed_element_types: &["inline_uniform_block"],
+                });
+            }
+
+            // VUID-VkWriteDescriptorSet-descriptorType-02219
+            // VUID-VkWriteDescriptorSet-descriptorType-02220
+            if descriptor_range_start % 4 != 0 || num_elements % 4 != 0 {
+                return Err(DescriptorSetUpdateError::InlineUniformBlockNotAligned {
+                    binding,
+                    offset: descriptor_range_start,
+                    size: num_elements,
+                });
+            }
+        }
     }
 
     Ok(layout_binding)
 }
 
+pub(crate) fn check_descriptor_copy<'a>(
+    copy: &CopyDescriptorSet,
+    dst_set: ash::vk::DescriptorSet,
+    dst_layout: &'a DescriptorSetLayout,
+    dst_variable_descriptor_count: u32,
+) -> Result<&'a DescriptorSetLayoutBinding, DescriptorSetUpdateError> {
+    let &CopyDescriptorSet {
+        ref src_set,
+        src_binding,
+        src_first_array_element,
+        dst_binding,
+        dst_first_array_element,
+        descriptor_count,
+        _ne: _,
+    } = copy;
+
+    // VUID-VkCopyDescriptorSet-commonparent
+    assert_eq!(src_set.device(), dst_layout.device());
+
+    let src_layout = src_set.layout();
+
+    // VUID-VkCopyDescriptorSet-srcBinding-00345
+    let src_layout_binding = match src_layout.bindings().get(&src_binding) {
+        Some(binding) => binding,
+        None => {
+            return Err(DescriptorSetUpdateError::InvalidBinding {
+                binding: src_binding,
+            })
+        }
+    };
+
+    // VUID-VkCopyDescriptorSet-dstBinding-00347
+    let dst_layout_binding = match dst_layout.bindings().get(&dst_binding) {
+        Some(binding) => binding,
+        None => {
+            return Err(DescriptorSetUpdateError::InvalidBinding {
+                binding: dst_binding,
+            })
+        }
+    };
+
+    // VUID-VkCopyDescriptorSet-srcArrayElement-00346
+    let src_max_descriptor_count = if src_layout_binding.variable_descriptor_count {
+        src_set.variable_descriptor_count()
+    } else {
+        src_layout_binding.descriptor_count
+    };
+
+    let src_end = src_first_array_element.checked_add(descriptor_count);
+
+    if src_end.map_or(true, |end| end > src_max_descriptor_count) {
+        return Err(DescriptorSetUpdateError::ArrayIndexOutOfBounds {
+            binding: src_binding,
+            available_count: src_max_descriptor_count,
+            written_count: src_end.unwrap_or(u32::MAX),
+        });
+    }
+
+    // VUID-VkCopyDescriptorSet-dstArrayElement-00348
+    let dst_max_descriptor_count = if dst_layout_binding.variable_descriptor_count {
+        dst_variable_descriptor_count
+    } else {
+        dst_layout_binding.descriptor_count
+    };
+
+    let dst_end = dst_first_array_element.checked_add(descriptor_count);
+
+    if dst_end.map_or(true, |end| end > dst_max_descriptor_count) {
+        return Err(DescriptorSetUpdateError::ArrayIndexOutOfBounds {
+            binding: dst_binding,
+            available_count: dst_max_descriptor_count,
+            written_count: dst_end.unwrap_or(u32::MAX),
+        });
+    }
+
+    // VUID-VkCopyDescriptorSet-dstBinding-02632
+    // The resources are tracked per binding, so bindings that only differ in whether they have
+    // immutable samplers can't be copied between either.
+    if src_layout_binding.descriptor_type != dst_layout_binding.descriptor_type
+        || src_layout_binding.immutable_samplers.is_empty()
+            != dst_layout_binding.immutable_samplers.is_empty()
+    {
+        return Err(DescriptorSetUpdateError::CopyIncompatibleBindings {
+            src_binding,
+            dst_binding,
+        });
+    }
+
+    // VUID-VkCopyDescriptorSet-dstBinding-02753
+    if dst_layout_binding.descriptor_type == DescriptorType::Sampler
+        && !dst_layout_binding.immutable_samplers.is_empty()
+    {
+        return Err(DescriptorSetUpdateError::CopyIncompatibleBindings {
+            src_binding,
+            dst_binding,
+        });
+    }
+
+    // VUID-VkCopyDescriptorSet-srcBinding-02223
+    // VUID-VkCopyDescriptorSet-dstBinding-02224
+    // VUID-VkCopyDescriptorSet-srcBinding-02225
+    if dst_layout_binding.descriptor_type == DescriptorType::InlineUniformBlock {
+        if src_first_array_element % 4 != 0 || descriptor_count % 4 != 0 {
+            return Err(DescriptorSetUpdateError::InlineUniformBlockNotAligned {
+                binding: src_binding,
+                offset: src_first_array_element,
+                size: descriptor_count,
+            });
+        }
+
+        if dst_first_array_element % 4 != 0 {
+            return Err(DescriptorSetUpdateError::InlineUniformBlockNotAligned {
+                binding: dst_binding,
+                offset: dst_first_array_element,
+                size: descriptor_count,
+            });
+        }
+    }
+
+    // VUID-VkCopyDescriptorSet-srcSet-00349
+    if src_set.inner().handle() == dst_set
+        && src_binding == dst_binding
+        && src_first_array_element < dst_first_array_element + descriptor_count
+        && dst_first_array_element < src_first_array_element + descriptor_count
+    {
+        return Err(DescriptorSetUpdateError::CopyOverlapping {
+            binding: dst_binding,
+        });
+    }
+
+    // VUID-VkCopyDescriptorSet-srcSet-01918
+    // VUID-VkCopyDescriptorSet-srcSet-01920
+    if src_layout.update_after_bind_pool() != dst_layout.update_after_bind_pool() {
+        return Err(DescriptorSetUpdateError::CopyUpdateAfterBindPoolMismatch);
+    }
+
+    Ok(dst_layout_binding)
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum DescriptorSetUpdateError {
     RequirementNotMet {
@@ -1019,6 +1302,17 @@ pub enum DescriptorSetUpdateError {
         written_count: u32,
     },
 
+    /// Tried to copy between two bindings whose descriptor types are different, or where only
+    /// one of them has immutable samplers, or into a sampler binding with immutable samplers.
+    CopyIncompatibleBindings { src_binding: u32, dst_binding: u32 },
+
+    /// Tried to copy between overlapping ranges of the same binding of the same descriptor set.
+    CopyOverlapping { binding: u32 },
+
+    /// Tried to copy between descriptor sets where only one of the layouts was created with
+    /// `update_after_bind_pool`.
+    CopyUpdateAfterBindPoolMismatch,
+
     /// Tried to write an image view with a 2D type and a 3D underlying image.
     ImageView2dFrom3d { binding: u32, index: u32 },
 
@@ -1053,6 +1347,21 @@ pub enum DescriptorSetUpdateError {
         allowed_element_types: &'static [&'static str],
     },
 
+    /// Tried to write an empty byte range to an inline uniform block.
+    InlineUniformBlockEmpty { binding: u32 },
+
+    /// Tried to write or copy a byte range of an inline uniform block whose offset or size was
+    /// not a multiple of 4.
+    InlineUniformBlockNotAligned {
+        binding: u32,
+        offset: u32,
+        size: u32,
+    },
+
+    /// Tried to update a binding of a descriptor set while the set may be in use by a command
+    /// buffer.
+    InUse { binding: u32 },
+
     /// Tried to write to a nonexistent binding.
     InvalidBinding { binding: u32 },
 
@@ -1108,6 +1417,26 @@ impl Display for DescriptorSetUpdateError {
                 available",
                 written_count, binding, available_count,
             ),
+            Self::CopyIncompatibleBindings {
+                src_binding,
+                dst_binding,
+            } => write!(
+                f,
+                "tried to copy descriptors from binding {} to binding {}, but the bindings are \
+                not compatible",
+                src_binding, dst_binding,
+            ),
+            Self::CopyOverlapping { binding } => write!(
+                f,
+                "tried to copy descriptors between overlapping ranges of binding {} of the same \
+                descriptor set",
+                binding,
+            ),
+            Self::CopyUpdateAfterBindPoolMismatch => write!(
+                f,
+                "tried to copy descriptors between descriptor sets where only one of the layouts \
+                was created with `update_after_bind_pool`",
+            ),
             Self::ImageView2dFrom3d { binding, index } => write!(
                 f,
                 "tried to write an image view to binding {} index {} with a 2D type and a 3D \
@@ -1169,6 +1498,27 @@ impl Display for DescriptorSetUpdateError {
                 Ok(())
             })
             .and_then(|_| write!(f, ") that can be bound to this buffer")),
+            Self::InlineUniformBlockEmpty { binding } => write!(
+                f,
+                "tried to write an empty byte range to the inline uniform block in binding {}",
+                binding,
+            ),
+            Self::InlineUniformBlockNotAligned {
+                binding,
+                offset,
+                size,
+            } => write!(
+                f,
+                "tried to update {} bytes at offset {} of the inline uniform block at binding {}, \
+                but the offset and size must both be a multiple of 4",
+                size, offset, binding,
+            ),
+            Self::InUse { binding } => write!(
+                f,
+                "tried to update binding {} of a descriptor set that may be in use by a command \
+                buffer",
+                binding,
+            ),
             Self::InvalidBinding { binding } => {
                 write!(f, "tried to write to a nonexistent binding {}", binding,)
             }
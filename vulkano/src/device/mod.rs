@@ -444,6 +444,10 @@ impl Device {
         Ok((device, queues_iter))
     }
 
+    pub(crate) fn id(&self) -> NonZeroU64 {
+        self.id
+    }
+
     /// Returns the Vulkan version supported by the device.
     ///
     /// This is the lower of the
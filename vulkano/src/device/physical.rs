@@ -12,10 +12,10 @@ use crate::{
     buffer::{ExternalBufferInfo, ExternalBufferProperties},
     cache::OnceCache,
     device::{properties::Properties, DeviceExtensions, Features, FeaturesFfi, PropertiesFfi},
-    format::{Format, FormatProperties},
+    format::{Format, FormatProperties, FormatTable},
     image::{
-        ImageAspects, ImageFormatInfo, ImageFormatProperties, ImageUsage, SparseImageFormatInfo,
-        SparseImageFormatProperties,
+        ImageAspects, ImageFormatInfo, ImageFormatProperties, ImageFormatQuery, ImageUsage,
+        SampleCounts, SparseImageFormatInfo, SparseImageFormatProperties,
     },
     instance::Instance,
     macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
@@ -31,6 +31,7 @@ use crate::{
     ExtensionProperties, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use bytemuck::cast_slice;
+use smallvec::{smallvec, SmallVec};
 use std::{
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
@@ -812,6 +813,84 @@ impl PhysicalDevice {
             })
     }
 
+    /// Returns the fragment shading rates that are supported by the physical device, in order
+    /// from largest to smallest fragment size.
+    ///
+    /// The [`khr_fragment_shading_rate`] extension must be supported by the physical device.
+    ///
+    /// [`khr_fragment_shading_rate`]: crate::device::DeviceExtensions::khr_fragment_shading_rate
+    #[inline]
+    pub fn fragment_shading_rates(
+        &self,
+    ) -> Result<Vec<PhysicalDeviceFragmentShadingRate>, PhysicalDeviceError> {
+        self.validate_fragment_shading_rates()?;
+
+        unsafe { Ok(self.fragment_shading_rates_unchecked()?) }
+    }
+
+    fn validate_fragment_shading_rates(&self) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().khr_fragment_shading_rate {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::fragment_shading_rates`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn fragment_shading_rates_unchecked(
+        &self,
+    ) -> Result<Vec<PhysicalDeviceFragmentShadingRate>, VulkanError> {
+        let fns = self.instance.fns();
+
+        loop {
+            let mut count = 0;
+            (fns.khr_fragment_shading_rate
+                .get_physical_device_fragment_shading_rates_khr)(
+                self.handle(),
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut fragment_shading_rates =
+                vec![ash::vk::PhysicalDeviceFragmentShadingRateKHR::default(); count as usize];
+            let result = (fns
+                .khr_fragment_shading_rate
+                .get_physical_device_fragment_shading_rates_khr)(
+                self.handle(),
+                &mut count,
+                fragment_shading_rates.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::INCOMPLETE => (),
+                ash::vk::Result::SUCCESS => {
+                    fragment_shading_rates.truncate(count as usize);
+
+                    return Ok(fragment_shading_rates
+                        .into_iter()
+                        .map(|fragment_shading_rate| PhysicalDeviceFragmentShadingRate {
+                            sample_counts: fragment_shading_rate.sample_counts.into(),
+                            fragment_size: [
+                                fragment_shading_rate.fragment_size.width,
+                                fragment_shading_rate.fragment_size.height,
+                            ],
+                        })
+                        .collect());
+                }
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+
     /// Retrieves the properties of a format when used by this physical device.
     ///
     /// The results of this function are cached, so that future calls with the same arguments
@@ -901,6 +980,111 @@ impl PhysicalDevice {
         })
     }
 
+    /// Returns the first format in `candidates` that can be used for images meeting the
+    /// requirements of `query`, along with the properties supported for such images.
+    ///
+    /// `candidates` is usually either a list of formats in order of preference, or
+    /// [`Format::all`] filtered by the attributes that the format must have. Formats that need a
+    /// device extension or API version that the physical device doesn't support are skipped, as
+    /// are formats for which `query` needs a feature that the physical device doesn't support.
+    /// `None` is returned if none of the candidates is suitable.
+    ///
+    /// If `query.external_memory_handle_types` is not empty, the returned properties are those for
+    /// the first handle type.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `query.usage` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vulkano::{
+    /// #     device::physical::PhysicalDevice,
+    /// #     format::Format,
+    /// #     image::{ImageFormatQuery, ImageUsage},
+    /// # };
+    /// # fn example(physical_device: &PhysicalDevice) {
+    /// let depth_stencil_format = physical_device
+    ///     .find_image_format(
+    ///         Format::all().iter().copied().filter(|format| {
+    ///             format.type_depth().is_some() && format.type_stencil().is_some()
+    ///         }),
+    ///         ImageFormatQuery {
+    ///             usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+    ///             ..Default::default()
+    ///         },
+    ///     )
+    ///     .unwrap()
+    ///     .map(|(format, _properties)| format);
+    /// # }
+    /// ```
+    pub fn find_image_format(
+        &self,
+        candidates: impl IntoIterator<Item = Format>,
+        query: ImageFormatQuery,
+    ) -> Result<Option<(Format, ImageFormatProperties)>, PhysicalDeviceError> {
+        let handle_types: SmallVec<[_; 2]> = if query.external_memory_handle_types.is_empty() {
+            smallvec![None]
+        } else {
+            query
+                .external_memory_handle_types
+                .into_iter()
+                .map(Some)
+                .collect()
+        };
+
+        'candidates: for format in candidates {
+            if format.validate_physical_device(self).is_err() {
+                continue;
+            }
+
+            let mut first_properties = None;
+
+            for &external_memory_handle_type in &handle_types {
+                let properties = match self.image_format_properties(ImageFormatInfo {
+                    flags: query.flags,
+                    format: Some(format),
+                    image_type: query.image_type,
+                    tiling: query.tiling,
+                    usage: query.usage,
+                    stencil_usage: query.stencil_usage,
+                    external_memory_handle_type,
+                    ..Default::default()
+                }) {
+                    Ok(Some(properties)) => properties,
+                    // The query doesn't apply to every candidate, for example `stencil_usage` to
+                    // formats with both a depth and a stencil aspect. Those are not suitable.
+                    Ok(None)
+                    | Err(PhysicalDeviceError::VulkanError(VulkanError::FormatNotSupported))
+                    | Err(PhysicalDeviceError::RequirementNotMet { .. }) => continue 'candidates,
+                    Err(err) => return Err(err),
+                };
+
+                let external_memory_supported = external_memory_handle_type.is_none()
+                    || properties.external_memory_properties.exportable
+                    || properties.external_memory_properties.importable;
+
+                if !(external_memory_supported && query.is_satisfied_by(&properties)) {
+                    continue 'candidates;
+                }
+
+                first_properties.get_or_insert(properties);
+            }
+
+            return Ok(first_properties.map(|properties| (format, properties)));
+        }
+
+        Ok(None)
+    }
+
+    /// Retrieves the properties of every format that this physical device supports, in a table
+    /// that can be printed.
+    #[inline]
+    pub fn format_table(&self) -> FormatTable {
+        FormatTable::new(self)
+    }
+
     /// Returns the properties supported for images with a given image configuration.
     ///
     /// `Some` is returned if the configuration is supported, `None` if it is not.
@@ -2571,6 +2755,17 @@ vulkan_enum! {
     MesaDozen = MESA_DOZEN,
 }
 
+/// A fragment shading rate that is supported by a physical device.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PhysicalDeviceFragmentShadingRate {
+    /// The sample counts that are supported for the fragment size.
+    pub sample_counts: SampleCounts,
+
+    /// The width and height of the fragment, in pixels.
+    pub fragment_size: [u32; 2],
+}
+
 /// Information provided about an active tool.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
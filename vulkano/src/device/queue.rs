@@ -11,22 +11,24 @@ use super::{Device, DeviceOwned};
 use crate::{
     buffer::BufferState,
     command_buffer::{
-        CommandBufferResourcesUsage, CommandBufferState, CommandBufferUsage, SemaphoreSubmitInfo,
-        SubmitInfo,
+        allocator::StandardCommandBufferAllocator, CommandBufferResourcesUsage, CommandBufferState,
+        CommandBufferUsage, LayoutTransitionCommandBuffer, PrimaryCommandBufferAbstract,
+        SemaphoreSubmitInfo, SubmitInfo,
     },
-    image::{sys::ImageState, ImageAccess},
+    image::{sys::ImageState, ImageAccess, ImageLayout},
     instance::debug::DebugUtilsLabel,
     macros::vulkan_bitflags,
     memory::{
         BindSparseInfo, SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
     },
+    range_map::RangeMap,
     swapchain::{PresentInfo, SwapchainPresentInfo},
     sync::{
         fence::{Fence, FenceState},
-        future::{AccessCheckError, FlushError, GpuFuture},
+        future::{AccessCheckError, AccessError, FlushError, GpuFuture},
         semaphore::SemaphoreState,
     },
-    OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
@@ -646,10 +648,20 @@ impl<'a> QueueGuard<'a> {
         queue: &Queue,
     ) -> Result<(), FlushError> {
         let submit_infos: SmallVec<[_; 4]> = smallvec![submit_info];
+        let layout_transition_command_buffers: SmallVec<[_; 4]>;
         let mut states = States::from_submit_infos(&submit_infos);
 
+        // The layouts that the images that track their layouts will be in when the next command
+        // buffer of the submission starts executing.
+        let mut image_layouts: HashMap<ash::vk::Image, RangeMap<DeviceSize, ImageLayout>> =
+            HashMap::default();
+        // For each command buffer, the layout transitions that must happen before it.
+        let mut layout_transitions: SmallVec<[Vec<_>; 4]> = SmallVec::new();
+
         for submit_info in &submit_infos {
             for command_buffer in &submit_info.command_buffers {
+                let mut command_buffer_layout_transitions = Vec::new();
+
                 let state = states
                     .command_buffers
                     .get(&command_buffer.handle())
@@ -715,13 +727,58 @@ impl<'a> QueueGuard<'a> {
 
                 for usage in images {
                     let state = states.images.get_mut(&usage.image.handle()).unwrap();
+                    let mut layouts = usage.image.tracks_layouts().then(|| {
+                        image_layouts
+                            .entry(usage.image.handle())
+                            .or_insert_with(|| state.layouts().collect())
+                    });
 
                     for (range, range_usage) in usage.ranges.iter() {
+                        let mut mutable = range_usage.mutable;
+                        let mut expected_layout = range_usage.expected_layout;
+
+                        // Instead of requiring the image to be in the layout that the command
+                        // buffer expects, transition it there first.
+                        if let Some(layouts) = layouts.as_mut() {
+                            if expected_layout != ImageLayout::Undefined {
+                                for (layout_range, &layout) in layouts.range(range) {
+                                    if layout == expected_layout {
+                                        continue;
+                                    }
+
+                                    // Images can't be transitioned to the `Preinitialized` layout.
+                                    if expected_layout == ImageLayout::Preinitialized {
+                                        return Err(FlushError::ResourceAccessError {
+                                            error: AccessError::UnexpectedImageLayout {
+                                                allowed: layout,
+                                                requested: expected_layout,
+                                            },
+                                            use_ref: range_usage.first_use,
+                                        });
+                                    }
+
+                                    command_buffer_layout_transitions.push((
+                                        usage.image.clone(),
+                                        layout_range.start.max(range.start)
+                                            ..layout_range.end.min(range.end),
+                                        layout,
+                                        expected_layout,
+                                    ));
+
+                                    // A layout transition is a write.
+                                    mutable = true;
+                                }
+                            }
+
+                            layouts.insert(range.clone(), range_usage.final_layout);
+                            expected_layout = ImageLayout::Undefined;
+                        }
+
                         match future.check_image_access(
                             &usage.image,
                             range.clone(),
-                            range_usage.mutable,
-                            range_usage.expected_layout,
+                            mutable,
+                            expected_layout,
                             queue,
                         ) {
                             Err(AccessCheckError::Denied(error)) => {
@@ -731,11 +788,10 @@ impl<'a> QueueGuard<'a> {
                                 });
                             }
                             Err(AccessCheckError::Unknown) => {
-                                let result = if range_usage.mutable {
-                                    state
-                                        .check_gpu_write(range.clone(), range_usage.expected_layout)
+                                let result = if mutable {
+                                    state.check_gpu_write(range.clone(), expected_layout)
                                 } else {
-                                    state.check_gpu_read(range.clone(), range_usage.expected_layout)
+                                    state.check_gpu_read(range.clone(), expected_layout)
                                 };
 
                                 if let Err(error) = result {
@@ -749,9 +805,61 @@ impl<'a> QueueGuard<'a> {
                         };
                     }
                 }
+
+                layout_transitions.push(command_buffer_layout_transitions);
             }
         }
 
+        let allocator = self.state.command_buffer_allocator.get_or_insert_with(|| {
+            StandardCommandBufferAllocator::new(self.queue.device.clone(), Default::default())
+        });
+        layout_transition_command_buffers = layout_transitions
+            .into_iter()
+            .map(|transitions| {
+                (!transitions.is_empty())
+                    .then(|| {
+                        LayoutTransitionCommandBuffer::new(
+                            allocator,
+                            self.queue.queue_family_index,
+                            transitions,
+                        )
+                    })
+                    .transpose()
+            })
+            .collect::<Result<_, _>>()
+            .map_err(FlushError::OomError)?;
+
+        for command_buffer in layout_transition_command_buffers.iter().flatten() {
+            states
+                .command_buffers
+                .insert(command_buffer.handle(), command_buffer.state());
+        }
+
+        // Put the layout transitions in front of the command buffers that need them.
+        let mut layout_transition_command_buffers_iter = layout_transition_command_buffers.iter();
+        let submit_infos: SmallVec<[_; 4]> = submit_infos
+            .iter()
+            .map(|submit_info| SubmitInfo {
+                command_buffers: submit_info
+                    .command_buffers
+                    .iter()
+                    .flat_map(|command_buffer| {
+                        let layout_transition_command_buffer =
+                            layout_transition_command_buffers_iter
+                                .next()
+                                .unwrap()
+                                .clone()
+                                .map(|command_buffer| command_buffer as _);
+
+                        layout_transition_command_buffer
+                            .into_iter()
+                            .chain([command_buffer.clone()])
+                    })
+                    .collect(),
+                ..submit_info.clone()
+            })
+            .collect();
+
         Ok(self.submit_unchecked_locked(
             &submit_infos,
             fence.as_ref().map(|fence| {
@@ -1268,6 +1376,9 @@ impl<'a> QueueGuard<'a> {
 #[derive(Debug, Default)]
 struct QueueState {
     operations: VecDeque<(QueueOperation, Option<Arc<Fence>>)>,
+    // Allocates the command buffers that transition images to the layouts that the submitted
+    // command buffers expect.
+    command_buffer_allocator: Option<StandardCommandBufferAllocator>,
 }
 
 impl QueueState {
@@ -0,0 +1,1077 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! The Adaptive Scalable Texture Compression (ASTC) format.
+//!
+//! Every block is 128 bits, but the number of texels it covers depends on the format. Only 2D
+//! blocks are supported. Decoded texels are in row-major order.
+//!
+//! Blocks that are invalid, or that use HDR features in an LDR format, decode to the error
+//! color: opaque magenta for LDR formats, and NaN for HDR formats.
+
+use super::BitReader;
+use half::f16;
+
+/// The supported ranges of integer sequence encoding, as the number of trits or quints (1 for
+/// neither, 3 for trits, 5 for quints) and the number of plain bits per value.
+const ISE_RANGES: [(u32, u32); 21] = [
+    (1, 1),
+    (3, 0),
+    (1, 2),
+    (5, 0),
+    (3, 1),
+    (1, 3),
+    (5, 1),
+    (3, 2),
+    (1, 4),
+    (5, 2),
+    (3, 3),
+    (1, 5),
+    (5, 3),
+    (3, 4),
+    (1, 6),
+    (5, 4),
+    (3, 5),
+    (1, 7),
+    (5, 5),
+    (3, 6),
+    (1, 8),
+];
+
+/// The lowest range that color endpoints may be encoded with, which has 6 levels.
+const MIN_COLOR_RANGE: usize = 4;
+
+pub(super) fn decode(
+    block: &[u8],
+    block_extent: [u32; 2],
+    hdr: bool,
+    srgb: bool,
+    out: &mut [[f32; 4]],
+) {
+    let bits = u128::from_le_bytes(block[..16].try_into().unwrap());
+
+    if decode_block(bits, block_extent, hdr, srgb, out).is_none() {
+        out.fill(if hdr {
+            [f32::NAN; 4]
+        } else {
+            [1.0, 0.0, 1.0, 1.0]
+        });
+    }
+}
+
+/// Decodes a block, or returns `None` if the block is invalid.
+fn decode_block(
+    bits: u128,
+    [block_width, block_height]: [u32; 2],
+    hdr: bool,
+    srgb: bool,
+    out: &mut [[f32; 4]],
+) -> Option<()> {
+    if bits & 0x1ff == 0x1fc {
+        return decode_void_extent(bits, hdr, srgb, out);
+    }
+
+    let BlockMode {
+        grid: [grid_width, grid_height],
+        dual_plane,
+        weight_range,
+    } = decode_block_mode(field(bits, 0, 11))?;
+
+    if grid_width > block_width || grid_height > block_height {
+        return None;
+    }
+
+    let planes = dual_plane as usize + 1;
+    let weight_count = (grid_width * grid_height) as usize * planes;
+    let weight_bits = ise_bit_count(weight_count, weight_range);
+
+    if weight_count > 64 || !(24..=96).contains(&weight_bits) {
+        return None;
+    }
+
+    let partitions = field(bits, 11, 2) as usize + 1;
+
+    if partitions == 4 && dual_plane {
+        return None;
+    }
+
+    // Bits below the weights are used, from the top down, for the part of the endpoint modes that
+    // does not fit in the mode field, and for the color component selector of dual-plane blocks.
+    let mut below_weights = 128 - weight_bits;
+    let mut endpoint_modes = [0; 4];
+    let partition_seed;
+    let color_start;
+
+    if partitions == 1 {
+        partition_seed = 0;
+        endpoint_modes[0] = field(bits, 13, 4);
+        color_start = 17;
+    } else {
+        partition_seed = field(bits, 13, 10);
+        color_start = 29;
+        let mode_field = field(bits, 23, 6);
+
+        if mode_field & 3 == 0 {
+            endpoint_modes = [mode_field >> 2; 4];
+        } else {
+            let extra_bits = 3 * partitions as u32 - 4;
+            below_weights = below_weights.checked_sub(extra_bits)?;
+            let value = mode_field >> 2 | field(bits, below_weights, extra_bits) << 4;
+            let base_class = (mode_field & 3) - 1;
+
+            for (i, mode) in endpoint_modes.iter_mut().take(partitions).enumerate() {
+                let class = base_class + (value >> i & 1);
+                *mode = class << 2 | value >> (partitions + 2 * i) & 3;
+            }
+        }
+    }
+
+    let plane2_component = if dual_plane {
+        below_weights = below_weights.checked_sub(2)?;
+        Some(field(bits, below_weights, 2) as usize)
+    } else {
+        None
+    };
+
+    let color_count: usize = endpoint_modes[..partitions]
+        .iter()
+        .map(|mode| ((mode >> 2) as usize + 1) * 2)
+        .sum();
+    let color_bits = below_weights.checked_sub(color_start)?;
+
+    if color_count > 18 {
+        return None;
+    }
+
+    let color_range = (0..ISE_RANGES.len())
+        .rev()
+        .find(|&range| ise_bit_count(color_count, range) <= color_bits)
+        .filter(|&range| range >= MIN_COLOR_RANGE)?;
+    let color_values: Vec<i32> = decode_ise(bits, color_start, color_count, color_range)
+        .into_iter()
+        .map(|value| unquantize_color(value, color_range))
+        .collect();
+
+    let mut endpoints = [Endpoints::default(); 4];
+    let mut color_values = color_values.as_slice();
+
+    for (endpoints, &mode) in endpoints.iter_mut().zip(&endpoint_modes).take(partitions) {
+        let (values, rest) = color_values.split_at(((mode >> 2) as usize + 1) * 2);
+        *endpoints = decode_endpoints(mode, values, srgb);
+        color_values = rest;
+
+        if !hdr && endpoints.hdr.contains(&true) {
+            return None;
+        }
+    }
+
+    let weights: Vec<u32> = decode_ise(bits.reverse_bits(), 0, weight_count, weight_range)
+        .into_iter()
+        .map(|value| unquantize_weight(value, weight_range))
+        .collect();
+
+    let small_block = block_width * block_height < 31;
+    let ds = (1024 + block_width / 2) / (block_width - 1);
+    let dt = (1024 + block_height / 2) / (block_height - 1);
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        let (x, y) = (i as u32 % block_width, i as u32 / block_width);
+        let partition = if partitions == 1 {
+            0
+        } else {
+            select_partition(partition_seed, x, y, partitions as u32, small_block)
+        };
+        let Endpoints {
+            colors,
+            hdr: hdr_channels,
+        } = &endpoints[partition];
+
+        // Bilinearly interpolate the weight grid to the texel position.
+        let gs = (ds * x * (grid_width - 1) + 32) >> 6;
+        let gt = (dt * y * (grid_height - 1) + 32) >> 6;
+        let (js, fs) = (gs >> 4, gs & 15);
+        let (jt, ft) = (gt >> 4, gt & 15);
+        let w11 = (fs * ft + 8) >> 4;
+        let factors = [16 - fs - ft + w11, fs - w11, ft - w11, w11];
+
+        let texel_weight = |plane: usize| {
+            let grid_weight = |gx: u32, gy: u32| {
+                let index = gy.min(grid_height - 1) * grid_width + gx.min(grid_width - 1);
+                weights[index as usize * planes + plane]
+            };
+            let neighbors = [
+                grid_weight(js, jt),
+                grid_weight(js + 1, jt),
+                grid_weight(js, jt + 1),
+                grid_weight(js + 1, jt + 1),
+            ];
+
+            let sum: u32 = neighbors.iter().zip(factors).map(|(w, f)| w * f).sum();
+
+            (sum + 8) >> 4
+        };
+        let texel_weights = [texel_weight(0), texel_weight(planes - 1)];
+
+        for channel in 0..4 {
+            let weight = texel_weights[(plane2_component == Some(channel)) as usize] as i32;
+            let value =
+                (colors[0][channel] * (64 - weight) + colors[1][channel] * weight + 32) >> 6;
+
+            texel[channel] = if hdr_channels[channel] {
+                f16::from_bits(lns_to_f16(value as u32)).to_f32()
+            } else if srgb {
+                (value >> 8) as f32 / 255.0
+            } else {
+                value as f32 / 65535.0
+            };
+        }
+    }
+
+    Some(())
+}
+
+/// Decodes a block that has a single color.
+fn decode_void_extent(bits: u128, hdr: bool, srgb: bool, out: &mut [[f32; 4]]) -> Option<()> {
+    let hdr_color = bits >> 9 & 1 != 0;
+
+    if hdr_color && !hdr {
+        return None;
+    }
+
+    let color: [u32; 4] = std::array::from_fn(|i| field(bits, 64 + 16 * i as u32, 16));
+    let texel = color.map(|c| {
+        if hdr_color {
+            f16::from_bits(c as u16).to_f32()
+        } else if srgb {
+            (c >> 8) as f32 / 255.0
+        } else {
+            c as f32 / 65535.0
+        }
+    });
+    out.fill(texel);
+
+    Some(())
+}
+
+struct BlockMode {
+    grid: [u32; 2],
+    dual_plane: bool,
+    weight_range: usize,
+}
+
+fn decode_block_mode(mode: u32) -> Option<BlockMode> {
+    let bit = |i: u32| mode >> i & 1;
+    let a = mode >> 5 & 3;
+    let mut precision_high = bit(9);
+    let mut dual_plane = bit(10);
+    let base_range;
+
+    let grid = if mode & 3 != 0 {
+        base_range = bit(4) | (mode & 3) << 1;
+        let b = mode >> 7 & 3;
+
+        match mode >> 2 & 3 {
+            0 => [b + 4, a + 2],
+            1 => [b + 8, a + 2],
+            2 => [a + 2, b + 8],
+            _ if bit(8) != 0 => [(b & 1) + 2, a + 2],
+            _ => [a + 2, (b & 1) + 6],
+        }
+    } else {
+        base_range = bit(4) | (mode >> 2 & 3) << 1;
+
+        if mode >> 2 & 3 == 0 {
+            return None;
+        }
+
+        let b = mode >> 9 & 3;
+
+        match mode >> 7 & 3 {
+            0 => [12, a + 2],
+            1 => [a + 2, 12],
+            2 => {
+                precision_high = 0;
+                dual_plane = 0;
+                [a + 6, b + 6]
+            }
+            _ => match a {
+                0 => [6, 10],
+                1 => [10, 6],
+                _ => return None,
+            },
+        }
+    };
+
+    Some(BlockMode {
+        grid,
+        dual_plane: dual_plane != 0,
+        weight_range: (base_range - 2 + 6 * precision_high) as usize,
+    })
+}
+
+fn ise_bit_count(count: usize, range: usize) -> u32 {
+    let (kind, bits) = ISE_RANGES[range];
+    let count = count as u32;
+
+    bits * count
+        + match kind {
+            3 => (8 * count + 4) / 5,
+            5 => (7 * count + 2) / 3,
+            _ => 0,
+        }
+}
+
+/// Decodes `count` values that are encoded with integer sequence encoding, starting at bit
+/// `start`.
+fn decode_ise(bits: u128, start: u32, count: usize, range: usize) -> Vec<u32> {
+    let (kind, value_bits) = ISE_RANGES[range];
+    let len = ise_bit_count(count, range);
+
+    // Bits beyond the end of the sequence are read as 0.
+    let mask = if len >= 128 {
+        u128::MAX
+    } else {
+        (1 << len) - 1
+    };
+    let mut reader = BitReader::from_bits(bits >> start & mask);
+    let mut values = Vec::with_capacity(count + 4);
+
+    while values.len() < count {
+        match kind {
+            3 => {
+                let mut plain = [0; 5];
+                let mut packed = 0;
+
+                for (value, (shift, len)) in
+                    plain
+                        .iter_mut()
+                        .zip([(0, 2), (2, 2), (4, 1), (5, 2), (7, 1)])
+                {
+                    *value = reader.read(value_bits);
+                    packed |= reader.read(len) << shift;
+                }
+
+                let trits = decode_trits(packed);
+                values.extend((0..5).map(|i| trits[i] << value_bits | plain[i]));
+            }
+            5 => {
+                let mut plain = [0; 3];
+                let mut packed = 0;
+
+                for (value, (shift, len)) in plain.iter_mut().zip([(0, 3), (3, 2), (5, 2)]) {
+                    *value = reader.read(value_bits);
+                    packed |= reader.read(len) << shift;
+                }
+
+                let quints = decode_quints(packed);
+                values.extend((0..3).map(|i| quints[i] << value_bits | plain[i]));
+            }
+            _ => values.push(reader.read(value_bits)),
+        }
+    }
+
+    values.truncate(count);
+    values
+}
+
+fn decode_trits(t: u32) -> [u32; 5] {
+    let bit = |value: u32, i: u32| value >> i & 1;
+    let (c, t3, t4);
+
+    if t >> 2 & 7 == 7 {
+        c = (t >> 5 & 7) << 2 | t & 3;
+        t4 = 2;
+        t3 = 2;
+    } else {
+        c = t & 0x1f;
+
+        if t >> 5 & 3 == 3 {
+            t4 = 2;
+            t3 = bit(t, 7);
+        } else {
+            t4 = bit(t, 7);
+            t3 = t >> 5 & 3;
+        }
+    }
+
+    let (t0, t1, t2);
+
+    if c & 3 == 3 {
+        t2 = 2;
+        t1 = bit(c, 4);
+        t0 = bit(c, 3) << 1 | bit(c, 2) & !bit(c, 3) & 1;
+    } else if c >> 2 & 3 == 3 {
+        t2 = 2;
+        t1 = 2;
+        t0 = c & 3;
+    } else {
+        t2 = bit(c, 4);
+        t1 = c >> 2 & 3;
+        t0 = bit(c, 1) << 1 | bit(c, 0) & !bit(c, 1) & 1;
+    }
+
+    [t0, t1, t2, t3, t4]
+}
+
+fn decode_quints(q: u32) -> [u32; 3] {
+    let bit = |value: u32, i: u32| value >> i & 1;
+
+    if q >> 1 & 3 == 3 && q >> 5 & 3 == 0 {
+        let q2 = bit(q, 0) << 2 | (bit(q, 4) & !bit(q, 0) & 1) << 1 | bit(q, 3) & !bit(q, 0) & 1;
+        return [4, 4, q2];
+    }
+
+    let (c, q2) = if q >> 1 & 3 == 3 {
+        ((q >> 3 & 3) << 3 | (!q >> 5 & 3) << 1 | bit(q, 0), 4)
+    } else {
+        (q & 0x1f, q >> 5 & 3)
+    };
+
+    if c & 7 == 5 {
+        [c >> 3 & 3, 4, q2]
+    } else {
+        [c & 7, c >> 3 & 3, q2]
+    }
+}
+
+/// Unquantizes a color endpoint value to the range 0..=255.
+fn unquantize_color(value: u32, range: usize) -> i32 {
+    let (kind, bits) = ISE_RANGES[range];
+
+    if kind == 1 {
+        return replicate(value, bits, 8) as i32;
+    }
+
+    let digit = value >> bits;
+    let plain = value & ((1 << bits) - 1);
+    let bit = |i: u32| plain >> i & 1;
+    let (b, c, d, e, f) = (bit(1), bit(2), bit(3), bit(4), bit(5));
+
+    let (scramble, multiplier) = match (kind, bits) {
+        (3, 1) => (0, 204),
+        (5, 1) => (0, 113),
+        (3, 2) => (b << 8 | b << 4 | b << 2 | b << 1, 93),
+        (5, 2) => (b << 8 | b << 3 | b << 2, 54),
+        (3, 3) => (c << 8 | b << 7 | c << 3 | b << 2 | c << 1 | b, 44),
+        (5, 3) => (c << 8 | b << 7 | c << 2 | b << 1 | c, 26),
+        (3, 4) => (d << 8 | c << 7 | b << 6 | d << 2 | c << 1 | b, 22),
+        (5, 4) => (d << 8 | c << 7 | b << 6 | d << 1 | c, 13),
+        (3, 5) => (e << 8 | d << 7 | c << 6 | b << 5 | e << 1 | d, 11),
+        (5, 5) => (e << 8 | d << 7 | c << 6 | b << 5 | e, 6),
+        (3, 6) => (f << 8 | e << 7 | d << 6 | c << 5 | b << 4 | f, 5),
+        _ => unreachable!(),
+    };
+
+    unscramble(digit, multiplier, scramble, bit(0) * 0x1ff, 0x80)
+}
+
+/// Unquantizes a weight to the range 0..=64.
+fn unquantize_weight(value: u32, range: usize) -> u32 {
+    let (kind, bits) = ISE_RANGES[range];
+
+    let weight = match (kind, bits) {
+        (1, _) => replicate(value, bits, 6),
+        (3, 0) => return [0, 32, 64][value as usize],
+        (5, 0) => return [0, 16, 32, 48, 64][value as usize],
+        _ => {
+            let digit = value >> bits;
+            let bit = |i: u32| value >> i & 1;
+            let (b, c) = (bit(1), bit(2));
+
+            let (scramble, multiplier) = match (kind, bits) {
+                (3, 1) => (0, 50),
+                (5, 1) => (0, 28),
+                (3, 2) => (b << 6 | b << 2 | b, 23),
+                (5, 2) => (b << 6 | b << 1, 13),
+                (3, 3) => (c << 6 | b << 5 | c << 1 | b, 11),
+                _ => unreachable!(),
+            };
+
+            unscramble(digit, multiplier, scramble, bit(0) * 0x7f, 0x20) as u32
+        }
+    };
+
+    if weight > 32 {
+        weight + 1
+    } else {
+        weight
+    }
+}
+
+fn unscramble(digit: u32, multiplier: u32, scramble: u32, a: u32, top_bit: u32) -> i32 {
+    let t = (digit * multiplier + scramble) ^ a;
+    ((a & top_bit) | t >> 2) as i32
+}
+
+/// Repeats the bits of `value` until they fill `to_bits` bits.
+fn replicate(value: u32, bits: u32, to_bits: u32) -> u32 {
+    let mut result = 0;
+    let mut filled = 0;
+
+    while filled < to_bits {
+        result = result << bits | value;
+        filled += bits;
+    }
+
+    result >> (filled - to_bits)
+}
+
+/// Returns the partition that a texel belongs to, for a given partition seed.
+fn select_partition(seed: u32, x: u32, y: u32, partitions: u32, small_block: bool) -> usize {
+    let (x, y) = if small_block {
+        (x << 1, y << 1)
+    } else {
+        (x, y)
+    };
+    let seed = seed + (partitions - 1) * 1024;
+    let rnum = hash52(seed);
+
+    let mut seeds: [u32; 8] = std::array::from_fn(|i| {
+        let s = rnum >> (4 * i) & 0xf;
+        s * s
+    });
+
+    let (sh1, sh2) = if seed & 1 != 0 {
+        (
+            if seed & 2 != 0 { 4 } else { 5 },
+            if partitions == 3 { 6 } else { 5 },
+        )
+    } else {
+        (
+            if partitions == 3 { 6 } else { 5 },
+            if seed & 2 != 0 { 4 } else { 5 },
+        )
+    };
+
+    for (i, seed) in seeds.iter_mut().enumerate() {
+        *seed >>= if i % 2 == 0 { sh1 } else { sh2 };
+    }
+
+    // The seeds for the z coordinate are not needed for 2D blocks.
+    let lines = [
+        seeds[0] * x + seeds[1] * y + (rnum >> 14),
+        seeds[2] * x + seeds[3] * y + (rnum >> 10),
+        seeds[4] * x + seeds[5] * y + (rnum >> 6),
+        seeds[6] * x + seeds[7] * y + (rnum >> 2),
+    ]
+    .map(|value| value & 0x3f);
+
+    let [a, b, c, d] = std::array::from_fn(|i| if i < partitions as usize { lines[i] } else { 0 });
+
+    if a >= b && a >= c && a >= d {
+        0
+    } else if b >= c && b >= d {
+        1
+    } else if c >= d {
+        2
+    } else {
+        3
+    }
+}
+
+fn hash52(mut p: u32) -> u32 {
+    p ^= p >> 15;
+    p = p.wrapping_sub(p << 17);
+    p = p.wrapping_add(p << 7);
+    p = p.wrapping_add(p << 4);
+    p ^= p >> 5;
+    p = p.wrapping_add(p << 16);
+    p ^= p >> 7;
+    p ^= p >> 3;
+    p ^= p << 6;
+    p ^= p >> 17;
+    p
+}
+
+/// The two endpoint colors of a partition. LDR channels are expanded to 16-bit unsigned
+/// normalized values, while HDR channels hold 16-bit logarithmic values.
+#[derive(Clone, Copy, Default)]
+struct Endpoints {
+    colors: [[i32; 4]; 2],
+    hdr: [bool; 4],
+}
+
+fn decode_endpoints(mode: u32, v: &[i32], srgb: bool) -> Endpoints {
+    let (colors, hdr_rgb, hdr_alpha) = match mode {
+        0 => (
+            [[v[0], v[0], v[0], 255], [v[1], v[1], v[1], 255]],
+            false,
+            false,
+        ),
+        1 => {
+            let l0 = v[0] >> 2 | v[1] & 0xc0;
+            let l1 = (l0 + (v[1] & 0x3f)).min(255);
+            ([[l0, l0, l0, 255], [l1, l1, l1, 255]], false, false)
+        }
+        2 => (hdr_luminance_large_range(v), true, true),
+        3 => (hdr_luminance_small_range(v), true, true),
+        4 => (
+            [[v[0], v[0], v[0], v[2]], [v[1], v[1], v[1], v[3]]],
+            false,
+            false,
+        ),
+        5 => {
+            let (o0, b0) = bit_transfer_signed(v[1], v[0]);
+            let (o1, b1) = bit_transfer_signed(v[3], v[2]);
+            let e1 = [b0 + o0, b0 + o0, b0 + o0, b1 + o1].map(|c| c.clamp(0, 255));
+            ([[b0, b0, b0, b1], e1], false, false)
+        }
+        6 => {
+            let e0 = [
+                (v[0] * v[3]) >> 8,
+                (v[1] * v[3]) >> 8,
+                (v[2] * v[3]) >> 8,
+                255,
+            ];
+            ([e0, [v[0], v[1], v[2], 255]], false, false)
+        }
+        7 => (hdr_rgb_base_scale(v), true, true),
+        8 | 12 => {
+            let a = if mode == 12 { [v[6], v[7]] } else { [255, 255] };
+
+            if v[1] + v[3] + v[5] >= v[0] + v[2] + v[4] {
+                (
+                    [[v[0], v[2], v[4], a[0]], [v[1], v[3], v[5], a[1]]],
+                    false,
+                    false,
+                )
+            } else {
+                (
+                    [
+                        blue_contract([v[1], v[3], v[5], a[1]]),
+                        blue_contract([v[0], v[2], v[4], a[0]]),
+                    ],
+                    false,
+                    false,
+                )
+            }
+        }
+        9 | 13 => {
+            let mut base = [0; 4];
+            let mut offset = [0; 4];
+
+            for i in 0..4 {
+                (offset[i], base[i]) = match (i, mode) {
+                    (3, 9) => (0, 255),
+                    _ => bit_transfer_signed(v[2 * i + 1], v[2 * i]),
+                };
+            }
+
+            let sum: [i32; 4] = std::array::from_fn(|i| base[i] + offset[i]);
+            let colors = if offset[0] + offset[1] + offset[2] >= 0 {
+                [base, sum]
+            } else {
+                [blue_contract(sum), blue_contract(base)]
+            };
+
+            (colors.map(|e| e.map(|c| c.clamp(0, 255))), false, false)
+        }
+        10 => {
+            let e0 = [
+                (v[0] * v[3]) >> 8,
+                (v[1] * v[3]) >> 8,
+                (v[2] * v[3]) >> 8,
+                v[4],
+            ];
+            ([e0, [v[0], v[1], v[2], v[5]]], false, false)
+        }
+        11 => (hdr_rgb_direct(v), true, true),
+        14 => {
+            let [mut e0, mut e1] = hdr_rgb_direct(v);
+            (e0[3], e1[3]) = (v[6], v[7]);
+            ([e0, e1], true, false)
+        }
+        _ => {
+            let [mut e0, mut e1] = hdr_rgb_direct(v);
+            (e0[3], e1[3]) = hdr_alpha(v[6], v[7]);
+            ([e0, e1], true, true)
+        }
+    };
+
+    let hdr = [hdr_rgb, hdr_rgb, hdr_rgb, hdr_alpha];
+    let colors = colors.map(|color| {
+        std::array::from_fn(|i| match (hdr[i], srgb && i < 3) {
+            (true, _) => color[i],
+            (false, true) => color[i] << 8 | 0x80,
+            (false, false) => color[i] << 8 | color[i],
+        })
+    });
+
+    Endpoints { colors, hdr }
+}
+
+/// Moves the top bit of `a` into `b`, and turns the rest of `a` into a signed 6-bit offset.
+fn bit_transfer_signed(a: i32, b: i32) -> (i32, i32) {
+    let b = b >> 1 | a & 0x80;
+    let a = a >> 1 & 0x3f;
+    let a = if a & 0x20 != 0 { a - 0x40 } else { a };
+
+    (a, b)
+}
+
+fn blue_contract([r, g, b, a]: [i32; 4]) -> [i32; 4] {
+    [(r + b) >> 1, (g + b) >> 1, b, a]
+}
+
+/// The alpha value of HDR endpoints whose alpha channel is not encoded, which is 1.0.
+const HDR_ALPHA_ONE: i32 = 0x7800;
+
+fn hdr_luminance_large_range(v: &[i32]) -> [[i32; 4]; 2] {
+    let (y0, y1) = if v[1] >= v[0] {
+        (v[0] << 4, v[1] << 4)
+    } else {
+        ((v[1] << 4) + 8, (v[0] << 4) - 8)
+    };
+
+    [y0, y1].map(|y| [y << 4, y << 4, y << 4, HDR_ALPHA_ONE])
+}
+
+fn hdr_luminance_small_range(v: &[i32]) -> [[i32; 4]; 2] {
+    let (y0, d) = if v[0] & 0x80 != 0 {
+        ((v[1] & 0xe0) << 4 | (v[0] & 0x7f) << 2, (v[1] & 0x1f) << 2)
+    } else {
+        ((v[1] & 0xf0) << 4 | (v[0] & 0x7f) << 1, (v[1] & 0xf) << 1)
+    };
+    let y1 = (y0 + d).min(0xfff);
+
+    [y0, y1].map(|y| [y << 4, y << 4, y << 4, HDR_ALPHA_ONE])
+}
+
+fn hdr_rgb_base_scale(v: &[i32]) -> [[i32; 4]; 2] {
+    let mode_value = (v[0] & 0xc0) >> 6 | (v[1] & 0x80) >> 5 | (v[2] & 0x80) >> 4;
+    let (major, mode) = if mode_value & 0xc != 0xc {
+        (mode_value >> 2, mode_value & 3)
+    } else if mode_value != 0xf {
+        (mode_value & 3, 4)
+    } else {
+        (0, 5)
+    };
+
+    let mut red = v[0] & 0x3f;
+    let mut green = v[1] & 0x1f;
+    let mut blue = v[2] & 0x1f;
+    let mut scale = v[3] & 0x1f;
+
+    let bit0 = v[1] >> 6 & 1;
+    let bit1 = v[1] >> 5 & 1;
+    let bit2 = v[2] >> 6 & 1;
+    let bit3 = v[2] >> 5 & 1;
+    let bit4 = v[3] >> 7 & 1;
+    let bit5 = v[3] >> 6 & 1;
+    let bit6 = v[3] >> 5 & 1;
+
+    // Which of the variable bits are present depends on the mode.
+    let one_hot = 1 << mode;
+    let has = |mask: i32| one_hot & mask != 0;
+
+    if has(0x30) {
+        green |= bit0 << 6;
+        blue |= bit2 << 6;
+    }
+    if has(0x3a) {
+        green |= bit1 << 5;
+        blue |= bit3 << 5;
+    }
+    if has(0x3d) {
+        scale |= bit6 << 5;
+    }
+    if has(0x2d) {
+        scale |= bit5 << 6;
+    }
+    if has(0x04) {
+        scale |= bit4 << 7;
+    }
+    if has(0x3b) {
+        red |= bit4 << 6;
+    }
+    if has(0x04) {
+        red |= bit3 << 6;
+    }
+    if has(0x10) {
+        red |= bit5 << 7;
+    }
+    if has(0x0f) {
+        red |= bit2 << 7;
+    }
+    if has(0x05) {
+        red |= bit1 << 8;
+    }
+    if has(0x0a) {
+        red |= bit0 << 8;
+    }
+    if has(0x05) {
+        red |= bit0 << 9;
+    }
+    if has(0x02) {
+        red |= bit6 << 9;
+    }
+    if has(0x01) {
+        red |= bit3 << 10;
+    }
+    if has(0x02) {
+        red |= bit5 << 10;
+    }
+
+    let shift = [1, 1, 2, 3, 4, 5][mode as usize];
+    red <<= shift;
+    green <<= shift;
+    blue <<= shift;
+    scale <<= shift;
+
+    if mode != 5 {
+        green = red - green;
+        blue = red - blue;
+    }
+
+    let mut color = [red, green, blue];
+
+    if major != 0 {
+        color.swap(0, major as usize);
+    }
+
+    let [r1, g1, b1] = color.map(|c| c.max(0));
+    let [r0, g0, b0] = color.map(|c| (c - scale).max(0));
+
+    [
+        [r0 << 4, g0 << 4, b0 << 4, HDR_ALPHA_ONE],
+        [r1 << 4, g1 << 4, b1 << 4, HDR_ALPHA_ONE],
+    ]
+}
+
+fn hdr_rgb_direct(v: &[i32]) -> [[i32; 4]; 2] {
+    let mode_value = (v[1] & 0x80) >> 7 | (v[2] & 0x80) >> 6 | (v[3] & 0x80) >> 5;
+    let major = (v[4] & 0x80) >> 7 | (v[5] & 0x80) >> 6;
+
+    if major == 3 {
+        return [
+            [v[0] << 8, v[2] << 8, (v[4] & 0x7f) << 9, HDR_ALPHA_ONE],
+            [v[1] << 8, v[3] << 8, (v[5] & 0x7f) << 9, HDR_ALPHA_ONE],
+        ];
+    }
+
+    let mut a = v[0] | (v[1] & 0x40) << 2;
+    let mut b0 = v[2] & 0x3f;
+    let mut b1 = v[3] & 0x3f;
+    let mut c = v[1] & 0x3f;
+    let mut d0 = v[4] & 0x7f;
+    let mut d1 = v[5] & 0x7f;
+
+    let d_bits = [7, 6, 7, 6, 5, 6, 5, 6][mode_value as usize];
+
+    let bit0 = v[2] >> 6 & 1;
+    let bit1 = v[3] >> 6 & 1;
+    let bit2 = v[4] >> 6 & 1;
+    let bit3 = v[5] >> 6 & 1;
+    let bit4 = v[4] >> 5 & 1;
+    let bit5 = v[5] >> 5 & 1;
+
+    // Which of the variable bits are present depends on the mode.
+    let one_hot = 1 << mode_value;
+    let has = |mask: i32| one_hot & mask != 0;
+
+    if has(0xa4) {
+        a |= bit0 << 9;
+    }
+    if has(0x8) {
+        a |= bit2 << 9;
+    }
+    if has(0x50) {
+        a |= bit4 << 9;
+        a |= bit5 << 10;
+    }
+    if has(0xa0) {
+        a |= bit1 << 10;
+    }
+    if has(0xc0) {
+        a |= bit2 << 11;
+    }
+    if has(0x4) {
+        c |= bit1 << 6;
+    }
+    if has(0xe8) {
+        c |= bit3 << 6;
+    }
+    if has(0x20) {
+        c |= bit2 << 7;
+    }
+    if has(0x5b) {
+        b0 |= bit0 << 6;
+        b1 |= bit1 << 6;
+    }
+    if has(0x12) {
+        b0 |= bit2 << 7;
+        b1 |= bit3 << 7;
+    }
+    if has(0xaf) {
+        d0 |= bit4 << 5;
+        d1 |= bit5 << 5;
+    }
+    if has(0x5) {
+        d0 |= bit2 << 6;
+        d1 |= bit3 << 6;
+    }
+
+    let sign_extend = |value: i32| (value << (32 - d_bits)) >> (32 - d_bits);
+    let (d0, d1) = (sign_extend(d0), sign_extend(d1));
+
+    let shift = (mode_value >> 1) ^ 3;
+    let [a, b0, b1, c, d0, d1] = [a, b0, b1, c, d0, d1].map(|value| value << shift);
+
+    let mut colors = [
+        [a - c, a - b0 - c - d0, a - b1 - c - d1],
+        [a, a - b0, a - b1],
+    ]
+    .map(|color| color.map(|c| c.clamp(0, 0xfff)));
+
+    if major != 0 {
+        for color in &mut colors {
+            color.swap(0, major as usize);
+        }
+    }
+
+    colors.map(|[r, g, b]| [r << 4, g << 4, b << 4, HDR_ALPHA_ONE])
+}
+
+fn hdr_alpha(v6: i32, v7: i32) -> (i32, i32) {
+    let selector = (v6 >> 7 & 1) | (v7 >> 6 & 2);
+    let (mut a0, mut a1) = (v6 & 0x7f, v7 & 0x7f);
+
+    if selector == 3 {
+        a0 <<= 5;
+        a1 <<= 5;
+    } else {
+        a0 |= (a1 << (selector + 1)) & 0x780;
+        a1 &= 0x3f >> selector;
+        a1 ^= 32 >> selector;
+        a1 -= 32 >> selector;
+        a0 <<= 4 - selector;
+        a1 <<= 4 - selector;
+        a1 = (a1 + a0).clamp(0, 0xfff);
+    }
+
+    (a0 << 4, a1 << 4)
+}
+
+/// Converts an interpolated logarithmic HDR value to the bits of a half-precision float.
+fn lns_to_f16(value: u32) -> u16 {
+    let mantissa = value & 0x7ff;
+    let exponent = value >> 11;
+
+    let mantissa = if mantissa < 512 {
+        3 * mantissa
+    } else if mantissa < 1536 {
+        4 * mantissa - 512
+    } else {
+        5 * mantissa - 2048
+    };
+
+    (exponent << 10 | mantissa >> 3).min(0x7bff) as u16
+}
+
+fn field(bits: u128, offset: u32, len: u32) -> u32 {
+    (bits >> offset) as u32 & ((1 << len) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode, decode_ise, decode_quints, decode_trits, unquantize_color, unquantize_weight,
+        ISE_RANGES,
+    };
+
+    fn set_bits(block: &mut u128, offset: u32, len: u32, value: u32) {
+        *block |= ((value & ((1 << len) - 1)) as u128) << offset;
+    }
+
+    #[test]
+    fn void_extent() {
+        // An LDR block with all extent coordinates set to 1, which means the whole block.
+        let mut block = 0x1fc | 0b11 << 10 | ((1 << 52) - 1) << 12;
+        set_bits(&mut block, 64, 16, 0x8000);
+        set_bits(&mut block, 112, 16, 0xffff);
+        let mut out = [[0.0; 4]; 16];
+        decode(&block.to_le_bytes(), [4, 4], false, false, &mut out);
+
+        let red = 0x8000 as f32 / 65535.0;
+        assert!(out.iter().all(|&texel| texel == [red, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn error_color() {
+        // An HDR void-extent block in an LDR format.
+        let block = 0xffcu128;
+        let mut out = [[0.0; 4]; 16];
+        decode(&block.to_le_bytes(), [4, 4], false, false, &mut out);
+        assert!(out.iter().all(|&texel| texel == [1.0, 0.0, 1.0, 1.0]));
+
+        // A reserved block mode.
+        let block = 0u128;
+        decode(&block.to_le_bytes(), [4, 4], true, false, &mut out);
+        assert!(out.iter().all(|texel| texel.iter().all(|c| c.is_nan())));
+    }
+
+    #[test]
+    fn single_partition() {
+        // A 4x4 weight grid with 2-bit weights, and a single partition with direct RGB endpoints
+        // that go from black to white.
+        let mut block = 0;
+        set_bits(&mut block, 0, 11, 0x42);
+        set_bits(&mut block, 13, 4, 8);
+
+        for (i, value) in [0, 255, 0, 255, 0, 255].into_iter().enumerate() {
+            set_bits(&mut block, 17 + 8 * i as u32, 8, value);
+        }
+
+        // Weights are stored in reverse bit order from the top of the block.
+        for i in 0..16u32 {
+            let weight = (i % 4).reverse_bits() >> 30;
+            set_bits(&mut block, 128 - 2 * (i + 1), 2, weight);
+        }
+
+        let mut out = [[0.0; 4]; 16];
+        decode(&block.to_le_bytes(), [4, 4], false, false, &mut out);
+
+        for (i, texel) in out.iter().enumerate() {
+            let expected = [0, 21504, 44031, 65535][i % 4] as f32 / 65535.0;
+            assert_eq!(*texel, [expected, expected, expected, 1.0]);
+        }
+    }
+
+    #[test]
+    fn integer_sequence() {
+        assert_eq!(decode_trits(0b0011_1001), [1, 2, 1, 1, 0]);
+        assert_eq!(decode_quints(0b101_1100), [4, 3, 2]);
+        assert_eq!(decode_ise(0xa3, 0, 2, 8), [3, 10]);
+    }
+
+    #[test]
+    fn unquantize() {
+        // Every range must unquantize to evenly spread, distinct values that include both ends.
+        for (range, &(kind, bits)) in ISE_RANGES.iter().enumerate() {
+            let levels = kind << bits;
+
+            if range >= 4 {
+                let mut colors: Vec<_> = (0..levels).map(|v| unquantize_color(v, range)).collect();
+                colors.sort_unstable();
+                colors.dedup();
+                assert_eq!(colors.len(), levels as usize);
+                assert_eq!((colors[0], colors[colors.len() - 1]), (0, 255));
+            }
+
+            if range < 12 {
+                let mut weights: Vec<_> =
+                    (0..levels).map(|v| unquantize_weight(v, range)).collect();
+                weights.sort_unstable();
+                weights.dedup();
+                assert_eq!(weights.len(), levels as usize);
+                assert_eq!((weights[0], weights[weights.len() - 1]), (0, 64));
+            }
+        }
+    }
+}
@@ -0,0 +1,954 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! The BC1 to BC7 block compression formats, also known as S3TC, RGTC and BPTC.
+//!
+//! All blocks are 4x4 texels. Decoded texels are in row-major order.
+
+use super::BitReader;
+use half::f16;
+
+/// How the endpoints of a BC1 color block select the color mode.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Bc1Mode {
+    /// BC1 without alpha: the three-color mode is selected when the first endpoint is not greater
+    /// than the second, and index 3 is opaque black.
+    Opaque,
+    /// BC1 with alpha: as `Opaque`, but index 3 is transparent black.
+    PunchThrough,
+    /// BC2 and BC3: the four-color mode is always used.
+    FourColor,
+}
+
+/// Decodes the color part of a BC1, BC2 or BC3 block.
+pub(super) fn decode_bc1(block: &[u8], mode: Bc1Mode, out: &mut [[f32; 4]]) {
+    let color0 = u16::from_le_bytes([block[0], block[1]]);
+    let color1 = u16::from_le_bytes([block[2], block[3]]);
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+
+    let palette = bc1_palette(color0, color1, mode);
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        *texel = palette[(indices >> (i * 2)) as usize & 3];
+    }
+}
+
+fn is_four_color(color0: u16, color1: u16, mode: Bc1Mode) -> bool {
+    color0 > color1 || mode == Bc1Mode::FourColor
+}
+
+fn bc1_palette(color0: u16, color1: u16, mode: Bc1Mode) -> [[f32; 4]; 4] {
+    let c0 = unpack_565(color0);
+    let c1 = unpack_565(color1);
+    let mix = |w0: f32, w1: f32, d: f32| {
+        let mut c = [0.0, 0.0, 0.0, 1.0];
+
+        for i in 0..3 {
+            c[i] = (w0 * c0[i] + w1 * c1[i]) / d / 255.0;
+        }
+
+        c
+    };
+
+    if is_four_color(color0, color1, mode) {
+        [
+            mix(1.0, 0.0, 1.0),
+            mix(0.0, 1.0, 1.0),
+            mix(2.0, 1.0, 3.0),
+            mix(1.0, 2.0, 3.0),
+        ]
+    } else {
+        [
+            mix(1.0, 0.0, 1.0),
+            mix(0.0, 1.0, 1.0),
+            mix(1.0, 1.0, 2.0),
+            if mode == Bc1Mode::PunchThrough {
+                [0.0; 4]
+            } else {
+                [0.0, 0.0, 0.0, 1.0]
+            },
+        ]
+    }
+}
+
+fn unpack_565(color: u16) -> [f32; 3] {
+    let r = (color >> 11) & 31;
+    let g = (color >> 5) & 63;
+    let b = color & 31;
+
+    [
+        ((r << 3) | (r >> 2)) as f32,
+        ((g << 2) | (g >> 4)) as f32,
+        ((b << 3) | (b >> 2)) as f32,
+    ]
+}
+
+/// Decodes the explicit alpha part of a BC2 block into the alpha channel of `out`.
+pub(super) fn decode_bc2_alpha(block: &[u8], out: &mut [[f32; 4]]) {
+    let alpha = u64::from_le_bytes(block[0..8].try_into().unwrap());
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        texel[3] = ((alpha >> (i * 4)) & 15) as f32 / 15.0;
+    }
+}
+
+/// Decodes a BC4 block, or one channel of a BC3 or BC5 block, into `channel` of `out`.
+pub(super) fn decode_bc4(block: &[u8], signed: bool, channel: usize, out: &mut [[f32; 4]]) {
+    let palette = bc4_palette(block[0], block[1], signed);
+    let indices = u64::from_le_bytes([
+        block[2], block[3], block[4], block[5], block[6], block[7], 0, 0,
+    ]);
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        texel[channel] = palette[(indices >> (i * 3)) as usize & 7];
+    }
+}
+
+fn bc4_palette(endpoint0: u8, endpoint1: u8, signed: bool) -> [f32; 8] {
+    let (e0, e1, min, max) = if signed {
+        (
+            (endpoint0 as i8).max(-127) as f32 / 127.0,
+            (endpoint1 as i8).max(-127) as f32 / 127.0,
+            -1.0,
+            1.0,
+        )
+    } else {
+        (endpoint0 as f32 / 255.0, endpoint1 as f32 / 255.0, 0.0, 1.0)
+    };
+
+    let greater = if signed {
+        endpoint0 as i8 > endpoint1 as i8
+    } else {
+        endpoint0 > endpoint1
+    };
+
+    let mut palette = [e0, e1, 0.0, 0.0, 0.0, 0.0, min, max];
+
+    if greater {
+        for i in 1..7 {
+            palette[i + 1] = ((7 - i) as f32 * e0 + i as f32 * e1) / 7.0;
+        }
+    } else {
+        for i in 1..5 {
+            palette[i + 1] = ((5 - i) as f32 * e0 + i as f32 * e1) / 5.0;
+        }
+    }
+
+    palette
+}
+
+const WEIGHTS_2: [u32; 4] = [0, 21, 43, 64];
+const WEIGHTS_3: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+const WEIGHTS_4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+fn weights(bits: u32) -> &'static [u32] {
+    match bits {
+        2 => &WEIGHTS_2,
+        3 => &WEIGHTS_3,
+        4 => &WEIGHTS_4,
+        _ => unreachable!(),
+    }
+}
+
+/// The subset of each texel for the two-subset partitions, one bit per texel.
+const PARTITIONS_2: [u16; 64] = [
+    0xcccc, 0x8888, 0xeeee, 0xecc8, 0xc880, 0xfeec, 0xfec8, 0xec80, 0xc800, 0xffec, 0xfe80, 0xe800,
+    0xffe8, 0xff00, 0xfff0, 0xf000, 0xf710, 0x008e, 0x7100, 0x08ce, 0x008c, 0x7310, 0x3100, 0x8cce,
+    0x088c, 0x3110, 0x6666, 0x366c, 0x17e8, 0x0ff0, 0x718e, 0x399c, 0xaaaa, 0xf0f0, 0x5a5a, 0x33cc,
+    0x3c3c, 0x55aa, 0x9696, 0xa55a, 0x73ce, 0x13c8, 0x324c, 0x3bdc, 0x6996, 0xc33c, 0x9966, 0x0660,
+    0x0272, 0x04e4, 0x4e40, 0x2720, 0xc936, 0x936c, 0x39c6, 0x639c, 0x9336, 0x9cc6, 0x817e, 0xe718,
+    0xccf0, 0x0fcc, 0x7744, 0xee22,
+];
+
+/// The subset of each texel for the three-subset partitions.
+const PARTITIONS_3: [[u8; 16]; 64] = [
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 1, 2, 2, 2, 2],
+    [0, 0, 0, 1, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 2, 0, 0, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [0, 2, 2, 2, 0, 0, 2, 2, 0, 0, 1, 1, 0, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2],
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 2, 2],
+    [0, 0, 2, 2, 0, 0, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2],
+    [0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2],
+    [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2],
+    [0, 1, 1, 2, 0, 1, 1, 2, 0, 1, 1, 2, 0, 1, 1, 2],
+    [0, 1, 2, 2, 0, 1, 2, 2, 0, 1, 2, 2, 0, 1, 2, 2],
+    [0, 0, 1, 1, 0, 1, 1, 2, 1, 1, 2, 2, 1, 2, 2, 2],
+    [0, 0, 1, 1, 2, 0, 0, 1, 2, 2, 0, 0, 2, 2, 2, 0],
+    [0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 2, 1, 1, 2, 2],
+    [0, 1, 1, 1, 0, 0, 1, 1, 2, 0, 0, 1, 2, 2, 0, 0],
+    [0, 0, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2],
+    [0, 0, 2, 2, 0, 0, 2, 2, 0, 0, 2, 2, 1, 1, 1, 1],
+    [0, 1, 1, 1, 0, 1, 1, 1, 0, 2, 2, 2, 0, 2, 2, 2],
+    [0, 0, 0, 1, 0, 0, 0, 1, 2, 2, 2, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 2, 2, 0, 1, 2, 2],
+    [0, 0, 0, 0, 1, 1, 0, 0, 2, 2, 1, 0, 2, 2, 1, 0],
+    [0, 1, 2, 2, 0, 1, 2, 2, 0, 0, 1, 1, 0, 0, 0, 0],
+    [0, 0, 1, 2, 0, 0, 1, 2, 1, 1, 2, 2, 2, 2, 2, 2],
+    [0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1, 0, 1, 1, 0],
+    [0, 0, 0, 0, 0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1],
+    [0, 0, 2, 2, 1, 1, 0, 2, 1, 1, 0, 2, 0, 0, 2, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 2, 0, 0, 2, 2, 2, 2, 2],
+    [0, 0, 1, 1, 0, 1, 2, 2, 0, 1, 2, 2, 0, 0, 1, 1],
+    [0, 0, 0, 0, 2, 0, 0, 0, 2, 2, 1, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 2, 2, 2],
+    [0, 2, 2, 2, 0, 0, 2, 2, 0, 0, 1, 2, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 2, 0, 0, 2, 2, 0, 2, 2, 2],
+    [0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0],
+    [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0],
+    [0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0],
+    [0, 1, 2, 0, 2, 0, 1, 2, 1, 2, 0, 1, 0, 1, 2, 0],
+    [0, 0, 1, 1, 2, 2, 0, 0, 1, 1, 2, 2, 0, 0, 1, 1],
+    [0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0, 1, 1],
+    [0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 2, 1, 2, 1, 2, 1],
+    [0, 0, 2, 2, 1, 1, 2, 2, 0, 0, 2, 2, 1, 1, 2, 2],
+    [0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 1, 1],
+    [0, 2, 2, 0, 1, 2, 2, 1, 0, 2, 2, 0, 1, 2, 2, 1],
+    [0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 0, 1, 0, 1],
+    [0, 0, 0, 0, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1],
+    [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2],
+    [0, 2, 2, 2, 0, 1, 1, 1, 0, 2, 2, 2, 0, 1, 1, 1],
+    [0, 0, 0, 2, 1, 1, 1, 2, 0, 0, 0, 2, 1, 1, 1, 2],
+    [0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2],
+    [0, 2, 2, 2, 0, 1, 1, 1, 0, 1, 1, 1, 0, 2, 2, 2],
+    [0, 0, 0, 2, 1, 1, 1, 2, 1, 1, 1, 2, 0, 0, 0, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 1, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 2, 2],
+    [0, 0, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2, 0, 0, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2],
+    [0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1],
+    [0, 2, 2, 2, 1, 2, 2, 2, 0, 2, 2, 2, 1, 2, 2, 2],
+    [0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 1, 1, 1, 2, 0, 1, 1, 2, 2, 0, 1, 2, 2, 2, 0],
+];
+
+/// The anchor texel of the second subset of the two-subset partitions.
+const ANCHORS_2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8,
+    2, 2, 8, 8, 2, 2, 15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6, 6, 2, 6, 8, 15, 15, 2,
+    2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+/// The anchor texel of the second subset of the three-subset partitions.
+const ANCHORS_3_1: [u8; 64] = [
+    3, 3, 15, 15, 8, 3, 15, 15, 8, 8, 6, 6, 6, 5, 3, 3, 3, 3, 8, 15, 3, 3, 6, 10, 5, 8, 8, 6, 8, 5,
+    15, 15, 8, 15, 3, 5, 6, 10, 8, 15, 15, 3, 15, 5, 15, 15, 15, 15, 3, 15, 5, 5, 5, 8, 5, 10, 5,
+    10, 8, 13, 15, 12, 3, 3,
+];
+
+/// The anchor texel of the third subset of the three-subset partitions.
+const ANCHORS_3_2: [u8; 64] = [
+    15, 8, 8, 3, 15, 15, 3, 8, 15, 15, 15, 15, 15, 15, 15, 8, 15, 8, 15, 3, 15, 8, 15, 8, 3, 15, 6,
+    10, 15, 15, 10, 8, 15, 3, 15, 10, 10, 8, 9, 10, 6, 15, 8, 15, 3, 6, 6, 8, 15, 3, 15, 15, 15,
+    15, 15, 15, 15, 15, 15, 15, 3, 15, 15, 8,
+];
+
+fn subset(subset_count: usize, partition: usize, texel: usize) -> usize {
+    match subset_count {
+        1 => 0,
+        2 => (PARTITIONS_2[partition] >> texel) as usize & 1,
+        3 => PARTITIONS_3[partition][texel] as usize,
+        _ => unreachable!(),
+    }
+}
+
+fn is_anchor(subset_count: usize, partition: usize, texel: usize) -> bool {
+    texel == 0
+        || match subset_count {
+            1 => false,
+            2 => texel == ANCHORS_2[partition] as usize,
+            3 => {
+                texel == ANCHORS_3_1[partition] as usize || texel == ANCHORS_3_2[partition] as usize
+            }
+            _ => unreachable!(),
+        }
+}
+
+struct Bc7Mode {
+    subset_count: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    index_selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    endpoint_p_bits: bool,
+    shared_p_bits: bool,
+    index_bits: u32,
+    index_bits_2: u32,
+}
+
+#[rustfmt::skip]
+const BC7_MODES: [Bc7Mode; 8] = [
+    Bc7Mode { subset_count: 3, partition_bits: 4, rotation_bits: 0, index_selection_bits: 0, color_bits: 4, alpha_bits: 0, endpoint_p_bits: true, shared_p_bits: false, index_bits: 3, index_bits_2: 0 },
+    Bc7Mode { subset_count: 2, partition_bits: 6, rotation_bits: 0, index_selection_bits: 0, color_bits: 6, alpha_bits: 0, endpoint_p_bits: false, shared_p_bits: true, index_bits: 3, index_bits_2: 0 },
+    Bc7Mode { subset_count: 3, partition_bits: 6, rotation_bits: 0, index_selection_bits: 0, color_bits: 5, alpha_bits: 0, endpoint_p_bits: false, shared_p_bits: false, index_bits: 2, index_bits_2: 0 },
+    Bc7Mode { subset_count: 2, partition_bits: 6, rotation_bits: 0, index_selection_bits: 0, color_bits: 7, alpha_bits: 0, endpoint_p_bits: true, shared_p_bits: false, index_bits: 2, index_bits_2: 0 },
+    Bc7Mode { subset_count: 1, partition_bits: 0, rotation_bits: 2, index_selection_bits: 1, color_bits: 5, alpha_bits: 6, endpoint_p_bits: false, shared_p_bits: false, index_bits: 2, index_bits_2: 3 },
+    Bc7Mode { subset_count: 1, partition_bits: 0, rotation_bits: 2, index_selection_bits: 0, color_bits: 7, alpha_bits: 8, endpoint_p_bits: false, shared_p_bits: false, index_bits: 2, index_bits_2: 2 },
+    Bc7Mode { subset_count: 1, partition_bits: 0, rotation_bits: 0, index_selection_bits: 0, color_bits: 7, alpha_bits: 7, endpoint_p_bits: true, shared_p_bits: false, index_bits: 4, index_bits_2: 0 },
+    Bc7Mode { subset_count: 2, partition_bits: 6, rotation_bits: 0, index_selection_bits: 0, color_bits: 5, alpha_bits: 5, endpoint_p_bits: true, shared_p_bits: false, index_bits: 2, index_bits_2: 0 },
+];
+
+/// Decodes a BC7 block.
+pub(super) fn decode_bc7(block: &[u8], out: &mut [[f32; 4]]) {
+    let mut reader = BitReader::new(block);
+    let mode = block[0].trailing_zeros() as usize;
+
+    // Reserved modes decode to transparent black.
+    if mode >= 8 {
+        out.fill([0.0; 4]);
+        return;
+    }
+
+    reader.skip(mode as u32 + 1);
+    let info = &BC7_MODES[mode];
+    let partition = reader.read(info.partition_bits) as usize;
+    let rotation = reader.read(info.rotation_bits);
+    let index_selection = reader.read(info.index_selection_bits);
+
+    // [subset][endpoint][channel]
+    let mut endpoints = [[[0u32; 4]; 2]; 3];
+    let subsets = 0..info.subset_count;
+
+    for channel in 0..4 {
+        let bits = if channel < 3 {
+            info.color_bits
+        } else {
+            info.alpha_bits
+        };
+
+        for subset in subsets.clone() {
+            for endpoint in &mut endpoints[subset] {
+                endpoint[channel] = reader.read(bits);
+            }
+        }
+    }
+
+    let mut p_bits = [[0u32; 2]; 3];
+    let has_p_bits = info.endpoint_p_bits || info.shared_p_bits;
+
+    if info.endpoint_p_bits {
+        for subset in subsets.clone() {
+            for p_bit in &mut p_bits[subset] {
+                *p_bit = reader.read(1);
+            }
+        }
+    } else if info.shared_p_bits {
+        for subset in subsets.clone() {
+            let p_bit = reader.read(1);
+            p_bits[subset] = [p_bit; 2];
+        }
+    }
+
+    for subset in subsets {
+        for (endpoint, &p_bit) in endpoints[subset].iter_mut().zip(&p_bits[subset]) {
+            for (channel, value) in endpoint.iter_mut().enumerate() {
+                let mut bits = if channel < 3 {
+                    info.color_bits
+                } else {
+                    info.alpha_bits
+                };
+
+                if bits == 0 {
+                    *value = 255;
+                    continue;
+                }
+
+                if has_p_bits {
+                    *value = (*value << 1) | p_bit;
+                    bits += 1;
+                }
+
+                *value = (*value << (8 - bits)) | (*value >> (2 * bits - 8));
+            }
+        }
+    }
+
+    let mut indices = [0u32; 16];
+    let mut indices_2 = [0u32; 16];
+
+    for (texel, index) in indices.iter_mut().enumerate() {
+        let anchor = is_anchor(info.subset_count, partition, texel);
+        *index = reader.read(info.index_bits - anchor as u32);
+    }
+
+    if info.index_bits_2 != 0 {
+        for (texel, index) in indices_2.iter_mut().enumerate() {
+            *index = reader.read(info.index_bits_2 - (texel == 0) as u32);
+        }
+    }
+
+    for (texel, out) in out.iter_mut().enumerate() {
+        let [endpoint0, endpoint1] = endpoints[subset(info.subset_count, partition, texel)];
+
+        let (color_weight, alpha_weight) = if info.index_bits_2 == 0 {
+            let weight = weights(info.index_bits)[indices[texel] as usize];
+            (weight, weight)
+        } else {
+            let primary = weights(info.index_bits)[indices[texel] as usize];
+            let secondary = weights(info.index_bits_2)[indices_2[texel] as usize];
+
+            if index_selection == 0 {
+                (primary, secondary)
+            } else {
+                (secondary, primary)
+            }
+        };
+
+        let mut color = [0u32; 4];
+
+        for channel in 0..4 {
+            let weight = if channel < 3 {
+                color_weight
+            } else {
+                alpha_weight
+            };
+            color[channel] =
+                ((64 - weight) * endpoint0[channel] + weight * endpoint1[channel] + 32) >> 6;
+        }
+
+        match rotation {
+            1 => color.swap(0, 3),
+            2 => color.swap(1, 3),
+            3 => color.swap(2, 3),
+            _ => (),
+        }
+
+        *out = color.map(|c| c as f32 / 255.0);
+    }
+}
+
+struct Bc6hMode {
+    transformed: bool,
+    endpoint_bits: u32,
+    delta_bits: [u32; 3],
+    /// The fields in the order that they appear in the block, as `(value, a, b)`, for the bits
+    /// `value[a:b]` in the notation of the specification. Values 0 to 11 are the R, G and B
+    /// components of the four endpoints.
+    fields: &'static [(u8, u8, u8)],
+}
+
+const R0: u8 = 0;
+const G0: u8 = 1;
+const B0: u8 = 2;
+const R1: u8 = 3;
+const G1: u8 = 4;
+const B1: u8 = 5;
+const R2: u8 = 6;
+const G2: u8 = 7;
+const B2: u8 = 8;
+const R3: u8 = 9;
+const G3: u8 = 10;
+const B3: u8 = 11;
+
+#[rustfmt::skip]
+const BC6H_MODES: [Bc6hMode; 14] = [
+    Bc6hMode { transformed: true, endpoint_bits: 10, delta_bits: [5, 5, 5], fields: &[
+        (G2, 4, 4), (B2, 4, 4), (B3, 4, 4), (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 4, 0),
+        (G3, 4, 4), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0), (G3, 3, 0), (B1, 4, 0), (B3, 1, 1),
+        (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 7, delta_bits: [6, 6, 6], fields: &[
+        (G2, 5, 5), (G3, 4, 4), (G3, 5, 5), (R0, 6, 0), (B3, 0, 0), (B3, 1, 1), (B2, 4, 4),
+        (G0, 6, 0), (B2, 5, 5), (B3, 2, 2), (G2, 4, 4), (B0, 6, 0), (B3, 3, 3), (B3, 5, 5),
+        (B3, 4, 4), (R1, 5, 0), (G2, 3, 0), (G1, 5, 0), (G3, 3, 0), (B1, 5, 0), (B2, 3, 0),
+        (R2, 5, 0), (R3, 5, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [5, 4, 4], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 4, 0), (R0, 10, 10), (G2, 3, 0), (G1, 3, 0),
+        (G0, 10, 10), (B3, 0, 0), (G3, 3, 0), (B1, 3, 0), (B0, 10, 10), (B3, 1, 1), (B2, 3, 0),
+        (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [4, 5, 4], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 3, 0), (R0, 10, 10), (G3, 4, 4), (G2, 3, 0),
+        (G1, 4, 0), (G0, 10, 10), (G3, 3, 0), (B1, 3, 0), (B0, 10, 10), (B3, 1, 1), (B2, 3, 0),
+        (R2, 3, 0), (B3, 0, 0), (B3, 2, 2), (R3, 3, 0), (G2, 4, 4), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [4, 4, 5], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 3, 0), (R0, 10, 10), (B2, 4, 4), (G2, 3, 0),
+        (G1, 3, 0), (G0, 10, 10), (B3, 0, 0), (G3, 3, 0), (B1, 4, 0), (B0, 10, 10), (B2, 3, 0),
+        (R2, 3, 0), (B3, 1, 1), (B3, 2, 2), (R3, 3, 0), (B3, 4, 4), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 9, delta_bits: [5, 5, 5], fields: &[
+        (R0, 8, 0), (B2, 4, 4), (G0, 8, 0), (G2, 4, 4), (B0, 8, 0), (B3, 4, 4), (R1, 4, 0),
+        (G3, 4, 4), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0), (G3, 3, 0), (B1, 4, 0), (B3, 1, 1),
+        (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 8, delta_bits: [6, 5, 5], fields: &[
+        (R0, 7, 0), (G3, 4, 4), (B2, 4, 4), (G0, 7, 0), (B3, 2, 2), (G2, 4, 4), (B0, 7, 0),
+        (B3, 3, 3), (B3, 4, 4), (R1, 5, 0), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0), (G3, 3, 0),
+        (B1, 4, 0), (B3, 1, 1), (B2, 3, 0), (R2, 5, 0), (R3, 5, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 8, delta_bits: [5, 6, 5], fields: &[
+        (R0, 7, 0), (B3, 0, 0), (B2, 4, 4), (G0, 7, 0), (G2, 5, 5), (G2, 4, 4), (B0, 7, 0),
+        (G3, 5, 5), (B3, 4, 4), (R1, 4, 0), (G3, 4, 4), (G2, 3, 0), (G1, 5, 0), (G3, 3, 0),
+        (B1, 4, 0), (B3, 1, 1), (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 8, delta_bits: [5, 5, 6], fields: &[
+        (R0, 7, 0), (B3, 1, 1), (B2, 4, 4), (G0, 7, 0), (B2, 5, 5), (G2, 4, 4), (B0, 7, 0),
+        (B3, 5, 5), (B3, 4, 4), (R1, 4, 0), (G3, 4, 4), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0),
+        (G3, 3, 0), (B1, 5, 0), (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+    ] },
+    Bc6hMode { transformed: false, endpoint_bits: 6, delta_bits: [6, 6, 6], fields: &[
+        (R0, 5, 0), (G3, 4, 4), (B3, 0, 0), (B3, 1, 1), (B2, 4, 4), (G0, 5, 0), (G2, 5, 5),
+        (B2, 5, 5), (B3, 2, 2), (G2, 4, 4), (B0, 5, 0), (G3, 5, 5), (B3, 3, 3), (B3, 5, 5),
+        (B3, 4, 4), (R1, 5, 0), (G2, 3, 0), (G1, 5, 0), (G3, 3, 0), (B1, 5, 0), (B2, 3, 0),
+        (R2, 5, 0), (R3, 5, 0),
+    ] },
+    Bc6hMode { transformed: false, endpoint_bits: 10, delta_bits: [10, 10, 10], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 9, 0), (G1, 9, 0), (B1, 9, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [9, 9, 9], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 8, 0), (R0, 10, 10), (G1, 8, 0), (G0, 10, 10),
+        (B1, 8, 0), (B0, 10, 10),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 12, delta_bits: [8, 8, 8], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 7, 0), (R0, 10, 11), (G1, 7, 0), (G0, 10, 11),
+        (B1, 7, 0), (B0, 10, 11),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 16, delta_bits: [4, 4, 4], fields: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 3, 0), (R0, 10, 15), (G1, 3, 0), (G0, 10, 15),
+        (B1, 3, 0), (B0, 10, 15),
+    ] },
+];
+
+/// Decodes a BC6H block.
+pub(super) fn decode_bc6h(block: &[u8], signed: bool, out: &mut [[f32; 4]]) {
+    let mut reader = BitReader::new(block);
+
+    let mode = match reader.read(2) {
+        0 => 0,
+        1 => 1,
+        low => match low | (reader.read(3) << 2) {
+            2 => 2,
+            6 => 3,
+            10 => 4,
+            14 => 5,
+            18 => 6,
+            22 => 7,
+            26 => 8,
+            30 => 9,
+            3 => 10,
+            7 => 11,
+            11 => 12,
+            15 => 13,
+            // Reserved modes decode to black.
+            _ => {
+                out.fill([0.0, 0.0, 0.0, 1.0]);
+                return;
+            }
+        },
+    };
+
+    let info = &BC6H_MODES[mode];
+    let mut values = [0i32; 12];
+
+    for &(value, a, b) in info.fields {
+        // The first bit in the block is bit `b` of the value, the last is bit `a`.
+        for i in 0..=a.abs_diff(b) {
+            let bit = if a >= b { b + i } else { b - i };
+            values[value as usize] |= (reader.read(1) as i32) << bit;
+        }
+    }
+
+    let subset_count = if mode >= 10 { 1 } else { 2 };
+    let partition = if subset_count == 2 {
+        reader.read(5) as usize
+    } else {
+        0
+    };
+
+    let endpoint_count = subset_count * 2;
+    let endpoint_bits = info.endpoint_bits;
+    let mut endpoints = [[0i32; 3]; 4];
+
+    for (index, endpoint) in endpoints.iter_mut().enumerate().take(endpoint_count) {
+        for (channel, value) in endpoint.iter_mut().enumerate() {
+            let raw = values[index * 3 + channel];
+
+            *value = if index == 0 {
+                if signed {
+                    sign_extend(raw, endpoint_bits)
+                } else {
+                    raw
+                }
+            } else if info.transformed {
+                let delta = sign_extend(raw, info.delta_bits[channel]);
+                let base = values[channel];
+                let sum = (base + delta) & ((1 << endpoint_bits) - 1);
+
+                if signed {
+                    sign_extend(sum, endpoint_bits)
+                } else {
+                    sum
+                }
+            } else if signed {
+                sign_extend(raw, endpoint_bits)
+            } else {
+                raw
+            };
+        }
+    }
+
+    let endpoints = endpoints
+        .map(|endpoint| endpoint.map(|value| bc6h_unquantize(value, endpoint_bits, signed)));
+
+    let index_bits = if subset_count == 2 { 3 } else { 4 };
+
+    for (texel, out) in out.iter_mut().enumerate() {
+        let anchor = is_anchor(subset_count, partition, texel);
+        let index = reader.read(index_bits - anchor as u32);
+        let weight = weights(index_bits)[index as usize] as i32;
+        let subset = subset(subset_count, partition, texel);
+        let endpoint0 = endpoints[subset * 2];
+        let endpoint1 = endpoints[subset * 2 + 1];
+
+        let mut color = [0.0, 0.0, 0.0, 1.0];
+
+        for channel in 0..3 {
+            let value =
+                ((64 - weight) * endpoint0[channel] + weight * endpoint1[channel] + 32) >> 6;
+            color[channel] = bc6h_finish_unquantize(value, signed);
+        }
+
+        *out = color;
+    }
+}
+
+fn sign_extend(value: i32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    (value << shift) >> shift
+}
+
+fn bc6h_unquantize(value: i32, bits: u32, signed: bool) -> i32 {
+    if signed {
+        if bits >= 16 {
+            return value;
+        }
+
+        let (negative, magnitude) = (value < 0, value.abs());
+        let unquantized = if magnitude == 0 {
+            0
+        } else if magnitude >= (1 << (bits - 1)) - 1 {
+            0x7fff
+        } else {
+            ((magnitude << 15) + 0x4000) >> (bits - 1)
+        };
+
+        if negative {
+            -unquantized
+        } else {
+            unquantized
+        }
+    } else if bits >= 15 {
+        value
+    } else if value == 0 {
+        0
+    } else if value == (1 << bits) - 1 {
+        0xffff
+    } else {
+        ((value << 16) + 0x8000) >> bits
+    }
+}
+
+fn bc6h_finish_unquantize(value: i32, signed: bool) -> f32 {
+    let bits = if signed {
+        if value < 0 {
+            (((-value) * 31) >> 5) as u16 | 0x8000
+        } else {
+            ((value * 31) >> 5) as u16
+        }
+    } else {
+        ((value * 31) >> 6) as u16
+    };
+
+    f16::from_bits(bits).to_f32()
+}
+
+/// Encodes the color part of a BC1, BC2 or BC3 block.
+///
+/// For `Bc1Mode::PunchThrough`, texels with an alpha below one half are encoded as transparent.
+pub(super) fn encode_bc1(texels: &[[f32; 4]], mode: Bc1Mode, block: &mut [u8]) {
+    let transparent = mode == Bc1Mode::PunchThrough && texels.iter().any(|t| t[3] < 0.5);
+    let colors: Vec<[f32; 3]> = texels
+        .iter()
+        .filter(|t| !transparent || t[3] >= 0.5)
+        .map(|t| [t[0] * 255.0, t[1] * 255.0, t[2] * 255.0])
+        .collect();
+
+    let (mut color0, mut color1) = if colors.is_empty() {
+        (0, 0)
+    } else {
+        let [min, max] = principal_extremes(&colors);
+        (pack_565(max), pack_565(min))
+    };
+
+    // The four-color mode is selected when the first endpoint is greater than the second, the
+    // three-color mode with transparency otherwise.
+    if (color0 < color1) != transparent {
+        std::mem::swap(&mut color0, &mut color1);
+    }
+
+    let palette = bc1_palette(color0, color1, mode);
+    let candidates = if is_four_color(color0, color1, mode) {
+        4
+    } else {
+        3
+    };
+    let mut indices = 0u32;
+
+    for (i, texel) in texels.iter().enumerate() {
+        let index = if transparent && texel[3] < 0.5 {
+            3
+        } else {
+            nearest(&palette[..candidates], |c| {
+                (0..3).map(|i| (c[i] - texel[i]).powi(2)).sum()
+            })
+        };
+
+        indices |= (index as u32) << (i * 2);
+    }
+
+    block[0..2].copy_from_slice(&color0.to_le_bytes());
+    block[2..4].copy_from_slice(&color1.to_le_bytes());
+    block[4..8].copy_from_slice(&indices.to_le_bytes());
+}
+
+fn pack_565(color: [f32; 3]) -> u16 {
+    let r = (color[0].clamp(0.0, 255.0) * 31.0 / 255.0).round() as u16;
+    let g = (color[1].clamp(0.0, 255.0) * 63.0 / 255.0).round() as u16;
+    let b = (color[2].clamp(0.0, 255.0) * 31.0 / 255.0).round() as u16;
+
+    (r << 11) | (g << 5) | b
+}
+
+/// Encodes a BC4 block, or one channel of a BC3 or BC5 block, from `channel` of `texels`.
+pub(super) fn encode_bc4(texels: &[[f32; 4]], signed: bool, channel: usize, block: &mut [u8]) {
+    let (min, max) = texels
+        .iter()
+        .map(|t| t[channel])
+        .fold((f32::MAX, f32::MIN), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+
+    let quantize = |v: f32| -> u8 {
+        if signed {
+            (v.clamp(-1.0, 1.0) * 127.0).round() as i8 as u8
+        } else {
+            (v.clamp(0.0, 1.0) * 255.0).round() as u8
+        }
+    };
+
+    // The eight-value mode is selected when the first endpoint is greater than the second.
+    let (endpoint0, endpoint1) = (quantize(max), quantize(min));
+    let palette = bc4_palette(endpoint0, endpoint1, signed);
+    let candidates = if endpoint0 == endpoint1 { 1 } else { 8 };
+    let mut indices = 0u64;
+
+    for (i, texel) in texels.iter().enumerate() {
+        let index = nearest(&palette[..candidates], |&v| (v - texel[channel]).abs());
+        indices |= (index as u64) << (i * 3);
+    }
+
+    block[0] = endpoint0;
+    block[1] = endpoint1;
+    block[2..8].copy_from_slice(&indices.to_le_bytes()[0..6]);
+}
+
+/// Encodes a BC7 block, using mode 6.
+pub(super) fn encode_bc7(texels: &[[f32; 4]], block: &mut [u8]) {
+    let colors: Vec<[f32; 4]> = texels.iter().map(|t| t.map(|c| c * 255.0)).collect();
+    let [min, max] = principal_extremes(&colors);
+
+    // Mode 6 endpoints have 7 bits per channel, plus a p-bit that is shared by all channels.
+    let quantize = |color: [f32; 4]| -> ([u32; 4], u32) {
+        (0..2)
+            .map(|p_bit| {
+                let quantized =
+                    color.map(|c| ((c.clamp(0.0, 255.0) - p_bit as f32) / 2.0).round() as u32);
+                let quantized = quantized.map(|q| q.min(127));
+                (quantized, p_bit)
+            })
+            .min_by(|(a, p_a), (b, p_b)| {
+                let error = |q: &[u32; 4], p: u32| -> f32 {
+                    (0..4)
+                        .map(|i| (((q[i] << 1) | p) as f32 - color[i]).powi(2))
+                        .sum()
+                };
+                error(a, *p_a).total_cmp(&error(b, *p_b))
+            })
+            .unwrap()
+    };
+
+    let mut endpoints = [quantize(min), quantize(max)];
+    let unquantized = |(q, p): ([u32; 4], u32)| q.map(|v| (v << 1) | p);
+
+    let palette_for = |endpoints: &[([u32; 4], u32); 2]| -> Vec<[f32; 4]> {
+        let e0 = unquantized(endpoints[0]);
+        let e1 = unquantized(endpoints[1]);
+
+        WEIGHTS_4
+            .iter()
+            .map(|&w| {
+                let mut color = [0.0; 4];
+
+                for i in 0..4 {
+                    color[i] = (((64 - w) * e0[i] + w * e1[i] + 32) >> 6) as f32;
+                }
+
+                color
+            })
+            .collect()
+    };
+
+    let mut indices: Vec<u32> = {
+        let palette = palette_for(&endpoints);
+        colors
+            .iter()
+            .map(|color| {
+                nearest(&palette, |c| {
+                    (0..4).map(|i| (c[i] - color[i]).powi(2)).sum()
+                }) as u32
+            })
+            .collect()
+    };
+
+    // The most significant index bit of the anchor texel is implied to be 0.
+    if indices[0] >= 8 {
+        endpoints.swap(0, 1);
+
+        for index in &mut indices {
+            *index = 15 - *index;
+        }
+    }
+
+    let mut writer = BitWriter::default();
+    writer.write(1 << 6, 7);
+
+    for channel in 0..4 {
+        for (quantized, _) in &endpoints {
+            writer.write(quantized[channel], 7);
+        }
+    }
+
+    for &(_, p_bit) in &endpoints {
+        writer.write(p_bit, 1);
+    }
+
+    for (texel, &index) in indices.iter().enumerate() {
+        writer.write(index, if texel == 0 { 3 } else { 4 });
+    }
+
+    block.copy_from_slice(&writer.bits.to_le_bytes());
+}
+
+/// Returns the two colors at the extremes of the principal axis of `colors`.
+fn principal_extremes<const N: usize>(colors: &[[f32; N]]) -> [[f32; N]; 2] {
+    let count = colors.len() as f32;
+    let mut mean = [0.0; N];
+
+    for color in colors {
+        for i in 0..N {
+            mean[i] += color[i] / count;
+        }
+    }
+
+    let mut covariance = [[0.0; N]; N];
+
+    for color in colors {
+        for i in 0..N {
+            for j in 0..N {
+                covariance[i][j] += (color[i] - mean[i]) * (color[j] - mean[j]);
+            }
+        }
+    }
+
+    // Power iteration, starting from the diagonal.
+    let mut axis = [1.0; N];
+
+    for _ in 0..8 {
+        let mut next = [0.0; N];
+
+        for i in 0..N {
+            for j in 0..N {
+                next[i] += covariance[i][j] * axis[j];
+            }
+        }
+
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+
+        if length < 1e-6 {
+            break;
+        }
+
+        axis = next.map(|v| v / length);
+    }
+
+    let project =
+        |color: &[f32; N]| -> f32 { (0..N).map(|i| (color[i] - mean[i]) * axis[i]).sum() };
+    let (min, max) = colors
+        .iter()
+        .map(project)
+        .fold((f32::MAX, f32::MIN), |(min, max), t| {
+            (min.min(t), max.max(t))
+        });
+
+    [
+        std::array::from_fn(|i| mean[i] + axis[i] * min),
+        std::array::from_fn(|i| mean[i] + axis[i] * max),
+    ]
+}
+
+fn nearest<T>(palette: &[T], distance: impl Fn(&T) -> f32) -> usize {
+    palette
+        .iter()
+        .map(distance)
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap()
+        .0
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bits: u128,
+    position: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        self.bits |= ((value & ((1 << bits) - 1)) as u128) << self.position;
+        self.position += bits;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ANCHORS_2, ANCHORS_3_1, ANCHORS_3_2, PARTITIONS_2, PARTITIONS_3};
+
+    #[test]
+    fn anchors_in_subset() {
+        for partition in 0..64 {
+            assert_eq!(
+                (PARTITIONS_2[partition] >> ANCHORS_2[partition]) & 1,
+                1,
+                "partition {}",
+                partition,
+            );
+            assert_eq!(PARTITIONS_3[partition][ANCHORS_3_1[partition] as usize], 1);
+            assert_eq!(PARTITIONS_3[partition][ANCHORS_3_2[partition] as usize], 2);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! The ETC2 and EAC block compression formats.
+//!
+//! All blocks are 4x4 texels, stored as big-endian 64-bit words. Within a block, texel indices
+//! are stored in column-major order, while decoded texels are in row-major order.
+
+/// The modifier tables of the individual and differential modes, as `[small, large]`.
+const MODIFIERS: [[i32; 2]; 8] = [
+    [2, 8],
+    [5, 17],
+    [9, 29],
+    [13, 42],
+    [18, 60],
+    [24, 80],
+    [33, 106],
+    [47, 183],
+];
+
+/// The distances of the T and H modes.
+const DISTANCES: [i32; 8] = [3, 6, 11, 16, 23, 32, 41, 64];
+
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+/// Decodes an ETC2 RGB block, or the RGB part of an ETC2 RGBA block.
+///
+/// If `punch_through` is true, the block is decoded as an ETC2 RGB8A1 block, where the
+/// differential bit instead selects whether the block can contain transparent texels.
+pub(super) fn decode_etc2(block: &[u8], punch_through: bool, out: &mut [[f32; 4]]) {
+    let bits = read_block(block);
+    let differential = bits >> 33 & 1 != 0;
+    let opaque = !punch_through || differential;
+
+    if !punch_through && !differential {
+        let base = [
+            [field(bits, 60, 4), field(bits, 52, 4), field(bits, 44, 4)].map(extend_4),
+            [field(bits, 56, 4), field(bits, 48, 4), field(bits, 40, 4)].map(extend_4),
+        ];
+
+        return decode_subblocks(bits, base, opaque, out);
+    }
+
+    let red = field(bits, 59, 5) as i32 + signed_field(bits, 56, 3);
+    let green = field(bits, 51, 5) as i32 + signed_field(bits, 48, 3);
+    let blue = field(bits, 43, 5) as i32 + signed_field(bits, 40, 3);
+
+    if !(0..32).contains(&red) {
+        decode_t(bits, opaque, out);
+    } else if !(0..32).contains(&green) {
+        decode_h(bits, opaque, out);
+    } else if !(0..32).contains(&blue) {
+        decode_planar(bits, out);
+    } else {
+        let base = [
+            [field(bits, 59, 5), field(bits, 51, 5), field(bits, 43, 5)].map(extend_5),
+            [red as u32, green as u32, blue as u32].map(extend_5),
+        ];
+
+        decode_subblocks(bits, base, opaque, out);
+    }
+}
+
+/// Decodes the individual and differential modes, where the block is split into two subblocks
+/// with their own base color and modifier table.
+fn decode_subblocks(bits: u64, base: [[i32; 3]; 2], opaque: bool, out: &mut [[f32; 4]]) {
+    let tables = [field(bits, 37, 3), field(bits, 34, 3)];
+    let flip = bits >> 32 & 1 != 0;
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        let (x, y) = (i % 4, i / 4);
+        let subblock = if flip { y >= 2 } else { x >= 2 } as usize;
+        let index = texel_index(bits, x, y);
+
+        if !opaque && index == 2 {
+            *texel = [0.0; 4];
+            continue;
+        }
+
+        let [small, large] = MODIFIERS[tables[subblock] as usize];
+        let modifier = match index {
+            0 if !opaque => 0,
+            0 => small,
+            1 => large,
+            2 => -small,
+            _ => -large,
+        };
+
+        *texel = to_float(base[subblock].map(|c| c + modifier));
+    }
+}
+
+fn decode_t(bits: u64, opaque: bool, out: &mut [[f32; 4]]) {
+    let color0 = [
+        field(bits, 59, 2) << 2 | field(bits, 56, 2),
+        field(bits, 52, 4),
+        field(bits, 48, 4),
+    ]
+    .map(extend_4);
+    let color1 = [field(bits, 44, 4), field(bits, 40, 4), field(bits, 36, 4)].map(extend_4);
+    let distance = DISTANCES[(field(bits, 34, 2) << 1 | field(bits, 32, 1)) as usize];
+
+    let paint = [
+        color0,
+        color1.map(|c| c + distance),
+        color1,
+        color1.map(|c| c - distance),
+    ];
+
+    decode_paint(bits, paint, opaque, out);
+}
+
+fn decode_h(bits: u64, opaque: bool, out: &mut [[f32; 4]]) {
+    let raw0 = [
+        field(bits, 59, 4),
+        field(bits, 56, 3) << 1 | field(bits, 52, 1),
+        field(bits, 51, 1) << 3 | field(bits, 47, 3),
+    ];
+    let raw1 = [field(bits, 43, 4), field(bits, 39, 4), field(bits, 35, 4)];
+
+    // The lowest bit of the distance index is given by the order of the two base colors.
+    let order = |[r, g, b]: [u32; 3]| r << 8 | g << 4 | b;
+    let distance_index =
+        field(bits, 34, 1) << 2 | field(bits, 32, 1) << 1 | (order(raw0) >= order(raw1)) as u32;
+    let distance = DISTANCES[distance_index as usize];
+
+    let (color0, color1) = (raw0.map(extend_4), raw1.map(extend_4));
+    let paint = [
+        color0.map(|c| c + distance),
+        color0.map(|c| c - distance),
+        color1.map(|c| c + distance),
+        color1.map(|c| c - distance),
+    ];
+
+    decode_paint(bits, paint, opaque, out);
+}
+
+/// Decodes the T and H modes, where each texel selects one of four paint colors.
+fn decode_paint(bits: u64, paint: [[i32; 3]; 4], opaque: bool, out: &mut [[f32; 4]]) {
+    for (i, texel) in out.iter_mut().enumerate() {
+        let index = texel_index(bits, i % 4, i / 4);
+
+        *texel = if !opaque && index == 2 {
+            [0.0; 4]
+        } else {
+            to_float(paint[index])
+        };
+    }
+}
+
+fn decode_planar(bits: u64, out: &mut [[f32; 4]]) {
+    let extend_6 = |c: u32| (c << 2 | c >> 4) as i32;
+    let extend_7 = |c: u32| (c << 1 | c >> 6) as i32;
+
+    let origin = [
+        extend_6(field(bits, 57, 6)),
+        extend_7(field(bits, 56, 1) << 6 | field(bits, 49, 6)),
+        extend_6(field(bits, 48, 1) << 5 | field(bits, 43, 2) << 3 | field(bits, 39, 3)),
+    ];
+    let horizontal = [
+        extend_6(field(bits, 34, 5) << 1 | field(bits, 32, 1)),
+        extend_7(field(bits, 25, 7)),
+        extend_6(field(bits, 19, 6)),
+    ];
+    let vertical = [
+        extend_6(field(bits, 13, 6)),
+        extend_7(field(bits, 6, 7)),
+        extend_6(field(bits, 0, 6)),
+    ];
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        let (x, y) = ((i % 4) as i32, (i / 4) as i32);
+        let color: [i32; 3] = std::array::from_fn(|c| {
+            (x * (horizontal[c] - origin[c]) + y * (vertical[c] - origin[c]) + 4 * origin[c] + 2)
+                >> 2
+        });
+
+        *texel = to_float(color);
+    }
+}
+
+/// How the values of an EAC block are interpreted.
+#[derive(Clone, Copy)]
+pub(super) enum EacMode {
+    /// The alpha part of an ETC2 RGBA block.
+    Alpha8,
+    /// An unsigned EAC R11 or RG11 channel.
+    Unsigned11,
+    /// A signed EAC R11 or RG11 channel.
+    Signed11,
+}
+
+/// Decodes a single channel from an EAC block.
+pub(super) fn decode_eac(block: &[u8], mode: EacMode, channel: usize, out: &mut [[f32; 4]]) {
+    let bits = read_block(block);
+    let base = field(bits, 56, 8);
+    let multiplier = field(bits, 52, 4) as i32;
+    let modifiers = &EAC_MODIFIERS[field(bits, 48, 4) as usize];
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        let (x, y) = (i % 4, i / 4);
+        let modifier = modifiers[field(bits, 45 - 3 * (x * 4 + y) as u32, 3) as usize];
+
+        // For the 11-bit modes, a multiplier of 0 means 1/8.
+        let scaled = if multiplier == 0 {
+            modifier
+        } else {
+            modifier * multiplier * 8
+        };
+
+        texel[channel] = match mode {
+            EacMode::Alpha8 => (base as i32 + modifier * multiplier).clamp(0, 255) as f32 / 255.0,
+            EacMode::Unsigned11 => (base as i32 * 8 + 4 + scaled).clamp(0, 2047) as f32 / 2047.0,
+            EacMode::Signed11 => {
+                let base = (base as u8 as i8).max(-127) as i32;
+                (base * 8 + scaled).clamp(-1023, 1023) as f32 / 1023.0
+            }
+        };
+    }
+}
+
+fn read_block(block: &[u8]) -> u64 {
+    u64::from_be_bytes(block[..8].try_into().unwrap())
+}
+
+fn field(bits: u64, offset: u32, len: u32) -> u32 {
+    (bits >> offset) as u32 & ((1 << len) - 1)
+}
+
+fn signed_field(bits: u64, offset: u32, len: u32) -> i32 {
+    let shift = 32 - len;
+    ((field(bits, offset, len) << shift) as i32) >> shift
+}
+
+/// Returns the 2-bit index of a texel, which is stored in column-major order.
+fn texel_index(bits: u64, x: usize, y: usize) -> usize {
+    let i = (x * 4 + y) as u32;
+    (field(bits, 16 + i, 1) << 1 | field(bits, i, 1)) as usize
+}
+
+fn extend_4(c: u32) -> i32 {
+    (c << 4 | c) as i32
+}
+
+fn extend_5(c: u32) -> i32 {
+    (c << 3 | c >> 2) as i32
+}
+
+fn to_float(color: [i32; 3]) -> [f32; 4] {
+    let [r, g, b] = color.map(|c| c.clamp(0, 255) as f32 / 255.0);
+    [r, g, b, 1.0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_eac, decode_etc2, EacMode};
+
+    #[test]
+    fn individual_mode() {
+        // Base colors 0x88 and 0x44 with tables 0 and 7, not differential, and flipped so that the
+        // top half uses the first subblock. All texels use index 1, the large positive modifier.
+        let block = [0x84, 0x84, 0x84, 0x1d, 0x00, 0x00, 0xFF, 0xFF];
+        let mut out = [[0.0; 4]; 16];
+        decode_etc2(&block, false, &mut out);
+
+        let top = (0x88 + 8) as f32 / 255.0;
+        let bottom = (0x44 + 183) as f32 / 255.0;
+        assert_eq!(out[7], [top, top, top, 1.0]);
+        assert_eq!(out[8], [bottom, bottom, bottom, 1.0]);
+    }
+
+    #[test]
+    fn punch_through() {
+        // Differential mode with the opaque bit cleared. Index 2 is transparent, and index 0 has
+        // no modifier.
+        let block = [0x80, 0x80, 0x80, 0x00, 0x00, 0xFF, 0x00, 0x00];
+        let mut out = [[0.0; 4]; 16];
+        decode_etc2(&block, true, &mut out);
+
+        // The texels in the first two columns use index 2.
+        let base = 132.0 / 255.0;
+        assert_eq!(out[1], [0.0; 4]);
+        assert_eq!(out[13], [0.0; 4]);
+        assert_eq!(out[2], [base, base, base, 1.0]);
+        assert_eq!(out[15], [base, base, base, 1.0]);
+    }
+
+    #[test]
+    fn eac_alpha() {
+        // Base 128, multiplier 1, table 13. All texels use index 7, with a modifier of 9.
+        let block = [128, 0x1D, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let mut out = [[0.0; 4]; 16];
+        decode_eac(&block, EacMode::Alpha8, 3, &mut out);
+
+        assert!(out.iter().all(|texel| texel[3] == 137.0 / 255.0));
+    }
+}
@@ -0,0 +1,531 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Software decoding and encoding of block-compressed formats.
+//!
+//! The codecs work on whole images that are tightly packed, in the layout produced by
+//! `copy_image_to_buffer` for a single mip level and array layer. Compressed data is a sequence of
+//! blocks in row-major order, and decompressed data is a sequence of texels in row-major order,
+//! encoded in the format returned by [`Format::decompressed_format`].
+//!
+//! For sRGB formats, the codecs do not convert between color spaces: decompressing an sRGB format
+//! produces sRGB-encoded texels, and compressing expects them.
+
+use self::bc::Bc1Mode;
+use super::{CompressionType, Format, NumericType};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+};
+
+mod astc;
+mod bc;
+mod etc2;
+
+impl Format {
+    /// Returns the uncompressed format that [`decompress`](Self::decompress) produces and
+    /// [`compress`](Self::compress) expects for this format, or `None` if this format has no
+    /// software codec.
+    ///
+    /// The returned format has the same numeric type as the compressed format, and enough
+    /// components and precision to represent the decoded data without loss.
+    pub fn decompressed_format(self) -> Option<Format> {
+        let format = match Codec::from_format(self)? {
+            Codec::Bc1(_) | Codec::Bc2 | Codec::Bc3 | Codec::Bc7 | Codec::Etc2 { .. } => {
+                if self.type_color() == Some(NumericType::SRGB) {
+                    Format::R8G8B8A8_SRGB
+                } else {
+                    Format::R8G8B8A8_UNORM
+                }
+            }
+            Codec::Bc4 { signed: false } => Format::R8_UNORM,
+            Codec::Bc4 { signed: true } => Format::R8_SNORM,
+            Codec::Bc5 { signed: false } => Format::R8G8_UNORM,
+            Codec::Bc5 { signed: true } => Format::R8G8_SNORM,
+            Codec::Eac {
+                signed: false,
+                channels: 1,
+            } => Format::R16_UNORM,
+            Codec::Eac {
+                signed: true,
+                channels: 1,
+            } => Format::R16_SNORM,
+            Codec::Eac { signed: false, .. } => Format::R16G16_UNORM,
+            Codec::Eac { signed: true, .. } => Format::R16G16_SNORM,
+            Codec::Bc6h { .. } | Codec::Astc { hdr: true, .. } => Format::R16G16B16A16_SFLOAT,
+            Codec::Astc { srgb: true, .. } => Format::R8G8B8A8_SRGB,
+            Codec::Astc { srgb: false, .. } => Format::R8G8B8A8_UNORM,
+        };
+
+        Some(format)
+    }
+
+    /// Decompresses an image of this format with the given extent, and returns the texels in
+    /// [`decompressed_format`](Self::decompressed_format).
+    ///
+    /// The extent is given in texels, and does not need to be a multiple of the block extent.
+    /// `data` must contain exactly the blocks needed to cover it.
+    ///
+    /// BC, ETC2, EAC and 2D ASTC formats are supported.
+    pub fn decompress(self, data: &[u8], extent: [u32; 3]) -> Result<Vec<u8>, CompressionError> {
+        let codec = Codec::from_format(self).ok_or(CompressionError::FormatNotSupported {
+            format: self,
+            operation: "decompression",
+        })?;
+        let block_extent = self.block_extent();
+        let block_size = self.block_size().unwrap() as usize;
+        let block_counts = block_counts(extent, block_extent);
+        let required = block_counts.iter().product::<usize>() * block_size;
+
+        if data.len() != required {
+            return Err(CompressionError::DataSizeInvalid {
+                provided: data.len(),
+                required,
+            });
+        }
+
+        let texel_format = codec.texel_codec_format(self);
+        let texel_size = texel_format.block_size().unwrap() as usize;
+        let mut output = vec![0; texel_count(extent) * texel_size];
+        let mut texels = vec![[0.0; 4]; (block_extent[0] * block_extent[1]) as usize];
+        let mut blocks = data.chunks_exact(block_size);
+
+        for z in 0..extent[2] {
+            for block_y in 0..block_counts[1] as u32 {
+                for block_x in 0..block_counts[0] as u32 {
+                    codec.decode(blocks.next().unwrap(), block_extent, &mut texels);
+
+                    for (i, texel) in texels.iter().enumerate() {
+                        let x = block_x * block_extent[0] + i as u32 % block_extent[0];
+                        let y = block_y * block_extent[1] + i as u32 / block_extent[0];
+
+                        if x >= extent[0] || y >= extent[1] {
+                            continue;
+                        }
+
+                        let offset = texel_offset([x, y, z], extent) * texel_size;
+                        texel_format
+                            .encode_texel(*texel, &mut output[offset..offset + texel_size])
+                            .unwrap();
+                    }
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Compresses an image with the given extent into this format, and returns the blocks.
+    ///
+    /// `data` must contain the texels in [`decompressed_format`](Self::decompressed_format). The
+    /// extent is given in texels, and does not need to be a multiple of the block extent; blocks
+    /// at the edge are padded by repeating the last row and column of texels.
+    ///
+    /// The BC1, BC3, BC4, BC5 and BC7 formats are supported. The encoders favor speed over
+    /// quality, which makes them suitable for generating textures at runtime or in tests, but an
+    /// offline compressor will usually give better results.
+    pub fn compress(self, data: &[u8], extent: [u32; 3]) -> Result<Vec<u8>, CompressionError> {
+        let codec = Codec::from_format(self).filter(Codec::can_encode).ok_or(
+            CompressionError::FormatNotSupported {
+                format: self,
+                operation: "compression",
+            },
+        )?;
+        let texel_format = codec.texel_codec_format(self);
+        let texel_size = texel_format.block_size().unwrap() as usize;
+        let required = texel_count(extent) * texel_size;
+
+        if data.len() != required {
+            return Err(CompressionError::DataSizeInvalid {
+                provided: data.len(),
+                required,
+            });
+        }
+
+        let block_extent = self.block_extent();
+        let block_size = self.block_size().unwrap() as usize;
+        let block_counts = block_counts(extent, block_extent);
+        let mut output = vec![0; block_counts.iter().product::<usize>() * block_size];
+        let mut texels = vec![[0.0; 4]; (block_extent[0] * block_extent[1]) as usize];
+        let mut blocks = output.chunks_exact_mut(block_size);
+
+        for z in 0..extent[2] {
+            for block_y in 0..block_counts[1] as u32 {
+                for block_x in 0..block_counts[0] as u32 {
+                    for (i, texel) in texels.iter_mut().enumerate() {
+                        let x = (block_x * block_extent[0] + i as u32 % block_extent[0])
+                            .min(extent[0] - 1);
+                        let y = (block_y * block_extent[1] + i as u32 / block_extent[0])
+                            .min(extent[1] - 1);
+                        let offset = texel_offset([x, y, z], extent) * texel_size;
+                        *texel = texel_format
+                            .decode_texel(&data[offset..offset + texel_size])
+                            .unwrap()
+                            .to_float();
+                    }
+
+                    codec.encode(&texels, blocks.next().unwrap());
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// The software codec that handles a compressed format.
+#[derive(Clone, Copy)]
+enum Codec {
+    Bc1(Bc1Mode),
+    Bc2,
+    Bc3,
+    Bc4 { signed: bool },
+    Bc5 { signed: bool },
+    Bc6h { signed: bool },
+    Bc7,
+    Etc2 { punch_through: bool, alpha: bool },
+    Eac { signed: bool, channels: usize },
+    Astc { hdr: bool, srgb: bool },
+}
+
+impl Codec {
+    fn from_format(format: Format) -> Option<Self> {
+        let codec = match format {
+            Format::BC1_RGB_UNORM_BLOCK | Format::BC1_RGB_SRGB_BLOCK => Codec::Bc1(Bc1Mode::Opaque),
+            Format::BC1_RGBA_UNORM_BLOCK | Format::BC1_RGBA_SRGB_BLOCK => {
+                Codec::Bc1(Bc1Mode::PunchThrough)
+            }
+            Format::BC2_UNORM_BLOCK | Format::BC2_SRGB_BLOCK => Codec::Bc2,
+            Format::BC3_UNORM_BLOCK | Format::BC3_SRGB_BLOCK => Codec::Bc3,
+            Format::BC4_UNORM_BLOCK => Codec::Bc4 { signed: false },
+            Format::BC4_SNORM_BLOCK => Codec::Bc4 { signed: true },
+            Format::BC5_UNORM_BLOCK => Codec::Bc5 { signed: false },
+            Format::BC5_SNORM_BLOCK => Codec::Bc5 { signed: true },
+            Format::BC6H_UFLOAT_BLOCK => Codec::Bc6h { signed: false },
+            Format::BC6H_SFLOAT_BLOCK => Codec::Bc6h { signed: true },
+            Format::BC7_UNORM_BLOCK | Format::BC7_SRGB_BLOCK => Codec::Bc7,
+            Format::ETC2_R8G8B8_UNORM_BLOCK | Format::ETC2_R8G8B8_SRGB_BLOCK => Codec::Etc2 {
+                punch_through: false,
+                alpha: false,
+            },
+            Format::ETC2_R8G8B8A1_UNORM_BLOCK | Format::ETC2_R8G8B8A1_SRGB_BLOCK => Codec::Etc2 {
+                punch_through: true,
+                alpha: false,
+            },
+            Format::ETC2_R8G8B8A8_UNORM_BLOCK | Format::ETC2_R8G8B8A8_SRGB_BLOCK => Codec::Etc2 {
+                punch_through: false,
+                alpha: true,
+            },
+            Format::EAC_R11_UNORM_BLOCK => Codec::Eac {
+                signed: false,
+                channels: 1,
+            },
+            Format::EAC_R11_SNORM_BLOCK => Codec::Eac {
+                signed: true,
+                channels: 1,
+            },
+            Format::EAC_R11G11_UNORM_BLOCK => Codec::Eac {
+                signed: false,
+                channels: 2,
+            },
+            Format::EAC_R11G11_SNORM_BLOCK => Codec::Eac {
+                signed: true,
+                channels: 2,
+            },
+            _ => match format.compression()? {
+                // Only 2D ASTC formats are exposed by Vulkan.
+                CompressionType::ASTC_LDR | CompressionType::ASTC_HDR
+                    if format.block_extent()[2] == 1 =>
+                {
+                    Codec::Astc {
+                        hdr: format.compression() == Some(CompressionType::ASTC_HDR),
+                        srgb: format.type_color() == Some(NumericType::SRGB),
+                    }
+                }
+                _ => return None,
+            },
+        };
+
+        Some(codec)
+    }
+
+    fn can_encode(&self) -> bool {
+        matches!(
+            self,
+            Codec::Bc1(_) | Codec::Bc3 | Codec::Bc4 { .. } | Codec::Bc5 { .. } | Codec::Bc7
+        )
+    }
+
+    /// Returns the format whose texel codec is used to read and write decompressed data.
+    ///
+    /// This is the decompressed format, except that sRGB formats use the corresponding `UNORM`
+    /// format, so that the codecs see the encoded values rather than linear ones.
+    fn texel_codec_format(self, format: Format) -> Format {
+        match format.decompressed_format().unwrap() {
+            Format::R8G8B8A8_SRGB => Format::R8G8B8A8_UNORM,
+            texel_format => texel_format,
+        }
+    }
+
+    fn decode(self, block: &[u8], block_extent: [u32; 3], out: &mut [[f32; 4]]) {
+        match self {
+            Codec::Bc1(mode) => bc::decode_bc1(block, mode, out),
+            Codec::Bc2 => {
+                bc::decode_bc1(&block[8..], Bc1Mode::FourColor, out);
+                bc::decode_bc2_alpha(&block[..8], out);
+            }
+            Codec::Bc3 => {
+                bc::decode_bc1(&block[8..], Bc1Mode::FourColor, out);
+                bc::decode_bc4(&block[..8], false, 3, out);
+            }
+            Codec::Bc4 { signed } => {
+                out.fill([0.0, 0.0, 0.0, 1.0]);
+                bc::decode_bc4(block, signed, 0, out);
+            }
+            Codec::Bc5 { signed } => {
+                out.fill([0.0, 0.0, 0.0, 1.0]);
+                bc::decode_bc4(&block[..8], signed, 0, out);
+                bc::decode_bc4(&block[8..], signed, 1, out);
+            }
+            Codec::Bc6h { signed } => bc::decode_bc6h(block, signed, out),
+            Codec::Bc7 => bc::decode_bc7(block, out),
+            Codec::Etc2 {
+                punch_through,
+                alpha: false,
+            } => etc2::decode_etc2(block, punch_through, out),
+            Codec::Etc2 { alpha: true, .. } => {
+                etc2::decode_etc2(&block[8..], false, out);
+                etc2::decode_eac(&block[..8], etc2::EacMode::Alpha8, 3, out);
+            }
+            Codec::Eac { signed, channels } => {
+                let mode = if signed {
+                    etc2::EacMode::Signed11
+                } else {
+                    etc2::EacMode::Unsigned11
+                };
+                out.fill([0.0, 0.0, 0.0, 1.0]);
+
+                for (channel, block) in block.chunks_exact(8).take(channels).enumerate() {
+                    etc2::decode_eac(block, mode, channel, out);
+                }
+            }
+            Codec::Astc { hdr, srgb } => {
+                astc::decode(block, [block_extent[0], block_extent[1]], hdr, srgb, out)
+            }
+        }
+    }
+
+    fn encode(self, texels: &[[f32; 4]], block: &mut [u8]) {
+        match self {
+            Codec::Bc1(mode) => bc::encode_bc1(texels, mode, block),
+            Codec::Bc3 => {
+                bc::encode_bc4(texels, false, 3, &mut block[..8]);
+                bc::encode_bc1(texels, Bc1Mode::FourColor, &mut block[8..]);
+            }
+            Codec::Bc4 { signed } => bc::encode_bc4(texels, signed, 0, block),
+            Codec::Bc5 { signed } => {
+                bc::encode_bc4(texels, signed, 0, &mut block[..8]);
+                bc::encode_bc4(texels, signed, 1, &mut block[8..]);
+            }
+            Codec::Bc7 => bc::encode_bc7(texels, block),
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn block_counts(extent: [u32; 3], block_extent: [u32; 3]) -> [usize; 3] {
+    [
+        ((extent[0] + block_extent[0] - 1) / block_extent[0]) as usize,
+        ((extent[1] + block_extent[1] - 1) / block_extent[1]) as usize,
+        extent[2] as usize,
+    ]
+}
+
+fn texel_count(extent: [u32; 3]) -> usize {
+    extent.iter().map(|&e| e as usize).product()
+}
+
+fn texel_offset([x, y, z]: [u32; 3], extent: [u32; 3]) -> usize {
+    (z as usize * extent[1] as usize + y as usize) * extent[0] as usize + x as usize
+}
+
+/// Reads little-endian bit fields from a block of at most 16 bytes.
+struct BitReader {
+    bits: u128,
+    position: u32,
+}
+
+impl BitReader {
+    fn new(block: &[u8]) -> Self {
+        let mut bytes = [0; 16];
+        bytes[..block.len()].copy_from_slice(block);
+
+        Self::from_bits(u128::from_le_bytes(bytes))
+    }
+
+    fn from_bits(bits: u128) -> Self {
+        BitReader { bits, position: 0 }
+    }
+
+    /// Reads the next `bits` bits, with the first bit read ending up in the least significant bit
+    /// of the result. Bits past the end of the block read as 0.
+    fn read(&mut self, bits: u32) -> u32 {
+        debug_assert!(bits <= 32);
+
+        if bits == 0 || self.position >= 128 {
+            self.position += bits;
+            return 0;
+        }
+
+        let value = (self.bits >> self.position) as u32 & (u32::MAX >> (32 - bits));
+        self.position += bits;
+
+        value
+    }
+
+    fn skip(&mut self, bits: u32) {
+        self.position += bits;
+    }
+}
+
+/// Error that can happen when compressing or decompressing an image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompressionError {
+    /// The size of the provided data does not match the size required for the extent.
+    DataSizeInvalid { provided: usize, required: usize },
+
+    /// The format does not have a software codec for the requested operation.
+    FormatNotSupported {
+        format: Format,
+        operation: &'static str,
+    },
+}
+
+impl Error for CompressionError {}
+
+impl Display for CompressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::DataSizeInvalid { provided, required } => write!(
+                f,
+                "the size of the provided data ({}) does not match the size required for the \
+                extent ({})",
+                provided, required,
+            ),
+            Self::FormatNotSupported { format, operation } => write!(
+                f,
+                "the format {:?} does not support software {}",
+                format, operation,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompressionError;
+    use crate::format::Format;
+
+    /// A diagonal gradient, where the colors within each block lie on a line.
+    fn gradient(extent: [u32; 3]) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        for y in 0..extent[1] {
+            for x in 0..extent[0] {
+                let t = ((x + y) * 255 / (extent[0] + extent[1] - 2)) as u8;
+                data.extend([t, 255 - t, t / 2, 255]);
+            }
+        }
+
+        data
+    }
+
+    fn max_difference(a: &[u8], b: &[u8]) -> u8 {
+        a.iter().zip(b).map(|(a, b)| a.abs_diff(*b)).max().unwrap()
+    }
+
+    #[test]
+    fn round_trip_rgba() {
+        let extent = [13, 7, 1];
+        let data = gradient(extent);
+
+        for format in [
+            Format::BC1_RGB_UNORM_BLOCK,
+            Format::BC3_UNORM_BLOCK,
+            Format::BC7_UNORM_BLOCK,
+            Format::BC7_SRGB_BLOCK,
+        ] {
+            let compressed = format.compress(&data, extent).unwrap();
+            assert_eq!(
+                compressed.len(),
+                4 * 2 * format.block_size().unwrap() as usize
+            );
+
+            let decompressed = format.decompress(&compressed, extent).unwrap();
+            assert!(max_difference(&data, &decompressed) <= 24, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn round_trip_channels() {
+        let extent = [8, 4, 1];
+        let data: Vec<u8> = (0..64).map(|i| (i * 4) as u8).collect();
+
+        let r = Format::BC4_UNORM_BLOCK
+            .decompress(
+                &Format::BC4_UNORM_BLOCK
+                    .compress(&data[..32], extent)
+                    .unwrap(),
+                extent,
+            )
+            .unwrap();
+        assert!(max_difference(&data[..32], &r) <= 20);
+
+        let rg = Format::BC5_UNORM_BLOCK
+            .decompress(
+                &Format::BC5_UNORM_BLOCK.compress(&data, extent).unwrap(),
+                extent,
+            )
+            .unwrap();
+        assert!(max_difference(&data, &rg) <= 20);
+    }
+
+    #[test]
+    fn decompressed_formats() {
+        assert_eq!(
+            Format::BC1_RGBA_SRGB_BLOCK.decompressed_format(),
+            Some(Format::R8G8B8A8_SRGB)
+        );
+        assert_eq!(
+            Format::EAC_R11G11_SNORM_BLOCK.decompressed_format(),
+            Some(Format::R16G16_SNORM)
+        );
+        assert_eq!(
+            Format::ASTC_6x5_SFLOAT_BLOCK.decompressed_format(),
+            Some(Format::R16G16B16A16_SFLOAT)
+        );
+        assert_eq!(Format::PVRTC1_2BPP_UNORM_BLOCK.decompressed_format(), None);
+        assert_eq!(Format::R8G8B8A8_UNORM.decompressed_format(), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            Format::BC1_RGB_UNORM_BLOCK.decompress(&[0; 7], [4, 4, 1]),
+            Err(CompressionError::DataSizeInvalid {
+                provided: 7,
+                required: 8
+            })
+        ));
+        assert!(matches!(
+            Format::BC6H_UFLOAT_BLOCK.compress(&[0; 128], [4, 4, 1]),
+            Err(CompressionError::FormatNotSupported { .. })
+        ));
+    }
+}
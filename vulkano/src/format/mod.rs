@@ -91,11 +91,44 @@
 //! plane as an individual *aspect* of the image. A single-plane aspect of a multi-planar image
 //! behaves as a regular image, and even has its own format, which can be queried with the `plane`
 //! method on a format.
-
+//!
+//! # Accessing texels on the CPU
+//!
+//! The `decode_texel` and `encode_texel` methods of `Format` convert between the in-memory
+//! representation of a single texel and a [`TexelValue`], and `convert_texels` converts tightly
+//! packed data from one format to another. This is useful to interpret the contents of a buffer
+//! that an image was copied into, or to prepare data for uploading to an image. These codecs are
+//! available for all uncompressed single-plane formats, including packed, shared-exponent and
+//! depth-only or stencil-only formats.
+//!
+//! Block-compressed formats can be decompressed on the CPU with `decompress`, and a subset of
+//! them can be compressed with `compress`. This can be used as a fallback when the device does not
+//! support a compressed format, by decompressing the data and uploading it in the format returned
+//! by `decompressed_format` instead.
+//!
+//! # Choosing a format
+//!
+//! Which formats can be used for what depends on the physical device. Given a list of candidate
+//! formats in order of preference, or a filter over [`Format::all`] such as "any format with a
+//! depth and a stencil aspect",
+//! [`PhysicalDevice::find_image_format`](crate::device::physical::PhysicalDevice::find_image_format)
+//! returns the first one that supports the required usage, sample counts and extent.
+//! [`PhysicalDevice::format_table`](crate::device::physical::PhysicalDevice::format_table)
+//! gathers the features of all supported formats into a table that can be printed.
+
+use self::texel::{TexelChannel, TexelComponent, TexelLayout};
+pub use self::{
+    compression::CompressionError,
+    texel::{TexelCodecError, TexelValue},
+};
 use crate::{
     device::physical::PhysicalDevice, image::ImageAspects, macros::vulkan_bitflags,
     shader::spirv::ImageFormat, DeviceSize,
 };
+use std::fmt::{Display, Error as FmtError, Formatter};
+
+mod compression;
+mod texel;
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/formats.rs"));
@@ -742,6 +775,103 @@ impl FormatProperties {
     }
 }
 
+/// The properties of every format that is supported by a physical device, returned by
+/// [`PhysicalDevice::format_table`].
+///
+/// When displayed, this is printed as a table with a row for each format and a column for each
+/// commonly needed format feature. A cell contains `L` if the feature is supported with linear
+/// tiling, `O` if it is supported with optimal tiling and `B` if it is supported for buffers, with
+/// `-` in the place of each one that isn't.
+#[derive(Clone, Debug)]
+pub struct FormatTable {
+    rows: Vec<(Format, FormatProperties)>,
+}
+
+impl FormatTable {
+    const COLUMNS: [(&'static str, FormatFeatures); 14] = [
+        ("sampled", FormatFeatures::SAMPLED_IMAGE),
+        ("filter", FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR),
+        ("storage", FormatFeatures::STORAGE_IMAGE),
+        ("atomic", FormatFeatures::STORAGE_IMAGE_ATOMIC),
+        ("color", FormatFeatures::COLOR_ATTACHMENT),
+        ("blend", FormatFeatures::COLOR_ATTACHMENT_BLEND),
+        ("depth", FormatFeatures::DEPTH_STENCIL_ATTACHMENT),
+        ("blit_src", FormatFeatures::BLIT_SRC),
+        ("blit_dst", FormatFeatures::BLIT_DST),
+        ("xfer_src", FormatFeatures::TRANSFER_SRC),
+        ("xfer_dst", FormatFeatures::TRANSFER_DST),
+        ("vertex", FormatFeatures::VERTEX_BUFFER),
+        ("uniform_texel", FormatFeatures::UNIFORM_TEXEL_BUFFER),
+        ("storage_texel", FormatFeatures::STORAGE_TEXEL_BUFFER),
+    ];
+
+    pub(crate) fn new(physical_device: &PhysicalDevice) -> Self {
+        let rows = Format::all()
+            .iter()
+            .filter_map(|&format| {
+                // Fails for formats from extensions that the physical device doesn't support.
+                let properties = physical_device.format_properties(format).ok()?;
+
+                (!(properties.linear_tiling_features
+                    | properties.optimal_tiling_features
+                    | properties.buffer_features)
+                    .is_empty())
+                .then_some((format, properties))
+            })
+            .collect();
+
+        FormatTable { rows }
+    }
+
+    /// Returns the supported formats and their properties, in the order of [`Format::all`].
+    ///
+    /// Formats that support no features at all are left out.
+    #[inline]
+    pub fn rows(&self) -> &[(Format, FormatProperties)] {
+        &self.rows
+    }
+}
+
+impl Display for FormatTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let names: Vec<String> = self
+            .rows
+            .iter()
+            .map(|(format, _)| format!("{:?}", format))
+            .collect();
+        let name_width = names.iter().map(String::len).max().unwrap_or(0).max(6);
+
+        write!(f, "{:<1$}", "format", name_width)?;
+
+        for (header, _) in Self::COLUMNS {
+            write!(f, " {}", header)?;
+        }
+
+        writeln!(f)?;
+
+        for (name, (_, properties)) in names.iter().zip(&self.rows) {
+            write!(f, "{:<1$}", name, name_width)?;
+
+            for (header, feature) in Self::COLUMNS {
+                let cell: String = [
+                    (properties.linear_tiling_features, 'L'),
+                    (properties.optimal_tiling_features, 'O'),
+                    (properties.buffer_features, 'B'),
+                ]
+                .into_iter()
+                .map(|(features, c)| if features.intersects(feature) { c } else { '-' })
+                .collect();
+
+                write!(f, " {:<1$}", cell, header.len())?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 vulkan_bitflags! {
     #[non_exhaustive]
 
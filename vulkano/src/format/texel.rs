@@ -0,0 +1,678 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Encoding and decoding of individual texels on the CPU.
+//!
+//! The codecs operate on the representation of a texel as it is stored in a buffer, for example
+//! after copying an image to a buffer with `copy_image_to_buffer`. Multi-byte values are assumed
+//! to be little-endian, which is the case on all platforms that vulkano supports.
+//!
+//! Compressed, YCbCr and multi-planar formats are not supported. Combined depth/stencil formats do
+//! not have a CPU-visible representation either, because the depth and stencil aspects are always
+//! copied to and from buffers separately. Use the depth-only or stencil-only format that matches
+//! the aspect instead, such as `X8_D24_UNORM_PACK32` for the depth aspect of `D24_UNORM_S8_UINT`.
+
+use super::{Format, NumericType};
+use half::f16;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+};
+
+impl Format {
+    /// Decodes a single texel of this format from `data`.
+    ///
+    /// `data` must contain exactly one texel. Components that are not present in the format are
+    /// returned as 0, except for alpha, which is returned as 1.
+    pub fn decode_texel(self, data: &[u8]) -> Result<TexelValue, TexelCodecError> {
+        let layout = self.checked_texel_layout()?;
+
+        if data.len() != layout.size {
+            return Err(TexelCodecError::DataSizeInvalid {
+                provided: data.len(),
+                texel_size: layout.size,
+            });
+        }
+
+        Ok(layout.decode(data))
+    }
+
+    /// Encodes `value` as a single texel of this format, and writes it to `data`.
+    ///
+    /// `data` must have room for exactly one texel. Values that are out of range for the format
+    /// are clamped. If `value` does not match the numeric type of the format, it is converted
+    /// first, as described on [`TexelValue`].
+    pub fn encode_texel(
+        self,
+        value: impl Into<TexelValue>,
+        data: &mut [u8],
+    ) -> Result<(), TexelCodecError> {
+        let layout = self.checked_texel_layout()?;
+
+        if data.len() != layout.size {
+            return Err(TexelCodecError::DataSizeInvalid {
+                provided: data.len(),
+                texel_size: layout.size,
+            });
+        }
+
+        layout.encode(value.into(), data);
+
+        Ok(())
+    }
+
+    /// Converts tightly packed texel data of this format to `dst_format`, and returns the
+    /// converted data.
+    ///
+    /// The length of `data` must be a multiple of the texel size of this format. Each texel is
+    /// decoded and then encoded again, so the conversion follows the same rules as
+    /// [`decode_texel`](Self::decode_texel) and [`encode_texel`](Self::encode_texel).
+    pub fn convert_texels(
+        self,
+        data: &[u8],
+        dst_format: Format,
+    ) -> Result<Vec<u8>, TexelCodecError> {
+        let src_layout = self.checked_texel_layout()?;
+        let dst_layout = dst_format.checked_texel_layout()?;
+
+        if data.len() % src_layout.size != 0 {
+            return Err(TexelCodecError::DataSizeInvalid {
+                provided: data.len(),
+                texel_size: src_layout.size,
+            });
+        }
+
+        let texel_count = data.len() / src_layout.size;
+        let mut output = vec![0; texel_count * dst_layout.size];
+
+        for (src, dst) in data
+            .chunks_exact(src_layout.size)
+            .zip(output.chunks_exact_mut(dst_layout.size))
+        {
+            dst_layout.encode(src_layout.decode(src), dst);
+        }
+
+        Ok(output)
+    }
+
+    fn checked_texel_layout(self) -> Result<TexelLayout, TexelCodecError> {
+        self.texel_layout()
+            .ok_or(TexelCodecError::FormatNotSupported { format: self })
+    }
+}
+
+/// The value of a single texel, as decoded from or encoded into a format.
+///
+/// When encoding a value into a format whose numeric type does not match, the value is converted
+/// as follows:
+/// - Floating-point values are rounded to the nearest integer for `UINT` and `SINT` formats, and
+///   integer values are converted to floating-point as-is for the other color formats.
+/// - For depth formats, the first component of a color value is used as the depth value, and
+///   for stencil formats, the first component is used as the stencil value.
+/// - A depth/stencil value is treated as a color value whose first component holds the depth or
+///   stencil value, with the other components 0 and alpha 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TexelValue {
+    /// A value of a `UNORM`, `SNORM`, `USCALED`, `SSCALED`, `SRGB`, `UFLOAT` or `SFLOAT` format.
+    ///
+    /// `SRGB` values are in linear color space; the conversion to and from sRGB is performed
+    /// by the codec. Values of 64-bit `SFLOAT` formats lose precision.
+    Float([f32; 4]),
+
+    /// A value of a `SINT` format.
+    Int([i64; 4]),
+
+    /// A value of a `UINT` format.
+    Uint([u64; 4]),
+
+    /// A value of a depth and/or stencil format.
+    DepthStencil {
+        depth: Option<f32>,
+        stencil: Option<u8>,
+    },
+}
+
+impl TexelValue {
+    /// Returns the value as floating-point components.
+    #[inline]
+    pub fn to_float(self) -> [f32; 4] {
+        match self {
+            Self::Float(value) => value,
+            Self::Int(value) => value.map(|c| c as f32),
+            Self::Uint(value) => value.map(|c| c as f32),
+            Self::DepthStencil { depth, stencil } => [
+                depth.unwrap_or_else(|| stencil.unwrap_or(0) as f32),
+                0.0,
+                0.0,
+                1.0,
+            ],
+        }
+    }
+
+    /// Returns the value as signed integer components.
+    #[inline]
+    pub fn to_int(self) -> [i64; 4] {
+        match self {
+            Self::Int(value) => value,
+            Self::Uint(value) => value.map(|c| c.min(i64::MAX as u64) as i64),
+            Self::Float(_) | Self::DepthStencil { .. } => self.to_float().map(|c| c.round() as i64),
+        }
+    }
+
+    /// Returns the value as unsigned integer components.
+    #[inline]
+    pub fn to_uint(self) -> [u64; 4] {
+        match self {
+            Self::Uint(value) => value,
+            Self::Int(value) => value.map(|c| c.max(0) as u64),
+            Self::DepthStencil {
+                depth: _,
+                stencil: Some(stencil),
+            } => [stencil as u64, 0, 0, 1],
+            Self::Float(_) | Self::DepthStencil { .. } => self.to_float().map(|c| c.round() as u64),
+        }
+    }
+
+    fn depth(self) -> f32 {
+        match self {
+            Self::DepthStencil {
+                depth: Some(depth), ..
+            } => depth,
+            _ => self.to_float()[0],
+        }
+    }
+
+    fn stencil(self) -> u64 {
+        match self {
+            Self::DepthStencil {
+                stencil: Some(stencil),
+                ..
+            } => stencil as u64,
+            _ => self.to_uint()[0],
+        }
+    }
+}
+
+impl From<[f32; 4]> for TexelValue {
+    #[inline]
+    fn from(val: [f32; 4]) -> Self {
+        Self::Float(val)
+    }
+}
+
+impl From<[i32; 4]> for TexelValue {
+    #[inline]
+    fn from(val: [i32; 4]) -> Self {
+        Self::Int(val.map(i64::from))
+    }
+}
+
+impl From<[u32; 4]> for TexelValue {
+    #[inline]
+    fn from(val: [u32; 4]) -> Self {
+        Self::Uint(val.map(u64::from))
+    }
+}
+
+impl From<[i64; 4]> for TexelValue {
+    #[inline]
+    fn from(val: [i64; 4]) -> Self {
+        Self::Int(val)
+    }
+}
+
+impl From<[u64; 4]> for TexelValue {
+    #[inline]
+    fn from(val: [u64; 4]) -> Self {
+        Self::Uint(val)
+    }
+}
+
+/// The bit layout of a single texel of a format.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TexelLayout {
+    /// The size of a texel in bytes.
+    pub(crate) size: usize,
+    pub(crate) components: &'static [TexelComponent],
+}
+
+/// A single component of a texel.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TexelComponent {
+    pub(crate) channel: TexelChannel,
+    /// The offset of the least significant bit of the component, counted from the least
+    /// significant bit of the first byte of the texel.
+    pub(crate) offset: u16,
+    pub(crate) bits: u8,
+    pub(crate) numeric_type: NumericType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TexelChannel {
+    R,
+    G,
+    B,
+    A,
+    D,
+    S,
+    /// The shared exponent of `E5B9G9R9_UFLOAT_PACK32`.
+    E,
+}
+
+impl TexelChannel {
+    fn color_index(self) -> Option<usize> {
+        match self {
+            Self::R => Some(0),
+            Self::G => Some(1),
+            Self::B => Some(2),
+            Self::A => Some(3),
+            Self::D | Self::S | Self::E => None,
+        }
+    }
+}
+
+/// The bias and mantissa width of `E5B9G9R9_UFLOAT_PACK32`.
+const SHARED_EXPONENT_BIAS: i32 = 15;
+const SHARED_EXPONENT_MANTISSA_BITS: i32 = 9;
+
+impl TexelLayout {
+    fn decode(&self, data: &[u8]) -> TexelValue {
+        let shared_exponent = self.shared_exponent(data);
+        let mut float = [0.0, 0.0, 0.0, 1.0];
+        let mut int = [0, 0, 0, 1];
+        let mut uint = [0, 0, 0, 1];
+        let mut kind = None;
+        let mut depth = None;
+        let mut stencil = None;
+
+        for &component in self.components {
+            let raw = read_bits(data, component.offset, component.bits);
+
+            match component.channel {
+                TexelChannel::E => continue,
+                TexelChannel::D => {
+                    depth = Some(decode_float(raw, component, None));
+                    continue;
+                }
+                TexelChannel::S => {
+                    stencil = Some(raw as u8);
+                    continue;
+                }
+                _ => (),
+            }
+
+            let index = component.channel.color_index().unwrap();
+
+            match component.numeric_type {
+                NumericType::UINT => uint[index] = raw,
+                NumericType::SINT => int[index] = sign_extend(raw, component.bits),
+                _ => {
+                    float[index] = decode_float(raw, component, shared_exponent);
+
+                    if component.numeric_type == NumericType::SRGB
+                        && component.channel != TexelChannel::A
+                    {
+                        float[index] = srgb_to_linear(float[index]);
+                    }
+                }
+            }
+
+            kind = Some(component.numeric_type);
+        }
+
+        match kind {
+            None => TexelValue::DepthStencil { depth, stencil },
+            Some(NumericType::UINT) => TexelValue::Uint(uint),
+            Some(NumericType::SINT) => TexelValue::Int(int),
+            Some(_) => TexelValue::Float(float),
+        }
+    }
+
+    fn encode(&self, value: TexelValue, data: &mut [u8]) {
+        data.fill(0);
+
+        if self
+            .components
+            .iter()
+            .any(|component| component.channel == TexelChannel::E)
+        {
+            self.encode_shared_exponent(value.to_float(), data);
+            return;
+        }
+
+        for &component in self.components {
+            let raw = match component.channel {
+                TexelChannel::D => encode_float(value.depth(), component),
+                TexelChannel::S => value.stencil().min(mask(component.bits)),
+                TexelChannel::E => unreachable!(),
+                channel => {
+                    let index = channel.color_index().unwrap();
+
+                    match component.numeric_type {
+                        NumericType::UINT => value.to_uint()[index].min(mask(component.bits)),
+                        NumericType::SINT => {
+                            let max = (mask(component.bits) >> 1) as i64;
+                            value.to_int()[index].clamp(-max - 1, max) as u64 & mask(component.bits)
+                        }
+                        NumericType::SRGB if channel != TexelChannel::A => {
+                            encode_float(linear_to_srgb(value.to_float()[index]), component)
+                        }
+                        _ => encode_float(value.to_float()[index], component),
+                    }
+                }
+            };
+
+            write_bits(data, component.offset, component.bits, raw);
+        }
+    }
+
+    fn shared_exponent(&self, data: &[u8]) -> Option<i32> {
+        self.components
+            .iter()
+            .find(|component| component.channel == TexelChannel::E)
+            .map(|component| read_bits(data, component.offset, component.bits) as i32)
+    }
+
+    fn encode_shared_exponent(&self, value: [f32; 4], data: &mut [u8]) {
+        // The algorithm from the "Shared Exponent" section of the Vulkan specification.
+        const N: i32 = SHARED_EXPONENT_MANTISSA_BITS;
+        const B: i32 = SHARED_EXPONENT_BIAS;
+        let max_value = ((1 << N) - 1) as f32 / (1 << N) as f32 * 2f32.powi(31 - B);
+
+        let clamped = value.map(|c| {
+            if c.is_nan() {
+                0.0
+            } else {
+                c.clamp(0.0, max_value)
+            }
+        });
+        let max_component = clamped[0].max(clamped[1]).max(clamped[2]);
+
+        let mut exponent = (max_component.log2().floor() as i32).max(-B - 1) + 1 + B;
+        let max_mantissa = (max_component / 2f32.powi(exponent - B - N) + 0.5).floor() as i32;
+
+        if max_mantissa == 1 << N {
+            exponent += 1;
+        }
+
+        for component in self.components {
+            let raw = match component.channel {
+                TexelChannel::E => exponent as u64,
+                channel => {
+                    let c = clamped[channel.color_index().unwrap()];
+                    (c / 2f32.powi(exponent - B - N) + 0.5).floor() as u64
+                }
+            };
+
+            write_bits(data, component.offset, component.bits, raw);
+        }
+    }
+}
+
+fn mask(bits: u8) -> u64 {
+    ((1u128 << bits) - 1) as u64
+}
+
+fn sign_extend(raw: u64, bits: u8) -> i64 {
+    let shift = 64 - bits as u32;
+    ((raw << shift) as i64) >> shift
+}
+
+fn read_bits(data: &[u8], offset: u16, bits: u8) -> u64 {
+    let first_byte = offset as usize / 8;
+    let last_byte = (offset as usize + bits as usize + 7) / 8;
+    let shift = offset % 8;
+
+    let word = data[first_byte..last_byte]
+        .iter()
+        .rev()
+        .fold(0u128, |word, &byte| (word << 8) | byte as u128);
+
+    (word >> shift) as u64 & mask(bits)
+}
+
+fn write_bits(data: &mut [u8], offset: u16, bits: u8, value: u64) {
+    let first_byte = offset as usize / 8;
+    let last_byte = (offset as usize + bits as usize + 7) / 8;
+    let shift = offset % 8;
+    let value = ((value & mask(bits)) as u128) << shift;
+
+    for (i, byte) in data[first_byte..last_byte].iter_mut().enumerate() {
+        *byte |= (value >> (i * 8)) as u8;
+    }
+}
+
+fn decode_float(raw: u64, component: TexelComponent, shared_exponent: Option<i32>) -> f32 {
+    let bits = component.bits;
+
+    match component.numeric_type {
+        NumericType::UNORM | NumericType::SRGB => (raw as f64 / mask(bits) as f64) as f32,
+        NumericType::SNORM => {
+            let max = (mask(bits) >> 1) as f64;
+            ((sign_extend(raw, bits) as f64 / max) as f32).max(-1.0)
+        }
+        NumericType::USCALED => raw as f32,
+        NumericType::SSCALED => sign_extend(raw, bits) as f32,
+        NumericType::SFLOAT => match bits {
+            16 => f16::from_bits(raw as u16).to_f32(),
+            32 => f32::from_bits(raw as u32),
+            64 => f64::from_bits(raw) as f32,
+            _ => unreachable!(),
+        },
+        NumericType::UFLOAT => match shared_exponent {
+            Some(exponent) => {
+                raw as f32
+                    * 2f32.powi(exponent - SHARED_EXPONENT_BIAS - SHARED_EXPONENT_MANTISSA_BITS)
+            }
+            // 10- and 11-bit unsigned floats have the same exponent as a 16-bit float, but fewer
+            // mantissa bits and no sign bit.
+            None => f16::from_bits((raw << (15 - bits)) as u16).to_f32(),
+        },
+        NumericType::UINT | NumericType::SINT => unreachable!(),
+    }
+}
+
+fn encode_float(value: f32, component: TexelComponent) -> u64 {
+    let bits = component.bits;
+
+    match component.numeric_type {
+        NumericType::UNORM | NumericType::SRGB => {
+            (value.clamp(0.0, 1.0) as f64 * mask(bits) as f64).round() as u64
+        }
+        NumericType::SNORM => {
+            let max = (mask(bits) >> 1) as f64;
+            (value.clamp(-1.0, 1.0) as f64 * max).round() as i64 as u64 & mask(bits)
+        }
+        NumericType::USCALED => (value.round() as u64).min(mask(bits)),
+        NumericType::SSCALED => {
+            let max = (mask(bits) >> 1) as i64;
+            (value.round() as i64).clamp(-max - 1, max) as u64 & mask(bits)
+        }
+        NumericType::SFLOAT => match bits {
+            16 => f16::from_f32(value).to_bits() as u64,
+            32 => value.to_bits() as u64,
+            64 => (value as f64).to_bits(),
+            _ => unreachable!(),
+        },
+        NumericType::UFLOAT => {
+            // Negative values can't be represented, but NaN must stay NaN.
+            let value = if value.is_nan() {
+                value
+            } else {
+                value.max(0.0)
+            };
+            (f16::from_f32(value).to_bits() >> (15 - bits)) as u64
+        }
+        NumericType::UINT | NumericType::SINT => unreachable!(),
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Error that can happen when encoding or decoding texels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TexelCodecError {
+    /// The size of the provided data is not a multiple of the size of a texel, or, for a
+    /// single texel, not equal to it.
+    DataSizeInvalid { provided: usize, texel_size: usize },
+
+    /// The format does not have a CPU-side texel codec.
+    FormatNotSupported { format: Format },
+}
+
+impl Error for TexelCodecError {}
+
+impl Display for TexelCodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::DataSizeInvalid {
+                provided,
+                texel_size,
+            } => write!(
+                f,
+                "the size of the provided data ({}) does not match the texel size ({})",
+                provided, texel_size,
+            ),
+            Self::FormatNotSupported { format } => write!(
+                f,
+                "the format {:?} does not have a CPU-side texel codec",
+                format,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TexelCodecError, TexelValue};
+    use crate::format::Format;
+
+    fn assert_close(a: [f32; 4], b: [f32; 4], epsilon: f32) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() <= epsilon),
+            "{:?} != {:?}",
+            a,
+            b,
+        );
+    }
+
+    #[test]
+    fn unorm_round_trip() {
+        let mut data = [0; 4];
+        Format::B8G8R8A8_UNORM
+            .encode_texel([1.0, 0.5, 0.0, 1.0], &mut data)
+            .unwrap();
+        assert_eq!(data, [0, 128, 255, 255]);
+
+        let value = Format::B8G8R8A8_UNORM.decode_texel(&data).unwrap();
+        assert_close(value.to_float(), [1.0, 128.0 / 255.0, 0.0, 1.0], 1e-6);
+    }
+
+    #[test]
+    fn packed() {
+        let mut data = [0; 4];
+        Format::A2B10G10R10_UNORM_PACK32
+            .encode_texel([1.0, 0.0, 1.0, 1.0], &mut data)
+            .unwrap();
+        assert_eq!(u32::from_le_bytes(data), 0xfff003ff);
+
+        let mut data = [0; 2];
+        Format::R5G6B5_UNORM_PACK16
+            .encode_texel([0.0, 1.0, 0.0, 1.0], &mut data)
+            .unwrap();
+        assert_eq!(u16::from_le_bytes(data), 0x07e0);
+    }
+
+    #[test]
+    fn ufloat() {
+        let value = [1.5, 0.25, 2.0, 1.0];
+
+        for format in [
+            Format::B10G11R11_UFLOAT_PACK32,
+            Format::E5B9G9R9_UFLOAT_PACK32,
+        ] {
+            let mut data = [0; 4];
+            format.encode_texel(value, &mut data).unwrap();
+            let decoded = format.decode_texel(&data).unwrap().to_float();
+            assert_close(decoded, value, 0.0);
+        }
+    }
+
+    #[test]
+    fn srgb() {
+        let mut data = [0; 4];
+        Format::R8G8B8A8_SRGB
+            .encode_texel([0.2159, 0.0, 1.0, 0.5], &mut data)
+            .unwrap();
+        assert_eq!(data, [128, 0, 255, 128]);
+    }
+
+    #[test]
+    fn integers() {
+        let mut data = [0; 4];
+        Format::R8G8B8A8_SINT
+            .encode_texel([-200i32, -1, 5, 300], &mut data)
+            .unwrap();
+        assert_eq!(
+            Format::R8G8B8A8_SINT.decode_texel(&data).unwrap(),
+            TexelValue::Int([-128, -1, 5, 127]),
+        );
+    }
+
+    #[test]
+    fn depth_stencil() {
+        let mut data = [0; 4];
+        Format::X8_D24_UNORM_PACK32
+            .encode_texel(
+                TexelValue::DepthStencil {
+                    depth: Some(1.0),
+                    stencil: None,
+                },
+                &mut data,
+            )
+            .unwrap();
+        assert_eq!(u32::from_le_bytes(data), 0x00ffffff);
+
+        assert!(matches!(
+            Format::D24_UNORM_S8_UINT.decode_texel(&data),
+            Err(TexelCodecError::FormatNotSupported { .. })
+        ));
+    }
+
+    #[test]
+    fn convert() {
+        let data = [255, 0, 0, 255, 0, 0, 255, 255];
+        let converted = Format::R8G8B8A8_UNORM
+            .convert_texels(&data, Format::R32G32B32A32_SFLOAT)
+            .unwrap();
+        let converted = Format::R32G32B32A32_SFLOAT
+            .convert_texels(&converted, Format::B8G8R8A8_UNORM)
+            .unwrap();
+        assert_eq!(converted, [0, 0, 255, 255, 255, 0, 0, 255]);
+
+        assert!(matches!(
+            Format::R8G8B8A8_UNORM.convert_texels(&data[..7], Format::R8_UNORM),
+            Err(TexelCodecError::DataSizeInvalid { .. })
+        ));
+    }
+}
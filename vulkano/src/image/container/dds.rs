@@ -0,0 +1,286 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{ImageContainer, ImageContainerError, Reader};
+use crate::{format::Format, image::ImageDimensions};
+
+pub(super) const MAGIC: [u8; 4] = *b"DDS ";
+
+const HEADER_SIZE: u32 = 124;
+
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_CUBEMAP_ALLFACES: u32 = 0xFC00;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+
+const D3D10_RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+const D3D10_RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+
+const D3D10_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+pub(super) fn parse(bytes: &[u8]) -> Result<ImageContainer, ImageContainerError> {
+    if !bytes.starts_with(&MAGIC) {
+        return Err(ImageContainerError::UnknownContainer);
+    }
+
+    let mut reader = Reader::new(bytes, MAGIC.len());
+    let size = reader.u32()?;
+    let flags = reader.u32()?;
+    let height = reader.u32()?;
+    let width = reader.u32()?;
+    let _pitch_or_linear_size = reader.u32()?;
+    let depth = reader.u32()?;
+    let mip_map_count = reader.u32()?;
+    reader.skip(11 * 4);
+    let _pf_size = reader.u32()?;
+    let pf_flags = reader.u32()?;
+    let four_cc = reader.u32()?;
+    let rgb_bit_count = reader.u32()?;
+    let masks = [reader.u32()?, reader.u32()?, reader.u32()?, reader.u32()?];
+    let _caps = reader.u32()?;
+    let caps2 = reader.u32()?;
+    reader.skip(3 * 4);
+
+    if size != HEADER_SIZE {
+        return Err(ImageContainerError::HeaderInvalid { field: "dwSize" });
+    }
+
+    if width == 0 || height == 0 {
+        return Err(ImageContainerError::HeaderInvalid { field: "dwWidth" });
+    }
+
+    let mip_levels = if flags & DDSD_MIPMAPCOUNT != 0 {
+        mip_map_count.max(1)
+    } else {
+        1
+    };
+
+    let (format, dimensions, cube_compatible) =
+        if pf_flags & DDPF_FOURCC != 0 && four_cc == u32::from_le_bytes(*b"DX10") {
+            let dxgi_format = reader.u32()?;
+            let resource_dimension = reader.u32()?;
+            let misc_flag = reader.u32()?;
+            let array_size = reader.u32()?.max(1);
+            let _misc_flags2 = reader.u32()?;
+
+            let format = dxgi_format_to_format(dxgi_format)
+                .ok_or(ImageContainerError::FormatNotSupported)?;
+            let cube_compatible = misc_flag & D3D10_RESOURCE_MISC_TEXTURECUBE != 0;
+            let array_layers = if cube_compatible {
+                array_size
+                    .checked_mul(6)
+                    .ok_or(ImageContainerError::HeaderInvalid { field: "arraySize" })?
+            } else {
+                array_size
+            };
+
+            let dimensions = match resource_dimension {
+                D3D10_RESOURCE_DIMENSION_TEXTURE1D => ImageDimensions::Dim1d {
+                    width,
+                    array_layers,
+                },
+                D3D10_RESOURCE_DIMENSION_TEXTURE2D => ImageDimensions::Dim2d {
+                    width,
+                    height,
+                    array_layers,
+                },
+                D3D10_RESOURCE_DIMENSION_TEXTURE3D if array_layers == 1 => ImageDimensions::Dim3d {
+                    width,
+                    height,
+                    depth: depth.max(1),
+                },
+                _ => {
+                    return Err(ImageContainerError::HeaderInvalid {
+                        field: "resourceDimension",
+                    })
+                }
+            };
+
+            (format, dimensions, cube_compatible)
+        } else {
+            let format = if pf_flags & DDPF_FOURCC != 0 {
+                four_cc_to_format(four_cc)
+            } else if pf_flags & (DDPF_RGB | DDPF_LUMINANCE) != 0 {
+                let mut masks = masks;
+
+                if pf_flags & DDPF_ALPHAPIXELS == 0 {
+                    masks[3] = 0;
+                }
+
+                masks_to_format(rgb_bit_count, masks)
+            } else {
+                None
+            }
+            .ok_or(ImageContainerError::FormatNotSupported)?;
+
+            let cube_compatible = caps2 & DDSCAPS2_CUBEMAP != 0;
+
+            if cube_compatible && caps2 & DDSCAPS2_CUBEMAP_ALLFACES != DDSCAPS2_CUBEMAP_ALLFACES {
+                return Err(ImageContainerError::HeaderInvalid { field: "dwCaps2" });
+            }
+
+            let dimensions = if caps2 & DDSCAPS2_VOLUME != 0 && depth > 1 {
+                ImageDimensions::Dim3d {
+                    width,
+                    height,
+                    depth,
+                }
+            } else {
+                ImageDimensions::Dim2d {
+                    width,
+                    height,
+                    array_layers: if cube_compatible { 6 } else { 1 },
+                }
+            };
+
+            (format, dimensions, cube_compatible)
+        };
+
+    let mut container = ImageContainer::new(format, dimensions, mip_levels, cube_compatible)?;
+    let mut data = &bytes[reader.offset..];
+
+    // Each array layer, or cube face, is stored with its complete mip chain.
+    for array_layer in 0..dimensions.array_layers() {
+        for mip_level in 0..mip_levels {
+            let subresource_size = container.subresource_size(mip_level)?;
+
+            if data.len() < subresource_size {
+                return Err(ImageContainerError::UnexpectedEnd);
+            }
+
+            let (subresource, rest) = data.split_at(subresource_size);
+            container.push_subresource(mip_level, array_layer, subresource);
+            data = rest;
+        }
+    }
+
+    Ok(container)
+}
+
+fn dxgi_format_to_format(dxgi_format: u32) -> Option<Format> {
+    Some(match dxgi_format {
+        2 => Format::R32G32B32A32_SFLOAT,
+        3 => Format::R32G32B32A32_UINT,
+        4 => Format::R32G32B32A32_SINT,
+        6 => Format::R32G32B32_SFLOAT,
+        7 => Format::R32G32B32_UINT,
+        8 => Format::R32G32B32_SINT,
+        10 => Format::R16G16B16A16_SFLOAT,
+        11 => Format::R16G16B16A16_UNORM,
+        12 => Format::R16G16B16A16_UINT,
+        13 => Format::R16G16B16A16_SNORM,
+        14 => Format::R16G16B16A16_SINT,
+        16 => Format::R32G32_SFLOAT,
+        17 => Format::R32G32_UINT,
+        18 => Format::R32G32_SINT,
+        24 => Format::A2B10G10R10_UNORM_PACK32,
+        25 => Format::A2B10G10R10_UINT_PACK32,
+        26 => Format::B10G11R11_UFLOAT_PACK32,
+        28 => Format::R8G8B8A8_UNORM,
+        29 => Format::R8G8B8A8_SRGB,
+        30 => Format::R8G8B8A8_UINT,
+        31 => Format::R8G8B8A8_SNORM,
+        32 => Format::R8G8B8A8_SINT,
+        34 => Format::R16G16_SFLOAT,
+        35 => Format::R16G16_UNORM,
+        36 => Format::R16G16_UINT,
+        37 => Format::R16G16_SNORM,
+        38 => Format::R16G16_SINT,
+        40 => Format::D32_SFLOAT,
+        41 => Format::R32_SFLOAT,
+        42 => Format::R32_UINT,
+        43 => Format::R32_SINT,
+        49 => Format::R8G8_UNORM,
+        50 => Format::R8G8_UINT,
+        51 => Format::R8G8_SNORM,
+        52 => Format::R8G8_SINT,
+        54 => Format::R16_SFLOAT,
+        55 => Format::D16_UNORM,
+        56 => Format::R16_UNORM,
+        57 => Format::R16_UINT,
+        58 => Format::R16_SNORM,
+        59 => Format::R16_SINT,
+        61 => Format::R8_UNORM,
+        62 => Format::R8_UINT,
+        63 => Format::R8_SNORM,
+        64 => Format::R8_SINT,
+        67 => Format::E5B9G9R9_UFLOAT_PACK32,
+        71 => Format::BC1_RGBA_UNORM_BLOCK,
+        72 => Format::BC1_RGBA_SRGB_BLOCK,
+        74 => Format::BC2_UNORM_BLOCK,
+        75 => Format::BC2_SRGB_BLOCK,
+        77 => Format::BC3_UNORM_BLOCK,
+        78 => Format::BC3_SRGB_BLOCK,
+        80 => Format::BC4_UNORM_BLOCK,
+        81 => Format::BC4_SNORM_BLOCK,
+        83 => Format::BC5_UNORM_BLOCK,
+        84 => Format::BC5_SNORM_BLOCK,
+        85 => Format::R5G6B5_UNORM_PACK16,
+        86 => Format::A1R5G5B5_UNORM_PACK16,
+        87 | 88 => Format::B8G8R8A8_UNORM,
+        91 | 93 => Format::B8G8R8A8_SRGB,
+        95 => Format::BC6H_UFLOAT_BLOCK,
+        96 => Format::BC6H_SFLOAT_BLOCK,
+        98 => Format::BC7_UNORM_BLOCK,
+        99 => Format::BC7_SRGB_BLOCK,
+        115 => Format::A4R4G4B4_UNORM_PACK16,
+        _ => return None,
+    })
+}
+
+fn four_cc_to_format(four_cc: u32) -> Option<Format> {
+    Some(match &four_cc.to_le_bytes() {
+        b"DXT1" => Format::BC1_RGBA_UNORM_BLOCK,
+        b"DXT2" | b"DXT3" => Format::BC2_UNORM_BLOCK,
+        b"DXT4" | b"DXT5" => Format::BC3_UNORM_BLOCK,
+        b"ATI1" | b"BC4U" => Format::BC4_UNORM_BLOCK,
+        b"BC4S" => Format::BC4_SNORM_BLOCK,
+        b"ATI2" | b"BC5U" => Format::BC5_UNORM_BLOCK,
+        b"BC5S" => Format::BC5_SNORM_BLOCK,
+        // Some formats are identified by their `D3DFORMAT` value instead of a character code.
+        _ => match four_cc {
+            36 => Format::R16G16B16A16_UNORM,
+            110 => Format::R16G16B16A16_SNORM,
+            111 => Format::R16_SFLOAT,
+            112 => Format::R16G16_SFLOAT,
+            113 => Format::R16G16B16A16_SFLOAT,
+            114 => Format::R32_SFLOAT,
+            115 => Format::R32G32_SFLOAT,
+            116 => Format::R32G32B32A32_SFLOAT,
+            _ => return None,
+        },
+    })
+}
+
+/// Returns the format for a bit count and red, green, blue and alpha masks. Luminance formats are
+/// mapped to the red channel.
+fn masks_to_format(bit_count: u32, masks: [u32; 4]) -> Option<Format> {
+    Some(match (bit_count, masks) {
+        (32, [0xFF, 0xFF00, 0xFF0000, 0xFF000000 | 0]) => Format::R8G8B8A8_UNORM,
+        (32, [0xFF0000, 0xFF00, 0xFF, 0xFF000000 | 0]) => Format::B8G8R8A8_UNORM,
+        (32, [0x3FF, 0xFFC00, 0x3FF00000, 0xC0000000]) => Format::A2B10G10R10_UNORM_PACK32,
+        (32, [0xFFFF, 0xFFFF0000, 0, 0]) => Format::R16G16_UNORM,
+        (24, [0xFF0000, 0xFF00, 0xFF, 0]) => Format::B8G8R8_UNORM,
+        (24, [0xFF, 0xFF00, 0xFF0000, 0]) => Format::R8G8B8_UNORM,
+        (16, [0xF800, 0x7E0, 0x1F, 0]) => Format::R5G6B5_UNORM_PACK16,
+        (16, [0x7C00, 0x3E0, 0x1F, 0x8000]) => Format::A1R5G5B5_UNORM_PACK16,
+        (16, [0xF00, 0xF0, 0xF, 0xF000]) => Format::A4R4G4B4_UNORM_PACK16,
+        (16, [0xFFFF, 0, 0, 0]) => Format::R16_UNORM,
+        (16, [0xFF, 0, 0, 0xFF00]) => Format::R8G8_UNORM,
+        (8, [0xFF, 0, 0, 0]) => Format::R8_UNORM,
+        _ => return None,
+    })
+}
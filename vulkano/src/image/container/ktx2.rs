@@ -0,0 +1,161 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{slice, ImageContainer, ImageContainerError, Reader};
+use crate::{format::Format, image::ImageDimensions};
+use std::borrow::Cow;
+
+pub(super) const IDENTIFIER: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+
+const SUPERCOMPRESSION_NONE: u32 = 0;
+const SUPERCOMPRESSION_ZSTD: u32 = 2;
+
+pub(super) fn parse(bytes: &[u8]) -> Result<ImageContainer, ImageContainerError> {
+    if !bytes.starts_with(&IDENTIFIER) {
+        return Err(ImageContainerError::UnknownContainer);
+    }
+
+    let mut reader = Reader::new(bytes, IDENTIFIER.len());
+    let vk_format = reader.u32()?;
+    let _type_size = reader.u32()?;
+    let width = reader.u32()?;
+    let height = reader.u32()?;
+    let depth = reader.u32()?;
+    let layer_count = reader.u32()?;
+    let face_count = reader.u32()?;
+    let level_count = reader.u32()?;
+    let supercompression_scheme = reader.u32()?;
+    // Data format descriptor, key/value data and supercompression global data.
+    reader.skip(32);
+
+    let format = Format::try_from(ash::vk::Format::from_raw(vk_format as i32))
+        .map_err(|_| ImageContainerError::FormatNotSupported)?;
+
+    if width == 0 {
+        return Err(ImageContainerError::HeaderInvalid {
+            field: "pixelWidth",
+        });
+    }
+
+    let cube_compatible = match face_count {
+        1 => false,
+        6 => true,
+        _ => return Err(ImageContainerError::HeaderInvalid { field: "faceCount" }),
+    };
+    let array_layers =
+        layer_count
+            .max(1)
+            .checked_mul(face_count)
+            .ok_or(ImageContainerError::HeaderInvalid {
+                field: "layerCount",
+            })?;
+
+    let dimensions = match (height, depth) {
+        (0, 0) => ImageDimensions::Dim1d {
+            width,
+            array_layers,
+        },
+        (_, 0) => ImageDimensions::Dim2d {
+            width,
+            height,
+            array_layers,
+        },
+        (0, _) => {
+            return Err(ImageContainerError::HeaderInvalid {
+                field: "pixelHeight",
+            })
+        }
+        _ if array_layers == 1 => ImageDimensions::Dim3d {
+            width,
+            height,
+            depth,
+        },
+        _ => {
+            return Err(ImageContainerError::HeaderInvalid {
+                field: "layerCount",
+            })
+        }
+    };
+
+    // A level count of 0 means that the mip levels should be generated after loading.
+    let mip_levels = level_count.max(1);
+
+    let mut container = ImageContainer::new(format, dimensions, mip_levels, cube_compatible)?;
+
+    let level_index = (0..mip_levels)
+        .map(|_| Ok((reader.u64()?, reader.u64()?, reader.u64()?)))
+        .collect::<Result<Vec<_>, ImageContainerError>>()?;
+
+    for (mip_level, (offset, len, uncompressed_len)) in (0..).zip(level_index) {
+        let level_data = decompress(
+            slice(bytes, offset, len)?,
+            uncompressed_len,
+            supercompression_scheme,
+        )?;
+        let subresource_size = container.subresource_size(mip_level)?;
+        let level_size = subresource_size.checked_mul(array_layers as usize).ok_or(
+            ImageContainerError::HeaderInvalid {
+                field: "layerCount",
+            },
+        )?;
+
+        // Within a level, the data is ordered by array layer, then by cube face. The depth slices
+        // of a 3D image follow each other, so they are copied with a single region.
+        if level_data.len() < level_size {
+            return Err(ImageContainerError::UnexpectedEnd);
+        }
+
+        for (array_layer, data) in (0..array_layers).zip(level_data.chunks_exact(subresource_size))
+        {
+            container.push_subresource(mip_level, array_layer, data);
+        }
+    }
+
+    Ok(container)
+}
+
+fn decompress(
+    data: &[u8],
+    uncompressed_len: u64,
+    scheme: u32,
+) -> Result<Cow<'_, [u8]>, ImageContainerError> {
+    match scheme {
+        SUPERCOMPRESSION_NONE => Ok(Cow::Borrowed(data)),
+        #[cfg(feature = "ruzstd")]
+        SUPERCOMPRESSION_ZSTD => {
+            use std::io::Read;
+
+            let decoder = ruzstd::StreamingDecoder::new(data)
+                .map_err(|_| ImageContainerError::SupercompressionFailed)?;
+
+            // The header may claim any length, so don't reserve more than the size of the
+            // compressed data up front, and stop reading once the output is longer than claimed.
+            let mut output = Vec::with_capacity(uncompressed_len.min(data.len() as u64) as usize);
+            decoder
+                .take(uncompressed_len.saturating_add(1))
+                .read_to_end(&mut output)
+                .map_err(|_| ImageContainerError::SupercompressionFailed)?;
+
+            if output.len() as u64 != uncompressed_len {
+                return Err(ImageContainerError::SupercompressionFailed);
+            }
+
+            Ok(Cow::Owned(output))
+        }
+        #[cfg(not(feature = "ruzstd"))]
+        SUPERCOMPRESSION_ZSTD => {
+            let _ = uncompressed_len;
+
+            Err(ImageContainerError::SupercompressionNotSupported { scheme })
+        }
+        _ => Err(ImageContainerError::SupercompressionNotSupported { scheme }),
+    }
+}
@@ -0,0 +1,603 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Loading of images that are stored in container files.
+//!
+//! Two container formats are supported:
+//! - [KTX2](https://registry.khronos.org/KTX/specs/2.0/ktxspec.v2.html), including files that
+//!   are supercompressed with Zstandard if the `ruzstd` feature is enabled. BasisLZ and ZLIB
+//!   supercompression are not supported.
+//! - DDS, both with the legacy pixel format description and with the DX10 header extension.
+//!
+//! A loaded [`ImageContainer`] holds the image data of all mip levels and array layers, together
+//! with the copy regions that are needed to upload it. Use
+//! [`ImmutableImage::from_container`](super::ImmutableImage::from_container) to create an image
+//! from it.
+
+use super::{ImageAspects, ImageDimensions, ImageSubresourceLayers};
+use crate::{command_buffer::BufferImageCopy, format::Format, DeviceSize};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+};
+
+mod dds;
+mod ktx2;
+
+/// The image data of a container file, ready to be uploaded to an image.
+#[derive(Clone, Debug)]
+pub struct ImageContainer {
+    format: Format,
+    dimensions: ImageDimensions,
+    mip_levels: u32,
+    cube_compatible: bool,
+    data: Vec<u8>,
+    regions: Vec<BufferImageCopy>,
+}
+
+impl ImageContainer {
+    /// Loads a container file, detecting the container format from the start of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageContainerError> {
+        if bytes.starts_with(&ktx2::IDENTIFIER) {
+            Self::from_ktx2(bytes)
+        } else if bytes.starts_with(&dds::MAGIC) {
+            Self::from_dds(bytes)
+        } else {
+            Err(ImageContainerError::UnknownContainer)
+        }
+    }
+
+    /// Loads a KTX2 file.
+    ///
+    /// The format of the image is taken from the `vkFormat` field. Files with a `levelCount` of
+    /// 0, which asks the application to generate the mip levels, are loaded with a single mip
+    /// level.
+    #[inline]
+    pub fn from_ktx2(bytes: &[u8]) -> Result<Self, ImageContainerError> {
+        ktx2::parse(bytes)
+    }
+
+    /// Loads a DDS file.
+    ///
+    /// Formats with an `X` channel in place of alpha, such as `DXGI_FORMAT_B8G8R8X8_UNORM`, are
+    /// loaded as the equivalent format with alpha, so the alpha channel of the image must be
+    /// ignored. Cube maps must contain all six faces.
+    #[inline]
+    pub fn from_dds(bytes: &[u8]) -> Result<Self, ImageContainerError> {
+        dds::parse(bytes)
+    }
+
+    /// Returns the format of the image.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the dimensions of the image. For cube maps, each face is a separate array layer,
+    /// in the order +X, -X, +Y, -Y, +Z, -Z.
+    #[inline]
+    pub fn dimensions(&self) -> ImageDimensions {
+        self.dimensions
+    }
+
+    /// Returns the number of mip levels that the container holds.
+    #[inline]
+    pub fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    /// Returns whether the image is a cube map or an array of cube maps, and must be created with
+    /// the `CUBE_COMPATIBLE` flag to be viewed as one.
+    #[inline]
+    pub fn cube_compatible(&self) -> bool {
+        self.cube_compatible
+    }
+
+    /// Returns the image data of all subresources.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the regions to copy from a buffer holding [`data`](Self::data) to the image, one
+    /// for each mip level of each array layer.
+    #[inline]
+    pub fn regions(&self) -> &[BufferImageCopy] {
+        &self.regions
+    }
+
+    fn new(
+        format: Format,
+        dimensions: ImageDimensions,
+        mip_levels: u32,
+        cube_compatible: bool,
+    ) -> Result<Self, ImageContainerError> {
+        let aspects = format.aspects();
+
+        // Multi-planar images and combined depth/stencil images need more than one region per
+        // subresource, with each aspect laid out separately.
+        if !format.planes().is_empty()
+            || aspects.contains(ImageAspects::DEPTH | ImageAspects::STENCIL)
+        {
+            return Err(ImageContainerError::FormatNotSupported);
+        }
+
+        if mip_levels > dimensions.max_mip_levels() {
+            return Err(ImageContainerError::HeaderInvalid {
+                field: "mip level count",
+            });
+        }
+
+        if cube_compatible {
+            match dimensions {
+                ImageDimensions::Dim2d { width, height, .. } if width == height => (),
+                _ => {
+                    return Err(ImageContainerError::HeaderInvalid {
+                        field: "cube map dimensions",
+                    })
+                }
+            }
+        }
+
+        Ok(ImageContainer {
+            format,
+            dimensions,
+            mip_levels,
+            cube_compatible,
+            data: Vec::new(),
+            regions: Vec::new(),
+        })
+    }
+
+    /// Returns the size in bytes of one array layer of a mip level, or an error if it doesn't fit
+    /// in memory.
+    fn subresource_size(&self, mip_level: u32) -> Result<usize, ImageContainerError> {
+        let extent = self.mip_level_extent(mip_level);
+        let block_extent = self.format.block_extent();
+
+        (0..3)
+            .map(|i| (extent[i] as u64 + block_extent[i] as u64 - 1) / block_extent[i] as u64)
+            .try_fold(self.format.block_size().unwrap(), u64::checked_mul)
+            .and_then(|size| usize::try_from(size).ok())
+            .ok_or(ImageContainerError::HeaderInvalid {
+                field: "image extent",
+            })
+    }
+
+    fn mip_level_extent(&self, mip_level: u32) -> [u32; 3] {
+        self.dimensions
+            .mip_level_dimensions(mip_level)
+            .unwrap()
+            .width_height_depth()
+    }
+
+    /// Appends the data of one array layer of a mip level, and the region to copy it.
+    fn push_subresource(&mut self, mip_level: u32, array_layer: u32, data: &[u8]) {
+        // The buffer offset must be a multiple of 4 for depth/stencil formats. For other formats,
+        // it must be a multiple of the block size, which is always true when the subresources are
+        // packed together.
+        if self
+            .format
+            .aspects()
+            .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+        {
+            self.data.resize((self.data.len() + 3) & !3, 0);
+        }
+
+        self.regions.push(BufferImageCopy {
+            buffer_offset: self.data.len() as DeviceSize,
+            image_subresource: ImageSubresourceLayers {
+                mip_level,
+                array_layers: array_layer..array_layer + 1,
+                ..ImageSubresourceLayers::from_parameters(self.format, 1)
+            },
+            image_extent: self.mip_level_extent(mip_level),
+            ..Default::default()
+        });
+        self.data.extend_from_slice(data);
+    }
+}
+
+/// Reads little-endian header fields.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], offset: usize) -> Self {
+        Reader { bytes, offset }
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], ImageContainerError> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset + N)
+            .ok_or(ImageContainerError::UnexpectedEnd)?;
+        self.offset += N;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<u32, ImageContainerError> {
+        self.bytes().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, ImageContainerError> {
+        self.bytes().map(u64::from_le_bytes)
+    }
+
+    fn skip(&mut self, len: usize) {
+        self.offset += len;
+    }
+}
+
+/// Returns `len` bytes of `bytes` starting at `offset`.
+fn slice(bytes: &[u8], offset: u64, len: u64) -> Result<&[u8], ImageContainerError> {
+    usize::try_from(offset)
+        .ok()
+        .zip(usize::try_from(len).ok())
+        .and_then(|(offset, len)| bytes.get(offset..offset.checked_add(len)?))
+        .ok_or(ImageContainerError::UnexpectedEnd)
+}
+
+/// Error that can happen when loading an image from a container file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageContainerError {
+    /// The data does not start with the identifier of a supported container format.
+    UnknownContainer,
+
+    /// The data ends before the end of a header, or before the end of the image data that the
+    /// header describes.
+    UnexpectedEnd,
+
+    /// A header field has an invalid value, or a value that is not consistent with the other
+    /// fields.
+    HeaderInvalid { field: &'static str },
+
+    /// The format of the image data has no equivalent `Format`, or it cannot be uploaded with a
+    /// single copy region per subresource.
+    FormatNotSupported,
+
+    /// The image data is supercompressed with a scheme that is not supported.
+    SupercompressionNotSupported { scheme: u32 },
+
+    /// The supercompressed image data could not be decompressed.
+    SupercompressionFailed,
+}
+
+impl Error for ImageContainerError {}
+
+impl Display for ImageContainerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::UnknownContainer => write!(f, "the data is not in a supported container format"),
+            Self::UnexpectedEnd => write!(
+                f,
+                "the data ended before the end of the header or of the image data",
+            ),
+            Self::HeaderInvalid { field } => {
+                write!(f, "the header field `{}` has an invalid value", field)
+            }
+            Self::FormatNotSupported => write!(f, "the format of the image data is not supported",),
+            Self::SupercompressionNotSupported { scheme } => write!(
+                f,
+                "the image data is supercompressed with an unsupported scheme ({})",
+                scheme,
+            ),
+            Self::SupercompressionFailed => write!(
+                f,
+                "the supercompressed image data could not be decompressed",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageContainer, ImageContainerError};
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        format::Format,
+        image::{ImageAccess, ImageDimensions, ImmutableImage},
+        memory::allocator::StandardMemoryAllocator,
+    };
+
+    fn ktx2_header(
+        vk_format: u32,
+        extent: [u32; 3],
+        layers: u32,
+        faces: u32,
+        levels: u32,
+    ) -> Vec<u8> {
+        let mut bytes = super::ktx2::IDENTIFIER.to_vec();
+
+        for value in [
+            vk_format, 1, extent[0], extent[1], extent[2], layers, faces, levels, 0,
+        ] {
+            bytes.extend(value.to_le_bytes());
+        }
+
+        // Data format descriptor, key/value data and supercompression global data.
+        bytes.extend([0; 32]);
+
+        bytes
+    }
+
+    #[test]
+    fn ktx2_cube_map() {
+        // R8G8B8A8_UNORM, 4x4 with three mip levels and six faces.
+        let mut bytes = ktx2_header(37, [4, 4, 0], 0, 6, 3);
+        let index_start = bytes.len();
+        bytes.resize(index_start + 3 * 24, 0);
+
+        for (level, size) in [(2, 4), (1, 16), (0, 64)] {
+            let offset = bytes.len() as u64;
+            let len = 6 * size as u64;
+            let entry = index_start + level * 24;
+            bytes[entry..entry + 8].copy_from_slice(&offset.to_le_bytes());
+            bytes[entry + 8..entry + 16].copy_from_slice(&len.to_le_bytes());
+            bytes[entry + 16..entry + 24].copy_from_slice(&len.to_le_bytes());
+            bytes.extend((0..len).map(|i| (i / size as u64) as u8));
+        }
+
+        let container = ImageContainer::from_bytes(&bytes).unwrap();
+        assert_eq!(container.format(), Format::R8G8B8A8_UNORM);
+        assert_eq!(
+            container.dimensions(),
+            ImageDimensions::Dim2d {
+                width: 4,
+                height: 4,
+                array_layers: 6,
+            },
+        );
+        assert_eq!(container.mip_levels(), 3);
+        assert!(container.cube_compatible());
+        assert_eq!(container.regions().len(), 18);
+
+        // The fourth face of the second mip level.
+        let region = &container.regions()[9];
+        assert_eq!(region.image_subresource.mip_level, 1);
+        assert_eq!(region.image_subresource.array_layers, 3..4);
+        assert_eq!(region.image_extent, [2, 2, 1]);
+        let offset = region.buffer_offset as usize;
+        assert!(container.data()[offset..offset + 16]
+            .iter()
+            .all(|&b| b == 3));
+    }
+
+    // Appends a level index with one entry per level, and the data of each level after it.
+    fn ktx2_levels(bytes: &mut Vec<u8>, levels: &[(&[u8], u64)]) {
+        let index_start = bytes.len();
+        bytes.resize(index_start + levels.len() * 24, 0);
+
+        for (level, &(data, uncompressed_len)) in levels.iter().enumerate() {
+            let offset = bytes.len() as u64;
+            let entry = index_start + level * 24;
+            bytes[entry..entry + 8].copy_from_slice(&offset.to_le_bytes());
+            bytes[entry + 8..entry + 16].copy_from_slice(&(data.len() as u64).to_le_bytes());
+            bytes[entry + 16..entry + 24].copy_from_slice(&uncompressed_len.to_le_bytes());
+            bytes.extend(data);
+        }
+    }
+
+    #[test]
+    fn ktx2_errors() {
+        let bytes = ktx2_header(37, [4, 4, 0], 0, 1, 1);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::UnexpectedEnd,
+        );
+
+        let bytes = ktx2_header(0, [4, 4, 0], 0, 1, 1);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::FormatNotSupported,
+        );
+
+        // Truncated in the middle of the header.
+        let bytes = ktx2_header(37, [4, 4, 0], 0, 1, 1);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes[..30]).unwrap_err(),
+            ImageContainerError::UnexpectedEnd,
+        );
+
+        // The level data ends before the end of the level.
+        let mut bytes = ktx2_header(37, [4, 4, 0], 0, 1, 1);
+        ktx2_levels(&mut bytes, &[(&[0; 63], 63)]);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::UnexpectedEnd,
+        );
+    }
+
+    #[test]
+    fn ktx2_overflow() {
+        // The layer count times the face count overflows.
+        let bytes = ktx2_header(37, [4, 4, 0], u32::MAX, 6, 1);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::HeaderInvalid {
+                field: "layerCount",
+            },
+        );
+
+        // The size of a level of a large array overflows.
+        let mut bytes = ktx2_header(109, [u32::MAX, 1, 0], u32::MAX, 1, 1);
+        ktx2_levels(&mut bytes, &[(&[], 0)]);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::HeaderInvalid {
+                field: "layerCount",
+            },
+        );
+
+        // The size of a subresource of a large 3D image overflows.
+        let mut bytes = ktx2_header(109, [u32::MAX, u32::MAX, u32::MAX], 0, 1, 1);
+        ktx2_levels(&mut bytes, &[(&[], 0)]);
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::HeaderInvalid {
+                field: "image extent",
+            },
+        );
+    }
+
+    #[test]
+    fn ktx2_zstd() {
+        // R8G8B8A8_UNORM, 2x2, compressed with Zstandard into a frame with a single raw block.
+        let mut bytes = ktx2_header(37, [2, 2, 0], 0, 1, 1);
+        bytes[44..48].copy_from_slice(&2u32.to_le_bytes());
+        let mut frame = vec![0x28, 0xB5, 0x2F, 0xFD, 0x20, 16, 0x81, 0x00, 0x00];
+        frame.extend(0..16);
+        ktx2_levels(&mut bytes, &[(&frame, 16)]);
+
+        #[cfg(feature = "ruzstd")]
+        {
+            let container = ImageContainer::from_ktx2(&bytes).unwrap();
+            assert_eq!(container.data(), (0..16).collect::<Vec<u8>>());
+
+            // The uncompressed length doesn't match the header.
+            let mut bytes = ktx2_header(37, [2, 2, 0], 0, 1, 1);
+            bytes[44..48].copy_from_slice(&2u32.to_le_bytes());
+            ktx2_levels(&mut bytes, &[(&frame, u64::MAX)]);
+            assert_eq!(
+                ImageContainer::from_ktx2(&bytes).unwrap_err(),
+                ImageContainerError::SupercompressionFailed,
+            );
+        }
+
+        #[cfg(not(feature = "ruzstd"))]
+        assert_eq!(
+            ImageContainer::from_ktx2(&bytes).unwrap_err(),
+            ImageContainerError::SupercompressionNotSupported { scheme: 2 },
+        );
+    }
+
+    fn dds_header(height: u32, width: u32, mip_levels: u32, four_cc: [u8; 4]) -> Vec<u8> {
+        let mut bytes = b"DDS ".to_vec();
+        // Size, flags with DDSD_MIPMAPCOUNT, height, width, pitch, depth, mip levels.
+        for value in [124, 0x20000, height, width, 0, 0, mip_levels] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend([0; 44]);
+        // Pixel format with DDPF_FOURCC.
+        for value in [32, 0x4, u32::from_le_bytes(four_cc), 0, 0, 0, 0, 0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend([0; 20]);
+
+        bytes
+    }
+
+    #[test]
+    fn dds_legacy() {
+        // A 6x6 BC3 image with three mip levels, of 2x2, 1x1 and 1x1 blocks.
+        let mut bytes = dds_header(6, 6, 3, *b"DXT5");
+        bytes.extend([0; (4 + 1 + 1) * 16]);
+
+        let container = ImageContainer::from_bytes(&bytes).unwrap();
+        assert_eq!(container.format(), Format::BC3_UNORM_BLOCK);
+        assert_eq!(container.mip_levels(), 3);
+        assert_eq!(
+            container
+                .regions()
+                .iter()
+                .map(|region| (region.buffer_offset, region.image_extent))
+                .collect::<Vec<_>>(),
+            [(0, [6, 6, 1]), (64, [3, 3, 1]), (80, [1, 1, 1])],
+        );
+    }
+
+    #[test]
+    fn dds_dx10_array() {
+        // A 2x2 R16_SFLOAT texture array with three layers.
+        let mut bytes = dds_header(2, 2, 1, *b"DX10");
+        for value in [54u32, 3, 0, 3, 0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend([0; 3 * 8]);
+
+        let container = ImageContainer::from_dds(&bytes).unwrap();
+        assert_eq!(container.format(), Format::R16_SFLOAT);
+        assert_eq!(container.dimensions().array_layers(), 3);
+        assert!(!container.cube_compatible());
+
+        bytes.pop();
+        assert_eq!(
+            ImageContainer::from_dds(&bytes).unwrap_err(),
+            ImageContainerError::UnexpectedEnd,
+        );
+    }
+
+    #[test]
+    fn dds_errors() {
+        // Truncated in the middle of the header.
+        let bytes = dds_header(2, 2, 1, *b"DXT1");
+        assert_eq!(
+            ImageContainer::from_dds(&bytes[..60]).unwrap_err(),
+            ImageContainerError::UnexpectedEnd,
+        );
+
+        // A cube map array whose number of faces overflows.
+        let mut bytes = dds_header(2, 2, 1, *b"DX10");
+        for value in [28u32, 3, 0x4, 0x4000_0000, 0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        assert_eq!(
+            ImageContainer::from_dds(&bytes).unwrap_err(),
+            ImageContainerError::HeaderInvalid { field: "arraySize" },
+        );
+
+        // The size of a subresource overflows.
+        let mut bytes = dds_header(u32::MAX, u32::MAX, 1, *b"DX10");
+        for value in [2u32, 4, 0, 1, 0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        // dwDepth
+        bytes[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            ImageContainer::from_dds(&bytes).unwrap_err(),
+            ImageContainerError::HeaderInvalid {
+                field: "image extent",
+            },
+        );
+    }
+
+    #[test]
+    fn from_container() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        // A 2x2 R8G8B8A8_UNORM texture array with two layers and two mip levels.
+        let mut bytes = dds_header(2, 2, 2, *b"DX10");
+        for value in [28u32, 3, 0, 2, 0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend([0; 2 * (16 + 4)]);
+        let container = ImageContainer::from_dds(&bytes).unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let image =
+            ImmutableImage::from_container(&memory_allocator, &container, &mut cbb).unwrap();
+        assert_eq!(image.format(), Format::R8G8B8A8_UNORM);
+        assert_eq!(image.dimensions(), container.dimensions());
+        assert_eq!(image.mip_levels(), 2);
+        cbb.build().unwrap();
+    }
+}
@@ -10,14 +10,14 @@
 use super::{
     sys::{Image, RawImage},
     traits::ImageContent,
-    ImageAccess, ImageCreateFlags, ImageDescriptorLayouts, ImageDimensions, ImageError, ImageInner,
-    ImageLayout, ImageSubresourceLayers, ImageUsage, MipmapsCount,
+    ImageAccess, ImageContainer, ImageCreateFlags, ImageDescriptorLayouts, ImageDimensions,
+    ImageError, ImageInner, ImageLayout, ImageSubresourceLayers, ImageUsage, MipmapsCount,
 };
 use crate::{
     buffer::{Buffer, BufferContents, BufferCreateInfo, BufferError, BufferUsage, Subbuffer},
     command_buffer::{
-        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BlitImageInfo,
-        BufferImageCopy, CommandBufferBeginError, CopyBufferToImageInfo, ImageBlit,
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BufferImageCopy,
+        CommandBufferBeginError, CopyBufferToImageInfo,
     },
     device::{Device, DeviceOwned},
     format::Format,
@@ -29,7 +29,6 @@ use crate::{
         },
         is_aligned, DedicatedAllocation,
     },
-    sampler::Filter,
     sync::Sharing,
     DeviceSize, VulkanError,
 };
@@ -58,46 +57,6 @@ fn has_mipmaps(mipmaps: MipmapsCount) -> bool {
     }
 }
 
-fn generate_mipmaps<L, Cba>(
-    cbb: &mut AutoCommandBufferBuilder<L, Cba>,
-    image: Arc<dyn ImageAccess>,
-    dimensions: ImageDimensions,
-    _layout: ImageLayout,
-) where
-    Cba: CommandBufferAllocator,
-{
-    for level in 1..image.mip_levels() {
-        let src_size = dimensions
-            .mip_level_dimensions(level - 1)
-            .unwrap()
-            .width_height_depth();
-        let dst_size = dimensions
-            .mip_level_dimensions(level)
-            .unwrap()
-            .width_height_depth();
-
-        cbb.blit_image(BlitImageInfo {
-            regions: [ImageBlit {
-                src_subresource: ImageSubresourceLayers {
-                    mip_level: level - 1,
-                    ..image.subresource_layers()
-                },
-                src_offsets: [[0; 3], src_size],
-                dst_subresource: ImageSubresourceLayers {
-                    mip_level: level,
-                    ..image.subresource_layers()
-                },
-                dst_offsets: [[0; 3], dst_size],
-                ..Default::default()
-            }]
-            .into(),
-            filter: Filter::Linear,
-            ..BlitImageInfo::images(image.clone(), image.clone())
-        })
-        .expect("failed to blit a mip map to image!");
-    }
-}
-
 impl ImmutableImage {
     /// Builds an uninitialized immutable image.
     ///
@@ -292,16 +251,77 @@ impl ImmutableImage {
             .unwrap();
 
         if need_to_generate_mipmaps {
-            generate_mipmaps(
-                command_buffer_builder,
-                image.clone(),
-                image.inner.dimensions(),
-                ImageLayout::ShaderReadOnlyOptimal,
-            );
+            command_buffer_builder
+                .generate_mipmaps(image.clone())
+                .expect("failed to generate the mipmaps of the image");
         }
 
         Ok(image)
     }
+
+    /// Construct an ImmutableImage containing the mip levels and array layers of an image that
+    /// was loaded from a container file.
+    ///
+    /// The data is copied to a single staging buffer, and uploaded with one
+    /// `copy_buffer_to_image` command that is recorded to `command_buffer_builder`. If the
+    /// container holds a cube map, the image is created with the `CUBE_COMPATIBLE` flag.
+    pub fn from_container<L, A>(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        container: &ImageContainer,
+        command_buffer_builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<Arc<Self>, ImmutableImageCreationError>
+    where
+        A: CommandBufferAllocator,
+    {
+        let source = Buffer::from_iter(
+            allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Upload,
+                ..Default::default()
+            },
+            container.data().iter().copied(),
+        )
+        .map_err(|err| match err {
+            BufferError::AllocError(err) => err,
+            // We don't use sparse-binding, concurrent sharing or external memory, therefore the
+            // other errors can't happen.
+            _ => unreachable!(),
+        })?;
+
+        let flags = if container.cube_compatible() {
+            ImageCreateFlags::CUBE_COMPATIBLE
+        } else {
+            ImageCreateFlags::empty()
+        };
+
+        let (image, initializer) = ImmutableImage::uninitialized(
+            allocator,
+            container.dimensions(),
+            container.format(),
+            container.mip_levels(),
+            ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
+            flags,
+            ImageLayout::ShaderReadOnlyOptimal,
+            source
+                .device()
+                .active_queue_family_indices()
+                .iter()
+                .copied(),
+        )?;
+
+        command_buffer_builder
+            .copy_buffer_to_image(CopyBufferToImageInfo {
+                regions: container.regions().iter().cloned().collect(),
+                ..CopyBufferToImageInfo::buffer_image(source, initializer)
+            })
+            .unwrap();
+
+        Ok(image)
+    }
 }
 
 unsafe impl DeviceOwned for ImmutableImage {
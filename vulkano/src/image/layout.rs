@@ -168,11 +168,11 @@ vulkan_enum! {
         device_extensions: [ext_fragment_density_map],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// A layout optimized for images that are used as a fragment shading rate attachment in a
+    /// render pass.
     FragmentShadingRateAttachmentOptimal = FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR {
         device_extensions: [khr_fragment_shading_rate],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -40,6 +40,12 @@
 //! - An `AttachmentImage` can be used when you want to draw to an image.
 //! - An `ImmutableImage` stores data which never need be changed after the initial upload,
 //!   like a texture.
+//! - A `YcbcrImage` stores a multi-planar YCbCr image, like a decoded video frame, and can be
+//!   uploaded to plane by plane.
+//!
+//! If none of these fit, [`Image::new`](crate::image::sys::Image::new) creates a general-purpose
+//! image that keeps track of the layout of each of its subresources. It can be used in command
+//! buffers by wrapping it in a [`TrackedImage`](crate::image::sys::TrackedImage).
 //!
 //! # Low-level information
 //!
@@ -49,14 +55,17 @@
 pub use self::{
     aspect::{ImageAspect, ImageAspects},
     attachment::AttachmentImage,
+    container::{ImageContainer, ImageContainerError},
     immutable::ImmutableImage,
     layout::{ImageDescriptorLayouts, ImageLayout},
+    readback::{CpuImage, ReadbackError, ReadbackTexel},
     storage::StorageImage,
     swapchain::SwapchainImage,
     sys::ImageError,
     traits::{ImageAccess, ImageInner},
     usage::ImageUsage,
     view::{ImageViewAbstract, ImageViewType},
+    ycbcr::{YcbcrImage, YcbcrImageError},
 };
 
 #[cfg(target_os = "linux")]
@@ -65,21 +74,24 @@ pub use self::storage::SubresourceData;
 use crate::{
     format::Format,
     macros::{vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
-    memory::{ExternalMemoryHandleType, ExternalMemoryProperties},
+    memory::{ExternalMemoryHandleType, ExternalMemoryHandleTypes, ExternalMemoryProperties},
     DeviceSize,
 };
 use std::{cmp, ops::Range};
 
 mod aspect;
 pub mod attachment; // TODO: make private
+mod container;
 pub mod immutable; // TODO: make private
 mod layout;
+mod readback;
 mod storage;
 pub mod swapchain; // TODO: make private
 pub mod sys;
 pub mod traits;
 mod usage;
 pub mod view;
+mod ycbcr;
 
 vulkan_bitflags! {
     #[non_exhaustive]
@@ -181,12 +193,15 @@ vulkan_bitflags! {
         device_extensions: [khr_maintenance2],
     },
 
-    /* TODO: enable
-    // TODO: document
+    /// For images with a mutable format, whether the image can be created with usages that are
+    /// not supported by its own format, as long as they are supported by at least one format
+    /// that views of the image can be created with.
+    ///
+    /// Requires `mutable_format`.
     EXTENDED_USAGE = EXTENDED_USAGE {
         api_version: V1_1,
         device_extensions: [khr_maintenance2],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -861,6 +876,104 @@ impl From<ash::vk::ImageFormatProperties> for ImageFormatProperties {
     }
 }
 
+/// The requirements that a format must meet in
+/// [`PhysicalDevice::find_image_format`](crate::device::physical::PhysicalDevice::find_image_format).
+#[derive(Clone, Debug)]
+pub struct ImageFormatQuery {
+    /// The `flags` that the image will have.
+    ///
+    /// The default value is [`ImageCreateFlags::empty()`].
+    pub flags: ImageCreateFlags,
+
+    /// The dimension type that the image will have.
+    ///
+    /// The default value is [`ImageType::Dim2d`].
+    pub image_type: ImageType,
+
+    /// The `tiling` that the image will have.
+    ///
+    /// The default value is [`ImageTiling::Optimal`].
+    pub tiling: ImageTiling,
+
+    /// The `usage` that the image will have.
+    ///
+    /// The default value is [`ImageUsage::empty()`], which must be overridden.
+    pub usage: ImageUsage,
+
+    /// The `stencil_usage` that the image will have, if the format has both a depth and a stencil
+    /// aspect.
+    ///
+    /// See [`ImageFormatInfo::stencil_usage`] for the requirements.
+    ///
+    /// The default value is [`ImageUsage::empty()`].
+    pub stencil_usage: ImageUsage,
+
+    /// The sample counts that must all be supported.
+    ///
+    /// The default value is [`SampleCounts::SAMPLE_1`].
+    pub samples: SampleCounts,
+
+    /// The extent that the image must be able to have.
+    ///
+    /// The default value is `[1; 3]`.
+    pub extent: [u32; 3],
+
+    /// The number of mip levels that the image must be able to have.
+    ///
+    /// The default value is `1`.
+    pub mip_levels: u32,
+
+    /// The number of array layers that the image must be able to have.
+    ///
+    /// The default value is `1`.
+    pub array_layers: u32,
+
+    /// The external memory handle types that must each be importable or exportable with the
+    /// image.
+    ///
+    /// If this is not empty, then the physical device API version must be at least 1.1 or the
+    /// [`khr_external_memory_capabilities`](crate::instance::InstanceExtensions::khr_external_memory_capabilities)
+    /// extension must be enabled on the instance.
+    ///
+    /// The default value is [`ExternalMemoryHandleTypes::empty()`].
+    pub external_memory_handle_types: ExternalMemoryHandleTypes,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ImageFormatQuery {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: ImageCreateFlags::empty(),
+            image_type: ImageType::Dim2d,
+            tiling: ImageTiling::Optimal,
+            usage: ImageUsage::empty(),
+            stencil_usage: ImageUsage::empty(),
+            samples: SampleCounts::SAMPLE_1,
+            extent: [1; 3],
+            mip_levels: 1,
+            array_layers: 1,
+            external_memory_handle_types: ExternalMemoryHandleTypes::empty(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl ImageFormatQuery {
+    /// Returns whether images with `properties` meet the requirements of `self`.
+    pub(crate) fn is_satisfied_by(&self, properties: &ImageFormatProperties) -> bool {
+        properties.sample_counts.contains(self.samples)
+            && properties
+                .max_extent
+                .iter()
+                .zip(self.extent)
+                .all(|(&max, extent)| extent <= max)
+            && self.mip_levels <= properties.max_mip_levels
+            && self.array_layers <= properties.max_array_layers
+    }
+}
+
 /// The image configuration to query in
 /// [`PhysicalDevice::sparse_image_format_properties`](crate::device::physical::PhysicalDevice::sparse_image_format_properties).
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -974,7 +1087,10 @@ mod tests {
             allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
         },
         format::Format,
-        image::{ImageAccess, ImageDimensions, ImmutableImage, MipmapsCount},
+        image::{
+            ImageAccess, ImageDimensions, ImageFormatQuery, ImageUsage, ImmutableImage,
+            MipmapsCount,
+        },
         memory::allocator::StandardMemoryAllocator,
     };
 
@@ -1129,4 +1245,88 @@ mod tests {
             assert_eq!(image.mip_levels(), 10);
         }
     }
+
+    #[test]
+    fn find_image_format() {
+        let (device, _) = gfx_dev_and_queue!();
+        let physical_device = device.physical_device();
+
+        let (format, properties) = physical_device
+            .find_image_format(
+                [Format::R8G8B8A8_UNORM],
+                ImageFormatQuery {
+                    usage: ImageUsage::SAMPLED | ImageUsage::TRANSFER_DST,
+                    extent: [64, 64, 1],
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(format, Format::R8G8B8A8_UNORM);
+        assert!(properties.max_extent[0] >= 64);
+
+        // No device supports this extent.
+        assert!(physical_device
+            .find_image_format(
+                [Format::R8G8B8A8_UNORM],
+                ImageFormatQuery {
+                    usage: ImageUsage::SAMPLED,
+                    extent: [u32::MAX, u32::MAX, 1],
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .is_none());
+
+        // Every device supports at least one of these.
+        let (format, _) = physical_device
+            .find_image_format(
+                Format::all().iter().copied().filter(|format| {
+                    format.type_depth().is_some() && format.type_stencil().is_some()
+                }),
+                ImageFormatQuery {
+                    usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            format,
+            Format::D16_UNORM_S8_UINT | Format::D24_UNORM_S8_UINT | Format::D32_SFLOAT_S8_UINT,
+        ));
+
+        // A separate stencil usage needs Vulkan 1.2 or an extension, but only for the depth/stencil
+        // candidate. If it's not supported, the search continues with the next candidate.
+        let (format, _) = physical_device
+            .find_image_format(
+                [Format::D32_SFLOAT_S8_UINT, Format::R8G8B8A8_UNORM],
+                ImageFormatQuery {
+                    usage: ImageUsage::SAMPLED,
+                    stencil_usage: ImageUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            format,
+            Format::D32_SFLOAT_S8_UINT | Format::R8G8B8A8_UNORM,
+        ));
+    }
+
+    #[test]
+    fn format_table() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let table = device.physical_device().format_table();
+        assert!(table
+            .rows()
+            .iter()
+            .any(|&(format, _)| format == Format::R8G8B8A8_UNORM));
+
+        let text = table.to_string();
+        assert!(text.starts_with("format"));
+        assert!(text.lines().any(|line| line.starts_with("R8G8B8A8_UNORM ")));
+    }
 }
@@ -0,0 +1,121 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::io::{Result as IoResult, Write};
+
+const MAGIC: [u8; 4] = [0x76, 0x2F, 0x31, 0x01];
+
+/// Version 2, single-part scanline file with short attribute names.
+const VERSION: [u8; 4] = [2, 0, 0, 0];
+
+const PIXEL_TYPE_FLOAT: i32 = 2;
+const NO_COMPRESSION: u8 = 0;
+const INCREASING_Y: u8 = 0;
+
+/// Writes an uncompressed scanline file. `channels` holds the name of each channel and the index
+/// of its component within each texel of `components`, and must be sorted by name.
+pub(super) fn write(
+    mut writer: impl Write,
+    width: u32,
+    height: u32,
+    channels: &[(&str, usize)],
+    components: impl IntoIterator<Item = f32>,
+) -> IoResult<()> {
+    debug_assert!(channels.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    let components: Vec<f32> = components.into_iter().collect();
+    let stride = channels.len();
+    let data_window = [0, 0, width as i32 - 1, height as i32 - 1];
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&VERSION);
+
+    let mut channel_list = Vec::new();
+
+    for (name, _) in channels {
+        channel_list.extend_from_slice(name.as_bytes());
+        channel_list.push(0);
+        channel_list.extend_from_slice(&PIXEL_TYPE_FLOAT.to_le_bytes());
+        // pLinear and three reserved bytes.
+        channel_list.extend_from_slice(&[0; 4]);
+        // The x and y sampling.
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+    }
+
+    channel_list.push(0);
+
+    write_attribute(&mut header, "channels", "chlist", &channel_list);
+    write_attribute(&mut header, "compression", "compression", &[NO_COMPRESSION]);
+    write_attribute(&mut header, "dataWindow", "box2i", &box2i(data_window));
+    write_attribute(&mut header, "displayWindow", "box2i", &box2i(data_window));
+    write_attribute(&mut header, "lineOrder", "lineOrder", &[INCREASING_Y]);
+    write_attribute(
+        &mut header,
+        "pixelAspectRatio",
+        "float",
+        &1f32.to_le_bytes(),
+    );
+    write_attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+    write_attribute(
+        &mut header,
+        "screenWindowWidth",
+        "float",
+        &1f32.to_le_bytes(),
+    );
+    header.push(0);
+
+    // Each scanline is a chunk of its own, and the offset table points at every chunk.
+    let scanline_size = width as usize * stride * 4;
+    let chunk_size = 8 + scanline_size;
+    let first_chunk_offset = header.len() + height as usize * 8;
+
+    for y in 0..height as usize {
+        header.extend_from_slice(&((first_chunk_offset + y * chunk_size) as u64).to_le_bytes());
+    }
+
+    writer.write_all(&header)?;
+
+    let mut chunk = Vec::with_capacity(chunk_size);
+
+    for y in 0..height as usize {
+        chunk.clear();
+        chunk.extend_from_slice(&(y as i32).to_le_bytes());
+        chunk.extend_from_slice(&(scanline_size as i32).to_le_bytes());
+
+        let row = &components[y * width as usize * stride..][..width as usize * stride];
+
+        for &(_, component) in channels {
+            for texel in row.chunks_exact(stride) {
+                chunk.extend_from_slice(&texel[component].to_le_bytes());
+            }
+        }
+
+        writer.write_all(&chunk)?;
+    }
+
+    Ok(())
+}
+
+fn write_attribute(header: &mut Vec<u8>, name: &str, attribute_type: &str, value: &[u8]) {
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(attribute_type.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    header.extend_from_slice(value);
+}
+
+fn box2i(values: [i32; 4]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
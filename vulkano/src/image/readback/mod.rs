@@ -0,0 +1,725 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reading images back to the CPU, and writing them to image files.
+//!
+//! A [`CpuImage`] holds the texels of a single subresource of an image, converted to a texel type
+//! that doesn't depend on the format of the image. It can be read back from the device with
+//! [`CpuImage::from_image`], which copies the subresource to a buffer and waits for the copy to
+//! finish, or it can be decoded from data that was already read back with
+//! [`CpuImage::from_texel_data`].
+//!
+//! The texels are decoded with the CPU-side codecs of [`Format`], so the component order of the
+//! format, such as that of `B8G8R8A8_UNORM`, doesn't matter, and sRGB formats are converted to
+//! linear values. Block-compressed formats are decompressed first.
+//!
+//! An image with `[u8; 4]` texels can be written as PNG or PPM, and an image with `[f32; 4]` or
+//! `f32` texels can be written as OpenEXR.
+
+use super::{ImageAccess, ImageAspects, ImageSubresourceLayers};
+use crate::{
+    buffer::{Buffer, BufferCreateInfo, BufferError, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BufferImageCopy, BuildError,
+        CommandBufferBeginError, CommandBufferExecError, CommandBufferUsage, CopyError,
+        CopyImageToBufferInfo, PrimaryCommandBufferAbstract,
+    },
+    device::Queue,
+    format::{CompressionError, Format, NumericType, TexelCodecError, TexelValue},
+    memory::allocator::{AllocationCreateInfo, MemoryAllocator, MemoryUsage},
+    sync::{future::FlushError, GpuFuture},
+    DeviceSize,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    io::{Result as IoResult, Write},
+    sync::Arc,
+};
+
+mod exr;
+mod png;
+mod ppm;
+
+/// The texels of a single subresource of an image, stored on the CPU.
+///
+/// The texels are stored in row-major order. For 3D images, the depth slices follow each other,
+/// and when multiple array layers are read back, the layers follow each other in the same way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuImage<T> {
+    extent: [u32; 3],
+    texels: Vec<T>,
+}
+
+impl<T> CpuImage<T> {
+    /// Creates a new `CpuImage` from texels in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the length of `texels` does not match `extent`.
+    pub fn new(extent: [u32; 3], texels: Vec<T>) -> Self {
+        assert_eq!(texels.len(), texel_count(extent));
+
+        CpuImage { extent, texels }
+    }
+
+    /// Returns the extent of the image. The third component is the depth of a 3D image, or the
+    /// number of array layers that were read back.
+    #[inline]
+    pub fn extent(&self) -> [u32; 3] {
+        self.extent
+    }
+
+    /// Returns the texels of the image.
+    #[inline]
+    pub fn texels(&self) -> &[T] {
+        &self.texels
+    }
+
+    /// Consumes the image and returns its texels.
+    #[inline]
+    pub fn into_texels(self) -> Vec<T> {
+        self.texels
+    }
+
+    /// Returns the texel at the given coordinates, or `None` if they are out of bounds.
+    #[inline]
+    pub fn get(&self, [x, y, z]: [u32; 3]) -> Option<&T> {
+        let [width, height, depth] = self.extent;
+
+        if x >= width || y >= height || z >= depth {
+            return None;
+        }
+
+        self.texels
+            .get(((z as usize * height as usize) + y as usize) * width as usize + x as usize)
+    }
+}
+
+impl<T> CpuImage<T>
+where
+    T: ReadbackTexel,
+{
+    /// Copies a subresource of `image` to a buffer, waits for the copy to finish and decodes it.
+    ///
+    /// `subresource` must select exactly one of the color, depth or stencil aspects. The depth
+    /// and stencil aspects of a combined depth/stencil image are read separately. The image must
+    /// have been created with the `transfer_src` usage, and `queue` must be able to perform the
+    /// copy.
+    pub fn from_image<A>(
+        memory_allocator: &(impl MemoryAllocator + ?Sized),
+        command_buffer_allocator: &A,
+        queue: Arc<Queue>,
+        image: Arc<dyn ImageAccess>,
+        subresource: ImageSubresourceLayers,
+    ) -> Result<Self, ReadbackError>
+    where
+        A: CommandBufferAllocator,
+    {
+        let format = aspect_format(image.format(), subresource.aspects)?;
+        let mip_levels = image.mip_levels();
+        let dimensions = image
+            .dimensions()
+            .mip_level_dimensions(subresource.mip_level)
+            .filter(|_| subresource.mip_level < mip_levels)
+            .ok_or(ReadbackError::MipLevelOutOfRange {
+                mip_level: subresource.mip_level,
+                image_mip_levels: mip_levels,
+            })?;
+        let image_extent = dimensions.width_height_depth();
+        let layer_count = subresource.array_layers.end - subresource.array_layers.start;
+        let extent = [
+            image_extent[0],
+            image_extent[1],
+            image_extent[2] * layer_count.max(1),
+        ];
+        let (row_size, row_count) = block_rows(format, extent)?;
+
+        let buffer = Buffer::new_slice::<u8>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Download,
+                ..Default::default()
+            },
+            (row_size * row_count * extent[2] as usize) as DeviceSize,
+        )?;
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )?;
+        builder.copy_image_to_buffer(CopyImageToBufferInfo {
+            regions: [BufferImageCopy {
+                image_subresource: subresource,
+                image_extent,
+                ..Default::default()
+            }]
+            .into(),
+            ..CopyImageToBufferInfo::image_buffer(image, buffer.clone())
+        })?;
+        builder
+            .build()?
+            .execute(queue)?
+            .then_signal_fence_and_flush()?
+            .wait(None)?;
+
+        let data = buffer.read()?;
+
+        Self::from_texel_data(format, extent, &data, 0, 0)
+    }
+
+    /// Decodes texel data of the given format and extent.
+    ///
+    /// `row_pitch` and `depth_pitch` are the number of bytes between the start of consecutive
+    /// rows and depth slices. A value of 0 means that the data is tightly packed, as it is after
+    /// `copy_image_to_buffer` with a `buffer_row_length` and `buffer_image_height` of 0. For
+    /// block-compressed formats, the pitches are those of the rows and slices of blocks.
+    pub fn from_texel_data(
+        format: Format,
+        extent: [u32; 3],
+        data: &[u8],
+        row_pitch: usize,
+        depth_pitch: usize,
+    ) -> Result<Self, ReadbackError> {
+        let (row_size, row_count) = block_rows(format, extent)?;
+        let slice_size = row_size * row_count;
+        let row_pitch = match row_pitch {
+            0 => row_size,
+            pitch if pitch < row_size => {
+                return Err(ReadbackError::PitchTooSmall {
+                    pitch,
+                    required: row_size,
+                })
+            }
+            pitch => pitch,
+        };
+        let depth_pitch = match depth_pitch {
+            0 => row_pitch * row_count,
+            pitch if pitch < row_pitch * row_count => {
+                return Err(ReadbackError::PitchTooSmall {
+                    pitch,
+                    required: row_pitch * row_count,
+                })
+            }
+            pitch => pitch,
+        };
+        let required = match texel_count(extent) {
+            0 => 0,
+            _ => (extent[2] as usize - 1) * depth_pitch + (row_count - 1) * row_pitch + row_size,
+        };
+
+        if data.len() < required {
+            return Err(ReadbackError::DataSizeInvalid {
+                provided: data.len(),
+                required,
+            });
+        }
+
+        let mut packed = Vec::with_capacity(slice_size * extent[2] as usize);
+
+        for z in 0..extent[2] as usize {
+            for y in 0..row_count {
+                let offset = z * depth_pitch + y * row_pitch;
+                packed.extend_from_slice(&data[offset..offset + row_size]);
+            }
+        }
+
+        let (texel_format, packed) = match format.compression() {
+            Some(_) => (
+                format
+                    .decompressed_format()
+                    .ok_or(ReadbackError::FormatNotSupported { format })?,
+                format.decompress(&packed, extent)?,
+            ),
+            None => (format, packed),
+        };
+        let srgb = texel_format.type_color() == Some(NumericType::SRGB);
+        let texel_size = texel_format.block_size().unwrap() as usize;
+
+        let texels = packed
+            .chunks_exact(texel_size)
+            .map(|texel| Ok(T::from_texel(texel_format.decode_texel(texel)?, srgb)))
+            .collect::<Result<_, TexelCodecError>>()?;
+
+        Ok(CpuImage { extent, texels })
+    }
+}
+
+impl CpuImage<[u8; 4]> {
+    /// Writes the image as an 8-bit RGBA PNG file. The depth slices or array layers of the image
+    /// are stacked vertically.
+    ///
+    /// The image data is stored without compression, so the files are larger than those of an
+    /// image editor, but can be read by any PNG decoder.
+    #[inline]
+    pub fn write_png(&self, writer: impl Write) -> IoResult<()> {
+        png::write(
+            writer,
+            self.extent[0],
+            self.extent[1] * self.extent[2],
+            &self.texels,
+        )
+    }
+
+    /// Writes the image as a binary PPM file. The alpha component is dropped, and the depth
+    /// slices or array layers of the image are stacked vertically.
+    #[inline]
+    pub fn write_ppm(&self, writer: impl Write) -> IoResult<()> {
+        ppm::write(
+            writer,
+            self.extent[0],
+            self.extent[1] * self.extent[2],
+            &self.texels,
+        )
+    }
+}
+
+impl CpuImage<[f32; 4]> {
+    /// Writes the image as an uncompressed OpenEXR file with 32-bit floating-point `R`, `G`, `B`
+    /// and `A` channels. The depth slices or array layers of the image are stacked vertically.
+    #[inline]
+    pub fn write_exr(&self, writer: impl Write) -> IoResult<()> {
+        exr::write(
+            writer,
+            self.extent[0],
+            self.extent[1] * self.extent[2],
+            // The channels of an OpenEXR file must be sorted by name.
+            &[("A", 3), ("B", 2), ("G", 1), ("R", 0)],
+            self.texels.iter().flatten().copied(),
+        )
+    }
+}
+
+impl CpuImage<f32> {
+    /// Writes the image as an uncompressed OpenEXR file with a single 32-bit floating-point `Z`
+    /// channel, which is how depth is usually stored. The depth slices or array layers of the
+    /// image are stacked vertically.
+    #[inline]
+    pub fn write_exr(&self, writer: impl Write) -> IoResult<()> {
+        exr::write(
+            writer,
+            self.extent[0],
+            self.extent[1] * self.extent[2],
+            &[("Z", 0)],
+            self.texels.iter().copied(),
+        )
+    }
+}
+
+/// A type that the texels of a [`CpuImage`] can be decoded into.
+pub trait ReadbackTexel: Sized {
+    /// Converts a decoded texel value. `srgb` is true if the value was decoded from an sRGB
+    /// format, in which case the color components of `value` are already in linear color space.
+    fn from_texel(value: TexelValue, srgb: bool) -> Self;
+}
+
+/// 8-bit RGBA components, as they would be shown on screen.
+///
+/// Values of sRGB formats are converted back to sRGB, and other floating-point and normalized
+/// values are clamped to the range `0.0..=1.0`. Integer values are clamped to `0..=255`. Depth
+/// and stencil values are replicated to the red, green and blue components, so that they appear
+/// as grayscale.
+impl ReadbackTexel for [u8; 4] {
+    fn from_texel(value: TexelValue, srgb: bool) -> Self {
+        let (format, value) = match value {
+            TexelValue::Float(_) if srgb => (Format::R8G8B8A8_SRGB, value),
+            TexelValue::Float(_) => (Format::R8G8B8A8_UNORM, value),
+            TexelValue::Int(_) | TexelValue::Uint(_) => (Format::R8G8B8A8_UINT, value),
+            TexelValue::DepthStencil {
+                depth: Some(depth), ..
+            } => (
+                Format::R8G8B8A8_UNORM,
+                TexelValue::Float([depth, depth, depth, 1.0]),
+            ),
+            TexelValue::DepthStencil { stencil, .. } => {
+                let stencil = stencil.unwrap_or(0) as u64;
+
+                (
+                    Format::R8G8B8A8_UINT,
+                    TexelValue::Uint([stencil, stencil, stencil, 255]),
+                )
+            }
+        };
+
+        let mut texel = [0; 4];
+        format.encode_texel(value, &mut texel).unwrap();
+
+        texel
+    }
+}
+
+/// Floating-point RGBA components, as returned by [`TexelValue::to_float`].
+impl ReadbackTexel for [f32; 4] {
+    #[inline]
+    fn from_texel(value: TexelValue, _srgb: bool) -> Self {
+        value.to_float()
+    }
+}
+
+/// The depth or stencil value, or the first component of a color value.
+impl ReadbackTexel for f32 {
+    #[inline]
+    fn from_texel(value: TexelValue, _srgb: bool) -> Self {
+        value.to_float()[0]
+    }
+}
+
+/// Returns the format that the texels of `aspects` of an image with `format` have in a buffer.
+fn aspect_format(format: Format, aspects: ImageAspects) -> Result<Format, ReadbackError> {
+    if !(aspects == ImageAspects::COLOR
+        || aspects == ImageAspects::DEPTH
+        || aspects == ImageAspects::STENCIL)
+        || !format.aspects().contains(aspects)
+    {
+        return Err(ReadbackError::AspectsNotSupported { aspects });
+    }
+
+    if aspects == ImageAspects::STENCIL {
+        return Ok(Format::S8_UINT);
+    }
+
+    let aspect_format = match format {
+        Format::D16_UNORM_S8_UINT if aspects == ImageAspects::DEPTH => Format::D16_UNORM,
+        Format::D24_UNORM_S8_UINT if aspects == ImageAspects::DEPTH => Format::X8_D24_UNORM_PACK32,
+        Format::D32_SFLOAT_S8_UINT if aspects == ImageAspects::DEPTH => Format::D32_SFLOAT,
+        _ if !format.planes().is_empty() => {
+            return Err(ReadbackError::FormatNotSupported { format })
+        }
+        _ => format,
+    };
+
+    Ok(aspect_format)
+}
+
+/// Returns the size in bytes of a tightly packed row of texel blocks, and the number of rows in
+/// a depth slice.
+fn block_rows(format: Format, extent: [u32; 3]) -> Result<(usize, usize), ReadbackError> {
+    let block_size = format
+        .block_size()
+        .filter(|_| format.planes().is_empty())
+        .ok_or(ReadbackError::FormatNotSupported { format })?;
+    let block_extent = format.block_extent();
+
+    Ok((
+        ((extent[0] + block_extent[0] - 1) / block_extent[0]) as usize * block_size as usize,
+        ((extent[1] + block_extent[1] - 1) / block_extent[1]) as usize,
+    ))
+}
+
+fn texel_count(extent: [u32; 3]) -> usize {
+    extent.iter().map(|&c| c as usize).product()
+}
+
+/// Error that can happen when reading back an image.
+#[derive(Clone, Debug)]
+pub enum ReadbackError {
+    /// The subresource does not select exactly one of the color, depth or stencil aspects, or
+    /// the image does not have the selected aspect.
+    AspectsNotSupported { aspects: ImageAspects },
+
+    /// The texels of the format can't be decoded on the CPU.
+    FormatNotSupported { format: Format },
+
+    /// The mip level of the subresource is not less than the number of mip levels in the image.
+    MipLevelOutOfRange {
+        mip_level: u32,
+        image_mip_levels: u32,
+    },
+
+    /// A row or depth pitch is smaller than the size of a row or depth slice.
+    PitchTooSmall { pitch: usize, required: usize },
+
+    /// The provided data is smaller than the size required for the extent and pitches.
+    DataSizeInvalid { provided: usize, required: usize },
+
+    /// Decoding a texel failed.
+    TexelCodecError(TexelCodecError),
+
+    /// Decompressing the data failed.
+    CompressionError(CompressionError),
+
+    /// Creating or reading the buffer that the image is copied to failed.
+    BufferError(BufferError),
+
+    /// Beginning the command buffer failed.
+    CommandBufferBeginError(CommandBufferBeginError),
+
+    /// Recording the copy command failed.
+    CopyError(CopyError),
+
+    /// Building the command buffer failed.
+    BuildError(BuildError),
+
+    /// Executing the command buffer failed.
+    CommandBufferExecError(CommandBufferExecError),
+
+    /// Submitting the command buffer or waiting for it to finish failed.
+    FlushError(FlushError),
+}
+
+impl Error for ReadbackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TexelCodecError(err) => Some(err),
+            Self::CompressionError(err) => Some(err),
+            Self::BufferError(err) => Some(err),
+            Self::CommandBufferBeginError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+            Self::BuildError(err) => Some(err),
+            Self::CommandBufferExecError(err) => Some(err),
+            Self::FlushError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ReadbackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AspectsNotSupported { aspects } => write!(
+                f,
+                "the aspects {:?} are not exactly one of the color, depth or stencil aspects of \
+                the image",
+                aspects,
+            ),
+            Self::FormatNotSupported { format } => write!(
+                f,
+                "the texels of the format {:?} can't be decoded on the CPU",
+                format,
+            ),
+            Self::MipLevelOutOfRange {
+                mip_level,
+                image_mip_levels,
+            } => write!(
+                f,
+                "the mip level ({}) is not less than the number of mip levels in the image ({})",
+                mip_level, image_mip_levels,
+            ),
+            Self::PitchTooSmall { pitch, required } => write!(
+                f,
+                "the pitch ({}) is smaller than the size of a row or slice ({})",
+                pitch, required,
+            ),
+            Self::DataSizeInvalid { provided, required } => write!(
+                f,
+                "the size of the provided data ({}) is smaller than the size required for the \
+                extent ({})",
+                provided, required,
+            ),
+            Self::TexelCodecError(_) => write!(f, "decoding a texel failed"),
+            Self::CompressionError(_) => write!(f, "decompressing the data failed"),
+            Self::BufferError(_) => write!(f, "creating or reading the buffer failed"),
+            Self::CommandBufferBeginError(_) => write!(f, "beginning the command buffer failed"),
+            Self::CopyError(_) => write!(f, "recording the copy command failed"),
+            Self::BuildError(_) => write!(f, "building the command buffer failed"),
+            Self::CommandBufferExecError(_) => write!(f, "executing the command buffer failed"),
+            Self::FlushError(_) => write!(f, "submitting the command buffer failed"),
+        }
+    }
+}
+
+impl From<TexelCodecError> for ReadbackError {
+    fn from(err: TexelCodecError) -> Self {
+        Self::TexelCodecError(err)
+    }
+}
+
+impl From<CompressionError> for ReadbackError {
+    fn from(err: CompressionError) -> Self {
+        Self::CompressionError(err)
+    }
+}
+
+impl From<BufferError> for ReadbackError {
+    fn from(err: BufferError) -> Self {
+        Self::BufferError(err)
+    }
+}
+
+impl From<CommandBufferBeginError> for ReadbackError {
+    fn from(err: CommandBufferBeginError) -> Self {
+        Self::CommandBufferBeginError(err)
+    }
+}
+
+impl From<CopyError> for ReadbackError {
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<BuildError> for ReadbackError {
+    fn from(err: BuildError) -> Self {
+        Self::BuildError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for ReadbackError {
+    fn from(err: CommandBufferExecError) -> Self {
+        Self::CommandBufferExecError(err)
+    }
+}
+
+impl From<FlushError> for ReadbackError {
+    fn from(err: FlushError) -> Self {
+        Self::FlushError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CpuImage, ReadbackError};
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            ClearColorImageInfo, CommandBufferUsage, PrimaryCommandBufferAbstract,
+        },
+        format::{ClearColorValue, Format},
+        image::{
+            sys::{Image, ImageCreateInfo, TrackedImage},
+            ImageAspects, ImageDimensions, ImageSubresourceLayers, ImageUsage,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn bgra_srgb_with_row_pitch() {
+        // Two rows of two texels, each followed by 4 bytes of padding.
+        let data = [
+            0, 0, 255, 255, 255, 0, 0, 255, 0, 0, 0, 0, //
+            128, 128, 128, 255, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let image =
+            CpuImage::<[u8; 4]>::from_texel_data(Format::B8G8R8A8_SRGB, [2, 2, 1], &data, 12, 0)
+                .unwrap();
+        assert_eq!(
+            image.texels(),
+            [
+                [255, 0, 0, 255],
+                [0, 0, 255, 255],
+                [128, 128, 128, 255],
+                [0, 0, 0, 0]
+            ],
+        );
+        assert_eq!(image.get([0, 1, 0]), Some(&[128, 128, 128, 255]));
+        assert_eq!(image.get([2, 0, 0]), None);
+
+        let image =
+            CpuImage::<[f32; 4]>::from_texel_data(Format::B8G8R8A8_SRGB, [2, 2, 1], &data, 12, 0)
+                .unwrap();
+        assert!((image.texels()[2][0] - 0.2158).abs() < 1e-4);
+
+        assert!(matches!(
+            CpuImage::<f32>::from_texel_data(Format::B8G8R8A8_SRGB, [2, 2, 1], &data, 4, 0),
+            Err(ReadbackError::PitchTooSmall {
+                pitch: 4,
+                required: 8,
+            })
+        ));
+        assert!(matches!(
+            CpuImage::<f32>::from_texel_data(Format::B8G8R8A8_SRGB, [2, 3, 1], &data, 12, 0),
+            Err(ReadbackError::DataSizeInvalid {
+                provided: 24,
+                required: 32,
+            })
+        ));
+    }
+
+    #[test]
+    fn write_files() {
+        let image = CpuImage::new([2, 1, 1], vec![[255, 0, 0, 255], [0, 255, 0, 128]]);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\xff\x00\x00\x00\xff\x00");
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        assert!(png.ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"));
+
+        let depth = CpuImage::new([2, 1, 1], vec![0.5f32, 1.0]);
+        let mut exr = Vec::new();
+        depth.write_exr(&mut exr).unwrap();
+        assert!(exr.starts_with(b"\x76\x2f\x31\x01\x02\x00\x00\x00channels\0chlist\0"));
+        // One scanline with its y coordinate, size and two floats.
+        assert!(exr.ends_with(&[0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0x3f, 0, 0, 0x80, 0x3f]));
+    }
+
+    #[test]
+    fn read_cleared_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let image = TrackedImage::new(
+            Image::new(
+                &memory_allocator,
+                ImageCreateInfo {
+                    dimensions: ImageDimensions::Dim2d {
+                        width: 4,
+                        height: 4,
+                        array_layers: 1,
+                    },
+                    format: Some(Format::B8G8R8A8_UNORM),
+                    usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        );
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.clear_color_image(ClearColorImageInfo {
+            clear_value: ClearColorValue::Float([1.0, 0.0, 0.0, 1.0]),
+            ..ClearColorImageInfo::image(image.clone())
+        })
+        .unwrap();
+        cbb.build()
+            .unwrap()
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let cpu_image = CpuImage::<[u8; 4]>::from_image(
+            &memory_allocator,
+            &cb_allocator,
+            queue,
+            image,
+            ImageSubresourceLayers {
+                aspects: ImageAspects::COLOR,
+                mip_level: 0,
+                array_layers: 0..1,
+            },
+        )
+        .unwrap();
+        assert_eq!(cpu_image.extent(), [4, 4, 1]);
+        assert!(cpu_image
+            .texels()
+            .iter()
+            .all(|&texel| texel == [255, 0, 0, 255]));
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::io::{Result as IoResult, Write};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// The maximum length of a stored (uncompressed) deflate block.
+const MAX_STORED_BLOCK_LEN: usize = 0xFFFF;
+
+pub(super) fn write(
+    mut writer: impl Write,
+    width: u32,
+    height: u32,
+    texels: &[[u8; 4]],
+) -> IoResult<()> {
+    writer.write_all(&SIGNATURE)?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // Bit depth 8, color type RGBA, default compression, filtering and no interlacing.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(&mut writer, *b"IHDR", &header)?;
+
+    // Each scanline starts with the filter type, which is always "none".
+    let mut scanlines = Vec::with_capacity(texels.len() * 4 + height as usize);
+
+    if width != 0 {
+        for row in texels.chunks_exact(width as usize) {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flatten());
+        }
+    }
+
+    write_chunk(&mut writer, *b"IDAT", &zlib_stored(&scanlines))?;
+    write_chunk(&mut writer, *b"IEND", &[])
+}
+
+fn write_chunk(writer: &mut impl Write, chunk_type: [u8; 4], data: &[u8]) -> IoResult<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(&chunk_type)?;
+    writer.write_all(data)?;
+
+    let crc = crc32(crc32(!0, &chunk_type), data);
+    writer.write_all(&(!crc).to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream made of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let block_count = (data.len() / MAX_STORED_BLOCK_LEN).max(1) + 1;
+    let mut output = Vec::with_capacity(data.len() + block_count * 5 + 6);
+    // Deflate with a 32K window, and no preset dictionary.
+    output.extend_from_slice(&[0x78, 0x01]);
+
+    let mut blocks = data.chunks(MAX_STORED_BLOCK_LEN).peekable();
+
+    if blocks.peek().is_none() {
+        output.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let len = block.len() as u16;
+        output.push(is_final as u8);
+        output.extend_from_slice(&len.to_le_bytes());
+        output.extend_from_slice(&(!len).to_le_bytes());
+        output.extend_from_slice(block);
+    }
+
+    output.extend_from_slice(&adler32(data).to_be_bytes());
+
+    output
+}
+
+fn crc32(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xEDB88320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1, 0);
+
+    // 5552 is the largest number of bytes for which `b` can't overflow before the modulo.
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+
+        a %= MOD;
+        b %= MOD;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adler32, crc32};
+
+    #[test]
+    fn checksums() {
+        assert_eq!(!crc32(!0, b"IEND"), 0xAE426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::io::{Result as IoResult, Write};
+
+pub(super) fn write(
+    mut writer: impl Write,
+    width: u32,
+    height: u32,
+    texels: &[[u8; 4]],
+) -> IoResult<()> {
+    write!(writer, "P6\n{} {}\n255\n", width, height)?;
+
+    let data: Vec<u8> = texels.iter().flat_map(|&[r, g, b, _a]| [r, g, b]).collect();
+    writer.write_all(&data)
+}
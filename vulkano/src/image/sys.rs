@@ -14,9 +14,10 @@
 //! that you create must wrap around the types in this module.
 
 use super::{
-    ImageAspect, ImageAspects, ImageCreateFlags, ImageDimensions, ImageLayout,
-    ImageSubresourceLayers, ImageSubresourceRange, ImageTiling, ImageUsage, SampleCount,
-    SampleCounts, SparseImageMemoryRequirements,
+    traits::{ImageAccess, ImageInner},
+    ImageAspect, ImageAspects, ImageCreateFlags, ImageDescriptorLayouts, ImageDimensions,
+    ImageLayout, ImageSubresourceLayers, ImageSubresourceRange, ImageTiling, ImageUsage,
+    SampleCount, SampleCounts, SparseImageMemoryRequirements,
 };
 use crate::{
     buffer::subbuffer::{ReadLockError, WriteLockError},
@@ -29,8 +30,11 @@ use crate::{
     },
     macros::impl_id_counter,
     memory::{
-        allocator::{AllocationCreationError, AllocationType, DeviceLayout, MemoryAlloc},
-        is_aligned, DedicatedTo, DeviceAlignment, ExternalMemoryHandleType,
+        allocator::{
+            AllocationCreateInfo, AllocationCreationError, AllocationType, DeviceLayout,
+            MemoryAlloc, MemoryAllocator,
+        },
+        is_aligned, DedicatedAllocation, DedicatedTo, DeviceAlignment, ExternalMemoryHandleType,
         ExternalMemoryHandleTypes, MemoryPropertyFlags, MemoryRequirements,
     },
     range_map::RangeMap,
@@ -45,7 +49,7 @@ use std::{
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
     hash::{Hash, Hasher},
-    iter::{FusedIterator, Peekable},
+    iter::{self, FusedIterator, Peekable},
     mem::{size_of_val, MaybeUninit},
     num::NonZeroU64,
     ops::Range,
@@ -76,6 +80,7 @@ pub struct RawImage {
     sharing: Sharing<SmallVec<[u32; 4]>>,
     stencil_usage: ImageUsage,
     external_memory_handle_types: ExternalMemoryHandleTypes,
+    view_formats: Vec<Format>,
 
     memory_requirements: SmallVec<[MemoryRequirements; 3]>,
     needs_destruction: bool, // `vkDestroyImage` is called only if true.
@@ -126,6 +131,7 @@ impl RawImage {
             flags,
             dimensions,
             format,
+            ref view_formats,
             mip_levels,
             samples,
             tiling,
@@ -217,7 +223,7 @@ impl RawImage {
         }
 
         // Get format features
-        let format_features = {
+        let tiling_format_features = |format: Format| {
             // Use unchecked, because all validation has been done above.
             let format_properties = unsafe { physical_device.format_properties_unchecked(format) };
             match tiling {
@@ -226,15 +232,88 @@ impl RawImage {
                 ImageTiling::DrmFormatModifier => format_properties.linear_tiling_features, // TODO: Improve
             }
         };
-
-        // TODO: VUID-VkImageCreateInfo-tiling-02353
-        // Vulkano currently has no high-level way to add or check for VkImageFormatListCreateInfo.
+        let format_features = tiling_format_features(format);
 
         // Format isn't supported at all?
         if format_features.is_empty() {
             return Err(ImageError::FormatNotSupported);
         }
 
+        if !view_formats.is_empty() {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_image_format_list)
+            {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.view_formats` is not empty",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_2),
+                        device_extensions: &["khr_image_format_list"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkImageCreateInfo-flags-04738
+            if !flags.intersects(ImageCreateFlags::MUTABLE_FORMAT) && view_formats.len() > 1 {
+                return Err(ImageError::ViewFormatsMultipleWithoutMutableFormat);
+            }
+
+            for (index, &view_format) in view_formats.iter().enumerate() {
+                // VUID-VkImageFormatListCreateInfo-pViewFormats-parameter
+                view_format.validate_device(device)?;
+
+                // VUID-VkImageCreateInfo-pNext-06722
+                let is_compatible = if flags
+                    .intersects(ImageCreateFlags::BLOCK_TEXEL_VIEW_COMPATIBLE)
+                    && view_format.compression().is_none()
+                {
+                    view_format.block_size() == format.block_size()
+                } else {
+                    view_format.compatibility() == format.compatibility()
+                        || format
+                            .planes()
+                            .iter()
+                            .any(|plane| view_format.compatibility() == plane.compatibility())
+                };
+
+                if !is_compatible {
+                    return Err(ImageError::ViewFormatNotCompatible { index, view_format });
+                }
+            }
+        }
+
+        // VUID-VkImageCreateInfo-tiling-02353
+        if tiling == ImageTiling::DrmFormatModifier
+            && flags.intersects(ImageCreateFlags::MUTABLE_FORMAT)
+            && view_formats.is_empty()
+        {
+            return Err(ImageError::DrmFormatModifierMutableFormatRequiresViewFormats);
+        }
+
+        // With the `extended_usage` flag, a usage only needs to be supported by one of the formats
+        // that views of the image can have.
+        let usage_format_features = if flags.intersects(ImageCreateFlags::EXTENDED_USAGE) {
+            if view_formats.is_empty() {
+                Format::all()
+                    .iter()
+                    .filter(|view_format| {
+                        view_format.compatibility() == format.compatibility()
+                            && view_format.validate_device(device).is_ok()
+                    })
+                    .fold(format_features, |features, &view_format| {
+                        features | tiling_format_features(view_format)
+                    })
+            } else {
+                view_formats
+                    .iter()
+                    .fold(format_features, |features, &view_format| {
+                        features | tiling_format_features(view_format)
+                    })
+            }
+        } else {
+            format_features
+        };
+
         // Decode the dimensions
         let (image_type, extent, array_layers) = match dimensions {
             ImageDimensions::Dim1d {
@@ -368,13 +447,13 @@ impl RawImage {
         let combined_usage = usage | stencil_usage;
 
         if combined_usage.intersects(ImageUsage::SAMPLED)
-            && !format_features.intersects(FormatFeatures::SAMPLED_IMAGE)
+            && !usage_format_features.intersects(FormatFeatures::SAMPLED_IMAGE)
         {
             return Err(ImageError::FormatUsageNotSupported { usage: "sampled" });
         }
 
         if combined_usage.intersects(ImageUsage::COLOR_ATTACHMENT)
-            && !format_features.intersects(FormatFeatures::COLOR_ATTACHMENT)
+            && !usage_format_features.intersects(FormatFeatures::COLOR_ATTACHMENT)
         {
             return Err(ImageError::FormatUsageNotSupported {
                 usage: "color_attachment",
@@ -382,7 +461,7 @@ impl RawImage {
         }
 
         if combined_usage.intersects(ImageUsage::DEPTH_STENCIL_ATTACHMENT)
-            && !format_features.intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT)
+            && !usage_format_features.intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT)
         {
             return Err(ImageError::FormatUsageNotSupported {
                 usage: "depth_stencil_attachment",
@@ -390,7 +469,7 @@ impl RawImage {
         }
 
         if combined_usage.intersects(ImageUsage::INPUT_ATTACHMENT)
-            && !format_features.intersects(
+            && !usage_format_features.intersects(
                 FormatFeatures::COLOR_ATTACHMENT | FormatFeatures::DEPTH_STENCIL_ATTACHMENT,
             )
         {
@@ -399,6 +478,24 @@ impl RawImage {
             });
         }
 
+        if combined_usage.intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT) {
+            // VUID-VkImageCreateInfo-imageType-02082
+            if image_type != ImageType::Dim2d {
+                return Err(ImageError::FragmentShadingRateAttachmentNot2d);
+            }
+
+            // VUID-VkImageCreateInfo-samples-02083
+            if samples != SampleCount::Sample1 {
+                return Err(ImageError::FragmentShadingRateAttachmentMultisampled);
+            }
+
+            if !usage_format_features.intersects(FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT) {
+                return Err(ImageError::FormatUsageNotSupported {
+                    usage: "fragment_shading_rate_attachment",
+                });
+            }
+        }
+
         if combined_usage.intersects(
             ImageUsage::COLOR_ATTACHMENT
                 | ImageUsage::DEPTH_STENCIL_ATTACHMENT
@@ -423,7 +520,7 @@ impl RawImage {
         }
 
         if combined_usage.intersects(ImageUsage::STORAGE) {
-            if !format_features.intersects(FormatFeatures::STORAGE_IMAGE) {
+            if !usage_format_features.intersects(FormatFeatures::STORAGE_IMAGE) {
                 return Err(ImageError::FormatUsageNotSupported { usage: "storage" });
             }
 
@@ -447,7 +544,7 @@ impl RawImage {
         // These flags only exist in later versions, ignore them otherwise
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
             if combined_usage.intersects(ImageUsage::TRANSFER_SRC)
-                && !format_features.intersects(FormatFeatures::TRANSFER_SRC)
+                && !usage_format_features.intersects(FormatFeatures::TRANSFER_SRC)
             {
                 return Err(ImageError::FormatUsageNotSupported {
                     usage: "transfer_src",
@@ -455,7 +552,7 @@ impl RawImage {
             }
 
             if combined_usage.intersects(ImageUsage::TRANSFER_DST)
-                && !format_features.intersects(FormatFeatures::TRANSFER_DST)
+                && !usage_format_features.intersects(FormatFeatures::TRANSFER_DST)
             {
                 return Err(ImageError::FormatUsageNotSupported {
                     usage: "transfer_dst",
@@ -876,6 +973,7 @@ impl RawImage {
             flags,
             dimensions,
             format,
+            ref view_formats,
             mip_levels,
             samples,
             tiling,
@@ -947,6 +1045,9 @@ impl RawImage {
         };
         let mut external_memory_info_vk = None;
         let mut stencil_usage_info_vk = None;
+        let view_formats_vk: SmallVec<[ash::vk::Format; 4]> =
+            view_formats.iter().copied().map(Into::into).collect();
+        let mut format_list_info_vk = None;
 
         if !external_memory_handle_types.is_empty() {
             let next = external_memory_info_vk.insert(ash::vk::ExternalMemoryImageCreateInfo {
@@ -968,6 +1069,17 @@ impl RawImage {
             info_vk.p_next = next as *const _ as *const _;
         }
 
+        if !view_formats_vk.is_empty() {
+            let next = format_list_info_vk.insert(ash::vk::ImageFormatListCreateInfo {
+                view_format_count: view_formats_vk.len() as u32,
+                p_view_formats: view_formats_vk.as_ptr(),
+                ..Default::default()
+            });
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
         if external_memory_handle_types.intersects(ExternalMemoryHandleTypes::DMA_BUF) {
             let next = image_drm_format_modifier_create_info.as_mut().unwrap();
 
@@ -1013,6 +1125,7 @@ impl RawImage {
             flags,
             dimensions,
             format,
+            view_formats,
             mip_levels,
             samples,
             tiling,
@@ -1075,6 +1188,7 @@ impl RawImage {
             stencil_usage,
             sharing,
             external_memory_handle_types,
+            view_formats,
             memory_requirements,
             needs_destruction,
             subresource_layout: OnceCache::new(),
@@ -1701,6 +1815,13 @@ impl RawImage {
         self.stencil_usage
     }
 
+    /// Returns the formats that views of the image can have, if they were listed when the image
+    /// was created.
+    #[inline]
+    pub fn view_formats(&self) -> &[Format] {
+        &self.view_formats
+    }
+
     /// Returns the sharing the image was created with.
     #[inline]
     pub fn sharing(&self) -> &Sharing<SmallVec<[u32; 4]>> {
@@ -1936,6 +2057,22 @@ pub struct ImageCreateInfo {
     /// The default value is `None`, which must be overridden.
     pub format: Option<Format>,
 
+    /// The formats that image views created from the image will have.
+    ///
+    /// If this is not empty, then image views of the image can only be created with one of the
+    /// listed formats, so it usually includes `format` itself. Each format must be compatible
+    /// with `format`, and if `flags` does not contain `mutable_format`, there can be at most one.
+    /// The implementation may be able to use a more efficient layout for the image when it knows
+    /// all of the formats that it will be viewed as.
+    ///
+    /// If this is not empty, then the device API version must be at least 1.2, or the
+    /// [`khr_image_format_list`](crate::device::DeviceExtensions::khr_image_format_list)
+    /// extension must be enabled on the device. It must not be empty if `tiling` is
+    /// [`ImageTiling::DrmFormatModifier`] and `flags` contains `mutable_format`.
+    ///
+    /// The default value is empty.
+    pub view_formats: Vec<Format>,
+
     /// The number of mip levels to create the image with.
     ///
     /// The default value is `1`.
@@ -2011,6 +2148,7 @@ impl Default for ImageCreateInfo {
                 array_layers: 1,
             },
             format: None,
+            view_formats: Vec::new(),
             mip_levels: 1,
             samples: SampleCount::Sample1,
             tiling: ImageTiling::Optimal,
@@ -2028,10 +2166,18 @@ impl Default for ImageCreateInfo {
 /// A multi-dimensioned storage for texel data.
 ///
 /// Unlike [`RawImage`], an `Image` has memory backing it, and can be used normally.
+///
+/// An `Image` created with [`Image::new`] keeps track of the layout that every subresource was
+/// left in by the command buffers that were submitted so far. Wrapped in a [`TrackedImage`], it
+/// can be used in an [`AutoCommandBufferBuilder`], which will transition each subresource from its
+/// current layout to the layout that a command needs, and leave it in the layout of its last use.
+///
+/// [`AutoCommandBufferBuilder`]: crate::command_buffer::AutoCommandBufferBuilder
 #[derive(Debug)]
 pub struct Image {
     inner: RawImage,
     memory: ImageMemory,
+    tracks_layouts: bool,
 
     aspect_list: SmallVec<[ImageAspect; 4]>,
     aspect_size: DeviceSize,
@@ -2061,8 +2207,66 @@ pub enum ImageMemory {
 }
 
 impl Image {
+    /// Creates a new image, allocates memory for it and binds the memory to it.
+    ///
+    /// If `image_info.flags` contains `DISJOINT`, a separate allocation is made for each plane of
+    /// the format.
+    ///
+    /// Unlike the images that are created through [`RawImage::bind_memory`], the returned image
+    /// tracks the layout of each of its subresources across command buffers. To use it in a
+    /// command buffer, wrap it in a [`TrackedImage`]. A primary command buffer doesn't transition
+    /// a subresource before its first use, and leaves it in the layout of its last use. When the
+    /// command buffer is submitted, the subresources are first transitioned from the layout that
+    /// the previous submissions left them in, to the layout of their first use. Command buffers
+    /// can therefore be recorded in any order, and submitted any number of times.
+    pub fn new(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        image_info: ImageCreateInfo,
+        allocation_info: AllocationCreateInfo,
+    ) -> Result<Arc<Self>, ImageError> {
+        let raw_image = RawImage::new(allocator.device().clone(), image_info)?;
+        let allocation_type = match raw_image.tiling() {
+            ImageTiling::Optimal => AllocationType::NonLinear,
+            ImageTiling::Linear | ImageTiling::DrmFormatModifier => AllocationType::Unknown,
+        };
+        // VUID-VkMemoryDedicatedAllocateInfo-image-01797
+        let disjoint = raw_image.flags().intersects(ImageCreateFlags::DISJOINT);
+
+        let allocations = raw_image
+            .memory_requirements()
+            .iter()
+            .map(|&requirements| {
+                let allocation = unsafe {
+                    allocator.allocate_unchecked(
+                        requirements,
+                        allocation_type,
+                        allocation_info.clone(),
+                        (!disjoint).then_some(DedicatedAllocation::Image(&raw_image)),
+                    )
+                }?;
+                debug_assert!(is_aligned(
+                    allocation.offset(),
+                    requirements.layout.alignment(),
+                ));
+                debug_assert!(allocation.size() == requirements.layout.size());
+
+                Ok(allocation)
+            })
+            .collect::<Result<SmallVec<[_; 3]>, AllocationCreationError>>()?;
+
+        let mut image =
+            unsafe { raw_image.bind_memory_unchecked(allocations) }.map_err(|(err, _, _)| err)?;
+        image.tracks_layouts = true;
+
+        Ok(Arc::new(image))
+    }
+
     fn from_raw(inner: RawImage, memory: ImageMemory) -> Self {
-        let aspects = inner.format.unwrap().aspects();
+        let aspects = state_aspects(
+            inner.format.unwrap(),
+            inner.flags,
+            inner.format.unwrap().aspects(),
+        );
         let aspect_list: SmallVec<[ImageAspect; 4]> = aspects.into_iter().collect();
         let mip_level_size = inner.dimensions.array_layers() as DeviceSize;
         let aspect_size = mip_level_size * inner.mip_levels as DeviceSize;
@@ -2072,6 +2276,7 @@ impl Image {
         Image {
             inner,
             memory,
+            tracks_layouts: false,
 
             aspect_list,
             aspect_size,
@@ -2195,6 +2400,13 @@ impl Image {
         self.inner.stencil_usage
     }
 
+    /// Returns the formats that views of the image can have, if they were listed when the image
+    /// was created.
+    #[inline]
+    pub fn view_formats(&self) -> &[Format] {
+        &self.inner.view_formats
+    }
+
     /// Returns the sharing the image was created with.
     #[inline]
     pub fn sharing(&self) -> &Sharing<SmallVec<[u32; 4]>> {
@@ -2267,13 +2479,25 @@ impl Image {
     /// with the mip levels in that aspect, and the array layers in that mip level.
     pub(crate) fn iter_ranges(
         &self,
-        subresource_range: ImageSubresourceRange,
+        mut subresource_range: ImageSubresourceRange,
     ) -> SubresourceRangeIterator {
         assert!(self
             .format()
             .unwrap()
             .aspects()
             .contains(subresource_range.aspects));
+        subresource_range.aspects = state_aspects(
+            self.format().unwrap(),
+            self.flags(),
+            subresource_range.aspects,
+        );
+
+        // A 2D or 2D array view of a 3D image selects depth slices as its array layers, but the
+        // state of a 3D image is tracked per mip level, because barriers cover all of its slices.
+        if self.dimensions().image_type() == ImageType::Dim3d {
+            subresource_range.array_layers = 0..1;
+        }
+
         assert!(subresource_range.mip_levels.end <= self.inner.mip_levels);
         assert!(subresource_range.array_layers.end <= self.inner.dimensions.array_layers());
 
@@ -2353,9 +2577,36 @@ impl Image {
         }
     }
 
+    /// Like `range_to_subresources`, but `range` doesn't have to correspond to a single
+    /// subresource range. It is split at the boundaries of the mip levels.
+    pub(crate) fn range_to_subresources_split(
+        &self,
+        range: Range<DeviceSize>,
+    ) -> impl Iterator<Item = ImageSubresourceRange> + '_ {
+        let mut start = range.start;
+
+        iter::from_fn(move || {
+            (start < range.end).then(|| {
+                let end = range
+                    .end
+                    .min((start / self.mip_level_size + 1) * self.mip_level_size);
+                let subresource_range = self.range_to_subresources(start..end);
+                start = end;
+
+                subresource_range
+            })
+        })
+    }
+
     pub(crate) fn state(&self) -> MutexGuard<'_, ImageState> {
         self.state.lock()
     }
+
+    /// Returns whether the image was created with [`Image::new`], and tracks the layout of its
+    /// subresources across command buffers.
+    pub(crate) fn tracks_layouts(&self) -> bool {
+        self.tracks_layouts
+    }
 }
 
 unsafe impl VulkanObject for Image {
@@ -2389,6 +2640,116 @@ impl Hash for Image {
     }
 }
 
+/// An [`Image`] created with [`Image::new`], that can be used in command buffers.
+///
+/// The command buffers that use the image keep track of the layout of each of its subresources.
+/// See [`Image::new`] for details.
+#[derive(Debug)]
+pub struct TrackedImage {
+    image: Arc<Image>,
+}
+
+impl TrackedImage {
+    /// Wraps `image` so that it can be used in command buffers.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image` was not created with [`Image::new`].
+    #[inline]
+    pub fn new(image: Arc<Image>) -> Arc<Self> {
+        assert!(image.tracks_layouts());
+
+        Arc::new(TrackedImage { image })
+    }
+
+    /// Returns the wrapped image.
+    #[inline]
+    pub fn image(&self) -> &Arc<Image> {
+        &self.image
+    }
+}
+
+unsafe impl DeviceOwned for TrackedImage {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.image.device()
+    }
+}
+
+unsafe impl ImageAccess for TrackedImage {
+    #[inline]
+    fn inner(&self) -> ImageInner<'_> {
+        ImageInner {
+            image: &self.image,
+            first_layer: 0,
+            num_layers: self.image.dimensions().array_layers(),
+            first_mipmap_level: 0,
+            num_mipmap_levels: self.image.mip_levels(),
+        }
+    }
+
+    // If the image tracks its layouts, these are only used when a command doesn't care about the
+    // layout and a layout still has to be picked.
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn descriptor_layouts(&self) -> Option<ImageDescriptorLayouts> {
+        Some(ImageDescriptorLayouts {
+            storage_image: ImageLayout::General,
+            combined_image_sampler: ImageLayout::ShaderReadOnlyOptimal,
+            sampled_image: ImageLayout::ShaderReadOnlyOptimal,
+            input_attachment: ImageLayout::ShaderReadOnlyOptimal,
+        })
+    }
+}
+
+impl PartialEq for TrackedImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for TrackedImage {}
+
+impl Hash for TrackedImage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
+/// Returns the aspects that the state of an image with `format` and `flags` is tracked with, when
+/// `aspects` are accessed.
+///
+/// The color aspect of a multi-planar image covers all of its planes. Barriers on a disjoint
+/// image can select the planes individually, so its state is tracked per plane, while barriers on
+/// other multi-planar images must select the color aspect, so their state is tracked as a whole.
+fn state_aspects(format: Format, flags: ImageCreateFlags, aspects: ImageAspects) -> ImageAspects {
+    let planes = ImageAspects::PLANE_0 | ImageAspects::PLANE_1 | ImageAspects::PLANE_2;
+
+    if format.planes().is_empty() {
+        aspects
+    } else if flags.intersects(ImageCreateFlags::DISJOINT) {
+        if aspects.intersects(ImageAspects::COLOR) {
+            (aspects - ImageAspects::COLOR) | (format.aspects() & planes)
+        } else {
+            aspects
+        }
+    } else if aspects.intersects(ImageAspects::COLOR | planes) {
+        (aspects - planes) | ImageAspects::COLOR
+    } else {
+        aspects
+    }
+}
+
 /// The current state of an image.
 #[derive(Debug)]
 pub(crate) struct ImageState {
@@ -2413,6 +2774,13 @@ impl ImageState {
         }
     }
 
+    /// Returns the layout that each range of the image is currently in.
+    pub(crate) fn layouts(&self) -> impl Iterator<Item = (Range<DeviceSize>, ImageLayout)> + '_ {
+        self.ranges
+            .iter()
+            .map(|(range, state)| (range.clone(), state.layout))
+    }
+
     #[allow(dead_code)]
     pub(crate) fn check_cpu_read(&self, range: Range<DeviceSize>) -> Result<(), ReadLockError> {
         for (_range, state) in self.ranges.range(&range) {
@@ -2864,6 +3232,12 @@ pub enum ImageError {
         usage: &'static str,
     },
 
+    /// The image has the `fragment_shading_rate_attachment` usage, but is multisampled.
+    FragmentShadingRateAttachmentMultisampled,
+
+    /// The image has the `fragment_shading_rate_attachment` usage, but is not 2D.
+    FragmentShadingRateAttachmentNot2d,
+
     /// The image configuration as queried through the `image_format_properties` function was not
     /// supported by the device.
     ImageFormatPropertiesNotSupported,
@@ -2982,6 +3356,16 @@ pub enum ImageError {
         stencil_usage: ImageUsage,
     },
 
+    /// A format in `view_formats` is not compatible with the format of the image.
+    ViewFormatNotCompatible {
+        index: usize,
+        view_format: Format,
+    },
+
+    /// The `mutable_format` flag was not enabled, but `view_formats` contains more than one
+    /// format.
+    ViewFormatsMultipleWithoutMutableFormat,
+
     /// A YCbCr format was given, but the specified width and/or height was not a multiple of 2
     /// as required by the format's chroma subsampling.
     YcbcrFormatInvalidDimensions,
@@ -2999,6 +3383,10 @@ pub enum ImageError {
 
     /// If and only if tiling is `DRMFormatModifier`, then `image_drm_format_modifier_create_info` must not be `None`.
     DrmFormatModifierRequiresCreateInfo,
+
+    /// The tiling is `DrmFormatModifier` and the `mutable_format` flag was enabled, but
+    /// `view_formats` is empty.
+    DrmFormatModifierMutableFormatRequiresViewFormats,
 }
 
 impl Error for ImageError {
@@ -3101,6 +3489,14 @@ impl Display for ImageError {
                 f,
                 "a requested usage flag was not supported by the given format",
             ),
+            Self::FragmentShadingRateAttachmentMultisampled => write!(
+                f,
+                "the image has the `fragment_shading_rate_attachment` usage, but is multisampled",
+            ),
+            Self::FragmentShadingRateAttachmentNot2d => write!(
+                f,
+                "the image has the `fragment_shading_rate_attachment` usage, but is not 2D",
+            ),
             Self::ImageFormatPropertiesNotSupported => write!(
                 f,
                 "the image configuration as queried through the `image_format_properties` function \
@@ -3242,6 +3638,17 @@ impl Display for ImageError {
                 "the provided `usage` and `stencil_usage` have different values for \
                 `depth_stencil_attachment` or `transient_attachment`",
             ),
+            Self::ViewFormatNotCompatible { index, view_format } => write!(
+                f,
+                "the format in `view_formats` at index {} ({:?}) is not compatible with the format \
+                of the image",
+                index, view_format,
+            ),
+            Self::ViewFormatsMultipleWithoutMutableFormat => write!(
+                f,
+                "the `mutable_format` flag was not enabled, but `view_formats` contains more than \
+                one format",
+            ),
             Self::YcbcrFormatInvalidDimensions => write!(
                 f,
                 "a YCbCr format was given, but the specified width and/or height was not a \
@@ -3259,6 +3666,11 @@ impl Display for ImageError {
             }
             Self::DirectImageViewCreationFailed(e) => write!(f, "Image view creation failed {}", e),
 	    Self::DrmFormatModifierRequiresCreateInfo => write!(f, "If and only if tiling is `DRMFormatModifier`, then `image_drm_format_modifier_create_info` must be `Some`"),
+            Self::DrmFormatModifierMutableFormatRequiresViewFormats => write!(
+                f,
+                "the tiling is `DrmFormatModifier` and the `mutable_format` flag was enabled, but \
+                `view_formats` is empty",
+            ),
         }
     }
 }
@@ -3286,16 +3698,27 @@ impl From<RequirementNotMet> for ImageError {
 
 #[cfg(test)]
 mod tests {
-    use super::{ImageCreateInfo, ImageError, ImageUsage, RawImage};
+    use super::{Image, ImageCreateInfo, ImageError, ImageUsage, RawImage, TrackedImage};
     use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            ClearColorImageInfo, CommandBufferUsage, CopyImageToBufferInfo,
+            PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract,
+        },
         format::Format,
         image::{
-            sys::SubresourceRangeIterator, ImageAspect, ImageAspects, ImageCreateFlags,
-            ImageDimensions, ImageSubresourceRange, SampleCount,
+            sys::SubresourceRangeIterator,
+            view::{ImageView, ImageViewCreateInfo, ImageViewCreationError},
+            ImageAspect, ImageAspects, ImageCreateFlags, ImageDimensions, ImageLayout,
+            ImageSubresourceRange, ImageViewAbstract, ImageViewType, SampleCount,
         },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+        sync::GpuFuture,
         DeviceSize, RequiresOneOf,
     };
-    use smallvec::SmallVec;
+    use smallvec::{smallvec, SmallVec};
+    use std::sync::Arc;
 
     #[test]
     fn create_sampled() {
@@ -3487,6 +3910,337 @@ mod tests {
         };
     }
 
+    #[test]
+    fn view_formats_not_compatible() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::MUTABLE_FORMAT,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                view_formats: vec![Format::R8G8B8A8_UNORM, Format::R16G16B16A16_SFLOAT],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::ViewFormatNotCompatible {
+                index: 1,
+                view_format: Format::R16G16B16A16_SFLOAT,
+            }) => (),
+            Err(ImageError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_image_format_list") => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn view_formats_multiple_without_mutable_format() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                view_formats: vec![Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::ViewFormatsMultipleWithoutMutableFormat) => (),
+            Err(ImageError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn view_format_not_listed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let image = match Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::MUTABLE_FORMAT,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                view_formats: vec![Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        ) {
+            Ok(image) => TrackedImage::new(image),
+            Err(ImageError::RequirementNotMet { .. }) => return,
+            Err(err) => panic!("{}", err),
+        };
+        assert_eq!(
+            image.image().view_formats(),
+            [Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB],
+        );
+
+        ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Some(Format::R8G8B8A8_SRGB),
+                ..ImageViewCreateInfo::from_image(&image)
+            },
+        )
+        .unwrap();
+
+        // Compatible with the image, but not listed.
+        match ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Some(Format::R32_UINT),
+                ..ImageViewCreateInfo::from_image(&image)
+            },
+        ) {
+            Err(ImageViewCreationError::FormatNotInViewFormats {
+                format: Format::R32_UINT,
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn array_2d_view_of_3d_image() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let image = TrackedImage::new(
+            Image::new(
+                &memory_allocator,
+                ImageCreateInfo {
+                    flags: ImageCreateFlags::ARRAY_2D_COMPATIBLE,
+                    dimensions: ImageDimensions::Dim3d {
+                        width: 16,
+                        height: 16,
+                        depth: 4,
+                    },
+                    format: Some(Format::R8G8B8A8_UNORM),
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        );
+
+        let view = ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                view_type: ImageViewType::Dim2dArray,
+                format: Some(Format::R8G8B8A8_UNORM),
+                subresource_range: ImageSubresourceRange {
+                    aspects: ImageAspects::COLOR,
+                    mip_levels: 0..1,
+                    array_layers: 1..4,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            view.dimensions(),
+            ImageDimensions::Dim2d {
+                width: 16,
+                height: 16,
+                array_layers: 3,
+            },
+        );
+
+        // The depth slices of the view share the state of the mip level.
+        let mut iter = image.image().iter_ranges(view.subresource_range().clone());
+        assert_eq!(iter.next(), Some(0..1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn layouts_tracked_across_command_buffers() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 2,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        assert!(image
+            .state()
+            .layouts()
+            .all(|(_, layout)| layout == ImageLayout::Undefined));
+
+        let clear_layers = |array_layers| {
+            let mut cbb = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            cbb.clear_color_image(ClearColorImageInfo {
+                regions: smallvec![ImageSubresourceRange {
+                    aspects: ImageAspects::COLOR,
+                    mip_levels: 0..1,
+                    array_layers,
+                }],
+                ..ClearColorImageInfo::image(TrackedImage::new(image.clone()))
+            })
+            .unwrap();
+            cbb.build()
+                .unwrap()
+                .execute(queue.clone())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+        };
+
+        // Only the first layer is transitioned, and it's left in the layout of the clear.
+        clear_layers(0..1);
+        assert_eq!(
+            image.state().layouts().collect::<Vec<_>>(),
+            [
+                (0..1, ImageLayout::TransferDstOptimal),
+                (1..2, ImageLayout::Undefined),
+            ],
+        );
+
+        // The second command buffer starts from the layouts that the first one left.
+        clear_layers(0..2);
+        assert!(image
+            .state()
+            .layouts()
+            .all(|(_, layout)| layout == ImageLayout::TransferDstOptimal));
+    }
+
+    #[test]
+    fn layouts_transitioned_at_submission() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let buffer = Buffer::new_slice::<u8>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            32 * 32 * 4,
+        )
+        .unwrap();
+
+        // Both command buffers are recorded before either of them is submitted, and in the
+        // opposite order of their submission.
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::MultipleSubmit,
+        )
+        .unwrap();
+        cbb.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+            TrackedImage::new(image.clone()),
+            buffer,
+        ))
+        .unwrap();
+        let copy = Arc::new(cbb.build().unwrap());
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::MultipleSubmit,
+        )
+        .unwrap();
+        cbb.clear_color_image(ClearColorImageInfo::image(TrackedImage::new(image.clone())))
+            .unwrap();
+        let clear = Arc::new(cbb.build().unwrap());
+
+        let submit = |command_buffer: &Arc<PrimaryAutoCommandBuffer>| {
+            command_buffer
+                .clone()
+                .execute(queue.clone())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+        };
+        let layouts = || {
+            image
+                .state()
+                .layouts()
+                .map(|(_, layout)| layout)
+                .collect::<Vec<_>>()
+        };
+
+        submit(&clear);
+        assert_eq!(layouts(), [ImageLayout::TransferDstOptimal]);
+        submit(&copy);
+        assert_eq!(layouts(), [ImageLayout::TransferSrcOptimal]);
+
+        // Submitting again transitions the image back from where the other command buffer left
+        // it.
+        submit(&clear);
+        assert_eq!(layouts(), [ImageLayout::TransferDstOptimal]);
+        submit(&copy);
+        assert_eq!(layouts(), [ImageLayout::TransferSrcOptimal]);
+    }
+
     #[test]
     #[allow(clippy::erasing_op, clippy::identity_op)]
     fn subresource_range_iterator() {
@@ -73,11 +73,10 @@ vulkan_bitflags! {
         device_extensions: [ext_fragment_density_map],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a fragment shading rate attachment in a render pass/framebuffer.
     FRAGMENT_SHADING_RATE_ATTACHMENT = FRAGMENT_SHADING_RATE_ATTACHMENT_KHR {
         device_extensions: [khr_fragment_shading_rate],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
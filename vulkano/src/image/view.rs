@@ -121,7 +121,7 @@ where
             usage = default_usage;
             false
         } else {
-            usage == default_usage
+            usage != default_usage
         };
 
         // VUID-VkImageViewCreateInfo-viewType-parameter
@@ -361,6 +361,11 @@ where
 
         /* Check flags requirements */
 
+        // VUID-VkImageViewCreateInfo-pNext-01585
+        if !image_inner.view_formats().is_empty() && !image_inner.view_formats().contains(&format) {
+            return Err(ImageViewCreationError::FormatNotInViewFormats { format });
+        }
+
         if Some(format) != image_inner.format() {
             // VUID-VkImageViewCreateInfo-image-01762
             if !image_inner
@@ -555,7 +560,7 @@ where
             usage = default_usage;
             false
         } else {
-            usage == default_usage
+            usage != default_usage
         };
 
         let mut info_vk = ash::vk::ImageViewCreateInfo {
@@ -808,13 +813,19 @@ pub struct ImageViewCreateInfo {
     /// The view type must be compatible with the dimensions of the image and the selected array
     /// layers.
     ///
+    /// A 3D image that was created with the `array_2d_compatible` flag can also have a
+    /// [`Dim2d`](ImageViewType::Dim2d) or [`Dim2dArray`](ImageViewType::Dim2dArray) view, for
+    /// example to render to its depth slices as the layers of a framebuffer. The `array_layers`
+    /// of `subresource_range` then select depth slices of a single mip level.
+    ///
     /// The default value is [`ImageViewType::Dim2d`].
     pub view_type: ImageViewType,
 
     /// The format of the image view.
     ///
     /// If this is set to a format that is different from the image, the image must be created with
-    /// the `mutable_format` flag.
+    /// the `mutable_format` flag. If the image was created with a non-empty `view_formats`, the
+    /// format must be one of those.
     ///
     /// On [portability subset](crate::instance#portability-subset-devices-and-the-enumerate_portability-flag)
     /// devices, if `format` does not have the same number of components and bits per component as
@@ -852,6 +863,11 @@ pub struct ImageViewCreateInfo {
     /// extension must be enabled on the device. The specified `usage` must be a subset of the
     /// default value; usages that are not set for the parent image are not allowed.
     ///
+    /// Restricting the usage allows views with a format that doesn't support all of the usages of
+    /// the image, such as an sRGB view of a UNORM storage image that is only used for sampling.
+    /// The usage of the view, rather than of the image, is checked when the view is written to a
+    /// descriptor set or used as an attachment.
+    ///
     /// The default value is [`ImageUsage::empty()`].
     pub usage: ImageUsage,
 
@@ -946,6 +962,9 @@ pub enum ImageViewCreationError {
     /// The requested format was not compatible with the image.
     FormatNotCompatible,
 
+    /// The image was created with a list of view formats, but the requested format is not in it.
+    FormatNotInViewFormats { format: Format },
+
     /// The given format was not supported by the device.
     FormatNotSupported,
 
@@ -1058,6 +1077,11 @@ impl Display for ImageViewCreationError {
             Self::FormatNotCompatible => {
                 write!(f, "the requested format was not compatible with the image")
             }
+            Self::FormatNotInViewFormats { .. } => write!(
+                f,
+                "the image was created with a list of view formats, but the requested format is \
+                not in it",
+            ),
             Self::FormatNotSupported => {
                 write!(f, "the given format was not supported by the device")
             }
@@ -1243,6 +1267,18 @@ pub unsafe trait ImageViewAbstract:
                 height,
                 array_layers,
             },
+            ImageDimensions::Dim3d { width, height, .. }
+                if matches!(
+                    self.view_type(),
+                    ImageViewType::Dim2d | ImageViewType::Dim2dArray
+                ) =>
+            {
+                ImageDimensions::Dim2d {
+                    width,
+                    height,
+                    array_layers,
+                }
+            }
             ImageDimensions::Dim3d {
                 width,
                 height,
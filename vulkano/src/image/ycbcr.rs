@@ -0,0 +1,518 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    sys::{Image, ImageCreateInfo, TrackedImage},
+    view::{ImageView, ImageViewCreateInfo, ImageViewCreationError},
+    ImageAccess, ImageAspects, ImageCreateFlags, ImageDescriptorLayouts, ImageDimensions,
+    ImageError, ImageInner, ImageLayout, ImageSubresourceLayers, ImageSubresourceRange, ImageUsage,
+};
+use crate::{
+    buffer::Subbuffer,
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BufferImageCopy,
+        CopyBufferToImageInfo, CopyError,
+    },
+    device::{Device, DeviceOwned},
+    format::{Format, FormatFeatures},
+    memory::allocator::{AllocationCreateInfo, MemoryAllocator},
+    sampler::ycbcr::SamplerYcbcrConversion,
+    DeviceSize,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+const PLANE_ASPECTS: [ImageAspects; 3] = [
+    ImageAspects::PLANE_0,
+    ImageAspects::PLANE_1,
+    ImageAspects::PLANE_2,
+];
+
+/// A two-dimensional image with a multi-planar YCbCr format, such as a decoded video frame.
+///
+/// The image is created with the format of a [`SamplerYcbcrConversion`], which is also used for
+/// the view returned by [`view`](Self::view), so that sampling it yields RGB values. If the
+/// format supports the `disjoint` format feature, each plane is bound to its own allocation.
+///
+/// Each plane can be uploaded separately with
+/// [`copy_plane_from_buffer`](Self::copy_plane_from_buffer), or all planes at once from a frame
+/// laid out like NV12, I420 or P010 with [`copy_from_buffer`](Self::copy_from_buffer). The chroma
+/// planes are smaller than the image if the format is subsampled; see
+/// [`plane_extent`](Self::plane_extent).
+#[derive(Debug)]
+pub struct YcbcrImage {
+    inner: Arc<TrackedImage>,
+    conversion: Arc<SamplerYcbcrConversion>,
+}
+
+impl YcbcrImage {
+    /// Creates a new `YcbcrImage` with the format of `conversion`.
+    ///
+    /// The image is always created with the `transfer_dst` and `sampled` usages, in addition to
+    /// `usage`.
+    pub fn new(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        conversion: Arc<SamplerYcbcrConversion>,
+        extent: [u32; 2],
+        usage: ImageUsage,
+        allocation_info: AllocationCreateInfo,
+    ) -> Result<Arc<YcbcrImage>, YcbcrImageError> {
+        assert_eq!(allocator.device(), conversion.device());
+
+        let format = conversion.format().unwrap();
+
+        if format.planes().is_empty() {
+            return Err(YcbcrImageError::FormatNotMultiPlanar { format });
+        }
+
+        let format_features = unsafe {
+            conversion
+                .device()
+                .physical_device()
+                .format_properties_unchecked(format)
+                .optimal_tiling_features
+        };
+
+        // Views of individual planes have a different format than the image.
+        let mut flags = ImageCreateFlags::MUTABLE_FORMAT;
+
+        if format_features.intersects(FormatFeatures::DISJOINT) {
+            flags |= ImageCreateFlags::DISJOINT;
+        }
+
+        let image = Image::new(
+            allocator,
+            ImageCreateInfo {
+                flags,
+                dimensions: ImageDimensions::Dim2d {
+                    width: extent[0],
+                    height: extent[1],
+                    array_layers: 1,
+                },
+                format: Some(format),
+                usage: usage | ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            allocation_info,
+        )?;
+        let inner = TrackedImage::new(image);
+
+        Ok(Arc::new(YcbcrImage { inner, conversion }))
+    }
+
+    /// Returns the underlying image.
+    #[inline]
+    pub fn image(&self) -> &Arc<Image> {
+        self.inner.image()
+    }
+
+    /// Returns the conversion that the image was created with.
+    #[inline]
+    pub fn conversion(&self) -> &Arc<SamplerYcbcrConversion> {
+        &self.conversion
+    }
+
+    /// Returns whether each plane of the image is bound to its own allocation.
+    #[inline]
+    pub fn is_disjoint(&self) -> bool {
+        self.image().flags().intersects(ImageCreateFlags::DISJOINT)
+    }
+
+    /// Returns the number of planes of the image.
+    #[inline]
+    pub fn plane_count(&self) -> usize {
+        self.format().planes().len()
+    }
+
+    /// Returns the format that a view of plane `plane` must have.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `plane` is not less than the number of planes.
+    #[inline]
+    pub fn plane_format(&self, plane: usize) -> Format {
+        self.format().planes()[plane]
+    }
+
+    /// Returns the extent of plane `plane`, in texels of its format.
+    ///
+    /// The first plane always has the extent of the image, while the chroma planes of a
+    /// subsampled format are halved in the subsampled dimensions.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `plane` is not less than the number of planes.
+    pub fn plane_extent(&self, plane: usize) -> [u32; 2] {
+        assert!(plane < self.plane_count());
+
+        let extent = self.dimensions().width_height_depth();
+        let [width, height, _] = match self.format().ycbcr_chroma_sampling() {
+            Some(chroma_sampling) if plane != 0 => chroma_sampling.subsampled_extent(extent),
+            _ => extent,
+        };
+
+        [width, height]
+    }
+
+    /// Returns the size in bytes of plane `plane` when its rows are tightly packed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `plane` is not less than the number of planes.
+    pub fn plane_size(&self, plane: usize) -> DeviceSize {
+        let [width, height] = self.plane_extent(plane);
+
+        self.plane_format(plane).block_size().unwrap() * width as DeviceSize * height as DeviceSize
+    }
+
+    /// Records a copy of plane `plane` from `source` to the image, with the rows of the plane
+    /// tightly packed at the start of `source`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `plane` is not less than the number of planes.
+    pub fn copy_plane_from_buffer<L, A>(
+        self: &Arc<Self>,
+        command_buffer_builder: &mut AutoCommandBufferBuilder<L, A>,
+        plane: usize,
+        source: Subbuffer<impl ?Sized>,
+    ) -> Result<(), YcbcrImageError>
+    where
+        A: CommandBufferAllocator,
+    {
+        let source = source.into_bytes();
+        let required_size = self.plane_size(plane);
+
+        if source.size() < required_size {
+            return Err(YcbcrImageError::SourceTooSmall {
+                plane,
+                source_size: source.size(),
+                required_size,
+            });
+        }
+
+        let [width, height] = self.plane_extent(plane);
+
+        command_buffer_builder.copy_buffer_to_image(CopyBufferToImageInfo {
+            regions: [BufferImageCopy {
+                image_subresource: ImageSubresourceLayers {
+                    aspects: PLANE_ASPECTS[plane],
+                    mip_level: 0,
+                    array_layers: 0..1,
+                },
+                image_extent: [width, height, 1],
+                ..Default::default()
+            }]
+            .into(),
+            ..CopyBufferToImageInfo::buffer_image(source, self.clone())
+        })?;
+
+        Ok(())
+    }
+
+    /// Records a copy of all planes from `source` to the image, with the planes stored one after
+    /// the other in order, each with its rows tightly packed.
+    ///
+    /// This is the layout of NV12 and P010 frames for two-plane 4:2:0 formats, and of I420 frames
+    /// for three-plane 4:2:0 formats.
+    pub fn copy_from_buffer<L, A>(
+        self: &Arc<Self>,
+        command_buffer_builder: &mut AutoCommandBufferBuilder<L, A>,
+        source: Subbuffer<impl ?Sized>,
+    ) -> Result<(), YcbcrImageError>
+    where
+        A: CommandBufferAllocator,
+    {
+        let source = source.into_bytes();
+        let required_size: DeviceSize = (0..self.plane_count()).map(|p| self.plane_size(p)).sum();
+
+        if source.size() < required_size {
+            return Err(YcbcrImageError::SourceTooSmall {
+                plane: self.plane_count() - 1,
+                source_size: source.size(),
+                required_size,
+            });
+        }
+
+        let mut offset = 0;
+
+        for plane in 0..self.plane_count() {
+            let size = self.plane_size(plane);
+            self.copy_plane_from_buffer(
+                command_buffer_builder,
+                plane,
+                source.clone().slice(offset..offset + size),
+            )?;
+            offset += size;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a view of the whole image that converts to RGB using the conversion of the image.
+    ///
+    /// Such a view can only be sampled with a sampler that was created with the same conversion.
+    pub fn view(self: &Arc<Self>) -> Result<Arc<ImageView<YcbcrImage>>, ImageViewCreationError> {
+        ImageView::new(
+            self.clone(),
+            ImageViewCreateInfo {
+                sampler_ycbcr_conversion: Some(self.conversion.clone()),
+                ..ImageViewCreateInfo::from_image(&**self)
+            },
+        )
+    }
+
+    /// Creates a view of plane `plane` alone, with the format returned by
+    /// [`plane_format`](Self::plane_format).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `plane` is not less than the number of planes.
+    pub fn plane_view(
+        self: &Arc<Self>,
+        plane: usize,
+    ) -> Result<Arc<ImageView<YcbcrImage>>, ImageViewCreationError> {
+        ImageView::new(
+            self.clone(),
+            ImageViewCreateInfo {
+                format: Some(self.plane_format(plane)),
+                subresource_range: ImageSubresourceRange {
+                    aspects: PLANE_ASPECTS[plane],
+                    mip_levels: 0..1,
+                    array_layers: 0..1,
+                },
+                ..ImageViewCreateInfo::from_image(&**self)
+            },
+        )
+    }
+}
+
+unsafe impl DeviceOwned for YcbcrImage {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+unsafe impl ImageAccess for YcbcrImage {
+    #[inline]
+    fn inner(&self) -> ImageInner<'_> {
+        self.inner.inner()
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        self.inner.initial_layout_requirement()
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        self.inner.final_layout_requirement()
+    }
+
+    #[inline]
+    fn descriptor_layouts(&self) -> Option<ImageDescriptorLayouts> {
+        self.inner.descriptor_layouts()
+    }
+}
+
+impl PartialEq for YcbcrImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for YcbcrImage {}
+
+impl Hash for YcbcrImage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
+/// Error that can happen when creating or uploading to a [`YcbcrImage`].
+#[derive(Clone, Debug)]
+pub enum YcbcrImageError {
+    ImageError(ImageError),
+    CopyError(CopyError),
+
+    /// The format of the conversion is not a multi-planar format.
+    FormatNotMultiPlanar {
+        format: Format,
+    },
+
+    /// The source buffer is too small for the plane or planes that are copied.
+    SourceTooSmall {
+        plane: usize,
+        source_size: DeviceSize,
+        required_size: DeviceSize,
+    },
+}
+
+impl Error for YcbcrImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ImageError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for YcbcrImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::ImageError(err) => err.fmt(f),
+            Self::CopyError(err) => err.fmt(f),
+            Self::FormatNotMultiPlanar { format } => write!(
+                f,
+                "the format of the conversion ({:?}) is not a multi-planar format",
+                format,
+            ),
+            Self::SourceTooSmall {
+                plane,
+                source_size,
+                required_size,
+            } => write!(
+                f,
+                "the source buffer ({} bytes) is too small to copy plane {} ({} bytes required)",
+                source_size, plane, required_size,
+            ),
+        }
+    }
+}
+
+impl From<ImageError> for YcbcrImageError {
+    fn from(err: ImageError) -> Self {
+        Self::ImageError(err)
+    }
+}
+
+impl From<CopyError> for YcbcrImageError {
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{YcbcrImage, YcbcrImageError};
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, PrimaryCommandBufferAbstract,
+        },
+        format::{Format, FormatFeatures},
+        image::{ImageAccess, ImageUsage},
+        memory::allocator::{AllocationCreateInfo, MemoryUsage, StandardMemoryAllocator},
+        sampler::ycbcr::{SamplerYcbcrConversion, SamplerYcbcrConversionCreateInfo},
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn upload_nv12() {
+        let (device, queue) = gfx_dev_and_queue!(sampler_ycbcr_conversion);
+
+        let format = Format::G8_B8R8_2PLANE_420_UNORM;
+        let format_features = device
+            .physical_device()
+            .format_properties(format)
+            .unwrap()
+            .optimal_tiling_features;
+
+        if !format_features.contains(
+            FormatFeatures::TRANSFER_DST
+                | FormatFeatures::SAMPLED_IMAGE
+                | FormatFeatures::MIDPOINT_CHROMA_SAMPLES,
+        ) {
+            return;
+        }
+
+        let conversion = SamplerYcbcrConversion::new(
+            device.clone(),
+            SamplerYcbcrConversionCreateInfo {
+                format: Some(format),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let image = YcbcrImage::new(
+            &memory_allocator,
+            conversion,
+            [6, 4],
+            ImageUsage::empty(),
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        assert_eq!(image.plane_count(), 2);
+        assert_eq!(image.plane_format(1), Format::R8G8_UNORM);
+        assert_eq!(image.plane_extent(0), [6, 4]);
+        assert_eq!(image.plane_extent(1), [3, 2]);
+        assert_eq!(image.plane_size(1), 12);
+        assert_eq!(
+            image.is_disjoint(),
+            image.inner().image.memory_requirements().len() == 2
+        );
+
+        // Luma plane followed by interleaved chroma, both tightly packed.
+        let source = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                usage: MemoryUsage::Upload,
+                ..Default::default()
+            },
+            (0..36u8).map(|i| i * 7),
+        )
+        .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            image.copy_from_buffer(&mut cbb, source.clone().slice(0..35)),
+            Err(YcbcrImageError::SourceTooSmall {
+                source_size: 35,
+                required_size: 36,
+                ..
+            }),
+        ));
+
+        image.copy_from_buffer(&mut cbb, source).unwrap();
+        cbb.build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        image.view().unwrap();
+        image.plane_view(0).unwrap();
+        image.plane_view(1).unwrap();
+    }
+}
@@ -100,6 +100,7 @@
 //! |----------------------|---------------------------------------------------------------||
 //! | `macros`             | Include reexports from [`vulkano-macros`]. Enabled by default. |
 //! | `document_unchecked` | Include `_unchecked` functions in the generated documentation. |
+//! | `ruzstd`             | Enables loading KTX2 files supercompressed with Zstandard.     |
 //! | `serde`              | Enables (de)serialization of certain types using [`serde`].    |
 //!
 //! [`VulkanLibrary`]: crate::VulkanLibrary
@@ -194,8 +194,14 @@ impl ComputePipeline {
                 ..Default::default()
             };
 
+            let mut flags = ash::vk::PipelineCreateFlags::empty();
+
+            if layout.descriptor_buffer() {
+                flags |= ash::vk::PipelineCreateFlags::DESCRIPTOR_BUFFER_EXT;
+            }
+
             let infos = ash::vk::ComputePipelineCreateInfo {
-                flags: ash::vk::PipelineCreateFlags::empty(),
+                flags,
                 stage,
                 layout: layout.handle(),
                 base_pipeline_handle: ash::vk::Pipeline::null(),
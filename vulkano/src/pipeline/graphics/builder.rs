@@ -17,24 +17,29 @@ use super::{
     },
     depth_stencil::{DepthStencilState, StencilOps},
     discard_rectangle::DiscardRectangleState,
+    fragment_shading_rate::{
+        FragmentShadingRate, FragmentShadingRateCombinerOp, FragmentShadingRateState,
+    },
     input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
+    interface_report::is_numeric_type_compatible,
     multisample::MultisampleState,
     rasterization::{
         CullMode, DepthBiasState, FrontFace, LineRasterizationMode, PolygonMode, RasterizationState,
     },
     render_pass::{PipelineRenderPassType, PipelineRenderingCreateInfo},
     tessellation::TessellationState,
+    transform_feedback::TransformFeedbackState,
     vertex_input::{
         VertexDefinition, VertexInputAttributeDescription, VertexInputBindingDescription,
         VertexInputState,
     },
     viewport::{Scissor, Viewport, ViewportState},
-    GraphicsPipeline, GraphicsPipelineCreationError,
+    GraphicsPipeline, GraphicsPipelineCreationError, PipelineInterfaceReport,
 };
 use crate::{
     descriptor_set::layout::{DescriptorSetLayout, DescriptorSetLayoutCreateInfo},
     device::{Device, DeviceOwned},
-    format::{FormatFeatures, NumericType},
+    format::FormatFeatures,
     image::ImageAspects,
     pipeline::{
         cache::PipelineCache,
@@ -48,8 +53,7 @@ use crate::{
     },
     shader::{
         DescriptorBindingRequirements, EntryPoint, FragmentShaderExecution, FragmentTestsStages,
-        ShaderExecution, ShaderScalarType, ShaderStage, SpecializationConstants,
-        SpecializationMapEntry,
+        ShaderExecution, ShaderStage, SpecializationConstants, SpecializationMapEntry,
     },
     DeviceSize, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
@@ -74,11 +78,14 @@ pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, T
     fragment_shader: Option<(EntryPoint<'fs>, Fss)>,
 
     vertex_input_state: Vdef,
+    vertex_input_state_dynamic: bool,
     input_assembly_state: InputAssemblyState,
     tessellation_state: TessellationState,
     viewport_state: ViewportState,
     discard_rectangle_state: DiscardRectangleState,
+    transform_feedback_state: TransformFeedbackState,
     rasterization_state: RasterizationState,
+    fragment_shading_rate_state: FragmentShadingRateState,
     multisample_state: MultisampleState,
     depth_stencil_state: DepthStencilState,
     color_blend_state: ColorBlendState,
@@ -118,11 +125,14 @@ impl
             fragment_shader: None,
 
             vertex_input_state: Default::default(),
+            vertex_input_state_dynamic: false,
             input_assembly_state: Default::default(),
             tessellation_state: Default::default(),
             viewport_state: Default::default(),
             discard_rectangle_state: Default::default(),
+            transform_feedback_state: Default::default(),
             rasterization_state: Default::default(),
+            fragment_shading_rate_state: Default::default(),
             multisample_state: Default::default(),
             depth_stencil_state: Default::default(),
             color_blend_state: Default::default(),
@@ -262,6 +272,46 @@ where
         self.with_pipeline_layout(device, pipeline_layout)
     }
 
+    /// Checks the interfaces between all parts of the pipeline, without creating it.
+    ///
+    /// This checks the vertex input state against the vertex shader, each shader stage against
+    /// the next one, the fragment shader outputs against the render pass or rendering formats,
+    /// and, if `pipeline_layout` is provided, the requirements of each shader stage against the
+    /// layout. Unlike pipeline creation, which stops at the first error, every problem that is
+    /// found is collected into the returned report.
+    pub fn check_interfaces(
+        &self,
+        pipeline_layout: Option<&PipelineLayout>,
+    ) -> PipelineInterfaceReport {
+        let stages: SmallVec<[&EntryPoint<'_>; 5]> = [
+            self.vertex_shader.as_ref().map(|s| &s.0),
+            self.tessellation_shaders.as_ref().map(|s| &s.control.0),
+            self.tessellation_shaders.as_ref().map(|s| &s.evaluation.0),
+            self.geometry_shader.as_ref().map(|s| &s.0),
+            self.fragment_shader.as_ref().map(|s| &s.0),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        // A dynamic vertex input state is only known at draw time.
+        let vertex_input_state = self
+            .vertex_shader
+            .as_ref()
+            .filter(|_| !self.vertex_input_state_dynamic)
+            .map(|(entry_point, _)| {
+                self.vertex_input_state
+                    .definition(entry_point.input_interface())
+            });
+
+        PipelineInterfaceReport::check(
+            vertex_input_state,
+            &stages,
+            self.render_pass.as_ref(),
+            pipeline_layout,
+        )
+    }
+
     /// Builds the graphics pipeline.
     ///
     /// Does the same as `build`, except that `build` automatically builds the pipeline layout
@@ -273,9 +323,13 @@ where
         device: Arc<Device>,
         pipeline_layout: Arc<PipelineLayout>,
     ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError> {
-        let vertex_input_state = self
-            .vertex_input_state
-            .definition(self.vertex_shader.as_ref().unwrap().0.input_interface())?;
+        let vertex_input_interface = self.vertex_shader.as_ref().unwrap().0.input_interface();
+        let vertex_input_state = if self.vertex_input_state_dynamic {
+            VertexInputState::new()
+        } else {
+            self.vertex_input_state.definition(vertex_input_interface)?
+        };
+        let vertex_input_interface = vertex_input_interface.clone();
 
         // If there is one element, duplicate it for all attachments.
         // TODO: this is undocumented and only exists for compatibility with some of the
@@ -307,11 +361,14 @@ where
                 fragment_shader: _,
 
                 vertex_input_state: _,
+                vertex_input_state_dynamic: _,
                 input_assembly_state: _,
                 tessellation_state: _,
                 viewport_state: _,
                 discard_rectangle_state: _,
+                transform_feedback_state: _,
                 rasterization_state,
+                fragment_shading_rate_state: _,
                 multisample_state: _,
                 depth_stencil_state: _,
                 color_blend_state: _,
@@ -367,6 +424,10 @@ where
 
         let (handle, descriptor_requirements, dynamic_state, shaders, fragment_tests_stages) =
             unsafe { self.record_create(&device, &pipeline_layout, &vertex_input_state, has)? };
+        let transform_feedback_outputs = self
+            .last_pre_rasterization_shader()
+            .and_then(|entry_point| entry_point.transform_feedback_outputs())
+            .map(<[_]>::to_vec);
 
         let Self {
             mut render_pass,
@@ -378,11 +439,14 @@ where
             fragment_shader: _,
 
             vertex_input_state: _,
+            vertex_input_state_dynamic: _,
             input_assembly_state,
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            transform_feedback_state,
             rasterization_state,
+            fragment_shading_rate_state,
             multisample_state,
             depth_stencil_state,
             color_blend_state,
@@ -406,6 +470,8 @@ where
             descriptor_binding_requirements: descriptor_requirements,
             num_used_descriptor_sets,
             fragment_tests_stages,
+            transform_feedback_outputs,
+            vertex_input_interface,
 
             vertex_input_state, // Can be None if there's a mesh shader, but we don't support that yet
             input_assembly_state, // Can be None if there's a mesh shader, but we don't support that yet
@@ -414,7 +480,13 @@ where
             discard_rectangle_state: has
                 .pre_rasterization_shader_state
                 .then_some(discard_rectangle_state),
+            transform_feedback_state: has
+                .pre_rasterization_shader_state
+                .then_some(transform_feedback_state),
             rasterization_state,
+            fragment_shading_rate_state: has
+                .fragment_shader_state
+                .then_some(fragment_shading_rate_state),
             multisample_state: has.fragment_output_state.then_some(multisample_state),
             depth_stencil_state: has.depth_stencil_state.then_some(depth_stencil_state),
             color_blend_state: has.color_blend_state.then_some(color_blend_state),
@@ -422,6 +494,19 @@ where
         }))
     }
 
+    // Returns the shader whose outputs are passed on to rasterization and transform feedback.
+    fn last_pre_rasterization_shader(&self) -> Option<&EntryPoint<'_>> {
+        if let Some((entry_point, _)) = &self.geometry_shader {
+            Some(entry_point)
+        } else if let Some(tessellation_shaders) = &self.tessellation_shaders {
+            Some(&tessellation_shaders.evaluation.0)
+        } else {
+            self.vertex_shader
+                .as_ref()
+                .map(|(entry_point, _)| entry_point)
+        }
+    }
+
     fn validate_create(
         &self,
         device: &Device,
@@ -442,11 +527,14 @@ where
             fragment_shader,
 
             vertex_input_state: _,
+            vertex_input_state_dynamic,
             input_assembly_state,
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            transform_feedback_state,
             rasterization_state,
+            fragment_shading_rate_state,
             multisample_state,
             depth_stencil_state,
             color_blend_state,
@@ -602,9 +690,22 @@ where
         */
 
         if has.vertex_input_state {
+            if *vertex_input_state_dynamic {
+                // VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-04807
+                if !device.enabled_features().vertex_input_dynamic_state {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "the vertex input state is dynamic",
+                        requires_one_of: RequiresOneOf {
+                            features: &["vertex_input_dynamic_state"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+
             // Vertex input state
             // VUID-VkGraphicsPipelineCreateInfo-pVertexInputState-04910
-            {
+            if !*vertex_input_state_dynamic {
                 let VertexInputState {
                     bindings,
                     attributes,
@@ -990,15 +1091,16 @@ where
 
                 // VUID?
                 // Check that the vertex input state contains attributes for all the shader's input
-                // variables.
-                for element in entry_point.input_interface().elements() {
-                    assert!(!element.ty.is_64bit); // TODO: implement
-                    let location_range =
-                        element.location..element.location + element.ty.num_locations();
-
-                    for location in location_range {
-                        let attribute_desc =
-                            match vertex_input_state.attributes.get(&location) {
+                // variables. If the vertex input state is dynamic, this is checked at draw time.
+                if !*vertex_input_state_dynamic {
+                    for element in entry_point.input_interface().elements() {
+                        assert!(!element.ty.is_64bit); // TODO: implement
+                        let location_range =
+                            element.location..element.location + element.ty.num_locations();
+
+                        for location in location_range {
+                            let attribute_desc = match vertex_input_state.attributes.get(&location)
+                            {
                                 Some(attribute_desc) => attribute_desc,
                                 None => return Err(
                                     GraphicsPipelineCreationError::VertexInputAttributeMissing {
@@ -1007,33 +1109,21 @@ where
                                 ),
                             };
 
-                        // TODO: Check component assignments too. Multiple variables can occupy the same
-                        // location but in different components.
-
-                        let shader_type = element.ty.base_type;
-                        let attribute_type = attribute_desc.format.type_color().unwrap();
-
-                        if !matches!(
-                            (shader_type, attribute_type),
-                            (
-                                ShaderScalarType::Float,
-                                NumericType::SFLOAT
-                                    | NumericType::UFLOAT
-                                    | NumericType::SNORM
-                                    | NumericType::UNORM
-                                    | NumericType::SSCALED
-                                    | NumericType::USCALED
-                                    | NumericType::SRGB,
-                            ) | (ShaderScalarType::Sint, NumericType::SINT)
-                                | (ShaderScalarType::Uint, NumericType::UINT)
-                        ) {
-                            return Err(
-                                GraphicsPipelineCreationError::VertexInputAttributeIncompatibleFormat {
-                                    location,
-                                    shader_type,
-                                    attribute_type,
-                                },
-                            );
+                            // TODO: Check component assignments too. Multiple variables can
+                            // occupy the same location but in different components.
+
+                            let shader_type = element.ty.base_type;
+                            let attribute_type = attribute_desc.format.type_color().unwrap();
+
+                            if !is_numeric_type_compatible(shader_type, Some(attribute_type)) {
+                                return Err(
+                                    GraphicsPipelineCreationError::VertexInputAttributeIncompatibleFormat {
+                                        location,
+                                        shader_type,
+                                        attribute_type,
+                                    },
+                                );
+                            }
                         }
                     }
                 }
@@ -1217,20 +1307,39 @@ where
                     line_width,
                     line_rasterization_mode,
                     line_stipple,
+                    line_stipple_enable_dynamic,
                 } = rasterization_state;
 
-                // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-parameter
-                polygon_mode.validate_device(device)?;
-
-                // VUID-VkPipelineRasterizationStateCreateInfo-depthClampEnable-00782
-                if depth_clamp_enable && !device.enabled_features().depth_clamp {
-                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                        required_for: "`rasterization_state.depth_clamp_enable` is set",
-                        requires_one_of: RequiresOneOf {
-                            features: &["depth_clamp"],
-                            ..Default::default()
-                        },
-                    });
+                match depth_clamp_enable {
+                    StateMode::Fixed(depth_clamp_enable) => {
+                        // VUID-VkPipelineRasterizationStateCreateInfo-depthClampEnable-00782
+                        if depth_clamp_enable && !device.enabled_features().depth_clamp {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.depth_clamp_enable` is \
+                                    `StateMode::Fixed(true)`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["depth_clamp"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3DepthClampEnable-07371
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_depth_clamp_enable
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.depth_clamp_enable` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_depth_clamp_enable"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
                 }
 
                 match rasterizer_discard_enable {
@@ -1254,13 +1363,14 @@ where
                         // VUID-VkPipelineRasterizationStateCreateInfo-pointPolygons-04458
                         if device.enabled_extensions().khr_portability_subset
                             && !device.enabled_features().point_polygons
-                            && polygon_mode == PolygonMode::Point
+                            && polygon_mode == StateMode::Fixed(PolygonMode::Point)
                         {
                             return Err(GraphicsPipelineCreationError::RequirementNotMet {
                                 required_for: "this device is a portability subset device, \
                                     `rasterization_state.rasterizer_discard_enable` is \
                                     `StateMode::Fixed(false)` and \
-                                    `rasterization_state.polygon_mode` is `PolygonMode::Point`",
+                                    `rasterization_state.polygon_mode` is \
+                                    `StateMode::Fixed(PolygonMode::Point)`",
                                 requires_one_of: RequiresOneOf {
                                     features: &["point_polygons"],
                                     ..Default::default()
@@ -1271,18 +1381,41 @@ where
                     _ => (),
                 }
 
-                // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-01507
-                if polygon_mode != PolygonMode::Fill
-                    && !device.enabled_features().fill_mode_non_solid
-                {
-                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                        required_for: "`rasterization_state.polygon_mode` is not \
-                            `PolygonMode::Fill`",
-                        requires_one_of: RequiresOneOf {
-                            features: &["fill_mode_non_solid"],
-                            ..Default::default()
-                        },
-                    });
+                match polygon_mode {
+                    StateMode::Fixed(polygon_mode) => {
+                        // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-parameter
+                        polygon_mode.validate_device(device)?;
+
+                        // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-01507
+                        if polygon_mode != PolygonMode::Fill
+                            && !device.enabled_features().fill_mode_non_solid
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.polygon_mode` is not \
+                                    `StateMode::Fixed(PolygonMode::Fill)`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["fill_mode_non_solid"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3PolygonMode-07372
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_polygon_mode
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.polygon_mode` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_polygon_mode"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
                 }
 
                 match cull_mode {
@@ -1385,119 +1518,185 @@ where
                 }
 
                 if device.enabled_extensions().ext_line_rasterization {
-                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-parameter
-                    line_rasterization_mode.validate_device(device)?;
-
                     match line_rasterization_mode {
-                        LineRasterizationMode::Default => (),
-                        LineRasterizationMode::Rectangular => {
-                            // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-02768
-                            if !device.enabled_features().rectangular_lines {
-                                return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                                    required_for: "`rasterization_state.line_rasterization_mode` \
-                                        is `LineRasterizationMode::Rectangular`",
-                                    requires_one_of: RequiresOneOf {
-                                        features: &["rectangular_lines"],
-                                        ..Default::default()
-                                    },
-                                });
+                        StateMode::Fixed(line_rasterization_mode) => {
+                            // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-parameter
+                            line_rasterization_mode.validate_device(device)?;
+
+                            match line_rasterization_mode {
+                                LineRasterizationMode::Default => (),
+                                LineRasterizationMode::Rectangular => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-02768
+                                    if !device.enabled_features().rectangular_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::Rectangular`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["rectangular_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
+                                }
+                                LineRasterizationMode::Bresenham => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-02769
+                                    if !device.enabled_features().bresenham_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::Bresenham`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["bresenham_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
+                                }
+                                LineRasterizationMode::RectangularSmooth => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-02770
+                                    if !device.enabled_features().smooth_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::RectangularSmooth`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["smooth_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
+                                }
                             }
                         }
-                        LineRasterizationMode::Bresenham => {
-                            // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-02769
-                            if !device.enabled_features().bresenham_lines {
+                        StateMode::Dynamic => {
+                            // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3LineRasterizationMode-07388
+                            if !device
+                                .enabled_features()
+                                .extended_dynamic_state3_line_rasterization_mode
+                            {
                                 return Err(GraphicsPipelineCreationError::RequirementNotMet {
                                     required_for: "`rasterization_state.line_rasterization_mode` \
-                                        is `LineRasterizationMode::Bresenham`",
+                                        is `StateMode::Dynamic`",
                                     requires_one_of: RequiresOneOf {
-                                        features: &["bresenham_lines"],
+                                        features: &[
+                                            "extended_dynamic_state3_line_rasterization_mode",
+                                        ],
                                         ..Default::default()
                                     },
                                 });
                             }
                         }
-                        LineRasterizationMode::RectangularSmooth => {
-                            // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-02770
-                            if !device.enabled_features().smooth_lines {
-                                return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                                    required_for: "`rasterization_state.line_rasterization_mode` \
-                                        is `LineRasterizationMode::RectangularSmooth`",
-                                    requires_one_of: RequiresOneOf {
-                                        features: &["smooth_lines"],
-                                        ..Default::default()
-                                    },
-                                });
-                            }
+                    }
+
+                    if line_stipple_enable_dynamic {
+                        // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3LineStippleEnable-07389
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_line_stipple_enable
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.line_stipple_enable_dynamic` \
+                                    is set",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_line_stipple_enable"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        if line_stipple.is_none() {
+                            return Err(GraphicsPipelineCreationError::LineStippleMissing);
                         }
                     }
 
                     if let Some(line_stipple) = line_stipple {
-                        match line_rasterization_mode {
-                            LineRasterizationMode::Default => {
-                                // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02774
-                                if !device.enabled_features().stippled_rectangular_lines {
-                                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                                        required_for: "`rasterization_state.line_stipple` is \
-                                            `Some` and \
-                                            `rasterization_state.line_rasterization_mode` \
-                                            is `LineRasterizationMode::Default`",
-                                        requires_one_of: RequiresOneOf {
-                                            features: &["stippled_rectangular_lines"],
-                                            ..Default::default()
-                                        },
-                                    });
-                                }
+                        if let StateMode::Fixed(line_rasterization_mode) = line_rasterization_mode {
+                            match line_rasterization_mode {
+                                LineRasterizationMode::Default => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02774
+                                    if !device.enabled_features().stippled_rectangular_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_stipple` is \
+                                                    `Some` and \
+                                                    `rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::Default`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["stippled_rectangular_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
 
-                                // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02774
-                                if !properties.strict_lines {
-                                    return Err(
-                                        GraphicsPipelineCreationError::StrictLinesNotSupported,
-                                    );
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02774
+                                    if !properties.strict_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::StrictLinesNotSupported,
+                                        );
+                                    }
                                 }
-                            }
-                            LineRasterizationMode::Rectangular => {
-                                // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02771
-                                if !device.enabled_features().stippled_rectangular_lines {
-                                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                                        required_for: "`rasterization_state.line_stipple` is \
-                                            `Some` and \
-                                            `rasterization_state.line_rasterization_mode` \
-                                            is `LineRasterizationMode::Rectangular`",
-                                        requires_one_of: RequiresOneOf {
-                                            features: &["stippled_rectangular_lines"],
-                                            ..Default::default()
-                                        },
-                                    });
+                                LineRasterizationMode::Rectangular => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02771
+                                    if !device.enabled_features().stippled_rectangular_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_stipple` is \
+                                                    `Some` and \
+                                                    `rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::Rectangular`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["stippled_rectangular_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
                                 }
-                            }
-                            LineRasterizationMode::Bresenham => {
-                                // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02772
-                                if !device.enabled_features().stippled_bresenham_lines {
-                                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                                        required_for: "`rasterization_state.line_stipple` is \
-                                            `Some` and \
-                                            `rasterization_state.line_rasterization_mode` \
-                                            is `LineRasterizationMode::Bresenham`",
-                                        requires_one_of: RequiresOneOf {
-                                            features: &["stippled_bresenham_lines"],
-                                            ..Default::default()
-                                        },
-                                    });
+                                LineRasterizationMode::Bresenham => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02772
+                                    if !device.enabled_features().stippled_bresenham_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_stipple` is \
+                                                    `Some` and \
+                                                    `rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::Bresenham`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["stippled_bresenham_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
                                 }
-                            }
-                            LineRasterizationMode::RectangularSmooth => {
-                                // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02773
-                                if !device.enabled_features().stippled_smooth_lines {
-                                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                                        required_for: "`rasterization_state.line_stipple` is \
-                                            `Some` and \
-                                            `rasterization_state.line_rasterization_mode` \
-                                            is `LineRasterizationMode::RectangularSmooth`",
-                                        requires_one_of: RequiresOneOf {
-                                            features: &["stippled_smooth_lines"],
-                                            ..Default::default()
-                                        },
-                                    });
+                                LineRasterizationMode::RectangularSmooth => {
+                                    // VUID-VkPipelineRasterizationLineStateCreateInfoEXT-stippledLineEnable-02773
+                                    if !device.enabled_features().stippled_smooth_lines {
+                                        return Err(
+                                            GraphicsPipelineCreationError::RequirementNotMet {
+                                                required_for:
+                                                    "`rasterization_state.line_stipple` is \
+                                                    `Some` and \
+                                                    `rasterization_state.line_rasterization_mode` \
+                                                    is `LineRasterizationMode::RectangularSmooth`",
+                                                requires_one_of: RequiresOneOf {
+                                                    features: &["stippled_smooth_lines"],
+                                                    ..Default::default()
+                                                },
+                                            },
+                                        );
+                                    }
                                 }
                             }
                         }
@@ -1509,10 +1708,10 @@ where
                         }
                     }
                 } else {
-                    if line_rasterization_mode != LineRasterizationMode::Default {
+                    if line_rasterization_mode != StateMode::Fixed(LineRasterizationMode::Default) {
                         return Err(GraphicsPipelineCreationError::RequirementNotMet {
                             required_for: "`rasterization_state.line_rasterization_mode` is not \
-                                `LineRasterizationMode::Default`",
+                                `StateMode::Fixed(LineRasterizationMode::Default)`",
                             requires_one_of: RequiresOneOf {
                                 device_extensions: &["ext_line_rasterization"],
                                 ..Default::default()
@@ -1520,9 +1719,10 @@ where
                         });
                     }
 
-                    if line_stipple.is_some() {
+                    if line_stipple.is_some() || line_stipple_enable_dynamic {
                         return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                            required_for: "`rasterization_state.line_stipple` is `Some`",
+                            required_for: "`rasterization_state.line_stipple` is `Some`, or \
+                                `rasterization_state.line_stipple_enable_dynamic` is set",
                             requires_one_of: RequiresOneOf {
                                 device_extensions: &["ext_line_rasterization"],
                                 ..Default::default()
@@ -1573,6 +1773,131 @@ where
                 }
             }
 
+            // Transform feedback state
+            {
+                let &TransformFeedbackState {
+                    rasterization_stream,
+                } = transform_feedback_state;
+                let max_transform_feedback_streams =
+                    properties.max_transform_feedback_streams.unwrap_or(0);
+
+                match rasterization_stream {
+                    StateMode::Fixed(0) => (),
+                    StateMode::Fixed(rasterization_stream) => {
+                        if !device.enabled_extensions().ext_transform_feedback {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`transform_feedback_state.rasterization_stream` is \
+                                    not 0",
+                                requires_one_of: RequiresOneOf {
+                                    device_extensions: &["ext_transform_feedback"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        // VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-geometryStreams-02324
+                        if !device.enabled_features().geometry_streams {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`transform_feedback_state.rasterization_stream` is \
+                                    not 0",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["geometry_streams"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        // VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02325
+                        if rasterization_stream >= max_transform_feedback_streams {
+                            return Err(
+                                GraphicsPipelineCreationError::MaxTransformFeedbackStreamsExceeded {
+                                    max: max_transform_feedback_streams,
+                                    obtained: rasterization_stream + 1,
+                                },
+                            );
+                        }
+
+                        // VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02326
+                        if !properties
+                            .transform_feedback_rasterization_stream_select
+                            .unwrap_or(false)
+                        {
+                            return Err(GraphicsPipelineCreationError::TransformFeedbackRasterizationStreamSelectNotSupported);
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3RasterizationStream-07381
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_rasterization_stream
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`transform_feedback_state.rasterization_stream` \
+                                    is `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_rasterization_stream"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                }
+
+                if let Some(outputs) = self
+                    .last_pre_rasterization_shader()
+                    .and_then(|entry_point| entry_point.transform_feedback_outputs())
+                {
+                    let max_transform_feedback_buffers =
+                        properties.max_transform_feedback_buffers.unwrap_or(0);
+                    let max_transform_feedback_buffer_data_size = properties
+                        .max_transform_feedback_buffer_data_size
+                        .unwrap_or(0);
+                    let max_transform_feedback_buffer_data_stride = properties
+                        .max_transform_feedback_buffer_data_stride
+                        .unwrap_or(0);
+
+                    for output in outputs {
+                        // VUID-RuntimeSpirv-XfbBuffer-06309
+                        if output.buffer >= max_transform_feedback_buffers {
+                            return Err(
+                                GraphicsPipelineCreationError::MaxTransformFeedbackBuffersExceeded {
+                                    max: max_transform_feedback_buffers,
+                                    obtained: output.buffer + 1,
+                                },
+                            );
+                        }
+
+                        // VUID-RuntimeSpirv-XfbStride-06313
+                        if output.stride > max_transform_feedback_buffer_data_stride {
+                            return Err(GraphicsPipelineCreationError::MaxTransformFeedbackBufferDataStrideExceeded {
+                                buffer: output.buffer,
+                                max: max_transform_feedback_buffer_data_stride,
+                                obtained: output.stride,
+                            });
+                        }
+
+                        // VUID-RuntimeSpirv-Offset-06308
+                        if output.offset + output.size > max_transform_feedback_buffer_data_size {
+                            return Err(GraphicsPipelineCreationError::MaxTransformFeedbackBufferDataSizeExceeded {
+                                buffer: output.buffer,
+                                max: max_transform_feedback_buffer_data_size,
+                                obtained: output.offset + output.size,
+                            });
+                        }
+
+                        // VUID-RuntimeSpirv-Stream-06312
+                        if output.stream >= max_transform_feedback_streams {
+                            return Err(
+                                GraphicsPipelineCreationError::MaxTransformFeedbackStreamsExceeded {
+                                    max: max_transform_feedback_streams,
+                                    obtained: output.stream + 1,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+
             // TODO:
             // VUID-VkPipelineShaderStageCreateInfo-stage-02596
             // VUID-VkPipelineShaderStageCreateInfo-stage-02597
@@ -1892,6 +2217,126 @@ where
 
             // TODO:
             // VUID-VkGraphicsPipelineCreateInfo-renderPass-06038
+
+            // Fragment shading rate state
+            match fragment_shading_rate_state.fragment_shading_rate {
+                StateMode::Fixed(fragment_shading_rate) => {
+                    if fragment_shading_rate != FragmentShadingRate::default() {
+                        let FragmentShadingRate {
+                            fragment_size,
+                            combiner_ops,
+                        } = fragment_shading_rate;
+
+                        if !device.enabled_extensions().khr_fragment_shading_rate {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for:
+                                    "`fragment_shading_rate_state.fragment_shading_rate` \
+                                    is not the default value",
+                                requires_one_of: RequiresOneOf {
+                                    device_extensions: &["khr_fragment_shading_rate"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04494
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04495
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04496
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04497
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04498
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04499
+                        if !fragment_size.iter().all(|&size| matches!(size, 1 | 2 | 4)) {
+                            return Err(
+                                GraphicsPipelineCreationError::FragmentShadingRateInvalidFragmentSize {
+                                    fragment_size,
+                                },
+                            );
+                        }
+
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04500
+                        if fragment_size != [1, 1]
+                            && !device.enabled_features().pipeline_fragment_shading_rate
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for:
+                                    "`fragment_shading_rate_state.fragment_shading_rate` \
+                                    is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                    `fragment_shading_rate.fragment_size` is not `[1, 1]`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["pipeline_fragment_shading_rate"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        // VUID-VkPipelineFragmentShadingRateStateCreateInfoKHR-combinerOps-parameter
+                        for op in combiner_ops {
+                            op.validate_device(device)?;
+                        }
+
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04501
+                        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+                            && !device.enabled_features().primitive_fragment_shading_rate
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for:
+                                    "`fragment_shading_rate_state.fragment_shading_rate` \
+                                    is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                    `fragment_shading_rate.combiner_ops[0]` is not \
+                                    `FragmentShadingRateCombinerOp::Keep`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["primitive_fragment_shading_rate"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04502
+                        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+                            && !device.enabled_features().attachment_fragment_shading_rate
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for:
+                                    "`fragment_shading_rate_state.fragment_shading_rate` \
+                                    is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                    `fragment_shading_rate.combiner_ops[1]` is not \
+                                    `FragmentShadingRateCombinerOp::Keep`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["attachment_fragment_shading_rate"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+
+                        // VUID-VkGraphicsPipelineCreateInfo-fragmentShadingRateNonTrivialCombinerOps-04506
+                        if !properties
+                            .fragment_shading_rate_non_trivial_combiner_ops
+                            .unwrap_or(false)
+                            && !combiner_ops.iter().all(|&op| {
+                                matches!(
+                                    op,
+                                    FragmentShadingRateCombinerOp::Keep
+                                        | FragmentShadingRateCombinerOp::Replace
+                                )
+                            })
+                        {
+                            return Err(GraphicsPipelineCreationError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+                        }
+                    }
+                }
+                StateMode::Dynamic => {
+                    if !device.enabled_extensions().khr_fragment_shading_rate {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`fragment_shading_rate_state.fragment_shading_rate` is \
+                                `StateMode::Dynamic`",
+                            requires_one_of: RequiresOneOf {
+                                device_extensions: &["khr_fragment_shading_rate"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+            }
         }
 
         // Depth/stencil state
@@ -2189,17 +2634,73 @@ where
                 let &MultisampleState {
                     rasterization_samples,
                     sample_shading,
-                    sample_mask: _,
-                    alpha_to_coverage_enable: _,
+                    sample_mask,
+                    alpha_to_coverage_enable,
                     alpha_to_one_enable,
                 } = multisample_state;
 
-                // VUID-VkPipelineMultisampleStateCreateInfo-rasterizationSamples-parameter
-                rasterization_samples.validate_device(device)?;
+                match rasterization_samples {
+                    StateMode::Fixed(rasterization_samples) => {
+                        // VUID-VkPipelineMultisampleStateCreateInfo-rasterizationSamples-parameter
+                        rasterization_samples.validate_device(device)?;
+                    }
+                    StateMode::Dynamic => {
+                        // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3RasterizationSamples-07373
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_rasterization_samples
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`multisample_state.rasterization_samples` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_rasterization_samples"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                }
+
+                if sample_mask == StateMode::Dynamic {
+                    // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3SampleMask-07374
+                    if !device
+                        .enabled_features()
+                        .extended_dynamic_state3_sample_mask
+                    {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`multisample_state.sample_mask` is \
+                                `StateMode::Dynamic`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["extended_dynamic_state3_sample_mask"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+
+                if alpha_to_coverage_enable == StateMode::Dynamic {
+                    // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3AlphaToCoverageEnable-07375
+                    if !device
+                        .enabled_features()
+                        .extended_dynamic_state3_alpha_to_coverage_enable
+                    {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`multisample_state.alpha_to_coverage_enable` is \
+                                `StateMode::Dynamic`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["extended_dynamic_state3_alpha_to_coverage_enable"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
 
                 match render_pass {
                     PipelineRenderPassType::BeginRenderPass(subpass) => {
-                        if let Some(samples) = subpass.num_samples() {
+                        if let (StateMode::Fixed(rasterization_samples), Some(samples)) =
+                            (rasterization_samples, subpass.num_samples())
+                        {
                             // VUID-VkGraphicsPipelineCreateInfo-subpass-00757
                             if rasterization_samples != samples {
                                 return Err(GraphicsPipelineCreationError::MultisampleRasterizationSamplesMismatch);
@@ -2235,15 +2736,36 @@ where
                     assert!((0.0..=1.0).contains(&min_sample_shading));
                 }
 
-                // VUID-VkPipelineMultisampleStateCreateInfo-alphaToOneEnable-00785
-                if alpha_to_one_enable && !device.enabled_features().alpha_to_one {
-                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                        required_for: "`multisample_state.alpha_to_one_enable` is set",
-                        requires_one_of: RequiresOneOf {
-                            features: &["alpha_to_one"],
-                            ..Default::default()
-                        },
-                    });
+                match alpha_to_one_enable {
+                    StateMode::Fixed(alpha_to_one_enable) => {
+                        // VUID-VkPipelineMultisampleStateCreateInfo-alphaToOneEnable-00785
+                        if alpha_to_one_enable && !device.enabled_features().alpha_to_one {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`multisample_state.alpha_to_one_enable` is \
+                                    `StateMode::Fixed(true)`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["alpha_to_one"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3AlphaToOneEnable-07376
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_alpha_to_one_enable
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`multisample_state.alpha_to_one_enable` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_alpha_to_one_enable"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
                 }
 
                 // TODO:
@@ -2255,12 +2777,84 @@ where
         // VUID-VkGraphicsPipelineCreateInfo-renderPass-06044
         // VUID-VkGraphicsPipelineCreateInfo-renderPass-06054
         if has.color_blend_state {
-            let ColorBlendState {
+            let &ColorBlendState {
                 logic_op,
-                attachments,
+                ref attachments,
+                logic_op_enable_dynamic,
+                blend_enable_dynamic,
+                blend_equation_dynamic,
+                color_write_mask_dynamic,
                 blend_constants: _,
             } = color_blend_state;
 
+            if logic_op_enable_dynamic {
+                // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3LogicOpEnable-07377
+                if !device
+                    .enabled_features()
+                    .extended_dynamic_state3_logic_op_enable
+                {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "`color_blend_state.logic_op_enable_dynamic` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: &["extended_dynamic_state3_logic_op_enable"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                if logic_op.is_none() {
+                    return Err(GraphicsPipelineCreationError::LogicOpMissing);
+                }
+            }
+
+            if blend_enable_dynamic {
+                // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorBlendEnable-07378
+                if !device
+                    .enabled_features()
+                    .extended_dynamic_state3_color_blend_enable
+                {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "`color_blend_state.blend_enable_dynamic` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: &["extended_dynamic_state3_color_blend_enable"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+
+            if blend_equation_dynamic {
+                // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorBlendEquation-07379
+                if !device
+                    .enabled_features()
+                    .extended_dynamic_state3_color_blend_equation
+                {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "`color_blend_state.blend_equation_dynamic` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: &["extended_dynamic_state3_color_blend_equation"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+
+            if color_write_mask_dynamic {
+                // VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorWriteMask-07380
+                if !device
+                    .enabled_features()
+                    .extended_dynamic_state3_color_write_mask
+                {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "`color_blend_state.color_write_mask_dynamic` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: &["extended_dynamic_state3_color_write_mask"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+
             if let Some(logic_op) = logic_op {
                 // VUID-VkPipelineColorBlendStateCreateInfo-logicOpEnable-00606
                 if !device.enabled_features().logic_op {
@@ -2538,7 +3132,6 @@ where
         // - ViewportShadingRatePalette (VkPipelineViewportShadingRateImageStateCreateInfoNV)
         // - ViewportCoarseSampleOrder (VkPipelineViewportCoarseSampleOrderStateCreateInfoNV)
         // - ExclusiveScissor (VkPipelineViewportExclusiveScissorStateCreateInfoNV)
-        // - FragmentShadingRate (VkPipelineFragmentShadingRateStateCreateInfoKHR)
 
         Ok(())
     }
@@ -2569,11 +3162,14 @@ where
             fragment_shader,
 
             vertex_input_state: _,
+            vertex_input_state_dynamic,
             input_assembly_state,
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            transform_feedback_state,
             rasterization_state,
+            fragment_shading_rate_state,
             multisample_state,
             depth_stencil_state,
             color_blend_state,
@@ -2645,7 +3241,9 @@ where
 
         if has.vertex_input_state {
             // Vertex input state
-            {
+            if *vertex_input_state_dynamic {
+                dynamic_state.insert(DynamicState::VertexInput, true);
+            } else {
                 dynamic_state.insert(DynamicState::VertexInput, false);
 
                 let VertexInputState {
@@ -2768,6 +3366,7 @@ where
         let mut scissors_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut viewport_state_vk = None;
         let mut rasterization_line_state_vk = None;
+        let mut rasterization_stream_state_vk = None;
         let mut rasterization_state_vk = None;
         let mut discard_rectangles: SmallVec<[_; 2]> = SmallVec::new();
         let mut discard_rectangle_state_vk = None;
@@ -2940,8 +3539,20 @@ where
                     line_width,
                     line_rasterization_mode,
                     line_stipple,
+                    line_stipple_enable_dynamic,
                 } = rasterization_state;
 
+                let depth_clamp_enable = match depth_clamp_enable {
+                    StateMode::Fixed(depth_clamp_enable) => {
+                        dynamic_state.insert(DynamicState::DepthClampEnable, false);
+                        depth_clamp_enable as ash::vk::Bool32
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::DepthClampEnable, true);
+                        ash::vk::FALSE
+                    }
+                };
+
                 let rasterizer_discard_enable = match rasterizer_discard_enable {
                     StateMode::Fixed(rasterizer_discard_enable) => {
                         dynamic_state.insert(DynamicState::RasterizerDiscardEnable, false);
@@ -3003,6 +3614,17 @@ where
                     (ash::vk::FALSE, 0.0, 0.0, 0.0)
                 };
 
+                let polygon_mode = match polygon_mode {
+                    StateMode::Fixed(polygon_mode) => {
+                        dynamic_state.insert(DynamicState::PolygonMode, false);
+                        polygon_mode.into()
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::PolygonMode, true);
+                        PolygonMode::default().into()
+                    }
+                };
+
                 let line_width = match line_width {
                     StateMode::Fixed(line_width) => {
                         dynamic_state.insert(DynamicState::LineWidth, false);
@@ -3017,9 +3639,9 @@ where
                 let rasterization_state =
                     rasterization_state_vk.insert(ash::vk::PipelineRasterizationStateCreateInfo {
                         flags: ash::vk::PipelineRasterizationStateCreateFlags::empty(),
-                        depth_clamp_enable: depth_clamp_enable as ash::vk::Bool32,
+                        depth_clamp_enable,
                         rasterizer_discard_enable,
-                        polygon_mode: polygon_mode.into(),
+                        polygon_mode,
                         cull_mode,
                         front_face,
                         depth_bias_enable,
@@ -3031,6 +3653,20 @@ where
                     });
 
                 if device.enabled_extensions().ext_line_rasterization {
+                    let line_rasterization_mode = match line_rasterization_mode {
+                        StateMode::Fixed(line_rasterization_mode) => {
+                            dynamic_state.insert(DynamicState::LineRasterizationMode, false);
+                            line_rasterization_mode.into()
+                        }
+                        StateMode::Dynamic => {
+                            dynamic_state.insert(DynamicState::LineRasterizationMode, true);
+                            LineRasterizationMode::default().into()
+                        }
+                    };
+
+                    dynamic_state
+                        .insert(DynamicState::LineStippleEnable, line_stipple_enable_dynamic);
+
                     let (stippled_line_enable, line_stipple_factor, line_stipple_pattern) =
                         if let Some(line_stipple) = line_stipple {
                             let (factor, pattern) = match line_stipple {
@@ -3051,7 +3687,7 @@ where
 
                     rasterization_state.p_next = rasterization_line_state_vk.insert(
                         ash::vk::PipelineRasterizationLineStateCreateInfoEXT {
-                            line_rasterization_mode: line_rasterization_mode.into(),
+                            line_rasterization_mode,
                             stippled_line_enable,
                             line_stipple_factor,
                             line_stipple_pattern,
@@ -3059,6 +3695,33 @@ where
                         },
                     ) as *const _ as *const _;
                 }
+
+                if device.enabled_extensions().ext_transform_feedback {
+                    let &TransformFeedbackState {
+                        rasterization_stream,
+                    } = transform_feedback_state;
+
+                    let rasterization_stream = match rasterization_stream {
+                        StateMode::Fixed(rasterization_stream) => {
+                            dynamic_state.insert(DynamicState::RasterizationStream, false);
+                            rasterization_stream
+                        }
+                        StateMode::Dynamic => {
+                            dynamic_state.insert(DynamicState::RasterizationStream, true);
+                            0
+                        }
+                    };
+
+                    let rasterization_stream_state = rasterization_stream_state_vk.insert(
+                        ash::vk::PipelineRasterizationStateStreamCreateInfoEXT {
+                            flags: ash::vk::PipelineRasterizationStateStreamCreateFlagsEXT::empty(),
+                            rasterization_stream,
+                            ..Default::default()
+                        },
+                    );
+                    rasterization_stream_state.p_next = rasterization_state.p_next;
+                    rasterization_state.p_next = rasterization_stream_state as *const _ as *const _;
+                }
             }
 
             // Discard rectangle state
@@ -3227,6 +3890,7 @@ where
         */
 
         let mut fragment_shader_specialization_vk = None;
+        let mut fragment_shading_rate_state_vk = None;
         let mut depth_stencil_state_vk = None;
 
         if has.fragment_shader_state {
@@ -3274,6 +3938,34 @@ where
                     _ => unreachable!(),
                 };
             }
+
+            // Fragment shading rate state
+            if device.enabled_extensions().khr_fragment_shading_rate {
+                let FragmentShadingRate {
+                    fragment_size,
+                    combiner_ops,
+                } = match fragment_shading_rate_state.fragment_shading_rate {
+                    StateMode::Fixed(fragment_shading_rate) => {
+                        dynamic_state.insert(DynamicState::FragmentShadingRate, false);
+                        fragment_shading_rate
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::FragmentShadingRate, true);
+                        Default::default()
+                    }
+                };
+
+                let _ = fragment_shading_rate_state_vk.insert(
+                    ash::vk::PipelineFragmentShadingRateStateCreateInfoKHR {
+                        fragment_size: ash::vk::Extent2D {
+                            width: fragment_size[0],
+                            height: fragment_size[1],
+                        },
+                        combiner_ops: combiner_ops.map(Into::into),
+                        ..Default::default()
+                    },
+                );
+            }
         }
 
         // Depth/stencil state
@@ -3484,6 +4176,50 @@ where
                     alpha_to_one_enable,
                 } = multisample_state;
 
+                let rasterization_samples = match rasterization_samples {
+                    StateMode::Fixed(rasterization_samples) => {
+                        dynamic_state.insert(DynamicState::RasterizationSamples, false);
+                        rasterization_samples.into()
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::RasterizationSamples, true);
+                        ash::vk::SampleCountFlags::TYPE_1
+                    }
+                };
+
+                let p_sample_mask = match sample_mask {
+                    StateMode::Fixed(sample_mask) => {
+                        dynamic_state.insert(DynamicState::SampleMask, false);
+                        sample_mask.as_ptr()
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::SampleMask, true);
+                        ptr::null()
+                    }
+                };
+
+                let alpha_to_coverage_enable = match alpha_to_coverage_enable {
+                    StateMode::Fixed(alpha_to_coverage_enable) => {
+                        dynamic_state.insert(DynamicState::AlphaToCoverageEnable, false);
+                        alpha_to_coverage_enable as ash::vk::Bool32
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::AlphaToCoverageEnable, true);
+                        ash::vk::FALSE
+                    }
+                };
+
+                let alpha_to_one_enable = match alpha_to_one_enable {
+                    StateMode::Fixed(alpha_to_one_enable) => {
+                        dynamic_state.insert(DynamicState::AlphaToOneEnable, false);
+                        alpha_to_one_enable as ash::vk::Bool32
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::AlphaToOneEnable, true);
+                        ash::vk::FALSE
+                    }
+                };
+
                 let (sample_shading_enable, min_sample_shading) =
                     if let Some(min_sample_shading) = sample_shading {
                         (ash::vk::TRUE, min_sample_shading)
@@ -3493,12 +4229,12 @@ where
 
                 let _ = multisample_state_vk.insert(ash::vk::PipelineMultisampleStateCreateInfo {
                     flags: ash::vk::PipelineMultisampleStateCreateFlags::empty(),
-                    rasterization_samples: rasterization_samples.into(),
+                    rasterization_samples,
                     sample_shading_enable,
                     min_sample_shading,
-                    p_sample_mask: sample_mask as _,
-                    alpha_to_coverage_enable: alpha_to_coverage_enable as ash::vk::Bool32,
-                    alpha_to_one_enable: alpha_to_one_enable as ash::vk::Bool32,
+                    p_sample_mask,
+                    alpha_to_coverage_enable,
+                    alpha_to_one_enable,
                     ..Default::default()
                 });
             }
@@ -3510,8 +4246,17 @@ where
                 logic_op,
                 ref attachments,
                 blend_constants,
+                logic_op_enable_dynamic,
+                blend_enable_dynamic,
+                blend_equation_dynamic,
+                color_write_mask_dynamic,
             } = color_blend_state;
 
+            dynamic_state.insert(DynamicState::LogicOpEnable, logic_op_enable_dynamic);
+            dynamic_state.insert(DynamicState::ColorBlendEnable, blend_enable_dynamic);
+            dynamic_state.insert(DynamicState::ColorBlendEquation, blend_equation_dynamic);
+            dynamic_state.insert(DynamicState::ColorWriteMask, color_write_mask_dynamic);
+
             color_blend_attachments_vk.extend(attachments.iter().map(
                 |color_blend_attachment_state| {
                     let &ColorBlendAttachmentState {
@@ -3632,8 +4377,14 @@ where
             Create
         */
 
+        let mut flags = ash::vk::PipelineCreateFlags::empty(); // TODO: some flags are available but none are critical
+
+        if pipeline_layout.descriptor_buffer() {
+            flags |= ash::vk::PipelineCreateFlags::DESCRIPTOR_BUFFER_EXT;
+        }
+
         let mut create_info = ash::vk::GraphicsPipelineCreateInfo {
-            flags: ash::vk::PipelineCreateFlags::empty(), // TODO: some flags are available but none are critical
+            flags,
             stage_count: stages_vk.len() as u32,
             p_stages: stages_vk.as_ptr(),
             p_vertex_input_state: vertex_input_state_vk
@@ -3685,6 +4436,11 @@ where
             create_info.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = fragment_shading_rate_state_vk.as_mut() {
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
         if let Some(info) = rendering_create_info_vk.as_mut() {
             info.p_next = create_info.p_next;
             create_info.p_next = info as *const _ as *const _;
@@ -3762,11 +4518,14 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             fragment_shader: self.fragment_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            transform_feedback_state: self.transform_feedback_state,
             rasterization_state: self.rasterization_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
@@ -3799,11 +4558,14 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             fragment_shader: self.fragment_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            transform_feedback_state: self.transform_feedback_state,
             rasterization_state: self.rasterization_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
@@ -3830,11 +4592,14 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             fragment_shader: self.fragment_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            transform_feedback_state: self.transform_feedback_state,
             rasterization_state: self.rasterization_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
@@ -3863,11 +4628,14 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             fragment_shader: Some((shader, specialization_constants)),
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            transform_feedback_state: self.transform_feedback_state,
             rasterization_state: self.rasterization_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
@@ -3894,17 +4662,39 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             fragment_shader: self.fragment_shader,
 
             vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            transform_feedback_state: self.transform_feedback_state,
             rasterization_state: self.rasterization_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
         }
     }
 
+    /// Sets the vertex input state as dynamic. It will need to be set with `set_vertex_input`
+    /// before drawing, and any vertex input state provided with
+    /// [`vertex_input_state`](Self::vertex_input_state) is ignored.
+    ///
+    /// This allows the same pipeline to be used with different vertex layouts. The vertex input
+    /// state for a given layout can be created by passing
+    /// [`GraphicsPipeline::vertex_input_interface`] to [`VertexDefinition::definition`], for
+    /// example with a [`BuffersDefinition`] or `Vertex::per_vertex()`.
+    ///
+    /// The [`vertex_input_dynamic_state`](crate::device::Features::vertex_input_dynamic_state)
+    /// feature must be enabled on the device.
+    ///
+    /// [`BuffersDefinition`]: super::vertex_input::BuffersDefinition
+    #[inline]
+    pub fn vertex_input_state_dynamic(mut self) -> Self {
+        self.vertex_input_state_dynamic = true;
+        self
+    }
+
     /// Sets the input assembly state.
     ///
     /// The default value is [`InputAssemblyState::default()`].
@@ -3945,6 +4735,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the transform feedback state.
+    ///
+    /// The default value is [`TransformFeedbackState::default()`].
+    #[inline]
+    pub fn transform_feedback_state(
+        mut self,
+        transform_feedback_state: TransformFeedbackState,
+    ) -> Self {
+        self.transform_feedback_state = transform_feedback_state;
+        self
+    }
+
     /// Sets the rasterization state.
     ///
     /// The default value is [`RasterizationState::default()`].
@@ -3954,6 +4756,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the fragment shading rate state.
+    ///
+    /// The default value is [`FragmentShadingRateState::default()`].
+    #[inline]
+    pub fn fragment_shading_rate_state(
+        mut self,
+        fragment_shading_rate_state: FragmentShadingRateState,
+    ) -> Self {
+        self.fragment_shading_rate_state = fragment_shading_rate_state;
+        self
+    }
+
     /// Sets the multisample state.
     ///
     /// The default value is [`MultisampleState::default()`].
@@ -4205,7 +5019,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn depth_clamp(mut self, clamp: bool) -> Self {
-        self.rasterization_state.depth_clamp_enable = clamp;
+        self.rasterization_state.depth_clamp_enable = StateMode::Fixed(clamp);
         self
     }
 
@@ -4272,7 +5086,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn polygon_mode_fill(mut self) -> Self {
-        self.rasterization_state.polygon_mode = PolygonMode::Fill;
+        self.rasterization_state.polygon_mode = StateMode::Fixed(PolygonMode::Fill);
         self
     }
 
@@ -4280,7 +5094,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn polygon_mode_line(mut self) -> Self {
-        self.rasterization_state.polygon_mode = PolygonMode::Line;
+        self.rasterization_state.polygon_mode = StateMode::Fixed(PolygonMode::Line);
         self
     }
 
@@ -4288,7 +5102,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn polygon_mode_point(mut self) -> Self {
-        self.rasterization_state.polygon_mode = PolygonMode::Point;
+        self.rasterization_state.polygon_mode = StateMode::Fixed(PolygonMode::Point);
         self
     }
 
@@ -4347,14 +5161,14 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     // TODO: doc
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     pub fn alpha_to_coverage_disabled(mut self) -> Self {
-        self.multisample_state.alpha_to_coverage_enable = false;
+        self.multisample_state.alpha_to_coverage_enable = StateMode::Fixed(false);
         self
     }
 
     // TODO: doc
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     pub fn alpha_to_coverage_enabled(mut self) -> Self {
-        self.multisample_state.alpha_to_coverage_enable = true;
+        self.multisample_state.alpha_to_coverage_enable = StateMode::Fixed(true);
         self
     }
 
@@ -4364,7 +5178,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     #[inline]
     pub fn alpha_to_one_disabled(mut self) -> Self {
-        self.multisample_state.alpha_to_one_enable = false;
+        self.multisample_state.alpha_to_one_enable = StateMode::Fixed(false);
         self
     }
 
@@ -4377,7 +5191,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     #[inline]
     pub fn alpha_to_one_enabled(mut self) -> Self {
-        self.multisample_state.alpha_to_one_enable = true;
+        self.multisample_state.alpha_to_one_enable = StateMode::Fixed(true);
         self
     }
 
@@ -4526,15 +5340,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             fragment_shader: self.fragment_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             rasterization_state: self.rasterization_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
 
             discard_rectangle_state: self.discard_rectangle_state,
+            transform_feedback_state: self.transform_feedback_state,
         }
     }
 
@@ -4571,15 +5388,18 @@ where
             fragment_shader: self.fragment_shader.clone(),
 
             vertex_input_state: self.vertex_input_state.clone(),
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state.clone(),
             rasterization_state: self.rasterization_state.clone(),
+            fragment_shading_rate_state: self.fragment_shading_rate_state.clone(),
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state.clone(),
             color_blend_state: self.color_blend_state.clone(),
 
             discard_rectangle_state: self.discard_rectangle_state.clone(),
+            transform_feedback_state: self.transform_feedback_state.clone(),
         }
     }
 }
@@ -39,6 +39,15 @@ pub struct ColorBlendState {
     /// feature must also be enabled on the device.
     pub logic_op: Option<StateMode<LogicOp>>,
 
+    /// Sets whether logical operations should be enabled and disabled dynamically. If set to
+    /// `false`, logical operations are enabled if `logic_op` is `Some`. If set to `true`,
+    /// `logic_op` must be `Some`.
+    ///
+    /// If set to `true`, the
+    /// [`extended_dynamic_state3_logic_op_enable`](crate::device::Features::extended_dynamic_state3_logic_op_enable)
+    /// feature must be enabled on the device.
+    pub logic_op_enable_dynamic: bool,
+
     /// Sets the blend and output state for each color attachment. The number of elements must match
     /// the number of color attachments in the framebuffer.
     ///
@@ -47,6 +56,32 @@ pub struct ColorBlendState {
     /// feature must be enabled on the device.
     pub attachments: Vec<ColorBlendAttachmentState>,
 
+    /// Sets whether blending should be enabled and disabled dynamically for each attachment. If
+    /// set to `true`, the `blend` member of each element of `attachments` only provides the blend
+    /// equation.
+    ///
+    /// If set to `true`, the
+    /// [`extended_dynamic_state3_color_blend_enable`](crate::device::Features::extended_dynamic_state3_color_blend_enable)
+    /// feature must be enabled on the device.
+    pub blend_enable_dynamic: bool,
+
+    /// Sets whether the blend equation of each attachment should be set dynamically. If set to
+    /// `true`, the blend factors and operations in the `blend` member of each element of
+    /// `attachments` are ignored.
+    ///
+    /// If set to `true`, the
+    /// [`extended_dynamic_state3_color_blend_equation`](crate::device::Features::extended_dynamic_state3_color_blend_equation)
+    /// feature must be enabled on the device.
+    pub blend_equation_dynamic: bool,
+
+    /// Sets whether the color write mask of each attachment should be set dynamically. If set to
+    /// `true`, the `color_write_mask` member of each element of `attachments` is ignored.
+    ///
+    /// If set to `true`, the
+    /// [`extended_dynamic_state3_color_write_mask`](crate::device::Features::extended_dynamic_state3_color_write_mask)
+    /// feature must be enabled on the device.
+    pub color_write_mask_dynamic: bool,
+
     /// The constant color to use for some of the `BlendFactor` variants.
     pub blend_constants: StateMode<[f32; 4]>,
 }
@@ -59,6 +94,7 @@ impl ColorBlendState {
     pub fn new(num: u32) -> Self {
         Self {
             logic_op: None,
+            logic_op_enable_dynamic: false,
             attachments: (0..num)
                 .map(|_| ColorBlendAttachmentState {
                     blend: None,
@@ -66,6 +102,9 @@ impl ColorBlendState {
                     color_write_enable: StateMode::Fixed(true),
                 })
                 .collect(),
+            blend_enable_dynamic: false,
+            blend_equation_dynamic: false,
+            color_write_mask_dynamic: false,
             blend_constants: StateMode::Fixed([0.0, 0.0, 0.0, 0.0]),
         }
     }
@@ -111,6 +150,29 @@ impl ColorBlendState {
         self
     }
 
+    /// Sets whether logical operations are enabled to dynamic. The logical operation must also
+    /// be provided with [`logic_op`](Self::logic_op) or
+    /// [`logic_op_dynamic`](Self::logic_op_dynamic).
+    #[inline]
+    pub fn logic_op_enable_dynamic(mut self) -> Self {
+        self.logic_op_enable_dynamic = true;
+        self
+    }
+
+    /// Sets whether blending is enabled to dynamic for all attachments.
+    #[inline]
+    pub fn blend_enable_dynamic(mut self) -> Self {
+        self.blend_enable_dynamic = true;
+        self
+    }
+
+    /// Sets the blend equation to dynamic for all attachments.
+    #[inline]
+    pub fn blend_equation_dynamic(mut self) -> Self {
+        self.blend_equation_dynamic = true;
+        self
+    }
+
     /// Sets the color write mask for all attachments.
     #[inline]
     pub fn color_write_mask(mut self, color_write_mask: ColorComponents) -> Self {
@@ -120,6 +182,13 @@ impl ColorBlendState {
         self
     }
 
+    /// Sets the color write mask to dynamic for all attachments.
+    #[inline]
+    pub fn color_write_mask_dynamic(mut self) -> Self {
+        self.color_write_mask_dynamic = true;
+        self
+    }
+
     /// Sets the blend constants.
     #[inline]
     pub fn blend_constants(mut self, constants: [f32; 4]) -> Self {
@@ -317,6 +386,20 @@ impl From<AttachmentBlend> for ash::vk::PipelineColorBlendAttachmentState {
     }
 }
 
+impl From<AttachmentBlend> for ash::vk::ColorBlendEquationEXT {
+    #[inline]
+    fn from(val: AttachmentBlend) -> Self {
+        ash::vk::ColorBlendEquationEXT {
+            src_color_blend_factor: val.color_source.into(),
+            dst_color_blend_factor: val.color_destination.into(),
+            color_blend_op: val.color_op.into(),
+            src_alpha_blend_factor: val.alpha_source.into(),
+            dst_alpha_blend_factor: val.alpha_destination.into(),
+            alpha_blend_op: val.alpha_op.into(),
+        }
+    }
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -44,6 +44,15 @@ pub enum GraphicsPipelineCreationError {
     /// expects.
     FragmentShaderRenderPassIncompatible,
 
+    /// A component of `fragment_shading_rate_state.fragment_shading_rate.fragment_size` was not
+    /// 1, 2 or 4.
+    FragmentShadingRateInvalidFragmentSize { fragment_size: [u32; 2] },
+
+    /// An element of `fragment_shading_rate_state.fragment_shading_rate.combiner_ops` was not
+    /// `Keep` or `Replace`, but the `fragment_shading_rate_non_trivial_combiner_ops` device
+    /// property was false.
+    FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+
     /// The pipeline layout is not compatible with what the shaders expect.
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
 
@@ -60,6 +69,14 @@ pub enum GraphicsPipelineCreationError {
     /// `patch_control_points` was not greater than 0 and less than or equal to the `max_tessellation_patch_size` limit.
     InvalidNumPatchControlPoints,
 
+    /// `rasterization_state.line_stipple_enable_dynamic` was set, but
+    /// `rasterization_state.line_stipple` was `None`.
+    LineStippleMissing,
+
+    /// `color_blend_state.logic_op_enable_dynamic` was set, but `color_blend_state.logic_op` was
+    /// `None`.
+    LogicOpMissing,
+
     /// The maximum number of discard rectangles has been exceeded.
     MaxDiscardRectanglesExceeded {
         /// Maximum allowed value.
@@ -71,6 +88,45 @@ pub enum GraphicsPipelineCreationError {
     /// The `max_multiview_view_count` limit has been exceeded.
     MaxMultiviewViewCountExceeded { view_count: u32, max: u32 },
 
+    /// The last pre-rasterization shader captures an output into a transform feedback buffer
+    /// whose index exceeds the `max_transform_feedback_buffers` limit.
+    MaxTransformFeedbackBuffersExceeded {
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
+    /// The last pre-rasterization shader captures an output past the
+    /// `max_transform_feedback_buffer_data_size` limit.
+    MaxTransformFeedbackBufferDataSizeExceeded {
+        /// Index of the faulty buffer.
+        buffer: u32,
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
+    /// The last pre-rasterization shader declares a transform feedback buffer stride that exceeds
+    /// the `max_transform_feedback_buffer_data_stride` limit.
+    MaxTransformFeedbackBufferDataStrideExceeded {
+        /// Index of the faulty buffer.
+        buffer: u32,
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
+    /// A vertex stream index exceeds the `max_transform_feedback_streams` limit.
+    MaxTransformFeedbackStreamsExceeded {
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
     /// The maximum value for the instance rate divisor has been exceeded.
     MaxVertexAttribDivisorExceeded {
         /// Index of the faulty binding.
@@ -172,6 +228,11 @@ pub enum GraphicsPipelineCreationError {
     /// The [`strict_lines`](crate::device::Properties::strict_lines) device property was `false`.
     StrictLinesNotSupported,
 
+    /// The [`transform_feedback_rasterization_stream_select`] device property was `false`.
+    ///
+    /// [`transform_feedback_rasterization_stream_select`]: crate::device::Properties::transform_feedback_rasterization_stream_select
+    TransformFeedbackRasterizationStreamSelectNotSupported,
+
     /// The primitives topology does not match what the geometry shader expects.
     TopologyNotMatchingGeometryShader,
 
@@ -257,6 +318,16 @@ impl Display for GraphicsPipelineCreationError {
                 "the output of the fragment shader is not compatible with what the render pass \
                 subpass expects",
             ),
+            Self::FragmentShadingRateInvalidFragmentSize { fragment_size } => write!(
+                f,
+                "a component of the fragment size {:?} was not 1, 2 or 4",
+                fragment_size,
+            ),
+            Self::FragmentShadingRateNonTrivialCombinerOpsNotSupported => write!(
+                f,
+                "a fragment shading rate combiner operation was not `Keep` or `Replace`, but the \
+                `fragment_shading_rate_non_trivial_combiner_ops` device property was false",
+            ),
             Self::IncompatiblePipelineLayout(_) => write!(
                 f,
                 "the pipeline layout is not compatible with what the shaders expect",
@@ -280,6 +351,16 @@ impl Display for GraphicsPipelineCreationError {
                 "patch_control_points was not greater than 0 and less than or equal to the \
                 max_tessellation_patch_size limit",
             ),
+            Self::LineStippleMissing => write!(
+                f,
+                "`rasterization_state.line_stipple_enable_dynamic` was set, but \
+                `rasterization_state.line_stipple` was `None`",
+            ),
+            Self::LogicOpMissing => write!(
+                f,
+                "`color_blend_state.logic_op_enable_dynamic` was set, but \
+                `color_blend_state.logic_op` was `None`",
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => write!(
                 f,
                 "the maximum number of discard rectangles has been exceeded",
@@ -287,6 +368,22 @@ impl Display for GraphicsPipelineCreationError {
             Self::MaxMultiviewViewCountExceeded { .. } => {
                 write!(f, "the `max_multiview_view_count` limit has been exceeded")
             }
+            Self::MaxTransformFeedbackBuffersExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_buffers` limit has been exceeded",
+            ),
+            Self::MaxTransformFeedbackBufferDataSizeExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_buffer_data_size` limit has been exceeded",
+            ),
+            Self::MaxTransformFeedbackBufferDataStrideExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_buffer_data_stride` limit has been exceeded",
+            ),
+            Self::MaxTransformFeedbackStreamsExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_streams` limit has been exceeded",
+            ),
             Self::MaxVertexAttribDivisorExceeded { .. } => write!(
                 f,
                 "the maximum value for the instance rate divisor has been exceeded",
@@ -354,6 +451,10 @@ impl Display for GraphicsPipelineCreationError {
             Self::StrictLinesNotSupported => {
                 write!(f, "the strict_lines device property was false")
             }
+            Self::TransformFeedbackRasterizationStreamSelectNotSupported => write!(
+                f,
+                "the transform_feedback_rasterization_stream_select device property was false",
+            ),
             Self::TopologyNotMatchingGeometryShader => write!(
                 f,
                 "the primitives topology does not match what the geometry shader expects",
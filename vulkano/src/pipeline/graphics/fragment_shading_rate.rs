@@ -0,0 +1,133 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Shading multiple pixels with a single fragment shader invocation.
+//!
+//! The rate at which fragments are shaded can be specified in three places: for the whole draw
+//! call with the pipeline state (or the `set_fragment_shading_rate` dynamic command), per
+//! primitive with the `PrimitiveShadingRateKHR` built-in in the last pre-rasterization shader,
+//! and per region of the framebuffer with a fragment shading rate attachment. The combiner
+//! operations determine how these three rates are merged into the final rate.
+
+use crate::{macros::vulkan_enum, pipeline::StateMode};
+
+/// The state in a graphics pipeline describing the rate at which fragments are shaded.
+#[derive(Clone, Debug)]
+pub struct FragmentShadingRateState {
+    /// The pipeline fragment shading rate and combiner operations.
+    ///
+    /// If `fragment_size` is not `[1, 1]`, the
+    /// [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// If set to `Dynamic`, the
+    /// [`khr_fragment_shading_rate`](crate::device::DeviceExtensions::khr_fragment_shading_rate)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is a fragment size of `[1, 1]` with [`FragmentShadingRateCombinerOp::Keep`]
+    /// for both combiner operations.
+    pub fragment_shading_rate: StateMode<FragmentShadingRate>,
+}
+
+impl FragmentShadingRateState {
+    /// Creates a `FragmentShadingRateState` that shades every pixel individually.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            fragment_shading_rate: StateMode::Fixed(FragmentShadingRate::default()),
+        }
+    }
+
+    /// Sets the pipeline fragment shading rate and combiner operations.
+    #[inline]
+    pub fn fragment_shading_rate(mut self, fragment_shading_rate: FragmentShadingRate) -> Self {
+        self.fragment_shading_rate = StateMode::Fixed(fragment_shading_rate);
+        self
+    }
+
+    /// Sets the pipeline fragment shading rate and combiner operations to dynamic.
+    #[inline]
+    pub fn fragment_shading_rate_dynamic(mut self) -> Self {
+        self.fragment_shading_rate = StateMode::Dynamic;
+        self
+    }
+}
+
+impl Default for FragmentShadingRateState {
+    /// Returns [`FragmentShadingRateState::new`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fragment shading rate, and how it is combined with the primitive and attachment rates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FragmentShadingRate {
+    /// The width and height, in pixels, of the area covered by each fragment.
+    ///
+    /// Both values must be 1, 2 or 4.
+    ///
+    /// The default value is `[1, 1]`.
+    pub fragment_size: [u32; 2],
+
+    /// The operations used to combine the pipeline rate with the primitive rate, and the result
+    /// of that with the attachment rate, respectively.
+    ///
+    /// If the first operation is not `Keep`, the
+    /// [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    /// feature must be enabled on the device. If the second operation is not `Keep`, the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device. If either operation is not `Keep` or `Replace`,
+    /// the
+    /// [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    /// device property must be `true`.
+    ///
+    /// The default value is `[FragmentShadingRateCombinerOp::Keep; 2]`.
+    pub combiner_ops: [FragmentShadingRateCombinerOp; 2],
+}
+
+impl Default for FragmentShadingRate {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            fragment_size: [1, 1],
+            combiner_ops: [FragmentShadingRateCombinerOp::Keep; 2],
+        }
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// How two fragment shading rates are combined into one.
+    FragmentShadingRateCombinerOp = FragmentShadingRateCombinerOpKHR(i32);
+
+    /// The first rate is used, the second is ignored.
+    Keep = KEEP,
+
+    /// The second rate is used, the first is ignored.
+    Replace = REPLACE,
+
+    /// The smaller of the two rates is used, in each dimension.
+    Min = MIN,
+
+    /// The larger of the two rates is used, in each dimension.
+    Max = MAX,
+
+    /// The product of the two rates is used, in each dimension.
+    Mul = MUL,
+}
+
+impl Default for FragmentShadingRateCombinerOp {
+    #[inline]
+    fn default() -> FragmentShadingRateCombinerOp {
+        FragmentShadingRateCombinerOp::Keep
+    }
+}
@@ -0,0 +1,604 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Pipeline-wide checking of the interfaces between the parts of a graphics pipeline.
+
+use super::{
+    render_pass::PipelineRenderPassType,
+    vertex_input::{IncompatibleVertexDefinitionError, VertexInputState},
+};
+use crate::{
+    format::{Format, NumericType},
+    pipeline::{layout::PipelineLayoutSupersetError, PipelineLayout},
+    shader::{
+        EntryPoint, ShaderInterface, ShaderInterfaceMismatchError, ShaderScalarType, ShaderStage,
+    },
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    iter,
+};
+
+/// A report of every interface problem found in a graphics pipeline description.
+///
+/// This is returned by
+/// [`GraphicsPipelineBuilder::check_interfaces`](super::GraphicsPipelineBuilder::check_interfaces).
+/// Unlike pipeline creation, which stops at the first problem, the report lists all of them.
+/// It covers:
+/// - the vertex input state against the input interface of the vertex shader;
+/// - the output interface of each shader stage against the input interface of the next one;
+/// - the output interface of the fragment shader against the color attachments of the subpass
+///   or the rendering formats. Outputs without a color attachment are allowed, their writes are
+///   discarded;
+/// - the descriptor and push constant requirements of every stage against the pipeline layout.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PipelineInterfaceReport {
+    problems: Vec<PipelineInterfaceProblem>,
+}
+
+impl PipelineInterfaceReport {
+    /// Returns whether no problems were found.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Returns the problems that were found, in pipeline order.
+    #[inline]
+    pub fn problems(&self) -> &[PipelineInterfaceProblem] {
+        &self.problems
+    }
+
+    /// Returns the problems that were found, in pipeline order.
+    #[inline]
+    pub fn into_problems(self) -> Vec<PipelineInterfaceProblem> {
+        self.problems
+    }
+
+    /// Returns `Ok` if no problems were found, or the report itself otherwise.
+    #[inline]
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Checks all interfaces of a pipeline.
+    ///
+    /// `stages` must be in pipeline order. `vertex_input_state` is the result of resolving the
+    /// vertex definition against the vertex shader, if there is one.
+    pub(super) fn check(
+        vertex_input_state: Option<Result<VertexInputState, IncompatibleVertexDefinitionError>>,
+        stages: &[&EntryPoint<'_>],
+        render_pass: Option<&PipelineRenderPassType>,
+        pipeline_layout: Option<&PipelineLayout>,
+    ) -> Self {
+        let mut report = Self::default();
+
+        if let Some(vertex_shader) = stages
+            .iter()
+            .find(|entry_point| ShaderStage::from(*entry_point.execution()) == ShaderStage::Vertex)
+        {
+            match vertex_input_state {
+                Some(Ok(vertex_input_state)) => {
+                    report.check_vertex_input(vertex_shader.input_interface(), &vertex_input_state)
+                }
+                Some(Err(err)) => report
+                    .problems
+                    .push(PipelineInterfaceProblem::VertexDefinition(err)),
+                None => (),
+            }
+        }
+
+        for (output, input) in stages.iter().zip(stages.iter().skip(1)) {
+            report.check_stages(
+                ShaderStage::from(*output.execution()),
+                output.output_interface(),
+                ShaderStage::from(*input.execution()),
+                input.input_interface(),
+            );
+        }
+
+        if let (Some(fragment_shader), Some(render_pass)) = (
+            stages.iter().find(|entry_point| {
+                ShaderStage::from(*entry_point.execution()) == ShaderStage::Fragment
+            }),
+            render_pass,
+        ) {
+            let color_attachment_formats: Vec<Option<Format>> = match render_pass {
+                PipelineRenderPassType::BeginRenderPass(subpass) => subpass
+                    .subpass_desc()
+                    .color_attachments
+                    .iter()
+                    .map(|atch_ref| {
+                        atch_ref.as_ref().and_then(|atch_ref| {
+                            subpass.render_pass().attachments()[atch_ref.attachment as usize].format
+                        })
+                    })
+                    .collect(),
+                PipelineRenderPassType::BeginRendering(rendering_info) => {
+                    rendering_info.color_attachment_formats.clone()
+                }
+            };
+
+            report.check_fragment_output(
+                fragment_shader.output_interface(),
+                &color_attachment_formats,
+            );
+        }
+
+        if let Some(pipeline_layout) = pipeline_layout {
+            for entry_point in stages {
+                let stage = ShaderStage::from(*entry_point.execution());
+
+                for (loc, reqs) in entry_point.descriptor_binding_requirements() {
+                    if let Err(error) =
+                        pipeline_layout.ensure_compatible_with_shader([(loc, reqs)], None)
+                    {
+                        report.problems.push(
+                            PipelineInterfaceProblem::PipelineLayoutIncompatible { stage, error },
+                        );
+                    }
+                }
+
+                if let Err(error) = pipeline_layout.ensure_compatible_with_shader(
+                    iter::empty(),
+                    entry_point.push_constant_requirements(),
+                ) {
+                    report
+                        .problems
+                        .push(PipelineInterfaceProblem::PipelineLayoutIncompatible {
+                            stage,
+                            error,
+                        });
+                }
+            }
+        }
+
+        report
+    }
+
+    fn check_vertex_input(
+        &mut self,
+        input_interface: &ShaderInterface,
+        vertex_input_state: &VertexInputState,
+    ) {
+        for element in input_interface.elements() {
+            let location_range = element.location..element.location + element.ty.num_locations();
+
+            for location in location_range {
+                let attribute_desc = match vertex_input_state.attributes.get(&location) {
+                    Some(attribute_desc) => attribute_desc,
+                    None => {
+                        self.problems
+                            .push(PipelineInterfaceProblem::VertexInputAttributeMissing {
+                                location,
+                            });
+                        continue;
+                    }
+                };
+
+                let shader_type = element.ty.base_type;
+
+                if !is_numeric_type_compatible(shader_type, attribute_desc.format.type_color()) {
+                    self.problems.push(
+                        PipelineInterfaceProblem::VertexInputAttributeIncompatibleFormat {
+                            location,
+                            shader_type,
+                            attribute_format: attribute_desc.format,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_stages(
+        &mut self,
+        output_stage: ShaderStage,
+        output_interface: &ShaderInterface,
+        input_stage: ShaderStage,
+        input_interface: &ShaderInterface,
+    ) {
+        self.problems.extend(
+            input_interface
+                .mismatches(output_interface)
+                .into_iter()
+                .map(|error| PipelineInterfaceProblem::ShaderStagesMismatch {
+                    output_stage,
+                    input_stage,
+                    error,
+                }),
+        );
+    }
+
+    fn check_fragment_output(
+        &mut self,
+        output_interface: &ShaderInterface,
+        color_attachment_formats: &[Option<Format>],
+    ) {
+        for element in output_interface.elements() {
+            let location_range = element.location..element.location + element.ty.num_locations();
+
+            for location in location_range {
+                // Writes to a location without a color attachment are discarded, which is valid.
+                let format = match color_attachment_formats.get(location as usize) {
+                    Some(Some(format)) => *format,
+                    _ => continue,
+                };
+
+                let shader_type = element.ty.base_type;
+
+                if !is_numeric_type_compatible(shader_type, format.type_color()) {
+                    self.problems.push(
+                        PipelineInterfaceProblem::FragmentOutputIncompatibleFormat {
+                            location,
+                            shader_type,
+                            attachment_format: format,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Error for PipelineInterfaceReport {}
+
+impl Display for PipelineInterfaceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self.problems.len() {
+            0 => return write!(f, "no interface problems were found"),
+            1 => write!(f, "1 interface problem was found:")?,
+            count => write!(f, "{} interface problems were found:", count)?,
+        }
+
+        for problem in &self.problems {
+            write!(f, "\n- {}", problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single problem found while checking the interfaces of a graphics pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineInterfaceProblem {
+    /// The vertex definition could not produce a vertex input state for the vertex shader.
+    VertexDefinition(IncompatibleVertexDefinitionError),
+
+    /// The vertex shader has an input variable at a location that has no vertex input attribute.
+    VertexInputAttributeMissing { location: u32 },
+
+    /// The format of a vertex input attribute is not compatible with the type of the
+    /// corresponding input variable of the vertex shader.
+    VertexInputAttributeIncompatibleFormat {
+        location: u32,
+        shader_type: ShaderScalarType,
+        attribute_format: Format,
+    },
+
+    /// The output interface of a shader stage does not match the input interface of the next
+    /// stage.
+    ShaderStagesMismatch {
+        output_stage: ShaderStage,
+        input_stage: ShaderStage,
+        error: ShaderInterfaceMismatchError,
+    },
+
+    /// The format of a color attachment is not compatible with the type of the corresponding
+    /// output variable of the fragment shader.
+    FragmentOutputIncompatibleFormat {
+        location: u32,
+        shader_type: ShaderScalarType,
+        attachment_format: Format,
+    },
+
+    /// The pipeline layout does not satisfy the descriptor or push constant requirements of a
+    /// shader stage.
+    PipelineLayoutIncompatible {
+        stage: ShaderStage,
+        error: PipelineLayoutSupersetError,
+    },
+}
+
+impl Error for PipelineInterfaceProblem {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::VertexDefinition(err) => Some(err),
+            Self::ShaderStagesMismatch { error, .. } => Some(error),
+            Self::PipelineLayoutIncompatible { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Display for PipelineInterfaceProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::VertexDefinition(err) => write!(
+                f,
+                "the vertex definition is not compatible with the input of the vertex shader: {}",
+                err,
+            ),
+            Self::VertexInputAttributeMissing { location } => write!(
+                f,
+                "the vertex shader has an input variable at location {}, but the vertex input \
+                state contains no attribute for it",
+                location,
+            ),
+            Self::VertexInputAttributeIncompatibleFormat {
+                location,
+                shader_type,
+                attribute_format,
+            } => write!(
+                f,
+                "the type of the vertex shader input variable at location {} ({:?}) is not \
+                compatible with the format of the corresponding vertex input attribute ({:?})",
+                location, shader_type, attribute_format,
+            ),
+            Self::ShaderStagesMismatch {
+                output_stage,
+                input_stage,
+                error,
+            } => write!(
+                f,
+                "the output interface of the {:?} shader does not match the input interface of \
+                the {:?} shader: {}",
+                output_stage, input_stage, error,
+            ),
+            Self::FragmentOutputIncompatibleFormat {
+                location,
+                shader_type,
+                attachment_format,
+            } => write!(
+                f,
+                "the type of the fragment shader output variable at location {} ({:?}) is not \
+                compatible with the format of the corresponding color attachment ({:?})",
+                location, shader_type, attachment_format,
+            ),
+            Self::PipelineLayoutIncompatible { stage, error } => write!(
+                f,
+                "the pipeline layout is not compatible with what the {:?} shader expects: {}",
+                stage, error,
+            ),
+        }
+    }
+}
+
+/// Returns whether a shader variable of type `shader_type` can be read from or written to
+/// a format with the numeric type `numeric_type`.
+pub(crate) fn is_numeric_type_compatible(
+    shader_type: ShaderScalarType,
+    numeric_type: Option<NumericType>,
+) -> bool {
+    matches!(
+        (shader_type, numeric_type),
+        (
+            ShaderScalarType::Float,
+            Some(
+                NumericType::SFLOAT
+                    | NumericType::UFLOAT
+                    | NumericType::SNORM
+                    | NumericType::UNORM
+                    | NumericType::SSCALED
+                    | NumericType::USCALED
+                    | NumericType::SRGB
+            ),
+        ) | (ShaderScalarType::Sint, Some(NumericType::SINT))
+            | (ShaderScalarType::Uint, Some(NumericType::UINT))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PipelineInterfaceProblem, PipelineInterfaceReport};
+    use crate::{
+        format::Format,
+        pipeline::graphics::vertex_input::{VertexInputAttributeDescription, VertexInputState},
+        shader::{
+            ShaderInterface, ShaderInterfaceEntry, ShaderInterfaceEntryType,
+            ShaderInterfaceMismatchError, ShaderScalarType, ShaderStage,
+        },
+    };
+
+    fn entry(
+        location: u32,
+        base_type: ShaderScalarType,
+        num_components: u32,
+    ) -> ShaderInterfaceEntry {
+        ShaderInterfaceEntry {
+            location,
+            component: 0,
+            name: None,
+            ty: ShaderInterfaceEntryType {
+                base_type,
+                num_components,
+                num_elements: 1,
+                is_64bit: false,
+            },
+        }
+    }
+
+    #[test]
+    fn vertex_input_collects_all() {
+        let interface = unsafe {
+            ShaderInterface::new_unchecked(vec![
+                entry(0, ShaderScalarType::Float, 3),
+                entry(1, ShaderScalarType::Uint, 1),
+                entry(2, ShaderScalarType::Float, 2),
+            ])
+        };
+        let vertex_input_state = VertexInputState::new()
+            .attribute(
+                0,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32G32B32_SFLOAT,
+                    offset: 0,
+                },
+            )
+            .attribute(
+                1,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32_SFLOAT,
+                    offset: 12,
+                },
+            );
+
+        let mut report = PipelineInterfaceReport::default();
+        report.check_vertex_input(&interface, &vertex_input_state);
+
+        assert_eq!(
+            report.problems(),
+            &[
+                PipelineInterfaceProblem::VertexInputAttributeIncompatibleFormat {
+                    location: 1,
+                    shader_type: ShaderScalarType::Uint,
+                    attribute_format: Format::R32_SFLOAT,
+                },
+                PipelineInterfaceProblem::VertexInputAttributeMissing { location: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn stages_collect_all() {
+        let output = unsafe {
+            ShaderInterface::new_unchecked(vec![
+                entry(0, ShaderScalarType::Float, 4),
+                entry(1, ShaderScalarType::Float, 2),
+            ])
+        };
+        let input = unsafe {
+            ShaderInterface::new_unchecked(vec![
+                entry(0, ShaderScalarType::Float, 3),
+                entry(1, ShaderScalarType::Float, 2),
+                entry(2, ShaderScalarType::Sint, 1),
+            ])
+        };
+
+        let mut report = PipelineInterfaceReport::default();
+        report.check_stages(ShaderStage::Vertex, &output, ShaderStage::Fragment, &input);
+
+        let errors: Vec<_> = report
+            .into_problems()
+            .into_iter()
+            .map(|problem| match problem {
+                PipelineInterfaceProblem::ShaderStagesMismatch {
+                    output_stage: ShaderStage::Vertex,
+                    input_stage: ShaderStage::Fragment,
+                    error,
+                } => error,
+                _ => panic!(),
+            })
+            .collect();
+
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            errors[0],
+            ShaderInterfaceMismatchError::ElementsCountMismatch {
+                self_elements: 3,
+                other_elements: 2,
+            }
+        ));
+        assert!(matches!(
+            errors[1],
+            ShaderInterfaceMismatchError::TypeMismatch { location: 0, .. }
+        ));
+        assert!(matches!(
+            errors[2],
+            ShaderInterfaceMismatchError::MissingElement { location: 2 }
+        ));
+    }
+
+    #[test]
+    fn stages_report_elements_once() {
+        let matrix = |location, base_type| {
+            let mut entry = entry(location, base_type, 4);
+            entry.ty.num_elements = 4;
+            entry
+        };
+        let output = unsafe {
+            ShaderInterface::new_unchecked(vec![
+                matrix(0, ShaderScalarType::Float),
+                entry(4, ShaderScalarType::Float, 4),
+            ])
+        };
+        let input = unsafe {
+            ShaderInterface::new_unchecked(vec![
+                matrix(0, ShaderScalarType::Sint),
+                matrix(4, ShaderScalarType::Float),
+            ])
+        };
+
+        let mut report = PipelineInterfaceReport::default();
+        report.check_stages(ShaderStage::Vertex, &output, ShaderStage::Fragment, &input);
+
+        let errors: Vec<_> = report
+            .into_problems()
+            .into_iter()
+            .map(|problem| match problem {
+                PipelineInterfaceProblem::ShaderStagesMismatch { error, .. } => error,
+                _ => panic!(),
+            })
+            .collect();
+
+        // The matrix at location 0 has a different type over all of its locations, and the one
+        // at location 4 overlaps a vector of a different type and is otherwise missing. Each
+        // problem is reported once.
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            errors[0],
+            ShaderInterfaceMismatchError::TypeMismatch { location: 0, .. }
+        ));
+        assert!(matches!(
+            errors[1],
+            ShaderInterfaceMismatchError::TypeMismatch { location: 4, .. }
+        ));
+        assert!(matches!(
+            errors[2],
+            ShaderInterfaceMismatchError::MissingElement { location: 5 }
+        ));
+    }
+
+    #[test]
+    fn fragment_output_collects_all() {
+        let interface = unsafe {
+            ShaderInterface::new_unchecked(vec![
+                entry(0, ShaderScalarType::Float, 4),
+                entry(1, ShaderScalarType::Sint, 4),
+                entry(2, ShaderScalarType::Float, 4),
+                entry(3, ShaderScalarType::Uint, 1),
+            ])
+        };
+        let color_attachment_formats = [
+            Some(Format::R8G8B8A8_SRGB),
+            Some(Format::R8G8B8A8_UNORM),
+            None,
+        ];
+
+        let mut report = PipelineInterfaceReport::default();
+        report.check_fragment_output(&interface, &color_attachment_formats);
+
+        assert_eq!(
+            report.problems(),
+            &[PipelineInterfaceProblem::FragmentOutputIncompatibleFormat {
+                location: 1,
+                shader_type: ShaderScalarType::Sint,
+                attachment_format: Format::R8G8B8A8_UNORM,
+            },]
+        );
+        assert!(report.into_result().is_err());
+    }
+}
@@ -56,19 +56,28 @@
 //! constants, and setting any dynamic state that the pipeline may need. Then you issue a `draw`
 //! command.
 
-pub use self::{builder::GraphicsPipelineBuilder, creation_error::GraphicsPipelineCreationError};
+pub(crate) use self::interface_report::is_numeric_type_compatible;
+pub use self::{
+    builder::GraphicsPipelineBuilder,
+    creation_error::GraphicsPipelineCreationError,
+    interface_report::{PipelineInterfaceProblem, PipelineInterfaceReport},
+};
 use self::{
     color_blend::ColorBlendState, depth_stencil::DepthStencilState,
-    discard_rectangle::DiscardRectangleState, input_assembly::InputAssemblyState,
-    multisample::MultisampleState, rasterization::RasterizationState,
-    render_pass::PipelineRenderPassType, tessellation::TessellationState,
+    discard_rectangle::DiscardRectangleState, fragment_shading_rate::FragmentShadingRateState,
+    input_assembly::InputAssemblyState, multisample::MultisampleState,
+    rasterization::RasterizationState, render_pass::PipelineRenderPassType,
+    tessellation::TessellationState, transform_feedback::TransformFeedbackState,
     vertex_input::VertexInputState, viewport::ViewportState,
 };
 use super::{DynamicState, Pipeline, PipelineBindPoint, PipelineLayout};
 use crate::{
     device::{Device, DeviceOwned},
     macros::impl_id_counter,
-    shader::{DescriptorBindingRequirements, FragmentTestsStages, ShaderStage},
+    shader::{
+        DescriptorBindingRequirements, FragmentTestsStages, ShaderInterface, ShaderStage,
+        TransformFeedbackOutput,
+    },
     VulkanObject,
 };
 use ahash::HashMap;
@@ -84,11 +93,14 @@ pub mod color_blend;
 mod creation_error;
 pub mod depth_stencil;
 pub mod discard_rectangle;
+pub mod fragment_shading_rate;
 pub mod input_assembly;
+mod interface_report;
 pub mod multisample;
 pub mod rasterization;
 pub mod render_pass;
 pub mod tessellation;
+pub mod transform_feedback;
 pub mod vertex_input;
 pub mod viewport;
 // FIXME: restore
@@ -110,13 +122,17 @@ pub struct GraphicsPipeline {
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
     fragment_tests_stages: Option<FragmentTestsStages>,
+    transform_feedback_outputs: Option<Vec<TransformFeedbackOutput>>,
+    vertex_input_interface: ShaderInterface,
 
     vertex_input_state: VertexInputState,
     input_assembly_state: InputAssemblyState,
     tessellation_state: Option<TessellationState>,
     viewport_state: Option<ViewportState>,
     discard_rectangle_state: Option<DiscardRectangleState>,
+    transform_feedback_state: Option<TransformFeedbackState>,
     rasterization_state: RasterizationState,
+    fragment_shading_rate_state: Option<FragmentShadingRateState>,
     multisample_state: Option<MultisampleState>,
     depth_stencil_state: Option<DepthStencilState>,
     color_blend_state: Option<ColorBlendState>,
@@ -168,6 +184,9 @@ impl GraphicsPipeline {
     }
 
     /// Returns the vertex input state used to create this pipeline.
+    ///
+    /// If the vertex input state is dynamic, this is empty. The state must then be set with
+    /// `set_vertex_input` on the command buffer before drawing.
     #[inline]
     pub fn vertex_input_state(&self) -> &VertexInputState {
         &self.vertex_input_state
@@ -197,12 +216,24 @@ impl GraphicsPipeline {
         self.discard_rectangle_state.as_ref()
     }
 
+    /// Returns the transform feedback state used to create this pipeline.
+    #[inline]
+    pub fn transform_feedback_state(&self) -> Option<&TransformFeedbackState> {
+        self.transform_feedback_state.as_ref()
+    }
+
     /// Returns the rasterization state used to create this pipeline.
     #[inline]
     pub fn rasterization_state(&self) -> &RasterizationState {
         &self.rasterization_state
     }
 
+    /// Returns the fragment shading rate state used to create this pipeline.
+    #[inline]
+    pub fn fragment_shading_rate_state(&self) -> Option<&FragmentShadingRateState> {
+        self.fragment_shading_rate_state.as_ref()
+    }
+
     /// Returns the multisample state used to create this pipeline.
     #[inline]
     pub fn multisample_state(&self) -> Option<&MultisampleState> {
@@ -241,6 +272,24 @@ impl GraphicsPipeline {
     pub fn fragment_tests_stages(&self) -> Option<FragmentTestsStages> {
         self.fragment_tests_stages
     }
+
+    /// If the last pre-rasterization shader of the pipeline declares the `Xfb` execution mode,
+    /// returns the outputs that it captures with transform feedback.
+    #[inline]
+    pub fn transform_feedback_outputs(&self) -> Option<&[TransformFeedbackOutput]> {
+        self.transform_feedback_outputs.as_deref()
+    }
+
+    /// Returns the input interface of the vertex shader.
+    ///
+    /// This can be passed to [`VertexDefinition::definition`] to create the vertex input state
+    /// for a pipeline with dynamic vertex input.
+    ///
+    /// [`VertexDefinition::definition`]: vertex_input::VertexDefinition::definition
+    #[inline]
+    pub fn vertex_input_interface(&self) -> &ShaderInterface {
+        &self.vertex_input_interface
+    }
 }
 
 impl Pipeline for GraphicsPipeline {
@@ -10,7 +10,7 @@
 //! Generates multiple fragments per framebuffer pixel when rasterizing. This can be used for
 //! anti-aliasing.
 
-use crate::image::SampleCount;
+use crate::{image::SampleCount, pipeline::StateMode};
 
 // TODO: handle some weird behaviors with non-floating-point targets
 
@@ -22,7 +22,11 @@ pub struct MultisampleState {
     /// The depth and stencil test will then be run for each sample.
     ///
     /// The default value is [`SampleCount::Sample1`].
-    pub rasterization_samples: SampleCount,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_rasterization_samples`](crate::device::Features::extended_dynamic_state3_rasterization_samples)
+    /// feature must be enabled on the device.
+    pub rasterization_samples: StateMode<SampleCount>,
 
     /// Controls the proportion (between 0.0 and 1.0) of the samples that will be run through the
     /// fragment shader.
@@ -40,21 +44,33 @@ pub struct MultisampleState {
     /// samples. Only the first `rasterization_samples / 32` bits are used, the rest is ignored.
     ///
     /// The default value is `[0xFFFFFFFF; 2]`.
-    pub sample_mask: [u32; 2], // 64 bits for needed for 64 SampleCount
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_sample_mask`](crate::device::Features::extended_dynamic_state3_sample_mask)
+    /// feature must be enabled on the device.
+    pub sample_mask: StateMode<[u32; 2]>, // 64 bits for needed for 64 SampleCount
 
     /// Controls whether the alpha value of the fragment will be used in an implementation-defined
     /// way to determine which samples get disabled or not. For example if the alpha value is 0.5,
     /// then about half of the samples will be discarded. If you render to a multisample image, this
     /// means that the color will end up being mixed with whatever color was underneath, which gives
     /// the same effect as alpha blending.
-    pub alpha_to_coverage_enable: bool,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_alpha_to_coverage_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_coverage_enable)
+    /// feature must be enabled on the device.
+    pub alpha_to_coverage_enable: StateMode<bool>,
 
     /// Controls whether the alpha value of all the samples will be forced to 1.0 (or the
     /// maximum possible value) after the effects of `alpha_to_coverage` have been applied.
     ///
     /// If set to `true`, the [`alpha_to_one`](crate::device::Features::alpha_to_one)
     /// feature must be enabled on the device.
-    pub alpha_to_one_enable: bool,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_alpha_to_one_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_one_enable)
+    /// feature must be enabled on the device.
+    pub alpha_to_one_enable: StateMode<bool>,
 }
 
 impl MultisampleState {
@@ -62,13 +78,48 @@ impl MultisampleState {
     #[inline]
     pub fn new() -> MultisampleState {
         MultisampleState {
-            rasterization_samples: SampleCount::Sample1,
+            rasterization_samples: StateMode::Fixed(SampleCount::Sample1),
             sample_shading: None,
-            sample_mask: [0xFFFFFFFF; 2],
-            alpha_to_coverage_enable: false,
-            alpha_to_one_enable: false,
+            sample_mask: StateMode::Fixed([0xFFFFFFFF; 2]),
+            alpha_to_coverage_enable: StateMode::Fixed(false),
+            alpha_to_one_enable: StateMode::Fixed(false),
         }
     }
+
+    /// Sets the number of rasterization samples.
+    #[inline]
+    pub fn rasterization_samples(mut self, rasterization_samples: SampleCount) -> Self {
+        self.rasterization_samples = StateMode::Fixed(rasterization_samples);
+        self
+    }
+
+    /// Sets the number of rasterization samples to dynamic.
+    #[inline]
+    pub fn rasterization_samples_dynamic(mut self) -> Self {
+        self.rasterization_samples = StateMode::Dynamic;
+        self
+    }
+
+    /// Sets the sample mask to dynamic.
+    #[inline]
+    pub fn sample_mask_dynamic(mut self) -> Self {
+        self.sample_mask = StateMode::Dynamic;
+        self
+    }
+
+    /// Sets whether alpha-to-coverage is enabled to dynamic.
+    #[inline]
+    pub fn alpha_to_coverage_enable_dynamic(mut self) -> Self {
+        self.alpha_to_coverage_enable = StateMode::Dynamic;
+        self
+    }
+
+    /// Sets whether alpha-to-one is enabled to dynamic.
+    #[inline]
+    pub fn alpha_to_one_enable_dynamic(mut self) -> Self {
+        self.alpha_to_one_enable = StateMode::Dynamic;
+        self
+    }
 }
 
 impl Default for MultisampleState {
@@ -19,7 +19,11 @@ pub struct RasterizationState {
     ///
     /// If enabled, the [`depth_clamp`](crate::device::Features::depth_clamp) feature must be
     /// enabled on the device.
-    pub depth_clamp_enable: bool,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_depth_clamp_enable`](crate::device::Features::extended_dynamic_state3_depth_clamp_enable)
+    /// feature must be enabled on the device.
+    pub depth_clamp_enable: StateMode<bool>,
 
     /// If true, all the fragments will be discarded, and the fragment shader will not be run. This
     /// is usually used when your vertex shader has some side effects and you don't need to run the
@@ -36,7 +40,11 @@ pub struct RasterizationState {
     /// If set to a value other than `Fill`, the
     /// [`fill_mode_non_solid`](crate::device::Features::fill_mode_non_solid) feature must be
     /// enabled on the device.
-    pub polygon_mode: PolygonMode,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_polygon_mode`](crate::device::Features::extended_dynamic_state3_polygon_mode)
+    /// feature must be enabled on the device.
+    pub polygon_mode: StateMode<PolygonMode>,
 
     /// Specifies whether front faces or back faces should be discarded, or none, or both.
     ///
@@ -70,7 +78,11 @@ pub struct RasterizationState {
     /// If this is not set to `Default`, the
     /// [`ext_line_rasterization`](crate::device::DeviceExtensions::ext_line_rasterization)
     /// extension and an additional feature must be enabled on the device.
-    pub line_rasterization_mode: LineRasterizationMode,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_line_rasterization_mode`](crate::device::Features::extended_dynamic_state3_line_rasterization_mode)
+    /// feature must be enabled on the device.
+    pub line_rasterization_mode: StateMode<LineRasterizationMode>,
 
     /// Enables and sets the parameters for line stippling.
     ///
@@ -78,6 +90,15 @@ pub struct RasterizationState {
     /// [`ext_line_rasterization`](crate::device::DeviceExtensions::ext_line_rasterization)
     /// extension and an additional feature must be enabled on the device.
     pub line_stipple: Option<StateMode<LineStipple>>,
+
+    /// Sets whether line stippling should be enabled and disabled dynamically. If set to `false`,
+    /// line stippling is enabled if `line_stipple` is `Some`. If set to `true`, `line_stipple`
+    /// must be `Some`.
+    ///
+    /// If set to `true`, the
+    /// [`extended_dynamic_state3_line_stipple_enable`](crate::device::Features::extended_dynamic_state3_line_stipple_enable)
+    /// feature must be enabled on the device.
+    pub line_stipple_enable_dynamic: bool,
 }
 
 impl RasterizationState {
@@ -87,22 +108,44 @@ impl RasterizationState {
     #[inline]
     pub fn new() -> Self {
         Self {
-            depth_clamp_enable: false,
+            depth_clamp_enable: StateMode::Fixed(false),
             rasterizer_discard_enable: StateMode::Fixed(false),
-            polygon_mode: Default::default(),
+            polygon_mode: StateMode::Fixed(Default::default()),
             cull_mode: StateMode::Fixed(Default::default()),
             front_face: StateMode::Fixed(Default::default()),
             depth_bias: None,
             line_width: StateMode::Fixed(1.0),
-            line_rasterization_mode: Default::default(),
+            line_rasterization_mode: StateMode::Fixed(Default::default()),
             line_stipple: None,
+            line_stipple_enable_dynamic: false,
         }
     }
 
+    /// Sets whether depth clamping is enabled.
+    #[inline]
+    pub fn depth_clamp_enable(mut self, enable: bool) -> Self {
+        self.depth_clamp_enable = StateMode::Fixed(enable);
+        self
+    }
+
+    /// Sets whether depth clamping is enabled to dynamic.
+    #[inline]
+    pub fn depth_clamp_enable_dynamic(mut self) -> Self {
+        self.depth_clamp_enable = StateMode::Dynamic;
+        self
+    }
+
     /// Sets the polygon mode.
     #[inline]
     pub fn polygon_mode(mut self, polygon_mode: PolygonMode) -> Self {
-        self.polygon_mode = polygon_mode;
+        self.polygon_mode = StateMode::Fixed(polygon_mode);
+        self
+    }
+
+    /// Sets the polygon mode to dynamic.
+    #[inline]
+    pub fn polygon_mode_dynamic(mut self) -> Self {
+        self.polygon_mode = StateMode::Dynamic;
         self
     }
 
@@ -133,6 +176,28 @@ impl RasterizationState {
         self.front_face = StateMode::Dynamic;
         self
     }
+
+    /// Sets the line rasterization mode.
+    #[inline]
+    pub fn line_rasterization_mode(mut self, mode: LineRasterizationMode) -> Self {
+        self.line_rasterization_mode = StateMode::Fixed(mode);
+        self
+    }
+
+    /// Sets the line rasterization mode to dynamic.
+    #[inline]
+    pub fn line_rasterization_mode_dynamic(mut self) -> Self {
+        self.line_rasterization_mode = StateMode::Dynamic;
+        self
+    }
+
+    /// Sets whether line stippling is enabled to dynamic. The line stipple parameters must also
+    /// be provided in `line_stipple`.
+    #[inline]
+    pub fn line_stipple_enable_dynamic(mut self) -> Self {
+        self.line_stipple_enable_dynamic = true;
+        self
+    }
 }
 
 impl Default for RasterizationState {
@@ -0,0 +1,72 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Capturing the vertices that are output by the pre-rasterization shaders into buffers.
+//!
+//! Which outputs are captured, and where they are written, is determined by the `XfbBuffer`,
+//! `XfbStride` and `Offset` decorations in the last pre-rasterization shader. These are available
+//! through [`EntryPoint::transform_feedback_outputs`]. Capturing only takes place between
+//! calls to `begin_transform_feedback` and `end_transform_feedback` on a command buffer, into the
+//! buffers that were bound with `bind_transform_feedback_buffers`.
+//!
+//! [`EntryPoint::transform_feedback_outputs`]: crate::shader::EntryPoint::transform_feedback_outputs
+
+use crate::pipeline::StateMode;
+
+/// The state in a graphics pipeline describing how transform feedback should behave.
+#[derive(Clone, Debug)]
+pub struct TransformFeedbackState {
+    /// The vertex stream that is passed on to rasterization. Vertices in other streams are only
+    /// captured, not rasterized.
+    ///
+    /// If set to a value other than 0, the
+    /// [`geometry_streams`](crate::device::Features::geometry_streams) feature must be enabled on
+    /// the device, and the
+    /// [`transform_feedback_rasterization_stream_select`](crate::device::Properties::transform_feedback_rasterization_stream_select)
+    /// device property must be `true`.
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_rasterization_stream`](crate::device::Features::extended_dynamic_state3_rasterization_stream)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is 0.
+    pub rasterization_stream: StateMode<u32>,
+}
+
+impl TransformFeedbackState {
+    /// Creates a `TransformFeedbackState` that rasterizes vertex stream 0.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            rasterization_stream: StateMode::Fixed(0),
+        }
+    }
+
+    /// Sets the vertex stream that is passed on to rasterization.
+    #[inline]
+    pub fn rasterization_stream(mut self, rasterization_stream: u32) -> Self {
+        self.rasterization_stream = StateMode::Fixed(rasterization_stream);
+        self
+    }
+
+    /// Sets the vertex stream that is passed on to rasterization to dynamic.
+    #[inline]
+    pub fn rasterization_stream_dynamic(mut self) -> Self {
+        self.rasterization_stream = StateMode::Dynamic;
+        self
+    }
+}
+
+impl Default for TransformFeedbackState {
+    /// Returns [`TransformFeedbackState::new`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
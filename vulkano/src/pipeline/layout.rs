@@ -64,7 +64,10 @@
 //! type. Each pipeline that you create holds a pipeline layout object.
 
 use crate::{
-    descriptor_set::layout::{DescriptorRequirementsNotMet, DescriptorSetLayout, DescriptorType},
+    descriptor_set::layout::{
+        DescriptorRequirementsNotMet, DescriptorSetLayout, DescriptorSetLayoutBinding,
+        DescriptorType,
+    },
     device::{Device, DeviceOwned},
     macros::impl_id_counter,
     shader::{DescriptorBindingRequirements, ShaderStages},
@@ -204,15 +207,8 @@ impl PipelineLayout {
         }
 
         {
-            let mut num_resources = Counter::default();
-            let mut num_samplers = Counter::default();
-            let mut num_uniform_buffers = Counter::default();
-            let mut num_uniform_buffers_dynamic = 0;
-            let mut num_storage_buffers = Counter::default();
-            let mut num_storage_buffers_dynamic = 0;
-            let mut num_sampled_images = Counter::default();
-            let mut num_storage_images = Counter::default();
-            let mut num_input_attachments = Counter::default();
+            let mut counters = DescriptorCounters::default();
+            let mut update_after_bind_counters = None;
             let mut push_descriptor_set = None;
 
             for (set_num, set_layout) in set_layouts.iter().enumerate() {
@@ -227,54 +223,41 @@ impl PipelineLayout {
                     }
                 }
 
+                if set_layout.update_after_bind_pool() {
+                    update_after_bind_counters.get_or_insert_with(|| counters.clone());
+                }
+
+                // VUID-VkPipelineLayoutCreateInfo-pSetLayouts-08008
+                if set_layout.descriptor_buffer() != set_layouts[0].descriptor_buffer() {
+                    return Err(PipelineLayoutCreationError::SetLayoutsDescriptorBufferMixed);
+                }
+
                 for layout_binding in set_layout.bindings().values() {
-                    num_resources.increment(layout_binding.descriptor_count, layout_binding.stages);
-
-                    match layout_binding.descriptor_type {
-                        DescriptorType::Sampler => {
-                            num_samplers
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
-                        DescriptorType::CombinedImageSampler => {
-                            num_samplers
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                            num_sampled_images
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
-                        DescriptorType::SampledImage | DescriptorType::UniformTexelBuffer => {
-                            num_sampled_images
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
-                        DescriptorType::StorageImage | DescriptorType::StorageTexelBuffer => {
-                            num_storage_images
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
-                        DescriptorType::UniformBuffer => {
-                            num_uniform_buffers
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
-                        DescriptorType::UniformBufferDynamic => {
-                            num_uniform_buffers
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                            num_uniform_buffers_dynamic += 1;
-                        }
-                        DescriptorType::StorageBuffer => {
-                            num_storage_buffers
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
-                        DescriptorType::StorageBufferDynamic => {
-                            num_storage_buffers
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                            num_storage_buffers_dynamic += 1;
-                        }
-                        DescriptorType::InputAttachment => {
-                            num_input_attachments
-                                .increment(layout_binding.descriptor_count, layout_binding.stages);
-                        }
+                    // The regular limits only count sets that are not `update_after_bind_pool`,
+                    // while the update-after-bind limits count all sets.
+                    if !set_layout.update_after_bind_pool() {
+                        counters.increment(layout_binding);
+                    }
+
+                    if let Some(update_after_bind_counters) = update_after_bind_counters.as_mut() {
+                        update_after_bind_counters.increment(layout_binding);
                     }
                 }
             }
 
+            let DescriptorCounters {
+                resources: num_resources,
+                samplers: num_samplers,
+                uniform_buffers: num_uniform_buffers,
+                uniform_buffers_dynamic: num_uniform_buffers_dynamic,
+                storage_buffers: num_storage_buffers,
+                storage_buffers_dynamic: num_storage_buffers_dynamic,
+                sampled_images: num_sampled_images,
+                storage_images: num_storage_images,
+                input_attachments: num_input_attachments,
+                inline_uniform_blocks: num_inline_uniform_blocks,
+            } = counters;
+
             if num_resources.max_per_stage() > properties.max_per_stage_resources {
                 return Err(PipelineLayoutCreationError::MaxPerStageResourcesExceeded {
                     provided: num_resources.max_per_stage(),
@@ -352,6 +335,22 @@ impl PipelineLayout {
                 );
             }
 
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-02214
+            if num_inline_uniform_blocks.max_per_stage()
+                > properties
+                    .max_per_stage_descriptor_inline_uniform_blocks
+                    .unwrap_or(0)
+            {
+                return Err(
+                    PipelineLayoutCreationError::MaxPerStageDescriptorInlineUniformBlocksExceeded {
+                        provided: num_inline_uniform_blocks.max_per_stage(),
+                        max_supported: properties
+                            .max_per_stage_descriptor_inline_uniform_blocks
+                            .unwrap_or(0),
+                    },
+                );
+            }
+
             // VUID-VkPipelineLayoutCreateInfo-descriptorType-03028
             if num_samplers.total > properties.max_descriptor_set_samplers {
                 return Err(
@@ -431,6 +430,143 @@ impl PipelineLayout {
                     },
                 );
             }
+
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-02216
+            if num_inline_uniform_blocks.total
+                > properties
+                    .max_descriptor_set_inline_uniform_blocks
+                    .unwrap_or(0)
+            {
+                return Err(
+                    PipelineLayoutCreationError::MaxDescriptorSetInlineUniformBlocksExceeded {
+                        provided: num_inline_uniform_blocks.total,
+                        max_supported: properties
+                            .max_descriptor_set_inline_uniform_blocks
+                            .unwrap_or(0),
+                    },
+                );
+            }
+
+            if let Some(counters) = update_after_bind_counters {
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03022
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03023
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03024
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03025
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03026
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03027
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03028
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03029
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03030
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03031
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03032
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03033
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03034
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03035
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-03036
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-02215
+                // VUID-VkPipelineLayoutCreateInfo-descriptorType-02217
+                let limits = [
+                    (
+                        "max_per_stage_update_after_bind_resources",
+                        counters.resources.max_per_stage(),
+                        properties.max_per_stage_update_after_bind_resources,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_samplers",
+                        counters.samplers.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_samplers,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_uniform_buffers",
+                        counters.uniform_buffers.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_uniform_buffers,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_storage_buffers",
+                        counters.storage_buffers.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_storage_buffers,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_sampled_images",
+                        counters.sampled_images.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_sampled_images,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_storage_images",
+                        counters.storage_images.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_storage_images,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_input_attachments",
+                        counters.input_attachments.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_input_attachments,
+                    ),
+                    (
+                        "max_per_stage_descriptor_update_after_bind_inline_uniform_blocks",
+                        counters.inline_uniform_blocks.max_per_stage(),
+                        properties.max_per_stage_descriptor_update_after_bind_inline_uniform_blocks,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_samplers",
+                        counters.samplers.total,
+                        properties.max_descriptor_set_update_after_bind_samplers,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_uniform_buffers",
+                        counters.uniform_buffers.total,
+                        properties.max_descriptor_set_update_after_bind_uniform_buffers,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_uniform_buffers_dynamic",
+                        counters.uniform_buffers_dynamic,
+                        properties.max_descriptor_set_update_after_bind_uniform_buffers_dynamic,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_storage_buffers",
+                        counters.storage_buffers.total,
+                        properties.max_descriptor_set_update_after_bind_storage_buffers,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_storage_buffers_dynamic",
+                        counters.storage_buffers_dynamic,
+                        properties.max_descriptor_set_update_after_bind_storage_buffers_dynamic,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_sampled_images",
+                        counters.sampled_images.total,
+                        properties.max_descriptor_set_update_after_bind_sampled_images,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_storage_images",
+                        counters.storage_images.total,
+                        properties.max_descriptor_set_update_after_bind_storage_images,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_input_attachments",
+                        counters.input_attachments.total,
+                        properties.max_descriptor_set_update_after_bind_input_attachments,
+                    ),
+                    (
+                        "max_descriptor_set_update_after_bind_inline_uniform_blocks",
+                        counters.inline_uniform_blocks.total,
+                        properties.max_descriptor_set_update_after_bind_inline_uniform_blocks,
+                    ),
+                ];
+
+                for (limit, provided, max_supported) in limits {
+                    let max_supported = max_supported.unwrap_or(0);
+
+                    if provided > max_supported {
+                        return Err(
+                            PipelineLayoutCreationError::MaxUpdateAfterBindDescriptorsExceeded {
+                                limit,
+                                provided,
+                                max_supported,
+                            },
+                        );
+                    }
+                }
+            }
         }
 
         /* Check push constant ranges */
@@ -563,6 +699,15 @@ impl PipelineLayout {
         &self.set_layouts
     }
 
+    /// Returns whether the descriptor set layouts of this pipeline layout are for descriptor
+    /// buffers. Pipelines created with this layout can only be used with descriptor buffers.
+    #[inline]
+    pub fn descriptor_buffer(&self) -> bool {
+        self.set_layouts
+            .first()
+            .map_or(false, |set_layout| set_layout.descriptor_buffer())
+    }
+
     /// Returns a slice containing the push constant ranges this pipeline layout was created from.
     ///
     /// The ranges are guaranteed to be sorted deterministically by offset, size, then stages.
@@ -756,11 +901,25 @@ pub enum PipelineLayoutCreationError {
     /// limit.
     MaxDescriptorSetInputAttachmentsExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` contain more [`DescriptorType::InlineUniformBlock`] bindings than the
+    /// [`max_descriptor_set_inline_uniform_blocks`](crate::device::Properties::max_descriptor_set_inline_uniform_blocks)
+    /// limit.
+    MaxDescriptorSetInlineUniformBlocksExceeded { provided: u32, max_supported: u32 },
+
     /// The `set_layouts` contain more bound resources in a single stage than the
     /// [`max_per_stage_resources`](crate::device::Properties::max_per_stage_resources)
     /// limit.
     MaxPerStageResourcesExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` include a layout with `update_after_bind_pool` enabled, and contain more
+    /// descriptors of some kind than the corresponding update-after-bind limit, such as
+    /// [`max_per_stage_update_after_bind_resources`](crate::device::Properties::max_per_stage_update_after_bind_resources).
+    MaxUpdateAfterBindDescriptorsExceeded {
+        limit: &'static str,
+        provided: u32,
+        max_supported: u32,
+    },
+
     /// The `set_layouts` contain more [`DescriptorType::Sampler`] and
     /// [`DescriptorType::CombinedImageSampler`] descriptors in a single stage than the
     /// [`max_per_stage_descriptor_samplers`](crate::device::Properties::max_per_stage_descriptor_samplers)
@@ -798,6 +957,12 @@ pub enum PipelineLayoutCreationError {
     /// limit.
     MaxPerStageDescriptorInputAttachmentsExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` contain more [`DescriptorType::InlineUniformBlock`] bindings in a single
+    /// stage than the
+    /// [`max_per_stage_descriptor_inline_uniform_blocks`](crate::device::Properties::max_per_stage_descriptor_inline_uniform_blocks)
+    /// limit.
+    MaxPerStageDescriptorInlineUniformBlocksExceeded { provided: u32, max_supported: u32 },
+
     /// An element in `push_constant_ranges` has an `offset + size` greater than the
     /// [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit.
     MaxPushConstantsSizeExceeded { provided: u32, max_supported: u32 },
@@ -805,6 +970,9 @@ pub enum PipelineLayoutCreationError {
     /// A shader stage appears in multiple elements of `push_constant_ranges`.
     PushConstantRangesStageMultiple,
 
+    /// Some elements of `set_layouts` have `descriptor_buffer` enabled, but not all of them.
+    SetLayoutsDescriptorBufferMixed,
+
     /// Multiple elements of `set_layouts` have `push_descriptor` enabled.
     SetLayoutsPushDescriptorMultiple,
 }
@@ -914,6 +1082,15 @@ impl Display for PipelineLayoutCreationError {
                 than the `max_descriptor_set_input_attachments` limit ({})",
                 provided, max_supported,
             ),
+            Self::MaxDescriptorSetInlineUniformBlocksExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::InlineUniformBlock` bindings \
+                ({}) than the `max_descriptor_set_inline_uniform_blocks` limit ({})",
+                provided, max_supported,
+            ),
             Self::MaxPerStageResourcesExceeded {
                 provided,
                 max_supported,
@@ -923,6 +1100,15 @@ impl Display for PipelineLayoutCreationError {
                 `max_per_stage_resources` limit ({})",
                 provided, max_supported,
             ),
+            Self::MaxUpdateAfterBindDescriptorsExceeded {
+                limit,
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more descriptors ({}) than the `{}` limit ({})",
+                provided, limit, max_supported,
+            ),
             Self::MaxPerStageDescriptorSamplersExceeded {
                 provided,
                 max_supported,
@@ -984,6 +1170,16 @@ impl Display for PipelineLayoutCreationError {
                 ({})",
                 provided, max_supported,
             ),
+            Self::MaxPerStageDescriptorInlineUniformBlocksExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::InlineUniformBlock` bindings \
+                ({}) in a single stage than the `max_per_stage_descriptor_inline_uniform_blocks` \
+                limit ({})",
+                provided, max_supported,
+            ),
             Self::MaxPushConstantsSizeExceeded {
                 provided,
                 max_supported,
@@ -997,6 +1193,11 @@ impl Display for PipelineLayoutCreationError {
                 f,
                 "a shader stage appears in multiple elements of `push_constant_ranges`",
             ),
+            Self::SetLayoutsDescriptorBufferMixed => write!(
+                f,
+                "some elements of `set_layouts` have `descriptor_buffer` enabled, but not all of \
+                them",
+            ),
             Self::SetLayoutsPushDescriptorMultiple => write!(
                 f,
                 "multiple elements of `set_layouts` have `push_descriptor` enabled",
@@ -1173,7 +1374,7 @@ impl Default for PushConstantRange {
 }
 
 // Helper struct for the main function.
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Counter {
     total: u32,
     compute: u32,
@@ -1222,6 +1423,76 @@ impl Counter {
     }
 }
 
+// Helper struct for the main function.
+#[derive(Clone, Default)]
+struct DescriptorCounters {
+    resources: Counter,
+    samplers: Counter,
+    uniform_buffers: Counter,
+    uniform_buffers_dynamic: u32,
+    storage_buffers: Counter,
+    storage_buffers_dynamic: u32,
+    sampled_images: Counter,
+    storage_images: Counter,
+    input_attachments: Counter,
+    inline_uniform_blocks: Counter,
+}
+
+impl DescriptorCounters {
+    fn increment(&mut self, layout_binding: &DescriptorSetLayoutBinding) {
+        let &DescriptorSetLayoutBinding {
+            descriptor_type,
+            descriptor_count,
+            stages,
+            ..
+        } = layout_binding;
+
+        // Inline uniform blocks don't count as resources.
+        if descriptor_type != DescriptorType::InlineUniformBlock {
+            self.resources.increment(descriptor_count, stages);
+        }
+
+        match descriptor_type {
+            DescriptorType::Sampler => {
+                self.samplers.increment(descriptor_count, stages);
+            }
+            DescriptorType::CombinedImageSampler => {
+                self.samplers.increment(descriptor_count, stages);
+                self.sampled_images.increment(descriptor_count, stages);
+            }
+            DescriptorType::SampledImage | DescriptorType::UniformTexelBuffer => {
+                self.sampled_images.increment(descriptor_count, stages);
+            }
+            DescriptorType::StorageImage | DescriptorType::StorageTexelBuffer => {
+                self.storage_images.increment(descriptor_count, stages);
+            }
+            DescriptorType::UniformBuffer => {
+                self.uniform_buffers.increment(descriptor_count, stages);
+            }
+            DescriptorType::UniformBufferDynamic => {
+                self.uniform_buffers.increment(descriptor_count, stages);
+                self.uniform_buffers_dynamic += 1;
+            }
+            DescriptorType::StorageBuffer => {
+                self.storage_buffers.increment(descriptor_count, stages);
+            }
+            DescriptorType::StorageBufferDynamic => {
+                self.storage_buffers.increment(descriptor_count, stages);
+                self.storage_buffers_dynamic += 1;
+            }
+            DescriptorType::InputAttachment => {
+                self.input_attachments.increment(descriptor_count, stages);
+            }
+            DescriptorType::InlineUniformBlock => {
+                // The descriptor count is the size of the block in bytes, but the limits count
+                // each binding as a single block.
+                self.inline_uniform_blocks
+                    .increment((descriptor_count != 0) as u32, stages);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -8,8 +8,8 @@
 // according to those terms.
 
 use super::{
-    AttachmentDescription, AttachmentReference, LoadOp, RenderPass, RenderPassCreateInfo,
-    SubpassDependency, SubpassDescription,
+    AttachmentDescription, AttachmentReference, FragmentShadingRateAttachment, LoadOp, RenderPass,
+    RenderPassCreateInfo, SubpassDependency, SubpassDescription,
 };
 use crate::{
     device::Device,
@@ -163,6 +163,7 @@ impl RenderPass {
                 ref resolve_attachments,
                 ref depth_stencil_attachment,
                 ref preserve_attachments,
+                ref fragment_shading_rate_attachment,
                 _ne: _,
             } = subpass;
             let subpass_num = subpass_num as u32;
@@ -592,6 +593,115 @@ impl RenderPass {
                 }
             }
 
+            /*
+                Check fragment shading rate attachment
+                This must be placed after all other attachment checks, so that `layouts` can be
+                used to detect other uses of the attachment in this subpass.
+            */
+
+            if let Some(fsr_atch) = fragment_shading_rate_attachment.as_ref() {
+                let &FragmentShadingRateAttachment {
+                    ref attachment_ref,
+                    texel_size,
+                    _ne: _,
+                } = fsr_atch;
+
+                // VUID?
+                if !device.enabled_features().attachment_fragment_shading_rate {
+                    return Err(RenderPassCreationError::RequirementNotMet {
+                        required_for: "`create_info.subpasses` has an element where \
+                            `fragment_shading_rate_attachment` is `Some`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["attachment_fragment_shading_rate"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkSubpassDescription2-pFragmentShadingRateAttachment-04585
+                if input_attachments
+                    .iter()
+                    .chain(color_attachments)
+                    .chain(resolve_attachments)
+                    .flatten()
+                    .chain(depth_stencil_attachment)
+                    .any(|atch_ref| atch_ref.attachment == attachment_ref.attachment)
+                {
+                    return Err(
+                        RenderPassCreationError::SubpassFragmentShadingRateAttachmentUsedElsewhere {
+                            subpass: subpass_num,
+                            attachment: attachment_ref.attachment,
+                        },
+                    );
+                }
+
+                let (_atch, features, _first_use) = check_attachment(attachment_ref)?;
+
+                // VUID-VkRenderPassCreateInfo2-pAttachments-04586
+                if !features.intersects(FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT) {
+                    return Err(
+                        RenderPassCreationError::SubpassAttachmentFormatUsageNotSupported {
+                            subpass: subpass_num,
+                            attachment: attachment_ref.attachment,
+                            usage: "fragment shading rate",
+                        },
+                    );
+                }
+
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04524
+                if !matches!(
+                    attachment_ref.layout,
+                    ImageLayout::General | ImageLayout::FragmentShadingRateAttachmentOptimal
+                ) {
+                    return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
+                        subpass: subpass_num,
+                        attachment: attachment_ref.attachment,
+                        usage: "fragment shading rate",
+                    });
+                }
+
+                // Not required by spec, but enforced by Vulkano for sanity.
+                if !attachment_ref.aspects.is_empty() {
+                    return Err(RenderPassCreationError::SubpassAttachmentAspectsNotEmpty {
+                        subpass: subpass_num,
+                        attachment: attachment_ref.attachment,
+                    });
+                }
+
+                let min_texel_size = properties
+                    .min_fragment_shading_rate_attachment_texel_size
+                    .unwrap_or([0; 2]);
+                let max_texel_size = properties
+                    .max_fragment_shading_rate_attachment_texel_size
+                    .unwrap_or([0; 2]);
+                let max_aspect_ratio = properties
+                    .max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+                    .unwrap_or(0);
+
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04525
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04526
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04527
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04528
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04529
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04530
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04531
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04532
+                if !(0..2).all(|i| {
+                    texel_size[i].is_power_of_two()
+                        && texel_size[i] >= min_texel_size[i]
+                        && texel_size[i] <= max_texel_size[i]
+                }) || texel_size[0] / texel_size[1] > max_aspect_ratio
+                    || texel_size[1] / texel_size[0] > max_aspect_ratio
+                {
+                    return Err(
+                        RenderPassCreationError::SubpassFragmentShadingRateAttachmentTexelSizeInvalid {
+                            subpass: subpass_num,
+                            texel_size,
+                        },
+                    );
+                }
+            }
+
             /*
                 Check preserve attachments
             */
@@ -1078,6 +1188,10 @@ impl RenderPass {
                     .chain(subpass.resolve_attachments.iter())
                     .map(Option::as_ref)
                     .chain(subpass.depth_stencil_attachment.iter().map(Some))
+                    .chain(
+                        (subpass.fragment_shading_rate_attachment.iter())
+                            .map(|fsr_atch| Some(&fsr_atch.attachment_ref)),
+                    )
                     .map(|atch_ref| {
                         if let Some(atch_ref) = atch_ref {
                             ash::vk::AttachmentReference2 {
@@ -1096,7 +1210,9 @@ impl RenderPass {
             })
             .collect::<SmallVec<[_; 8]>>();
 
-        let subpasses_vk = {
+        let mut fragment_shading_rate_attachments_vk: SmallVec<[_; 4]> = SmallVec::new();
+
+        let mut subpasses_vk = {
             // `ref_index` is increased during the loop and points to the next element to use
             // in `attachment_references_vk`.
             let mut ref_index = 0usize;
@@ -1116,6 +1232,24 @@ impl RenderPass {
                     } else {
                         ptr::null()
                     };
+                    fragment_shading_rate_attachments_vk.push(
+                        subpass
+                            .fragment_shading_rate_attachment
+                            .as_ref()
+                            .map(|fsr_atch| {
+                                let a = attachment_references_vk.as_ptr().add(ref_index);
+                                ref_index += 1;
+
+                                ash::vk::FragmentShadingRateAttachmentInfoKHR {
+                                    p_fragment_shading_rate_attachment: a,
+                                    shading_rate_attachment_texel_size: ash::vk::Extent2D {
+                                        width: fsr_atch.texel_size[0],
+                                        height: fsr_atch.texel_size[1],
+                                    },
+                                    ..Default::default()
+                                }
+                            }),
+                    );
 
                     ash::vk::SubpassDescription2 {
                         flags: ash::vk::SubpassDescriptionFlags::empty(),
@@ -1156,6 +1290,15 @@ impl RenderPass {
             out
         };
 
+        for (subpass_vk, fragment_shading_rate_attachment_vk) in subpasses_vk
+            .iter_mut()
+            .zip(fragment_shading_rate_attachments_vk.iter())
+        {
+            if let Some(info) = fragment_shading_rate_attachment_vk {
+                subpass_vk.p_next = info as *const _ as *const _;
+            }
+        }
+
         let memory_barriers_vk: SmallVec<[_; 4]> = if device.enabled_features().synchronization2 {
             debug_assert!(
                 device.api_version() >= Version::V1_3
@@ -1621,6 +1764,14 @@ pub enum RenderPassCreationError {
         first_samples: SampleCount,
     },
 
+    /// The attachment used as the fragment shading rate attachment in a subpass is also used as
+    /// another attachment type in that subpass.
+    SubpassFragmentShadingRateAttachmentUsedElsewhere { subpass: u32, attachment: u32 },
+
+    /// The texel size of the fragment shading rate attachment in a subpass is not a power of two,
+    /// is outside the range allowed by the device, or exceeds the maximum aspect ratio.
+    SubpassFragmentShadingRateAttachmentTexelSizeInvalid { subpass: u32, texel_size: [u32; 2] },
+
     /// A reference to an attachment used as an input attachment in a subpass selects aspects that
     /// are not present in the format of the attachment.
     SubpassInputAttachmentAspectsNotCompatible { subpass: u32, attachment: u32 },
@@ -1879,6 +2030,24 @@ impl Display for RenderPassCreationError {
                 `samples` value {:?} that is different from the first color attachment ({:?})",
                 attachment, subpass, samples, first_samples,
             ),
+            Self::SubpassFragmentShadingRateAttachmentUsedElsewhere {
+                subpass,
+                attachment,
+            } => write!(
+                f,
+                "attachment {} is used as the fragment shading rate attachment of subpass {}, but \
+                is also used as another attachment type in that subpass",
+                attachment, subpass,
+            ),
+            Self::SubpassFragmentShadingRateAttachmentTexelSizeInvalid {
+                subpass,
+                texel_size,
+            } => write!(
+                f,
+                "the texel size {:?} of the fragment shading rate attachment of subpass {} is not \
+                supported by the device",
+                texel_size, subpass,
+            ),
             Self::SubpassInputAttachmentAspectsNotCompatible {
                 subpass,
                 attachment,
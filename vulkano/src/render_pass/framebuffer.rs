@@ -155,6 +155,20 @@ impl Framebuffer {
                         }
                     }
 
+                    // VUID-VkFramebufferCreateInfo-flags-04548
+                    if let Some(fsr_atch) = &subpass.fragment_shading_rate_attachment {
+                        if fsr_atch.attachment_ref.attachment == attachment_num
+                            && !image_view
+                                .usage()
+                                .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                        {
+                            return Err(FramebufferCreationError::AttachmentMissingUsage {
+                                attachment: attachment_num,
+                                usage: "fragment_shading_rate_attachment",
+                            });
+                        }
+                    }
+
                     // VUID-VkFramebufferCreateInfo-pAttachments-00879
                     if subpass
                         .input_attachments
@@ -204,28 +218,42 @@ impl Framebuffer {
                     );
                 }
 
-                // VUID-VkFramebufferCreateInfo-flags-04533
-                // VUID-VkFramebufferCreateInfo-flags-04534
-                if auto_extent {
-                    extent[0] = extent[0].min(image_view_extent[0]);
-                    extent[1] = extent[1].min(image_view_extent[1]);
-                } else if image_view_extent[0] < extent[0] || image_view_extent[1] < extent[1] {
-                    return Err(FramebufferCreationError::AttachmentExtentTooSmall {
-                        attachment: attachment_num,
-                        provided: image_view_extent,
-                        min: extent,
+                // Fragment shading rate attachments are smaller than the framebuffer, and are
+                // checked separately below, once the extent and layers are known.
+                let is_fragment_shading_rate_attachment =
+                    render_pass.subpasses().iter().any(|subpass| {
+                        subpass
+                            .fragment_shading_rate_attachment
+                            .as_ref()
+                            .map_or(false, |fsr_atch| {
+                                fsr_atch.attachment_ref.attachment == attachment_num
+                            })
                     });
-                }
 
-                // VUID-VkFramebufferCreateInfo-flags-04535
-                if auto_layers {
-                    layers = layers.min(image_view_array_layers);
-                } else if image_view_array_layers < layers {
-                    return Err(FramebufferCreationError::AttachmentNotEnoughLayers {
-                        attachment: attachment_num,
-                        provided: image_view_array_layers,
-                        min: layers,
-                    });
+                if !is_fragment_shading_rate_attachment {
+                    // VUID-VkFramebufferCreateInfo-flags-04533
+                    // VUID-VkFramebufferCreateInfo-flags-04534
+                    if auto_extent {
+                        extent[0] = extent[0].min(image_view_extent[0]);
+                        extent[1] = extent[1].min(image_view_extent[1]);
+                    } else if image_view_extent[0] < extent[0] || image_view_extent[1] < extent[1] {
+                        return Err(FramebufferCreationError::AttachmentExtentTooSmall {
+                            attachment: attachment_num,
+                            provided: image_view_extent,
+                            min: extent,
+                        });
+                    }
+
+                    // VUID-VkFramebufferCreateInfo-flags-04535
+                    if auto_layers {
+                        layers = layers.min(image_view_array_layers);
+                    } else if image_view_array_layers < layers {
+                        return Err(FramebufferCreationError::AttachmentNotEnoughLayers {
+                            attachment: attachment_num,
+                            provided: image_view_array_layers,
+                            min: layers,
+                        });
+                    }
                 }
 
                 // VUID-VkFramebufferCreateInfo-pAttachments-00883
@@ -300,6 +328,41 @@ impl Framebuffer {
             }
         }
 
+        for fsr_atch in render_pass
+            .subpasses()
+            .iter()
+            .filter_map(|subpass| subpass.fragment_shading_rate_attachment.as_ref())
+        {
+            let attachment_num = fsr_atch.attachment_ref.attachment;
+            let image_view = &attachments[attachment_num as usize];
+            let image_view_extent = image_view.image().dimensions().width_height();
+            let image_view_array_layers = image_view.subresource_range().array_layers.end
+                - image_view.subresource_range().array_layers.start;
+            let min_extent = [
+                (extent[0] + fsr_atch.texel_size[0] - 1) / fsr_atch.texel_size[0],
+                (extent[1] + fsr_atch.texel_size[1] - 1) / fsr_atch.texel_size[1],
+            ];
+
+            // VUID-VkFramebufferCreateInfo-flags-04539
+            // VUID-VkFramebufferCreateInfo-flags-04540
+            if image_view_extent[0] < min_extent[0] || image_view_extent[1] < min_extent[1] {
+                return Err(FramebufferCreationError::AttachmentExtentTooSmall {
+                    attachment: attachment_num,
+                    provided: image_view_extent,
+                    min: min_extent,
+                });
+            }
+
+            // VUID-VkFramebufferCreateInfo-flags-04541
+            if image_view_array_layers != 1 && image_view_array_layers < layers {
+                return Err(FramebufferCreationError::AttachmentNotEnoughLayers {
+                    attachment: attachment_num,
+                    provided: image_view_array_layers,
+                    min: layers,
+                });
+            }
+        }
+
         let create_info = ash::vk::FramebufferCreateInfo {
             flags: ash::vk::FramebufferCreateFlags::empty(),
             render_pass: render_pass.handle(),
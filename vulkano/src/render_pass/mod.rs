@@ -190,6 +190,7 @@ impl RenderPass {
                 ref color_attachments,
                 ref resolve_attachments,
                 ref depth_stencil_attachment,
+                ref fragment_shading_rate_attachment,
                 ..
             } = subpass_desc;
 
@@ -197,6 +198,11 @@ impl RenderPass {
                 .chain(color_attachments.iter().flatten())
                 .chain(resolve_attachments.iter().flatten())
                 .chain(depth_stencil_attachment.iter())
+                .chain(
+                    fragment_shading_rate_attachment
+                        .iter()
+                        .map(|fsr_atch| &fsr_atch.attachment_ref),
+                )
             {
                 match &mut attachment_uses[atch_ref.attachment as usize] {
                     Some(attachment_use) => attachment_use.last_use_subpass = index,
@@ -416,6 +422,7 @@ impl RenderPass {
                     resolve_attachments: resolve_attachments1,
                     depth_stencil_attachment: depth_stencil_attachment1,
                     preserve_attachments: _,
+                    fragment_shading_rate_attachment: fragment_shading_rate_attachment1,
                     _ne: _,
                 } = subpass1;
                 let SubpassDescription {
@@ -425,6 +432,7 @@ impl RenderPass {
                     resolve_attachments: resolve_attachments2,
                     depth_stencil_attachment: depth_stencil_attachment2,
                     preserve_attachments: _,
+                    fragment_shading_rate_attachment: fragment_shading_rate_attachment2,
                     _ne: _,
                 } = subpass2;
 
@@ -464,6 +472,17 @@ impl RenderPass {
                     return false;
                 }
 
+                if !are_atch_refs_compatible(
+                    fragment_shading_rate_attachment1
+                        .as_ref()
+                        .map(|fsr_atch| &fsr_atch.attachment_ref),
+                    fragment_shading_rate_attachment2
+                        .as_ref()
+                        .map(|fsr_atch| &fsr_atch.attachment_ref),
+                ) {
+                    return false;
+                }
+
                 if view_mask1 != view_mask2 {
                     return false;
                 }
@@ -964,6 +983,17 @@ pub struct SubpassDescription {
     /// The default value is empty.
     pub preserve_attachments: Vec<u32>,
 
+    /// The attachment of the render pass that is to be used as the fragment shading rate
+    /// attachment in this subpass.
+    ///
+    /// If set to `Some`, the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device, and the referenced attachment must not be used as
+    /// any other attachment type in the subpass.
+    ///
+    /// The default value is `None`.
+    pub fragment_shading_rate_attachment: Option<FragmentShadingRateAttachment>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -977,6 +1007,50 @@ impl Default for SubpassDescription {
             input_attachments: Vec::new(),
             resolve_attachments: Vec::new(),
             preserve_attachments: Vec::new(),
+            fragment_shading_rate_attachment: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A fragment shading rate attachment of a subpass.
+#[derive(Clone, Debug)]
+pub struct FragmentShadingRateAttachment {
+    /// The attachment that is used.
+    ///
+    /// The referenced attachment must have a format that supports the
+    /// [`FRAGMENT_SHADING_RATE_ATTACHMENT`](crate::format::FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT)
+    /// format feature, and its layout must be [`ImageLayout::General`] or
+    /// [`ImageLayout::FragmentShadingRateAttachmentOptimal`].
+    ///
+    /// The default value is [`AttachmentReference::default()`].
+    pub attachment_ref: AttachmentReference,
+
+    /// The width and height, in pixels, of the area of the framebuffer that each texel of the
+    /// attachment corresponds to.
+    ///
+    /// Both values must be powers of two, and must lie between the
+    /// [`min_fragment_shading_rate_attachment_texel_size`] and
+    /// [`max_fragment_shading_rate_attachment_texel_size`] device properties. The ratio between
+    /// the larger and the smaller value must not exceed the
+    /// [`max_fragment_shading_rate_attachment_texel_size_aspect_ratio`] device property.
+    ///
+    /// The default value is `[0, 0]`, which must be overridden.
+    ///
+    /// [`min_fragment_shading_rate_attachment_texel_size`]: crate::device::Properties::min_fragment_shading_rate_attachment_texel_size
+    /// [`max_fragment_shading_rate_attachment_texel_size`]: crate::device::Properties::max_fragment_shading_rate_attachment_texel_size
+    /// [`max_fragment_shading_rate_attachment_texel_size_aspect_ratio`]: crate::device::Properties::max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+    pub texel_size: [u32; 2],
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FragmentShadingRateAttachment {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            attachment_ref: Default::default(),
+            texel_size: [0, 0],
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1246,7 +1320,11 @@ pub(crate) struct AttachmentUse {
 mod tests {
     use crate::{
         format::Format,
-        render_pass::{RenderPass, RenderPassCreationError},
+        image::ImageLayout,
+        render_pass::{
+            AttachmentDescription, AttachmentReference, FragmentShadingRateAttachment, RenderPass,
+            RenderPassCreateInfo, RenderPassCreationError, SubpassDescription,
+        },
     };
 
     #[test]
@@ -1309,4 +1387,94 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn fragment_shading_rate_attachment_texel_size() {
+        let (device, _) =
+            gfx_dev_and_queue!(attachment_fragment_shading_rate; khr_fragment_shading_rate);
+
+        let properties = device.physical_device().properties();
+        let min_texel_size = properties
+            .min_fragment_shading_rate_attachment_texel_size
+            .unwrap();
+        let max_texel_size = properties
+            .max_fragment_shading_rate_attachment_texel_size
+            .unwrap();
+        let max_aspect_ratio = properties
+            .max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+            .unwrap();
+
+        let create = |texel_size| {
+            RenderPass::new(
+                device.clone(),
+                RenderPassCreateInfo {
+                    attachments: vec![AttachmentDescription {
+                        format: Some(Format::R8_UINT),
+                        initial_layout: ImageLayout::FragmentShadingRateAttachmentOptimal,
+                        final_layout: ImageLayout::FragmentShadingRateAttachmentOptimal,
+                        ..Default::default()
+                    }],
+                    subpasses: vec![SubpassDescription {
+                        fragment_shading_rate_attachment: Some(FragmentShadingRateAttachment {
+                            attachment_ref: AttachmentReference {
+                                attachment: 0,
+                                layout: ImageLayout::FragmentShadingRateAttachmentOptimal,
+                                ..Default::default()
+                            },
+                            texel_size,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )
+        };
+        let is_texel_size_invalid = |texel_size| {
+            matches!(
+                create(texel_size),
+                Err(RenderPassCreationError::SubpassFragmentShadingRateAttachmentTexelSizeInvalid {
+                    subpass: 0,
+                    texel_size: error_texel_size,
+                }) if error_texel_size == texel_size
+            )
+        };
+
+        assert!(create(min_texel_size).is_ok());
+        assert!(is_texel_size_invalid([0, 0]));
+        assert!(is_texel_size_invalid([
+            min_texel_size[0] + 3,
+            min_texel_size[1]
+        ]));
+        assert!(is_texel_size_invalid([
+            max_texel_size[0] * 2,
+            min_texel_size[1]
+        ]));
+        assert!(is_texel_size_invalid([
+            min_texel_size[0],
+            max_texel_size[1] * 2
+        ]));
+
+        if min_texel_size[0] > 1 {
+            assert!(is_texel_size_invalid([
+                min_texel_size[0] / 2,
+                min_texel_size[1]
+            ]));
+        }
+
+        // The aspect ratio is checked in both directions.
+        if max_texel_size[0] / min_texel_size[1] > max_aspect_ratio {
+            assert!(is_texel_size_invalid([
+                max_texel_size[0],
+                min_texel_size[1]
+            ]));
+        }
+
+        if max_texel_size[1] / min_texel_size[0] > max_aspect_ratio {
+            assert!(is_texel_size_invalid([
+                min_texel_size[0],
+                max_texel_size[1]
+            ]));
+        }
+    }
 }
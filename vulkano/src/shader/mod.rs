@@ -143,6 +143,7 @@ use crate::{
     DeviceSize, OomError, Version, VulkanError, VulkanObject,
 };
 use ahash::{HashMap, HashSet};
+use smallvec::SmallVec;
 use std::{
     borrow::Cow,
     collections::hash_map::Entry,
@@ -501,6 +502,7 @@ pub struct EntryPointInfo {
     pub specialization_constant_requirements: HashMap<u32, SpecializationConstantRequirements>,
     pub input_interface: ShaderInterface,
     pub output_interface: ShaderInterface,
+    pub transform_feedback_outputs: Option<Vec<TransformFeedbackOutput>>,
 }
 
 /// Represents a shader entry point in a shader module.
@@ -571,6 +573,13 @@ impl<'a> EntryPoint<'a> {
     pub fn output_interface(&self) -> &ShaderInterface {
         &self.info.output_interface
     }
+
+    /// Returns the outputs that are captured by transform feedback, or `None` if the shader does
+    /// not declare the `Xfb` execution mode.
+    #[inline]
+    pub fn transform_feedback_outputs(&self) -> Option<&[TransformFeedbackOutput]> {
+        self.info.transform_feedback_outputs.as_deref()
+    }
 }
 
 /// The mode in which a shader executes. This includes both information about the shader type/stage,
@@ -707,6 +716,11 @@ pub struct DescriptorBindingRequirements {
     /// This is `None` for non-image bindings.
     pub image_view_type: Option<ImageViewType>,
 
+    /// The size in bytes of the block that the shader declares, if the binding is a uniform
+    /// block. If it is bound as an inline uniform block, the descriptor count of the binding must
+    /// be at least this size.
+    pub uniform_block_size: Option<u32>,
+
     /// The shader stages that the binding must be declared for.
     pub stages: ShaderStages,
 
@@ -767,6 +781,7 @@ impl DescriptorBindingRequirements {
             image_multisampled,
             image_scalar_type,
             image_view_type,
+            uniform_block_size,
             stages,
             descriptors,
         } = self;
@@ -810,6 +825,7 @@ impl DescriptorBindingRequirements {
         *image_format = image_format.or(other.image_format);
         *image_scalar_type = image_scalar_type.or(other.image_scalar_type);
         *image_view_type = image_view_type.or(other.image_view_type);
+        *uniform_block_size = (*uniform_block_size).max(other.uniform_block_size);
         *stages |= other.stages;
 
         for (&index, other) in &other.descriptors {
@@ -1036,36 +1052,63 @@ impl ShaderInterface {
 
     /// Checks whether the interface is potentially compatible with another one.
     ///
-    /// Returns `Ok` if the two interfaces are compatible.
+    /// Returns `Ok` if the two interfaces are compatible, or the first mismatch that was found
+    /// otherwise. Use [`mismatches`](Self::mismatches) to get every mismatch at once.
     #[inline]
     pub fn matches(&self, other: &ShaderInterface) -> Result<(), ShaderInterfaceMismatchError> {
+        match self.mismatches(other).into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns every mismatch between this interface and another one.
+    ///
+    /// The returned list is empty if the two interfaces are compatible.
+    pub fn mismatches(&self, other: &ShaderInterface) -> Vec<ShaderInterfaceMismatchError> {
+        let mut mismatches = Vec::new();
+
         if self.elements().len() != other.elements().len() {
-            return Err(ShaderInterfaceMismatchError::ElementsCountMismatch {
+            mismatches.push(ShaderInterfaceMismatchError::ElementsCountMismatch {
                 self_elements: self.elements().len() as u32,
                 other_elements: other.elements().len() as u32,
             });
         }
 
         for a in self.elements() {
+            // An element spanning several locations is reported only once, at the first
+            // location where it is missing, and once for each element of `other` that it
+            // overlaps with a different type.
+            let mut missing_reported = false;
+            let mut mismatched_elements: SmallVec<[usize; 4]> = SmallVec::new();
+
             let location_range = a.location..a.location + a.ty.num_locations();
             for loc in location_range {
-                let b = match other
+                let (b_index, b) = match other
                     .elements()
                     .iter()
-                    .find(|e| loc >= e.location && loc < e.location + e.ty.num_locations())
+                    .enumerate()
+                    .find(|(_, e)| loc >= e.location && loc < e.location + e.ty.num_locations())
                 {
                     None => {
-                        return Err(ShaderInterfaceMismatchError::MissingElement { location: loc })
+                        if !missing_reported {
+                            mismatches.push(ShaderInterfaceMismatchError::MissingElement {
+                                location: loc,
+                            });
+                            missing_reported = true;
+                        }
+                        continue;
                     }
                     Some(b) => b,
                 };
 
-                if a.ty != b.ty {
-                    return Err(ShaderInterfaceMismatchError::TypeMismatch {
+                if a.ty != b.ty && !mismatched_elements.contains(&b_index) {
+                    mismatches.push(ShaderInterfaceMismatchError::TypeMismatch {
                         location: loc,
                         self_ty: a.ty,
                         other_ty: b.ty,
                     });
+                    mismatched_elements.push(b_index);
                 }
 
                 // TODO: enforce this?
@@ -1076,10 +1119,10 @@ impl ShaderInterface {
             }
         }
 
-        // Note: since we check that the number of elements is the same, we don't need to iterate
-        // over b's elements.
+        // Note: if the number of elements is the same, we don't need to iterate over b's
+        // elements. Otherwise, the count mismatch has already been reported.
 
-        Ok(())
+        mismatches
     }
 }
 
@@ -1123,6 +1166,32 @@ impl ShaderInterfaceEntryType {
     }
 }
 
+/// An output of a shader that is captured into a transform feedback buffer.
+///
+/// These are derived from the `XfbBuffer`, `XfbStride`, `Offset` and `Stream` decorations of the
+/// output variables of a shader that declares the `Xfb` execution mode. Outputs whose size depends
+/// on a specialization constant are not included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransformFeedbackOutput {
+    /// The index of the transform feedback buffer that the output is written to.
+    pub buffer: u32,
+
+    /// The number of bytes between consecutive vertices in the transform feedback buffer.
+    pub stride: u32,
+
+    /// The offset in bytes of the output within each vertex in the transform feedback buffer.
+    pub offset: u32,
+
+    /// The size in bytes of the output.
+    pub size: u32,
+
+    /// The vertex stream that the output belongs to. This is always 0 outside geometry shaders.
+    pub stream: u32,
+
+    /// Name of the output, or `None` if the name is unknown.
+    pub name: Option<Cow<'static, str>>,
+}
+
 /// The numeric base type of a shader variable.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ShaderScalarType {
@@ -22,7 +22,7 @@ use crate::{
         DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, GeometryShaderExecution,
         GeometryShaderInput, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
         ShaderInterfaceEntryType, ShaderScalarType, ShaderStage,
-        SpecializationConstantRequirements,
+        SpecializationConstantRequirements, TransformFeedbackOutput,
     },
     DeviceSize,
 };
@@ -98,6 +98,7 @@ pub fn entry_points(
             StorageClass::Output,
             matches!(execution_model, ExecutionModel::TessellationControl),
         );
+        let transform_feedback_outputs = transform_feedback_outputs(spirv, function_id, interface);
 
         Some((
             entry_point_name.clone(),
@@ -109,6 +110,7 @@ pub fn entry_points(
                 specialization_constant_requirements,
                 input_interface,
                 output_interface,
+                transform_feedback_outputs,
             },
         ))
     })
@@ -775,16 +777,21 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
     let (mut next_type_id, is_storage_buffer) = {
         let variable_type_id = match *variable_id_info.instruction() {
             Instruction::Variable { result_type_id, .. } => result_type_id,
-            _ => panic!("Id {} is not a variable", variable_id),
+            ref instruction => panic!(
+                "Id {} is not a variable: `{}`",
+                variable_id,
+                spirv.disassemble_instruction(instruction),
+            ),
         };
 
         match *spirv.id(variable_type_id).instruction() {
             Instruction::TypePointer {
                 ty, storage_class, ..
             } => (Some(ty), storage_class == StorageClass::StorageBuffer),
-            _ => panic!(
-                "Variable {} result_type_id does not refer to a TypePointer instruction",
-                variable_id
+            ref instruction => panic!(
+                "Variable {} result_type_id does not refer to a TypePointer instruction: `{}`",
+                variable_id,
+                spirv.disassemble_instruction(instruction),
             ),
         }
     };
@@ -829,7 +836,10 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
                     reqs.descriptor_types = vec![
                         DescriptorType::UniformBuffer,
                         DescriptorType::UniformBufferDynamic,
+                        DescriptorType::InlineUniformBlock,
                     ];
+                    reqs.uniform_block_size =
+                        size_of_type(spirv, id).and_then(|size| size.try_into().ok());
                 };
 
                 None
@@ -957,7 +967,7 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
 
             Instruction::TypeAccelerationStructureKHR { .. } => None, // FIXME temporary workaround
 
-            _ => {
+            ref instruction => {
                 let name = variable_id_info
                     .iter_name()
                     .find_map(|instruction| match *instruction {
@@ -968,8 +978,10 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
 
                 panic!(
                     "Couldn't find relevant type for global variable `{}` (id {}, maybe \
-                    unimplemented)",
-                    name, variable_id,
+                    unimplemented): `{}`",
+                    name,
+                    variable_id,
+                    spirv.disassemble_instruction(instruction),
                 );
             }
         };
@@ -1180,6 +1192,233 @@ fn shader_interface(
     ShaderInterface { elements }
 }
 
+/// Extracts the outputs that are captured by transform feedback from `spirv`, or `None` if the
+/// entry point `function_id` does not declare the `Xfb` execution mode.
+fn transform_feedback_outputs(
+    spirv: &Spirv,
+    function_id: Id,
+    interface: &[Id],
+) -> Option<Vec<TransformFeedbackOutput>> {
+    #[derive(Clone, Copy, Default)]
+    struct XfbDecorations {
+        buffer: Option<u32>,
+        stride: Option<u32>,
+        offset: Option<u32>,
+        stream: Option<u32>,
+    }
+
+    impl XfbDecorations {
+        fn from_instructions<'a>(instructions: impl IntoIterator<Item = &'a Instruction>) -> Self {
+            let mut decorations = Self::default();
+
+            for instruction in instructions {
+                let decoration = match instruction {
+                    Instruction::Decorate { decoration, .. }
+                    | Instruction::MemberDecorate { decoration, .. } => decoration,
+                    _ => continue,
+                };
+
+                match *decoration {
+                    Decoration::XfbBuffer { xfb_buffer_number } => {
+                        decorations.buffer = Some(xfb_buffer_number)
+                    }
+                    Decoration::XfbStride { xfb_stride } => decorations.stride = Some(xfb_stride),
+                    Decoration::Offset { byte_offset } => decorations.offset = Some(byte_offset),
+                    Decoration::Stream { stream_number } => {
+                        decorations.stream = Some(stream_number)
+                    }
+                    _ => (),
+                }
+            }
+
+            decorations
+        }
+
+        // Members of a block inherit the buffer, stride and stream of the block variable.
+        fn inherit(self, parent: Self) -> Self {
+            Self {
+                buffer: self.buffer.or(parent.buffer),
+                stride: self.stride.or(parent.stride),
+                offset: self.offset,
+                stream: self.stream.or(parent.stream),
+            }
+        }
+    }
+
+    let has_xfb = spirv.iter_execution_mode().any(|instruction| {
+        matches!(
+            instruction,
+            Instruction::ExecutionMode {
+                entry_point,
+                mode: ExecutionMode::Xfb,
+                ..
+            } if *entry_point == function_id
+        )
+    });
+
+    if !has_xfb {
+        return None;
+    }
+
+    let mut strides: HashMap<u32, u32> = HashMap::default();
+    let mut outputs = Vec::new();
+    let mut add_output = |decorations: XfbDecorations, ty: Id, name: Option<Cow<'static, str>>| {
+        let buffer = match decorations.buffer {
+            Some(x) => x,
+            None => return,
+        };
+
+        if let Some(stride) = decorations.stride {
+            strides.insert(buffer, stride);
+        }
+
+        if let Some(offset) = decorations.offset {
+            // Outputs whose size isn't known at compile time, such as arrays with a
+            // specialization constant length, are skipped.
+            let size = match transform_feedback_size_of_type(spirv, ty) {
+                Some(x) => x,
+                None => return,
+            };
+
+            outputs.push(TransformFeedbackOutput {
+                buffer,
+                stride: 0,
+                offset,
+                size,
+                stream: decorations.stream.unwrap_or(0),
+                name,
+            });
+        }
+    };
+
+    for &id in interface {
+        let (result_type_id, result_id) = match *spirv.id(id).instruction() {
+            Instruction::Variable {
+                result_type_id,
+                result_id,
+                storage_class: StorageClass::Output,
+                ..
+            } => (result_type_id, result_id),
+            _ => continue,
+        };
+
+        let id_info = spirv.id(result_id);
+        let variable_decorations = XfbDecorations::from_instructions(id_info.iter_decoration());
+        let pointee_type_id = match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer { ty, .. } => ty,
+            _ => result_type_id,
+        };
+
+        match spirv.id(pointee_type_id).instruction() {
+            Instruction::TypeStruct { member_types, .. } => {
+                for (&member_type_id, member_info) in member_types
+                    .iter()
+                    .zip(spirv.id(pointee_type_id).iter_members())
+                {
+                    let member_decorations =
+                        XfbDecorations::from_instructions(member_info.iter_decoration())
+                            .inherit(variable_decorations);
+                    let name = member_info
+                        .iter_name()
+                        .find_map(|instruction| match *instruction {
+                            Instruction::MemberName { ref name, .. } => {
+                                Some(Cow::Owned(name.clone()))
+                            }
+                            _ => None,
+                        });
+
+                    add_output(member_decorations, member_type_id, name);
+                }
+
+                // The block variable itself may carry the stride of its buffer.
+                add_output(
+                    XfbDecorations {
+                        offset: None,
+                        ..variable_decorations
+                    },
+                    pointee_type_id,
+                    None,
+                );
+            }
+            _ => {
+                let name = id_info
+                    .iter_name()
+                    .find_map(|instruction| match *instruction {
+                        Instruction::Name { ref name, .. } => Some(Cow::Owned(name.clone())),
+                        _ => None,
+                    });
+
+                add_output(variable_decorations, pointee_type_id, name);
+            }
+        }
+    }
+
+    for output in &mut outputs {
+        // The stride of a buffer can be declared on any of the variables that write to it.
+        output.stride = strides.get(&output.buffer).copied().unwrap_or(0);
+    }
+
+    Some(outputs)
+}
+
+/// Returns the number of bytes that a value of the type `id` occupies when it is captured by
+/// transform feedback, or `None` if it cannot be determined.
+fn transform_feedback_size_of_type(spirv: &Spirv, id: Id) -> Option<u32> {
+    let id_info = spirv.id(id);
+
+    match *id_info.instruction() {
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. } => {
+            Some(width / 8)
+        }
+        Instruction::TypeVector {
+            component_type,
+            component_count,
+            ..
+        } => transform_feedback_size_of_type(spirv, component_type)?.checked_mul(component_count),
+        Instruction::TypeMatrix {
+            column_type,
+            column_count,
+            ..
+        } => transform_feedback_size_of_type(spirv, column_type)?.checked_mul(column_count),
+        Instruction::TypeArray {
+            element_type,
+            length,
+            ..
+        } => {
+            // Specialization constants only have a default value at this point.
+            let length = match spirv.id(length).instruction() {
+                Instruction::Constant { value, .. } => value[0],
+                _ => return None,
+            };
+
+            transform_feedback_size_of_type(spirv, element_type)?.checked_mul(length)
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => {
+            let mut end_of_struct: u32 = 0;
+
+            for (&member, member_info) in member_types.iter().zip(id_info.iter_members()) {
+                let offset = member_info
+                    .iter_decoration()
+                    .find_map(|instruction| match *instruction {
+                        Instruction::MemberDecorate {
+                            decoration: Decoration::Offset { byte_offset },
+                            ..
+                        } => Some(byte_offset),
+                        _ => None,
+                    })
+                    .unwrap_or(end_of_struct);
+                end_of_struct =
+                    offset.checked_add(transform_feedback_size_of_type(spirv, member)?)?;
+            }
+
+            Some(end_of_struct)
+        }
+        _ => None,
+    }
+}
+
 /// Returns the size of a type, or `None` if its size cannot be determined.
 fn size_of_type(spirv: &Spirv, id: Id) -> Option<DeviceSize> {
     let id_info = spirv.id(id);
@@ -1269,7 +1508,11 @@ fn size_of_type(spirv: &Spirv, id: Id) -> Option<DeviceSize> {
 
             Some(end_of_struct)
         }
-        _ => panic!("Type {} not found", id),
+        ref instruction => panic!(
+            "Type {} not found: `{}`",
+            id,
+            spirv.disassemble_instruction(instruction),
+        ),
     }
 }
 
@@ -1388,7 +1631,11 @@ fn shader_interface_type_of(
         Instruction::TypePointer { ty, .. } => {
             shader_interface_type_of(spirv, ty, ignore_first_array)
         }
-        _ => panic!("Type {} not found or invalid", id),
+        ref instruction => panic!(
+            "Type {} not found or invalid: `{}`",
+            id,
+            spirv.disassemble_instruction(instruction),
+        ),
     }
 }
 
@@ -12,14 +12,20 @@
 //! This can be used to inspect and validate a SPIR-V module at runtime. The `Spirv` type does some
 //! validation, but you should not assume that code that is read successfully is valid.
 //!
+//! A `Spirv` can also be edited and written back out as words with [`Spirv::to_words`]. This
+//! makes it possible to, for example, remap the descriptor bindings of a third-party shader
+//! before creating a shader module from it. For debugging, [`Spirv::disassemble`] produces a
+//! textual representation of the module, in the same format as `spirv-dis`.
+//!
 //! For more information about SPIR-V modules, instructions and types, see the
 //! [SPIR-V specification](https://registry.khronos.org/SPIR-V/specs/unified1/SPIRV.html).
 
 use crate::Version;
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet};
 use std::{
     error::Error,
-    fmt::{Display, Error as FmtError, Formatter},
+    fmt::{Display, Error as FmtError, Formatter, Write},
+    mem,
     ops::Range,
     string::FromUtf8Error,
 };
@@ -31,6 +37,7 @@ include!(concat!(env!("OUT_DIR"), "/spirv_parse.rs"));
 #[derive(Clone, Debug)]
 pub struct Spirv {
     version: Version,
+    generator: u32,
     bound: u32,
     instructions: Vec<Instruction>,
     ids: HashMap<Id, IdDataIndices>,
@@ -64,6 +71,7 @@ impl Spirv {
             patch: words[1] & 0x000000ff,
         };
 
+        let generator = words[2];
         let bound = words[3];
 
         let instructions = {
@@ -96,6 +104,29 @@ impl Spirv {
             ret
         };
 
+        Self::analyze(version, generator, bound, instructions)
+    }
+
+    /// Builds a module from a list of already parsed instructions, performing the same analysis
+    /// as [`Spirv::new`].
+    ///
+    /// This can be used together with [`Spirv::into_instructions`] to make arbitrary edits to a
+    /// module.
+    #[inline]
+    pub fn from_instructions(
+        version: Version,
+        bound: u32,
+        instructions: Vec<Instruction>,
+    ) -> Result<Spirv, SpirvError> {
+        Self::analyze(version, 0, bound, instructions)
+    }
+
+    fn analyze(
+        version: Version,
+        generator: u32,
+        bound: u32,
+        instructions: Vec<Instruction>,
+    ) -> Result<Spirv, SpirvError> {
         // It is impossible for a valid SPIR-V file to contain more Ids than instructions, so put
         // a sane upper limit on the allocation. This prevents a malicious file from causing huge
         // memory allocations.
@@ -111,10 +142,17 @@ impl Spirv {
         let mut range_global: Option<Range<usize>> = None;
         let mut in_function = false;
 
-        fn set_range(range: &mut Option<Range<usize>>, index: usize) -> Result<(), SpirvError> {
+        fn set_range(
+            range: &mut Option<Range<usize>>,
+            index: usize,
+            instruction: &Instruction,
+        ) -> Result<(), SpirvError> {
             if let Some(range) = range {
                 if range.end != index {
-                    return Err(SpirvError::BadLayout { index });
+                    return Err(SpirvError::BadLayout {
+                        index,
+                        instruction: Box::new(instruction.clone()),
+                    });
                 }
 
                 range.end = index + 1;
@@ -131,7 +169,12 @@ impl Spirv {
         for (index, instruction) in instructions.iter().enumerate() {
             if let Some(id) = instruction.result_id() {
                 if u32::from(id) >= bound {
-                    return Err(SpirvError::IdOutOfBounds { id, index, bound });
+                    return Err(SpirvError::IdOutOfBounds {
+                        id,
+                        index,
+                        bound,
+                        instruction: Box::new(instruction.clone()),
+                    });
                 }
 
                 let members = if let Instruction::TypeStruct { member_types, .. } = instruction {
@@ -153,21 +196,32 @@ impl Spirv {
                         id,
                         first_index: first.index,
                         second_index: index,
+                        instruction: Box::new(instruction.clone()),
                     });
                 }
             }
 
             match instruction {
-                Instruction::Capability { .. } => set_range(&mut range_capability, index)?,
-                Instruction::Extension { .. } => set_range(&mut range_extension, index)?,
-                Instruction::ExtInstImport { .. } => set_range(&mut range_ext_inst_import, index)?,
-                Instruction::MemoryModel { .. } => set_range(&mut range_memory_model, index)?,
-                Instruction::EntryPoint { .. } => set_range(&mut range_entry_point, index)?,
+                Instruction::Capability { .. } => {
+                    set_range(&mut range_capability, index, instruction)?
+                }
+                Instruction::Extension { .. } => {
+                    set_range(&mut range_extension, index, instruction)?
+                }
+                Instruction::ExtInstImport { .. } => {
+                    set_range(&mut range_ext_inst_import, index, instruction)?
+                }
+                Instruction::MemoryModel { .. } => {
+                    set_range(&mut range_memory_model, index, instruction)?
+                }
+                Instruction::EntryPoint { .. } => {
+                    set_range(&mut range_entry_point, index, instruction)?
+                }
                 Instruction::ExecutionMode { .. } | Instruction::ExecutionModeId { .. } => {
-                    set_range(&mut range_execution_mode, index)?
+                    set_range(&mut range_execution_mode, index, instruction)?
                 }
                 Instruction::Name { .. } | Instruction::MemberName { .. } => {
-                    set_range(&mut range_name, index)?
+                    set_range(&mut range_name, index, instruction)?
                 }
                 Instruction::Decorate { .. }
                 | Instruction::MemberDecorate { .. }
@@ -177,7 +231,7 @@ impl Spirv {
                 | Instruction::DecorateId { .. }
                 | Instruction::DecorateString { .. }
                 | Instruction::MemberDecorateString { .. } => {
-                    set_range(&mut range_decoration, index)?
+                    set_range(&mut range_decoration, index, instruction)?
                 }
                 Instruction::TypeVoid { .. }
                 | Instruction::TypeBool { .. }
@@ -229,19 +283,23 @@ impl Spirv {
                 | Instruction::SpecConstantFalse { .. }
                 | Instruction::SpecConstant { .. }
                 | Instruction::SpecConstantComposite { .. }
-                | Instruction::SpecConstantOp { .. } => set_range(&mut range_global, index)?,
-                Instruction::Undef { .. } if !in_function => set_range(&mut range_global, index)?,
+                | Instruction::SpecConstantOp { .. } => {
+                    set_range(&mut range_global, index, instruction)?
+                }
+                Instruction::Undef { .. } if !in_function => {
+                    set_range(&mut range_global, index, instruction)?
+                }
                 Instruction::Variable { storage_class, .. }
                     if *storage_class != StorageClass::Function =>
                 {
-                    set_range(&mut range_global, index)?
+                    set_range(&mut range_global, index, instruction)?
                 }
                 Instruction::Function { .. } => {
                     in_function = true;
                 }
                 Instruction::Line { .. } | Instruction::NoLine { .. } => {
                     if !in_function {
-                        set_range(&mut range_global, index)?
+                        set_range(&mut range_global, index, instruction)?
                     }
                 }
                 _ => (),
@@ -250,6 +308,7 @@ impl Spirv {
 
         let mut spirv = Spirv {
             version,
+            generator,
             bound,
             instructions,
             ids,
@@ -327,7 +386,10 @@ impl Spirv {
                             spirv.instructions[data.index],
                             Instruction::DecorationGroup { .. }
                         ) {
-                            return Err(SpirvError::GroupDecorateNotGroup { index });
+                            return Err(SpirvError::GroupDecorateNotGroup {
+                                index,
+                                instruction: Box::new(spirv.instructions[index].clone()),
+                            });
                         };
                         data.decorations.clone()
                     };
@@ -352,7 +414,10 @@ impl Spirv {
                             spirv.instructions[data.index],
                             Instruction::DecorationGroup { .. }
                         ) {
-                            return Err(SpirvError::GroupDecorateNotGroup { index });
+                            return Err(SpirvError::GroupDecorateNotGroup {
+                                index,
+                                instruction: Box::new(spirv.instructions[index].clone()),
+                            });
                         };
                         data.decorations.clone()
                     };
@@ -459,6 +524,450 @@ impl Spirv {
     pub fn iter_global(&self) -> impl ExactSizeIterator<Item = &Instruction> {
         self.instructions[self.range_global.clone()].iter()
     }
+
+    /// Consumes the module and returns its instructions.
+    #[inline]
+    pub fn into_instructions(self) -> Vec<Instruction> {
+        self.instructions
+    }
+
+    /// Encodes the module back into a list of SPIR-V words, including the header.
+    pub fn to_words(&self) -> Vec<u32> {
+        let mut words = vec![
+            0x07230203,
+            self.version.major << 16 | self.version.minor << 8 | self.version.patch,
+            self.generator,
+            self.bound,
+            0,
+        ];
+
+        for instruction in &self.instructions {
+            instruction.encode(&mut words);
+        }
+
+        words
+    }
+
+    /// Returns a textual representation of the module, in the same format that is used by the
+    /// `spirv-dis` tool.
+    ///
+    /// `Id`s are given the names assigned by `Name` instructions where possible. The values of
+    /// constants are printed as raw words.
+    pub fn disassemble(&self) -> String {
+        let names = self.id_names();
+        let mut output = String::new();
+
+        writeln!(output, "; SPIR-V").unwrap();
+        writeln!(
+            output,
+            "; Version: {}.{}",
+            self.version.major, self.version.minor
+        )
+        .unwrap();
+        writeln!(output, "; Generator: {:#010x}", self.generator).unwrap();
+        writeln!(output, "; Bound: {}", self.bound).unwrap();
+        writeln!(output, "; Schema: 0").unwrap();
+
+        for instruction in &self.instructions {
+            let (result, operands) = disassemble(instruction, Some(&names));
+
+            match result {
+                Some(result) => writeln!(output, "{:>12} = {}", result, operands),
+                None => writeln!(output, "{:15}{}", "", operands),
+            }
+            .unwrap();
+        }
+
+        output
+    }
+
+    /// Returns a textual representation of a single instruction, in the same format as
+    /// [`disassemble`](Spirv::disassemble). This can be used to quote an instruction in an
+    /// error message.
+    pub fn disassemble_instruction(&self, instruction: &Instruction) -> String {
+        let (result, operands) = disassemble(instruction, Some(&self.id_names()));
+
+        match result {
+            Some(result) => format!("{} = {}", result, operands),
+            None => operands,
+        }
+    }
+
+    /// Returns the names that `disassemble` uses for `Id`s, derived from `Name` instructions.
+    /// Names are made unique, and characters that are not allowed are replaced.
+    fn id_names(&self) -> HashMap<Id, String> {
+        let mut names = HashMap::default();
+        let mut used_names = HashSet::default();
+
+        for instruction in self.iter_name() {
+            let (target, name) = match instruction {
+                Instruction::Name { target, name } if !name.is_empty() => (*target, name),
+                _ => continue,
+            };
+
+            if names.contains_key(&target) {
+                continue;
+            }
+
+            let mut name: String = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+
+            // Purely numeric names would look like unnamed ids.
+            if name.starts_with(|c: char| c.is_ascii_digit()) {
+                name.insert(0, '_');
+            }
+
+            if !used_names.insert(name.clone()) {
+                name = format!("{}_{}", name, target.0);
+                used_names.insert(name.clone());
+            }
+
+            names.insert(target, name);
+        }
+
+        names
+    }
+
+    /// Changes the default value of the specialization constant with the given `constant_id`,
+    /// which is the value of its `SpecId` decoration.
+    ///
+    /// For boolean constants, `value` must contain a single word, where `0` means `false` and any
+    /// other value means `true`. For scalar constants, `value` must have the same number of words
+    /// as the existing default value.
+    pub fn set_specialization_constant_default(
+        &mut self,
+        constant_id: u32,
+        value: &[u32],
+    ) -> Result<(), SpirvEditError> {
+        let target = self
+            .iter_decoration()
+            .find_map(|instruction| match *instruction {
+                Instruction::Decorate {
+                    target,
+                    decoration:
+                        Decoration::SpecId {
+                            specialization_constant_id,
+                        },
+                } if specialization_constant_id == constant_id => Some(target),
+                _ => None,
+            })
+            .ok_or(SpirvEditError::SpecializationConstantNotFound { constant_id })?;
+        let index = self.ids[&target].index;
+
+        let instruction = match self.instructions[index] {
+            Instruction::SpecConstantTrue {
+                result_type_id,
+                result_id,
+            }
+            | Instruction::SpecConstantFalse {
+                result_type_id,
+                result_id,
+            } => {
+                if value.len() != 1 {
+                    return Err(SpirvEditError::SpecializationConstantSizeMismatch {
+                        constant_id,
+                        required: 1,
+                        provided: value.len(),
+                    });
+                }
+
+                if value[0] != 0 {
+                    Instruction::SpecConstantTrue {
+                        result_type_id,
+                        result_id,
+                    }
+                } else {
+                    Instruction::SpecConstantFalse {
+                        result_type_id,
+                        result_id,
+                    }
+                }
+            }
+            Instruction::SpecConstant {
+                result_type_id,
+                result_id,
+                value: ref old_value,
+            } => {
+                if value.len() != old_value.len() {
+                    return Err(SpirvEditError::SpecializationConstantSizeMismatch {
+                        constant_id,
+                        required: old_value.len(),
+                        provided: value.len(),
+                    });
+                }
+
+                Instruction::SpecConstant {
+                    result_type_id,
+                    result_id,
+                    value: value.to_owned(),
+                }
+            }
+            _ => return Err(SpirvEditError::SpecializationConstantNotScalar { constant_id }),
+        };
+
+        self.instructions[index] = instruction;
+
+        Ok(())
+    }
+
+    /// Removes all `Name`, `MemberName`, `Line` and `NoLine` instructions from the module.
+    pub fn strip_debug_info(&mut self) {
+        self.retain_instructions(|instruction| {
+            !matches!(
+                instruction,
+                Instruction::Name { .. }
+                    | Instruction::MemberName { .. }
+                    | Instruction::Line { .. }
+                    | Instruction::NoLine
+            )
+        });
+    }
+
+    /// Changes the descriptor set and binding number of every variable that has both a
+    /// `DescriptorSet` and a `Binding` decoration. `f` is called with the current set and binding
+    /// numbers, and returns the new ones.
+    ///
+    /// Only decorations that target a variable directly are changed. Decorations applied through
+    /// a `DecorationGroup` are left as they are.
+    pub fn remap_descriptor_bindings(&mut self, mut f: impl FnMut(u32, u32) -> (u32, u32)) {
+        let mut variables: HashMap<Id, (Option<usize>, Option<usize>)> = HashMap::default();
+
+        for index in self.range_decoration.clone() {
+            match self.instructions[index] {
+                Instruction::Decorate {
+                    target,
+                    decoration: Decoration::DescriptorSet { .. },
+                } => variables.entry(target).or_default().0 = Some(index),
+                Instruction::Decorate {
+                    target,
+                    decoration: Decoration::Binding { .. },
+                } => variables.entry(target).or_default().1 = Some(index),
+                _ => (),
+            }
+        }
+
+        for (set_index, binding_index) in variables.into_values() {
+            let (set_index, binding_index) = match (set_index, binding_index) {
+                (Some(set_index), Some(binding_index)) => (set_index, binding_index),
+                _ => continue,
+            };
+
+            let (set, binding) = match (
+                &self.instructions[set_index],
+                &self.instructions[binding_index],
+            ) {
+                (
+                    Instruction::Decorate {
+                        decoration: Decoration::DescriptorSet { descriptor_set },
+                        ..
+                    },
+                    Instruction::Decorate {
+                        decoration: Decoration::Binding { binding_point },
+                        ..
+                    },
+                ) => f(*descriptor_set, *binding_point),
+                _ => unreachable!(),
+            };
+
+            if let Instruction::Decorate {
+                decoration: Decoration::DescriptorSet { descriptor_set },
+                ..
+            } = &mut self.instructions[set_index]
+            {
+                *descriptor_set = set;
+            }
+
+            if let Instruction::Decorate {
+                decoration: Decoration::Binding { binding_point },
+                ..
+            } = &mut self.instructions[binding_index]
+            {
+                *binding_point = binding;
+            }
+        }
+    }
+
+    /// Removes every entry point for which `f` returns `false`, given its name and execution
+    /// model.
+    ///
+    /// Afterwards, functions that are no longer reachable from a remaining entry point are
+    /// removed, as are global variables that are no longer used by any function. Names and
+    /// decorations of the removed items are removed too. Types and constants are kept.
+    pub fn retain_entry_points(&mut self, mut f: impl FnMut(&str, ExecutionModel) -> bool) {
+        let mut keep = vec![true; self.instructions.len()];
+        let mut live_functions = Vec::new();
+
+        for index in self.range_entry_point.clone() {
+            if let Instruction::EntryPoint {
+                execution_model,
+                entry_point,
+                ref name,
+                ..
+            } = self.instructions[index]
+            {
+                if f(name, execution_model) {
+                    live_functions.push(entry_point);
+                } else {
+                    keep[index] = false;
+                }
+            }
+        }
+
+        for index in self.range_execution_mode.clone() {
+            match self.instructions[index] {
+                Instruction::ExecutionMode { entry_point, .. }
+                | Instruction::ExecutionModeId { entry_point, .. }
+                    if !live_functions.contains(&entry_point) =>
+                {
+                    keep[index] = false;
+                }
+                _ => (),
+            }
+        }
+
+        // Find the instruction range of every function body.
+        let mut function_ranges: HashMap<Id, Range<usize>> = HashMap::default();
+        let mut current_function = None;
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            match *instruction {
+                Instruction::Function { result_id, .. } => {
+                    current_function = Some((result_id, index));
+                }
+                Instruction::FunctionEnd => {
+                    if let Some((id, start)) = current_function.take() {
+                        function_ranges.insert(id, start..index + 1);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // Functions can be referenced by other functions, and in rare cases by globals.
+        for instruction in self.iter_global() {
+            instruction.visit_operand_ids(&mut |id| {
+                if function_ranges.contains_key(&id) {
+                    live_functions.push(id);
+                }
+            });
+        }
+
+        let mut reachable_functions = HashSet::default();
+
+        while let Some(function) = live_functions.pop() {
+            if !reachable_functions.insert(function) {
+                continue;
+            }
+
+            for instruction in &self.instructions[function_ranges[&function].clone()] {
+                instruction.visit_operand_ids(&mut |id| {
+                    if function_ranges.contains_key(&id) && !reachable_functions.contains(&id) {
+                        live_functions.push(id);
+                    }
+                });
+            }
+        }
+
+        for (function, range) in &function_ranges {
+            if !reachable_functions.contains(function) {
+                keep[range.clone()].fill(false);
+            }
+        }
+
+        // Remove global variables that are not referenced by anything but debug instructions,
+        // annotations and entry point interfaces.
+        let mut referenced_ids = HashSet::default();
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            if keep[index] && !is_debug_or_annotation(instruction) {
+                if let Instruction::EntryPoint { entry_point, .. } = *instruction {
+                    referenced_ids.insert(entry_point);
+                } else {
+                    instruction.visit_operand_ids(&mut |id| {
+                        referenced_ids.insert(id);
+                    });
+                }
+            }
+        }
+
+        for index in self.range_global.clone() {
+            if let Instruction::Variable { result_id, .. } = self.instructions[index] {
+                if !referenced_ids.contains(&result_id) {
+                    keep[index] = false;
+                }
+            }
+        }
+
+        // Clean up any remaining references to removed ids.
+        let defined_ids: HashSet<Id> = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| keep[index])
+            .filter_map(|(_, instruction)| instruction.result_id())
+            .collect();
+
+        for (index, instruction) in self.instructions.iter_mut().enumerate() {
+            if !keep[index] {
+                continue;
+            }
+
+            match instruction {
+                Instruction::EntryPoint { interface, .. } => {
+                    interface.retain(|id| defined_ids.contains(id));
+                }
+                Instruction::GroupDecorate { targets, .. } => {
+                    targets.retain(|id| defined_ids.contains(id));
+                }
+                Instruction::GroupMemberDecorate { targets, .. } => {
+                    targets.retain(|(id, _)| defined_ids.contains(id));
+                }
+                _ if is_debug_or_annotation(instruction) => {
+                    instruction.visit_operand_ids(&mut |id| {
+                        if !defined_ids.contains(&id) {
+                            keep[index] = false;
+                        }
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        let mut index = 0;
+        self.retain_instructions(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+    }
+
+    /// Removes all instructions for which `f` returns `false`, and redoes the analysis of the
+    /// module.
+    fn retain_instructions(&mut self, f: impl FnMut(&Instruction) -> bool) {
+        let mut instructions = mem::take(&mut self.instructions);
+        instructions.retain(f);
+
+        // Removing instructions keeps the logical layout intact, and the callers only remove
+        // instructions together with everything that refers to them, so this can't fail.
+        *self = Self::analyze(self.version, self.generator, self.bound, instructions)
+            .expect("removing instructions made the module invalid");
+    }
+}
+
+fn is_debug_or_annotation(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::Name { .. }
+            | Instruction::MemberName { .. }
+            | Instruction::Decorate { .. }
+            | Instruction::MemberDecorate { .. }
+            | Instruction::GroupDecorate { .. }
+            | Instruction::GroupMemberDecorate { .. }
+            | Instruction::DecorateId { .. }
+            | Instruction::DecorateString { .. }
+            | Instruction::MemberDecorateString { .. }
+    )
 }
 
 #[derive(Clone, Debug)]
@@ -570,6 +1079,13 @@ impl From<Id> for u32 {
     }
 }
 
+impl Id {
+    // Used to generate SPIR-V within vulkano. Outside of it, ids only come from parsed modules.
+    pub(crate) fn new(id: u32) -> Id {
+        Id(id)
+    }
+}
+
 impl Display for Id {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
@@ -577,6 +1093,312 @@ impl Display for Id {
     }
 }
 
+impl Instruction {
+    /// Encodes the instruction as SPIR-V words, and appends them to `words`.
+    pub fn encode(&self, words: &mut Vec<u32>) {
+        let start = words.len();
+        words.push(0); // Placeholder for the word count and opcode
+        self.write_operands(&mut InstructionWriter { words });
+
+        let word_count = (words.len() - start) as u32;
+        words[start] = word_count << 16 | self.opcode() as u32;
+    }
+}
+
+/// Helper type for writing the words of an instruction.
+#[derive(Debug)]
+struct InstructionWriter<'a> {
+    words: &'a mut Vec<u32>,
+}
+
+impl<'a> InstructionWriter<'a> {
+    /// Writes a single word.
+    fn push_u32(&mut self, word: u32) {
+        self.words.push(word);
+    }
+
+    /// Writes a nul-terminated string, padded with nul bytes to a whole number of words.
+    fn push_string(&mut self, string: &str) {
+        let mut bytes = string.as_bytes().to_owned();
+        bytes.push(0);
+
+        for chunk in bytes.chunks(4) {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.words.push(u32::from_le_bytes(word));
+        }
+    }
+}
+
+/// Implemented on every type that can be an operand of an instruction.
+trait WriteOperand {
+    /// Writes the operand.
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>);
+
+    /// Calls `f` for every `Id` contained in the operand.
+    fn visit_ids(&self, _f: &mut dyn FnMut(Id)) {}
+}
+
+impl WriteOperand for u32 {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        writer.push_u32(*self);
+    }
+}
+
+impl WriteOperand for u64 {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        writer.push_u32(*self as u32);
+        writer.push_u32((*self >> 32) as u32);
+    }
+}
+
+impl WriteOperand for f32 {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        writer.push_u32(self.to_bits());
+    }
+}
+
+impl WriteOperand for f64 {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        self.to_bits().write_operand(writer);
+    }
+}
+
+impl WriteOperand for String {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        writer.push_string(self);
+    }
+}
+
+impl WriteOperand for Id {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        writer.push_u32(self.0);
+    }
+
+    fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+        f(*self);
+    }
+}
+
+impl WriteOperand for SpecConstantInstruction {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        writer.push_u32(self.opcode() as u32);
+        self.write_operands(writer);
+    }
+
+    fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+        self.visit_operand_ids(f);
+    }
+}
+
+impl<T: WriteOperand> WriteOperand for Option<T> {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        if let Some(operand) = self {
+            operand.write_operand(writer);
+        }
+    }
+
+    fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+        if let Some(operand) = self {
+            operand.visit_ids(f);
+        }
+    }
+}
+
+impl<T: WriteOperand> WriteOperand for Vec<T> {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        for operand in self {
+            operand.write_operand(writer);
+        }
+    }
+
+    fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+        for operand in self {
+            operand.visit_ids(f);
+        }
+    }
+}
+
+impl<A: WriteOperand, B: WriteOperand> WriteOperand for (A, B) {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        self.0.write_operand(writer);
+        self.1.write_operand(writer);
+    }
+
+    fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+        self.0.visit_ids(f);
+        self.1.visit_ids(f);
+    }
+}
+
+impl<A: WriteOperand, B: WriteOperand, C: WriteOperand> WriteOperand for (A, B, C) {
+    fn write_operand(&self, writer: &mut InstructionWriter<'_>) {
+        self.0.write_operand(writer);
+        self.1.write_operand(writer);
+        self.2.write_operand(writer);
+    }
+
+    fn visit_ids(&self, f: &mut dyn FnMut(Id)) {
+        self.0.visit_ids(f);
+        self.1.visit_ids(f);
+        self.2.visit_ids(f);
+    }
+}
+
+impl Display for Instruction {
+    /// Formats the instruction in `spirv-dis` format, without using any `Id` names.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match disassemble(self, None) {
+            (Some(result), operands) => write!(f, "{} = {}", result, operands),
+            (None, operands) => write!(f, "{}", operands),
+        }
+    }
+}
+
+/// Formats an instruction, returning the formatted result id if there is one, and the rest of the
+/// instruction.
+fn disassemble(
+    instruction: &Instruction,
+    names: Option<&HashMap<Id, String>>,
+) -> (Option<String>, String) {
+    let mut output = instruction.opname().to_owned();
+    instruction.fmt_operands(&mut OperandFormatter {
+        output: &mut output,
+        names,
+    });
+
+    let result = instruction.result_id().map(|id| {
+        let mut result = String::new();
+        OperandFormatter {
+            output: &mut result,
+            names,
+        }
+        .push_id(id);
+        result.trim_start().to_owned()
+    });
+
+    (result, output)
+}
+
+/// Helper type for formatting the operands of an instruction.
+#[derive(Debug)]
+struct OperandFormatter<'a> {
+    output: &'a mut String,
+    names: Option<&'a HashMap<Id, String>>,
+}
+
+impl<'a> OperandFormatter<'a> {
+    /// Writes a single operand, preceded by a space.
+    fn push(&mut self, operand: impl Display) {
+        write!(self.output, " {}", operand).unwrap();
+    }
+
+    /// Writes an `Id`, using its name if it has one.
+    fn push_id(&mut self, id: Id) {
+        match self.names.and_then(|names| names.get(&id)) {
+            Some(name) => self.push(format_args!("%{}", name)),
+            None => self.push(id),
+        }
+    }
+
+    /// Writes a quoted string literal.
+    fn push_string(&mut self, string: &str) {
+        let mut quoted = String::with_capacity(string.len() + 2);
+        quoted.push('"');
+
+        for c in string.chars() {
+            if matches!(c, '"' | '\\') {
+                quoted.push('\\');
+            }
+
+            quoted.push(c);
+        }
+
+        quoted.push('"');
+        self.push(quoted);
+    }
+}
+
+/// Implemented on every type that can be an operand of an instruction.
+trait FormatOperand {
+    /// Formats the operand.
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>);
+}
+
+impl FormatOperand for u32 {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push(self);
+    }
+}
+
+impl FormatOperand for u64 {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push(self);
+    }
+}
+
+impl FormatOperand for f32 {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push(self);
+    }
+}
+
+impl FormatOperand for f64 {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push(self);
+    }
+}
+
+impl FormatOperand for String {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push_string(self);
+    }
+}
+
+impl FormatOperand for Id {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push_id(*self);
+    }
+}
+
+impl FormatOperand for SpecConstantInstruction {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        f.push(self.opname().strip_prefix("Op").unwrap());
+        self.fmt_operands(f);
+    }
+}
+
+impl<T: FormatOperand> FormatOperand for Option<T> {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        if let Some(operand) = self {
+            operand.fmt_operand(f);
+        }
+    }
+}
+
+impl<T: FormatOperand> FormatOperand for Vec<T> {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        for operand in self {
+            operand.fmt_operand(f);
+        }
+    }
+}
+
+impl<A: FormatOperand, B: FormatOperand> FormatOperand for (A, B) {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        self.0.fmt_operand(f);
+        self.1.fmt_operand(f);
+    }
+}
+
+impl<A: FormatOperand, B: FormatOperand, C: FormatOperand> FormatOperand for (A, B, C) {
+    fn fmt_operand(&self, f: &mut OperandFormatter<'_>) {
+        self.0.fmt_operand(f);
+        self.1.fmt_operand(f);
+        self.2.fmt_operand(f);
+    }
+}
+
 /// Helper type for parsing the words of an instruction.
 #[derive(Debug)]
 struct InstructionReader<'a> {
@@ -663,19 +1485,24 @@ impl<'a> InstructionReader<'a> {
 pub enum SpirvError {
     BadLayout {
         index: usize,
+        instruction: Box<Instruction>,
     },
     DuplicateId {
         id: Id,
         first_index: usize,
         second_index: usize,
+        /// The second instruction that assigns the id.
+        instruction: Box<Instruction>,
     },
     GroupDecorateNotGroup {
         index: usize,
+        instruction: Box<Instruction>,
     },
     IdOutOfBounds {
         id: Id,
         index: usize,
         bound: u32,
+        instruction: Box<Instruction>,
     },
     InvalidHeader,
     MemoryModelInvalid,
@@ -685,30 +1512,37 @@ pub enum SpirvError {
 impl Display for SpirvError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
-            Self::BadLayout { index } => write!(
+            Self::BadLayout { index, instruction } => write!(
                 f,
-                "the instruction at index {} does not follow the logical layout of a module",
-                index,
+                "the instruction at index {} (`{}`) does not follow the logical layout of a \
+                module",
+                index, instruction,
             ),
             Self::DuplicateId {
                 id,
                 first_index,
                 second_index,
+                instruction,
             } => write!(
                 f,
-                "id {} is assigned more than once, by instructions {} and {}",
-                id, first_index, second_index,
+                "id {} is assigned more than once, by instructions {} and {} (`{}`)",
+                id, first_index, second_index, instruction,
             ),
-            Self::GroupDecorateNotGroup { index } => write!(
+            Self::GroupDecorateNotGroup { index, instruction } => write!(
                 f,
-                "a GroupDecorate or GroupMemberDecorate instruction at index {} referred to an Id \
-                that was not a DecorationGroup",
-                index,
+                "a GroupDecorate or GroupMemberDecorate instruction at index {} (`{}`) referred \
+                to an Id that was not a DecorationGroup",
+                index, instruction,
             ),
-            Self::IdOutOfBounds { id, bound, index } => write!(
+            Self::IdOutOfBounds {
+                id,
+                bound,
+                index,
+                instruction,
+            } => write!(
                 f,
-                "id {}, assigned at instruction {}, is not below the maximum bound {}",
-                id, index, bound,
+                "id {}, assigned at instruction {} (`{}`), is not below the maximum bound {}",
+                id, index, instruction, bound,
             ),
             Self::InvalidHeader => write!(f, "the SPIR-V module header is invalid"),
             Self::MemoryModelInvalid => {
@@ -734,6 +1568,52 @@ impl From<ParseError> for SpirvError {
     }
 }
 
+/// Error that can happen when editing a SPIR-V module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpirvEditError {
+    /// No specialization constant with the given `SpecId` exists in the module.
+    SpecializationConstantNotFound { constant_id: u32 },
+
+    /// The specialization constant is a composite, and has no default value of its own.
+    SpecializationConstantNotScalar { constant_id: u32 },
+
+    /// The provided value does not have the same number of words as the specialization constant.
+    SpecializationConstantSizeMismatch {
+        constant_id: u32,
+        required: usize,
+        provided: usize,
+    },
+}
+
+impl Error for SpirvEditError {}
+
+impl Display for SpirvEditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SpecializationConstantNotFound { constant_id } => write!(
+                f,
+                "no specialization constant with constant_id {} exists in the module",
+                constant_id,
+            ),
+            Self::SpecializationConstantNotScalar { constant_id } => write!(
+                f,
+                "the specialization constant with constant_id {} is a composite",
+                constant_id,
+            ),
+            Self::SpecializationConstantSizeMismatch {
+                constant_id,
+                required,
+                provided,
+            } => write!(
+                f,
+                "the specialization constant with constant_id {} requires a value of {} words, \
+                but {} were provided",
+                constant_id, required, provided,
+            ),
+        }
+    }
+}
+
 /// Error that can happen when parsing SPIR-V instructions into Rust data structures.
 #[derive(Clone, Debug)]
 pub struct ParseError {
@@ -792,3 +1672,139 @@ impl Display for ParseErrors {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A compute shader with an unused uniform buffer and a specialization constant.
+    #[rustfmt::skip]
+    const WORDS: &[u32] = &[
+        0x07230203, 0x00010000, 0, 9, 0,
+        2 << 16 | 17, 1,                            // OpCapability Shader
+        3 << 16 | 14, 0, 1,                         // OpMemoryModel Logical GLSL450
+        5 << 16 | 15, 5, 1, 0x6e69616d, 0,          // OpEntryPoint GLCompute %1 "main"
+        6 << 16 | 16, 1, 17, 1, 1, 1,               // OpExecutionMode %1 LocalSize 1 1 1
+        4 << 16 | 5, 1, 0x6e69616d, 0,              // OpName %1 "main"
+        4 << 16 | 71, 5, 34, 0,                     // OpDecorate %5 DescriptorSet 0
+        4 << 16 | 71, 5, 33, 1,                     // OpDecorate %5 Binding 1
+        4 << 16 | 71, 7, 1, 3,                      // OpDecorate %7 SpecId 3
+        2 << 16 | 19, 2,                            // %2 = OpTypeVoid
+        3 << 16 | 33, 3, 2,                         // %3 = OpTypeFunction %2
+        4 << 16 | 21, 4, 32, 0,                     // %4 = OpTypeInt 32 0
+        4 << 16 | 32, 6, 2, 4,                      // %6 = OpTypePointer Uniform %4
+        4 << 16 | 59, 6, 5, 2,                      // %5 = OpVariable %6 Uniform
+        4 << 16 | 50, 4, 7, 5,                      // %7 = OpSpecConstant %4 5
+        5 << 16 | 54, 2, 1, 0, 3,                   // %1 = OpFunction %2 None %3
+        2 << 16 | 248, 8,                           // %8 = OpLabel
+        1 << 16 | 253,                              // OpReturn
+        1 << 16 | 56,                               // OpFunctionEnd
+    ];
+
+    #[test]
+    fn encode_roundtrip() {
+        let spirv = Spirv::new(WORDS).unwrap();
+        assert_eq!(spirv.to_words(), WORDS);
+    }
+
+    #[test]
+    fn disassemble() {
+        let spirv = Spirv::new(WORDS).unwrap();
+        let disassembly = spirv.disassemble();
+        let lines: Vec<_> = disassembly.lines().skip(5).collect();
+
+        assert_eq!(lines[0], "               OpCapability Shader");
+        assert_eq!(
+            lines[2],
+            "               OpEntryPoint GLCompute %main \"main\""
+        );
+        assert_eq!(
+            lines[3],
+            "               OpExecutionMode %main LocalSize 1 1 1"
+        );
+        assert_eq!(lines[5], "               OpDecorate %5 DescriptorSet 0");
+        assert_eq!(lines[14], "       %main = OpFunction %2 None %3");
+
+        let instruction = spirv.id(Id(5)).instruction();
+        assert_eq!(
+            spirv.disassemble_instruction(instruction),
+            "%5 = OpVariable %6 Uniform",
+        );
+        assert_eq!(instruction.to_string(), "%5 = OpVariable %6 Uniform");
+    }
+
+    #[test]
+    fn remap_descriptor_bindings() {
+        let mut spirv = Spirv::new(WORDS).unwrap();
+        spirv.remap_descriptor_bindings(|set, binding| (set + 1, binding + 2));
+
+        let spirv = Spirv::new(&spirv.to_words()).unwrap();
+        let decorations: Vec<_> = spirv.id(Id(5)).iter_decoration().cloned().collect();
+        assert!(decorations.contains(&Instruction::Decorate {
+            target: Id(5),
+            decoration: Decoration::DescriptorSet { descriptor_set: 1 },
+        }));
+        assert!(decorations.contains(&Instruction::Decorate {
+            target: Id(5),
+            decoration: Decoration::Binding { binding_point: 3 },
+        }));
+    }
+
+    #[test]
+    fn set_specialization_constant_default() {
+        let mut spirv = Spirv::new(WORDS).unwrap();
+        assert_eq!(
+            spirv.set_specialization_constant_default(0, &[1]),
+            Err(SpirvEditError::SpecializationConstantNotFound { constant_id: 0 }),
+        );
+        assert_eq!(
+            spirv.set_specialization_constant_default(3, &[1, 2]),
+            Err(SpirvEditError::SpecializationConstantSizeMismatch {
+                constant_id: 3,
+                required: 1,
+                provided: 2,
+            }),
+        );
+
+        spirv.set_specialization_constant_default(3, &[42]).unwrap();
+        let spirv = Spirv::new(&spirv.to_words()).unwrap();
+        assert!(matches!(
+            spirv.id(Id(7)).instruction(),
+            Instruction::SpecConstant { value, .. } if value == &[42],
+        ));
+    }
+
+    #[test]
+    fn strip_debug_info() {
+        let mut spirv = Spirv::new(WORDS).unwrap();
+        spirv.strip_debug_info();
+
+        let spirv = Spirv::new(&spirv.to_words()).unwrap();
+        assert_eq!(spirv.iter_name().len(), 0);
+        assert_eq!(spirv.iter_entry_point().len(), 1);
+    }
+
+    #[test]
+    fn retain_entry_points() {
+        let mut spirv = Spirv::new(WORDS).unwrap();
+        spirv.retain_entry_points(|_, _| true);
+
+        // The unused uniform buffer and its decorations are removed.
+        let spirv = Spirv::new(&spirv.to_words()).unwrap();
+        assert_eq!(spirv.iter_decoration().len(), 1);
+        assert!(!spirv
+            .iter_global()
+            .any(|instruction| instruction.result_id() == Some(Id(5))));
+
+        let mut spirv = spirv;
+        spirv.retain_entry_points(|_, _| false);
+
+        let spirv = Spirv::new(&spirv.to_words()).unwrap();
+        assert_eq!(spirv.iter_entry_point().len(), 0);
+        assert_eq!(spirv.iter_execution_mode().len(), 0);
+        assert!(!spirv
+            .instructions()
+            .iter()
+            .any(|instruction| matches!(instruction, Instruction::Function { .. })));
+    }
+}
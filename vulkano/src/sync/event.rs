@@ -35,7 +35,10 @@ use std::{
     mem::MaybeUninit,
     num::NonZeroU64,
     ptr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 /// Used to block the GPU execution until an event on the CPU occurs.
@@ -50,6 +53,9 @@ pub struct Event {
     device: Arc<Device>,
     id: NonZeroU64,
     must_put_in_pool: bool,
+
+    // Whether a command buffer that records commands on the event currently exists.
+    recording: AtomicBool,
 }
 
 impl Event {
@@ -97,6 +103,7 @@ impl Event {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            recording: AtomicBool::new(false),
         })
     }
 
@@ -123,6 +130,7 @@ impl Event {
                     device,
                     id: Self::next_id(),
                     must_put_in_pool: true,
+                    recording: AtomicBool::new(false),
                 }
             }
             None => {
@@ -153,6 +161,7 @@ impl Event {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            recording: AtomicBool::new(false),
         }
     }
 
@@ -215,6 +224,18 @@ impl Event {
     pub fn reset(&mut self) {
         self.reset_raw().unwrap();
     }
+
+    /// Gives a command buffer or an `EventSetFuture` exclusive use of the event for changing its
+    /// state while other command buffers may wait on it, until the returned lock is dropped.
+    /// Returns `None` if someone else already has it.
+    pub(crate) fn lock_recording(self: &Arc<Self>) -> Option<EventRecordingLock> {
+        self.recording
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| EventRecordingLock {
+                event: self.clone(),
+            })
+    }
 }
 
 impl Drop for Event {
@@ -232,6 +253,35 @@ impl Drop for Event {
     }
 }
 
+/// Keeps other command buffers and `EventSetFuture`s from changing the state of an [`Event`].
+#[derive(Debug)]
+pub(crate) struct EventRecordingLock {
+    event: Arc<Event>,
+}
+
+impl EventRecordingLock {
+    /// Changes the event to the signaled state from the host.
+    ///
+    /// The event may be shared with command buffers that wait on it, but the lock ensures that
+    /// nothing else changes its state, and the public host methods require `&mut Event`.
+    pub(crate) fn set(&self) -> Result<(), OomError> {
+        unsafe {
+            let fns = self.event.device.fns();
+            (fns.v1_0.set_event)(self.event.device.handle(), self.event.handle)
+                .result()
+                .map_err(VulkanError::from)?;
+            Ok(())
+        }
+    }
+}
+
+impl Drop for EventRecordingLock {
+    #[inline]
+    fn drop(&mut self) {
+        self.event.recording.store(false, Ordering::Release);
+    }
+}
+
 unsafe impl VulkanObject for Event {
     type Handle = ash::vk::Event;
 
@@ -0,0 +1,190 @@
+// Copyright (c) 2016 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{AccessCheckError, FlushError, GpuFuture, SubmitAnyBuilder};
+use crate::{
+    buffer::Buffer,
+    device::{Device, DeviceOwned, Queue},
+    image::{sys::Image, ImageLayout},
+    swapchain::Swapchain,
+    sync::event::{Event, EventRecordingLock},
+    DeviceSize,
+};
+use parking_lot::Mutex;
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+/// Builds a new event set future.
+pub fn then_set_event<F>(future: F, event: Arc<Event>) -> EventSetFuture<F>
+where
+    F: GpuFuture,
+{
+    assert_eq!(future.device(), event.device());
+    let lock = event
+        .lock_recording()
+        .expect("the event is already set or reset by a command buffer or another future");
+
+    EventSetFuture {
+        previous: future,
+        event,
+        lock,
+        set: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Represents an event being set from the host after a previous future has been submitted.
+///
+/// This unblocks any [`wait_events`] command that waits on the event with the [`HOST`] stage in
+/// its source stages.
+///
+/// [`wait_events`]: crate::command_buffer::AutoCommandBufferBuilder::wait_events
+/// [`HOST`]: crate::sync::PipelineStages::HOST
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct EventSetFuture<F>
+where
+    F: GpuFuture,
+{
+    previous: F,
+    event: Arc<Event>,
+    // Keeps command buffers from setting or resetting the event, which could unblock a wait on
+    // the event before the device commands it depends on.
+    lock: EventRecordingLock,
+    // True if the event has already been set.
+    // If flush is called multiple times, we want to block so that only one flushing is executed.
+    // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
+    set: Mutex<bool>,
+    finished: AtomicBool,
+}
+
+impl<F> EventSetFuture<F>
+where
+    F: GpuFuture,
+{
+    /// Returns the event that is set by this future.
+    #[inline]
+    pub fn event(&self) -> &Arc<Event> {
+        &self.event
+    }
+}
+
+unsafe impl<F> GpuFuture for EventSetFuture<F>
+where
+    F: GpuFuture,
+{
+    fn cleanup_finished(&mut self) {
+        self.previous.cleanup_finished();
+    }
+
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        // The event can only be set once the previous submission has been made, so anything that
+        // comes after must be submitted separately.
+        self.flush()?;
+
+        Ok(SubmitAnyBuilder::Empty)
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        let mut set = self.set.lock();
+
+        if *set {
+            return Ok(());
+        }
+
+        self.previous.flush()?;
+        self.lock.set().map_err(FlushError::OomError)?;
+
+        // Only write `true` here in order to try again next time if an error occurs.
+        *set = true;
+        Ok(())
+    }
+
+    unsafe fn signal_finished(&self) {
+        debug_assert!(*self.set.lock());
+        self.finished.store(true, Ordering::SeqCst);
+        self.previous.signal_finished();
+    }
+
+    fn queue_change_allowed(&self) -> bool {
+        self.previous.queue_change_allowed()
+    }
+
+    fn queue(&self) -> Option<Arc<Queue>> {
+        self.previous.queue()
+    }
+
+    fn check_buffer_access(
+        &self,
+        buffer: &Buffer,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_buffer_access(buffer, range, exclusive, queue)
+    }
+
+    fn check_image_access(
+        &self,
+        image: &Image,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        expected_layout: ImageLayout,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_image_access(image, range, exclusive, expected_layout, queue)
+    }
+
+    #[inline]
+    fn check_swapchain_image_acquired(
+        &self,
+        swapchain: &Swapchain,
+        image_index: u32,
+        before: bool,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_swapchain_image_acquired(swapchain, image_index, before)
+    }
+}
+
+unsafe impl<F> DeviceOwned for EventSetFuture<F>
+where
+    F: GpuFuture,
+{
+    fn device(&self) -> &Arc<Device> {
+        self.event.device()
+    }
+}
+
+impl<F> Drop for EventSetFuture<F>
+where
+    F: GpuFuture,
+{
+    fn drop(&mut self) {
+        if !*self.finished.get_mut() && !thread::panicking() {
+            // TODO: handle errors?
+            self.flush().unwrap();
+
+            // Block until the queue finished.
+            if let Some(queue) = self.queue() {
+                queue.with(|mut q| q.wait_idle()).unwrap();
+            }
+
+            unsafe { self.previous.signal_finished() };
+        }
+    }
+}
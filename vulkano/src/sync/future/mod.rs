@@ -91,12 +91,14 @@
 //! Signalling a fence is kind of a "terminator" to a chain of futures
 
 pub use self::{
+    event_set::EventSetFuture,
     fence_signal::{FenceSignalFuture, FenceSignalFutureBehavior},
     join::JoinFuture,
     now::{now, NowFuture},
     semaphore_signal::SemaphoreSignalFuture,
 };
 use super::{
+    event::Event,
     fence::{Fence, FenceError},
     semaphore::Semaphore,
 };
@@ -120,6 +122,7 @@ use std::{
     sync::Arc,
 };
 
+mod event_set;
 mod fence_signal;
 mod join;
 mod now;
@@ -312,6 +315,24 @@ pub unsafe trait GpuFuture: DeviceOwned {
         Ok(f)
     }
 
+    /// Sets an event from the host once this future has been flushed. Returns another future
+    /// that represents the moment when the event is set.
+    ///
+    /// Call this function when a command buffer waits on an event that is set from the host, to
+    /// make sure that the event is only set after the work it depends on has been submitted.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if a command buffer that sets or resets `event` exists, or another future already
+    ///   sets it.
+    #[inline]
+    fn then_set_event(self, event: Arc<Event>) -> EventSetFuture<Self>
+    where
+        Self: Sized,
+    {
+        event_set::then_set_event(self, event)
+    }
+
     /// Signals a fence after this future. Returns another future that represents the signal.
     ///
     /// > **Note**: More often than not you want to immediately flush the future after calling this
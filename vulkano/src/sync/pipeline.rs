@@ -825,6 +825,11 @@ vulkan_bitflags! {
         device_extensions: [ext_opacity_micromap],
     },
 
+    /// Read access to a descriptor buffer in a shader.
+    DESCRIPTOR_BUFFER_READ = DESCRIPTOR_BUFFER_READ_EXT {
+        device_extensions: [ext_descriptor_buffer],
+    },
+
     /// Read access to a buffer or image during optical flow operations.
     OPTICAL_FLOW_READ = OPTICAL_FLOW_READ_NV {
         device_extensions: [nv_optical_flow],
@@ -871,7 +876,8 @@ impl From<PipelineStages> for AccessFlags {
                 | AccessFlags::SHADER_STORAGE_READ
                 | AccessFlags::SHADER_WRITE
                 | AccessFlags::SHADER_STORAGE_WRITE
-                | AccessFlags::ACCELERATION_STRUCTURE_READ;
+                | AccessFlags::ACCELERATION_STRUCTURE_READ
+                | AccessFlags::DESCRIPTOR_BUFFER_READ;
         }
 
         if val.intersects(PipelineStages::FRAGMENT_SHADER | PipelineStages::SUBPASS_SHADING) {